@@ -20,6 +20,8 @@ use datatypes::prelude::ConcreteDataType;
 use snafu::prelude::*;
 use snafu::{Backtrace, ErrorCompat};
 
+use crate::v1::{ColumnDataType, ColumnDataTypeExtension};
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Snafu)]
@@ -33,6 +35,29 @@ pub enum Error {
         from: ConcreteDataType,
         backtrace: Backtrace,
     },
+
+    #[snafu(display(
+        "Invalid extension {:?} of proto column datatype {:?}",
+        extension,
+        datatype
+    ))]
+    InvalidColumnDataTypeExtension {
+        datatype: ColumnDataType,
+        extension: Option<ColumnDataTypeExtension>,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unsupported vector of datatype {:?} in proto column", datatype))]
+    UnsupportedVectorType {
+        datatype: ConcreteDataType,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to create vector, source: {}", source))]
+    CreateVector {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
 }
 
 impl ErrorExt for Error {
@@ -40,6 +65,9 @@ impl ErrorExt for Error {
         match self {
             Error::UnknownColumnDataType { .. } => StatusCode::InvalidArguments,
             Error::IntoColumnDataType { .. } => StatusCode::Unexpected,
+            Error::InvalidColumnDataTypeExtension { .. } => StatusCode::InvalidArguments,
+            Error::UnsupportedVectorType { .. } => StatusCode::Unsupported,
+            Error::CreateVector { source } => source.status_code(),
         }
     }
     fn backtrace_opt(&self) -> Option<&Backtrace> {
@@ -12,35 +12,81 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Range;
+
 use common_base::BitVec;
 use common_time::timestamp::TimeUnit;
+use datatypes::decimal::DECIMAL128_MAX_PRECISION;
 use datatypes::prelude::ConcreteDataType;
-use datatypes::value::Value;
-use datatypes::vectors::VectorRef;
+use datatypes::types::{MapType, StructField, StructType};
+use datatypes::value::ValueRef;
+use datatypes::vectors::{Helper, MapVector, StructVector, Vector, VectorRef};
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
 use crate::v1::column::Values;
-use crate::v1::{Column, ColumnDataType};
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct ColumnDataTypeWrapper(ColumnDataType);
+use crate::v1::column_data_type_extension::TypeExt;
+use crate::v1::{
+    Column, ColumnDataType, ColumnDataTypeExtension, ColumnDef, Decimal128, DecimalTypeExtension,
+    IntervalMonthDayNano, MapTypeExtension, StructTypeExtension,
+};
+
+/// Names of the entry fields of maps in [MapTypeExtension] and [Values::map_entries].
+const MAP_KEY_NAME: &str = "key";
+const MAP_VALUE_NAME: &str = "value";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDataTypeWrapper {
+    datatype: ColumnDataType,
+    datatype_ext: Option<ColumnDataTypeExtension>,
+}
 
 impl ColumnDataTypeWrapper {
-    pub fn try_new(datatype: i32) -> Result<Self> {
+    pub fn try_new(datatype: i32, datatype_ext: Option<ColumnDataTypeExtension>) -> Result<Self> {
         let datatype = ColumnDataType::from_i32(datatype)
             .context(error::UnknownColumnDataTypeSnafu { datatype })?;
-        Ok(Self(datatype))
+        Ok(Self::new(datatype, datatype_ext))
+    }
+
+    pub fn new(datatype: ColumnDataType, datatype_ext: Option<ColumnDataTypeExtension>) -> Self {
+        Self {
+            datatype,
+            datatype_ext,
+        }
     }
 
     pub fn datatype(&self) -> ColumnDataType {
-        self.0
+        self.datatype
+    }
+
+    pub fn datatype_extension(&self) -> Option<&ColumnDataTypeExtension> {
+        self.datatype_ext.as_ref()
+    }
+
+    pub fn into_parts(self) -> (ColumnDataType, Option<ColumnDataTypeExtension>) {
+        (self.datatype, self.datatype_ext)
+    }
+
+    fn type_ext(&self) -> Option<&TypeExt> {
+        self.datatype_ext
+            .as_ref()
+            .and_then(|ext| ext.type_ext.as_ref())
+    }
+
+    fn invalid_extension<T>(&self) -> Result<T> {
+        error::InvalidColumnDataTypeExtensionSnafu {
+            datatype: self.datatype,
+            extension: self.datatype_ext.clone(),
+        }
+        .fail()
     }
 }
 
-impl From<ColumnDataTypeWrapper> for ConcreteDataType {
-    fn from(datatype: ColumnDataTypeWrapper) -> Self {
-        match datatype.0 {
+impl TryFrom<ColumnDataTypeWrapper> for ConcreteDataType {
+    type Error = error::Error;
+
+    fn try_from(datatype: ColumnDataTypeWrapper) -> Result<Self> {
+        let concrete_datatype = match datatype.datatype {
             ColumnDataType::Boolean => ConcreteDataType::boolean_datatype(),
             ColumnDataType::Int8 => ConcreteDataType::int8_datatype(),
             ColumnDataType::Int16 => ConcreteDataType::int16_datatype(),
@@ -57,7 +103,50 @@ impl From<ColumnDataTypeWrapper> for ConcreteDataType {
             ColumnDataType::Date => ConcreteDataType::date_datatype(),
             ColumnDataType::Datetime => ConcreteDataType::datetime_datatype(),
             ColumnDataType::Timestamp => ConcreteDataType::timestamp_millis_datatype(),
-        }
+            ColumnDataType::Json => ConcreteDataType::json_datatype(),
+            ColumnDataType::Interval => ConcreteDataType::interval_datatype(),
+            ColumnDataType::Decimal128 => match datatype.type_ext() {
+                Some(TypeExt::DecimalType(DecimalTypeExtension { precision, scale }))
+                    if *precision > 0
+                        && *precision <= DECIMAL128_MAX_PRECISION as i32
+                        && *scale >= 0
+                        && scale <= precision =>
+                {
+                    ConcreteDataType::decimal128_datatype(*precision as u8, *scale as u8)
+                }
+                _ => return datatype.invalid_extension(),
+            },
+            ColumnDataType::Struct => match datatype.type_ext() {
+                Some(TypeExt::StructType(StructTypeExtension { fields })) => {
+                    ConcreteDataType::struct_datatype(
+                        fields
+                            .iter()
+                            .map(|field| {
+                                Ok(StructField::new(
+                                    field.name.clone(),
+                                    column_def_datatype(field)?,
+                                    field.is_nullable,
+                                ))
+                            })
+                            .collect::<Result<_>>()?,
+                    )
+                }
+                _ => return datatype.invalid_extension(),
+            },
+            ColumnDataType::Map => match datatype.type_ext() {
+                Some(TypeExt::MapType(MapTypeExtension { entry_fields })) => {
+                    match &entry_fields[..] {
+                        [key, value] => ConcreteDataType::map_datatype(
+                            column_def_datatype(key)?,
+                            column_def_datatype(value)?,
+                        ),
+                        _ => return datatype.invalid_extension(),
+                    }
+                }
+                _ => return datatype.invalid_extension(),
+            },
+        };
+        Ok(concrete_datatype)
     }
 }
 
@@ -65,7 +154,7 @@ impl TryFrom<ConcreteDataType> for ColumnDataTypeWrapper {
     type Error = error::Error;
 
     fn try_from(datatype: ConcreteDataType) -> Result<Self> {
-        let datatype = ColumnDataTypeWrapper(match datatype {
+        let column_datatype = match datatype {
             ConcreteDataType::Boolean(_) => ColumnDataType::Boolean,
             ConcreteDataType::Int8(_) => ColumnDataType::Int8,
             ConcreteDataType::Int16(_) => ColumnDataType::Int16,
@@ -82,11 +171,75 @@ impl TryFrom<ConcreteDataType> for ColumnDataTypeWrapper {
             ConcreteDataType::Date(_) => ColumnDataType::Date,
             ConcreteDataType::DateTime(_) => ColumnDataType::Datetime,
             ConcreteDataType::Timestamp(_) => ColumnDataType::Timestamp,
+            ConcreteDataType::Decimal128(_) => ColumnDataType::Decimal128,
+            ConcreteDataType::Json(_) => ColumnDataType::Json,
+            ConcreteDataType::Interval(_) => ColumnDataType::Interval,
+            ConcreteDataType::Struct(_) => ColumnDataType::Struct,
+            ConcreteDataType::Map(_) => ColumnDataType::Map,
             ConcreteDataType::Null(_) | ConcreteDataType::List(_) => {
                 return error::IntoColumnDataTypeSnafu { from: datatype }.fail()
             }
-        });
-        Ok(datatype)
+        };
+        let type_ext = match &datatype {
+            ConcreteDataType::Decimal128(t) => Some(TypeExt::DecimalType(DecimalTypeExtension {
+                precision: t.precision() as i32,
+                scale: t.scale() as i32,
+            })),
+            ConcreteDataType::Struct(t) => Some(TypeExt::StructType(StructTypeExtension {
+                fields: t
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        to_column_def(field.name(), field.data_type(), field.is_nullable())
+                    })
+                    .collect::<Result<_>>()?,
+            })),
+            ConcreteDataType::Map(t) => Some(TypeExt::MapType(MapTypeExtension {
+                entry_fields: vec![
+                    to_column_def(MAP_KEY_NAME, t.key_type(), false)?,
+                    to_column_def(MAP_VALUE_NAME, t.value_type(), true)?,
+                ],
+            })),
+            _ => None,
+        };
+        Ok(ColumnDataTypeWrapper::new(
+            column_datatype,
+            type_ext.map(|type_ext| ColumnDataTypeExtension {
+                type_ext: Some(type_ext),
+            }),
+        ))
+    }
+}
+
+fn column_def_datatype(column_def: &ColumnDef) -> Result<ConcreteDataType> {
+    ColumnDataTypeWrapper::try_new(column_def.datatype, column_def.datatype_extension.clone())?
+        .try_into()
+}
+
+fn to_column_def(name: &str, datatype: &ConcreteDataType, is_nullable: bool) -> Result<ColumnDef> {
+    let (datatype, datatype_extension) =
+        ColumnDataTypeWrapper::try_from(datatype.clone())?.into_parts();
+    Ok(ColumnDef {
+        name: name.to_string(),
+        datatype: datatype as i32,
+        is_nullable,
+        default_constraint: None,
+        datatype_extension,
+    })
+}
+
+impl From<i128> for Decimal128 {
+    fn from(value: i128) -> Self {
+        Decimal128 {
+            hi: (value >> 64) as i64,
+            lo: value as i64,
+        }
+    }
+}
+
+impl From<&Decimal128> for i128 {
+    fn from(value: &Decimal128) -> Self {
+        ((value.hi as i128) << 64) | (value.lo as u64 as i128)
     }
 }
 
@@ -157,49 +310,183 @@ impl Values {
                 ts_millis_values: Vec::with_capacity(capacity),
                 ..Default::default()
             },
+            ColumnDataType::Decimal128 => Values {
+                decimal128_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::Json => Values {
+                json_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::Interval => Values {
+                interval_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            // The field columns are created by the first push of values.
+            ColumnDataType::Struct => Values::default(),
+            ColumnDataType::Map => Values {
+                map_lengths: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
         }
     }
 }
 
 impl Column {
     // The type of vals must be same.
-    pub fn push_vals(&mut self, origin_count: usize, vector: VectorRef) {
+    pub fn push_vals(&mut self, origin_count: usize, vector: VectorRef) -> Result<()> {
         let values = self.values.get_or_insert_with(Values::default);
         let mut null_mask = BitVec::from_slice(&self.null_mask);
         let len = vector.len();
         null_mask.reserve_exact(origin_count + len);
         null_mask.extend(BitVec::repeat(false, len));
 
-        (0..len).into_iter().for_each(|idx| match vector.get(idx) {
-            Value::Null => null_mask.set(idx + origin_count, true),
-            Value::Boolean(val) => values.bool_values.push(val),
-            Value::UInt8(val) => values.u8_values.push(val.into()),
-            Value::UInt16(val) => values.u16_values.push(val.into()),
-            Value::UInt32(val) => values.u32_values.push(val),
-            Value::UInt64(val) => values.u64_values.push(val),
-            Value::Int8(val) => values.i8_values.push(val.into()),
-            Value::Int16(val) => values.i16_values.push(val.into()),
-            Value::Int32(val) => values.i32_values.push(val),
-            Value::Int64(val) => values.i64_values.push(val),
-            Value::Float32(val) => values.f32_values.push(*val),
-            Value::Float64(val) => values.f64_values.push(*val),
-            Value::String(val) => values.string_values.push(val.as_utf8().to_string()),
-            Value::Binary(val) => values.binary_values.push(val.to_vec()),
-            Value::Date(val) => values.date_values.push(val.val()),
-            Value::DateTime(val) => values.datetime_values.push(val.val()),
-            Value::Timestamp(val) => values
-                .ts_millis_values
-                .push(val.convert_to(TimeUnit::Millisecond)),
-            Value::List(_) => unreachable!(),
-        });
+        for idx in 0..len {
+            match vector.get_ref(idx) {
+                ValueRef::Null => null_mask.set(idx + origin_count, true),
+                ValueRef::Boolean(val) => values.bool_values.push(val),
+                ValueRef::UInt8(val) => values.u8_values.push(val.into()),
+                ValueRef::UInt16(val) => values.u16_values.push(val.into()),
+                ValueRef::UInt32(val) => values.u32_values.push(val),
+                ValueRef::UInt64(val) => values.u64_values.push(val),
+                ValueRef::Int8(val) => values.i8_values.push(val.into()),
+                ValueRef::Int16(val) => values.i16_values.push(val.into()),
+                ValueRef::Int32(val) => values.i32_values.push(val),
+                ValueRef::Int64(val) => values.i64_values.push(val),
+                ValueRef::Float32(val) => values.f32_values.push(*val),
+                ValueRef::Float64(val) => values.f64_values.push(*val),
+                ValueRef::String(val) => values.string_values.push(val.to_string()),
+                ValueRef::Binary(val) => values.binary_values.push(val.to_vec()),
+                ValueRef::Date(val) => values.date_values.push(val.val()),
+                ValueRef::DateTime(val) => values.datetime_values.push(val.val()),
+                ValueRef::Timestamp(val) => values
+                    .ts_millis_values
+                    .push(val.convert_to(TimeUnit::Millisecond)),
+                ValueRef::Decimal128(val) => values.decimal128_values.push(val.value().into()),
+                ValueRef::Json(val) => values.json_values.push(val.to_string()),
+                ValueRef::Interval(val) => values.interval_values.push(IntervalMonthDayNano {
+                    months: val.months(),
+                    days: val.days(),
+                    nanoseconds: val.nanos(),
+                }),
+                // Nested values are pushed to the nested columns below.
+                ValueRef::Struct(_) | ValueRef::Map(_) => {}
+                ValueRef::List(_) => {
+                    return error::UnsupportedVectorTypeSnafu {
+                        datatype: vector.data_type(),
+                    }
+                    .fail()
+                }
+            }
+        }
         self.null_mask = null_mask.into_vec();
+
+        match vector.data_type() {
+            ConcreteDataType::Struct(datatype) => {
+                push_struct_fields(values, origin_count, &datatype, &vector)
+            }
+            ConcreteDataType::Map(datatype) => push_map_entries(values, &datatype, &vector),
+            _ => Ok(()),
+        }
     }
 }
 
+fn new_nested_column(name: &str, datatype: &ConcreteDataType) -> Result<Column> {
+    let (datatype, datatype_extension) =
+        ColumnDataTypeWrapper::try_from(datatype.clone())?.into_parts();
+    Ok(Column {
+        column_name: name.to_string(),
+        datatype: datatype as i32,
+        datatype_extension,
+        ..Default::default()
+    })
+}
+
+/// Converts the constant or dictionary encoded `vector` to a plain vector of type `T`.
+fn flatten_vector<T: 'static + Clone>(vector: &VectorRef) -> Result<T> {
+    let flattened;
+    let vector = if vector.as_any().is::<T>() {
+        vector
+    } else {
+        flattened =
+            Helper::try_into_vector(vector.to_arrow_array()).context(error::CreateVectorSnafu)?;
+        &flattened
+    };
+    vector
+        .as_any()
+        .downcast_ref::<T>()
+        .cloned()
+        .with_context(|| error::UnsupportedVectorTypeSnafu {
+            datatype: vector.data_type(),
+        })
+}
+
+fn push_struct_fields(
+    values: &mut Values,
+    origin_count: usize,
+    datatype: &StructType,
+    vector: &VectorRef,
+) -> Result<()> {
+    if values.struct_fields.is_empty() {
+        values.struct_fields = datatype
+            .fields()
+            .iter()
+            .map(|field| new_nested_column(field.name(), field.data_type()))
+            .collect::<Result<_>>()?;
+    }
+    let vector = flatten_vector::<StructVector>(vector)?;
+    for (column, field) in values.struct_fields.iter_mut().zip(vector.fields()) {
+        column.push_vals(origin_count, field.clone())?;
+    }
+    Ok(())
+}
+
+fn push_map_entries(values: &mut Values, datatype: &MapType, vector: &VectorRef) -> Result<()> {
+    if values.map_entries.is_empty() {
+        values.map_entries = vec![
+            new_nested_column(MAP_KEY_NAME, datatype.key_type())?,
+            new_nested_column(MAP_VALUE_NAME, datatype.value_type())?,
+        ];
+    }
+    let mut entry_count = values
+        .map_lengths
+        .iter()
+        .map(|len| *len as usize)
+        .sum::<usize>();
+
+    // Pushes the entries of adjacent maps together.
+    let vector = flatten_vector::<MapVector>(vector)?;
+    let mut runs: Vec<Range<usize>> = vec![];
+    for idx in 0..vector.len() {
+        let range = if vector.is_null(idx) {
+            0..0
+        } else {
+            vector.entries_range(idx)
+        };
+        values.map_lengths.push(range.len() as u32);
+        if range.is_empty() {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if run.end == range.start => run.end = range.end,
+            _ => runs.push(range),
+        }
+    }
+    for run in runs {
+        let len = run.len();
+        values.map_entries[0].push_vals(entry_count, vector.keys().slice(run.start, len))?;
+        values.map_entries[1].push_vals(entry_count, vector.values().slice(run.start, len))?;
+        entry_count += len;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
+    use datatypes::prelude::DataType;
+    use datatypes::value::{MapValue, StructValue, Value};
     use datatypes::vectors::BooleanVector;
 
     use super::*;
@@ -267,134 +554,150 @@ mod tests {
     fn test_concrete_datatype_from_column_datatype() {
         assert_eq!(
             ConcreteDataType::boolean_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Boolean).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Boolean, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::int8_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int8).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Int8, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::int16_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int16).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Int16, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::int32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int32).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Int32, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::int64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int64).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Int64, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::uint8_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint8).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Uint8, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::uint16_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint16).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Uint16, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::uint32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint32).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Uint32, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::uint64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint64).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Uint64, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::float32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Float32).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Float32, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::float64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Float64).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Float64, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::binary_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Binary).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Binary, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::string_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::String).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::String, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::date_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Date).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Date, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::datetime_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Datetime).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Datetime, None))
+                .unwrap()
         );
         assert_eq!(
             ConcreteDataType::timestamp_millis_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Timestamp).into()
+            ConcreteDataType::try_from(ColumnDataTypeWrapper::new(ColumnDataType::Timestamp, None))
+                .unwrap()
         );
     }
 
     #[test]
     fn test_column_datatype_from_concrete_datatype() {
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Boolean),
+            ColumnDataTypeWrapper::new(ColumnDataType::Boolean, None),
             ConcreteDataType::boolean_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int8),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int8, None),
             ConcreteDataType::int8_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int16),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int16, None),
             ConcreteDataType::int16_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int32, None),
             ConcreteDataType::int32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int64, None),
             ConcreteDataType::int64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint8),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint8, None),
             ConcreteDataType::uint8_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint16),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint16, None),
             ConcreteDataType::uint16_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint32, None),
             ConcreteDataType::uint32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint64, None),
             ConcreteDataType::uint64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Float32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Float32, None),
             ConcreteDataType::float32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Float64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Float64, None),
             ConcreteDataType::float64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Binary),
+            ColumnDataTypeWrapper::new(ColumnDataType::Binary, None),
             ConcreteDataType::binary_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::String),
+            ColumnDataTypeWrapper::new(ColumnDataType::String, None),
             ConcreteDataType::string_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Date),
+            ColumnDataTypeWrapper::new(ColumnDataType::Date, None),
             ConcreteDataType::date_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Datetime),
+            ColumnDataTypeWrapper::new(ColumnDataType::Datetime, None),
             ConcreteDataType::datetime_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Timestamp),
+            ColumnDataTypeWrapper::new(ColumnDataType::Timestamp, None),
             ConcreteDataType::timestamp_millis_datatype()
                 .try_into()
                 .unwrap()
@@ -416,6 +719,143 @@ mod tests {
         );
     }
 
+    fn new_struct_type() -> StructType {
+        StructType::new(vec![
+            StructField::new("a", ConcreteDataType::decimal128_datatype(10, 2), true),
+            StructField::new(
+                "b",
+                ConcreteDataType::map_datatype(
+                    ConcreteDataType::string_datatype(),
+                    ConcreteDataType::json_datatype(),
+                ),
+                false,
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_nested_datatype_roundtrip() {
+        let datatypes = vec![
+            ConcreteDataType::decimal128_datatype(10, 2),
+            ConcreteDataType::json_datatype(),
+            ConcreteDataType::interval_datatype(),
+            ConcreteDataType::Struct(new_struct_type()),
+            ConcreteDataType::map_datatype(
+                ConcreteDataType::int32_datatype(),
+                ConcreteDataType::Struct(new_struct_type()),
+            ),
+        ];
+        for datatype in datatypes {
+            let wrapper = ColumnDataTypeWrapper::try_from(datatype.clone()).unwrap();
+            assert_eq!(datatype, ConcreteDataType::try_from(wrapper).unwrap());
+        }
+
+        let wrapper = ColumnDataTypeWrapper::new(ColumnDataType::Decimal128, None);
+        assert!(ConcreteDataType::try_from(wrapper).is_err());
+        let wrapper = ColumnDataTypeWrapper::new(
+            ColumnDataType::Decimal128,
+            Some(ColumnDataTypeExtension {
+                type_ext: Some(TypeExt::DecimalType(DecimalTypeExtension {
+                    precision: 39,
+                    scale: 2,
+                })),
+            }),
+        );
+        assert!(ConcreteDataType::try_from(wrapper).is_err());
+        let wrapper = ColumnDataTypeWrapper::new(
+            ColumnDataType::Map,
+            Some(ColumnDataTypeExtension {
+                type_ext: Some(TypeExt::MapType(MapTypeExtension {
+                    entry_fields: vec![],
+                })),
+            }),
+        );
+        assert!(ConcreteDataType::try_from(wrapper).is_err());
+    }
+
+    #[test]
+    fn test_decimal128_roundtrip() {
+        for value in [0, 1, -1, i64::MAX as i128 + 1, i128::MAX, i128::MIN] {
+            let decimal = Decimal128::from(value);
+            assert_eq!(value, i128::from(&decimal));
+        }
+    }
+
+    #[test]
+    fn test_column_put_nested_vectors() {
+        let datatype = new_struct_type();
+        let map_type = match datatype.fields()[1].data_type() {
+            ConcreteDataType::Map(t) => t.clone(),
+            _ => unreachable!(),
+        };
+        let new_struct = |decimal: Option<i128>, entries: Vec<(&str, &str)>| {
+            Value::Struct(StructValue::new(
+                vec![
+                    decimal
+                        .map(|v| Value::Decimal128(datatypes::decimal::Decimal128::new(v, 10, 2)))
+                        .unwrap_or(Value::Null),
+                    Value::Map(MapValue::new(
+                        entries
+                            .into_iter()
+                            .map(|(k, v)| (Value::from(k), Value::Json(v.into())))
+                            .collect(),
+                        map_type.clone(),
+                    )),
+                ],
+                datatype.clone(),
+            ))
+        };
+
+        let (datatype_enum, datatype_extension) =
+            ColumnDataTypeWrapper::try_from(ConcreteDataType::Struct(datatype.clone()))
+                .unwrap()
+                .into_parts();
+        let mut column = Column {
+            column_name: "test".to_string(),
+            datatype: datatype_enum as i32,
+            datatype_extension,
+            ..Default::default()
+        };
+        let mut builder = datatype.create_mutable_vector(2);
+        builder
+            .push_value_ref(new_struct(Some(100), vec![("a", "1")]).as_value_ref())
+            .unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        column.push_vals(0, builder.to_vector()).unwrap();
+        builder
+            .push_value_ref(new_struct(None, vec![("b", "[]"), ("c", "{}")]).as_value_ref())
+            .unwrap();
+        column.push_vals(2, builder.to_vector()).unwrap();
+
+        assert_eq!(vec![2], column.null_mask);
+        let fields = column.values.unwrap().struct_fields;
+        assert_eq!(2, fields.len());
+        // Field columns have a row for the null struct.
+        assert_eq!(
+            vec![Decimal128::from(100)],
+            fields[0].values.as_ref().unwrap().decimal128_values
+        );
+        assert_eq!(vec![6], fields[0].null_mask);
+        let map_values = fields[1].values.as_ref().unwrap();
+        assert_eq!(vec![1, 0, 2], map_values.map_lengths);
+        assert_eq!(
+            vec!["a", "b", "c"],
+            map_values.map_entries[0]
+                .values
+                .as_ref()
+                .unwrap()
+                .string_values
+        );
+        assert_eq!(
+            vec!["1", "[]", "{}"],
+            map_values.map_entries[1]
+                .values
+                .as_ref()
+                .unwrap()
+                .json_values
+        );
+    }
+
     #[test]
     fn test_column_put_vector() {
         use crate::v1::column::SemanticType;
@@ -429,11 +869,12 @@ mod tests {
             }),
             null_mask: vec![2],
             datatype: ColumnDataType::Boolean as i32,
+            datatype_extension: None,
         };
         let row_count = 4;
 
         let vector = Arc::new(BooleanVector::from(vec![Some(true), None, Some(false)]));
-        column.push_vals(row_count, vector);
+        column.push_vals(row_count, vector).unwrap();
         // Some(false), None, Some(true), Some(true), Some(true), None, Some(false)
         let bool_values = column.values.unwrap().bool_values;
         assert_eq!(vec![false, true, true, true, false], bool_values);
@@ -188,6 +188,7 @@ mod tests {
         SelectResult {
             columns: vec![column],
             row_count: 8,
+            ..Default::default()
         }
     }
 }
@@ -17,6 +17,10 @@ tonic::include_proto!("greptime.v1");
 
 pub const GREPTIME_FD_SET: &[u8] = tonic::include_file_descriptor_set!("greptime_fd");
 
+/// Columns of the rows selected by [DataVersionsExpr].
+pub const MANIFEST_VERSION_COLUMN: &str = "manifest_version";
+pub const COMMITTED_SEQUENCE_COLUMN: &str = "committed_sequence";
+
 pub mod codec {
     tonic::include_proto!("greptime.v1.codec");
 }
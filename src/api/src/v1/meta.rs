@@ -149,6 +149,7 @@ gen_set_header!(PutRequest);
 gen_set_header!(BatchPutRequest);
 gen_set_header!(CompareAndPutRequest);
 gen_set_header!(DeleteRangeRequest);
+gen_set_header!(WatchRequest);
 
 #[cfg(test)]
 mod tests {
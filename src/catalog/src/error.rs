@@ -196,6 +196,43 @@ pub enum Error {
         #[snafu(backtrace)]
         source: BoxedError,
     },
+
+    #[snafu(display("User already exists: {}", username))]
+    UserExists {
+        username: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("User not found: {}", username))]
+    UserNotFound {
+        username: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Role already exists: {}", role))]
+    RoleExists { role: String, backtrace: Backtrace },
+
+    #[snafu(display("Role not found: {}", role))]
+    RoleNotFound { role: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Access denied for user {}, {} privilege is required on {}",
+        username,
+        privilege,
+        object
+    ))]
+    AccessDenied {
+        username: String,
+        privilege: String,
+        object: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unsupported operation of catalog manager: {}", operation))]
+    UnsupportedOperation {
+        operation: String,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -222,7 +259,13 @@ impl ErrorExt for Error {
 
             Error::RegisterTable { .. } => StatusCode::Internal,
             Error::TableExists { .. } => StatusCode::TableAlreadyExists,
-            Error::SchemaExists { .. } => StatusCode::InvalidArguments,
+            Error::SchemaExists { .. }
+            | Error::UserExists { .. }
+            | Error::RoleExists { .. }
+            | Error::RoleNotFound { .. } => StatusCode::InvalidArguments,
+            Error::UserNotFound { .. } => StatusCode::UserNotFound,
+            Error::AccessDenied { .. } => StatusCode::AccessDenied,
+            Error::UnsupportedOperation { .. } => StatusCode::Unsupported,
 
             Error::OpenSystemCatalog { source, .. }
             | Error::CreateSystemCatalog { source, .. }
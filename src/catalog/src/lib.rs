@@ -24,11 +24,15 @@ use table::metadata::TableId;
 use table::requests::CreateTableRequest;
 use table::TableRef;
 
-use crate::error::{CreateTableSnafu, Result};
+use crate::error::{CreateTableSnafu, Result, UnsupportedOperationSnafu};
+use crate::privilege::{
+    CreateRoleRequest, CreateUserRequest, GrantRequest, GrantRoleRequest, PrivilegeManagerRef,
+};
 pub use crate::schema::{SchemaProvider, SchemaProviderRef};
 
 pub mod error;
 pub mod local;
+pub mod privilege;
 pub mod remote;
 pub mod schema;
 pub mod system;
@@ -98,6 +102,55 @@ pub trait CatalogManager: CatalogList {
 
     /// Returns the table by catalog, schema and table name.
     fn table(&self, catalog: &str, schema: &str, table_name: &str) -> Result<Option<TableRef>>;
+
+    /// Creates a user for access control, returns false if the user already exists and
+    /// `if_not_exists` is set.
+    async fn create_user(&self, _request: CreateUserRequest) -> Result<bool> {
+        UnsupportedOperationSnafu {
+            operation: "CREATE USER",
+        }
+        .fail()
+    }
+
+    /// Creates a role, returns false if the role already exists and `if_not_exists` is set.
+    async fn create_role(&self, _request: CreateRoleRequest) -> Result<bool> {
+        UnsupportedOperationSnafu {
+            operation: "CREATE ROLE",
+        }
+        .fail()
+    }
+
+    /// Grants privileges to a user or role.
+    async fn grant(&self, _request: GrantRequest) -> Result<()> {
+        UnsupportedOperationSnafu { operation: "GRANT" }.fail()
+    }
+
+    /// Revokes privileges from a user or role.
+    async fn revoke(&self, _request: GrantRequest) -> Result<()> {
+        UnsupportedOperationSnafu {
+            operation: "REVOKE",
+        }
+        .fail()
+    }
+
+    /// Grants roles to a user or role.
+    async fn grant_roles(&self, _request: GrantRoleRequest) -> Result<()> {
+        UnsupportedOperationSnafu { operation: "GRANT" }.fail()
+    }
+
+    /// Revokes roles from a user or role.
+    async fn revoke_roles(&self, _request: GrantRoleRequest) -> Result<()> {
+        UnsupportedOperationSnafu {
+            operation: "REVOKE",
+        }
+        .fail()
+    }
+
+    /// Returns the users and their privileges, `None` if access control is not supported by
+    /// this catalog manager.
+    fn privilege_manager(&self) -> Option<PrivilegeManagerRef> {
+        None
+    }
 }
 
 pub type CatalogManagerRef = Arc<dyn CatalogManager>;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
@@ -35,10 +36,15 @@ use table::TableRef;
 
 use crate::error::{
     CatalogNotFoundSnafu, IllegalManagerStateSnafu, OpenTableSnafu, ReadSystemCatalogSnafu, Result,
-    SchemaExistsSnafu, SchemaNotFoundSnafu, SystemCatalogSnafu, SystemCatalogTypeMismatchSnafu,
-    TableExistsSnafu, TableNotFoundSnafu,
+    RoleExistsSnafu, RoleNotFoundSnafu, SchemaExistsSnafu, SchemaNotFoundSnafu, SystemCatalogSnafu,
+    SystemCatalogTypeMismatchSnafu, TableExistsSnafu, TableNotFoundSnafu, UserExistsSnafu,
+    UserNotFoundSnafu,
 };
 use crate::local::memory::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
+use crate::privilege::{
+    CreateRoleRequest, CreateUserRequest, GrantRequest, GrantRoleRequest, Privilege,
+    PrivilegeManager, PrivilegeManagerRef,
+};
 use crate::system::{
     decode_system_catalog, Entry, SystemCatalogTable, TableEntry, ENTRY_TYPE_INDEX, KEY_INDEX,
     VALUE_INDEX,
@@ -58,6 +64,7 @@ pub struct LocalCatalogManager {
     next_table_id: AtomicU32,
     init_lock: Mutex<bool>,
     system_table_requests: Mutex<Vec<RegisterSystemTableRequest>>,
+    privileges: PrivilegeManagerRef,
 }
 
 impl LocalCatalogManager {
@@ -77,6 +84,7 @@ impl LocalCatalogManager {
             next_table_id: AtomicU32::new(MIN_USER_TABLE_ID),
             init_lock: Mutex::new(false),
             system_table_requests: Mutex::new(Vec::default()),
+            privileges: Arc::new(PrivilegeManager::default()),
         })
     }
 
@@ -210,13 +218,32 @@ impl LocalCatalogManager {
                     info!("Registered table: {:?}", t);
                     max_table_id = max_table_id.max(t.table_id);
                 }
+                Entry::User(u) => {
+                    self.privileges.add_user(&u.username);
+                    info!("Registered user: {}", u.username);
+                }
+                Entry::Role(r) => {
+                    self.privileges.add_role(&r.role);
+                    info!("Registered role: {}", r.role);
+                }
+                Entry::Privilege(p) => {
+                    self.privileges.set_privileges(
+                        &p.username,
+                        p.object,
+                        p.privileges.into_iter().collect(),
+                    );
+                }
+                Entry::UserRoles(r) => {
+                    self.privileges
+                        .set_roles(&r.username, r.roles.into_iter().collect());
+                }
             }
         }
         Ok(max_table_id)
     }
 
     /// Sort catalog entries to ensure catalog entries comes first, then schema entries,
-    /// and table entries is the last.
+    /// and table entries, users, roles and their privileges are the last.
     fn sort_entries(mut entries: Vec<Entry>) -> Vec<Entry> {
         entries.sort();
         entries
@@ -419,6 +446,150 @@ impl CatalogManager for LocalCatalogManager {
             })?;
         schema.table(table_name)
     }
+
+    async fn create_user(&self, request: CreateUserRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+
+        // Users and roles share the same names.
+        let username = &request.username;
+        if self.privileges.contains(username) {
+            ensure!(request.if_not_exists, UserExistsSnafu { username });
+            return Ok(false);
+        }
+        self.system.register_user(username).await?;
+        self.privileges.add_user(username);
+        Ok(true)
+    }
+
+    async fn create_role(&self, request: CreateRoleRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+
+        let role = &request.role;
+        if self.privileges.contains(role) {
+            ensure!(request.if_not_exists, RoleExistsSnafu { role });
+            return Ok(false);
+        }
+        self.system.register_role(role).await?;
+        self.privileges.add_role(role);
+        Ok(true)
+    }
+
+    async fn grant(&self, request: GrantRequest) -> Result<()> {
+        self.update_privileges(request, |privileges, changes| {
+            privileges.extend(changes);
+        })
+        .await
+    }
+
+    async fn revoke(&self, request: GrantRequest) -> Result<()> {
+        self.update_privileges(request, |privileges, changes| {
+            for privilege in changes {
+                let _ = privileges.remove(privilege);
+            }
+        })
+        .await
+    }
+
+    async fn grant_roles(&self, request: GrantRoleRequest) -> Result<()> {
+        self.update_roles(request, |roles, changes| {
+            roles.extend(changes.iter().cloned());
+        })
+        .await
+    }
+
+    async fn revoke_roles(&self, request: GrantRoleRequest) -> Result<()> {
+        self.update_roles(request, |roles, changes| {
+            for role in changes {
+                let _ = roles.remove(role);
+            }
+        })
+        .await
+    }
+
+    fn privilege_manager(&self) -> Option<PrivilegeManagerRef> {
+        Some(self.privileges.clone())
+    }
+}
+
+impl LocalCatalogManager {
+    /// Updates the privileges of the user on the object and persists them.
+    async fn update_privileges<F>(&self, request: GrantRequest, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut BTreeSet<Privilege>, &[Privilege]) + Send,
+    {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+
+        let username = &request.username;
+        ensure!(
+            self.privileges.contains(username),
+            UserNotFoundSnafu { username }
+        );
+        let mut privileges = self.privileges.privileges(username, &request.object);
+        update(&mut privileges, &request.privileges);
+
+        self.system
+            .register_privileges(
+                username,
+                &request.object,
+                privileges.iter().copied().collect(),
+            )
+            .await?;
+        self.privileges
+            .set_privileges(username, request.object, privileges);
+        Ok(())
+    }
+
+    /// Updates the roles granted to the user or role and persists them.
+    async fn update_roles<F>(&self, request: GrantRoleRequest, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut BTreeSet<String>, &[String]) + Send,
+    {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+
+        let username = &request.username;
+        ensure!(
+            self.privileges.contains(username),
+            UserNotFoundSnafu { username }
+        );
+        for role in &request.roles {
+            ensure!(
+                self.privileges.contains_role(role),
+                RoleNotFoundSnafu { role }
+            );
+        }
+        let mut roles = self.privileges.roles(username);
+        update(&mut roles, &request.roles);
+
+        self.system
+            .register_user_roles(username, roles.iter().cloned().collect())
+            .await?;
+        self.privileges.set_roles(username, roles);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
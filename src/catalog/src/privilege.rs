@@ -0,0 +1,472 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Users, roles and their privileges on databases and tables, persisted in the system catalog.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::error::{AccessDeniedSnafu, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Alter,
+}
+
+impl Privilege {
+    pub fn all() -> Vec<Privilege> {
+        vec![Privilege::Select, Privilege::Insert, Privilege::Alter]
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Privilege::Select => write!(f, "SELECT"),
+            Privilege::Insert => write!(f, "INSERT"),
+            Privilege::Alter => write!(f, "ALTER"),
+        }
+    }
+}
+
+/// The object privileges are granted on. A `None` schema or table stands for all schemas or
+/// all tables.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GrantObject {
+    pub catalog: String,
+    pub schema: Option<String>,
+    pub table: Option<String>,
+}
+
+impl GrantObject {
+    /// All schemas and tables in the catalog.
+    pub fn catalog(catalog: impl Into<String>) -> Self {
+        Self {
+            catalog: catalog.into(),
+            schema: None,
+            table: None,
+        }
+    }
+
+    /// All tables in the schema.
+    pub fn schema(catalog: impl Into<String>, schema: impl Into<String>) -> Self {
+        Self {
+            catalog: catalog.into(),
+            schema: Some(schema.into()),
+            table: None,
+        }
+    }
+
+    pub fn table(
+        catalog: impl Into<String>,
+        schema: impl Into<String>,
+        table: impl Into<String>,
+    ) -> Self {
+        Self {
+            catalog: catalog.into(),
+            schema: Some(schema.into()),
+            table: Some(table.into()),
+        }
+    }
+
+    /// Whether privileges granted on this object also apply to the `other` object.
+    pub fn covers(&self, other: &GrantObject) -> bool {
+        fn covers_part(this: &Option<String>, other: &Option<String>) -> bool {
+            this.is_none() || this == other
+        }
+
+        self.catalog == other.catalog
+            && covers_part(&self.schema, &other.schema)
+            && covers_part(&self.table, &other.table)
+    }
+}
+
+impl fmt::Display for GrantObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.catalog,
+            self.schema.as_deref().unwrap_or("*"),
+            self.table.as_deref().unwrap_or("*")
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateUserRequest {
+    pub username: String,
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateRoleRequest {
+    pub role: String,
+    pub if_not_exists: bool,
+}
+
+/// Request of both `GRANT` and `REVOKE` of privileges, the grantee is either a user or a role.
+#[derive(Debug, Clone)]
+pub struct GrantRequest {
+    pub username: String,
+    pub object: GrantObject,
+    pub privileges: Vec<Privilege>,
+}
+
+/// Request of both `GRANT` and `REVOKE` of roles to a user or role.
+#[derive(Debug, Clone)]
+pub struct GrantRoleRequest {
+    pub username: String,
+    pub roles: Vec<String>,
+}
+
+/// A user or a role. Users and roles share the same names, so that privileges are granted to
+/// either of them in the same way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Principal {
+    pub is_role: bool,
+    pub grants: Grants,
+    /// Roles granted to this principal, whose privileges this principal also has.
+    pub roles: BTreeSet<String>,
+}
+
+impl Principal {
+    pub fn user() -> Self {
+        Self::default()
+    }
+
+    pub fn role() -> Self {
+        Self {
+            is_role: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// In-memory users, roles and privileges, loaded from and kept in sync with the system catalog
+/// by the catalog manager.
+///
+/// Privileges are not enforced until the first user is created, so that existing deployments
+/// keep working. Since then, only created users are allowed to access the objects they are
+/// granted, directly or through their roles.
+#[derive(Debug, Default)]
+pub struct PrivilegeManager {
+    principals: RwLock<HashMap<String, Principal>>,
+}
+
+/// Privileges of a user or role on each object.
+pub type Grants = HashMap<GrantObject, BTreeSet<Privilege>>;
+
+pub type PrivilegeManagerRef = Arc<PrivilegeManager>;
+
+impl PrivilegeManager {
+    pub fn has_users(&self) -> bool {
+        self.principals
+            .read()
+            .unwrap()
+            .values()
+            .any(|principal| !principal.is_role)
+    }
+
+    /// Whether the name is taken by either a user or a role.
+    pub fn contains(&self, name: &str) -> bool {
+        self.principals.read().unwrap().contains_key(name)
+    }
+
+    pub fn contains_user(&self, username: &str) -> bool {
+        self.principals
+            .read()
+            .unwrap()
+            .get(username)
+            .map_or(false, |principal| !principal.is_role)
+    }
+
+    pub fn contains_role(&self, role: &str) -> bool {
+        self.principals
+            .read()
+            .unwrap()
+            .get(role)
+            .map_or(false, |principal| principal.is_role)
+    }
+
+    pub fn add_user(&self, username: &str) {
+        self.add_principal(username, Principal::user());
+    }
+
+    pub fn add_role(&self, role: &str) {
+        self.add_principal(role, Principal::role());
+    }
+
+    fn add_principal(&self, name: &str, principal: Principal) {
+        let _ = self
+            .principals
+            .write()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert(principal);
+    }
+
+    /// Privileges granted to the user or role on exactly the `object`.
+    pub fn privileges(&self, name: &str, object: &GrantObject) -> BTreeSet<Privilege> {
+        self.principals
+            .read()
+            .unwrap()
+            .get(name)
+            .and_then(|principal| principal.grants.get(object))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_privileges(&self, name: &str, object: GrantObject, privileges: BTreeSet<Privilege>) {
+        let mut principals = self.principals.write().unwrap();
+        let grants = &mut principals.entry(name.to_string()).or_default().grants;
+        if privileges.is_empty() {
+            let _ = grants.remove(&object);
+        } else {
+            let _ = grants.insert(object, privileges);
+        }
+    }
+
+    /// Roles granted directly to the user or role.
+    pub fn roles(&self, name: &str) -> BTreeSet<String> {
+        self.principals
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|principal| principal.roles.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_roles(&self, name: &str, roles: BTreeSet<String>) {
+        self.principals
+            .write()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .roles = roles;
+    }
+
+    /// The user or role, `None` if it doesn't exist.
+    pub fn principal(&self, name: &str) -> Option<Principal> {
+        self.principals.read().unwrap().get(name).cloned()
+    }
+
+    /// Replaces the user or role, and all its privileges and roles.
+    pub fn set_principal(&self, name: &str, principal: Principal) {
+        let _ = self
+            .principals
+            .write()
+            .unwrap()
+            .insert(name.to_string(), principal);
+    }
+
+    pub fn remove_principal(&self, name: &str) {
+        let _ = self.principals.write().unwrap().remove(name);
+    }
+
+    /// Replaces all users and roles.
+    pub fn reset(&self, principals: HashMap<String, Principal>) {
+        *self.principals.write().unwrap() = principals;
+    }
+
+    /// Whether the user has the `privilege` on the `object`, either granted on the object itself
+    /// or on the schema or catalog containing it, to the user or to any role the user has,
+    /// directly or through other roles.
+    pub fn is_granted(&self, username: &str, object: &GrantObject, privilege: Privilege) -> bool {
+        let principals = self.principals.read().unwrap();
+        if !principals.values().any(|principal| !principal.is_role) {
+            return true;
+        }
+        match principals.get(username) {
+            Some(user) if !user.is_role => {}
+            // Roles can't be used to log in.
+            _ => return false,
+        }
+
+        let mut visited = HashSet::new();
+        let mut pending = vec![username];
+        while let Some(name) = pending.pop() {
+            if !visited.insert(name) {
+                continue;
+            }
+            let principal = match principals.get(name) {
+                Some(principal) => principal,
+                None => continue,
+            };
+            let granted = principal.grants.iter().any(|(granted, privileges)| {
+                granted.covers(object) && privileges.contains(&privilege)
+            });
+            if granted {
+                return true;
+            }
+            pending.extend(principal.roles.iter().map(String::as_str));
+        }
+        false
+    }
+
+    /// Checks the privilege of a session without a user, e.g. from a protocol that doesn't
+    /// authenticate. Such sessions are denied once any user is created.
+    pub fn check_unauthenticated(&self, object: &GrantObject, privilege: Privilege) -> Result<()> {
+        ensure!(
+            !self.has_users(),
+            AccessDeniedSnafu {
+                username: "<unauthenticated>",
+                privilege: privilege.to_string(),
+                object: object.to_string(),
+            }
+        );
+        Ok(())
+    }
+
+    /// Checks whether the user has the `privilege` on the `object`, see [Self::is_granted].
+    pub fn check(&self, username: &str, object: &GrantObject, privilege: Privilege) -> Result<()> {
+        ensure!(
+            self.is_granted(username, object, privilege),
+            AccessDeniedSnafu {
+                username,
+                privilege: privilege.to_string(),
+                object: object.to_string(),
+            }
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_object_covers() {
+        let table = GrantObject::table("greptime", "public", "cpu");
+        assert!(GrantObject::catalog("greptime").covers(&table));
+        assert!(GrantObject::schema("greptime", "public").covers(&table));
+        assert!(table.covers(&table));
+        assert!(!GrantObject::schema("greptime", "other").covers(&table));
+        assert!(!GrantObject::catalog("other").covers(&table));
+        assert!(!table.covers(&GrantObject::schema("greptime", "public")));
+        assert!(!GrantObject::table("greptime", "public", "mem").covers(&table));
+
+        assert_eq!(
+            "greptime.public.*",
+            GrantObject::schema("greptime", "public").to_string()
+        );
+    }
+
+    #[test]
+    fn test_privilege_manager() {
+        let manager = PrivilegeManager::default();
+        let cpu = GrantObject::table("greptime", "public", "cpu");
+        // Nothing is enforced before any user is created.
+        assert!(manager.is_granted("alice", &cpu, Privilege::Select));
+
+        manager.add_user("alice");
+        manager.add_user("bob");
+        assert!(manager.has_users());
+        assert!(manager.contains_user("alice"));
+        assert!(!manager.is_granted("alice", &cpu, Privilege::Select));
+        assert!(!manager.is_granted("unknown", &cpu, Privilege::Select));
+
+        manager.set_privileges(
+            "alice",
+            GrantObject::schema("greptime", "public"),
+            BTreeSet::from([Privilege::Select]),
+        );
+        manager.set_privileges("bob", cpu.clone(), Privilege::all().into_iter().collect());
+        assert!(manager.is_granted("alice", &cpu, Privilege::Select));
+        assert!(!manager.is_granted("alice", &cpu, Privilege::Insert));
+        assert!(manager.is_granted("bob", &cpu, Privilege::Insert));
+        assert!(!manager.is_granted(
+            "bob",
+            &GrantObject::table("greptime", "public", "mem"),
+            Privilege::Select
+        ));
+
+        assert!(manager.check("bob", &cpu, Privilege::Alter).is_ok());
+        let err = manager.check("alice", &cpu, Privilege::Alter).unwrap_err();
+        assert_eq!(
+            "Access denied for user alice, ALTER privilege is required on greptime.public.cpu",
+            err.to_string()
+        );
+
+        manager.set_privileges("bob", cpu.clone(), BTreeSet::new());
+        assert!(manager.privileges("bob", &cpu).is_empty());
+        assert!(!manager.is_granted("bob", &cpu, Privilege::Insert));
+
+        let err = manager
+            .check_unauthenticated(&cpu, Privilege::Select)
+            .unwrap_err();
+        assert_eq!(
+            "Access denied for user <unauthenticated>, SELECT privilege is required on greptime.public.cpu",
+            err.to_string()
+        );
+        manager.remove_principal("alice");
+        assert!(manager.principal("alice").is_none());
+        manager.reset(HashMap::new());
+        assert!(manager
+            .check_unauthenticated(&cpu, Privilege::Select)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_privileges_of_roles() {
+        let manager = PrivilegeManager::default();
+        let cpu = GrantObject::table("greptime", "public", "cpu");
+        manager.add_role("reader");
+        manager.add_role("writer");
+        // Roles alone don't enforce privileges.
+        assert!(!manager.has_users());
+        assert!(manager.is_granted("alice", &cpu, Privilege::Insert));
+
+        manager.add_user("alice");
+        assert!(manager.contains("reader"));
+        assert!(manager.contains_role("reader"));
+        assert!(!manager.contains_user("reader"));
+        assert!(!manager.contains_role("alice"));
+
+        manager.set_privileges(
+            "reader",
+            GrantObject::schema("greptime", "public"),
+            BTreeSet::from([Privilege::Select]),
+        );
+        manager.set_privileges("writer", cpu.clone(), BTreeSet::from([Privilege::Insert]));
+        assert!(!manager.is_granted("alice", &cpu, Privilege::Select));
+
+        manager.set_roles("alice", BTreeSet::from(["reader".to_string()]));
+        assert_eq!(
+            BTreeSet::from(["reader".to_string()]),
+            manager.roles("alice")
+        );
+        assert!(manager.is_granted("alice", &cpu, Privilege::Select));
+        assert!(!manager.is_granted("alice", &cpu, Privilege::Insert));
+
+        // Privileges of roles granted to roles, even in a cycle.
+        manager.set_roles("reader", BTreeSet::from(["writer".to_string()]));
+        manager.set_roles("writer", BTreeSet::from(["reader".to_string()]));
+        assert!(manager.is_granted("alice", &cpu, Privilege::Insert));
+        assert!(!manager.is_granted("alice", &cpu, Privilege::Alter));
+
+        // Roles can't log in.
+        assert!(!manager.is_granted("reader", &cpu, Privilege::Select));
+
+        manager.set_roles("alice", BTreeSet::new());
+        assert!(manager.check("alice", &cpu, Privilege::Select).is_err());
+    }
+}
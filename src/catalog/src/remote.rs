@@ -20,17 +20,30 @@ pub use client::MetaKvBackend;
 use futures::Stream;
 use futures_util::StreamExt;
 pub use manager::{RemoteCatalogManager, RemoteCatalogProvider, RemoteSchemaProvider};
+pub use privilege::RemotePrivileges;
 
 use crate::error::Error;
 
 mod client;
 mod manager;
+mod privilege;
 
 #[derive(Debug, Clone)]
 pub struct Kv(pub Vec<u8>, pub Vec<u8>);
 
 pub type ValueIter<'a, E> = Pin<Box<dyn Stream<Item = Result<Kv, E>> + Send + 'a>>;
 
+/// Change of a key watched from backend.
+#[derive(Debug, Clone)]
+pub enum KvEvent {
+    /// Key has been created or updated, along with its current value.
+    Put(Kv),
+    /// Key has been deleted.
+    Delete(Vec<u8>),
+}
+
+pub type EventIter<'a, E> = Pin<Box<dyn Stream<Item = Result<KvEvent, E>> + Send + 'a>>;
+
 #[async_trait::async_trait]
 pub trait KvBackend: Send + Sync {
     fn range<'a, 'b>(&'a self, key: &[u8]) -> ValueIter<'b, Error>
@@ -59,6 +72,11 @@ pub trait KvBackend: Send + Sync {
         self.delete_range(key, &[]).await
     }
 
+    /// Watches all keys with the given prefix. Returns after the watch is established, so the
+    /// returned stream yields every change of those keys happened after this method returns,
+    /// and terminates when backend cancels the watch.
+    async fn watch_prefix(&self, prefix: &[u8]) -> Result<EventIter<'static, Error>, Error>;
+
     /// Default get is implemented based on `range` method.
     async fn get(&self, key: &[u8]) -> Result<Option<Kv>, Error> {
         let mut iter = self.range(key);
@@ -114,6 +132,10 @@ mod tests {
         async fn delete_range(&self, _key: &[u8], _end: &[u8]) -> Result<(), Error> {
             unimplemented!()
         }
+
+        async fn watch_prefix(&self, _prefix: &[u8]) -> Result<EventIter<'static, Error>, Error> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
@@ -18,11 +18,13 @@ use std::sync::Arc;
 use async_stream::stream;
 use common_telemetry::info;
 use meta_client::client::MetaClient;
-use meta_client::rpc::{CompareAndPutRequest, DeleteRangeRequest, PutRequest, RangeRequest};
+use meta_client::rpc::{
+    CompareAndPutRequest, DeleteRangeRequest, EventType, PutRequest, RangeRequest, WatchRequest,
+};
 use snafu::ResultExt;
 
 use crate::error::{Error, MetaSrvSnafu};
-use crate::remote::{Kv, KvBackend, ValueIter};
+use crate::remote::{EventIter, Kv, KvBackend, KvEvent, ValueIter};
 #[derive(Debug)]
 pub struct MetaKvBackend {
     pub client: Arc<MetaClient>,
@@ -105,4 +107,29 @@ impl KvBackend for MetaKvBackend {
             Ok(Err(response.take_prev_kv().map(|v| v.value().to_vec())))
         }
     }
+
+    async fn watch_prefix(&self, prefix: &[u8]) -> Result<EventIter<'static, Error>, Error> {
+        let mut watcher = self
+            .client
+            .watch(WatchRequest::new().with_prefix(prefix))
+            .await
+            .context(MetaSrvSnafu)?;
+        info!("Watch established, watcher id: {:?}", watcher.id());
+        Ok(Box::pin(stream!({
+            while let Some(mut resp) = watcher.message().await.context(MetaSrvSnafu)? {
+                for mut event in resp.take_events() {
+                    let mut kv = match event.take_kv() {
+                        Some(kv) => kv,
+                        None => continue,
+                    };
+                    match event.event_type() {
+                        EventType::Put => {
+                            yield Ok(KvEvent::Put(Kv(kv.take_key(), kv.take_value())))
+                        }
+                        EventType::Delete => yield Ok(KvEvent::Delete(kv.take_key())),
+                    }
+                }
+            }
+        })))
+    }
 }
@@ -13,18 +13,20 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use arc_swap::ArcSwap;
 use async_stream::stream;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MIN_USER_TABLE_ID};
 use common_catalog::{
-    build_catalog_prefix, build_schema_prefix, build_table_global_prefix, CatalogKey, CatalogValue,
-    SchemaKey, SchemaValue, TableGlobalKey, TableGlobalValue, TableRegionalKey, TableRegionalValue,
+    build_catalog_prefix, build_schema_prefix, build_schemas_prefix, build_table_global_prefix,
+    build_tables_global_prefix, CatalogKey, CatalogValue, SchemaKey, SchemaValue, TableGlobalKey,
+    TableGlobalValue, TableRegionalKey, TableRegionalValue,
 };
-use common_telemetry::{debug, info};
+use common_telemetry::{debug, error, info, warn};
 use futures::Stream;
 use futures_util::StreamExt;
 use snafu::{OptionExt, ResultExt};
@@ -36,10 +38,10 @@ use table::TableRef;
 use tokio::sync::Mutex;
 
 use crate::error::{
-    CatalogNotFoundSnafu, CreateTableSnafu, InvalidCatalogValueSnafu, InvalidTableSchemaSnafu,
-    OpenTableSnafu, Result, SchemaNotFoundSnafu, TableExistsSnafu,
+    CatalogNotFoundSnafu, CreateTableSnafu, Error, InvalidCatalogValueSnafu,
+    InvalidTableSchemaSnafu, OpenTableSnafu, Result, SchemaNotFoundSnafu, TableExistsSnafu,
 };
-use crate::remote::{Kv, KvBackendRef};
+use crate::remote::{EventIter, Kv, KvBackendRef, KvEvent};
 use crate::{
     handle_system_table_request, CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef,
     RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, SchemaProvider,
@@ -247,7 +249,8 @@ impl RemoteCatalogManager {
         let mut tables = self.iter_remote_tables(catalog_name, schema_name).await;
         while let Some(r) = tables.next().await {
             let (table_key, table_value) = r?;
-            let table_ref = self.open_or_create_table(&table_key, &table_value).await?;
+            let table_ref =
+                open_or_create_table(&self.engine, self.node_id, &table_key, &table_value).await?;
             schema.register_table(table_key.table_name.to_string(), table_ref)?;
             info!("Registered table {}", &table_key.table_name);
             if table_value.id > max_table_id {
@@ -298,90 +301,24 @@ impl RemoteCatalogManager {
         Ok(default_catalog)
     }
 
-    async fn open_or_create_table(
-        &self,
-        table_key: &TableGlobalKey,
-        table_value: &TableGlobalValue,
-    ) -> Result<TableRef> {
-        let context = EngineContext {};
-        let TableGlobalKey {
-            catalog_name,
-            schema_name,
-            table_name,
-            ..
-        } = table_key;
-
-        let TableGlobalValue {
-            id,
-            meta,
-            regions_id_map,
-            ..
-        } = table_value;
-
-        let request = OpenTableRequest {
-            catalog_name: catalog_name.clone(),
-            schema_name: schema_name.clone(),
-            table_name: table_name.clone(),
-            table_id: *id,
+    /// Spawns a background task handling catalog changes from `events`.
+    fn start_watching(&self, events: EventIter<'static, Error>) {
+        let watcher = CatalogWatcher {
+            node_id: self.node_id,
+            backend: self.backend.clone(),
+            catalogs: self.catalogs.clone(),
+            engine: self.engine.clone(),
         };
-        match self
-            .engine
-            .open_table(&context, request)
-            .await
-            .with_context(|_| OpenTableSnafu {
-                table_info: format!("{}.{}.{}, id:{}", catalog_name, schema_name, table_name, id,),
-            })? {
-            Some(table) => {
-                info!(
-                    "Table opened: {}.{}.{}",
-                    catalog_name, schema_name, table_name
-                );
-                Ok(table)
-            }
-            None => {
-                info!(
-                    "Try create table: {}.{}.{}",
-                    catalog_name, schema_name, table_name
-                );
-
-                let schema = meta
-                    .schema
-                    .clone()
-                    .try_into()
-                    .context(InvalidTableSchemaSnafu {
-                        table_info: format!("{}.{}.{}", catalog_name, schema_name, table_name,),
-                        schema: meta.schema.clone(),
-                    })?;
-                let req = CreateTableRequest {
-                    id: *id,
-                    catalog_name: catalog_name.clone(),
-                    schema_name: schema_name.clone(),
-                    table_name: table_name.clone(),
-                    desc: None,
-                    schema: Arc::new(schema),
-                    region_numbers: regions_id_map.get(&self.node_id).unwrap().clone(), // this unwrap is safe because region_id_map is checked in `iter_remote_tables`
-                    primary_key_indices: meta.primary_key_indices.clone(),
-                    create_if_not_exists: true,
-                    table_options: meta.options.clone(),
-                };
-
-                self.engine
-                    .create_table(&context, req)
-                    .await
-                    .context(CreateTableSnafu {
-                        table_info: format!(
-                            "{}.{}.{}, id:{}",
-                            &catalog_name, &schema_name, &table_name, id
-                        ),
-                    })
-            }
-        }
+        common_runtime::spawn_bg(watcher.run(events));
     }
 }
 
 #[async_trait::async_trait]
 impl CatalogManager for RemoteCatalogManager {
     async fn start(&self) -> Result<()> {
+        // Establishes the watch before loading catalogs, so changes made during loading are
+        // delivered by the watch. Replaying them on the loaded catalogs is harmless.
+        let events = CatalogWatcher::watch(&self.backend).await?;
         let (catalogs, max_table_id) = self.initiate_catalogs().await?;
         info!(
             "Initialized catalogs: {:?}",
@@ -402,6 +339,8 @@ impl CatalogManager for RemoteCatalogManager {
             .unwrap()
             .register_table("numbers".to_string(), Arc::new(NumbersTable::default()))
             .unwrap();
+
+        self.start_watching(events);
         Ok(())
     }
 
@@ -580,6 +519,29 @@ impl RemoteCatalogProvider {
         Ok(())
     }
 
+    /// Adds schema to local cache without writing metasrv, it's used when the schema is
+    /// already created by other nodes. Returns the cached schema provider.
+    fn cache_schema(&self, schema_name: &str) -> SchemaProviderRef {
+        if let Some(schema) = self.schemas.load().get(schema_name) {
+            return schema.clone();
+        }
+
+        let schema: SchemaProviderRef = Arc::new(RemoteSchemaProvider::new(
+            self.catalog_name.clone(),
+            schema_name.to_string(),
+            self.node_id,
+            self.backend.clone(),
+        ));
+        let prev = self.schemas.rcu(|schemas| {
+            let mut schemas = HashMap::clone(schemas);
+            schemas
+                .entry(schema_name.to_string())
+                .or_insert_with(|| schema.clone());
+            schemas
+        });
+        prev.get(schema_name).cloned().unwrap_or(schema)
+    }
+
     fn build_schema_key(&self, schema_name: impl AsRef<str>) -> SchemaKey {
         SchemaKey {
             catalog_name: self.catalog_name.clone(),
@@ -594,7 +556,6 @@ impl CatalogProvider for RemoteCatalogProvider {
     }
 
     fn schema_names(&self) -> Result<Vec<String>> {
-        self.refresh_schemas()?;
         Ok(self.schemas.load().keys().cloned().collect::<Vec<_>>())
     }
 
@@ -633,8 +594,6 @@ impl CatalogProvider for RemoteCatalogProvider {
     }
 
     fn schema(&self, name: &str) -> Result<Option<Arc<dyn SchemaProvider>>> {
-        // TODO(hl): We should refresh whole catalog before calling datafusion's query engine.
-        self.refresh_schemas()?;
         Ok(self.schemas.load().get(name).cloned())
     }
 }
@@ -760,3 +719,299 @@ impl SchemaProvider for RemoteSchemaProvider {
         Ok(self.tables.load().contains_key(name))
     }
 }
+
+async fn open_or_create_table(
+    engine: &TableEngineRef,
+    node_id: u64,
+    table_key: &TableGlobalKey,
+    table_value: &TableGlobalValue,
+) -> Result<TableRef> {
+    let context = EngineContext {};
+    let TableGlobalKey {
+        catalog_name,
+        schema_name,
+        table_name,
+        ..
+    } = table_key;
+
+    let TableGlobalValue {
+        id,
+        meta,
+        regions_id_map,
+        ..
+    } = table_value;
+
+    let request = OpenTableRequest {
+        catalog_name: catalog_name.clone(),
+        schema_name: schema_name.clone(),
+        table_name: table_name.clone(),
+        table_id: *id,
+    };
+    match engine
+        .open_table(&context, request)
+        .await
+        .with_context(|_| OpenTableSnafu {
+            table_info: format!("{}.{}.{}, id:{}", catalog_name, schema_name, table_name, id,),
+        })? {
+        Some(table) => {
+            info!(
+                "Table opened: {}.{}.{}",
+                catalog_name, schema_name, table_name
+            );
+            Ok(table)
+        }
+        None => {
+            info!(
+                "Try create table: {}.{}.{}",
+                catalog_name, schema_name, table_name
+            );
+
+            let schema = meta
+                .schema
+                .clone()
+                .try_into()
+                .context(InvalidTableSchemaSnafu {
+                    table_info: format!("{}.{}.{}", catalog_name, schema_name, table_name,),
+                    schema: meta.schema.clone(),
+                })?;
+            let req = CreateTableRequest {
+                id: *id,
+                catalog_name: catalog_name.clone(),
+                schema_name: schema_name.clone(),
+                table_name: table_name.clone(),
+                desc: None,
+                schema: Arc::new(schema),
+                region_numbers: regions_id_map.get(&node_id).unwrap().clone(), // this unwrap is safe because region_id_map is checked by callers
+                primary_key_indices: meta.primary_key_indices.clone(),
+                create_if_not_exists: true,
+                table_options: meta.options.clone(),
+            };
+
+            engine
+                .create_table(&context, req)
+                .await
+                .context(CreateTableSnafu {
+                    table_info: format!(
+                        "{}.{}.{}, id:{}",
+                        &catalog_name, &schema_name, &table_name, id
+                    ),
+                })
+        }
+    }
+}
+
+/// Keeps catalogs of [RemoteCatalogManager] in sync with metasrv by watching catalog, schema and
+/// table global keys, so that changes made by other nodes take effect without restarting.
+struct CatalogWatcher {
+    node_id: u64,
+    backend: KvBackendRef,
+    catalogs: Arc<ArcSwap<HashMap<String, CatalogProviderRef>>>,
+    engine: TableEngineRef,
+}
+
+impl CatalogWatcher {
+    /// Watches catalog, schema and table global keys on metasrv.
+    async fn watch(backend: &KvBackendRef) -> Result<EventIter<'static, Error>> {
+        let catalog_prefix = build_catalog_prefix();
+        let schemas_prefix = build_schemas_prefix();
+        let tables_prefix = build_tables_global_prefix();
+        let events = vec![
+            backend.watch_prefix(catalog_prefix.as_bytes()).await?,
+            backend.watch_prefix(schemas_prefix.as_bytes()).await?,
+            backend.watch_prefix(tables_prefix.as_bytes()).await?,
+        ];
+        Ok(Box::pin(futures::stream::select_all(events)))
+    }
+
+    async fn run(self, mut events: EventIter<'static, Error>) {
+        loop {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(event) => {
+                        if let Err(e) = self.handle_event(event).await {
+                            error!(e; "Failed to handle catalog event");
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Catalog watch interrupted, err: {:?}", e);
+                        break;
+                    }
+                }
+            }
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                match self.rewatch().await {
+                    Ok(new_events) => {
+                        events = new_events;
+                        break;
+                    }
+                    Err(e) => error!(e; "Failed to rewatch catalogs on metasrv"),
+                }
+            }
+        }
+    }
+
+    /// Watches metasrv again and then catches up changes happened while the watch is broken.
+    /// Changes made after the new watch is established are delivered by the returned stream.
+    async fn rewatch(&self) -> Result<EventIter<'static, Error>> {
+        let events = Self::watch(&self.backend).await?;
+        self.resync().await?;
+        Ok(events)
+    }
+
+    async fn resync(&self) -> Result<()> {
+        for catalog in self.catalogs.load().values() {
+            if let Some(catalog) = catalog.as_any().downcast_ref::<RemoteCatalogProvider>() {
+                catalog.refresh_schemas()?;
+            }
+        }
+
+        // Opens tables created during the outage.
+        let tables_prefix = build_tables_global_prefix();
+        let mut remote_tables = HashSet::new();
+        let mut tables = self.backend.range(tables_prefix.as_bytes());
+        while let Some(r) = tables.next().await {
+            let Kv(k, v) = r?;
+            if !k.starts_with(tables_prefix.as_bytes()) {
+                continue;
+            }
+            let table_key = TableGlobalKey::parse(&String::from_utf8_lossy(&k))
+                .context(InvalidCatalogValueSnafu)?;
+            let table_value = TableGlobalValue::parse(&String::from_utf8_lossy(&v))
+                .context(InvalidCatalogValueSnafu)?;
+            self.open_table(&table_key, &table_value).await?;
+            let _ = remote_tables.insert(table_key.to_string());
+        }
+
+        // Closes tables dropped during the outage.
+        for (catalog_name, catalog) in self.catalogs.load().iter() {
+            for schema_name in catalog.schema_names()? {
+                let schema = match catalog.schema(&schema_name)? {
+                    Some(schema) => schema,
+                    None => continue,
+                };
+                for table_name in schema.table_names()? {
+                    // System tables and the numbers table are registered locally.
+                    let is_user_table = schema
+                        .table(&table_name)?
+                        .map(|t| t.table_info().ident.table_id >= MIN_USER_TABLE_ID)
+                        .unwrap_or(false);
+                    let table_key = TableGlobalKey {
+                        catalog_name: catalog_name.clone(),
+                        schema_name: schema_name.clone(),
+                        table_name,
+                    };
+                    if is_user_table && !remote_tables.contains(&table_key.to_string()) {
+                        self.close_table(&table_key)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: KvEvent) -> Result<()> {
+        match event {
+            KvEvent::Put(Kv(k, v)) => {
+                let key = String::from_utf8_lossy(&k);
+                if key.starts_with(&build_catalog_prefix()) {
+                    let catalog_key = CatalogKey::parse(&key).context(InvalidCatalogValueSnafu)?;
+                    let _ = self.cache_catalog(&catalog_key.catalog_name);
+                } else if key.starts_with(&build_schemas_prefix()) {
+                    let schema_key = SchemaKey::parse(&key).context(InvalidCatalogValueSnafu)?;
+                    let _ = self.cache_schema(&schema_key.catalog_name, &schema_key.schema_name);
+                } else if key.starts_with(&build_tables_global_prefix()) {
+                    let table_key =
+                        TableGlobalKey::parse(&key).context(InvalidCatalogValueSnafu)?;
+                    let table_value = TableGlobalValue::parse(&String::from_utf8_lossy(&v))
+                        .context(InvalidCatalogValueSnafu)?;
+                    self.open_table(&table_key, &table_value).await?;
+                }
+            }
+            KvEvent::Delete(k) => {
+                let key = String::from_utf8_lossy(&k);
+                // Dropping catalogs and schemas is not supported yet, only tables are handled.
+                if key.starts_with(&build_tables_global_prefix()) {
+                    let table_key =
+                        TableGlobalKey::parse(&key).context(InvalidCatalogValueSnafu)?;
+                    self.close_table(&table_key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn cache_catalog(&self, catalog_name: &str) -> CatalogProviderRef {
+        if let Some(catalog) = self.catalogs.load().get(catalog_name) {
+            return catalog.clone();
+        }
+
+        info!("Found new catalog from metasrv: {}", catalog_name);
+        let catalog: CatalogProviderRef = Arc::new(RemoteCatalogProvider::new(
+            catalog_name.to_string(),
+            self.backend.clone(),
+            self.node_id,
+        ));
+        let prev = self.catalogs.rcu(|catalogs| {
+            let mut catalogs = HashMap::clone(catalogs);
+            catalogs
+                .entry(catalog_name.to_string())
+                .or_insert_with(|| catalog.clone());
+            catalogs
+        });
+        prev.get(catalog_name).cloned().unwrap_or(catalog)
+    }
+
+    fn cache_schema(&self, catalog_name: &str, schema_name: &str) -> Option<SchemaProviderRef> {
+        let catalog = self.cache_catalog(catalog_name);
+        let catalog = catalog.as_any().downcast_ref::<RemoteCatalogProvider>()?;
+        Some(catalog.cache_schema(schema_name))
+    }
+
+    /// Opens the table if metasrv has allocated regions of it to current datanode.
+    async fn open_table(
+        &self,
+        table_key: &TableGlobalKey,
+        table_value: &TableGlobalValue,
+    ) -> Result<()> {
+        if !table_value
+            .regions_id_map
+            .get(&self.node_id)
+            .map(|v| !v.is_empty())
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+
+        let schema = match self.cache_schema(&table_key.catalog_name, &table_key.schema_name) {
+            Some(schema) => schema,
+            None => return Ok(()),
+        };
+        if schema.table_exist(&table_key.table_name)? {
+            return Ok(());
+        }
+
+        let table =
+            open_or_create_table(&self.engine, self.node_id, table_key, table_value).await?;
+        schema.register_table(table_key.table_name.clone(), table)?;
+        info!("Registered table {} from metasrv", table_key);
+        Ok(())
+    }
+
+    fn close_table(&self, table_key: &TableGlobalKey) -> Result<()> {
+        let schema = self
+            .catalogs
+            .load()
+            .get(&table_key.catalog_name)
+            .map(|catalog| catalog.schema(&table_key.schema_name))
+            .transpose()?
+            .flatten();
+        if let Some(schema) = schema {
+            if schema.deregister_table(&table_key.table_name)?.is_some() {
+                info!("Deregistered table {} removed from metasrv", table_key);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Users and privileges shared by all frontends, persisted in metasrv.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use common_telemetry::{error, warn};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, ResultExt};
+
+use crate::error::{
+    Error, Result, RoleExistsSnafu, RoleNotFoundSnafu, UserExistsSnafu, UserNotFoundSnafu,
+    ValueDeserializeSnafu,
+};
+use crate::privilege::{
+    CreateRoleRequest, CreateUserRequest, GrantObject, GrantRequest, GrantRoleRequest, Principal,
+    Privilege, PrivilegeManager, PrivilegeManagerRef,
+};
+use crate::remote::{EventIter, Kv, KvBackendRef, KvEvent};
+
+const USER_KEY_PREFIX: &str = "__u-";
+
+fn build_user_key(username: &str) -> String {
+    format!("{}{}", USER_KEY_PREFIX, username)
+}
+
+/// Value of a user key, which holds all privileges and roles of the user, so that granting and
+/// revoking are atomic updates of a single key. Roles are kept under the same keys, since users
+/// and roles share the same names.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UserValue {
+    grants: Vec<(GrantObject, Vec<Privilege>)>,
+    #[serde(default)]
+    is_role: bool,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+impl UserValue {
+    fn parse(value: &[u8]) -> Result<Principal> {
+        let value: UserValue = serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+        Ok(Principal {
+            is_role: value.is_role,
+            grants: value
+                .grants
+                .into_iter()
+                .map(|(object, privileges)| (object, privileges.into_iter().collect()))
+                .collect(),
+            roles: value.roles.into_iter().collect(),
+        })
+    }
+
+    fn encode(principal: &Principal) -> Vec<u8> {
+        let value = UserValue {
+            grants: principal
+                .grants
+                .iter()
+                .map(|(object, privileges)| (object.clone(), privileges.iter().copied().collect()))
+                .collect(),
+            is_role: principal.is_role,
+            roles: principal.roles.iter().cloned().collect(),
+        };
+        // Safety: serializing plain structs and enums to JSON never fails.
+        serde_json::to_vec(&value).unwrap()
+    }
+}
+
+/// Keeps the users, roles and privileges in metasrv, and caches them in a [PrivilegeManager]
+/// which is kept in sync by watching the user keys, so that changes made by other frontends take
+/// effect immediately.
+pub struct RemotePrivileges {
+    backend: KvBackendRef,
+    privileges: PrivilegeManagerRef,
+}
+
+impl RemotePrivileges {
+    pub fn new(backend: KvBackendRef) -> Self {
+        Self {
+            backend,
+            privileges: PrivilegeManagerRef::new(PrivilegeManager::default()),
+        }
+    }
+
+    pub fn privilege_manager(&self) -> PrivilegeManagerRef {
+        self.privileges.clone()
+    }
+
+    /// Loads the users from metasrv and starts watching their changes.
+    pub async fn start(&self) -> Result<()> {
+        let syncer = PrivilegeSyncer {
+            backend: self.backend.clone(),
+            privileges: self.privileges.clone(),
+        };
+        let events = syncer.rewatch().await?;
+        common_runtime::spawn_bg(syncer.run(events));
+        Ok(())
+    }
+
+    pub async fn create_user(&self, request: CreateUserRequest) -> Result<bool> {
+        let username = &request.username;
+        let created = self.create_principal(username, Principal::user()).await?;
+        if !created {
+            ensure!(request.if_not_exists, UserExistsSnafu { username });
+        }
+        Ok(created)
+    }
+
+    pub async fn create_role(&self, request: CreateRoleRequest) -> Result<bool> {
+        let role = &request.role;
+        let created = self.create_principal(role, Principal::role()).await?;
+        if !created {
+            ensure!(request.if_not_exists, RoleExistsSnafu { role });
+        }
+        Ok(created)
+    }
+
+    /// Creates the user or role, returns false if the name is taken.
+    async fn create_principal(&self, name: &str, principal: Principal) -> Result<bool> {
+        let created = self
+            .backend
+            .compare_and_set(
+                build_user_key(name).as_bytes(),
+                &[],
+                &UserValue::encode(&principal),
+            )
+            .await?
+            .is_ok();
+        if created {
+            self.privileges.set_principal(name, principal);
+        }
+        Ok(created)
+    }
+
+    pub async fn grant(&self, request: GrantRequest) -> Result<()> {
+        self.update_principal(&request.username, |principal| {
+            principal
+                .grants
+                .entry(request.object.clone())
+                .or_default()
+                .extend(&request.privileges);
+        })
+        .await
+    }
+
+    pub async fn revoke(&self, request: GrantRequest) -> Result<()> {
+        self.update_principal(&request.username, |principal| {
+            if let Some(privileges) = principal.grants.get_mut(&request.object) {
+                for privilege in &request.privileges {
+                    let _ = privileges.remove(privilege);
+                }
+                if privileges.is_empty() {
+                    let _ = principal.grants.remove(&request.object);
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn grant_roles(&self, request: GrantRoleRequest) -> Result<()> {
+        self.ensure_roles_exist(&request.roles)?;
+        self.update_principal(&request.username, |principal| {
+            principal.roles.extend(request.roles.iter().cloned());
+        })
+        .await
+    }
+
+    pub async fn revoke_roles(&self, request: GrantRoleRequest) -> Result<()> {
+        self.ensure_roles_exist(&request.roles)?;
+        self.update_principal(&request.username, |principal| {
+            for role in &request.roles {
+                let _ = principal.roles.remove(role);
+            }
+        })
+        .await
+    }
+
+    fn ensure_roles_exist(&self, roles: &[String]) -> Result<()> {
+        for role in roles {
+            ensure!(
+                self.privileges.contains_role(role),
+                RoleNotFoundSnafu { role }
+            );
+        }
+        Ok(())
+    }
+
+    /// Updates the user or role, retries if it is modified by others concurrently.
+    async fn update_principal<F>(&self, username: &str, update: F) -> Result<()>
+    where
+        F: Fn(&mut Principal) + Send,
+    {
+        let key = build_user_key(username);
+        let mut current = self.backend.get(key.as_bytes()).await?.map(|Kv(_, v)| v);
+        loop {
+            let value = match &current {
+                Some(value) => value,
+                None => return UserNotFoundSnafu { username }.fail(),
+            };
+            let mut principal = UserValue::parse(value)?;
+            update(&mut principal);
+
+            match self
+                .backend
+                .compare_and_set(key.as_bytes(), value, &UserValue::encode(&principal))
+                .await?
+            {
+                Ok(()) => {
+                    self.privileges.set_principal(username, principal);
+                    return Ok(());
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+struct PrivilegeSyncer {
+    backend: KvBackendRef,
+    privileges: PrivilegeManagerRef,
+}
+
+impl PrivilegeSyncer {
+    async fn run(self, mut events: EventIter<'static, Error>) {
+        loop {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(event) => {
+                        if let Err(e) = self.handle_event(event) {
+                            error!(e; "Failed to handle user event");
+                        }
+                    }
+                    Err(e) => {
+                        warn!("User watch interrupted, err: {:?}", e);
+                        break;
+                    }
+                }
+            }
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                match self.rewatch().await {
+                    Ok(new_events) => {
+                        events = new_events;
+                        break;
+                    }
+                    Err(e) => error!(e; "Failed to rewatch users on metasrv"),
+                }
+            }
+        }
+    }
+
+    /// Watches the user keys, then reloads all users, so that no change is missed in between.
+    async fn rewatch(&self) -> Result<EventIter<'static, Error>> {
+        let events = self
+            .backend
+            .watch_prefix(USER_KEY_PREFIX.as_bytes())
+            .await?;
+
+        let mut users = HashMap::new();
+        let mut iter = self.backend.range(USER_KEY_PREFIX.as_bytes());
+        while let Some(kv) = iter.next().await {
+            let Kv(key, value) = kv?;
+            if let Some(username) = parse_username(&key) {
+                let _ = users.insert(username, UserValue::parse(&value)?);
+            }
+        }
+        self.privileges.reset(users);
+        Ok(events)
+    }
+
+    fn handle_event(&self, event: KvEvent) -> Result<()> {
+        match event {
+            KvEvent::Put(Kv(key, value)) => {
+                if let Some(username) = parse_username(&key) {
+                    self.privileges
+                        .set_principal(&username, UserValue::parse(&value)?);
+                }
+            }
+            KvEvent::Delete(key) => {
+                if let Some(username) = parse_username(&key) {
+                    self.privileges.remove_principal(&username);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_username(key: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(key)
+        .strip_prefix(USER_KEY_PREFIX)
+        .map(|username| username.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::privilege::Grants;
+
+    #[test]
+    fn test_user_value() {
+        let principal = Principal {
+            grants: Grants::from([(
+                GrantObject::schema("greptime", "public"),
+                BTreeSet::from([Privilege::Select, Privilege::Insert]),
+            )]),
+            roles: BTreeSet::from(["reader".to_string()]),
+            ..Default::default()
+        };
+        let value = UserValue::encode(&principal);
+        assert_eq!(principal, UserValue::parse(&value).unwrap());
+
+        let role = Principal::role();
+        assert_eq!(role, UserValue::parse(&UserValue::encode(&role)).unwrap());
+
+        // Values written before roles are supported are users.
+        let value = br#"{"grants":[]}"#;
+        assert_eq!(Principal::user(), UserValue::parse(value).unwrap());
+
+        assert_eq!(Some("alice".to_string()), parse_username(b"__u-alice"));
+        assert_eq!(None, parse_username(b"__tg-greptime-public-cpu"));
+    }
+}
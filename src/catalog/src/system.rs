@@ -40,6 +40,7 @@ use crate::error::{
     self, CreateSystemCatalogSnafu, EmptyValueSnafu, Error, InvalidEntryTypeSnafu, InvalidKeySnafu,
     OpenSystemCatalogSnafu, Result, ValueDeserializeSnafu,
 };
+use crate::privilege::{GrantObject, Privilege};
 
 pub const ENTRY_TYPE_INDEX: usize = 0;
 pub const KEY_INDEX: usize = 1;
@@ -208,6 +209,57 @@ pub fn build_schema_insert_request(catalog_name: String, schema_name: String) ->
     )
 }
 
+pub fn build_user_insert_request(username: &str) -> InsertRequest {
+    build_insert_request(
+        EntryType::User,
+        username.as_bytes(),
+        serde_json::to_string(&UserEntryValue {})
+            .unwrap()
+            .as_bytes(),
+    )
+}
+
+pub fn build_role_insert_request(role: &str) -> InsertRequest {
+    build_insert_request(
+        EntryType::Role,
+        role.as_bytes(),
+        serde_json::to_string(&RoleEntryValue {})
+            .unwrap()
+            .as_bytes(),
+    )
+}
+
+/// Builds the request to persist all roles granted to the user or role, which replaces the
+/// roles persisted before.
+pub fn build_user_roles_insert_request(username: &str, roles: Vec<String>) -> InsertRequest {
+    let value = UserRolesEntryValue { roles };
+    build_insert_request(
+        EntryType::UserRoles,
+        username.as_bytes(),
+        serde_json::to_string(&value).unwrap().as_bytes(),
+    )
+}
+
+/// Builds the request to persist all privileges of the user on the object, which replaces the
+/// privileges persisted before.
+pub fn build_privilege_insert_request(
+    username: &str,
+    object: &GrantObject,
+    privileges: Vec<Privilege>,
+) -> InsertRequest {
+    let key = format!("{}@{}", username, object);
+    let value = PrivilegeEntryValue {
+        username: username.to_string(),
+        object: object.clone(),
+        privileges,
+    };
+    build_insert_request(
+        EntryType::Privilege,
+        key.as_bytes(),
+        serde_json::to_string(&value).unwrap().as_bytes(),
+    )
+}
+
 pub fn build_insert_request(entry_type: EntryType, key: &[u8], value: &[u8]) -> InsertRequest {
     let mut columns_values = HashMap::with_capacity(6);
     columns_values.insert(
@@ -309,6 +361,45 @@ pub fn decode_system_catalog(
                 table_id: table_meta.table_id,
             }))
         }
+
+        EntryType::User => {
+            // As for user entry, the key is the username and the value is currently not used.
+            Ok(Entry::User(UserEntry {
+                username: key.to_string(),
+            }))
+        }
+
+        EntryType::Role => {
+            // As for role entry, the key is the role name and the value is currently not used.
+            Ok(Entry::Role(RoleEntry {
+                role: key.to_string(),
+            }))
+        }
+
+        EntryType::Privilege => {
+            // As for privilege entry, the key is a string with format: `<username>@<grant_object>`
+            // and the value is a JSON-encoded [PrivilegeEntryValue].
+            let value = value.context(EmptyValueSnafu)?;
+            let privilege: PrivilegeEntryValue =
+                serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+            Ok(Entry::Privilege(PrivilegeEntry {
+                username: privilege.username,
+                object: privilege.object,
+                privileges: privilege.privileges,
+            }))
+        }
+
+        EntryType::UserRoles => {
+            // As for user roles entry, the key is the name of the user or role and the value is
+            // a JSON-encoded [UserRolesEntryValue].
+            let value = value.context(EmptyValueSnafu)?;
+            let user_roles: UserRolesEntryValue =
+                serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+            Ok(Entry::UserRoles(UserRolesEntry {
+                username: key.to_string(),
+                roles: user_roles.roles,
+            }))
+        }
     }
 }
 
@@ -317,6 +408,10 @@ pub enum EntryType {
     Catalog = 1,
     Schema = 2,
     Table = 3,
+    User = 4,
+    Privilege = 5,
+    Role = 6,
+    UserRoles = 7,
 }
 
 impl TryFrom<u8> for EntryType {
@@ -327,6 +422,10 @@ impl TryFrom<u8> for EntryType {
             b if b == Self::Catalog as u8 => Ok(Self::Catalog),
             b if b == Self::Schema as u8 => Ok(Self::Schema),
             b if b == Self::Table as u8 => Ok(Self::Table),
+            b if b == Self::User as u8 => Ok(Self::User),
+            b if b == Self::Privilege as u8 => Ok(Self::Privilege),
+            b if b == Self::Role as u8 => Ok(Self::Role),
+            b if b == Self::UserRoles as u8 => Ok(Self::UserRoles),
             b => InvalidEntryTypeSnafu {
                 entry_type: Some(b),
             }
@@ -340,6 +439,11 @@ pub enum Entry {
     Catalog(CatalogEntry),
     Schema(SchemaEntry),
     Table(TableEntry),
+    User(UserEntry),
+    // Roles are loaded before the privileges granted to them.
+    Role(RoleEntry),
+    Privilege(PrivilegeEntry),
+    UserRoles(UserRolesEntry),
 }
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
@@ -369,6 +473,48 @@ pub struct TableEntryValue {
     pub table_id: TableId,
 }
 
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct UserEntry {
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserEntryValue {}
+
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct RoleEntry {
+    pub role: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoleEntryValue {}
+
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct PrivilegeEntry {
+    pub username: String,
+    pub object: GrantObject,
+    pub privileges: Vec<Privilege>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrivilegeEntryValue {
+    pub username: String,
+    pub object: GrantObject,
+    pub privileges: Vec<Privilege>,
+}
+
+/// Roles granted to a user or role.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct UserRolesEntry {
+    pub username: String,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserRolesEntryValue {
+    pub roles: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use log_store::fs::noop::NoopLogStore;
@@ -445,12 +591,82 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    pub fn test_decode_user_and_privilege() {
+        let entry =
+            decode_system_catalog(Some(EntryType::User as u8), Some("alice".as_bytes()), None)
+                .unwrap();
+        assert_eq!(
+            Entry::User(UserEntry {
+                username: "alice".to_string()
+            }),
+            entry
+        );
+
+        let object = GrantObject::schema("greptime", "public");
+        let value = serde_json::to_vec(&PrivilegeEntryValue {
+            username: "alice".to_string(),
+            object: object.clone(),
+            privileges: vec![Privilege::Select],
+        })
+        .unwrap();
+        let entry = decode_system_catalog(
+            Some(EntryType::Privilege as u8),
+            Some("alice@greptime.public.*".as_bytes()),
+            Some(&value),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::Privilege(PrivilegeEntry {
+                username: "alice".to_string(),
+                object,
+                privileges: vec![Privilege::Select],
+            }),
+            entry
+        );
+    }
+
+    #[test]
+    pub fn test_decode_role_and_user_roles() {
+        let entry =
+            decode_system_catalog(Some(EntryType::Role as u8), Some("reader".as_bytes()), None)
+                .unwrap();
+        assert_eq!(
+            Entry::Role(RoleEntry {
+                role: "reader".to_string()
+            }),
+            entry
+        );
+
+        let value = serde_json::to_vec(&UserRolesEntryValue {
+            roles: vec!["reader".to_string()],
+        })
+        .unwrap();
+        let entry = decode_system_catalog(
+            Some(EntryType::UserRoles as u8),
+            Some("alice".as_bytes()),
+            Some(&value),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::UserRoles(UserRolesEntry {
+                username: "alice".to_string(),
+                roles: vec!["reader".to_string()],
+            }),
+            entry
+        );
+    }
+
     #[test]
     pub fn test_entry_type() {
         assert_eq!(EntryType::Catalog, EntryType::try_from(1).unwrap());
         assert_eq!(EntryType::Schema, EntryType::try_from(2).unwrap());
         assert_eq!(EntryType::Table, EntryType::try_from(3).unwrap());
-        assert!(EntryType::try_from(4).is_err());
+        assert_eq!(EntryType::User, EntryType::try_from(4).unwrap());
+        assert_eq!(EntryType::Privilege, EntryType::try_from(5).unwrap());
+        assert_eq!(EntryType::Role, EntryType::try_from(6).unwrap());
+        assert_eq!(EntryType::UserRoles, EntryType::try_from(7).unwrap());
+        assert!(EntryType::try_from(8).is_err());
     }
 
     pub async fn prepare_table_engine() -> (TempDir, TableEngineRef) {
@@ -39,7 +39,12 @@ use table::table::scan::SimpleTableScan;
 use table::{Table, TableRef};
 
 use crate::error::{Error, InsertCatalogRecordSnafu};
-use crate::system::{build_schema_insert_request, build_table_insert_request, SystemCatalogTable};
+use crate::privilege::{GrantObject, Privilege};
+use crate::system::{
+    build_privilege_insert_request, build_role_insert_request, build_schema_insert_request,
+    build_table_insert_request, build_user_insert_request, build_user_roles_insert_request,
+    SystemCatalogTable,
+};
 use crate::{
     format_full_table_name, CatalogListRef, CatalogProvider, SchemaProvider, SchemaProviderRef,
 };
@@ -283,6 +288,53 @@ impl SystemCatalog {
             .await
             .context(InsertCatalogRecordSnafu)
     }
+
+    pub async fn register_user(&self, username: &str) -> crate::error::Result<usize> {
+        let request = build_user_insert_request(username);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    pub async fn register_role(&self, role: &str) -> crate::error::Result<usize> {
+        let request = build_role_insert_request(role);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    /// Persists all roles granted to the user or role.
+    pub async fn register_user_roles(
+        &self,
+        username: &str,
+        roles: Vec<String>,
+    ) -> crate::error::Result<usize> {
+        let request = build_user_roles_insert_request(username, roles);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    /// Persists all privileges of the user on the object.
+    pub async fn register_privileges(
+        &self,
+        username: &str,
+        object: &GrantObject,
+        privileges: Vec<Privilege>,
+    ) -> crate::error::Result<usize> {
+        let request = build_privilege_insert_request(username, object, privileges);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
 }
 
 impl CatalogProvider for SystemCatalog {
@@ -20,7 +20,7 @@ use std::sync::Arc;
 
 use async_stream::stream;
 use catalog::error::Error;
-use catalog::remote::{Kv, KvBackend, ValueIter};
+use catalog::remote::{EventIter, Kv, KvBackend, KvEvent, ValueIter};
 use common_recordbatch::RecordBatch;
 use common_telemetry::logging::info;
 use datatypes::data_type::ConcreteDataType;
@@ -32,11 +32,46 @@ use table::metadata::TableId;
 use table::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
 use table::test_util::MemTable;
 use table::TableRef;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
-#[derive(Default)]
 pub struct MockKvBackend {
     map: RwLock<BTreeMap<Vec<u8>, Vec<u8>>>,
+    events: std::sync::Mutex<broadcast::Sender<KvEvent>>,
+}
+
+impl Default for MockKvBackend {
+    fn default() -> Self {
+        let (events, _) = broadcast::channel(1024);
+        Self {
+            map: Default::default(),
+            events: std::sync::Mutex::new(events),
+        }
+    }
+}
+
+impl MockKvBackend {
+    fn notify(&self, event: KvEvent) {
+        // Sending fails only when there are no watchers, which is fine.
+        let _ = self.events.lock().unwrap().send(event);
+    }
+
+    /// Terminates all established watches, like a broken connection to metasrv.
+    pub fn interrupt_watches(&self) {
+        let (events, _) = broadcast::channel(1024);
+        *self.events.lock().unwrap() = events;
+    }
+
+    /// Sets the value without notifying watchers, like a change missed by watchers.
+    pub async fn set_silently(&self, key: &[u8], val: &[u8]) {
+        let mut map = self.map.write().await;
+        map.insert(key.to_vec(), val.to_vec());
+    }
+
+    /// Deletes the key without notifying watchers.
+    pub async fn delete_silently(&self, key: &[u8]) {
+        let mut map = self.map.write().await;
+        map.remove(key);
+    }
 }
 
 impl Display for MockKvBackend {
@@ -80,6 +115,7 @@ impl KvBackend for MockKvBackend {
     async fn set(&self, key: &[u8], val: &[u8]) -> Result<(), Error> {
         let mut map = self.map.write().await;
         map.insert(key.to_vec(), val.to_vec());
+        self.notify(KvEvent::Put(Kv(key.to_vec(), val.to_vec())));
         Ok(())
     }
 
@@ -95,6 +131,7 @@ impl KvBackend for MockKvBackend {
             Entry::Vacant(e) => {
                 if expect.is_empty() {
                     e.insert(val.to_vec());
+                    self.notify(KvEvent::Put(Kv(key.to_vec(), val.to_vec())));
                     Ok(Ok(()))
                 } else {
                     Ok(Err(None))
@@ -103,6 +140,7 @@ impl KvBackend for MockKvBackend {
             Entry::Occupied(mut existing) => {
                 if existing.get() == expect {
                     existing.insert(val.to_vec());
+                    self.notify(KvEvent::Put(Kv(key.to_vec(), val.to_vec())));
                     Ok(Ok(()))
                 } else {
                     Ok(Err(Some(existing.get().clone())))
@@ -117,9 +155,33 @@ impl KvBackend for MockKvBackend {
         let range = start..end;
 
         let mut map = self.map.write().await;
+        let deleted = map
+            .keys()
+            .filter(|k| range.contains(k))
+            .cloned()
+            .collect::<Vec<_>>();
         map.retain(|k, _| !range.contains(k));
+        for key in deleted {
+            self.notify(KvEvent::Delete(key));
+        }
         Ok(())
     }
+
+    async fn watch_prefix(&self, prefix: &[u8]) -> Result<EventIter<'static, Error>, Error> {
+        let prefix = prefix.to_vec();
+        let mut receiver = self.events.lock().unwrap().subscribe();
+        Ok(Box::pin(stream!({
+            while let Ok(event) = receiver.recv().await {
+                let key = match &event {
+                    KvEvent::Put(Kv(k, _)) => k,
+                    KvEvent::Delete(k) => k,
+                };
+                if key.starts_with(&prefix) {
+                    yield Ok(event)
+                }
+            }
+        })))
+    }
 }
 
 #[derive(Default)]
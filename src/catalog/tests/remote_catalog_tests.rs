@@ -19,18 +19,23 @@ mod mock;
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
+    use std::time::Duration;
 
     use catalog::remote::{
         KvBackend, KvBackendRef, RemoteCatalogManager, RemoteCatalogProvider, RemoteSchemaProvider,
     };
     use catalog::{CatalogList, CatalogManager, RegisterTableRequest};
-    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
-    use common_catalog::{CatalogKey, CatalogValue, SchemaKey, SchemaValue};
-    use datatypes::schema::Schema;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MIN_USER_TABLE_ID};
+    use common_catalog::{
+        CatalogKey, CatalogValue, SchemaKey, SchemaValue, TableGlobalKey, TableGlobalValue,
+    };
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
     use futures_util::StreamExt;
     use table::engine::{EngineContext, TableEngineRef};
+    use table::metadata::{RawTableMeta, TableMetaBuilder};
     use table::requests::CreateTableRequest;
 
     use crate::mock::{MockKvBackend, MockTableEngine};
@@ -294,4 +299,126 @@ mod tests {
             new_catalog.schema_names().unwrap().into_iter().collect()
         )
     }
+
+    #[tokio::test]
+    async fn test_watch_remote_catalog_changes() {
+        common_telemetry::init_default_ut_logging();
+        let node_id = 42;
+        let (backend, _, catalog_manager) = prepare_components(node_id).await;
+
+        let catalog_name = "watched_catalog".to_string();
+        let schema_name = "watched_schema".to_string();
+
+        // Catalog and schema created by other nodes.
+        let catalog_key = CatalogKey {
+            catalog_name: catalog_name.clone(),
+        }
+        .to_string();
+        backend
+            .set(catalog_key.as_bytes(), &CatalogValue {}.as_bytes().unwrap())
+            .await
+            .unwrap();
+        let schema_key = SchemaKey {
+            catalog_name: catalog_name.clone(),
+            schema_name: schema_name.clone(),
+        }
+        .to_string();
+        backend
+            .set(schema_key.as_bytes(), &SchemaValue {}.as_bytes().unwrap())
+            .await
+            .unwrap();
+
+        let mut schema = None;
+        for _ in 0..50 {
+            schema = catalog_manager
+                .schema(&catalog_name, &schema_name)
+                .unwrap_or(None);
+            if schema.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(schema.is_some());
+        assert_eq!(
+            HashSet::from([DEFAULT_CATALOG_NAME.to_string(), catalog_name]),
+            catalog_manager
+                .catalog_names()
+                .unwrap()
+                .into_iter()
+                .collect::<HashSet<_>>()
+        );
+    }
+
+    async fn wait_table(
+        catalog_manager: &RemoteCatalogManager,
+        table_name: &str,
+        exists: bool,
+    ) -> bool {
+        for _ in 0..100 {
+            let table = catalog_manager
+                .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, table_name)
+                .unwrap();
+            if table.is_some() == exists {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_resync_tables_after_watch_interrupted() {
+        common_telemetry::init_default_ut_logging();
+        let node_id = 42;
+        let backend = Arc::new(MockKvBackend::default());
+        let catalog_manager = RemoteCatalogManager::new(
+            Arc::new(MockTableEngine::default()),
+            node_id,
+            backend.clone(),
+        );
+        catalog_manager.start().await.unwrap();
+
+        let table_name = "created_during_outage";
+        let table_key = TableGlobalKey {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: table_name.to_string(),
+        }
+        .to_string();
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "name",
+            ConcreteDataType::string_datatype(),
+            true,
+        )]));
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![])
+            .next_column_id(1)
+            .options(HashMap::from([(
+                "table_id".to_string(),
+                MIN_USER_TABLE_ID.to_string(),
+            )]))
+            .build()
+            .unwrap();
+        let table_value = TableGlobalValue {
+            id: MIN_USER_TABLE_ID,
+            node_id,
+            regions_id_map: HashMap::from([(node_id, vec![0])]),
+            meta: RawTableMeta::from(meta),
+        };
+
+        // Table created while the watch is broken.
+        backend
+            .set_silently(table_key.as_bytes(), &table_value.as_bytes().unwrap())
+            .await;
+        backend.interrupt_watches();
+        assert!(wait_table(&catalog_manager, table_name, true).await);
+
+        // Table dropped while the watch is broken.
+        backend.delete_silently(table_key.as_bytes()).await;
+        backend.interrupt_watches();
+        assert!(wait_table(&catalog_manager, table_name, false).await);
+        // The numbers table is not managed by metasrv.
+        assert!(wait_table(&catalog_manager, "numbers", true).await);
+    }
 }
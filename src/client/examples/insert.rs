@@ -27,7 +27,7 @@ fn main() {
 #[tokio::main]
 async fn run() {
     let client = Client::with_urls(vec!["127.0.0.1:3001"]);
-    let db = Database::new("greptime", client);
+    let db = Database::new("public", client);
 
     let expr = InsertExpr {
         schema_name: "public".to_string(),
@@ -19,7 +19,7 @@ use prost_09::Message;
 use substrait_proto::protobuf::plan_rel::RelType as PlanRelType;
 use substrait_proto::protobuf::read_rel::{NamedTable, ReadType};
 use substrait_proto::protobuf::rel::RelType;
-use substrait_proto::protobuf::{PlanRel, ReadRel, Rel};
+use substrait_proto::protobuf::{Plan, PlanRel, ReadRel, Rel};
 use tracing::{event, Level};
 
 fn main() {
@@ -44,18 +44,21 @@ async fn run() {
                 datatype: ColumnDataType::Timestamp as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "key".to_string(),
                 datatype: ColumnDataType::Uint64 as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "value".to_string(),
                 datatype: ColumnDataType::Uint64 as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ],
         time_index: "timestamp".to_string(),
@@ -72,7 +75,7 @@ async fn run() {
 
     let logical = mock_logical_plan();
     event!(Level::INFO, "plan size: {:#?}", logical.len());
-    let db = Database::new("greptime", client);
+    let db = Database::new("public", client);
     let result = db.logical_plan(logical).await.unwrap();
 
     event!(Level::INFO, "result: {:#?}", result);
@@ -102,10 +105,13 @@ fn mock_logical_plan() -> Vec<u8> {
     let rel = Rel {
         rel_type: Some(RelType::Read(Box::new(read_rel))),
     };
-    let plan_rel = PlanRel {
-        rel_type: Some(PlanRelType::Rel(rel)),
+    let plan = Plan {
+        relations: vec![PlanRel {
+            rel_type: Some(PlanRelType::Rel(rel)),
+        }],
+        ..Default::default()
     };
-    plan_rel.encode(&mut buf).unwrap();
+    plan.encode(&mut buf).unwrap();
 
     buf
 }
@@ -31,7 +31,7 @@ fn main() {
 #[tokio::main]
 async fn run() {
     let client = Client::with_urls(vec!["127.0.0.1:3001"]);
-    let db = Database::new("greptime", client);
+    let db = Database::new("public", client);
 
     let physical = mock_physical_plan();
     let result = db.physical_plan(physical, None).await;
@@ -25,7 +25,7 @@ fn main() {
 #[tokio::main]
 async fn run() {
     let client = Client::with_urls(vec!["127.0.0.1:3001"]);
-    let db = Database::new("greptime", client);
+    let db = Database::new("public", client);
 
     let sql = Select::Sql("select * from demo".to_string());
     let result = db.select(sql).await.unwrap();
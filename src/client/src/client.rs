@@ -19,7 +19,9 @@ use api::v1::*;
 use common_grpc::channel_manager::ChannelManager;
 use parking_lot::RwLock;
 use snafu::{OptionExt, ResultExt};
+use tonic::metadata::{Ascii, MetadataValue};
 use tonic::transport::Channel;
+use tonic::Request;
 
 use crate::load_balance::{LoadBalance, Loadbalancer};
 use crate::{error, Result};
@@ -34,6 +36,8 @@ struct Inner {
     channel_manager: ChannelManager,
     peers: Arc<RwLock<Vec<String>>>,
     load_balance: Loadbalancer,
+    // Value of the `authorization` metadata sent with each request.
+    authorization: RwLock<Option<MetadataValue<Ascii>>>,
 }
 
 impl Inner {
@@ -104,6 +108,15 @@ impl Client {
         self.inner.set_peers(urls);
     }
 
+    /// Authenticates requests by the username and password, for servers requiring
+    /// authentication.
+    pub fn set_basic_auth(&self, username: &str, password: &str) {
+        let credential = base64::encode(format!("{}:{}", username, password));
+        // Safety: base64 encoded credential is valid ASCII.
+        let value = format!("Basic {}", credential).parse().unwrap();
+        *self.inner.authorization.write() = Some(value);
+    }
+
     pub async fn admin(&self, req: AdminRequest) -> Result<AdminResponse> {
         let req = BatchRequest {
             admins: vec![req],
@@ -140,6 +153,10 @@ impl Client {
                 err_msg: "No available peer found",
             })?;
         let mut client = self.make_client(&peer)?;
+        let mut req = Request::new(req);
+        if let Some(authorization) = self.inner.authorization.read().clone() {
+            let _ = req.metadata_mut().insert("authorization", authorization);
+        }
         let result = client
             .batch(req)
             .await
@@ -16,9 +16,9 @@ use std::sync::Arc;
 
 use api::v1::codec::SelectResult as GrpcSelectResult;
 use api::v1::{
-    object_expr, object_result, select_expr, DatabaseRequest, ExprHeader, InsertExpr,
-    MutateResult as GrpcMutateResult, ObjectExpr, ObjectResult as GrpcObjectResult, PhysicalPlan,
-    SelectExpr,
+    object_expr, object_result, select_expr, DataVersionsExpr, DatabaseRequest, ExprHeader,
+    InsertExpr, MutateResult as GrpcMutateResult, ObjectExpr, ObjectResult as GrpcObjectResult,
+    PhysicalPlan, SelectExpr,
 };
 use common_error::status_code::StatusCode;
 use common_grpc::{AsExcutionPlan, DefaultAsPlanImpl};
@@ -118,6 +118,14 @@ impl Database {
         self.do_select(select_expr).await
     }
 
+    /// Selects the versions of the data in the regions of the table.
+    pub async fn data_versions(&self, expr: DataVersionsExpr) -> Result<ObjectResult> {
+        let select_expr = SelectExpr {
+            expr: Some(select_expr::Expr::DataVersions(expr)),
+        };
+        self.do_select(select_expr).await
+    }
+
     async fn do_select(&self, select_expr: SelectExpr) -> Result<ObjectResult> {
         let header = ExprHeader {
             version: PROTOCOL_VERSION,
@@ -251,11 +259,12 @@ impl TryFrom<ObjectResult> for Output {
 mod tests {
     use api::helper::ColumnDataTypeWrapper;
     use api::v1::Column;
+    use common_time::Interval;
     use datanode::server::grpc::select::{null_mask, values};
     use datatypes::vectors::{
-        BinaryVector, BooleanVector, DateTimeVector, DateVector, Float32Vector, Float64Vector,
-        Int16Vector, Int32Vector, Int64Vector, Int8Vector, StringVector, UInt16Vector,
-        UInt32Vector, UInt64Vector, UInt8Vector,
+        BinaryVector, BooleanVector, DateTimeVector, DateVector, DecimalVector, Float32Vector,
+        Float64Vector, Int16Vector, Int32Vector, Int64Vector, Int8Vector, IntervalVector,
+        JsonVector, StringVector, UInt16Vector, UInt32Vector, UInt64Vector, UInt8Vector,
     };
 
     use super::*;
@@ -331,17 +340,31 @@ mod tests {
         test_with_vector!(StringVector::from(vec![Some(""), None, Some("foo"),]));
         test_with_vector!(DateVector::from(vec![Some(1), None, Some(3)]));
         test_with_vector!(DateTimeVector::from(vec![Some(4), None, Some(6)]));
+        test_with_vector!(DecimalVector::from_values(
+            10,
+            2,
+            vec![Some(i128::from(i64::MIN)), None, Some(12345)]
+        ));
+        test_with_vector!(JsonVector::from(vec![Some(r#"{"a":1}"#), None, Some("[]")]));
+        test_with_vector!(IntervalVector::from(vec![
+            Some(Interval::new(1, 2, 3)),
+            None,
+            Some(Interval::new(-1, 0, i64::MAX))
+        ]));
     }
 
     fn create_test_column(vector: VectorRef) -> Column {
-        let wrapper: ColumnDataTypeWrapper = vector.data_type().try_into().unwrap();
+        let (datatype, datatype_extension) = ColumnDataTypeWrapper::try_from(vector.data_type())
+            .unwrap()
+            .into_parts();
         let array = vector.to_arrow_array();
         Column {
             column_name: "test".to_string(),
             semantic_type: 1,
             values: Some(values(&[array.clone()]).unwrap()),
             null_mask: null_mask(&vec![array], vector.len()),
-            datatype: wrapper.datatype() as i32,
+            datatype: datatype as i32,
+            datatype_extension,
         }
     }
 }
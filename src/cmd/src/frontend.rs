@@ -68,6 +68,8 @@ pub struct StartCommand {
     influxdb_enable: Option<bool>,
     #[clap(long)]
     metasrv_addr: Option<String>,
+    #[clap(long)]
+    user_provider: Option<String>,
 }
 
 impl StartCommand {
@@ -133,6 +135,9 @@ impl TryFrom<StartCommand> for FrontendOptions {
             );
             opts.mode = Mode::Distributed;
         }
+        if let Some(user_provider) = cmd.user_provider {
+            opts.user_provider = Some(user_provider);
+        }
         Ok(opts)
     }
 }
@@ -152,6 +157,7 @@ mod tests {
             influxdb_enable: Some(false),
             config_file: None,
             metasrv_addr: None,
+            user_provider: Some("static_user_provider:file:/path/to/users".to_string()),
         };
 
         let opts: FrontendOptions = command.try_into().unwrap();
@@ -185,5 +191,9 @@ mod tests {
         );
 
         assert!(!opts.influxdb_options.unwrap().enable);
+        assert_eq!(
+            opts.user_provider,
+            Some("static_user_provider:file:/path/to/users".to_string())
+        );
     }
 }
@@ -14,9 +14,9 @@
 
 use clap::Parser;
 use common_telemetry::info;
-use datanode::datanode::{Datanode, DatanodeOptions, ObjectStoreConfig};
+use datanode::datanode::{Datanode, DatanodeOptions, ObjectStoreConfig, QueryCacheOptions};
 use datanode::instance::InstanceRef;
-use frontend::frontend::{Frontend, FrontendOptions, Mode};
+use frontend::frontend::{Frontend, FrontendOptions, Mode, TlsOption};
 use frontend::grpc::GrpcOptions;
 use frontend::influxdb::InfluxdbOptions;
 use frontend::instance::Instance as FeInstance;
@@ -24,6 +24,7 @@ use frontend::mysql::MysqlOptions;
 use frontend::opentsdb::OpentsdbOptions;
 use frontend::postgres::PostgresOptions;
 use frontend::prometheus::PrometheusOptions;
+use frontend::quota::QuotaOptions;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use tokio::try_join;
@@ -61,6 +62,8 @@ impl SubCommand {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StandaloneOptions {
     pub http_addr: Option<String>,
+    #[serde(default)]
+    pub http_tls: TlsOption,
     pub grpc_options: Option<GrpcOptions>,
     pub mysql_options: Option<MysqlOptions>,
     pub postgres_options: Option<PostgresOptions>,
@@ -72,12 +75,18 @@ pub struct StandaloneOptions {
     pub storage: ObjectStoreConfig,
     pub datanode_mysql_addr: String,
     pub datanode_mysql_runtime_size: usize,
+    pub user_provider: Option<String>,
+    #[serde(default)]
+    pub quota: QuotaOptions,
+    #[serde(default)]
+    pub query_cache: QueryCacheOptions,
 }
 
 impl Default for StandaloneOptions {
     fn default() -> Self {
         Self {
             http_addr: Some("0.0.0.0:4000".to_string()),
+            http_tls: TlsOption::default(),
             grpc_options: Some(GrpcOptions::default()),
             mysql_options: Some(MysqlOptions::default()),
             postgres_options: Some(PostgresOptions::default()),
@@ -89,6 +98,9 @@ impl Default for StandaloneOptions {
             storage: ObjectStoreConfig::default(),
             datanode_mysql_addr: "0.0.0.0:3306".to_string(),
             datanode_mysql_runtime_size: 4,
+            user_provider: None,
+            quota: QuotaOptions::default(),
+            query_cache: QueryCacheOptions::default(),
         }
     }
 }
@@ -97,6 +109,7 @@ impl StandaloneOptions {
     fn frontend_options(self) -> FrontendOptions {
         FrontendOptions {
             http_addr: self.http_addr,
+            http_tls: self.http_tls,
             grpc_options: self.grpc_options,
             mysql_options: self.mysql_options,
             postgres_options: self.postgres_options,
@@ -106,6 +119,9 @@ impl StandaloneOptions {
             mode: self.mode,
             datanode_rpc_addr: "127.0.0.1:3001".to_string(),
             metasrv_addr: None,
+            user_provider: self.user_provider,
+            quota: self.quota,
+            client_tls: None,
         }
     }
 
@@ -115,6 +131,7 @@ impl StandaloneOptions {
             storage: self.storage,
             mysql_addr: self.datanode_mysql_addr,
             mysql_runtime_size: self.datanode_mysql_runtime_size,
+            query_cache: self.query_cache,
             ..Default::default()
         }
     }
@@ -136,6 +153,8 @@ struct StartCommand {
     influxdb_enable: bool,
     #[clap(short, long)]
     config_file: Option<String>,
+    #[clap(long)]
+    user_provider: Option<String>,
 }
 
 impl StartCommand {
@@ -248,12 +267,18 @@ impl TryFrom<StartCommand> for FrontendOptions {
             opts.influxdb_options = Some(InfluxdbOptions { enable: true });
         }
 
+        if let Some(user_provider) = cmd.user_provider {
+            opts.user_provider = Some(user_provider);
+        }
+
         Ok(opts)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use frontend::postgres::PgAuthMethod;
+
     use super::*;
 
     #[test]
@@ -269,6 +294,7 @@ mod tests {
                 std::env::current_dir().unwrap().as_path().to_str().unwrap()
             )),
             influxdb_enable: false,
+            user_provider: None,
         };
 
         let fe_opts = FrontendOptions::try_from(cmd).unwrap();
@@ -282,5 +308,10 @@ mod tests {
         assert_eq!("0.0.0.0:4002", fe_opts.mysql_options.as_ref().unwrap().addr);
         assert_eq!(2, fe_opts.mysql_options.as_ref().unwrap().runtime_size);
         assert!(fe_opts.influxdb_options.as_ref().unwrap().enable);
+        assert_eq!(
+            PgAuthMethod::Md5,
+            fe_opts.postgres_options.as_ref().unwrap().auth_method
+        );
+        assert!(fe_opts.user_provider.is_none());
     }
 }
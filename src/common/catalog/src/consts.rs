@@ -25,6 +25,8 @@ pub const MIN_USER_TABLE_ID: u32 = 1024;
 pub const SYSTEM_CATALOG_TABLE_ID: u32 = 0;
 /// scripts table id
 pub const SCRIPTS_TABLE_ID: u32 = 1;
+/// script_runs table id
+pub const SCRIPT_RUNS_TABLE_ID: u32 = 2;
 
 pub(crate) const CATALOG_KEY_PREFIX: &str = "__c";
 pub(crate) const SCHEMA_KEY_PREFIX: &str = "__s";
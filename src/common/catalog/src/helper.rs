@@ -61,10 +61,20 @@ pub fn build_catalog_prefix() -> String {
     format!("{}-", CATALOG_KEY_PREFIX)
 }
 
+/// Prefix of schema keys across all catalogs.
+pub fn build_schemas_prefix() -> String {
+    format!("{}-", SCHEMA_KEY_PREFIX)
+}
+
 pub fn build_schema_prefix(catalog_name: impl AsRef<str>) -> String {
     format!("{}-{}-", SCHEMA_KEY_PREFIX, catalog_name.as_ref())
 }
 
+/// Prefix of table global keys across all catalogs and schemas.
+pub fn build_tables_global_prefix() -> String {
+    format!("{}-", TABLE_GLOBAL_KEY_PREFIX)
+}
+
 pub fn build_table_global_prefix(
     catalog_name: impl AsRef<str>,
     schema_name: impl AsRef<str>,
@@ -312,7 +322,9 @@ mod tests {
     #[test]
     fn test_build_prefix() {
         assert_eq!("__c-", build_catalog_prefix());
+        assert_eq!("__s-", build_schemas_prefix());
         assert_eq!("__s-CATALOG-", build_schema_prefix("CATALOG"));
+        assert_eq!("__tg-", build_tables_global_prefix());
         assert_eq!(
             "__tg-CATALOG-SCHEMA-",
             build_table_global_prefix("CATALOG", "SCHEMA")
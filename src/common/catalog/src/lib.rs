@@ -17,7 +17,7 @@ pub mod error;
 mod helper;
 
 pub use helper::{
-    build_catalog_prefix, build_schema_prefix, build_table_global_prefix,
-    build_table_regional_prefix, CatalogKey, CatalogValue, SchemaKey, SchemaValue, TableGlobalKey,
-    TableGlobalValue, TableRegionalKey, TableRegionalValue,
+    build_catalog_prefix, build_schema_prefix, build_schemas_prefix, build_table_global_prefix,
+    build_table_regional_prefix, build_tables_global_prefix, CatalogKey, CatalogValue, SchemaKey,
+    SchemaValue, TableGlobalKey, TableGlobalValue, TableRegionalKey, TableRegionalValue,
 };
@@ -61,7 +61,26 @@ pub enum StatusCode {
     // ====== Begin of server related status code =====
     /// Runtime resources exhausted, like creating threads failed.
     RuntimeResourcesExhausted = 6000,
+    /// The request exceeds the quota of the user or the database.
+    QuotaExceeded = 6001,
+    /// The query is cancelled by `KILL`.
+    QueryCancelled = 6002,
     // ====== End of server related status code =======
+
+    // ====== Begin of auth related status code =====
+    /// User not exist.
+    UserNotFound = 7000,
+    /// Unsupported password type.
+    UnsupportedPasswordType = 7001,
+    /// Username and password does not match.
+    UserPasswordMismatch = 7002,
+    /// Authorization header not found in HTTP request.
+    AuthHeaderNotFound = 7003,
+    /// Invalid authorization header in HTTP request.
+    InvalidAuthHeader = 7004,
+    /// The user lacks the privilege required by the request.
+    AccessDenied = 7005,
+    // ====== End of auth related status code =====
 }
 
 impl StatusCode {
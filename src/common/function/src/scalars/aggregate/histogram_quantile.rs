@@ -0,0 +1,283 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{BadAccumulatorImplSnafu, InvalidFuncArgsSnafu, Result};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use datatypes::value::ListValue;
+use snafu::{ensure, OptionExt};
+
+use crate::scalars::aggregate::series::{constant_arg, list_state};
+
+/// Parses the upper bound of a bucket, which is either a number or a string like `"+Inf"`.
+fn parse_le(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.as_utf8().trim().parse().ok(),
+        v => v.as_f64_lossy(),
+    }
+}
+
+/// Estimates the `q` quantile from the buckets of a histogram, as `(upper bound, cumulative
+/// count)`, like `histogram_quantile` of Prometheus. The value is interpolated linearly in the
+/// bucket containing the quantile, assuming the lowest bucket starts from zero.
+///
+/// Returns `None` if the histogram has no `+Inf` bucket, less than two buckets or no observation.
+fn bucket_quantile(q: f64, buckets: &[(f64, f64)]) -> Option<f64> {
+    if q < 0.0 {
+        return Some(f64::NEG_INFINITY);
+    }
+    if q > 1.0 {
+        return Some(f64::INFINITY);
+    }
+
+    let mut sorted = buckets.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // Buckets with the same upper bound are merged, and the counts are forced to be monotonic as
+    // the scrapes of the buckets are not atomic.
+    let mut buckets: Vec<(f64, f64)> = Vec::with_capacity(sorted.len());
+    for (le, count) in sorted {
+        match buckets.last_mut() {
+            Some(last) if last.0 == le => last.1 += count,
+            _ => buckets.push((le, count)),
+        }
+    }
+    for i in 1..buckets.len() {
+        if buckets[i].1 < buckets[i - 1].1 {
+            buckets[i].1 = buckets[i - 1].1;
+        }
+    }
+
+    match buckets.last() {
+        Some((le, _)) if *le == f64::INFINITY => {}
+        _ => return None,
+    }
+    if buckets.len() < 2 {
+        return None;
+    }
+    let observations = buckets[buckets.len() - 1].1;
+    if observations == 0.0 {
+        return None;
+    }
+
+    let mut rank = q * observations;
+    let b = buckets
+        .iter()
+        .position(|(_, count)| *count >= rank)
+        .unwrap_or(buckets.len() - 1);
+    if b == buckets.len() - 1 {
+        return Some(buckets[buckets.len() - 2].0);
+    }
+    if b == 0 && buckets[0].0 <= 0.0 {
+        return Some(buckets[0].0);
+    }
+
+    let (mut start, end, mut count) = (0.0, buckets[b].0, buckets[b].1);
+    if b > 0 {
+        start = buckets[b - 1].0;
+        count -= buckets[b - 1].1;
+        rank -= buckets[b - 1].1;
+    }
+    Some(start + (end - start) * (rank / count))
+}
+
+// `histogram_quantile(le, count, q)` estimates the `q` quantile from the rows of the buckets of a
+// histogram, whose upper bound is `le` and cumulative count is `count`.
+#[derive(Debug, Default)]
+pub struct HistogramQuantile {
+    buckets: Vec<(f64, f64)>,
+    q: Option<f64>,
+}
+
+impl HistogramQuantile {
+    fn push_buckets(&mut self, les: &VectorRef, counts: &VectorRef) -> Result<()> {
+        ensure!(les.len() == counts.len(), InvalidInputStateSnafu);
+        for i in 0..les.len() {
+            let le = les.get(i);
+            if le.is_null() {
+                continue;
+            }
+            let le = parse_le(&le).with_context(|| InvalidFuncArgsSnafu {
+                err_msg: format!("invalid upper bound of bucket: {:?}", le),
+            })?;
+            if let Some(count) = counts.get(i).as_f64_lossy() {
+                self.buckets.push((le, count));
+            }
+        }
+        Ok(())
+    }
+
+    fn set_q(&mut self, q: &Value) -> Result<()> {
+        let q = q.as_f64_lossy().context(InvalidFuncArgsSnafu {
+            err_msg: "the quantile of `histogram_quantile` must be a number",
+        })?;
+        self.q = Some(q);
+        Ok(())
+    }
+}
+
+impl Accumulator for HistogramQuantile {
+    fn state(&self) -> Result<Vec<Value>> {
+        let les = self.buckets.iter().map(|(le, _)| (*le).into()).collect();
+        let counts = self.buckets.iter().map(|(_, c)| (*c).into()).collect();
+        Ok(vec![
+            Value::List(ListValue::new(
+                Some(Box::new(les)),
+                ConcreteDataType::float64_datatype(),
+            )),
+            Value::List(ListValue::new(
+                Some(Box::new(counts)),
+                ConcreteDataType::float64_datatype(),
+            )),
+            self.q.into(),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 3, InvalidInputStateSnafu);
+        if values[0].is_empty() {
+            return Ok(());
+        }
+        self.set_q(&constant_arg(&values[2])?)?;
+        self.push_buckets(&values[0], &values[1])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 3,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 3 states in `merge_batch`",
+            }
+        );
+        for (i, (les, counts)) in list_state(&states[0])?
+            .iter()
+            .zip(list_state(&states[1])?.iter())
+            .enumerate()
+        {
+            let q = states[2].get(i);
+            if q.is_null() {
+                continue;
+            }
+            self.set_q(&q)?;
+            self.push_buckets(les, counts)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        Ok(self
+            .q
+            .and_then(|q| bucket_quantile(q, &self.buckets))
+            .map(Value::from)
+            .unwrap_or(Value::Null))
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct HistogramQuantileAccumulatorCreator {}
+
+impl AggregateFunctionCreator for HistogramQuantileAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            ensure!(types.len() == 3, InvalidInputStateSnafu);
+            Ok(Box::new(HistogramQuantile::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 3, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 3, InvalidInputStateSnafu);
+        Ok(vec![
+            ConcreteDataType::list_datatype(ConcreteDataType::float64_datatype()),
+            ConcreteDataType::list_datatype(ConcreteDataType::float64_datatype()),
+            ConcreteDataType::float64_datatype(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::vectors::{ConstantVector, Float64Vector, StringVector};
+
+    use super::*;
+
+    #[test]
+    fn test_bucket_quantile() {
+        let buckets = [
+            (1.0, 10.0),
+            (5.0, 30.0),
+            (10.0, 40.0),
+            (f64::INFINITY, 40.0),
+        ];
+        assert_eq!(Some(3.0), bucket_quantile(0.5, &buckets));
+        assert_eq!(Some(0.5), bucket_quantile(0.125, &buckets));
+        assert_eq!(Some(10.0), bucket_quantile(1.0, &buckets));
+        assert_eq!(Some(f64::NEG_INFINITY), bucket_quantile(-1.0, &buckets));
+        assert_eq!(Some(f64::INFINITY), bucket_quantile(2.0, &buckets));
+
+        // The quantile falls in the +Inf bucket.
+        let buckets = [(0.1, 10.0), (f64::INFINITY, 20.0)];
+        assert_eq!(Some(0.1), bucket_quantile(0.9, &buckets));
+
+        // No +Inf bucket, or no observation.
+        assert_eq!(None, bucket_quantile(0.5, &[(0.1, 10.0), (0.5, 20.0)]));
+        assert_eq!(None, bucket_quantile(0.5, &[(f64::INFINITY, 0.0)]));
+        assert_eq!(
+            None,
+            bucket_quantile(0.5, &[(1.0, 0.0), (f64::INFINITY, 0.0)])
+        );
+    }
+
+    #[test]
+    fn test_update_batch() {
+        let mut histogram_quantile = HistogramQuantile::default();
+        assert!(histogram_quantile.update_batch(&[]).is_ok());
+        assert_eq!(Value::Null, histogram_quantile.evaluate().unwrap());
+
+        let v: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec!["+Inf", "5", "1", "10"])),
+            Arc::new(Float64Vector::from_vec(vec![40.0, 30.0, 10.0, 40.0])),
+            Arc::new(ConstantVector::new(
+                Arc::new(Float64Vector::from_vec(vec![0.5])),
+                4,
+            )),
+        ];
+        histogram_quantile.update_batch(&v).unwrap();
+        assert_eq!(Value::from(3.0), histogram_quantile.evaluate().unwrap());
+
+        let v: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec!["foo"])),
+            Arc::new(Float64Vector::from_vec(vec![1.0])),
+            Arc::new(Float64Vector::from_vec(vec![0.5])),
+        ];
+        assert!(histogram_quantile.update_batch(&v).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::Result;
+use common_query::logical_plan::AggregateFunctionCreator;
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::aggregate::series::{
+    check_series_types, counter_increase, Series, SeriesAccumulator,
+};
+
+// The per-second instant rate of increase of a counter, computed from the last two points, like
+// `irate` of Prometheus.
+fn irate(points: &[(i64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let last = &points[points.len() - 2..];
+    let seconds = (last[1].0 - last[0].0) as f64 / 1000.0;
+    if seconds <= 0.0 {
+        return None;
+    }
+    Some(counter_increase(last) / seconds)
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct IrateAccumulatorCreator {}
+
+impl AggregateFunctionCreator for IrateAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            check_series_types("IRATE", types)?;
+            Ok(Box::new(SeriesAccumulator::new(irate)))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(Series::state_types())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_irate() {
+        assert_eq!(
+            Some(2.0),
+            irate(&[(0, 1.0), (10_000, 11.0), (20_000, 31.0)])
+        );
+        assert_eq!(
+            Some(1.0),
+            irate(&[(0, 10.0), (10_000, 20.0), (15_000, 5.0)])
+        );
+        assert_eq!(None, irate(&[(0, 1.0)]));
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{CreateAccumulatorSnafu, Result};
+use common_query::logical_plan::AggregateFunctionCreator;
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::aggregate::series::ValueByTimeAccumulator;
+
+// `last_value(value, ts)` returns the value with the highest timestamp.
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct LastValueAccumulatorCreator {}
+
+impl AggregateFunctionCreator for LastValueAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            ensure!(types.len() == 2, InvalidInputStateSnafu);
+            let ts_type = &types[1];
+            ensure!(
+                ts_type.is_timestamp() || ts_type.is_signed() || ts_type.is_unsigned(),
+                CreateAccumulatorSnafu {
+                    err_msg: format!(
+                        "\"LAST_VALUE\" aggregate function expects a timestamp, got data type {:?}",
+                        ts_type.logical_type_id(),
+                    ),
+                }
+            );
+            Ok(Box::new(ValueByTimeAccumulator::new(true)))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(input_types.into_iter().next().unwrap())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(vec![
+            input_types.into_iter().next().unwrap(),
+            ConcreteDataType::int64_datatype(),
+        ])
+    }
+}
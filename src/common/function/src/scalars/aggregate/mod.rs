@@ -12,26 +12,45 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod approx_count_distinct;
 mod argmax;
 mod argmin;
+mod delta;
 mod diff;
+mod first_value;
+mod histogram_quantile;
+mod increase;
+mod irate;
+mod last_value;
 mod mean;
 mod median;
+mod moving_average;
 mod percentile;
 mod polyval;
+mod rate;
 mod scipy_stats_norm_cdf;
 mod scipy_stats_norm_pdf;
+mod series;
 
 use std::sync::Arc;
 
+pub use approx_count_distinct::ApproxCountDistinctAccumulatorCreator;
 pub use argmax::ArgmaxAccumulatorCreator;
 pub use argmin::ArgminAccumulatorCreator;
 use common_query::logical_plan::AggregateFunctionCreatorRef;
+pub use delta::DeltaAccumulatorCreator;
 pub use diff::DiffAccumulatorCreator;
+pub use first_value::FirstValueAccumulatorCreator;
+pub use histogram_quantile::HistogramQuantileAccumulatorCreator;
+pub use increase::IncreaseAccumulatorCreator;
+pub use irate::IrateAccumulatorCreator;
+pub use last_value::LastValueAccumulatorCreator;
 pub use mean::MeanAccumulatorCreator;
 pub use median::MedianAccumulatorCreator;
+pub use moving_average::MovingAverageAccumulatorCreator;
 pub use percentile::PercentileAccumulatorCreator;
 pub use polyval::PolyvalAccumulatorCreator;
+pub use rate::RateAccumulatorCreator;
 pub use scipy_stats_norm_cdf::ScipyStatsNormCdfAccumulatorCreator;
 pub use scipy_stats_norm_pdf::ScipyStatsNormPdfAccumulatorCreator;
 
@@ -97,5 +116,18 @@ impl AggregateFunctions {
         register_aggr_func!("percentile", 2, PercentileAccumulatorCreator);
         register_aggr_func!("scipystatsnormcdf", 2, ScipyStatsNormCdfAccumulatorCreator);
         register_aggr_func!("scipystatsnormpdf", 2, ScipyStatsNormPdfAccumulatorCreator);
+        register_aggr_func!("rate", 2, RateAccumulatorCreator);
+        register_aggr_func!("irate", 2, IrateAccumulatorCreator);
+        register_aggr_func!("delta", 2, DeltaAccumulatorCreator);
+        register_aggr_func!("increase", 2, IncreaseAccumulatorCreator);
+        register_aggr_func!("first_value", 2, FirstValueAccumulatorCreator);
+        register_aggr_func!("last_value", 2, LastValueAccumulatorCreator);
+        register_aggr_func!("moving_average", 3, MovingAverageAccumulatorCreator);
+        register_aggr_func!("histogram_quantile", 3, HistogramQuantileAccumulatorCreator);
+        register_aggr_func!(
+            "approx_count_distinct",
+            1,
+            ApproxCountDistinctAccumulatorCreator
+        );
     }
 }
@@ -0,0 +1,245 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::{BadAccumulatorImplSnafu, InvalidFuncArgsSnafu, Result};
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreator};
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use datatypes::value::ListValue;
+use snafu::{ensure, OptionExt};
+
+use crate::scalars::aggregate::series::{check_series_types, constant_arg, Series};
+
+// `moving_average(value, ts, window)` returns the averages of each `window` consecutive values
+// ordered by `ts`, like `numpy.convolve(values, numpy.ones(window) / window, "valid")`. The rows
+// whose value or timestamp is null are ignored.
+#[derive(Debug, Default)]
+pub struct MovingAverage {
+    series: Series,
+    window: Option<u64>,
+}
+
+impl MovingAverage {
+    fn set_window(&mut self, window: &Value) -> Result<()> {
+        let window = match window {
+            Value::Int8(_)
+            | Value::Int16(_)
+            | Value::Int32(_)
+            | Value::Int64(_)
+            | Value::UInt8(_)
+            | Value::UInt16(_)
+            | Value::UInt32(_)
+            | Value::UInt64(_) => window.as_f64_lossy().map(|v| v as i64),
+            _ => None,
+        }
+        .context(InvalidFuncArgsSnafu {
+            err_msg: "the window of `moving_average` must be an integer",
+        })?;
+        ensure!(
+            window > 0,
+            InvalidFuncArgsSnafu {
+                err_msg: "the window of `moving_average` must be positive",
+            }
+        );
+        let window = window as u64;
+        if let Some(current) = self.window {
+            ensure!(
+                current == window,
+                BadAccumulatorImplSnafu {
+                    err_msg: "the window of `moving_average` must be a constant",
+                }
+            );
+        }
+        self.window = Some(window);
+        Ok(())
+    }
+}
+
+impl Accumulator for MovingAverage {
+    fn state(&self) -> Result<Vec<Value>> {
+        let mut state = self.series.state();
+        state.push(self.window.into());
+        Ok(state)
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 3, InvalidInputStateSnafu);
+        if values[0].is_empty() {
+            return Ok(());
+        }
+        self.set_window(&constant_arg(&values[2])?)?;
+        self.series.update(&values[0], &values[1])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 3,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 3 states in `merge_batch`",
+            }
+        );
+        for i in 0..states[2].len() {
+            let window = states[2].get(i);
+            if !window.is_null() {
+                self.set_window(&window)?;
+            }
+        }
+        self.series.merge(&states[..2])
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        let window = match self.window {
+            Some(window) => window as usize,
+            None => return Ok(Value::Null),
+        };
+        let values = self
+            .series
+            .sorted()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        let averages = values
+            .windows(window)
+            .map(|w| Value::from(w.iter().sum::<f64>() / window as f64))
+            .collect::<Vec<Value>>();
+        Ok(Value::List(ListValue::new(
+            Some(Box::new(averages)),
+            ConcreteDataType::float64_datatype(),
+        )))
+    }
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct MovingAverageAccumulatorCreator {}
+
+impl AggregateFunctionCreator for MovingAverageAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            ensure!(types.len() == 3, InvalidInputStateSnafu);
+            check_series_types("MOVING_AVERAGE", &types[..2])?;
+            Ok(Box::new(MovingAverage::default()))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 3, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::list_datatype(
+            ConcreteDataType::float64_datatype(),
+        ))
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 3, InvalidInputStateSnafu);
+        let mut state_types = Series::state_types();
+        state_types.push(ConcreteDataType::uint64_datatype());
+        Ok(state_types)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datatypes::vectors::{ConstantVector, Float64Vector, Int64Vector};
+
+    use super::*;
+
+    fn list(values: Vec<f64>) -> Value {
+        Value::List(ListValue::new(
+            Some(Box::new(values.into_iter().map(Value::from).collect())),
+            ConcreteDataType::float64_datatype(),
+        ))
+    }
+
+    #[test]
+    fn test_update_batch() {
+        let mut moving_average = MovingAverage::default();
+        assert!(moving_average.update_batch(&[]).is_ok());
+        assert_eq!(Value::Null, moving_average.evaluate().unwrap());
+
+        let window: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(Int64Vector::from_vec(vec![2])),
+            4,
+        ));
+        // The values are averaged in the order of their timestamps, not of the rows.
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from(vec![
+                Some(7.0),
+                Some(3.0),
+                None,
+                Some(1.0),
+            ])),
+            Arc::new(Int64Vector::from_vec(vec![4, 2, 3, 1])),
+            window.clone(),
+        ];
+        moving_average.update_batch(&v).unwrap();
+        assert_eq!(list(vec![2.0, 5.0]), moving_average.evaluate().unwrap());
+
+        // Merges the states of the accumulator into another one, like the final aggregation.
+        let states: Vec<VectorRef> = moving_average
+            .state()
+            .unwrap()
+            .iter()
+            .zip(
+                Series::state_types()
+                    .into_iter()
+                    .chain([ConcreteDataType::uint64_datatype()]),
+            )
+            .map(|(value, data_type)| {
+                let mut builder = data_type.create_mutable_vector(1);
+                builder.push_value_ref(value.as_value_ref()).unwrap();
+                builder.to_vector()
+            })
+            .collect();
+        let mut merged = MovingAverage::default();
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from_vec(vec![5.0])),
+            Arc::new(Int64Vector::from_vec(vec![0])),
+            window.clone(),
+        ];
+        merged.update_batch(&v).unwrap();
+        merged.merge_batch(&states).unwrap();
+        assert_eq!(list(vec![3.0, 2.0, 5.0]), merged.evaluate().unwrap());
+
+        // The window is larger than the values.
+        let mut moving_average = MovingAverage::default();
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from_vec(vec![1.0])),
+            Arc::new(Int64Vector::from_vec(vec![1])),
+            window,
+        ];
+        moving_average.update_batch(&v).unwrap();
+        assert_eq!(list(vec![]), moving_average.evaluate().unwrap());
+
+        // The window must be positive.
+        let mut moving_average = MovingAverage::default();
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from_vec(vec![1.0])),
+            Arc::new(Int64Vector::from_vec(vec![1])),
+            Arc::new(Int64Vector::from_vec(vec![0])),
+        ];
+        assert!(moving_average.update_batch(&v).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_function_macro::{as_aggr_func_creator, AggrFuncTypeStore};
+use common_query::error::Result;
+use common_query::logical_plan::AggregateFunctionCreator;
+use common_query::prelude::*;
+use datatypes::prelude::*;
+use snafu::ensure;
+
+use crate::scalars::aggregate::series::{
+    check_series_types, counter_increase, Series, SeriesAccumulator,
+};
+
+// The per-second average rate of increase of a counter, like `rate` of Prometheus. A value lower
+// than the previous one means the counter was reset, the increase is then the value itself.
+fn rate(points: &[(i64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let seconds = (points[points.len() - 1].0 - points[0].0) as f64 / 1000.0;
+    if seconds <= 0.0 {
+        return None;
+    }
+    Some(counter_increase(points) / seconds)
+}
+
+#[as_aggr_func_creator]
+#[derive(Debug, Default, AggrFuncTypeStore)]
+pub struct RateAccumulatorCreator {}
+
+impl AggregateFunctionCreator for RateAccumulatorCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            check_series_types("RATE", types)?;
+            Ok(Box::new(SeriesAccumulator::new(rate)))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> Result<ConcreteDataType> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(ConcreteDataType::float64_datatype())
+    }
+
+    fn state_types(&self) -> Result<Vec<ConcreteDataType>> {
+        let input_types = self.input_types()?;
+        ensure!(input_types.len() == 2, InvalidInputStateSnafu);
+        Ok(Series::state_types())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(Some(1.5), rate(&[(0, 1.0), (10_000, 11.0), (20_000, 31.0)]));
+        assert_eq!(
+            Some(0.75),
+            rate(&[(0, 10.0), (10_000, 20.0), (20_000, 5.0)])
+        );
+        assert_eq!(None, rate(&[(0, 1.0)]));
+        assert_eq!(None, rate(&[(0, 1.0), (0, 2.0)]));
+    }
+}
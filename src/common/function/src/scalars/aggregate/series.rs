@@ -0,0 +1,394 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared pieces of the time series aggregates, whose inputs are a value and a timestamp.
+
+use common_query::error::{
+    BadAccumulatorImplSnafu, CreateAccumulatorSnafu, DowncastVectorSnafu, FromScalarValueSnafu,
+    InvalidInputStateSnafu, Result,
+};
+use common_query::logical_plan::Accumulator;
+use common_time::timestamp::TimeUnit;
+use datatypes::prelude::*;
+use datatypes::value::ListValue;
+use datatypes::vectors::ListVector;
+use snafu::{ensure, OptionExt, ResultExt};
+
+/// Converts a timestamp or an integer to milliseconds, `None` for nulls and other types.
+pub(crate) fn value_to_millis(value: &Value) -> Option<i64> {
+    match value {
+        Value::Timestamp(ts) => Some(ts.convert_to(TimeUnit::Millisecond)),
+        Value::DateTime(dt) => Some(dt.val() * 1000),
+        Value::UInt8(_)
+        | Value::UInt16(_)
+        | Value::UInt32(_)
+        | Value::UInt64(_)
+        | Value::Int8(_)
+        | Value::Int16(_)
+        | Value::Int32(_)
+        | Value::Int64(_) => value.as_f64_lossy().map(|v| v as i64),
+        _ => None,
+    }
+}
+
+/// Checks the input types of an aggregate taking a numeric value and its timestamp.
+pub(crate) fn check_series_types(name: &str, types: &[ConcreteDataType]) -> Result<()> {
+    ensure!(types.len() == 2, InvalidInputStateSnafu);
+    let (value_type, ts_type) = (&types[0], &types[1]);
+    ensure!(
+        value_type.is_float() || value_type.is_signed() || value_type.is_unsigned(),
+        CreateAccumulatorSnafu {
+            err_msg: format!(
+                "\"{}\" aggregate function not support data type {:?}",
+                name,
+                value_type.logical_type_id(),
+            ),
+        }
+    );
+    ensure!(
+        ts_type.is_timestamp() || ts_type.is_signed() || ts_type.is_unsigned(),
+        CreateAccumulatorSnafu {
+            err_msg: format!(
+                "\"{}\" aggregate function expects a timestamp, got data type {:?}",
+                name,
+                ts_type.logical_type_id(),
+            ),
+        }
+    );
+    Ok(())
+}
+
+/// Returns the only value of a constant argument, like the quantile of `histogram_quantile`.
+pub(crate) fn constant_arg(column: &VectorRef) -> Result<Value> {
+    ensure!(!column.is_empty(), InvalidInputStateSnafu);
+    let value = column.get(0);
+    ensure!(!value.is_null(), InvalidInputStateSnafu);
+    Ok(value)
+}
+
+/// Iterates the lists of a `ListVector` state.
+pub(crate) fn list_state(state: &VectorRef) -> Result<Vec<VectorRef>> {
+    let lists = state
+        .as_any()
+        .downcast_ref::<ListVector>()
+        .with_context(|| DowncastVectorSnafu {
+            err_msg: format!(
+                "expect ListVector, got vector type {}",
+                state.vector_type_name()
+            ),
+        })?;
+    lists
+        .values_iter()
+        .map(|list| list.context(FromScalarValueSnafu))
+        .collect()
+}
+
+/// Points of a time series as `(timestamp in milliseconds, value)`, the rows whose value or
+/// timestamp is null are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct Series {
+    points: Vec<(i64, f64)>,
+}
+
+impl Series {
+    pub(crate) fn update(&mut self, values: &VectorRef, timestamps: &VectorRef) -> Result<()> {
+        ensure!(values.len() == timestamps.len(), InvalidInputStateSnafu);
+        for i in 0..values.len() {
+            if let (Some(v), Some(ts)) = (
+                values.get(i).as_f64_lossy(),
+                value_to_millis(&timestamps.get(i)),
+            ) {
+                self.points.push((ts, v));
+            }
+        }
+        Ok(())
+    }
+
+    /// The states are the list of the timestamps and the list of the values.
+    pub(crate) fn state_types() -> Vec<ConcreteDataType> {
+        vec![
+            ConcreteDataType::list_datatype(ConcreteDataType::int64_datatype()),
+            ConcreteDataType::list_datatype(ConcreteDataType::float64_datatype()),
+        ]
+    }
+
+    pub(crate) fn state(&self) -> Vec<Value> {
+        let timestamps = self.points.iter().map(|(ts, _)| Value::from(*ts)).collect();
+        let values = self.points.iter().map(|(_, v)| Value::from(*v)).collect();
+        vec![
+            Value::List(ListValue::new(
+                Some(Box::new(timestamps)),
+                ConcreteDataType::int64_datatype(),
+            )),
+            Value::List(ListValue::new(
+                Some(Box::new(values)),
+                ConcreteDataType::float64_datatype(),
+            )),
+        ]
+    }
+
+    pub(crate) fn merge(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 2,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 2 states in `merge_batch`",
+            }
+        );
+        for (timestamps, values) in list_state(&states[0])?
+            .into_iter()
+            .zip(list_state(&states[1])?)
+        {
+            self.update(&values, &timestamps)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the points sorted by time.
+    pub(crate) fn sorted(&self) -> Vec<(i64, f64)> {
+        let mut points = self.points.clone();
+        points.sort_by_key(|(ts, _)| *ts);
+        points
+    }
+}
+
+/// An accumulator that evaluates a function over the points of a series sorted by time, the
+/// result is null if the function returns `None`.
+#[derive(Debug)]
+pub(crate) struct SeriesAccumulator {
+    series: Series,
+    evaluate: fn(&[(i64, f64)]) -> Option<f64>,
+}
+
+impl SeriesAccumulator {
+    pub(crate) fn new(evaluate: fn(&[(i64, f64)]) -> Option<f64>) -> Self {
+        Self {
+            series: Series::default(),
+            evaluate,
+        }
+    }
+}
+
+impl Accumulator for SeriesAccumulator {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(self.series.state())
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 2, InvalidInputStateSnafu);
+        self.series.update(&values[0], &values[1])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        self.series.merge(states)
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        let points = self.series.sorted();
+        Ok((self.evaluate)(&points)
+            .map(Value::from)
+            .unwrap_or(Value::Null))
+    }
+}
+
+/// The increase of a counter over the points, a value lower than the previous one means the
+/// counter was reset and counts from zero again.
+pub(crate) fn counter_increase(points: &[(i64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| {
+            let (prev, cur) = (w[0].1, w[1].1);
+            if cur < prev {
+                cur
+            } else {
+                cur - prev
+            }
+        })
+        .sum()
+}
+
+/// An accumulator keeping the value with the lowest or the highest timestamp, the rows whose value
+/// or timestamp is null are ignored. The states are the value and its timestamp.
+#[derive(Debug)]
+pub(crate) struct ValueByTimeAccumulator {
+    latest: bool,
+    point: Option<(i64, Value)>,
+}
+
+impl ValueByTimeAccumulator {
+    /// Creates an accumulator keeping the value with the highest timestamp if `latest`, the
+    /// lowest otherwise. The earliest row wins a tie.
+    pub(crate) fn new(latest: bool) -> Self {
+        Self {
+            latest,
+            point: None,
+        }
+    }
+
+    fn update(&mut self, values: &VectorRef, timestamps: &VectorRef) -> Result<()> {
+        ensure!(values.len() == timestamps.len(), InvalidInputStateSnafu);
+        for i in 0..values.len() {
+            let value = values.get(i);
+            if value.is_null() {
+                continue;
+            }
+            if let Some(ts) = value_to_millis(&timestamps.get(i)) {
+                let replace = match &self.point {
+                    Some((current, _)) if self.latest => ts > *current,
+                    Some((current, _)) => ts < *current,
+                    None => true,
+                };
+                if replace {
+                    self.point = Some((ts, value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ValueByTimeAccumulator {
+    fn state(&self) -> Result<Vec<Value>> {
+        Ok(match &self.point {
+            Some((ts, value)) => vec![value.clone(), Value::from(*ts)],
+            None => vec![Value::Null, Value::Null],
+        })
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == 2, InvalidInputStateSnafu);
+        self.update(&values[0], &values[1])
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == 2,
+            BadAccumulatorImplSnafu {
+                err_msg: "expect 2 states in `merge_batch`",
+            }
+        );
+        self.update(&states[0], &states[1])
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        Ok(self
+            .point
+            .as_ref()
+            .map(|(_, value)| value.clone())
+            .unwrap_or(Value::Null))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use datatypes::vectors::{Float64Vector, Int64Vector};
+
+    use super::*;
+
+    fn sum(points: &[(i64, f64)]) -> Option<f64> {
+        if points.is_empty() {
+            None
+        } else {
+            Some(points.iter().map(|(_, v)| v).sum())
+        }
+    }
+
+    #[test]
+    fn test_update_and_merge() {
+        let mut acc = SeriesAccumulator::new(sum);
+        assert!(acc.update_batch(&[]).is_ok());
+        assert_eq!(Value::Null, acc.evaluate().unwrap());
+
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from(vec![Some(1.0), None, Some(3.0)])),
+            Arc::new(Int64Vector::from(vec![Some(3), Some(2), Some(1)])),
+        ];
+        acc.update_batch(&v).unwrap();
+        assert_eq!(vec![(1, 3.0), (3, 1.0)], acc.series.sorted());
+
+        // Merges the states of the accumulator into another one, like the final aggregation.
+        let states: Vec<VectorRef> = acc
+            .state()
+            .unwrap()
+            .iter()
+            .zip(Series::state_types())
+            .map(|(value, data_type)| {
+                let mut builder = data_type.create_mutable_vector(1);
+                builder.push_value_ref(value.as_value_ref()).unwrap();
+                builder.to_vector()
+            })
+            .collect();
+        let mut merged = SeriesAccumulator::new(sum);
+        merged.update_batch(&v).unwrap();
+        merged.merge_batch(&states).unwrap();
+        assert_eq!(Value::from(8.0), merged.evaluate().unwrap());
+    }
+
+    #[test]
+    fn test_counter_increase() {
+        assert_eq!(0.0, counter_increase(&[]));
+        assert_eq!(4.0, counter_increase(&[(0, 1.0), (1, 3.0), (2, 5.0)]));
+        // The counter is reset at the third point.
+        assert_eq!(6.0, counter_increase(&[(0, 1.0), (1, 5.0), (2, 2.0)]));
+    }
+
+    #[test]
+    fn test_value_by_time() {
+        let v: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from(vec![
+                Some(1.0),
+                Some(2.0),
+                None,
+                Some(4.0),
+            ])),
+            Arc::new(Int64Vector::from(vec![
+                Some(20),
+                Some(10),
+                Some(0),
+                Some(30),
+            ])),
+        ];
+        let mut first = ValueByTimeAccumulator::new(false);
+        first.update_batch(&v).unwrap();
+        assert_eq!(Value::from(2.0), first.evaluate().unwrap());
+        assert_eq!(
+            vec![Value::from(2.0), Value::from(10i64)],
+            first.state().unwrap()
+        );
+
+        let mut last = ValueByTimeAccumulator::new(true);
+        last.update_batch(&v).unwrap();
+        assert_eq!(Value::from(4.0), last.evaluate().unwrap());
+
+        let states: Vec<VectorRef> = vec![
+            Arc::new(Float64Vector::from(vec![Some(5.0), None])),
+            Arc::new(Int64Vector::from(vec![Some(5), None])),
+        ];
+        first.merge_batch(&states).unwrap();
+        assert_eq!(Value::from(5.0), first.evaluate().unwrap());
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! date_bin and time_bucket functions.
+use std::fmt;
+
+use common_query::error::InvalidFuncArgsSnafu;
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use common_time::timestamp::TimeUnit;
+use datatypes::prelude::{ConcreteDataType, Value, VectorBuilder};
+use datatypes::vectors::VectorRef;
+use snafu::{ensure, OptionExt};
+
+use crate::error::Result;
+use crate::scalars::function::{Function, FunctionContext};
+
+/// Parses a duration like `5m` or `1h30m` into milliseconds.
+fn parse_duration_millis(s: &str) -> Option<i64> {
+    let mut millis: i64 = 0;
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value = rest[..digits].parse::<i64>().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            "d" => 24 * 60 * 60 * 1000,
+            "w" => 7 * 24 * 60 * 60 * 1000,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        millis = millis.checked_add(value.checked_mul(factor)?)?;
+    }
+    Some(millis)
+}
+
+/// Returns the stride in milliseconds, from a number of milliseconds or a duration string.
+fn stride_millis(value: &Value) -> Result<Option<i64>> {
+    let stride = match value {
+        Value::Null => return Ok(None),
+        Value::Int64(v) => Some(*v),
+        Value::String(s) => parse_duration_millis(s.as_utf8()),
+        _ => None,
+    };
+    let stride = stride.with_context(|| InvalidFuncArgsSnafu {
+        err_msg: format!("invalid stride: {:?}", value),
+    })?;
+    ensure!(
+        stride > 0,
+        InvalidFuncArgsSnafu {
+            err_msg: format!("stride must be positive, got {}ms", stride),
+        }
+    );
+    Ok(Some(stride))
+}
+
+fn timestamp_millis(value: &Value) -> Option<i64> {
+    match value {
+        Value::Timestamp(ts) => Some(ts.convert_to(TimeUnit::Millisecond)),
+        Value::Int64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Accepts `(stride, ts)` and `(stride, ts, origin)`, where the stride is a number of
+/// milliseconds or a duration string and the timestamps are timestamps or milliseconds.
+fn date_bin_signature() -> Signature {
+    let strides = [
+        ConcreteDataType::int64_datatype(),
+        ConcreteDataType::string_datatype(),
+    ];
+    let timestamps = [
+        ConcreteDataType::timestamp_millis_datatype(),
+        ConcreteDataType::int64_datatype(),
+    ];
+    let mut signatures = Vec::new();
+    for stride in &strides {
+        for ts in &timestamps {
+            signatures.push(TypeSignature::Exact(vec![stride.clone(), ts.clone()]));
+            for origin in &timestamps {
+                signatures.push(TypeSignature::Exact(vec![
+                    stride.clone(),
+                    ts.clone(),
+                    origin.clone(),
+                ]));
+            }
+        }
+    }
+    Signature::one_of(signatures, Volatility::Immutable)
+}
+
+/// Truncates the timestamps into the bins of `stride` aligned to `origin`, which is the unix
+/// epoch by default. The timestamps before the origin fall into the bins before it.
+fn date_bin(columns: &[VectorRef]) -> Result<VectorRef> {
+    ensure!(
+        columns.len() == 2 || columns.len() == 3,
+        InvalidFuncArgsSnafu {
+            err_msg: format!("expect 2 or 3 args, got {}", columns.len()),
+        }
+    );
+    let (strides, timestamps) = (&columns[0], &columns[1]);
+    let mut builder = VectorBuilder::with_capacity(
+        ConcreteDataType::timestamp_millis_datatype(),
+        timestamps.len(),
+    );
+    for i in 0..timestamps.len() {
+        let origin = match columns.get(2) {
+            Some(origins) => timestamp_millis(&origins.get(i)),
+            None => Some(0),
+        };
+        match (
+            stride_millis(&strides.get(i))?,
+            timestamp_millis(&timestamps.get(i)),
+            origin,
+        ) {
+            (Some(stride), Some(ts), Some(origin)) => {
+                let bin = origin + (ts - origin).div_euclid(stride) * stride;
+                builder.push(&Value::Timestamp(bin.into()));
+            }
+            _ => builder.push_null(),
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// `date_bin(stride, ts[, origin])` returns the start of the bin containing `ts`, like
+/// `date_bin` of PostgreSQL.
+#[derive(Clone, Debug, Default)]
+pub struct DateBinFunction;
+
+impl Function for DateBinFunction {
+    fn name(&self) -> &str {
+        "date_bin"
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::timestamp_millis_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        date_bin_signature()
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        date_bin(columns)
+    }
+}
+
+impl fmt::Display for DateBinFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DATE_BIN")
+    }
+}
+
+/// `time_bucket(stride, ts[, origin])`, an alias of `date_bin` known from TimescaleDB.
+#[derive(Clone, Debug, Default)]
+pub struct TimeBucketFunction;
+
+impl Function for TimeBucketFunction {
+    fn name(&self) -> &str {
+        "time_bucket"
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::timestamp_millis_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        date_bin_signature()
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        date_bin(columns)
+    }
+}
+
+impl fmt::Display for TimeBucketFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TIME_BUCKET")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_time::Timestamp;
+    use datatypes::vectors::{ConstantVector, Int64Vector, StringVector, TimestampVector};
+
+    use super::*;
+
+    fn timestamps(values: Vec<Option<i64>>) -> Vec<Value> {
+        values
+            .into_iter()
+            .map(|v| {
+                v.map(|v| Value::Timestamp(Timestamp::from_millis(v)))
+                    .unwrap_or(Value::Null)
+            })
+            .collect()
+    }
+
+    fn eval(f: &dyn Function, args: &[VectorRef]) -> Result<Vec<Value>> {
+        let vector = f.eval(FunctionContext::default(), args)?;
+        Ok((0..vector.len()).map(|i| vector.get(i)).collect())
+    }
+
+    #[test]
+    fn test_date_bin() {
+        let f = DateBinFunction::default();
+        assert_eq!("date_bin", f.name());
+        assert_eq!(
+            ConcreteDataType::timestamp_millis_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+        assert!(matches!(f.signature(),
+                         Signature {
+                             type_signature: TypeSignature::OneOf(signatures),
+                             volatility: Volatility::Immutable
+                         } if signatures.len() == 12
+        ));
+
+        let ts: VectorRef = Arc::new(Int64Vector::from(vec![
+            Some(0),
+            Some(299_999),
+            Some(300_000),
+            None,
+            Some(-1),
+        ]));
+        let stride: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec!["5m"])),
+            5,
+        ));
+        assert_eq!(
+            timestamps(vec![Some(0), Some(0), Some(300_000), None, Some(-300_000)]),
+            eval(&f, &[stride.clone(), ts.clone()]).unwrap()
+        );
+
+        // With an origin.
+        let origin: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(Int64Vector::from_vec(vec![60_000])),
+            5,
+        ));
+        assert_eq!(
+            timestamps(vec![
+                Some(-240_000),
+                Some(60_000),
+                Some(60_000),
+                None,
+                Some(-240_000)
+            ]),
+            eval(&f, &[stride, ts.clone(), origin]).unwrap()
+        );
+
+        // The stride in milliseconds.
+        let stride: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(Int64Vector::from_vec(vec![100_000])),
+            5,
+        ));
+        assert_eq!(
+            timestamps(vec![
+                Some(0),
+                Some(200_000),
+                Some(300_000),
+                None,
+                Some(-100_000)
+            ]),
+            eval(&TimeBucketFunction::default(), &[stride, ts.clone()]).unwrap()
+        );
+
+        // The timestamps of a timestamp column.
+        let stride: VectorRef = Arc::new(Int64Vector::from_vec(vec![60_000]));
+        let ts_column: VectorRef = Arc::new(TimestampVector::from_values(vec![90_000]));
+        assert_eq!(
+            timestamps(vec![Some(60_000)]),
+            eval(&f, &[stride, ts_column]).unwrap()
+        );
+
+        for stride in ["5x", "0s", ""] {
+            let stride: VectorRef = Arc::new(ConstantVector::new(
+                Arc::new(StringVector::from(vec![stride])),
+                5,
+            ));
+            assert!(eval(&f, &[stride, ts.clone()]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_millis() {
+        assert_eq!(Some(300_000), parse_duration_millis("5m"));
+        assert_eq!(Some(5_400_000), parse_duration_millis("1h30m"));
+        assert_eq!(Some(10), parse_duration_millis("10ms"));
+        assert_eq!(None, parse_duration_millis("1y"));
+        assert_eq!(None, parse_duration_millis("m"));
+    }
+}
@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use std::sync::Arc;
+mod date_bin;
 mod from_unixtime;
 
+use date_bin::{DateBinFunction, TimeBucketFunction};
 use from_unixtime::FromUnixtimeFunction;
 
 use crate::scalars::function_registry::FunctionRegistry;
@@ -24,5 +26,7 @@ pub(crate) struct TimestampFunction;
 impl TimestampFunction {
     pub fn register(registry: &FunctionRegistry) {
         registry.register(Arc::new(FromUnixtimeFunction::default()));
+        registry.register(Arc::new(DateBinFunction::default()));
+        registry.register(Arc::new(TimeBucketFunction::default()));
     }
 }
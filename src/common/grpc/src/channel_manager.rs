@@ -19,17 +19,19 @@ use std::time::Duration;
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use snafu::ResultExt;
-use tonic::transport::{Channel as InnerChannel, Endpoint, Uri};
+use tonic::transport::{Channel as InnerChannel, ClientTlsConfig, Endpoint, Uri};
 use tower::make::MakeConnection;
 
 use crate::error;
 use crate::error::Result;
+use crate::tls::ClientTlsOption;
 
 const RECYCLE_CHANNEL_INTERVAL_SECS: u64 = 60;
 
 #[derive(Clone, Debug)]
 pub struct ChannelManager {
     config: ChannelConfig,
+    client_tls_config: Option<ClientTlsConfig>,
     pool: Arc<Pool>,
 }
 
@@ -52,7 +54,24 @@ impl ChannelManager {
             recycle_channel_in_loop(cloned_pool, RECYCLE_CHANNEL_INTERVAL_SECS).await;
         });
 
-        Self { config, pool }
+        Self {
+            config,
+            client_tls_config: None,
+            pool,
+        }
+    }
+
+    /// Creates a channel manager whose channels are secured by TLS if `client_tls` of the
+    /// config is set.
+    pub fn with_tls_config(config: ChannelConfig) -> Result<Self> {
+        let client_tls_config = config
+            .client_tls
+            .as_ref()
+            .map(|option| option.setup())
+            .transpose()?;
+        let mut manager = Self::with_config(config);
+        manager.client_tls_config = client_tls_config;
+        Ok(manager)
     }
 
     pub fn config(&self) -> &ChannelConfig {
@@ -119,8 +138,19 @@ impl ChannelManager {
     }
 
     fn build_endpoint(&self, addr: &str) -> Result<Endpoint> {
+        let scheme = if self.client_tls_config.is_some() {
+            "https"
+        } else {
+            "http"
+        };
         let mut endpoint =
-            Endpoint::new(format!("http://{}", addr)).context(error::CreateChannelSnafu)?;
+            Endpoint::new(format!("{}://{}", scheme, addr)).context(error::CreateChannelSnafu)?;
+
+        if let Some(tls_config) = &self.client_tls_config {
+            endpoint = endpoint
+                .tls_config(tls_config.clone())
+                .context(error::CreateChannelSnafu)?;
+        }
 
         if let Some(dur) = self.config.timeout {
             endpoint = endpoint.timeout(dur);
@@ -174,6 +204,7 @@ pub struct ChannelConfig {
     pub http2_adaptive_window: Option<bool>,
     pub tcp_keepalive: Option<Duration>,
     pub tcp_nodelay: bool,
+    pub client_tls: Option<ClientTlsOption>,
 }
 
 impl Default for ChannelConfig {
@@ -191,6 +222,7 @@ impl Default for ChannelConfig {
             http2_adaptive_window: None,
             tcp_keepalive: None,
             tcp_nodelay: true,
+            client_tls: None,
         }
     }
 }
@@ -307,6 +339,15 @@ impl ChannelConfig {
             ..self
         }
     }
+
+    /// Secures channels by TLS, only takes effect on channel managers created by
+    /// [ChannelManager::with_tls_config].
+    pub fn client_tls_config(self, client_tls: ClientTlsOption) -> Self {
+        Self {
+            client_tls: Some(client_tls),
+            ..self
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -401,7 +442,11 @@ mod tests {
     async fn test_access_count() {
         let pool = Arc::new(Pool::default());
         let config = ChannelConfig::new();
-        let mgr = Arc::new(ChannelManager { pool, config });
+        let mgr = Arc::new(ChannelManager {
+            pool,
+            config,
+            client_tls_config: None,
+        });
         let addr = "test_uri";
 
         let mut joins = Vec::with_capacity(10);
@@ -443,6 +488,7 @@ mod tests {
                 http2_adaptive_window: None,
                 tcp_keepalive: None,
                 tcp_nodelay: true,
+                client_tls: None,
             },
             default_cfg
         );
@@ -459,7 +505,11 @@ mod tests {
             .http2_keep_alive_while_idle(true)
             .http2_adaptive_window(true)
             .tcp_keepalive(Duration::from_secs(2))
-            .tcp_nodelay(false);
+            .tcp_nodelay(false)
+            .client_tls_config(ClientTlsOption {
+                server_ca_cert_path: "/path/to/ca.pem".to_string(),
+                ..Default::default()
+            });
 
         assert_eq!(
             ChannelConfig {
@@ -475,11 +525,28 @@ mod tests {
                 http2_adaptive_window: Some(true),
                 tcp_keepalive: Some(Duration::from_secs(2)),
                 tcp_nodelay: false,
+                client_tls: Some(ClientTlsOption {
+                    server_ca_cert_path: "/path/to/ca.pem".to_string(),
+                    ..Default::default()
+                }),
             },
             cfg
         );
     }
 
+    #[tokio::test]
+    async fn test_with_tls_config() {
+        let config = ChannelConfig::new();
+        let mgr = ChannelManager::with_tls_config(config).unwrap();
+        assert!(mgr.client_tls_config.is_none());
+
+        let config = ChannelConfig::new().client_tls_config(ClientTlsOption {
+            server_ca_cert_path: "/path/not/exist/ca.pem".to_string(),
+            ..Default::default()
+        });
+        assert!(ChannelManager::with_tls_config(config).is_err());
+    }
+
     #[test]
     fn test_build_endpoint() {
         let pool = Arc::new(Pool::default());
@@ -496,7 +563,11 @@ mod tests {
             .http2_adaptive_window(true)
             .tcp_keepalive(Duration::from_secs(2))
             .tcp_nodelay(true);
-        let mgr = ChannelManager { pool, config };
+        let mgr = ChannelManager {
+            pool,
+            config,
+            client_tls_config: None,
+        };
 
         let res = mgr.build_endpoint("test_addr");
 
@@ -512,7 +583,11 @@ mod tests {
         let pool = Arc::new(pool);
 
         let config = ChannelConfig::new();
-        let mgr = ChannelManager { pool, config };
+        let mgr = ChannelManager {
+            pool,
+            config,
+            client_tls_config: None,
+        };
 
         let addr = "test_addr";
         let res = mgr.get(addr);
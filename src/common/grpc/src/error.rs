@@ -69,6 +69,16 @@ pub enum Error {
         source: tonic::transport::Error,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Failed to read TLS file: {}, source: {}", path, source))]
+    ReadTlsFile {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid TLS config: {}", msg))]
+    InvalidTlsConfig { msg: String, backtrace: Backtrace },
 }
 
 impl ErrorExt for Error {
@@ -77,7 +87,9 @@ impl ErrorExt for Error {
             Error::EmptyPhysicalPlan { .. }
             | Error::EmptyPhysicalExpr { .. }
             | Error::MissingField { .. }
-            | Error::TypeMismatch { .. } => StatusCode::InvalidArguments,
+            | Error::TypeMismatch { .. }
+            | Error::ReadTlsFile { .. }
+            | Error::InvalidTlsConfig { .. } => StatusCode::InvalidArguments,
             Error::UnsupportedDfPlan { .. } | Error::UnsupportedDfExpr { .. } => {
                 StatusCode::Unsupported
             }
@@ -0,0 +1,118 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS options of gRPC servers and channels, loaded from PEM files.
+
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tonic::transport::{Certificate, ClientTlsConfig, Identity, ServerTlsConfig};
+
+use crate::error::{self, Result};
+
+/// TLS of a gRPC server. Clients must present certificates signed by `ca_cert_path` if it's
+/// set, which enables mutual TLS between nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerTlsOption {
+    pub cert_path: String,
+    pub key_path: String,
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+}
+
+impl ServerTlsOption {
+    pub fn setup(&self) -> Result<ServerTlsConfig> {
+        let identity = Identity::from_pem(read_pem(&self.cert_path)?, read_pem(&self.key_path)?);
+        let mut config = ServerTlsConfig::new().identity(identity);
+        if let Some(ca_cert_path) = &self.ca_cert_path {
+            config = config.client_ca_root(Certificate::from_pem(read_pem(ca_cert_path)?));
+        }
+        Ok(config)
+    }
+}
+
+/// TLS of gRPC channels. Servers are verified by `server_ca_cert_path`, and the client
+/// authenticates itself by the certificate and key if the server requires mutual TLS.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientTlsOption {
+    pub server_ca_cert_path: String,
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// The name to verify the certificates of servers against, defaults to the host of the
+    /// address to connect.
+    #[serde(default)]
+    pub domain_name: Option<String>,
+}
+
+impl ClientTlsOption {
+    pub fn setup(&self) -> Result<ClientTlsConfig> {
+        let mut config = ClientTlsConfig::new()
+            .ca_certificate(Certificate::from_pem(read_pem(&self.server_ca_cert_path)?));
+        match (&self.client_cert_path, &self.client_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                config = config.identity(Identity::from_pem(
+                    read_pem(cert_path)?,
+                    read_pem(key_path)?,
+                ));
+            }
+            (None, None) => {}
+            _ => {
+                return error::InvalidTlsConfigSnafu {
+                    msg: "client_cert_path and client_key_path must be set together",
+                }
+                .fail()
+            }
+        }
+        if let Some(domain_name) = &self.domain_name {
+            config = config.domain_name(domain_name);
+        }
+        Ok(config)
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).context(error::ReadTlsFileSnafu { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_tls_option() {
+        let option = ClientTlsOption {
+            server_ca_cert_path: "/path/not/exist/ca.pem".to_string(),
+            ..Default::default()
+        };
+        let err = option.setup().unwrap_err();
+        assert!(matches!(err, error::Error::ReadTlsFile { .. }), "{:?}", err);
+
+        let dir = tempdir::TempDir::new("test_client_tls_option").unwrap();
+        let ca_cert_path = dir.path().join("ca.pem");
+        std::fs::write(&ca_cert_path, "not a real certificate").unwrap();
+        let option = ClientTlsOption {
+            server_ca_cert_path: ca_cert_path.to_str().unwrap().to_string(),
+            client_cert_path: Some("/path/not/exist/client.pem".to_string()),
+            client_key_path: None,
+            domain_name: None,
+        };
+        let err = option.setup().unwrap_err();
+        assert!(
+            matches!(err, error::Error::InvalidTlsConfig { .. }),
+            "{:?}",
+            err
+        );
+    }
+}
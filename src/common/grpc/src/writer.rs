@@ -212,6 +212,7 @@ impl LinesWriter {
                     values: Some(Values::with_capacity(datatype, to_insert)),
                     datatype: datatype as i32,
                     null_mask: Vec::default(),
+                    datatype_extension: None,
                 });
                 column_names.insert(column_name.to_string(), new_idx);
                 new_idx
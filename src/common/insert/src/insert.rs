@@ -20,14 +20,17 @@ use std::sync::Arc;
 use api::helper::ColumnDataTypeWrapper;
 use api::v1::codec::InsertBatch;
 use api::v1::column::{SemanticType, Values};
-use api::v1::{AddColumn, AddColumns, Column, ColumnDataType, ColumnDef, CreateExpr};
+use api::v1::{
+    AddColumn, AddColumns, Column, ColumnDataType, ColumnDataTypeExtension, ColumnDef, CreateExpr,
+};
 use common_base::BitVec;
 use common_time::timestamp::Timestamp;
-use common_time::{Date, DateTime};
+use common_time::{Date, DateTime, Interval};
 use datatypes::data_type::ConcreteDataType;
+use datatypes::decimal::Decimal128;
 use datatypes::prelude::{ValueRef, VectorRef};
 use datatypes::schema::SchemaRef;
-use datatypes::value::Value;
+use datatypes::value::{MapValue, StructValue, Value};
 use datatypes::vectors::VectorBuilder;
 use snafu::{ensure, OptionExt, ResultExt};
 use table::metadata::TableId;
@@ -43,12 +46,18 @@ const TAG_SEMANTIC_TYPE: i32 = SemanticType::Tag as i32;
 const TIMESTAMP_SEMANTIC_TYPE: i32 = SemanticType::Timestamp as i32;
 
 #[inline]
-fn build_column_def(column_name: &str, datatype: i32, nullable: bool) -> ColumnDef {
+fn build_column_def(
+    column_name: &str,
+    datatype: i32,
+    datatype_extension: Option<ColumnDataTypeExtension>,
+    nullable: bool,
+) -> ColumnDef {
     ColumnDef {
         name: column_name.to_string(),
         datatype,
         is_nullable: nullable,
         default_constraint: None,
+        datatype_extension,
     }
 }
 
@@ -68,13 +77,19 @@ pub fn find_new_columns(
             column_name,
             semantic_type,
             datatype,
+            datatype_extension,
             ..
         } in columns
         {
             if schema.column_schema_by_name(column_name).is_none()
                 && !new_columns.contains(column_name)
             {
-                let column_def = Some(build_column_def(column_name, *datatype, true));
+                let column_def = Some(build_column_def(
+                    column_name,
+                    *datatype,
+                    datatype_extension.clone(),
+                    true,
+                ));
                 columns_to_add.push(AddColumn {
                     column_def,
                     is_key: *semantic_type == TAG_SEMANTIC_TYPE,
@@ -108,17 +123,25 @@ pub fn build_alter_table_request(
 }
 
 pub fn column_to_vector(column: &Column, rows: u32) -> Result<VectorRef> {
-    let wrapper = ColumnDataTypeWrapper::try_new(column.datatype).context(ColumnDataTypeSnafu)?;
+    let wrapper =
+        ColumnDataTypeWrapper::try_new(column.datatype, column.datatype_extension.clone())
+            .context(ColumnDataTypeSnafu)?;
     let column_datatype = wrapper.datatype();
+    let data_type = ConcreteDataType::try_from(wrapper).context(ColumnDataTypeSnafu)?;
 
     let rows = rows as usize;
-    let mut vector = VectorBuilder::with_capacity(wrapper.into(), rows);
+    let mut vector = VectorBuilder::with_capacity(data_type.clone(), rows);
 
     if let Some(values) = &column.values {
-        let values = collect_column_values(column_datatype, values);
+        let null_mask = BitVec::from_slice(&column.null_mask);
+        let parameterized_values =
+            collect_parameterized_values(&data_type, values, &null_mask, rows)?;
+        let values = match &parameterized_values {
+            Some(values) => values.iter().map(Value::as_value_ref).collect(),
+            None => collect_column_values(column_datatype, values),
+        };
         let mut values_iter = values.into_iter();
 
-        let null_mask = BitVec::from_slice(&column.null_mask);
         let mut nulls_iter = null_mask.iter().by_vals().fuse();
 
         for i in 0..rows {
@@ -192,9 +215,118 @@ fn collect_column_values(column_datatype: ColumnDataType, values: &Values) -> Ve
                 Timestamp::from_millis(*v)
             ))
         }
+        ColumnDataType::Json => {
+            collect_values!(values.json_values, |v| ValueRef::Json(v.as_str()))
+        }
+        ColumnDataType::Interval => {
+            collect_values!(values.interval_values, |v| ValueRef::Interval(
+                Interval::new(v.months, v.days, v.nanoseconds)
+            ))
+        }
+        // Values of datatypes with parameters are collected by `collect_parameterized_values`.
+        ColumnDataType::Decimal128 | ColumnDataType::Struct | ColumnDataType::Map => Vec::new(),
     }
 }
 
+/// Collects the non-null values of decimal, struct and map columns, which require the
+/// parameters of `data_type` to decode, returns `None` for other datatypes.
+///
+/// The nested columns of structs and maps are decoded recursively, they must have the
+/// datatypes declared in `data_type`.
+fn collect_parameterized_values(
+    data_type: &ConcreteDataType,
+    values: &Values,
+    null_mask: &BitVec,
+    rows: usize,
+) -> Result<Option<Vec<Value>>> {
+    let is_valid = |idx: usize| is_null(null_mask, idx) != Some(true);
+    let collected = match data_type {
+        ConcreteDataType::Decimal128(t) => values
+            .decimal128_values
+            .iter()
+            .map(|v| Value::Decimal128(Decimal128::new(v.into(), t.precision(), t.scale())))
+            .collect(),
+        ConcreteDataType::Struct(t) => {
+            ensure!(
+                values.struct_fields.len() == t.fields().len(),
+                InvalidColumnProtoSnafu {
+                    err_msg: format!(
+                        "expect {} struct fields, found {}",
+                        t.fields().len(),
+                        values.struct_fields.len()
+                    ),
+                }
+            );
+            let fields = values
+                .struct_fields
+                .iter()
+                .zip(t.fields())
+                .map(|(column, field)| nested_column_to_vector(column, field.data_type(), rows))
+                .collect::<Result<Vec<_>>>()?;
+            (0..rows)
+                .filter(|idx| is_valid(*idx))
+                .map(|idx| {
+                    let items = fields.iter().map(|field| field.get(idx)).collect();
+                    Value::Struct(StructValue::new(items, t.clone()))
+                })
+                .collect()
+        }
+        ConcreteDataType::Map(t) => {
+            ensure!(
+                values.map_lengths.len() == rows && values.map_entries.len() == 2,
+                InvalidColumnProtoSnafu {
+                    err_msg: format!(
+                        "expect {} map lengths and 2 entry columns, found {} and {}",
+                        rows,
+                        values.map_lengths.len(),
+                        values.map_entries.len()
+                    ),
+                }
+            );
+            let entry_count = values.map_lengths.iter().map(|len| *len as usize).sum();
+            let keys = nested_column_to_vector(&values.map_entries[0], t.key_type(), entry_count)?;
+            let map_values =
+                nested_column_to_vector(&values.map_entries[1], t.value_type(), entry_count)?;
+
+            let mut maps = Vec::with_capacity(rows);
+            let mut offset = 0;
+            for (idx, len) in values.map_lengths.iter().enumerate() {
+                let len = *len as usize;
+                if is_valid(idx) {
+                    let entries = (offset..offset + len)
+                        .map(|i| (keys.get(i), map_values.get(i)))
+                        .collect();
+                    maps.push(Value::Map(MapValue::new(entries, t.clone())));
+                }
+                offset += len;
+            }
+            maps
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(collected))
+}
+
+fn nested_column_to_vector(
+    column: &Column,
+    data_type: &ConcreteDataType,
+    rows: usize,
+) -> Result<VectorRef> {
+    let vector = column_to_vector(column, rows as u32)?;
+    ensure!(
+        &vector.data_type() == data_type,
+        InvalidColumnProtoSnafu {
+            err_msg: format!(
+                "expect nested column {} of datatype {:?}, found {:?}",
+                column.column_name,
+                data_type,
+                vector.data_type()
+            ),
+        }
+    );
+    Ok(vector)
+}
+
 /// Try to build create table request from insert data.
 pub fn build_create_expr_from_insertion(
     catalog_name: &str,
@@ -217,6 +349,7 @@ pub fn build_create_expr_from_insertion(
             column_name,
             semantic_type,
             datatype,
+            datatype_extension,
             ..
         } in columns
         {
@@ -239,7 +372,12 @@ pub fn build_create_expr_from_insertion(
                     _ => {}
                 }
 
-                let column_def = build_column_def(column_name, *datatype, is_nullable);
+                let column_def = build_column_def(
+                    column_name,
+                    *datatype,
+                    datatype_extension.clone(),
+                    is_nullable,
+                );
                 column_defs.push(column_def);
                 new_columns.insert(column_name.to_string());
             }
@@ -344,16 +482,18 @@ fn add_values_to_builder(
     null_mask: Vec<u8>,
 ) -> Result<()> {
     let data_type = builder.data_type();
-    let values = convert_values(&data_type, values);
+    let null_mask = BitVec::from_vec(null_mask);
+    let values = convert_values(&data_type, values, &null_mask, row_count)?;
 
     if null_mask.is_empty() {
         ensure!(values.len() == row_count, IllegalInsertDataSnafu);
 
-        values.iter().for_each(|value| {
-            builder.push(value);
-        });
+        for value in &values {
+            builder
+                .try_push_ref(value.as_value_ref())
+                .context(CreateVectorSnafu)?;
+        }
     } else {
-        let null_mask = BitVec::from_vec(null_mask);
         ensure!(
             null_mask.count_ones() + values.len() == row_count,
             IllegalInsertDataSnafu
@@ -362,9 +502,11 @@ fn add_values_to_builder(
         let mut idx_of_values = 0;
         for idx in 0..row_count {
             match is_null(&null_mask, idx) {
-                Some(true) => builder.push(&Value::Null),
+                Some(true) => builder.push_null(),
                 _ => {
-                    builder.push(&values[idx_of_values]);
+                    builder
+                        .try_push_ref(values[idx_of_values].as_value_ref())
+                        .context(CreateVectorSnafu)?;
                     idx_of_values += 1
                 }
             }
@@ -373,9 +515,18 @@ fn add_values_to_builder(
     Ok(())
 }
 
-fn convert_values(data_type: &ConcreteDataType, values: Values) -> Vec<Value> {
+fn convert_values(
+    data_type: &ConcreteDataType,
+    values: Values,
+    null_mask: &BitVec,
+    row_count: usize,
+) -> Result<Vec<Value>> {
+    if let Some(values) = collect_parameterized_values(data_type, &values, null_mask, row_count)? {
+        return Ok(values);
+    }
+
     // TODO(fys): use macros to optimize code
-    match data_type {
+    let values: Vec<Value> = match data_type {
         ConcreteDataType::Int64(_) => values
             .i64_values
             .into_iter()
@@ -448,9 +599,28 @@ fn convert_values(data_type: &ConcreteDataType, values: Values) -> Vec<Value> {
             .into_iter()
             .map(|v| Value::Timestamp(Timestamp::from_millis(v)))
             .collect(),
-        ConcreteDataType::Null(_) => unreachable!(),
-        ConcreteDataType::List(_) => unreachable!(),
-    }
+        ConcreteDataType::Json(_) => values
+            .json_values
+            .into_iter()
+            .map(|v| Value::Json(v.into()))
+            .collect(),
+        ConcreteDataType::Interval(_) => values
+            .interval_values
+            .into_iter()
+            .map(|v| Value::Interval(Interval::new(v.months, v.days, v.nanoseconds)))
+            .collect(),
+        // Collected by `collect_parameterized_values`.
+        ConcreteDataType::Decimal128(_)
+        | ConcreteDataType::Struct(_)
+        | ConcreteDataType::Map(_) => Vec::new(),
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) => {
+            return InvalidColumnProtoSnafu {
+                err_msg: format!("cannot insert values of datatype {:?}", data_type),
+            }
+            .fail()
+        }
+    };
+    Ok(values)
 }
 
 fn is_null(null_mask: &BitVec, idx: usize) -> Option<bool> {
@@ -470,17 +640,22 @@ mod tests {
     use common_query::physical_plan::PhysicalPlanRef;
     use common_query::prelude::Expr;
     use common_time::timestamp::Timestamp;
+    use common_time::Interval;
     use datatypes::data_type::ConcreteDataType;
+    use datatypes::decimal::Decimal128;
     use datatypes::schema::{ColumnSchema, SchemaBuilder, SchemaRef};
-    use datatypes::value::Value;
+    use datatypes::types::StructField;
+    use datatypes::value::{MapValue, StructValue, Value};
+    use datatypes::vectors::VectorBuilder;
     use snafu::ResultExt;
     use table::error::Result as TableResult;
     use table::metadata::TableInfoRef;
     use table::Table;
 
     use super::{
-        build_create_expr_from_insertion, convert_values, find_new_columns, insert_batches,
-        insertion_expr_to_request, is_null, TAG_SEMANTIC_TYPE, TIMESTAMP_SEMANTIC_TYPE,
+        add_values_to_builder, build_create_expr_from_insertion, column_to_vector, convert_values,
+        find_new_columns, insert_batches, insertion_expr_to_request, is_null, TAG_SEMANTIC_TYPE,
+        TIMESTAMP_SEMANTIC_TYPE,
     };
     use crate::error;
     use crate::error::ColumnDataTypeSnafu;
@@ -492,11 +667,11 @@ mod tests {
         nullable: bool,
     ) -> error::Result<ColumnSchema> {
         let datatype_wrapper =
-            ColumnDataTypeWrapper::try_new(datatype).context(ColumnDataTypeSnafu)?;
+            ColumnDataTypeWrapper::try_new(datatype, None).context(ColumnDataTypeSnafu)?;
 
         Ok(ColumnSchema::new(
             column_name,
-            datatype_wrapper.into(),
+            ConcreteDataType::try_from(datatype_wrapper).context(ColumnDataTypeSnafu)?,
             nullable,
         ))
     }
@@ -529,58 +704,66 @@ mod tests {
 
         assert_eq!(
             ConcreteDataType::string_datatype(),
-            ConcreteDataType::from(
+            ConcreteDataType::try_from(
                 ColumnDataTypeWrapper::try_new(
                     column_defs
                         .iter()
                         .find(|c| c.name == "host")
                         .unwrap()
-                        .datatype
+                        .datatype,
+                    None
                 )
                 .unwrap()
             )
+            .unwrap()
         );
 
         assert_eq!(
             ConcreteDataType::float64_datatype(),
-            ConcreteDataType::from(
+            ConcreteDataType::try_from(
                 ColumnDataTypeWrapper::try_new(
                     column_defs
                         .iter()
                         .find(|c| c.name == "cpu")
                         .unwrap()
-                        .datatype
+                        .datatype,
+                    None
                 )
                 .unwrap()
             )
+            .unwrap()
         );
 
         assert_eq!(
             ConcreteDataType::float64_datatype(),
-            ConcreteDataType::from(
+            ConcreteDataType::try_from(
                 ColumnDataTypeWrapper::try_new(
                     column_defs
                         .iter()
                         .find(|c| c.name == "memory")
                         .unwrap()
-                        .datatype
+                        .datatype,
+                    None
                 )
                 .unwrap()
             )
+            .unwrap()
         );
 
         assert_eq!(
             ConcreteDataType::timestamp_millis_datatype(),
-            ConcreteDataType::from(
+            ConcreteDataType::try_from(
                 ColumnDataTypeWrapper::try_new(
                     column_defs
                         .iter()
                         .find(|c| c.name == "ts")
                         .unwrap()
-                        .datatype
+                        .datatype,
+                    None
                 )
                 .unwrap()
             )
+            .unwrap()
         );
     }
 
@@ -608,10 +791,14 @@ mod tests {
 
         assert_eq!(
             ConcreteDataType::string_datatype(),
-            ConcreteDataType::from(
-                ColumnDataTypeWrapper::try_new(host_column.column_def.as_ref().unwrap().datatype)
-                    .unwrap()
+            ConcreteDataType::try_from(
+                ColumnDataTypeWrapper::try_new(
+                    host_column.column_def.as_ref().unwrap().datatype,
+                    None
+                )
+                .unwrap()
             )
+            .unwrap()
         );
 
         let memory_column = &add_columns.add_columns[1];
@@ -619,10 +806,14 @@ mod tests {
 
         assert_eq!(
             ConcreteDataType::float64_datatype(),
-            ConcreteDataType::from(
-                ColumnDataTypeWrapper::try_new(memory_column.column_def.as_ref().unwrap().datatype)
-                    .unwrap()
+            ConcreteDataType::try_from(
+                ColumnDataTypeWrapper::try_new(
+                    memory_column.column_def.as_ref().unwrap().datatype,
+                    None
+                )
+                .unwrap()
             )
+            .unwrap()
         );
     }
 
@@ -666,7 +857,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = convert_values(&data_type, values);
+        let result = convert_values(&data_type, values, &BitVec::default(), 3).unwrap();
 
         assert_eq!(
             vec![
@@ -678,6 +869,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_column_to_vector_with_parameters() {
+        let struct_type = ConcreteDataType::struct_datatype(vec![
+            StructField::new("a", ConcreteDataType::decimal128_datatype(10, 2), true),
+            StructField::new("b", ConcreteDataType::interval_datatype(), true),
+        ]);
+        let map_type = ConcreteDataType::map_datatype(
+            ConcreteDataType::string_datatype(),
+            struct_type.clone(),
+        );
+        let struct_value = |decimal: i128, months: i32| {
+            Value::Struct(StructValue::new(
+                vec![
+                    Value::Decimal128(Decimal128::new(decimal, 10, 2)),
+                    Value::Interval(Interval::new(months, 0, 0)),
+                ],
+                match &struct_type {
+                    ConcreteDataType::Struct(t) => t.clone(),
+                    _ => unreachable!(),
+                },
+            ))
+        };
+        let map_value = |entries: Vec<(&str, Value)>| {
+            Value::Map(MapValue::new(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Value::from(key), value))
+                    .collect(),
+                match &map_type {
+                    ConcreteDataType::Map(t) => t.clone(),
+                    _ => unreachable!(),
+                },
+            ))
+        };
+        let rows = vec![
+            map_value(vec![("a", struct_value(100, 1)), ("b", Value::Null)]),
+            Value::Null,
+            map_value(vec![]),
+            map_value(vec![("c", struct_value(-1, 2))]),
+        ];
+        let mut builder = VectorBuilder::new(map_type.clone());
+        rows.iter().for_each(|row| builder.push(row));
+        let vector = builder.finish();
+
+        let (datatype, datatype_extension) = ColumnDataTypeWrapper::try_from(map_type.clone())
+            .unwrap()
+            .into_parts();
+        let mut column = Column {
+            column_name: "attrs".to_string(),
+            datatype: datatype as i32,
+            datatype_extension,
+            ..Default::default()
+        };
+        column.push_vals(0, vector.slice(0, 2)).unwrap();
+        column.push_vals(2, vector.slice(2, 2)).unwrap();
+
+        let decoded = column_to_vector(&column, 4).unwrap();
+        assert_eq!(map_type, decoded.data_type());
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(*row, decoded.get(i));
+        }
+
+        // Inserts the values to a table column of the same type.
+        let mut builder = VectorBuilder::new(map_type.clone());
+        let null_mask = column.null_mask.clone();
+        add_values_to_builder(&mut builder, column.values.clone().unwrap(), 4, null_mask).unwrap();
+        assert_eq!(decoded, builder.finish());
+
+        // Nested columns must have the declared datatypes.
+        let mut values = column.values.unwrap();
+        values.map_entries[0].datatype = ColumnDataType::Binary as i32;
+        let mut builder = VectorBuilder::new(map_type);
+        assert!(add_values_to_builder(&mut builder, values, 4, column.null_mask).is_err());
+    }
+
     #[test]
     fn test_is_null() {
         let null_mask = BitVec::from_slice(&[0b0000_0001, 0b0000_1000]);
@@ -744,6 +1010,7 @@ mod tests {
             values: Some(host_vals),
             null_mask: vec![0],
             datatype: ColumnDataType::String as i32,
+            datatype_extension: None,
         };
 
         let cpu_vals = column::Values {
@@ -756,6 +1023,7 @@ mod tests {
             values: Some(cpu_vals),
             null_mask: vec![2],
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
 
         let mem_vals = column::Values {
@@ -768,6 +1036,7 @@ mod tests {
             values: Some(mem_vals),
             null_mask: vec![1],
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
 
         let ts_vals = column::Values {
@@ -780,6 +1049,7 @@ mod tests {
             values: Some(ts_vals),
             null_mask: vec![0],
             datatype: ColumnDataType::Timestamp as i32,
+            datatype_extension: None,
         };
 
         let insert_batch = InsertBatch {
@@ -85,6 +85,12 @@ pub enum InnerError {
     #[snafu(display("unexpected: not constant column"))]
     InvalidInputCol { backtrace: Backtrace },
 
+    #[snafu(display("Invalid function args: {}", err_msg))]
+    InvalidFuncArgs {
+        err_msg: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Not expected to run ExecutionPlan more than once"))]
     ExecuteRepeatedly { backtrace: Backtrace },
 
@@ -138,7 +144,9 @@ impl ErrorExt for InnerError {
             | InnerError::GeneralDataFusion { .. }
             | InnerError::DataFusionExecutionPlan { .. } => StatusCode::Unexpected,
 
-            InnerError::UnsupportedInputDataType { .. } => StatusCode::InvalidArguments,
+            InnerError::UnsupportedInputDataType { .. } | InnerError::InvalidFuncArgs { .. } => {
+                StatusCode::InvalidArguments
+            }
 
             InnerError::ConvertDfRecordBatchStream { source, .. } => source.status_code(),
         }
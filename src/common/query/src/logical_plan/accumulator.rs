@@ -207,6 +207,18 @@ fn try_into_scalar_value(value: Value, datatype: &ConcreteDataType) -> Result<Sc
         Value::Null => try_convert_null_value(datatype)?,
         Value::List(list) => try_convert_list_value(list)?,
         Value::Timestamp(t) => timestamp_to_scalar_value(t.unit(), Some(t.value())),
+        Value::Decimal128(v) => {
+            ScalarValue::Decimal128(Some(v.value()), v.precision() as usize, v.scale() as usize)
+        }
+        Value::Json(_) | Value::Interval(_) | Value::Struct(_) | Value::Map(_) => {
+            return error::BadAccumulatorImplSnafu {
+                err_msg: format!(
+                    "unsupported state value of datatype {:?}",
+                    value.data_type()
+                ),
+            }
+            .fail()?
+        }
     })
 }
 
@@ -235,6 +247,9 @@ fn try_convert_null_value(datatype: &ConcreteDataType) -> Result<ScalarValue> {
         ConcreteDataType::Binary(_) => ScalarValue::LargeBinary(None),
         ConcreteDataType::String(_) => ScalarValue::Utf8(None),
         ConcreteDataType::Timestamp(t) => timestamp_to_scalar_value(t.unit, None),
+        ConcreteDataType::Decimal128(t) => {
+            ScalarValue::Decimal128(None, t.precision() as usize, t.scale() as usize)
+        }
         _ => {
             return error::BadAccumulatorImplSnafu {
                 err_msg: format!(
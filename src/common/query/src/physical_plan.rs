@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -23,8 +23,9 @@ use datafusion::arrow::datatypes::SchemaRef as DfSchemaRef;
 use datafusion::error::Result as DfResult;
 pub use datafusion::execution::runtime_env::RuntimeEnv;
 use datafusion::physical_plan::expressions::PhysicalSortExpr;
-pub use datafusion::physical_plan::Partitioning;
+pub use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
 use datafusion::physical_plan::Statistics;
+pub use datafusion::physical_plan::{DisplayFormatType, Partitioning};
 use datatypes::schema::SchemaRef;
 use snafu::ResultExt;
 
@@ -66,6 +67,18 @@ pub trait PhysicalPlan: Debug + Send + Sync {
         partition: usize,
         runtime: Arc<RuntimeEnv>,
     ) -> Result<SendableRecordBatchStream>;
+
+    /// Formats this plan, without its children, as a line of `EXPLAIN`.
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = std::any::type_name::<Self>();
+        write!(f, "{}", name.rsplit("::").next().unwrap_or(name))
+    }
+
+    /// Returns the metrics collected while executing this plan, which are reported by
+    /// `EXPLAIN ANALYZE`.
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -132,6 +145,14 @@ impl PhysicalPlan for PhysicalPlanAdapter {
             .context(error::ConvertDfRecordBatchStreamSnafu)?;
         Ok(Box::pin(stream))
     }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        self.df_plan.fmt_as(t, f)
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.df_plan.metrics()
+    }
 }
 
 #[derive(Debug)]
@@ -191,6 +212,14 @@ impl DfPhysicalPlan for DfPhysicalPlanAdapter {
         Ok(Box::pin(DfRecordBatchStreamAdapter::new(stream)))
     }
 
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.0.metrics()
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_as(t, f)
+    }
+
     fn statistics(&self) -> Statistics {
         // TODO(LFC): impl statistics
         Statistics::default()
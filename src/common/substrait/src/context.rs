@@ -0,0 +1,105 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use substrait_proto::protobuf::extensions::simple_extension_declaration::{
+    ExtensionFunction, MappingType,
+};
+use substrait_proto::protobuf::extensions::SimpleExtensionDeclaration;
+
+/// Context shared by the whole convertion of one plan. It records the functions
+/// referenced in the plan, which are serialized as extension declarations of
+/// the substrait `Plan` and referred to by their anchors.
+#[derive(Default)]
+pub struct ConvertorContext {
+    scalar_fn_names: HashMap<String, u32>,
+    scalar_fn_map: HashMap<u32, String>,
+}
+
+impl ConvertorContext {
+    /// Register a function by its name, returns the anchor of it.
+    /// Registering the same name twice gets the same anchor.
+    pub fn register_scalar_fn<S: AsRef<str>>(&mut self, name: S) -> u32 {
+        if let Some(anchor) = self.scalar_fn_names.get(name.as_ref()) {
+            return *anchor;
+        }
+
+        let next_anchor = self.scalar_fn_map.len() as u32;
+        self.scalar_fn_map
+            .insert(next_anchor, name.as_ref().to_string());
+        self.scalar_fn_names
+            .insert(name.as_ref().to_string(), next_anchor);
+        next_anchor
+    }
+
+    pub fn register_scalar_with_anchor<S: AsRef<str>>(&mut self, name: S, anchor: u32) {
+        self.scalar_fn_map.insert(anchor, name.as_ref().to_string());
+        self.scalar_fn_names
+            .insert(name.as_ref().to_string(), anchor);
+    }
+
+    pub fn find_scalar_fn(&self, anchor: u32) -> Option<&str> {
+        self.scalar_fn_map.get(&anchor).map(|s| s.as_str())
+    }
+
+    /// Build the extension declarations of all registered functions.
+    pub fn generate_function_extension(&self) -> Vec<SimpleExtensionDeclaration> {
+        let mut anchors = self.scalar_fn_map.keys().copied().collect::<Vec<_>>();
+        anchors.sort_unstable();
+        anchors
+            .into_iter()
+            .map(|anchor| SimpleExtensionDeclaration {
+                mapping_type: Some(MappingType::ExtensionFunction(ExtensionFunction {
+                    extension_uri_reference: 0,
+                    function_anchor: anchor,
+                    name: self.scalar_fn_map[&anchor].clone(),
+                })),
+            })
+            .collect()
+    }
+
+    /// Restore the context from the extension declarations of a substrait `Plan`.
+    /// Declarations other than functions are ignored.
+    pub fn from_extensions(extensions: &[SimpleExtensionDeclaration]) -> Self {
+        let mut ctx = Self::default();
+        for extension in extensions {
+            if let Some(MappingType::ExtensionFunction(function)) = &extension.mapping_type {
+                ctx.register_scalar_with_anchor(&function.name, function.function_anchor);
+            }
+        }
+        ctx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_function_extension_round_trip() {
+        let mut ctx = ConvertorContext::default();
+        assert_eq!(0, ctx.register_scalar_fn("equal"));
+        assert_eq!(1, ctx.register_scalar_fn("and"));
+        assert_eq!(0, ctx.register_scalar_fn("equal"));
+
+        let extensions = ctx.generate_function_extension();
+        assert_eq!(2, extensions.len());
+
+        let ctx = ConvertorContext::from_extensions(&extensions);
+        assert_eq!(Some("equal"), ctx.find_scalar_fn(0));
+        assert_eq!(Some("and"), ctx.find_scalar_fn(1));
+        assert_eq!(None, ctx.find_scalar_fn(2));
+    }
+}
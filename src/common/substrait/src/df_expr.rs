@@ -0,0 +1,611 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Methods that perform convertion between Substrait's [Expression] and DataFusion's [Expr].
+//!
+//! Columns are referenced by their indices in the input schema. Operators and functions are
+//! encoded as scalar or aggregate functions, whose names are registered in [ConvertorContext].
+//! Besides operators, scalar function names are resolved in this order when decoding:
+//! expressions that have no native substrait representation (e.g. `alias`, `between`),
+//! DataFusion's builtin functions, then functions from [FUNCTION_REGISTRY].
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_function::scalars::udf::create_udf;
+use common_function::scalars::FUNCTION_REGISTRY;
+use common_query::logical_plan::create_aggregate_function;
+use datafusion::logical_plan::{Column, DFSchemaRef, Expr, Operator};
+use datafusion::physical_plan::aggregates::AggregateFunction as DfAggregateFunction;
+use datafusion::physical_plan::functions::BuiltinScalarFunction;
+use datafusion::physical_plan::udaf::AggregateUDF as DfAggregateUdf;
+use datafusion::scalar::ScalarValue;
+use datatypes::prelude::{ConcreteDataType, DataType};
+use snafu::{ensure, OptionExt, ResultExt};
+use substrait_proto::protobuf::expression::field_reference::ReferenceType as FieldReferenceType;
+use substrait_proto::protobuf::expression::if_then::IfClause;
+use substrait_proto::protobuf::expression::reference_segment::{
+    ReferenceType as SegReferenceType, StructField,
+};
+use substrait_proto::protobuf::expression::{
+    Cast, FieldReference, IfThen, ReferenceSegment, RexType, ScalarFunction, SingularOrList,
+};
+use substrait_proto::protobuf::function_argument::ArgType;
+use substrait_proto::protobuf::{AggregateFunction, Expression, FunctionArgument};
+
+use crate::context::ConvertorContext;
+use crate::error::{
+    DFInternalSnafu, EmptyExprSnafu, InvalidParametersSnafu, MissingFieldSnafu, Result,
+    UnsupportedExprSnafu,
+};
+use crate::types::{from_concrete_type, from_scalar_value, to_concrete_type, to_scalar_value};
+
+/// Convert substrait's [Expression] to DataFusion's [Expr].
+pub(crate) fn to_df_expr(
+    ctx: &ConvertorContext,
+    expression: Expression,
+    schema: &DFSchemaRef,
+) -> Result<Expr> {
+    let rex_type = expression.rex_type.context(EmptyExprSnafu)?;
+    match rex_type {
+        RexType::Literal(literal) => Ok(Expr::Literal(to_scalar_value(literal)?)),
+        RexType::Selection(selection) => convert_selection_rex(*selection, schema),
+        RexType::ScalarFunction(scalar_fn) => convert_scalar_function(ctx, scalar_fn, schema),
+        RexType::Cast(cast) => convert_cast_rex(ctx, *cast, schema),
+        RexType::SingularOrList(list) => convert_singular_or_list_rex(ctx, *list, schema),
+        RexType::IfThen(if_then) => convert_if_then_rex(ctx, *if_then, schema),
+        other => UnsupportedExprSnafu {
+            name: format!("substrait expression {:?}", other),
+        }
+        .fail(),
+    }
+}
+
+/// Convert a field reference into a [Column] of the input schema.
+fn convert_selection_rex(selection: FieldReference, schema: &DFSchemaRef) -> Result<Expr> {
+    let field = match selection.reference_type {
+        Some(FieldReferenceType::DirectReference(ReferenceSegment {
+            reference_type: Some(SegReferenceType::StructField(field)),
+        })) => field,
+        _ => InvalidParametersSnafu {
+            reason: "Only direct struct field reference is supported",
+        }
+        .fail()?,
+    };
+
+    let index = field.field as usize;
+    ensure!(
+        index < schema.fields().len(),
+        InvalidParametersSnafu {
+            reason: format!(
+                "Field index {} out of range, schema has {} fields",
+                index,
+                schema.fields().len()
+            ),
+        }
+    );
+    Ok(Expr::Column(schema.field(index).qualified_column()))
+}
+
+fn convert_cast_rex(ctx: &ConvertorContext, cast: Cast, schema: &DFSchemaRef) -> Result<Expr> {
+    let input = cast.input.context(MissingFieldSnafu {
+        field: "input",
+        plan: "Cast",
+    })?;
+    let ty = cast.r#type.context(MissingFieldSnafu {
+        field: "type",
+        plan: "Cast",
+    })?;
+    let (concrete_type, _) = to_concrete_type(&ty)?;
+
+    Ok(Expr::Cast {
+        expr: Box::new(to_df_expr(ctx, *input, schema)?),
+        data_type: concrete_type.as_arrow_type(),
+    })
+}
+
+fn convert_singular_or_list_rex(
+    ctx: &ConvertorContext,
+    list: SingularOrList,
+    schema: &DFSchemaRef,
+) -> Result<Expr> {
+    let value = list.value.context(MissingFieldSnafu {
+        field: "value",
+        plan: "SingularOrList",
+    })?;
+    let list = list
+        .options
+        .into_iter()
+        .map(|option| to_df_expr(ctx, option, schema))
+        .collect::<Result<_>>()?;
+
+    Ok(Expr::InList {
+        expr: Box::new(to_df_expr(ctx, *value, schema)?),
+        list,
+        negated: false,
+    })
+}
+
+fn convert_if_then_rex(
+    ctx: &ConvertorContext,
+    if_then: IfThen,
+    schema: &DFSchemaRef,
+) -> Result<Expr> {
+    let when_then_expr = if_then
+        .ifs
+        .into_iter()
+        .map(|clause| {
+            let when = clause.r#if.context(MissingFieldSnafu {
+                field: "if",
+                plan: "IfThen",
+            })?;
+            let then = clause.then.context(MissingFieldSnafu {
+                field: "then",
+                plan: "IfThen",
+            })?;
+            Ok((
+                Box::new(to_df_expr(ctx, when, schema)?),
+                Box::new(to_df_expr(ctx, then, schema)?),
+            ))
+        })
+        .collect::<Result<_>>()?;
+    let else_expr = if_then
+        .r#else
+        .map(|e| to_df_expr(ctx, *e, schema).map(Box::new))
+        .transpose()?;
+
+    Ok(Expr::Case {
+        expr: None,
+        when_then_expr,
+        else_expr,
+    })
+}
+
+fn convert_function_arguments(
+    ctx: &ConvertorContext,
+    arguments: Vec<FunctionArgument>,
+    schema: &DFSchemaRef,
+) -> Result<VecDeque<Expr>> {
+    arguments
+        .into_iter()
+        .map(|arg| match arg.arg_type {
+            Some(ArgType::Value(e)) => to_df_expr(ctx, e, schema),
+            _ => UnsupportedExprSnafu {
+                name: "Non-value function argument",
+            }
+            .fail(),
+        })
+        .collect()
+}
+
+fn convert_scalar_function(
+    ctx: &ConvertorContext,
+    scalar_fn: ScalarFunction,
+    schema: &DFSchemaRef,
+) -> Result<Expr> {
+    let fn_name = ctx
+        .find_scalar_fn(scalar_fn.function_reference)
+        .with_context(|| InvalidParametersSnafu {
+            reason: format!(
+                "Function anchor {} is not registered",
+                scalar_fn.function_reference
+            ),
+        })?;
+    let mut inputs = convert_function_arguments(ctx, scalar_fn.arguments, schema)?;
+
+    let ensure_arg_len = |expected: usize, inputs: &VecDeque<Expr>| -> Result<()> {
+        ensure!(
+            inputs.len() == expected,
+            InvalidParametersSnafu {
+                reason: format!(
+                    "Function {} expects {} arguments, found {}",
+                    fn_name,
+                    expected,
+                    inputs.len()
+                ),
+            }
+        );
+        Ok(())
+    };
+
+    if let Some(op) = name_to_op(fn_name) {
+        ensure_arg_len(2, &inputs)?;
+        return Ok(Expr::BinaryExpr {
+            left: Box::new(inputs.pop_front().unwrap()),
+            op,
+            right: Box::new(inputs.pop_front().unwrap()),
+        });
+    }
+
+    let expr = match fn_name {
+        "not" => {
+            ensure_arg_len(1, &inputs)?;
+            match inputs.pop_front().unwrap() {
+                // `NOT IN` is encoded as `not(SingularOrList)`.
+                Expr::InList {
+                    expr,
+                    list,
+                    negated: false,
+                } => Expr::InList {
+                    expr,
+                    list,
+                    negated: true,
+                },
+                other => Expr::Not(Box::new(other)),
+            }
+        }
+        "is_null" => {
+            ensure_arg_len(1, &inputs)?;
+            Expr::IsNull(Box::new(inputs.pop_front().unwrap()))
+        }
+        "is_not_null" => {
+            ensure_arg_len(1, &inputs)?;
+            Expr::IsNotNull(Box::new(inputs.pop_front().unwrap()))
+        }
+        "negative" => {
+            ensure_arg_len(1, &inputs)?;
+            Expr::Negative(Box::new(inputs.pop_front().unwrap()))
+        }
+        "between" | "not_between" => {
+            ensure_arg_len(3, &inputs)?;
+            Expr::Between {
+                expr: Box::new(inputs.pop_front().unwrap()),
+                negated: fn_name == "not_between",
+                low: Box::new(inputs.pop_front().unwrap()),
+                high: Box::new(inputs.pop_front().unwrap()),
+            }
+        }
+        "alias" => {
+            ensure_arg_len(2, &inputs)?;
+            let expr = inputs.pop_front().unwrap();
+            match inputs.pop_front().unwrap() {
+                Expr::Literal(ScalarValue::Utf8(Some(name))) => Expr::Alias(Box::new(expr), name),
+                other => InvalidParametersSnafu {
+                    reason: format!("Alias name must be a string literal, found {:?}", other),
+                }
+                .fail()?,
+            }
+        }
+        _ => {
+            let args = inputs.into();
+            if let Ok(fun) = BuiltinScalarFunction::from_str(fn_name) {
+                Expr::ScalarFunction { fun, args }
+            } else if let Some(func) = FUNCTION_REGISTRY.get_function(fn_name) {
+                Expr::ScalarUDF {
+                    fun: Arc::new(create_udf(func).into_df_udf()),
+                    args,
+                }
+            } else {
+                UnsupportedExprSnafu {
+                    name: format!("scalar function {}", fn_name),
+                }
+                .fail()?
+            }
+        }
+    };
+    Ok(expr)
+}
+
+/// Convert substrait's [AggregateFunction] to DataFusion's aggregate [Expr].
+pub(crate) fn to_df_aggregate_expr(
+    ctx: &ConvertorContext,
+    aggregate_fn: AggregateFunction,
+    schema: &DFSchemaRef,
+) -> Result<Expr> {
+    let fn_name = ctx
+        .find_scalar_fn(aggregate_fn.function_reference)
+        .with_context(|| InvalidParametersSnafu {
+            reason: format!(
+                "Function anchor {} is not registered",
+                aggregate_fn.function_reference
+            ),
+        })?;
+    let args = convert_function_arguments(ctx, aggregate_fn.arguments, schema)?.into();
+
+    if let Ok(fun) = DfAggregateFunction::from_str(fn_name) {
+        return Ok(Expr::AggregateFunction {
+            fun,
+            args,
+            distinct: false,
+        });
+    }
+
+    let func = FUNCTION_REGISTRY
+        .get_aggr_function(fn_name)
+        .with_context(|| UnsupportedExprSnafu {
+            name: format!("aggregate function {}", fn_name),
+        })?;
+    let fun: DfAggregateUdf =
+        create_aggregate_function(func.name(), func.args_count(), func.create()).into();
+    Ok(Expr::AggregateUDF {
+        fun: Arc::new(fun),
+        args,
+    })
+}
+
+/// Convert DataFusion's [Expr] to substrait's [Expression].
+pub(crate) fn expression_from_df_expr(
+    ctx: &mut ConvertorContext,
+    expr: &Expr,
+    schema: &DFSchemaRef,
+) -> Result<Expression> {
+    let rex_type = match expr {
+        Expr::Column(column) => RexType::Selection(Box::new(field_reference(column, schema)?)),
+        Expr::Literal(value) => RexType::Literal(from_scalar_value(value)?),
+        Expr::BinaryExpr { left, op, right } => {
+            let fn_name = op_to_name(op).with_context(|| UnsupportedExprSnafu {
+                name: format!("operator {}", op),
+            })?;
+            scalar_fn_rex(ctx, fn_name, &[left.as_ref(), right.as_ref()], schema)?
+        }
+        Expr::Not(e) => scalar_fn_rex(ctx, "not", &[e.as_ref()], schema)?,
+        Expr::IsNull(e) => scalar_fn_rex(ctx, "is_null", &[e.as_ref()], schema)?,
+        Expr::IsNotNull(e) => scalar_fn_rex(ctx, "is_not_null", &[e.as_ref()], schema)?,
+        Expr::Negative(e) => scalar_fn_rex(ctx, "negative", &[e.as_ref()], schema)?,
+        Expr::Alias(e, name) => {
+            let name = Expr::Literal(ScalarValue::Utf8(Some(name.clone())));
+            scalar_fn_rex(ctx, "alias", &[e.as_ref(), &name], schema)?
+        }
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            let fn_name = if *negated { "not_between" } else { "between" };
+            scalar_fn_rex(
+                ctx,
+                fn_name,
+                &[expr.as_ref(), low.as_ref(), high.as_ref()],
+                schema,
+            )?
+        }
+        Expr::Cast { expr, data_type } => {
+            let concrete_type = ConcreteDataType::try_from(data_type).map_err(|_| {
+                UnsupportedExprSnafu {
+                    name: format!("cast to {:?}", data_type),
+                }
+                .build()
+            })?;
+            RexType::Cast(Box::new(Cast {
+                r#type: Some(from_concrete_type(concrete_type, None)?),
+                input: Some(Box::new(expression_from_df_expr(ctx, expr, schema)?)),
+                ..Default::default()
+            }))
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let options = list
+                .iter()
+                .map(|e| expression_from_df_expr(ctx, e, schema))
+                .collect::<Result<_>>()?;
+            let rex = RexType::SingularOrList(Box::new(SingularOrList {
+                value: Some(Box::new(expression_from_df_expr(ctx, expr, schema)?)),
+                options,
+            }));
+            if *negated {
+                let function_reference = ctx.register_scalar_fn("not");
+                RexType::ScalarFunction(ScalarFunction {
+                    function_reference,
+                    arguments: vec![FunctionArgument {
+                        arg_type: Some(ArgType::Value(Expression {
+                            rex_type: Some(rex),
+                        })),
+                    }],
+                    ..Default::default()
+                })
+            } else {
+                rex
+            }
+        }
+        Expr::Case {
+            expr: base,
+            when_then_expr,
+            else_expr,
+        } => {
+            // `CASE base WHEN x THEN y` is encoded as `CASE WHEN base = x THEN y`.
+            let ifs = when_then_expr
+                .iter()
+                .map(|(when, then)| {
+                    let when = match base {
+                        Some(base) => Expr::BinaryExpr {
+                            left: base.clone(),
+                            op: Operator::Eq,
+                            right: when.clone(),
+                        },
+                        None => when.as_ref().clone(),
+                    };
+                    Ok(IfClause {
+                        r#if: Some(expression_from_df_expr(ctx, &when, schema)?),
+                        then: Some(expression_from_df_expr(ctx, then, schema)?),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let r#else = else_expr
+                .as_ref()
+                .map(|e| expression_from_df_expr(ctx, e, schema).map(Box::new))
+                .transpose()?;
+            RexType::IfThen(Box::new(IfThen { ifs, r#else }))
+        }
+        Expr::ScalarFunction { fun, args } => {
+            // Only functions that can be decoded by name are supported.
+            let fn_name = fun.to_string();
+            ensure!(
+                BuiltinScalarFunction::from_str(&fn_name).is_ok(),
+                UnsupportedExprSnafu {
+                    name: format!("scalar function {}", fun),
+                }
+            );
+            let args = args.iter().collect::<Vec<_>>();
+            scalar_fn_rex(ctx, &fn_name, &args, schema)?
+        }
+        Expr::ScalarUDF { fun, args } => {
+            ensure!(
+                FUNCTION_REGISTRY.get_function(&fun.name).is_some(),
+                UnsupportedExprSnafu {
+                    name: format!("unregistered scalar UDF {}", fun.name),
+                }
+            );
+            let args = args.iter().collect::<Vec<_>>();
+            scalar_fn_rex(ctx, &fun.name, &args, schema)?
+        }
+        _ => UnsupportedExprSnafu {
+            name: format!("DataFusion expression {:?}", expr),
+        }
+        .fail()?,
+    };
+
+    Ok(Expression {
+        rex_type: Some(rex_type),
+    })
+}
+
+/// Convert DataFusion's aggregate [Expr] to substrait's [AggregateFunction].
+pub(crate) fn aggregate_fn_from_df_expr(
+    ctx: &mut ConvertorContext,
+    expr: &Expr,
+    schema: &DFSchemaRef,
+) -> Result<AggregateFunction> {
+    match expr {
+        Expr::AggregateFunction {
+            fun,
+            args,
+            distinct: false,
+        } => {
+            // Only functions that can be decoded by name are supported.
+            let fn_name = fun.to_string().to_lowercase();
+            ensure!(
+                DfAggregateFunction::from_str(&fn_name).is_ok(),
+                UnsupportedExprSnafu {
+                    name: format!("aggregate function {}", fun),
+                }
+            );
+            let function_reference = ctx.register_scalar_fn(fn_name);
+            let arguments = build_function_arguments(ctx, args.iter(), schema)?;
+            Ok(AggregateFunction {
+                function_reference,
+                arguments,
+                ..Default::default()
+            })
+        }
+        Expr::AggregateUDF { fun, args } => {
+            ensure!(
+                FUNCTION_REGISTRY.get_aggr_function(&fun.name).is_some(),
+                UnsupportedExprSnafu {
+                    name: format!("unregistered aggregate UDF {}", fun.name),
+                }
+            );
+            let function_reference = ctx.register_scalar_fn(&fun.name);
+            let arguments = build_function_arguments(ctx, args.iter(), schema)?;
+            Ok(AggregateFunction {
+                function_reference,
+                arguments,
+                ..Default::default()
+            })
+        }
+        _ => UnsupportedExprSnafu {
+            name: format!("aggregate expression {:?}", expr),
+        }
+        .fail(),
+    }
+}
+
+fn field_reference(column: &Column, schema: &DFSchemaRef) -> Result<FieldReference> {
+    let index = schema.index_of_column(column).context(DFInternalSnafu)?;
+    Ok(FieldReference {
+        reference_type: Some(FieldReferenceType::DirectReference(ReferenceSegment {
+            reference_type: Some(SegReferenceType::StructField(Box::new(StructField {
+                field: index as i32,
+                child: None,
+            }))),
+        })),
+        root_type: None,
+    })
+}
+
+fn build_function_arguments<'a>(
+    ctx: &mut ConvertorContext,
+    args: impl Iterator<Item = &'a Expr>,
+    schema: &DFSchemaRef,
+) -> Result<Vec<FunctionArgument>> {
+    args.map(|arg| {
+        Ok(FunctionArgument {
+            arg_type: Some(ArgType::Value(expression_from_df_expr(ctx, arg, schema)?)),
+        })
+    })
+    .collect()
+}
+
+fn scalar_fn_rex(
+    ctx: &mut ConvertorContext,
+    fn_name: &str,
+    args: &[&Expr],
+    schema: &DFSchemaRef,
+) -> Result<RexType> {
+    let function_reference = ctx.register_scalar_fn(fn_name);
+    let arguments = build_function_arguments(ctx, args.iter().copied(), schema)?;
+    Ok(RexType::ScalarFunction(ScalarFunction {
+        function_reference,
+        arguments,
+        ..Default::default()
+    }))
+}
+
+fn op_to_name(op: &Operator) -> Option<&'static str> {
+    let name = match op {
+        Operator::Eq => "equal",
+        Operator::NotEq => "not_equal",
+        Operator::Lt => "lt",
+        Operator::LtEq => "lte",
+        Operator::Gt => "gt",
+        Operator::GtEq => "gte",
+        Operator::Plus => "add",
+        Operator::Minus => "subtract",
+        Operator::Multiply => "multiply",
+        Operator::Divide => "divide",
+        Operator::Modulo => "modulus",
+        Operator::And => "and",
+        Operator::Or => "or",
+        Operator::Like => "like",
+        Operator::NotLike => "not_like",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn name_to_op(name: &str) -> Option<Operator> {
+    let op = match name {
+        "equal" => Operator::Eq,
+        "not_equal" => Operator::NotEq,
+        "lt" => Operator::Lt,
+        "lte" => Operator::LtEq,
+        "gt" => Operator::Gt,
+        "gte" => Operator::GtEq,
+        "add" => Operator::Plus,
+        "subtract" => Operator::Minus,
+        "multiply" => Operator::Multiply,
+        "divide" => Operator::Divide,
+        "modulus" => Operator::Modulo,
+        "and" => Operator::And,
+        "or" => Operator::Or,
+        "like" => Operator::Like,
+        "not_like" => Operator::NotLike,
+        _ => return None,
+    };
+    Some(op)
+}
@@ -18,18 +18,36 @@ use bytes::{Buf, Bytes, BytesMut};
 use catalog::CatalogManagerRef;
 use common_error::prelude::BoxedError;
 use datafusion::datasource::TableProvider;
-use datafusion::logical_plan::{LogicalPlan, TableScan, ToDFSchema};
+use datafusion::logical_plan::plan::{
+    Aggregate, CrossJoin, Filter, Join, Limit, Projection, Sort, Union,
+};
+use datafusion::logical_plan::{
+    unnormalize_col, DFSchema, DFSchemaRef, Expr, JoinType, LogicalPlan, LogicalPlanBuilder,
+    Operator, TableScan, ToDFSchema,
+};
 use datafusion::physical_plan::project_schema;
 use prost::Message;
 use snafu::{ensure, OptionExt, ResultExt};
+use substrait_proto::protobuf::aggregate_rel::{Grouping, Measure};
 use substrait_proto::protobuf::expression::mask_expression::{StructItem, StructSelect};
 use substrait_proto::protobuf::expression::MaskExpression;
+use substrait_proto::protobuf::join_rel::JoinType as SJoinType;
 use substrait_proto::protobuf::plan_rel::RelType as PlanRelType;
 use substrait_proto::protobuf::read_rel::{NamedTable, ReadType};
 use substrait_proto::protobuf::rel::RelType;
-use substrait_proto::protobuf::{PlanRel, ReadRel, Rel};
+use substrait_proto::protobuf::rel_common::{Emit, EmitKind};
+use substrait_proto::protobuf::set_rel::SetOp;
+use substrait_proto::protobuf::sort_field::{SortDirection, SortKind};
+use substrait_proto::protobuf::{
+    AggregateRel, CrossRel, FetchRel, FilterRel, JoinRel, Plan, PlanRel, ProjectRel, ReadRel, Rel,
+    RelCommon, SetRel, SortField, SortRel,
+};
 use table::table::adapter::DfTableProviderAdapter;
 
+use crate::context::ConvertorContext;
+use crate::df_expr::{
+    aggregate_fn_from_df_expr, expression_from_df_expr, to_df_aggregate_expr, to_df_expr,
+};
 use crate::error::{
     DFInternalSnafu, DecodeRelSnafu, EmptyPlanSnafu, EncodeRelSnafu, Error, InternalSnafu,
     InvalidParametersSnafu, MissingFieldSnafu, SchemaNotMatchSnafu, TableNotFoundSnafu,
@@ -38,17 +56,21 @@ use crate::error::{
 use crate::schema::{from_schema, to_schema};
 use crate::SubstraitPlan;
 
-pub struct DFLogicalSubstraitConvertor {
-    catalog_manager: CatalogManagerRef,
-}
+pub struct DFLogicalSubstraitConvertor;
 
 impl SubstraitPlan for DFLogicalSubstraitConvertor {
     type Error = Error;
 
     type Plan = LogicalPlan;
 
-    fn decode<B: Buf + Send>(&self, message: B) -> Result<Self::Plan, Self::Error> {
-        let plan_rel = PlanRel::decode(message).context(DecodeRelSnafu)?;
+    fn decode<B: Buf + Send>(
+        &self,
+        message: B,
+        catalog_manager: CatalogManagerRef,
+    ) -> Result<Self::Plan, Self::Error> {
+        let plan = Plan::decode(message).context(DecodeRelSnafu)?;
+        let ctx = ConvertorContext::from_extensions(&plan.extensions);
+        let plan_rel = plan.relations.into_iter().next().context(EmptyPlanSnafu)?;
         let rel = match plan_rel.rel_type.context(EmptyPlanSnafu)? {
             PlanRelType::Rel(rel) => rel,
             PlanRelType::Root(_) => UnsupportedPlanSnafu {
@@ -56,61 +78,50 @@ impl SubstraitPlan for DFLogicalSubstraitConvertor {
             }
             .fail()?,
         };
-        self.convert_rel(rel)
+        self.convert_rel(&ctx, rel, &catalog_manager)
     }
 
     fn encode(&self, plan: Self::Plan) -> Result<Bytes, Self::Error> {
-        let rel = self.convert_plan(plan)?;
-        let plan_rel = PlanRel {
-            rel_type: Some(PlanRelType::Rel(rel)),
+        let mut ctx = ConvertorContext::default();
+        let rel = self.convert_plan(&mut ctx, plan)?;
+        let plan = Plan {
+            extensions: ctx.generate_function_extension(),
+            relations: vec![PlanRel {
+                rel_type: Some(PlanRelType::Rel(rel)),
+            }],
+            ..Default::default()
         };
 
         let mut buf = BytesMut::new();
-        plan_rel.encode(&mut buf).context(EncodeRelSnafu)?;
+        plan.encode(&mut buf).context(EncodeRelSnafu)?;
 
         Ok(buf.freeze())
     }
 }
 
 impl DFLogicalSubstraitConvertor {
-    pub fn new(catalog_manager: CatalogManagerRef) -> Self {
-        Self { catalog_manager }
-    }
-}
-
-impl DFLogicalSubstraitConvertor {
-    pub fn convert_rel(&self, rel: Rel) -> Result<LogicalPlan, Error> {
+    pub fn convert_rel(
+        &self,
+        ctx: &ConvertorContext,
+        rel: Rel,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
         let rel_type = rel.rel_type.context(EmptyPlanSnafu)?;
         let logical_plan = match rel_type {
-            RelType::Read(read_rel) => self.convert_read_rel(read_rel),
-            RelType::Filter(_filter_rel) => UnsupportedPlanSnafu {
-                name: "Filter Relation",
-            }
-            .fail()?,
-            RelType::Fetch(_fetch_rel) => UnsupportedPlanSnafu {
-                name: "Fetch Relation",
-            }
-            .fail()?,
-            RelType::Aggregate(_aggr_rel) => UnsupportedPlanSnafu {
-                name: "Fetch Relation",
+            RelType::Read(read_rel) => self.convert_read_rel(ctx, read_rel, catalog_manager),
+            RelType::Filter(filter_rel) => {
+                self.convert_filter_rel(ctx, filter_rel, catalog_manager)
             }
-            .fail()?,
-            RelType::Sort(_sort_rel) => UnsupportedPlanSnafu {
-                name: "Sort Relation",
+            RelType::Fetch(fetch_rel) => self.convert_fetch_rel(ctx, fetch_rel, catalog_manager),
+            RelType::Aggregate(aggr_rel) => {
+                self.convert_aggregate_rel(ctx, aggr_rel, catalog_manager)
             }
-            .fail()?,
-            RelType::Join(_join_rel) => UnsupportedPlanSnafu {
-                name: "Join Relation",
+            RelType::Sort(sort_rel) => self.convert_sort_rel(ctx, sort_rel, catalog_manager),
+            RelType::Join(join_rel) => self.convert_join_rel(ctx, join_rel, catalog_manager),
+            RelType::Project(project_rel) => {
+                self.convert_project_rel(ctx, project_rel, catalog_manager)
             }
-            .fail()?,
-            RelType::Project(_project_rel) => UnsupportedPlanSnafu {
-                name: "Project Relation",
-            }
-            .fail()?,
-            RelType::Set(_set_rel) => UnsupportedPlanSnafu {
-                name: "Set Relation",
-            }
-            .fail()?,
+            RelType::Set(set_rel) => self.convert_set_rel(ctx, set_rel, catalog_manager),
             RelType::ExtensionSingle(_ext_single_rel) => UnsupportedPlanSnafu {
                 name: "Extension Single Relation",
             }
@@ -123,16 +134,33 @@ impl DFLogicalSubstraitConvertor {
                 name: "Extension Leaf Relation",
             }
             .fail()?,
-            RelType::Cross(_cross_rel) => UnsupportedPlanSnafu {
-                name: "Cross Relation",
-            }
-            .fail()?,
+            RelType::Cross(cross_rel) => self.convert_cross_rel(ctx, cross_rel, catalog_manager),
         }?;
 
         Ok(logical_plan)
     }
 
-    fn convert_read_rel(&self, read_rel: Box<ReadRel>) -> Result<LogicalPlan, Error> {
+    /// Convert the required input relation of a substrait relation.
+    fn convert_input_rel(
+        &self,
+        ctx: &ConvertorContext,
+        input: Option<Box<Rel>>,
+        plan: &str,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = input.context(MissingFieldSnafu {
+            field: "input",
+            plan,
+        })?;
+        self.convert_rel(ctx, *input, catalog_manager)
+    }
+
+    fn convert_read_rel(
+        &self,
+        ctx: &ConvertorContext,
+        read_rel: Box<ReadRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
         // Extract the catalog, schema and table name from NamedTable. Assume the first three are those names.
 
         let read_type = read_rel.read_type.context(MissingFieldSnafu {
@@ -168,8 +196,7 @@ impl DFLogicalSubstraitConvertor {
             .map(|mask_expr| self.convert_mask_expression(mask_expr));
 
         // Get table handle from catalog manager
-        let table_ref = self
-            .catalog_manager
+        let table_ref = catalog_manager
             .table(&catalog_name, &schema_name, &table_name)
             .map_err(BoxedError::new)
             .context(InternalSnafu)?
@@ -179,34 +206,311 @@ impl DFLogicalSubstraitConvertor {
         let adapter = Arc::new(DfTableProviderAdapter::new(table_ref));
 
         // Get schema directly from the table, and compare it with the schema retrived from substrait proto.
+        // Only names and types are compared, nullability and metadata of fields (like time index)
+        // may differ between the table schema of frontend and datanode.
         let stored_schema = adapter.schema();
         let retrived_schema = to_schema(read_rel.base_schema.unwrap_or_default())?;
         let retrived_arrow_schema = retrived_schema.arrow_schema();
         ensure!(
-            stored_schema.fields == retrived_arrow_schema.fields,
+            stored_schema.fields.len() == retrived_arrow_schema.fields.len()
+                && stored_schema
+                    .fields
+                    .iter()
+                    .zip(retrived_arrow_schema.fields.iter())
+                    .all(|(stored, retrived)| {
+                        stored.name == retrived.name && stored.data_type == retrived.data_type
+                    }),
             SchemaNotMatchSnafu {
                 substrait_schema: retrived_arrow_schema.clone(),
                 storage_schema: stored_schema
             }
         );
 
+        // Filters are encoded against the whole table schema.
+        let filters = match read_rel.filter {
+            Some(filter) => {
+                let table_schema = Arc::new(
+                    DFSchema::try_from_qualified_schema(&table_name, &stored_schema)
+                        .context(DFInternalSnafu)?,
+                );
+                let filter = to_df_expr(ctx, *filter, &table_schema)?;
+                split_conjunction(filter)
+            }
+            None => vec![],
+        };
+
         // Calculate the projected schema
-        let projected_schema = project_schema(&stored_schema, projection.as_ref())
-            .context(DFInternalSnafu)?
-            .to_dfschema_ref()
-            .context(DFInternalSnafu)?;
+        let projected_schema =
+            project_schema(&stored_schema, projection.as_ref()).context(DFInternalSnafu)?;
+        let projected_schema = Arc::new(
+            DFSchema::try_from_qualified_schema(&table_name, &projected_schema)
+                .context(DFInternalSnafu)?,
+        );
 
-        // TODO(ruihang): Support filters and limit
         Ok(LogicalPlan::TableScan(TableScan {
             table_name,
             source: adapter,
             projection,
             projected_schema,
-            filters: vec![],
+            filters,
             limit: None,
         }))
     }
 
+    fn convert_filter_rel(
+        &self,
+        ctx: &ConvertorContext,
+        filter_rel: Box<FilterRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = self.convert_input_rel(ctx, filter_rel.input, "Filter", catalog_manager)?;
+        let condition = filter_rel.condition.context(MissingFieldSnafu {
+            field: "condition",
+            plan: "Filter",
+        })?;
+        let predicate = to_df_expr(ctx, *condition, input.schema())?;
+
+        LogicalPlanBuilder::from(input)
+            .filter(predicate)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_project_rel(
+        &self,
+        ctx: &ConvertorContext,
+        project_rel: Box<ProjectRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = self.convert_input_rel(ctx, project_rel.input, "Project", catalog_manager)?;
+        let exprs = project_rel
+            .expressions
+            .into_iter()
+            .map(|expr| to_df_expr(ctx, expr, input.schema()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        LogicalPlanBuilder::from(input)
+            .project(exprs)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_fetch_rel(
+        &self,
+        ctx: &ConvertorContext,
+        fetch_rel: Box<FetchRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = self.convert_input_rel(ctx, fetch_rel.input, "Fetch", catalog_manager)?;
+        ensure!(
+            fetch_rel.offset == 0,
+            UnsupportedPlanSnafu {
+                name: "Fetch Relation with offset",
+            }
+        );
+        ensure!(
+            fetch_rel.count >= 0,
+            InvalidParametersSnafu {
+                reason: format!("Invalid fetch count {}", fetch_rel.count),
+            }
+        );
+
+        LogicalPlanBuilder::from(input)
+            .limit(fetch_rel.count as usize)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_aggregate_rel(
+        &self,
+        ctx: &ConvertorContext,
+        aggr_rel: Box<AggregateRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = self.convert_input_rel(ctx, aggr_rel.input, "Aggregate", catalog_manager)?;
+        ensure!(
+            aggr_rel.groupings.len() <= 1,
+            UnsupportedPlanSnafu {
+                name: "Aggregate Relation with multiple groupings",
+            }
+        );
+
+        let group_exprs = aggr_rel
+            .groupings
+            .into_iter()
+            .flat_map(|grouping| grouping.grouping_expressions)
+            .map(|expr| to_df_expr(ctx, expr, input.schema()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggr_exprs = aggr_rel
+            .measures
+            .into_iter()
+            .map(|measure| {
+                let aggregate_fn = measure.measure.context(MissingFieldSnafu {
+                    field: "measure",
+                    plan: "Aggregate",
+                })?;
+                to_df_aggregate_expr(ctx, aggregate_fn, input.schema())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        LogicalPlanBuilder::from(input)
+            .aggregate(group_exprs, aggr_exprs)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_sort_rel(
+        &self,
+        ctx: &ConvertorContext,
+        sort_rel: Box<SortRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let input = self.convert_input_rel(ctx, sort_rel.input, "Sort", catalog_manager)?;
+        let sort_exprs = sort_rel
+            .sorts
+            .into_iter()
+            .map(|sort_field| {
+                let expr = sort_field.expr.context(MissingFieldSnafu {
+                    field: "expr",
+                    plan: "Sort",
+                })?;
+                let expr = to_df_expr(ctx, expr, input.schema())?;
+                let direction = match sort_field.sort_kind {
+                    Some(SortKind::Direction(direction)) => SortDirection::from_i32(direction),
+                    _ => None,
+                };
+                let (asc, nulls_first) = match direction {
+                    Some(SortDirection::AscNullsFirst) => (true, true),
+                    Some(SortDirection::AscNullsLast) => (true, false),
+                    Some(SortDirection::DescNullsFirst) => (false, true),
+                    Some(SortDirection::DescNullsLast) => (false, false),
+                    _ => UnsupportedPlanSnafu {
+                        name: format!("Sort Relation with sort kind {:?}", sort_field.sort_kind),
+                    }
+                    .fail()?,
+                };
+                Ok(Expr::Sort {
+                    expr: Box::new(expr),
+                    asc,
+                    nulls_first,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        LogicalPlanBuilder::from(input)
+            .sort(sort_exprs)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_join_rel(
+        &self,
+        ctx: &ConvertorContext,
+        join_rel: Box<JoinRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        ensure!(
+            join_rel.post_join_filter.is_none(),
+            UnsupportedPlanSnafu {
+                name: "Join Relation with post join filter",
+            }
+        );
+        let join_type = match SJoinType::from_i32(join_rel.r#type) {
+            Some(SJoinType::Inner) => JoinType::Inner,
+            Some(SJoinType::Outer) => JoinType::Full,
+            Some(SJoinType::Left) => JoinType::Left,
+            Some(SJoinType::Right) => JoinType::Right,
+            Some(SJoinType::Semi) => JoinType::Semi,
+            Some(SJoinType::Anti) => JoinType::Anti,
+            _ => UnsupportedPlanSnafu {
+                name: format!("Join Relation with join type {}", join_rel.r#type),
+            }
+            .fail()?,
+        };
+        let left = self.convert_input_rel(ctx, join_rel.left, "Join", catalog_manager)?;
+        let right = self.convert_input_rel(ctx, join_rel.right, "Join", catalog_manager)?;
+        let expression = join_rel.expression.context(MissingFieldSnafu {
+            field: "expression",
+            plan: "Join",
+        })?;
+
+        // The join condition references fields of both inputs, left first.
+        let join_schema = join_input_schema(&left, &right)?;
+        let condition = to_df_expr(ctx, *expression, &join_schema)?;
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        for expr in split_conjunction(condition) {
+            let (left_key, right_key) = match expr {
+                Expr::BinaryExpr {
+                    left,
+                    op: Operator::Eq,
+                    right,
+                } => match (*left, *right) {
+                    (Expr::Column(left), Expr::Column(right)) => (left, right),
+                    (left, right) => UnsupportedExprSnafu {
+                        name: format!("join condition {:?} = {:?}", left, right),
+                    }
+                    .fail()?,
+                },
+                expr => UnsupportedExprSnafu {
+                    name: format!("join condition {:?}", expr),
+                }
+                .fail()?,
+            };
+            left_keys.push(left_key);
+            right_keys.push(right_key);
+        }
+
+        LogicalPlanBuilder::from(left)
+            .join(&right, join_type, (left_keys, right_keys))
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_cross_rel(
+        &self,
+        ctx: &ConvertorContext,
+        cross_rel: Box<CrossRel>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        let left = self.convert_input_rel(ctx, cross_rel.left, "Cross", catalog_manager)?;
+        let right = self.convert_input_rel(ctx, cross_rel.right, "Cross", catalog_manager)?;
+
+        LogicalPlanBuilder::from(left)
+            .cross_join(&right)
+            .and_then(|builder| builder.build())
+            .context(DFInternalSnafu)
+    }
+
+    fn convert_set_rel(
+        &self,
+        ctx: &ConvertorContext,
+        set_rel: SetRel,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<LogicalPlan, Error> {
+        ensure!(
+            set_rel.op == SetOp::UnionAll as i32,
+            UnsupportedPlanSnafu {
+                name: format!("Set Relation with operation {}", set_rel.op),
+            }
+        );
+        let mut inputs = set_rel
+            .inputs
+            .into_iter()
+            .map(|input| self.convert_rel(ctx, input, catalog_manager));
+        let first = inputs.next().context(MissingFieldSnafu {
+            field: "inputs",
+            plan: "Set",
+        })??;
+
+        inputs
+            .try_fold(LogicalPlanBuilder::from(first), |builder, input| {
+                builder.union(input?).context(DFInternalSnafu)
+            })?
+            .build()
+            .context(DFInternalSnafu)
+    }
+
     fn convert_mask_expression(&self, mask_expression: MaskExpression) -> Vec<usize> {
         mask_expression
             .select
@@ -219,58 +523,61 @@ impl DFLogicalSubstraitConvertor {
 }
 
 impl DFLogicalSubstraitConvertor {
-    pub fn convert_plan(&self, plan: LogicalPlan) -> Result<Rel, Error> {
-        match plan {
-            LogicalPlan::Projection(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Projection",
+    pub fn convert_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        plan: LogicalPlan,
+    ) -> Result<Rel, Error> {
+        let rel_type = match plan {
+            LogicalPlan::Projection(projection) => {
+                RelType::Project(Box::new(self.convert_projection_plan(ctx, projection)?))
             }
-            .fail()?,
-            LogicalPlan::Filter(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Filter",
+            LogicalPlan::Filter(filter) => {
+                RelType::Filter(Box::new(self.convert_filter_plan(ctx, filter)?))
             }
-            .fail()?,
             LogicalPlan::Window(_) => UnsupportedPlanSnafu {
                 name: "DataFusion Logical Window",
             }
             .fail()?,
-            LogicalPlan::Aggregate(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Aggregate",
-            }
-            .fail()?,
-            LogicalPlan::Sort(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Sort",
-            }
-            .fail()?,
-            LogicalPlan::Join(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Join",
+            LogicalPlan::Aggregate(aggregate) => {
+                RelType::Aggregate(Box::new(self.convert_aggregate_plan(ctx, aggregate)?))
             }
-            .fail()?,
-            LogicalPlan::CrossJoin(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical CrossJoin",
+            LogicalPlan::Sort(sort) => RelType::Sort(Box::new(self.convert_sort_plan(ctx, sort)?)),
+            LogicalPlan::Join(join) => RelType::Join(Box::new(self.convert_join_plan(ctx, join)?)),
+            LogicalPlan::CrossJoin(cross_join) => {
+                RelType::Cross(Box::new(self.convert_cross_join_plan(ctx, cross_join)?))
             }
-            .fail()?,
             LogicalPlan::Repartition(_) => UnsupportedPlanSnafu {
                 name: "DataFusion Logical Repartition",
             }
             .fail()?,
-            LogicalPlan::Union(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Union",
-            }
-            .fail()?,
+            LogicalPlan::Union(union) => RelType::Set(self.convert_union_plan(ctx, union)?),
             LogicalPlan::TableScan(table_scan) => {
-                let read_rel = self.convert_table_scan_plan(table_scan)?;
-                Ok(Rel {
-                    rel_type: Some(RelType::Read(Box::new(read_rel))),
-                })
+                let limit = table_scan.limit;
+                let read_rel = Rel {
+                    rel_type: Some(RelType::Read(Box::new(
+                        self.convert_table_scan_plan(ctx, table_scan)?,
+                    ))),
+                };
+                // ReadRel cannot carry a limit, wrap it with a FetchRel.
+                match limit {
+                    Some(limit) => RelType::Fetch(Box::new(FetchRel {
+                        common: None,
+                        input: Some(Box::new(read_rel)),
+                        offset: 0,
+                        count: limit as i64,
+                        advanced_extension: None,
+                    })),
+                    None => return Ok(read_rel),
+                }
             }
             LogicalPlan::EmptyRelation(_) => UnsupportedPlanSnafu {
                 name: "DataFusion Logical EmptyRelation",
             }
             .fail()?,
-            LogicalPlan::Limit(_) => UnsupportedPlanSnafu {
-                name: "DataFusion Logical Limit",
+            LogicalPlan::Limit(limit) => {
+                RelType::Fetch(Box::new(self.convert_limit_plan(ctx, limit)?))
             }
-            .fail()?,
             LogicalPlan::CreateExternalTable(_)
             | LogicalPlan::CreateMemoryTable(_)
             | LogicalPlan::DropTable(_)
@@ -284,10 +591,243 @@ impl DFLogicalSubstraitConvertor {
                 ),
             }
             .fail()?,
-        }
+        };
+
+        Ok(Rel {
+            rel_type: Some(rel_type),
+        })
+    }
+
+    fn convert_input_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        input: &LogicalPlan,
+    ) -> Result<Option<Box<Rel>>, Error> {
+        Ok(Some(Box::new(self.convert_plan(ctx, input.clone())?)))
+    }
+
+    pub fn convert_projection_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        projection: Projection,
+    ) -> Result<ProjectRel, Error> {
+        ensure!(
+            projection.alias.is_none(),
+            UnsupportedPlanSnafu {
+                name: "DataFusion Logical Projection with alias",
+            }
+        );
+        let input_schema = projection.input.schema();
+        let expressions = projection
+            .expr
+            .iter()
+            .map(|expr| expression_from_df_expr(ctx, expr, input_schema))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Substrait appends the projected expressions to the input fields. Only emit the
+        // projected ones.
+        let input_len = input_schema.fields().len() as i32;
+        let output_mapping = (input_len..input_len + expressions.len() as i32).collect();
+        let common = RelCommon {
+            emit_kind: Some(EmitKind::Emit(Emit { output_mapping })),
+            ..Default::default()
+        };
+
+        Ok(ProjectRel {
+            common: Some(common),
+            input: self.convert_input_plan(ctx, &projection.input)?,
+            expressions,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_filter_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        filter: Filter,
+    ) -> Result<FilterRel, Error> {
+        let condition = expression_from_df_expr(ctx, &filter.predicate, filter.input.schema())?;
+
+        Ok(FilterRel {
+            common: None,
+            input: self.convert_input_plan(ctx, &filter.input)?,
+            condition: Some(Box::new(condition)),
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_limit_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        limit: Limit,
+    ) -> Result<FetchRel, Error> {
+        Ok(FetchRel {
+            common: None,
+            input: self.convert_input_plan(ctx, &limit.input)?,
+            offset: 0,
+            count: limit.n as i64,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_aggregate_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        aggregate: Aggregate,
+    ) -> Result<AggregateRel, Error> {
+        let input_schema = aggregate.input.schema();
+        let grouping_expressions = aggregate
+            .group_expr
+            .iter()
+            .map(|expr| expression_from_df_expr(ctx, expr, input_schema))
+            .collect::<Result<Vec<_>, _>>()?;
+        let groupings = if grouping_expressions.is_empty() {
+            vec![]
+        } else {
+            vec![Grouping {
+                grouping_expressions,
+            }]
+        };
+        let measures = aggregate
+            .aggr_expr
+            .iter()
+            .map(|expr| {
+                Ok(Measure {
+                    measure: Some(aggregate_fn_from_df_expr(ctx, expr, input_schema)?),
+                    filter: None,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(AggregateRel {
+            common: None,
+            input: self.convert_input_plan(ctx, &aggregate.input)?,
+            groupings,
+            measures,
+            advanced_extension: None,
+        })
     }
 
-    pub fn convert_table_scan_plan(&self, table_scan: TableScan) -> Result<ReadRel, Error> {
+    pub fn convert_sort_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        sort: Sort,
+    ) -> Result<SortRel, Error> {
+        let input_schema = sort.input.schema();
+        let sorts = sort
+            .expr
+            .iter()
+            .map(|expr| match expr {
+                Expr::Sort {
+                    expr,
+                    asc,
+                    nulls_first,
+                } => {
+                    let direction = match (asc, nulls_first) {
+                        (true, true) => SortDirection::AscNullsFirst,
+                        (true, false) => SortDirection::AscNullsLast,
+                        (false, true) => SortDirection::DescNullsFirst,
+                        (false, false) => SortDirection::DescNullsLast,
+                    };
+                    Ok(SortField {
+                        expr: Some(expression_from_df_expr(ctx, expr, input_schema)?),
+                        sort_kind: Some(SortKind::Direction(direction as i32)),
+                    })
+                }
+                _ => UnsupportedExprSnafu {
+                    name: format!("sort expression {:?}", expr),
+                }
+                .fail(),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(SortRel {
+            common: None,
+            input: self.convert_input_plan(ctx, &sort.input)?,
+            sorts,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_join_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        join: Join,
+    ) -> Result<JoinRel, Error> {
+        let r#type = match join.join_type {
+            JoinType::Inner => SJoinType::Inner,
+            JoinType::Full => SJoinType::Outer,
+            JoinType::Left => SJoinType::Left,
+            JoinType::Right => SJoinType::Right,
+            JoinType::Semi => SJoinType::Semi,
+            JoinType::Anti => SJoinType::Anti,
+        } as i32;
+
+        let join_schema = join_input_schema(&join.left, &join.right)?;
+        let condition = conjunction(join.on.into_iter().map(|(left, right)| Expr::BinaryExpr {
+            left: Box::new(Expr::Column(left)),
+            op: Operator::Eq,
+            right: Box::new(Expr::Column(right)),
+        }))
+        .context(InvalidParametersSnafu {
+            reason: "Join without condition",
+        })?;
+        let expression = expression_from_df_expr(ctx, &condition, &join_schema)?;
+
+        Ok(JoinRel {
+            common: None,
+            left: self.convert_input_plan(ctx, &join.left)?,
+            right: self.convert_input_plan(ctx, &join.right)?,
+            expression: Some(Box::new(expression)),
+            post_join_filter: None,
+            r#type,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_cross_join_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        cross_join: CrossJoin,
+    ) -> Result<CrossRel, Error> {
+        Ok(CrossRel {
+            common: None,
+            left: self.convert_input_plan(ctx, &cross_join.left)?,
+            right: self.convert_input_plan(ctx, &cross_join.right)?,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_union_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        union: Union,
+    ) -> Result<SetRel, Error> {
+        ensure!(
+            union.alias.is_none(),
+            UnsupportedPlanSnafu {
+                name: "DataFusion Logical Union with alias",
+            }
+        );
+        let inputs = union
+            .inputs
+            .into_iter()
+            .map(|input| self.convert_plan(ctx, input))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SetRel {
+            common: None,
+            inputs,
+            op: SetOp::UnionAll as i32,
+            advanced_extension: None,
+        })
+    }
+
+    pub fn convert_table_scan_plan(
+        &self,
+        ctx: &mut ConvertorContext,
+        table_scan: TableScan,
+    ) -> Result<ReadRel, Error> {
         let provider = table_scan
             .source
             .as_any()
@@ -313,10 +853,27 @@ impl DFLogicalSubstraitConvertor {
         // assemble base (unprojected) schema using Table's schema.
         let base_schema = from_schema(&provider.table().schema())?;
 
+        // assemble filters, the qualifiers of columns are dropped as the table may be scanned
+        // under another name.
+        let filter = match conjunction(table_scan.filters.into_iter().map(unnormalize_col)) {
+            Some(filter) => {
+                let table_schema = provider
+                    .schema()
+                    .to_dfschema_ref()
+                    .context(DFInternalSnafu)?;
+                Some(Box::new(expression_from_df_expr(
+                    ctx,
+                    &filter,
+                    &table_schema,
+                )?))
+            }
+            None => None,
+        };
+
         let read_rel = ReadRel {
             common: None,
             base_schema: Some(base_schema),
-            filter: None,
+            filter,
             projection,
             advanced_extension: None,
             read_type: Some(read_type),
@@ -342,12 +899,51 @@ impl DFLogicalSubstraitConvertor {
     }
 }
 
+/// Schema that join conditions are resolved against: fields of the left input followed by
+/// fields of the right input.
+fn join_input_schema(left: &LogicalPlan, right: &LogicalPlan) -> Result<DFSchemaRef, Error> {
+    left.schema()
+        .join(right.schema())
+        .map(Arc::new)
+        .context(DFInternalSnafu)
+}
+
+/// Combine expressions with `AND`.
+fn conjunction(exprs: impl Iterator<Item = Expr>) -> Option<Expr> {
+    exprs.reduce(|acc, expr| Expr::BinaryExpr {
+        left: Box::new(acc),
+        op: Operator::And,
+        right: Box::new(expr),
+    })
+}
+
+/// Split an expression combined by `AND` into a list of expressions, inverse of [conjunction].
+fn split_conjunction(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        } => {
+            let mut exprs = split_conjunction(*left);
+            exprs.push(*right);
+            exprs
+        }
+        expr => vec![expr],
+    }
+}
+
 #[cfg(test)]
 mod test {
     use catalog::local::{LocalCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
     use catalog::{CatalogList, CatalogProvider, RegisterTableRequest};
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
-    use datafusion::logical_plan::DFSchema;
+    use common_function::scalars::udf::create_udf;
+    use common_function::scalars::FUNCTION_REGISTRY;
+    use common_query::logical_plan::create_aggregate_function;
+    use datafusion::arrow::datatypes::DataType as ArrowDataType;
+    use datafusion::logical_plan::{abs, avg, col, count, lit, max, min, sum, when, Column};
+    use datafusion::physical_plan::udaf::AggregateUDF as DfAggregateUdf;
     use datatypes::schema::Schema;
     use table::requests::CreateTableRequest;
     use table::test_util::{EmptyTable, MockTableEngine};
@@ -377,9 +973,9 @@ mod test {
         catalog_manager
     }
 
-    fn build_create_table_request<N: ToString>(table_name: N) -> CreateTableRequest {
+    fn build_create_table_request<N: ToString>(table_name: N, table_id: u32) -> CreateTableRequest {
         CreateTableRequest {
-            id: 1,
+            id: table_id,
             catalog_name: DEFAULT_CATALOG_NAME.to_string(),
             schema_name: DEFAULT_SCHEMA_NAME.to_string(),
             table_name: table_name.to_string(),
@@ -393,31 +989,49 @@ mod test {
     }
 
     async fn logical_plan_round_trip(plan: LogicalPlan, catalog: CatalogManagerRef) {
-        let convertor = DFLogicalSubstraitConvertor::new(catalog);
+        let convertor = DFLogicalSubstraitConvertor;
 
         let proto = convertor.encode(plan.clone()).unwrap();
-        let tripped_plan = convertor.decode(proto).unwrap();
+        let tripped_plan = convertor.decode(proto, catalog).unwrap();
 
         assert_eq!(format!("{:?}", plan), format!("{:?}", tripped_plan));
     }
 
-    #[tokio::test]
-    async fn test_table_scan() {
-        let catalog_manager = build_mock_catalog_manager().await;
+    async fn register_mock_table(
+        catalog_manager: &CatalogManagerRef,
+    ) -> Arc<DfTableProviderAdapter> {
+        register_mock_table_with_name(catalog_manager, DEFAULT_TABLE_NAME, 1).await
+    }
+
+    async fn register_mock_table_with_name(
+        catalog_manager: &CatalogManagerRef,
+        table_name: &str,
+        table_id: u32,
+    ) -> Arc<DfTableProviderAdapter> {
         let table_ref = Arc::new(EmptyTable::new(build_create_table_request(
-            DEFAULT_TABLE_NAME,
+            table_name, table_id,
         )));
         catalog_manager
             .register_table(RegisterTableRequest {
                 catalog: DEFAULT_CATALOG_NAME.to_string(),
                 schema: DEFAULT_SCHEMA_NAME.to_string(),
-                table_name: DEFAULT_TABLE_NAME.to_string(),
-                table_id: 1,
+                table_name: table_name.to_string(),
+                table_id,
                 table: table_ref.clone(),
             })
             .await
             .unwrap();
-        let adapter = Arc::new(DfTableProviderAdapter::new(table_ref));
+        Arc::new(DfTableProviderAdapter::new(table_ref))
+    }
+
+    fn table_column(name: &str) -> Expr {
+        Expr::Column(Column::new(Some(DEFAULT_TABLE_NAME), name))
+    }
+
+    #[tokio::test]
+    async fn test_table_scan() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
         let projection = vec![1, 3, 5];
         let df_schema = adapter.schema().to_dfschema().unwrap();
         let projected_fields = projection
@@ -438,4 +1052,214 @@ mod test {
 
         logical_plan_round_trip(table_scan_plan, catalog_manager).await;
     }
+
+    #[tokio::test]
+    async fn test_table_scan_with_filters() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
+
+        let plan = LogicalPlanBuilder::scan_with_filters(
+            DEFAULT_TABLE_NAME,
+            adapter,
+            Some(vec![1, 3, 5]),
+            vec![
+                table_column("Int64").gt(lit(1i64)),
+                table_column("String")
+                    .eq(lit("greptime"))
+                    .or(table_column("UInt32").is_null()),
+            ],
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
+
+    #[tokio::test]
+    async fn test_filter_projection_and_limit() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, adapter, None)
+            .unwrap()
+            .filter(
+                col("Int32")
+                    .lt_eq(lit(100i32))
+                    .and(Expr::Not(Box::new(col("Boolean")))),
+            )
+            .unwrap()
+            .project(vec![col("Int32"), col("Int64") + lit(1i64)])
+            .unwrap()
+            .sort(vec![col("Int32").sort(false, true)])
+            .unwrap()
+            .limit(10)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
+
+    #[tokio::test]
+    async fn test_aggregate() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, adapter.clone(), None)
+            .unwrap()
+            .filter(col("UInt8").not_eq(lit(0u8)))
+            .unwrap()
+            .aggregate(
+                vec![col("Int32"), col("String")],
+                vec![
+                    count(col("Int64")),
+                    sum(col("Float64")),
+                    min(col("UInt16")),
+                    max(col("UInt16")),
+                    avg(col("Float32")),
+                ],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        logical_plan_round_trip(plan, catalog_manager.clone()).await;
+
+        // aggregate without group by
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, adapter, None)
+            .unwrap()
+            .aggregate(Vec::<Expr>::new(), vec![count(col("Int64"))])
+            .unwrap()
+            .build()
+            .unwrap();
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
+
+    #[tokio::test]
+    async fn test_scalar_expressions() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
+        let clip = FUNCTION_REGISTRY.get_function("clip").unwrap();
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, adapter, None)
+            .unwrap()
+            .filter(
+                Expr::Between {
+                    expr: Box::new(col("Int32")),
+                    negated: false,
+                    low: Box::new(lit(1i32)),
+                    high: Box::new(lit(10i32)),
+                }
+                .and(col("Int64").in_list(vec![lit(1i64), lit(2i64)], true)),
+            )
+            .unwrap()
+            .project(vec![
+                Expr::Cast {
+                    expr: Box::new(col("Int32")),
+                    data_type: ArrowDataType::Int64,
+                }
+                .alias("cast_int"),
+                abs(col("Float64")),
+                Expr::ScalarUDF {
+                    fun: Arc::new(create_udf(clip).into_df_udf()),
+                    args: vec![col("Float64"), lit(0f64), lit(1f64)],
+                },
+                when(col("Boolean"), lit(1i32))
+                    .otherwise(lit(0i32))
+                    .unwrap()
+                    .alias("case_when"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_udf() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let adapter = register_mock_table(&catalog_manager).await;
+        let median = FUNCTION_REGISTRY.get_aggr_function("median").unwrap();
+        let median: DfAggregateUdf =
+            create_aggregate_function(median.name(), median.args_count(), median.create()).into();
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, adapter, None)
+            .unwrap()
+            .aggregate(
+                vec![col("String")],
+                vec![Expr::AggregateUDF {
+                    fun: Arc::new(median),
+                    args: vec![col("Int32")],
+                }],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
+
+    #[tokio::test]
+    async fn test_join_and_union() {
+        let catalog_manager = build_mock_catalog_manager().await;
+        let left = register_mock_table(&catalog_manager).await;
+        let right_table_name = "SubstraitTable2";
+        let right = register_mock_table_with_name(&catalog_manager, right_table_name, 2).await;
+
+        let right_plan = LogicalPlanBuilder::scan(right_table_name, right.clone(), None)
+            .unwrap()
+            .build()
+            .unwrap();
+        for join_type in [
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::Semi,
+            JoinType::Anti,
+        ] {
+            let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, left.clone(), None)
+                .unwrap()
+                .join(
+                    &right_plan,
+                    join_type,
+                    (
+                        vec![
+                            Column::new(Some(DEFAULT_TABLE_NAME), "Int32"),
+                            Column::new(Some(DEFAULT_TABLE_NAME), "String"),
+                        ],
+                        vec![
+                            Column::new(Some(right_table_name), "Int32"),
+                            Column::new(Some(right_table_name), "String"),
+                        ],
+                    ),
+                )
+                .unwrap()
+                .build()
+                .unwrap();
+            logical_plan_round_trip(plan, catalog_manager.clone()).await;
+        }
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, left.clone(), None)
+            .unwrap()
+            .cross_join(&right_plan)
+            .unwrap()
+            .build()
+            .unwrap();
+        logical_plan_round_trip(plan, catalog_manager.clone()).await;
+
+        let plan = LogicalPlanBuilder::scan(DEFAULT_TABLE_NAME, left, Some(vec![1, 3]))
+            .unwrap()
+            .union(
+                LogicalPlanBuilder::scan(right_table_name, right, Some(vec![1, 3]))
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        logical_plan_round_trip(plan, catalog_manager).await;
+    }
 }
@@ -53,11 +53,11 @@ pub fn from_schema(schema: &Schema) -> Result<NamedStruct> {
         types.push(substrait_type);
     }
 
-    // TODO(ruihang): `type_variation_reference` and `nullability` are unspecified.
+    // The struct itself (i.e. a row) is always present, only its fields can be null.
     let substrait_struct = SubstraitStruct {
         types,
         type_variation_reference: 0,
-        nullability: Nullability::Unspecified as _,
+        nullability: Nullability::Required as _,
     };
 
     Ok(NamedStruct {
@@ -68,12 +68,13 @@ pub fn from_schema(schema: &Schema) -> Result<NamedStruct> {
 
 #[cfg(test)]
 pub(crate) mod test {
+    use common_time::timestamp::TimeUnit;
     use datatypes::prelude::{ConcreteDataType, DataType};
 
     use super::*;
 
     pub(crate) fn supported_types() -> Vec<ColumnSchema> {
-        [
+        let mut column_schemas: Vec<_> = [
             ConcreteDataType::null_datatype(),
             ConcreteDataType::boolean_datatype(),
             ConcreteDataType::int8_datatype(),
@@ -90,12 +91,44 @@ pub(crate) mod test {
             ConcreteDataType::string_datatype(),
             ConcreteDataType::date_datatype(),
             ConcreteDataType::timestamp_datatype(Default::default()),
-            // TODO(ruihang): DateTime and List type are not supported now
+            ConcreteDataType::datetime_datatype(),
         ]
         .into_iter()
         .enumerate()
         .map(|(ordinal, ty)| ColumnSchema::new(ty.name().to_string(), ty, ordinal % 2 == 0))
-        .collect()
+        .collect();
+
+        // Types whose names are duplicated with the types above.
+        column_schemas.extend([
+            ColumnSchema::new(
+                "TimestampSecond",
+                ConcreteDataType::timestamp_datatype(TimeUnit::Second),
+                true,
+            ),
+            ColumnSchema::new(
+                "TimestampMicrosecond",
+                ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond),
+                false,
+            ),
+            ColumnSchema::new(
+                "TimestampNanosecond",
+                ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+                true,
+            ),
+            ColumnSchema::new(
+                "ListInt32",
+                ConcreteDataType::list_datatype(ConcreteDataType::int32_datatype()),
+                false,
+            ),
+            ColumnSchema::new(
+                "ListListString",
+                ConcreteDataType::list_datatype(ConcreteDataType::list_datatype(
+                    ConcreteDataType::string_datatype(),
+                )),
+                true,
+            ),
+        ]);
+        column_schemas
     }
 
     #[test]
@@ -15,14 +15,26 @@
 //! Methods that perform convertion between Substrait's type ([Type](SType)) and GreptimeDB's type ([ConcreteDataType]).
 //!
 //! Substrait use [type variation](https://substrait.io/types/type_variations/) to express different "logical types".
-//! Current we only have variations on integer types. Variation 0 (system prefered) are the same with base types, which
-//! are signed integer (i.e. I8 -> [i8]), and Variation 1 stands for unsigned integer (i.e. I8 -> [u8]).
+//! Current we have variations on integer, timestamp and date types:
+//! - For integer types, variation 0 (system prefered) are the same with base types, which are signed integer
+//!   (i.e. I8 -> [i8]), and Variation 1 stands for unsigned integer (i.e. I8 -> [u8]).
+//! - For timestamp type, variation 0 stands for millisecond precision, 1, 2 and 3 stand for second, microsecond
+//!   and nanosecond precisions.
+//! - For date type, variation 0 stands for date, and variation 1 stands for datetime.
 
+use common_time::timestamp::TimeUnit;
+use datafusion::scalar::ScalarValue;
 use datatypes::prelude::ConcreteDataType;
+use snafu::OptionExt;
+use substrait_proto::protobuf::expression::literal::LiteralType;
+use substrait_proto::protobuf::expression::Literal;
 use substrait_proto::protobuf::r#type::{self as s_type, Kind, Nullability};
 use substrait_proto::protobuf::Type as SType;
 
-use crate::error::{Result, UnsupportedConcreteTypeSnafu, UnsupportedSubstraitTypeSnafu};
+use crate::error::{
+    EmptyExprSnafu, MissingFieldSnafu, Result, UnsupportedConcreteTypeSnafu, UnsupportedExprSnafu,
+    UnsupportedSubstraitTypeSnafu,
+};
 
 macro_rules! substrait_kind {
     ($desc:ident, $concrete_ty:ident) => {{
@@ -67,11 +79,32 @@ pub fn to_concrete_type(ty: &SType) -> Result<(ConcreteDataType, bool)> {
         Kind::Fp64(desc) => substrait_kind!(desc, float64_datatype),
         Kind::String(desc) => substrait_kind!(desc, string_datatype),
         Kind::Binary(desc) => substrait_kind!(desc, binary_datatype),
-        Kind::Timestamp(desc) => substrait_kind!(
+        Kind::Timestamp(desc) => {
+            let unit = match desc.type_variation_reference {
+                0 => TimeUnit::Millisecond,
+                1 => TimeUnit::Second,
+                2 => TimeUnit::Microsecond,
+                3 => TimeUnit::Nanosecond,
+                _ => UnsupportedSubstraitTypeSnafu {
+                    ty: format!("{:?}", desc),
+                }
+                .fail()?,
+            };
+            substrait_kind!(desc, ConcreteDataType::timestamp_datatype(unit))
+        }
+        Kind::Date(desc) => substrait_kind!(desc, date_datatype, datetime_datatype),
+        Kind::List(desc) => {
+            let item_type = desc.r#type.as_ref().context(MissingFieldSnafu {
+                field: "type",
+                plan: "List type",
+            })?;
+            let (item_type, _) = to_concrete_type(item_type)?;
+            substrait_kind!(desc, ConcreteDataType::list_datatype(item_type))
+        }
+        Kind::Decimal(desc) => substrait_kind!(
             desc,
-            ConcreteDataType::timestamp_datatype(Default::default())
+            ConcreteDataType::decimal128_datatype(desc.precision as u8, desc.scale as u8)
         ),
-        Kind::Date(desc) => substrait_kind!(desc, date_datatype),
         Kind::Time(_)
         | Kind::IntervalYear(_)
         | Kind::IntervalDay(_)
@@ -80,9 +113,7 @@ pub fn to_concrete_type(ty: &SType) -> Result<(ConcreteDataType, bool)> {
         | Kind::FixedChar(_)
         | Kind::Varchar(_)
         | Kind::FixedBinary(_)
-        | Kind::Decimal(_)
         | Kind::Struct(_)
-        | Kind::List(_)
         | Kind::Map(_)
         | Kind::UserDefinedTypeReference(_) => UnsupportedSubstraitTypeSnafu {
             ty: format!("{:?}", kind),
@@ -91,16 +122,20 @@ pub fn to_concrete_type(ty: &SType) -> Result<(ConcreteDataType, bool)> {
     }
 }
 
+fn to_nullability(nullable: Option<bool>) -> i32 {
+    let nullability = match nullable {
+        Some(true) => Nullability::Nullable,
+        Some(false) => Nullability::Required,
+        None => Nullability::Unspecified,
+    };
+    nullability as _
+}
+
 macro_rules! build_substrait_kind {
     ($kind:ident,$s_type:ident,$nullable:ident,$variation:literal) => {{
-        let nullability = match $nullable {
-            Some(true) => Nullability::Nullable,
-            Some(false) => Nullability::Required,
-            None => Nullability::Unspecified,
-        } as _;
         Some(Kind::$kind(s_type::$s_type {
             type_variation_reference: $variation,
-            nullability,
+            nullability: to_nullability($nullable),
         }))
     }};
 }
@@ -125,12 +160,236 @@ pub fn from_concrete_type(ty: ConcreteDataType, nullability: Option<bool>) -> Re
         ConcreteDataType::Binary(_) => build_substrait_kind!(Binary, Binary, nullability, 0),
         ConcreteDataType::String(_) => build_substrait_kind!(String, String, nullability, 0),
         ConcreteDataType::Date(_) => build_substrait_kind!(Date, Date, nullability, 0),
-        ConcreteDataType::DateTime(_) => UnsupportedConcreteTypeSnafu { ty }.fail()?,
-        ConcreteDataType::Timestamp(_) => {
-            build_substrait_kind!(Timestamp, Timestamp, nullability, 0)
+        ConcreteDataType::DateTime(_) => build_substrait_kind!(Date, Date, nullability, 1),
+        ConcreteDataType::Timestamp(ty) => match ty.unit {
+            TimeUnit::Millisecond => build_substrait_kind!(Timestamp, Timestamp, nullability, 0),
+            TimeUnit::Second => build_substrait_kind!(Timestamp, Timestamp, nullability, 1),
+            TimeUnit::Microsecond => build_substrait_kind!(Timestamp, Timestamp, nullability, 2),
+            TimeUnit::Nanosecond => build_substrait_kind!(Timestamp, Timestamp, nullability, 3),
+        },
+        ConcreteDataType::List(list_type) => {
+            // The nullability of items is not tracked by ConcreteDataType.
+            let item_type = from_concrete_type(list_type.item_type().clone(), Some(true))?;
+            Some(Kind::List(Box::new(s_type::List {
+                r#type: Some(Box::new(item_type)),
+                type_variation_reference: 0,
+                nullability: to_nullability(nullability),
+            })))
+        }
+        ConcreteDataType::Decimal128(decimal_type) => Some(Kind::Decimal(s_type::Decimal {
+            scale: decimal_type.scale() as i32,
+            precision: decimal_type.precision() as i32,
+            type_variation_reference: 0,
+            nullability: to_nullability(nullability),
+        })),
+        ConcreteDataType::Json(_)
+        | ConcreteDataType::Interval(_)
+        | ConcreteDataType::Struct(_)
+        | ConcreteDataType::Map(_) => {
+            return UnsupportedConcreteTypeSnafu { ty }.fail();
         }
-        ConcreteDataType::List(_) => UnsupportedConcreteTypeSnafu { ty }.fail()?,
     };
 
     Ok(SType { kind })
 }
+
+/// Type variation of unsigned integers, refer to [mod level documentation](super::types).
+const UNSIGNED_INTEGER_TYPE_VARIATION: u32 = 1;
+
+macro_rules! literal {
+    ($literal_type:ident, $value:expr, $variation:expr) => {
+        Literal {
+            nullable: true,
+            type_variation_reference: $variation,
+            literal_type: Some(LiteralType::$literal_type($value)),
+        }
+    };
+}
+
+/// Build a null [Literal] of given type.
+fn null_literal(ty: ConcreteDataType) -> Result<Literal> {
+    let ty = from_concrete_type(ty, Some(true))?;
+    Ok(Literal {
+        nullable: true,
+        type_variation_reference: 0,
+        literal_type: Some(LiteralType::Null(ty)),
+    })
+}
+
+/// Convert DataFusion's [ScalarValue] to Substrait's [Literal].
+///
+/// Timestamps are carried as microseconds since epoch as substrait specified.
+pub fn from_scalar_value(value: &ScalarValue) -> Result<Literal> {
+    if value.is_null() {
+        return match value {
+            ScalarValue::Boolean(_) => null_literal(ConcreteDataType::boolean_datatype()),
+            ScalarValue::Int8(_) => null_literal(ConcreteDataType::int8_datatype()),
+            ScalarValue::Int16(_) => null_literal(ConcreteDataType::int16_datatype()),
+            ScalarValue::Int32(_) => null_literal(ConcreteDataType::int32_datatype()),
+            ScalarValue::Int64(_) => null_literal(ConcreteDataType::int64_datatype()),
+            ScalarValue::UInt8(_) => null_literal(ConcreteDataType::uint8_datatype()),
+            ScalarValue::UInt16(_) => null_literal(ConcreteDataType::uint16_datatype()),
+            ScalarValue::UInt32(_) => null_literal(ConcreteDataType::uint32_datatype()),
+            ScalarValue::UInt64(_) => null_literal(ConcreteDataType::uint64_datatype()),
+            ScalarValue::Float32(_) => null_literal(ConcreteDataType::float32_datatype()),
+            ScalarValue::Float64(_) => null_literal(ConcreteDataType::float64_datatype()),
+            ScalarValue::Utf8(_) | ScalarValue::LargeUtf8(_) => {
+                null_literal(ConcreteDataType::string_datatype())
+            }
+            ScalarValue::Binary(_) | ScalarValue::LargeBinary(_) => {
+                null_literal(ConcreteDataType::binary_datatype())
+            }
+            ScalarValue::Date32(_) => null_literal(ConcreteDataType::date_datatype()),
+            ScalarValue::TimestampSecond(..)
+            | ScalarValue::TimestampMillisecond(..)
+            | ScalarValue::TimestampMicrosecond(..)
+            | ScalarValue::TimestampNanosecond(..) => {
+                null_literal(ConcreteDataType::timestamp_datatype(Default::default()))
+            }
+            _ => UnsupportedExprSnafu {
+                name: format!("null literal {:?}", value),
+            }
+            .fail(),
+        };
+    }
+
+    let literal = match value {
+        ScalarValue::Boolean(Some(v)) => literal!(Boolean, *v, 0),
+        ScalarValue::Int8(Some(v)) => literal!(I8, *v as i32, 0),
+        ScalarValue::Int16(Some(v)) => literal!(I16, *v as i32, 0),
+        ScalarValue::Int32(Some(v)) => literal!(I32, *v, 0),
+        ScalarValue::Int64(Some(v)) => literal!(I64, *v, 0),
+        ScalarValue::UInt8(Some(v)) => literal!(I8, *v as i32, UNSIGNED_INTEGER_TYPE_VARIATION),
+        ScalarValue::UInt16(Some(v)) => {
+            literal!(I16, *v as i32, UNSIGNED_INTEGER_TYPE_VARIATION)
+        }
+        ScalarValue::UInt32(Some(v)) => {
+            literal!(I32, *v as i32, UNSIGNED_INTEGER_TYPE_VARIATION)
+        }
+        ScalarValue::UInt64(Some(v)) => {
+            literal!(I64, *v as i64, UNSIGNED_INTEGER_TYPE_VARIATION)
+        }
+        ScalarValue::Float32(Some(v)) => literal!(Fp32, *v, 0),
+        ScalarValue::Float64(Some(v)) => literal!(Fp64, *v, 0),
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+            literal!(String, v.clone(), 0)
+        }
+        ScalarValue::Binary(Some(v)) | ScalarValue::LargeBinary(Some(v)) => {
+            literal!(Binary, v.clone(), 0)
+        }
+        ScalarValue::Date32(Some(v)) => literal!(Date, *v, 0),
+        ScalarValue::TimestampSecond(Some(v), _) => literal!(Timestamp, *v * 1_000_000, 0),
+        ScalarValue::TimestampMillisecond(Some(v), _) => literal!(Timestamp, *v * 1_000, 0),
+        ScalarValue::TimestampMicrosecond(Some(v), _) => literal!(Timestamp, *v, 0),
+        ScalarValue::TimestampNanosecond(Some(v), _) => literal!(Timestamp, *v / 1_000, 0),
+        _ => UnsupportedExprSnafu {
+            name: format!("literal {:?}", value),
+        }
+        .fail()?,
+    };
+    Ok(literal)
+}
+
+/// Convert Substrait's [Literal] to DataFusion's [ScalarValue].
+///
+/// Timestamps are restored in millisecond, which is the default precision of GreptimeDB.
+pub fn to_scalar_value(literal: Literal) -> Result<ScalarValue> {
+    let unsigned = literal.type_variation_reference == UNSIGNED_INTEGER_TYPE_VARIATION;
+    let literal_type = literal.literal_type.context(EmptyExprSnafu)?;
+
+    let value = match literal_type {
+        LiteralType::Boolean(v) => ScalarValue::Boolean(Some(v)),
+        LiteralType::I8(v) if unsigned => ScalarValue::UInt8(Some(v as u8)),
+        LiteralType::I8(v) => ScalarValue::Int8(Some(v as i8)),
+        LiteralType::I16(v) if unsigned => ScalarValue::UInt16(Some(v as u16)),
+        LiteralType::I16(v) => ScalarValue::Int16(Some(v as i16)),
+        LiteralType::I32(v) if unsigned => ScalarValue::UInt32(Some(v as u32)),
+        LiteralType::I32(v) => ScalarValue::Int32(Some(v)),
+        LiteralType::I64(v) if unsigned => ScalarValue::UInt64(Some(v as u64)),
+        LiteralType::I64(v) => ScalarValue::Int64(Some(v)),
+        LiteralType::Fp32(v) => ScalarValue::Float32(Some(v)),
+        LiteralType::Fp64(v) => ScalarValue::Float64(Some(v)),
+        LiteralType::String(v) => ScalarValue::Utf8(Some(v)),
+        LiteralType::Binary(v) => ScalarValue::LargeBinary(Some(v)),
+        LiteralType::Date(v) => ScalarValue::Date32(Some(v)),
+        LiteralType::Timestamp(v) => ScalarValue::TimestampMillisecond(Some(v / 1_000), None),
+        LiteralType::Null(ty) => {
+            let (concrete_type, _) = to_concrete_type(&ty)?;
+            match concrete_type {
+                ConcreteDataType::Boolean(_) => ScalarValue::Boolean(None),
+                ConcreteDataType::Int8(_) => ScalarValue::Int8(None),
+                ConcreteDataType::Int16(_) => ScalarValue::Int16(None),
+                ConcreteDataType::Int32(_) => ScalarValue::Int32(None),
+                ConcreteDataType::Int64(_) => ScalarValue::Int64(None),
+                ConcreteDataType::UInt8(_) => ScalarValue::UInt8(None),
+                ConcreteDataType::UInt16(_) => ScalarValue::UInt16(None),
+                ConcreteDataType::UInt32(_) => ScalarValue::UInt32(None),
+                ConcreteDataType::UInt64(_) => ScalarValue::UInt64(None),
+                ConcreteDataType::Float32(_) => ScalarValue::Float32(None),
+                ConcreteDataType::Float64(_) => ScalarValue::Float64(None),
+                ConcreteDataType::String(_) => ScalarValue::Utf8(None),
+                ConcreteDataType::Binary(_) => ScalarValue::LargeBinary(None),
+                ConcreteDataType::Date(_) => ScalarValue::Date32(None),
+                ConcreteDataType::Timestamp(_) => ScalarValue::TimestampMillisecond(None, None),
+                ConcreteDataType::Decimal128(decimal_type) => ScalarValue::Decimal128(
+                    None,
+                    decimal_type.precision() as usize,
+                    decimal_type.scale() as usize,
+                ),
+                ConcreteDataType::Null(_)
+                | ConcreteDataType::DateTime(_)
+                | ConcreteDataType::List(_)
+                | ConcreteDataType::Json(_)
+                | ConcreteDataType::Interval(_)
+                | ConcreteDataType::Struct(_)
+                | ConcreteDataType::Map(_) => {
+                    UnsupportedConcreteTypeSnafu { ty: concrete_type }.fail()?
+                }
+            }
+        }
+        other => UnsupportedSubstraitTypeSnafu {
+            ty: format!("literal {:?}", other),
+        }
+        .fail()?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scalar_value_round_trip() {
+        let values = vec![
+            ScalarValue::Boolean(Some(true)),
+            ScalarValue::Int8(Some(-8)),
+            ScalarValue::Int16(Some(-16)),
+            ScalarValue::Int32(Some(-32)),
+            ScalarValue::Int64(Some(-64)),
+            ScalarValue::UInt8(Some(u8::MAX)),
+            ScalarValue::UInt16(Some(u16::MAX)),
+            ScalarValue::UInt32(Some(u32::MAX)),
+            ScalarValue::UInt64(Some(u64::MAX)),
+            ScalarValue::Float32(Some(1.5)),
+            ScalarValue::Float64(Some(-2.5)),
+            ScalarValue::Utf8(Some("greptime".to_string())),
+            ScalarValue::LargeBinary(Some(b"greptime".to_vec())),
+            ScalarValue::Date32(Some(19000)),
+            ScalarValue::TimestampMillisecond(Some(1665000000123), None),
+            ScalarValue::Int64(None),
+            ScalarValue::Utf8(None),
+            ScalarValue::TimestampMillisecond(None, None),
+        ];
+
+        for value in values {
+            let literal = from_scalar_value(&value).unwrap();
+            assert_eq!(value, to_scalar_value(literal).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unsupported_scalar_value() {
+        assert!(from_scalar_value(&ScalarValue::Date64(Some(1))).is_err());
+    }
+}
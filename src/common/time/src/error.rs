@@ -22,6 +22,8 @@ pub enum Error {
     ParseDateStr { raw: String, source: ParseError },
     #[snafu(display("Failed to parse a string into Timestamp, raw string: {}", raw))]
     ParseTimestamp { raw: String, backtrace: Backtrace },
+    #[snafu(display("Failed to parse a string into Interval, raw string: {}", raw))]
+    ParseInterval { raw: String, backtrace: Backtrace },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
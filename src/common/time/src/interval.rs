@@ -0,0 +1,204 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt};
+
+use crate::error::{Error, ParseIntervalSnafu, Result};
+
+const NANOS_PER_MICRO: i64 = 1_000;
+const NANOS_PER_MILLI: i64 = 1_000_000;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_MINUTE: i64 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+
+/// [Interval] represents a span of calendar time, stored as separate months, days
+/// and nanoseconds parts because the length of a month or a day is not fixed.
+///
+/// The ordering compares months first, then days and nanoseconds, so it is a total
+/// order but not a comparison of the actual durations.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    nanos: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, nanos: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanos,
+        }
+    }
+
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn nanos(&self) -> i64 {
+        self.nanos
+    }
+}
+
+impl Display for Interval {
+    /// [Interval] is formatted like PostgreSQL does, e.g. `1 year 2 mons 3 days 04:05:06.789`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            parts.push(format!("{} year{}", years, plural(years as i64)));
+        }
+        if months != 0 {
+            parts.push(format!("{} mon{}", months, plural(months as i64)));
+        }
+        if self.days != 0 {
+            parts.push(format!("{} day{}", self.days, plural(self.days as i64)));
+        }
+        if self.nanos != 0 || parts.is_empty() {
+            let sign = if self.nanos < 0 { "-" } else { "" };
+            let nanos = self.nanos.unsigned_abs();
+            let hours = nanos / NANOS_PER_HOUR as u64;
+            let minutes = nanos % NANOS_PER_HOUR as u64 / NANOS_PER_MINUTE as u64;
+            let seconds = nanos % NANOS_PER_MINUTE as u64 / NANOS_PER_SECOND as u64;
+            let fraction = nanos % NANOS_PER_SECOND as u64;
+            let mut time = format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds);
+            if fraction != 0 {
+                let fraction = format!("{:09}", fraction);
+                time.push('.');
+                time.push_str(fraction.trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+fn plural(n: i64) -> &'static str {
+    if n.abs() == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+impl From<Interval> for serde_json::Value {
+    fn from(i: Interval) -> Self {
+        serde_json::Value::String(i.to_string())
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Error;
+
+    /// Parses intervals in the `<quantity> <unit> [<quantity> <unit> ...]` form, such as
+    /// `1 year 2 months`, `3 days` or `1.5 hours`.
+    fn from_str(s: &str) -> Result<Self> {
+        let tokens = s.split_whitespace().collect::<Vec<_>>();
+        ensure!(
+            !tokens.is_empty() && tokens.len() % 2 == 0,
+            ParseIntervalSnafu { raw: s }
+        );
+
+        let mut interval = Interval::default();
+        for pair in tokens.chunks(2) {
+            let quantity = pair[0]
+                .parse::<f64>()
+                .ok()
+                .context(ParseIntervalSnafu { raw: s })?;
+            let unit = pair[1].to_ascii_lowercase();
+            let unit = unit.trim_end_matches('s');
+            match unit {
+                "year" => interval.months += (quantity * 12.0) as i32,
+                "mon" | "month" => interval.months += quantity as i32,
+                "week" => interval.days += (quantity * 7.0) as i32,
+                "day" => interval.days += quantity as i32,
+                "hour" => interval.nanos += (quantity * NANOS_PER_HOUR as f64) as i64,
+                "minute" | "min" => interval.nanos += (quantity * NANOS_PER_MINUTE as f64) as i64,
+                "second" | "sec" => interval.nanos += (quantity * NANOS_PER_SECOND as f64) as i64,
+                "millisecond" | "m" => interval.nanos += (quantity * NANOS_PER_MILLI as f64) as i64,
+                "microsecond" | "u" => interval.nanos += (quantity * NANOS_PER_MICRO as f64) as i64,
+                "nanosecond" | "n" => interval.nanos += quantity as i64,
+                _ => return ParseIntervalSnafu { raw: s }.fail(),
+            }
+        }
+        Ok(interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_display() {
+        assert_eq!("00:00:00", Interval::default().to_string());
+        assert_eq!("1 year 2 mons", Interval::new(14, 0, 0).to_string());
+        assert_eq!("1 day", Interval::new(0, 1, 0).to_string());
+        assert_eq!(
+            "3 days 04:05:06.5",
+            Interval::new(
+                0,
+                3,
+                4 * NANOS_PER_HOUR + 5 * NANOS_PER_MINUTE + 6_500_000_000
+            )
+            .to_string()
+        );
+        assert_eq!(
+            "-00:00:01",
+            Interval::new(0, 0, -NANOS_PER_SECOND).to_string()
+        );
+    }
+
+    #[test]
+    fn test_interval_parse() {
+        assert_eq!(
+            Interval::new(14, 3, 0),
+            Interval::from_str("1 year 2 months 3 days").unwrap()
+        );
+        assert_eq!(
+            Interval::new(0, 0, 90 * NANOS_PER_MINUTE),
+            Interval::from_str("1.5 HOURS").unwrap()
+        );
+        assert_eq!(
+            Interval::new(0, 14, 10 * NANOS_PER_MILLI),
+            Interval::from_str("2 weeks 10 milliseconds").unwrap()
+        );
+
+        assert!(Interval::from_str("").is_err());
+        assert!(Interval::from_str("1").is_err());
+        assert!(Interval::from_str("1 fortnight").is_err());
+        assert!(Interval::from_str("x days").is_err());
+    }
+
+    #[test]
+    fn test_interval_display_parse_roundtrip() {
+        let interval = Interval::new(25, -3, 0);
+        assert_eq!("2 years 1 mon -3 days", interval.to_string());
+        assert_eq!(
+            interval,
+            Interval::from_str("2 years 1 mon -3 days").unwrap()
+        );
+    }
+}
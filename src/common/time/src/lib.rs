@@ -15,6 +15,7 @@
 pub mod date;
 pub mod datetime;
 pub mod error;
+pub mod interval;
 pub mod range;
 pub mod timestamp;
 pub mod timestamp_millis;
@@ -22,6 +23,7 @@ pub mod util;
 
 pub use date::Date;
 pub use datetime::DateTime;
+pub use interval::Interval;
 pub use range::RangeMillis;
 pub use timestamp::Timestamp;
 pub use timestamp_millis::TimestampMillis;
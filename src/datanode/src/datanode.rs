@@ -17,7 +17,9 @@ use std::sync::Arc;
 use common_telemetry::info;
 use frontend::frontend::Mode;
 use meta_client::MetaClientOpts;
+pub use query::cache::QueryCacheOptions;
 use serde::{Deserialize, Serialize};
+use servers::tls::TlsOption;
 
 use crate::error::Result;
 use crate::instance::{Instance, InstanceRef};
@@ -44,11 +46,18 @@ pub struct DatanodeOptions {
     pub rpc_runtime_size: usize,
     pub mysql_addr: String,
     pub mysql_runtime_size: usize,
+    /// TLS of the gRPC server, mutual TLS with frontends is enabled if its `ca_cert_path` is set.
+    #[serde(default)]
+    pub rpc_tls: TlsOption,
+    #[serde(default)]
+    pub mysql_tls: TlsOption,
     pub meta_client_opts: MetaClientOpts,
     pub wal_dir: String,
     pub storage: ObjectStoreConfig,
     pub mode: Mode,
     pub metasrv_addr: Option<Vec<String>>,
+    #[serde(default)]
+    pub query_cache: QueryCacheOptions,
 }
 
 impl Default for DatanodeOptions {
@@ -59,11 +68,14 @@ impl Default for DatanodeOptions {
             rpc_runtime_size: 8,
             mysql_addr: "0.0.0.0:3306".to_string(),
             mysql_runtime_size: 2,
+            rpc_tls: TlsOption::default(),
+            mysql_tls: TlsOption::default(),
             meta_client_opts: MetaClientOpts::default(),
             wal_dir: "/tmp/greptimedb/wal".to_string(),
             storage: ObjectStoreConfig::default(),
             mode: Mode::Standalone,
             metasrv_addr: None,
+            query_cache: QueryCacheOptions::default(),
         }
     }
 }
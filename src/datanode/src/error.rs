@@ -114,6 +114,12 @@ pub enum Error {
         source: servers::error::Error,
     },
 
+    #[snafu(display("Failed to set up TLS, source: {}", source))]
+    SetupTls {
+        #[snafu(backtrace)]
+        source: servers::error::Error,
+    },
+
     #[snafu(display("Failed to parse address {}, source: {}", addr, source))]
     ParseAddr {
         addr: String,
@@ -151,6 +157,9 @@ pub enum Error {
     #[snafu(display("Unsupported expr type: {}", name))]
     UnsupportedExpr { name: String },
 
+    #[snafu(display("Not supported: {}", feat))]
+    NotSupported { feat: String, backtrace: Backtrace },
+
     #[snafu(display("Runtime resource error, source: {}", source))]
     RuntimeResource {
         #[snafu(backtrace)]
@@ -284,6 +293,24 @@ pub enum Error {
         source: table::error::Error,
     },
 
+    #[snafu(display("Invalid materialized view {}, source: {}", name, source))]
+    MaterializedView {
+        name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to update materialized views of table {}, source: {}",
+        table_name,
+        source
+    ))]
+    UpdateMaterializedViews {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
     #[snafu(display("Failed to do vector computation, source: {}", source))]
     VectorComputation {
         #[snafu(backtrace)]
@@ -297,6 +324,8 @@ impl ErrorExt for Error {
     fn status_code(&self) -> StatusCode {
         match self {
             Error::ExecuteSql { source } => source.status_code(),
+            Error::MaterializedView { source, .. }
+            | Error::UpdateMaterializedViews { source, .. } => source.status_code(),
             Error::DecodeLogicalPlan { source } => source.status_code(),
             Error::ExecutePhysicalPlan { source } => source.status_code(),
             Error::NewCatalog { source } => source.status_code(),
@@ -352,9 +381,12 @@ impl ErrorExt for Error {
             Error::CollectRecordBatches { source } => source.status_code(),
 
             Error::MetaClientInit { source, .. } => source.status_code(),
+            Error::SetupTls { source } => source.status_code(),
             Error::InsertData { source, .. } => source.status_code(),
             Error::EmptyInsertBatch => StatusCode::InvalidArguments,
-            Error::TableIdProviderNotFound { .. } => StatusCode::Unsupported,
+            Error::TableIdProviderNotFound { .. } | Error::NotSupported { .. } => {
+                StatusCode::Unsupported
+            }
             Error::BumpTableId { source, .. } => source.status_code(),
         }
     }
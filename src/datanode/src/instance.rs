@@ -52,7 +52,7 @@ pub(crate) type DefaultEngine = MitoEngine<EngineImpl<LocalFileLogStore>>;
 // An abstraction to read/write services.
 pub struct Instance {
     pub(crate) query_engine: QueryEngineRef,
-    pub(crate) sql_handler: SqlHandler,
+    pub(crate) sql_handler: Arc<SqlHandler>,
     pub(crate) catalog_manager: CatalogManagerRef,
     pub(crate) physical_planner: PhysicalPlanner,
     pub(crate) script_executor: ScriptExecutor,
@@ -95,7 +95,8 @@ impl Instance {
                         .await
                         .context(CatalogSnafu)?,
                 );
-                let factory = QueryEngineFactory::new(catalog.clone());
+                let factory =
+                    QueryEngineFactory::new_with_cache(catalog.clone(), &opts.query_cache);
                 (
                     catalog.clone() as CatalogManagerRef,
                     factory,
@@ -111,14 +112,24 @@ impl Instance {
                         client: meta_client.as_ref().unwrap().clone(),
                     }),
                 ));
-                let factory = QueryEngineFactory::new(catalog.clone());
+                let factory =
+                    QueryEngineFactory::new_with_cache(catalog.clone(), &opts.query_cache);
                 (catalog as CatalogManagerRef, factory, None)
             }
         };
 
         let query_engine = factory.query_engine();
-        let script_executor =
-            ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?;
+        let sql_handler = Arc::new(SqlHandler::new(
+            table_engine,
+            catalog_manager.clone(),
+            query_engine.clone(),
+        ));
+        let script_executor = ScriptExecutor::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            sql_handler.clone(),
+        )
+        .await?;
 
         let heartbeat_task = match opts.mode {
             Mode::Standalone => None,
@@ -130,7 +141,7 @@ impl Instance {
         };
         Ok(Self {
             query_engine: query_engine.clone(),
-            sql_handler: SqlHandler::new(table_engine, catalog_manager.clone()),
+            sql_handler,
             catalog_manager,
             physical_planner: PhysicalPlanner::new(query_engine),
             script_executor,
@@ -145,6 +156,8 @@ impl Instance {
             .start()
             .await
             .context(NewCatalogSnafu)?;
+        self.script_executor.start().await?;
+        self.sql_handler.load_materialized_views().await?;
         if let Some(task) = &self.heartbeat_task {
             task.start().await?;
         }
@@ -186,11 +199,16 @@ async fn new_metasrv_client(node_id: u64, meta_config: &MetaClientOpts) -> Resul
     let cluster_id = 0; // TODO(hl): read from config
     let member_id = node_id;
 
-    let config = ChannelConfig::new()
+    let mut config = ChannelConfig::new()
         .timeout(Duration::from_millis(meta_config.timeout_millis))
         .connect_timeout(Duration::from_millis(meta_config.connect_timeout_millis))
         .tcp_nodelay(meta_config.tcp_nodelay);
-    let channel_manager = ChannelManager::with_config(config);
+    if let Some(tls) = &meta_config.tls {
+        config = config.client_tls_config(tls.clone());
+    }
+    let channel_manager = ChannelManager::with_tls_config(config)
+        .context(meta_client::error::CreateChannelSnafu)
+        .context(MetaClientInitSnafu)?;
     let mut meta_client = MetaClientBuilder::new(cluster_id, member_id)
         .enable_heartbeat()
         .enable_router()
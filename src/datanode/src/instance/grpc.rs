@@ -12,31 +12,39 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use api::result::{build_err_result, AdminResultBuilder, ObjectResultBuilder};
 use api::v1::{
     admin_expr, insert_expr, object_expr, select_expr, AdminExpr, AdminResult, CreateDatabaseExpr,
-    ObjectExpr, ObjectResult, SelectExpr,
+    DataVersionsExpr, ObjectExpr, ObjectResult, SelectExpr, COMMITTED_SEQUENCE_COLUMN,
+    MANIFEST_VERSION_COLUMN,
 };
 use async_trait::async_trait;
-use common_catalog::consts::DEFAULT_CATALOG_NAME;
 use common_error::ext::ErrorExt;
 use common_error::status_code::StatusCode;
 use common_insert::insertion_expr_to_request;
 use common_query::Output;
-use query::plan::LogicalPlan;
+use common_recordbatch::RecordBatches;
+use datatypes::prelude::{ConcreteDataType, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::UInt64Vector;
+use query::plan::{LogicalPlan, OperatorMetrics};
 use servers::query_handler::{GrpcAdminHandler, GrpcQueryHandler};
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
+use table::engine::TableReference;
 use table::requests::CreateDatabaseRequest;
 
 use crate::error::{
-    CatalogNotFoundSnafu, CatalogSnafu, DecodeLogicalPlanSnafu, EmptyInsertBatchSnafu,
-    ExecuteSqlSnafu, InsertDataSnafu, InsertSnafu, Result, SchemaNotFoundSnafu, TableNotFoundSnafu,
-    UnsupportedExprSnafu,
+    CatalogNotFoundSnafu, CatalogSnafu, CollectRecordBatchesSnafu, DecodeLogicalPlanSnafu,
+    EmptyInsertBatchSnafu, ExecuteSqlSnafu, InsertDataSnafu, InsertSnafu, NotSupportedSnafu,
+    Result, SchemaNotFoundSnafu, TableNotFoundSnafu, UnsupportedExprSnafu,
 };
 use crate::instance::Instance;
 use crate::server::grpc::plan::PhysicalPlanner;
-use crate::server::grpc::select::to_object_result;
+use crate::server::grpc::select::{to_object_result, to_object_result_with_metrics};
 
 impl Instance {
     pub async fn execute_grpc_insert(
@@ -74,10 +82,19 @@ impl Instance {
         )
         .context(InsertDataSnafu)?;
 
+        let columns_values = insert.columns_values.clone();
         let affected_rows = table
             .insert(insert)
             .await
             .context(InsertSnafu { table_name })?;
+        let table_ref = TableReference {
+            catalog: catalog_name,
+            schema: schema_name,
+            table: table_name,
+        };
+        self.sql_handler
+            .insert_into_materialized_views(&table_ref, &columns_values)
+            .await?;
 
         Ok(Output::AffectedRows(affected_rows))
     }
@@ -106,16 +123,29 @@ impl Instance {
         }
     }
 
-    async fn handle_select(&self, select_expr: SelectExpr) -> ObjectResult {
-        let result = self.do_handle_select(select_expr).await;
+    async fn handle_select(
+        &self,
+        select_expr: SelectExpr,
+        query_ctx: QueryContextRef,
+    ) -> ObjectResult {
+        if let Some(select_expr::Expr::LogicalPlan(plan)) = select_expr.expr {
+            // Returns the metrics of executing the plan, for the frontend to report them.
+            let result = self.execute_logical(plan).await;
+            return to_object_result_with_metrics(result);
+        }
+        let result = self.do_handle_select(select_expr, query_ctx).await;
         to_object_result(result).await
     }
 
-    async fn do_handle_select(&self, select_expr: SelectExpr) -> Result<Output> {
+    async fn do_handle_select(
+        &self,
+        select_expr: SelectExpr,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         let expr = select_expr.expr;
         match expr {
-            Some(select_expr::Expr::Sql(sql)) => self.execute_sql(&sql).await,
-            Some(select_expr::Expr::LogicalPlan(plan)) => self.execute_logical(plan).await,
+            Some(select_expr::Expr::Sql(sql)) => self.execute_sql(&sql, query_ctx).await,
+            Some(select_expr::Expr::DataVersions(expr)) => self.execute_data_versions(expr).await,
             Some(select_expr::Expr::PhysicalPlan(api::v1::PhysicalPlan { original_ql, plan })) => {
                 self.physical_planner
                     .execute(PhysicalPlanner::parse(plan)?, original_ql)
@@ -149,14 +179,60 @@ impl Instance {
         }
     }
 
-    async fn execute_logical(&self, plan_bytes: Vec<u8>) -> Result<Output> {
-        let logical_plan_converter = DFLogicalSubstraitConvertor::new(self.catalog_manager.clone());
-        let logical_plan = logical_plan_converter
-            .decode(plan_bytes.as_slice())
+    /// Selects the versions of the data in the regions of the table, for the frontend to cache
+    /// the results of queries over it.
+    async fn execute_data_versions(&self, expr: DataVersionsExpr) -> Result<Output> {
+        let table_ref = TableReference {
+            catalog: &expr.catalog_name,
+            schema: &expr.schema_name,
+            table: &expr.table_name,
+        };
+        let table = self
+            .catalog_manager
+            .table(&expr.catalog_name, &expr.schema_name, &expr.table_name)
+            .context(CatalogSnafu)?
+            .context(TableNotFoundSnafu {
+                table_name: table_ref.to_string(),
+            })?;
+        let versions = table.data_versions().await.context(NotSupportedSnafu {
+            feat: format!("data versions of table {}", table_ref),
+        })?;
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new(
+                MANIFEST_VERSION_COLUMN,
+                ConcreteDataType::uint64_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                COMMITTED_SEQUENCE_COLUMN,
+                ConcreteDataType::uint64_datatype(),
+                false,
+            ),
+        ]));
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(UInt64Vector::from_vec(
+                versions.iter().map(|v| v.manifest_version).collect(),
+            )),
+            Arc::new(UInt64Vector::from_vec(
+                versions.iter().map(|v| v.committed_sequence).collect(),
+            )),
+        ];
+        let recordbatches =
+            RecordBatches::try_from_columns(schema, columns).context(CollectRecordBatchesSnafu)?;
+        Ok(Output::RecordBatches(recordbatches))
+    }
+
+    async fn execute_logical(
+        &self,
+        plan_bytes: Vec<u8>,
+    ) -> Result<(RecordBatches, Vec<OperatorMetrics>)> {
+        let logical_plan = DFLogicalSubstraitConvertor
+            .decode(plan_bytes.as_slice(), self.catalog_manager.clone())
             .context(DecodeLogicalPlanSnafu)?;
 
         self.query_engine
-            .execute(&LogicalPlan::DfPlan(logical_plan))
+            .execute_with_metrics(&LogicalPlan::DfPlan(logical_plan))
             .await
             .context(ExecuteSqlSnafu)
     }
@@ -164,10 +240,14 @@ impl Instance {
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
-    async fn do_query(&self, query: ObjectExpr) -> servers::error::Result<ObjectResult> {
+    async fn do_query(
+        &self,
+        query: ObjectExpr,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<ObjectResult> {
         let object_resp = match query.expr {
             Some(object_expr::Expr::Insert(insert_expr)) => {
-                let catalog_name = DEFAULT_CATALOG_NAME;
+                let catalog_name = &query_ctx.current_catalog();
                 let schema_name = &insert_expr.schema_name;
                 let table_name = &insert_expr.table_name;
                 let expr = insert_expr
@@ -185,12 +265,14 @@ impl GrpcQueryHandler for Instance {
                             .await
                     }
                     insert_expr::Expr::Sql(sql) => {
-                        let output = self.execute_sql(&sql).await;
+                        let output = self.execute_sql(&sql, query_ctx).await;
                         to_object_result(output).await
                     }
                 }
             }
-            Some(object_expr::Expr::Select(select_expr)) => self.handle_select(select_expr).await,
+            Some(object_expr::Expr::Select(select_expr)) => {
+                self.handle_select(select_expr, query_ctx).await
+            }
             other => {
                 return servers::error::NotSupportedSnafu {
                     feat: format!("{:?}", other),
@@ -204,7 +286,11 @@ impl GrpcQueryHandler for Instance {
 
 #[async_trait]
 impl GrpcAdminHandler for Instance {
-    async fn exec_admin_request(&self, expr: AdminExpr) -> servers::error::Result<AdminResult> {
+    async fn exec_admin_request(
+        &self,
+        expr: AdminExpr,
+        _query_ctx: QueryContextRef,
+    ) -> servers::error::Result<AdminResult> {
         let admin_resp = match expr.expr {
             Some(admin_expr::Expr::Create(create_expr)) => self.handle_create(create_expr).await,
             Some(admin_expr::Expr::Alter(alter_expr)) => self.handle_alter(alter_expr).await,
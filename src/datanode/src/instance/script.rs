@@ -22,13 +22,20 @@ use crate::metric;
 
 #[async_trait]
 impl ScriptHandler for Instance {
-    async fn insert_script(&self, name: &str, script: &str) -> servers::error::Result<()> {
+    async fn insert_script(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+    ) -> servers::error::Result<()> {
         let _timer = timer!(metric::METRIC_HANDLE_SCRIPTS_ELAPSED);
-        self.script_executor.insert_script(name, script).await
+        self.script_executor
+            .insert_script(schema, name, script)
+            .await
     }
 
-    async fn execute_script(&self, name: &str) -> servers::error::Result<Output> {
+    async fn execute_script(&self, schema: &str, name: &str) -> servers::error::Result<Output> {
         let _timer = timer!(metric::METRIC_RUN_SCRIPT_ELAPSED);
-        self.script_executor.execute_script(name).await
+        self.script_executor.execute_script(schema, name).await
     }
 }
@@ -17,31 +17,42 @@ use common_error::prelude::BoxedError;
 use common_query::Output;
 use common_telemetry::logging::{error, info};
 use common_telemetry::timer;
+use datatypes::prelude::ConcreteDataType;
 use servers::query_handler::SqlQueryHandler;
+use session::context::QueryContextRef;
 use snafu::prelude::*;
+use sql::prepared::PreparedStatement;
+use sql::statements::set_variables::TIME_ZONE_VARIABLE;
 use sql::statements::statement::Statement;
 use table::requests::CreateDatabaseRequest;
 
 use crate::error::{
-    BumpTableIdSnafu, CatalogNotFoundSnafu, CatalogSnafu, ExecuteSqlSnafu, ParseSqlSnafu, Result,
-    SchemaNotFoundSnafu, TableIdProviderNotFoundSnafu,
+    BumpTableIdSnafu, CatalogNotFoundSnafu, CatalogSnafu, ExecuteSqlSnafu, NotSupportedSnafu,
+    ParseSqlSnafu, Result, SchemaNotFoundSnafu, TableIdProviderNotFoundSnafu,
 };
 use crate::instance::Instance;
 use crate::metric;
 use crate::sql::SqlRequest;
 
 impl Instance {
-    pub async fn execute_sql(&self, sql: &str) -> Result<Output> {
+    pub async fn execute_sql(&self, sql: &str, query_ctx: QueryContextRef) -> Result<Output> {
         let stmt = self
             .query_engine
             .sql_to_statement(sql)
             .context(ExecuteSqlSnafu)?;
+        self.execute_stmt(stmt, query_ctx).await
+    }
 
+    pub async fn execute_stmt(
+        &self,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         match stmt {
-            Statement::Query(_) => {
+            Statement::Query(_) | Statement::Explain(_) => {
                 let logical_plan = self
                     .query_engine
-                    .statement_to_plan(stmt)
+                    .statement_to_plan(stmt, query_ctx)
                     .context(ExecuteSqlSnafu)?;
 
                 self.query_engine
@@ -51,7 +62,7 @@ impl Instance {
             }
             Statement::Insert(i) => {
                 let (catalog_name, schema_name, _table_name) =
-                    i.full_table_name().context(ParseSqlSnafu)?;
+                    i.full_table_name(&query_ctx).context(ParseSqlSnafu)?;
 
                 let schema_provider = self
                     .catalog_manager
@@ -62,8 +73,10 @@ impl Instance {
                     .context(CatalogSnafu)?
                     .context(SchemaNotFoundSnafu { name: schema_name })?;
 
-                let request = self.sql_handler.insert_to_request(schema_provider, *i)?;
-                self.sql_handler.execute(request).await
+                let request =
+                    self.sql_handler
+                        .insert_to_request(schema_provider, *i, query_ctx.clone())?;
+                self.sql_handler.execute(request, query_ctx).await
             }
 
             Statement::CreateDatabase(c) => {
@@ -74,7 +87,7 @@ impl Instance {
                 info!("Creating a new database: {}", request.db_name);
 
                 self.sql_handler
-                    .execute(SqlRequest::CreateDatabase(request))
+                    .execute(SqlRequest::CreateDatabase(request), query_ctx)
                     .await
             }
 
@@ -89,7 +102,9 @@ impl Instance {
                 let _engine_name = c.engine.clone();
                 // TODO(hl): Select table engine by engine_name
 
-                let request = self.sql_handler.create_to_request(table_id, c)?;
+                let request = self
+                    .sql_handler
+                    .create_to_request(table_id, c, query_ctx.clone())?;
                 let catalog_name = &request.catalog_name;
                 let schema_name = &request.schema_name;
                 let table_name = &request.table_name;
@@ -100,33 +115,110 @@ impl Instance {
                 );
 
                 self.sql_handler
-                    .execute(SqlRequest::CreateTable(request))
+                    .execute(SqlRequest::CreateTable(request), query_ctx)
+                    .await
+            }
+            Statement::CreateMaterializedView(c) => {
+                let table_id = self
+                    .table_id_provider
+                    .as_ref()
+                    .context(TableIdProviderNotFoundSnafu)?
+                    .next_table_id()
+                    .await
+                    .context(BumpTableIdSnafu)?;
+                info!(
+                    "Creating materialized view {}, table id: {}",
+                    c.name, table_id
+                );
+
+                self.sql_handler
+                    .create_materialized_view(table_id, c, &query_ctx)
                     .await
             }
             Statement::Alter(alter_table) => {
-                let req = self.sql_handler.alter_to_request(alter_table)?;
-                self.sql_handler.execute(SqlRequest::Alter(req)).await
+                let req = self
+                    .sql_handler
+                    .alter_to_request(alter_table, query_ctx.clone())?;
+                self.sql_handler
+                    .execute(SqlRequest::Alter(req), query_ctx)
+                    .await
             }
             Statement::ShowDatabases(stmt) => {
                 self.sql_handler
-                    .execute(SqlRequest::ShowDatabases(stmt))
+                    .execute(SqlRequest::ShowDatabases(stmt), query_ctx)
                     .await
             }
             Statement::ShowTables(stmt) => {
-                self.sql_handler.execute(SqlRequest::ShowTables(stmt)).await
+                self.sql_handler
+                    .execute(SqlRequest::ShowTables(stmt), query_ctx)
+                    .await
             }
             Statement::ShowCreateTable(_stmt) => {
                 unimplemented!("SHOW CREATE TABLE is unimplemented yet");
             }
+            Statement::Use(db) => {
+                let catalog_name = query_ctx.current_catalog();
+                let _ = self
+                    .catalog_manager
+                    .schema(&catalog_name, &db)
+                    .context(CatalogSnafu)?
+                    .context(SchemaNotFoundSnafu { name: &db })?;
+
+                query_ctx.set_current_schema(&db);
+                Ok(Output::AffectedRows(0))
+            }
+            Statement::SetVariables(set_variables) => {
+                let name = set_variables.name();
+                let value = set_variables.value();
+                if name == TIME_ZONE_VARIABLE {
+                    query_ctx.set_time_zone(Some(value));
+                } else {
+                    query_ctx.set_variable(&name, value);
+                }
+                Ok(Output::AffectedRows(0))
+            }
+            Statement::CreateUser(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)
+            | Statement::CreateRole(_)
+            | Statement::GrantRole(_)
+            | Statement::RevokeRole(_) => NotSupportedSnafu {
+                feat: "user and privilege management in datanode",
+            }
+            .fail(),
+            Statement::ShowProcesslist(_) | Statement::Kill(_) => NotSupportedSnafu {
+                feat: "process list in datanode",
+            }
+            .fail(),
         }
     }
 }
 
 #[async_trait]
 impl SqlQueryHandler for Instance {
-    async fn do_query(&self, query: &str) -> servers::error::Result<Output> {
+    async fn do_statement_query(
+        &self,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<Output> {
+        let _timer = timer!(metric::METRIC_HANDLE_SQL_ELAPSED);
+        self.execute_sql(query, query_ctx)
+            .await
+            .map_err(|e| {
+                error!(e; "Instance failed to execute sql");
+                BoxedError::new(e)
+            })
+            .context(servers::error::ExecuteQuerySnafu { query })
+    }
+
+    async fn do_parsed_statement_query(
+        &self,
+        stmt: Statement,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<Output> {
         let _timer = timer!(metric::METRIC_HANDLE_SQL_ELAPSED);
-        self.execute_sql(query)
+        self.execute_stmt(stmt, query_ctx)
             .await
             .map_err(|e| {
                 error!(e; "Instance failed to execute sql");
@@ -134,4 +226,15 @@ impl SqlQueryHandler for Instance {
             })
             .context(servers::error::ExecuteQuerySnafu { query })
     }
+
+    fn param_types(
+        &self,
+        stmt: &PreparedStatement,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<Vec<Option<ConcreteDataType>>> {
+        query::parameter::infer_param_types(stmt, &self.catalog_manager, &query_ctx)
+            .context(ExecuteSqlSnafu)
+            .map_err(BoxedError::new)
+            .context(servers::error::ExecuteQuerySnafu { query: stmt.sql() })
+    }
 }
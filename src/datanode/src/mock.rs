@@ -58,11 +58,19 @@ impl Instance {
         let factory = QueryEngineFactory::new(catalog_manager.clone());
         let query_engine = factory.query_engine();
 
-        let sql_handler = SqlHandler::new(mock_engine.clone(), catalog_manager.clone());
+        let sql_handler = Arc::new(SqlHandler::new(
+            mock_engine.clone(),
+            catalog_manager.clone(),
+            query_engine.clone(),
+        ));
         let physical_planner = PhysicalPlanner::new(query_engine.clone());
-        let script_executor = ScriptExecutor::new(catalog_manager.clone(), query_engine.clone())
-            .await
-            .unwrap();
+        let script_executor = ScriptExecutor::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            sql_handler.clone(),
+        )
+        .await
+        .unwrap();
 
         let heartbeat_task = Some(HeartbeatTask::new(
             0,
@@ -111,16 +119,26 @@ impl Instance {
             }),
         ));
 
-        let factory = QueryEngineFactory::new(catalog_manager.clone());
+        let factory =
+            QueryEngineFactory::new_with_cache(catalog_manager.clone(), &opts.query_cache);
         let query_engine = factory.query_engine();
-        let script_executor =
-            ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?;
+        let sql_handler = Arc::new(SqlHandler::new(
+            table_engine,
+            catalog_manager.clone(),
+            query_engine.clone(),
+        ));
+        let script_executor = ScriptExecutor::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            sql_handler.clone(),
+        )
+        .await?;
 
         let heartbeat_task =
             HeartbeatTask::new(opts.node_id, opts.rpc_addr.clone(), meta_client.clone());
         Ok(Self {
             query_engine: query_engine.clone(),
-            sql_handler: SqlHandler::new(table_engine, catalog_manager.clone()),
+            sql_handler,
             catalog_manager,
             physical_planner: PhysicalPlanner::new(query_engine),
             script_executor,
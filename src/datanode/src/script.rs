@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use catalog::CatalogManagerRef;
 use common_query::Output;
 use query::QueryEngineRef;
 
 use crate::error::Result;
+use crate::sql::SqlHandler;
 
 #[cfg(not(feature = "python"))]
 mod dummy {
@@ -28,19 +31,29 @@ mod dummy {
         pub async fn new(
             _catalog_manager: CatalogManagerRef,
             _query_engine: QueryEngineRef,
+            _sql_handler: Arc<SqlHandler>,
         ) -> Result<Self> {
             Ok(Self {})
         }
 
+        pub async fn start(&self) -> Result<()> {
+            Ok(())
+        }
+
         pub async fn insert_script(
             &self,
+            _schema: &str,
             _name: &str,
             _script: &str,
         ) -> servers::error::Result<()> {
             servers::error::NotSupportedSnafu { feat: "script" }.fail()
         }
 
-        pub async fn execute_script(&self, _script: &str) -> servers::error::Result<Output> {
+        pub async fn execute_script(
+            &self,
+            _schema: &str,
+            _name: &str,
+        ) -> servers::error::Result<Output> {
             servers::error::NotSupportedSnafu { feat: "script" }.fail()
         }
     }
@@ -48,10 +61,13 @@ mod dummy {
 
 #[cfg(feature = "python")]
 mod python {
+    use async_trait::async_trait;
     use common_error::prelude::BoxedError;
     use common_telemetry::logging::error;
     use script::manager::ScriptManager;
+    use script::scheduler::InsertHandler;
     use snafu::ResultExt;
+    use table::requests::InsertRequest;
 
     use super::*;
 
@@ -59,22 +75,52 @@ mod python {
         script_manager: ScriptManager,
     }
 
+    /// Scheduled scripts insert their results the same as the inserts of users, e.g. into the
+    /// materialized views of the table as well.
+    #[async_trait]
+    impl InsertHandler for SqlHandler {
+        async fn insert(&self, request: InsertRequest) -> std::result::Result<usize, BoxedError> {
+            match SqlHandler::insert(self, request)
+                .await
+                .map_err(BoxedError::new)?
+            {
+                Output::AffectedRows(rows) => Ok(rows),
+                _ => unreachable!(),
+            }
+        }
+    }
+
     impl ScriptExecutor {
         pub async fn new(
             catalog_manager: CatalogManagerRef,
             query_engine: QueryEngineRef,
+            sql_handler: Arc<SqlHandler>,
         ) -> Result<Self> {
             Ok(Self {
-                script_manager: ScriptManager::new(catalog_manager, query_engine)
+                script_manager: ScriptManager::new(catalog_manager, query_engine, sql_handler)
                     .await
                     .context(crate::error::StartScriptManagerSnafu)?,
             })
         }
 
-        pub async fn insert_script(&self, name: &str, script: &str) -> servers::error::Result<()> {
+        /// Registers the scripts declared as udf and schedules the periodic ones, the catalog
+        /// must be started.
+        pub async fn start(&self) -> Result<()> {
+            self.script_manager
+                .load_scripts()
+                .await
+                .context(crate::error::StartScriptManagerSnafu)
+        }
+
+        pub async fn insert_script(
+            &self,
+            schema: &str,
+            name: &str,
+            script: &str,
+        ) -> servers::error::Result<()> {
             let _s = self
                 .script_manager
-                .insert_and_compile(name, script)
+                .insert_and_compile(schema, name, script)
                 .await
                 .map_err(|e| {
                     error!(e; "Instance failed to insert script");
@@ -85,9 +131,13 @@ mod python {
             Ok(())
         }
 
-        pub async fn execute_script(&self, name: &str) -> servers::error::Result<Output> {
+        pub async fn execute_script(
+            &self,
+            schema: &str,
+            name: &str,
+        ) -> servers::error::Result<Output> {
             self.script_manager
-                .execute(name)
+                .execute(schema, name)
                 .await
                 .map_err(|e| {
                     error!(e; "Instance failed to execute script");
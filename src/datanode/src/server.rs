@@ -24,7 +24,7 @@ use snafu::ResultExt;
 use tokio::try_join;
 
 use crate::datanode::DatanodeOptions;
-use crate::error::{ParseAddrSnafu, Result, RuntimeResourceSnafu, StartServerSnafu};
+use crate::error::{ParseAddrSnafu, Result, RuntimeResourceSnafu, SetupTlsSnafu, StartServerSnafu};
 use crate::instance::InstanceRef;
 
 pub mod grpc;
@@ -53,9 +53,13 @@ impl Services {
                 .context(RuntimeResourceSnafu)?,
         );
 
+        let mut grpc_server = GrpcServer::new(instance.clone(), instance.clone(), grpc_runtime);
+        grpc_server.set_tls(&opts.rpc_tls).context(SetupTlsSnafu)?;
+        let mysql_tls = opts.mysql_tls.setup().context(SetupTlsSnafu)?;
+
         Ok(Self {
-            grpc_server: GrpcServer::new(instance.clone(), instance.clone(), grpc_runtime),
-            mysql_server: MysqlServer::create_server(instance, mysql_io_runtime),
+            grpc_server,
+            mysql_server: MysqlServer::create_server(instance, mysql_io_runtime, None, mysql_tls),
         })
     }
 
@@ -22,8 +22,10 @@ use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_error::prelude::{ErrorExt, StatusCode};
 use common_query::Output;
 use common_telemetry::{error, info};
+use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, SchemaBuilder, SchemaRef};
 use futures::TryFutureExt;
+use session::context::QueryContext;
 use snafu::prelude::*;
 use table::metadata::TableId;
 use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, CreateTableRequest};
@@ -80,7 +82,10 @@ impl Instance {
 
         let request = create_expr_to_request(table_id, expr).await;
         let result = futures::future::ready(request)
-            .and_then(|request| self.sql_handler().execute(SqlRequest::CreateTable(request)))
+            .and_then(|request| {
+                self.sql_handler()
+                    .execute(SqlRequest::CreateTable(request), QueryContext::arc())
+            })
             .await;
         match result {
             Ok(Output::AffectedRows(rows)) => AdminResultBuilder::default()
@@ -108,7 +113,10 @@ impl Instance {
         };
 
         let result = futures::future::ready(request)
-            .and_then(|request| self.sql_handler().execute(SqlRequest::Alter(request)))
+            .and_then(|request| {
+                self.sql_handler()
+                    .execute(SqlRequest::Alter(request), QueryContext::arc())
+            })
             .await;
         match result {
             Ok(Output::AffectedRows(rows)) => AdminResultBuilder::default()
@@ -232,26 +240,23 @@ fn create_table_schema(expr: &CreateExpr) -> Result<SchemaRef> {
 
 fn create_column_schema(column_def: &ColumnDef) -> Result<ColumnSchema> {
     let data_type =
-        ColumnDataTypeWrapper::try_new(column_def.datatype).context(error::ColumnDataTypeSnafu)?;
+        ColumnDataTypeWrapper::try_new(column_def.datatype, column_def.datatype_extension.clone())
+            .and_then(ConcreteDataType::try_from)
+            .context(error::ColumnDataTypeSnafu)?;
     let default_constraint = match &column_def.default_constraint {
         None => None,
         Some(v) => {
             Some(ColumnDefaultConstraint::try_from(&v[..]).context(ColumnDefaultConstraintSnafu)?)
         }
     };
-    ColumnSchema::new(
-        column_def.name.clone(),
-        data_type.into(),
-        column_def.is_nullable,
-    )
-    .with_default_constraint(default_constraint)
-    .context(ColumnDefaultConstraintSnafu)
+    ColumnSchema::new(column_def.name.clone(), data_type, column_def.is_nullable)
+        .with_default_constraint(default_constraint)
+        .context(ColumnDefaultConstraintSnafu)
 }
 
 #[cfg(test)]
 mod tests {
     use common_catalog::consts::MIN_USER_TABLE_ID;
-    use datatypes::prelude::ConcreteDataType;
     use datatypes::value::Value;
 
     use super::*;
@@ -307,6 +312,7 @@ mod tests {
             datatype: 1024,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         };
         let result = create_column_schema(&column_def);
         assert!(result.is_err());
@@ -320,6 +326,7 @@ mod tests {
             datatype: 12, // string
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         };
         let column_schema = create_column_schema(&column_def).unwrap();
         assert_eq!(column_schema.name, "a");
@@ -332,6 +339,7 @@ mod tests {
             datatype: 12, // string
             is_nullable: true,
             default_constraint: Some(default_constraint.clone().try_into().unwrap()),
+            datatype_extension: None,
         };
         let column_schema = create_column_schema(&column_def).unwrap();
         assert_eq!(column_schema.name, "a");
@@ -350,24 +358,28 @@ mod tests {
                 datatype: 12, // string
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "ts".to_string(),
                 datatype: 15, // timestamp
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "cpu".to_string(),
                 datatype: 9, // float32
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "memory".to_string(),
                 datatype: 10, // float64
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ];
         CreateExpr {
@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use api::helper::ColumnDataTypeWrapper;
 use api::result::{build_err_result, ObjectResultBuilder};
-use api::v1::codec::SelectResult;
+use api::v1::codec::{self, SelectResult};
 use api::v1::column::{SemanticType, Values};
 use api::v1::{Column, ObjectResult};
 use arrow::array::{Array, BooleanArray, PrimitiveArray};
@@ -26,6 +27,8 @@ use common_query::Output;
 use common_recordbatch::{util, RecordBatches, SendableRecordBatchStream};
 use datatypes::arrow_array::{BinaryArray, StringArray};
 use datatypes::schema::SchemaRef;
+use datatypes::vectors::Helper;
+use query::plan::OperatorMetrics;
 use snafu::{OptionExt, ResultExt};
 
 use crate::error::{self, ConversionSnafu, Result};
@@ -45,6 +48,39 @@ pub async fn to_object_result(output: Result<Output>) -> ObjectResult {
         Err(e) => build_err_result(&e),
     }
 }
+/// Converts the results of a logical plan from the frontend, along with the metrics of
+/// executing it.
+pub fn to_object_result_with_metrics(
+    result: Result<(RecordBatches, Vec<OperatorMetrics>)>,
+) -> ObjectResult {
+    let result = result.and_then(|(recordbatches, metrics)| {
+        let mut select_result = try_convert(recordbatches)?;
+        select_result.metrics = metrics.into_iter().map(to_operator_metrics).collect();
+        Ok(ObjectResultBuilder::new()
+            .status_code(StatusCode::Success as u32)
+            .select_result(select_result)
+            .build())
+    });
+    match result {
+        Ok(r) => r,
+        Err(e) => build_err_result(&e),
+    }
+}
+
+fn to_operator_metrics(metrics: OperatorMetrics) -> codec::OperatorMetrics {
+    let to_u64 = |values: HashMap<String, usize>| {
+        values
+            .into_iter()
+            .map(|(name, value)| (name, value as u64))
+            .collect()
+    };
+    codec::OperatorMetrics {
+        operator: metrics.operator,
+        counters: to_u64(metrics.counters),
+        times: to_u64(metrics.times),
+    }
+}
+
 async fn collect(stream: SendableRecordBatchStream) -> Result<ObjectResult> {
     let schema = stream.schema();
 
@@ -97,14 +133,17 @@ fn try_convert(record_batches: RecordBatches) -> Result<SelectResult> {
             .map(|r| r.df_recordbatch.columns()[idx].clone())
             .collect();
 
+        let (datatype, datatype_extension) =
+            ColumnDataTypeWrapper::try_from(column_schema.data_type.clone())
+                .context(error::ColumnDataTypeSnafu)?
+                .into_parts();
         let column = Column {
             column_name,
             values: Some(values(&arrays)?),
             null_mask: null_mask(&arrays, row_count),
-            datatype: ColumnDataTypeWrapper::try_from(column_schema.data_type.clone())
-                .context(error::ColumnDataTypeSnafu)?
-                .datatype() as i32,
+            datatype: datatype as i32,
             semantic_type: get_semantic_type(&schema, idx),
+            datatype_extension,
         };
         columns.push(column);
     }
@@ -112,6 +151,7 @@ fn try_convert(record_batches: RecordBatches) -> Result<SelectResult> {
     Ok(SelectResult {
         columns,
         row_count: row_count as u32,
+        ..Default::default()
     })
 }
 
@@ -152,7 +192,20 @@ macro_rules! convert_arrow_array_to_grpc_vals {
                     return Ok(vals);
                 },
             )+
-            _ => unimplemented!(),
+            // Parameterized and nested types are encoded through their vectors.
+            _ => {
+                let mut column = Column::default();
+                let mut row_count = 0;
+                for array in $arrays {
+                    let vector = Helper::try_into_vector(array.clone())
+                        .context(error::VectorComputationSnafu)?;
+                    column
+                        .push_vals(row_count, vector)
+                        .context(error::ColumnDataTypeSnafu)?;
+                    row_count += array.len();
+                }
+                return Ok(column.values.unwrap_or_default());
+            }
         }
     };
 }
@@ -16,7 +16,10 @@
 
 use catalog::CatalogManagerRef;
 use common_query::Output;
+use query::materialized_view::MaterializedViews;
 use query::sql::{show_databases, show_tables};
+use query::QueryEngineRef;
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 use sql::statements::show::{ShowDatabases, ShowTables};
 use table::engine::{EngineContext, TableEngineRef, TableReference};
@@ -28,6 +31,7 @@ use crate::error::{self, GetTableSnafu, Result, TableNotFoundSnafu};
 mod alter;
 mod create;
 mod insert;
+mod materialized_view;
 
 #[derive(Debug)]
 pub enum SqlRequest {
@@ -43,27 +47,37 @@ pub enum SqlRequest {
 pub struct SqlHandler {
     table_engine: TableEngineRef,
     catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+    materialized_views: MaterializedViews,
 }
 
 impl SqlHandler {
-    pub fn new(table_engine: TableEngineRef, catalog_manager: CatalogManagerRef) -> Self {
+    pub fn new(
+        table_engine: TableEngineRef,
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+    ) -> Self {
         Self {
             table_engine,
             catalog_manager,
+            query_engine,
+            materialized_views: MaterializedViews::default(),
         }
     }
 
-    pub async fn execute(&self, request: SqlRequest) -> Result<Output> {
+    pub async fn execute(&self, request: SqlRequest, query_ctx: QueryContextRef) -> Result<Output> {
         match request {
             SqlRequest::Insert(req) => self.insert(req).await,
             SqlRequest::CreateTable(req) => self.create_table(req).await,
             SqlRequest::CreateDatabase(req) => self.create_database(req).await,
             SqlRequest::Alter(req) => self.alter(req).await,
             SqlRequest::ShowDatabases(stmt) => {
-                show_databases(stmt, self.catalog_manager.clone()).context(error::ExecuteSqlSnafu)
+                show_databases(stmt, self.catalog_manager.clone(), query_ctx)
+                    .context(error::ExecuteSqlSnafu)
             }
             SqlRequest::ShowTables(stmt) => {
-                show_tables(stmt, self.catalog_manager.clone()).context(error::ExecuteSqlSnafu)
+                show_tables(stmt, self.catalog_manager.clone(), query_ctx)
+                    .context(error::ExecuteSqlSnafu)
             }
         }
     }
@@ -100,6 +114,7 @@ mod tests {
     use object_store::services::fs::Builder;
     use object_store::ObjectStore;
     use query::QueryEngineFactory;
+    use session::context::QueryContext;
     use sql::statements::statement::Statement;
     use storage::config::EngineConfig as StorageEngineConfig;
     use storage::EngineImpl;
@@ -211,7 +226,7 @@ mod tests {
         );
         let factory = QueryEngineFactory::new(catalog_list.clone());
         let query_engine = factory.query_engine();
-        let sql_handler = SqlHandler::new(table_engine, catalog_list);
+        let sql_handler = SqlHandler::new(table_engine, catalog_list, query_engine.clone());
 
         let stmt = match query_engine.sql_to_statement(sql).unwrap() {
             Statement::Insert(i) => i,
@@ -221,7 +236,7 @@ mod tests {
         };
         let schema_provider = Arc::new(MockSchemaProvider {});
         let request = sql_handler
-            .insert_to_request(schema_provider, *stmt)
+            .insert_to_request(schema_provider, *stmt, QueryContext::arc())
             .unwrap();
 
         match request {
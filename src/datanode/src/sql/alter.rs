@@ -14,6 +14,7 @@
 
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
 use sql::statements::{column_def_to_schema, table_idents_to_full_name};
@@ -53,9 +54,14 @@ impl SqlHandler {
         Ok(Output::AffectedRows(0))
     }
 
-    pub(crate) fn alter_to_request(&self, alter_table: AlterTable) -> Result<AlterTableRequest> {
+    pub(crate) fn alter_to_request(
+        &self,
+        alter_table: AlterTable,
+        query_ctx: QueryContextRef,
+    ) -> Result<AlterTableRequest> {
         let (catalog_name, schema_name, table_name) =
-            table_idents_to_full_name(alter_table.table_name()).context(error::ParseSqlSnafu)?;
+            table_idents_to_full_name(alter_table.table_name(), &query_ctx)
+                .context(error::ParseSqlSnafu)?;
 
         let alter_kind = match alter_table.alter_operation() {
             AlterTableOperation::AddConstraint(table_constraint) => {
@@ -87,6 +93,7 @@ mod tests {
     use std::assert_matches::assert_matches;
 
     use datatypes::prelude::ConcreteDataType;
+    use session::context::QueryContext;
     use sql::dialect::GenericDialect;
     use sql::parser::ParserContext;
     use sql::statements::statement::Statement;
@@ -109,7 +116,9 @@ mod tests {
     async fn test_alter_to_request_with_adding_column() {
         let handler = create_mock_sql_handler().await;
         let alter_table = parse_sql("ALTER TABLE my_metric_1 ADD tagk_i STRING Null;");
-        let req = handler.alter_to_request(alter_table).unwrap();
+        let req = handler
+            .alter_to_request(alter_table, QueryContext::arc())
+            .unwrap();
         assert_eq!(req.catalog_name, Some("greptime".to_string()));
         assert_eq!(req.schema_name, Some("public".to_string()));
         assert_eq!(req.table_name, "my_metric_1");
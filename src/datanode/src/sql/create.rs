@@ -21,6 +21,7 @@ use common_query::Output;
 use common_telemetry::tracing::info;
 use common_telemetry::tracing::log::error;
 use datatypes::schema::SchemaBuilder;
+use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::TableConstraint;
 use sql::statements::create::CreateTable;
@@ -115,12 +116,13 @@ impl SqlHandler {
         &self,
         table_id: TableId,
         stmt: CreateTable,
+        query_ctx: QueryContextRef,
     ) -> Result<CreateTableRequest> {
         let mut ts_index = usize::MAX;
         let mut primary_keys = vec![];
 
         let (catalog_name, schema_name, table_name) =
-            table_idents_to_full_name(&stmt.name).context(error::ParseSqlSnafu)?;
+            table_idents_to_full_name(&stmt.name, &query_ctx).context(error::ParseSqlSnafu)?;
 
         let col_map = stmt
             .columns
@@ -231,6 +233,7 @@ mod tests {
     use std::assert_matches::assert_matches;
 
     use datatypes::prelude::ConcreteDataType;
+    use session::context::QueryContext;
     use sql::dialect::GenericDialect;
     use sql::parser::ParserContext;
     use sql::statements::statement::Statement;
@@ -262,7 +265,9 @@ mod tests {
                        TIME INDEX (ts),
                        PRIMARY KEY(host)) engine=mito with(regions=1);"#,
         );
-        let c = handler.create_to_request(42, parsed_stmt).unwrap();
+        let c = handler
+            .create_to_request(42, parsed_stmt, QueryContext::arc())
+            .unwrap();
         assert_eq!("demo_table", c.table_name);
         assert_eq!(42, c.id);
         assert!(!c.create_if_not_exists);
@@ -283,7 +288,9 @@ mod tests {
                       memory double,
                       PRIMARY KEY(host)) engine=mito with(regions=1);"#,
         );
-        let error = handler.create_to_request(42, parsed_stmt).unwrap_err();
+        let error = handler
+            .create_to_request(42, parsed_stmt, QueryContext::arc())
+            .unwrap_err();
         assert_matches!(error, Error::MissingTimestampColumn { .. });
     }
 
@@ -300,7 +307,9 @@ mod tests {
                       memory double,
                       TIME INDEX (ts)) engine=mito with(regions=1);"#,
         );
-        let c = handler.create_to_request(42, parsed_stmt).unwrap();
+        let c = handler
+            .create_to_request(42, parsed_stmt, QueryContext::arc())
+            .unwrap();
         assert_eq!(1, c.primary_key_indices.len());
         assert_eq!(
             c.schema.timestamp_index().unwrap(),
@@ -319,7 +328,9 @@ mod tests {
                 TIME INDEX (ts)) engine=mito with(regions=1);"#,
         );
 
-        let error = handler.create_to_request(42, parsed_stmt).unwrap_err();
+        let error = handler
+            .create_to_request(42, parsed_stmt, QueryContext::arc())
+            .unwrap_err();
         assert_matches!(error, Error::KeyColumnNotFound { .. });
     }
 
@@ -339,7 +350,9 @@ mod tests {
 
         let handler = create_mock_sql_handler().await;
 
-        let error = handler.create_to_request(42, create_table).unwrap_err();
+        let error = handler
+            .create_to_request(42, create_table, QueryContext::arc())
+            .unwrap_err();
         assert_matches!(error, Error::InvalidPrimaryKey { .. });
     }
 
@@ -359,7 +372,9 @@ mod tests {
 
         let handler = create_mock_sql_handler().await;
 
-        let request = handler.create_to_request(42, create_table).unwrap();
+        let request = handler
+            .create_to_request(42, create_table, QueryContext::arc())
+            .unwrap();
 
         assert_eq!(42, request.id);
         assert_eq!("c".to_string(), request.catalog_name);
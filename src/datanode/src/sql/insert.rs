@@ -15,6 +15,7 @@
 use catalog::SchemaProviderRef;
 use common_query::Output;
 use datatypes::prelude::{ConcreteDataType, VectorBuilder};
+use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::Value as SqlValue;
 use sql::statements::insert::Insert;
@@ -40,9 +41,12 @@ impl SqlHandler {
 
         let table = self.get_table(&table_ref)?;
 
+        let columns_values = req.columns_values.clone();
         let affected_rows = table.insert(req).await.with_context(|_| InsertSnafu {
             table_name: table_ref.to_string(),
         })?;
+        self.insert_into_materialized_views(&table_ref, &columns_values)
+            .await?;
 
         Ok(Output::AffectedRows(affected_rows))
     }
@@ -51,11 +55,12 @@ impl SqlHandler {
         &self,
         schema_provider: SchemaProviderRef,
         stmt: Insert,
+        query_ctx: QueryContextRef,
     ) -> Result<SqlRequest> {
         let columns = stmt.columns();
         let values = stmt.values().context(ParseSqlValueSnafu)?;
         let (catalog_name, schema_name, table_name) =
-            stmt.full_table_name().context(ParseSqlSnafu)?;
+            stmt.full_table_name(&query_ctx).context(ParseSqlSnafu)?;
 
         let table = schema_provider
             .table(&table_name)
@@ -0,0 +1,238 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_query::Output;
+use common_telemetry::logging::{error, info};
+use datatypes::vectors::VectorRef;
+use query::materialized_view::{MaterializedView, ViewTable};
+use session::context::QueryContext;
+use snafu::{OptionExt, ResultExt};
+use sql::statements::create::CreateMaterializedView;
+use sql::statements::query::Query;
+use sql::statements::statement::Statement;
+use sql::statements::table_idents_to_full_name;
+use table::engine::TableReference;
+use table::metadata::TableId;
+use table::requests::CreateTableRequest;
+use table::TableRef;
+
+use crate::error::{
+    CatalogSnafu, ExecuteSqlSnafu, FindTableSnafu, InvalidSqlSnafu, MaterializedViewSnafu,
+    ParseSqlSnafu, Result, TableNotFoundSnafu, UpdateMaterializedViewsSnafu,
+};
+use crate::sql::SqlHandler;
+
+/// Key of the table option that stores the query of a materialized view, the view is created
+/// again from it when the datanode starts.
+const MATERIALIZED_VIEW_OPTION: &str = "materialized_view";
+
+impl SqlHandler {
+    pub(crate) async fn create_materialized_view(
+        &self,
+        table_id: TableId,
+        stmt: CreateMaterializedView,
+        query_ctx: &QueryContext,
+    ) -> Result<Output> {
+        let (catalog_name, schema_name, table_name) =
+            table_idents_to_full_name(&stmt.name, query_ctx).context(ParseSqlSnafu)?;
+        let name = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+
+        let exists = self
+            .catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .context(FindTableSnafu { table_name: &name })?
+            .is_some();
+        if exists && stmt.if_not_exists {
+            return Ok(Output::AffectedRows(0));
+        }
+
+        let (source, view) =
+            self.new_materialized_view(&name, &catalog_name, &schema_name, &stmt.query)?;
+        let request = CreateTableRequest {
+            id: table_id,
+            catalog_name: catalog_name.clone(),
+            schema_name: schema_name.clone(),
+            table_name: table_name.clone(),
+            desc: None,
+            schema: view.schema(),
+            region_numbers: vec![0],
+            primary_key_indices: view.primary_key_indices(),
+            create_if_not_exists: false,
+            table_options: HashMap::from([(
+                MATERIALIZED_VIEW_OPTION.to_string(),
+                stmt.query.inner.to_string(),
+            )]),
+        };
+        let output = self.create_table(request).await?;
+
+        let table = self
+            .catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .context(FindTableSnafu { table_name: &name })?
+            .context(TableNotFoundSnafu { table_name: &name })?;
+        info!("Created materialized view {} over table {}", name, source);
+        self.materialized_views
+            .register(ViewTable::new(name, source, table, view, false));
+        Ok(output)
+    }
+
+    /// Creates the views stored in the options of the tables again, and rebuilds the states of
+    /// the buckets still open when the datanode stopped from the rows of their source tables.
+    pub(crate) async fn load_materialized_views(&self) -> Result<()> {
+        for catalog_name in self.catalog_manager.catalog_names().context(CatalogSnafu)? {
+            let catalog = match self
+                .catalog_manager
+                .catalog(&catalog_name)
+                .context(CatalogSnafu)?
+            {
+                Some(catalog) => catalog,
+                None => continue,
+            };
+            for schema_name in catalog.schema_names().context(CatalogSnafu)? {
+                let schema = match catalog.schema(&schema_name).context(CatalogSnafu)? {
+                    Some(schema) => schema,
+                    None => continue,
+                };
+                for table_name in schema.table_names().context(CatalogSnafu)? {
+                    let table = match schema.table(&table_name).context(CatalogSnafu)? {
+                        Some(table) => table,
+                        None => continue,
+                    };
+                    let sql = match table
+                        .table_info()
+                        .meta
+                        .options
+                        .get(MATERIALIZED_VIEW_OPTION)
+                    {
+                        Some(sql) => sql.clone(),
+                        None => continue,
+                    };
+
+                    let name = TableReference {
+                        catalog: &catalog_name,
+                        schema: &schema_name,
+                        table: &table_name,
+                    }
+                    .to_string();
+                    if let Err(e) = self
+                        .load_materialized_view(
+                            name.clone(),
+                            &catalog_name,
+                            &schema_name,
+                            table,
+                            &sql,
+                        )
+                        .await
+                    {
+                        error!(e; "Failed to load materialized view {}", name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn load_materialized_view(
+        &self,
+        name: String,
+        catalog_name: &str,
+        schema_name: &str,
+        table: TableRef,
+        sql: &str,
+    ) -> Result<()> {
+        let query = match self
+            .query_engine
+            .sql_to_statement(sql)
+            .context(ExecuteSqlSnafu)?
+        {
+            Statement::Query(query) => query,
+            _ => {
+                return InvalidSqlSnafu {
+                    msg: format!("the query of materialized view {} is not a SELECT", name),
+                }
+                .fail()
+            }
+        };
+        let (source, view) =
+            self.new_materialized_view(&name, catalog_name, schema_name, &query)?;
+        let created_on = table.table_info().meta.created_on.timestamp_millis();
+        let view = ViewTable::new(name.clone(), source, table, view, false);
+        view.rebuild(created_on, self.query_engine.as_ref())
+            .await
+            .context(MaterializedViewSnafu { name: &name })?;
+        info!(
+            "Loaded materialized view {} over table {}",
+            name,
+            view.source()
+        );
+        self.materialized_views.register(view);
+        Ok(())
+    }
+
+    /// Creates the view of the query, returns it with the full name of its source table. An
+    /// unqualified source table is looked up in the catalog and schema of the view.
+    fn new_materialized_view(
+        &self,
+        name: &str,
+        catalog_name: &str,
+        schema_name: &str,
+        query: &Query,
+    ) -> Result<(String, MaterializedView)> {
+        let source =
+            MaterializedView::source_table(query).context(MaterializedViewSnafu { name })?;
+        let (catalog_name, schema_name, table_name) =
+            table_idents_to_full_name(source, &QueryContext::with(catalog_name, schema_name))
+                .context(ParseSqlSnafu)?;
+        let source = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+
+        let table = self
+            .catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .context(FindTableSnafu {
+                table_name: &source,
+            })?
+            .context(TableNotFoundSnafu {
+                table_name: &source,
+            })?;
+        let view = MaterializedView::try_new(query, &table.schema(), self.query_engine.as_ref())
+            .context(MaterializedViewSnafu { name })?;
+        Ok((source, view))
+    }
+
+    /// Updates the materialized views over the table with the inserted rows, which have been
+    /// written into the table.
+    pub(crate) async fn insert_into_materialized_views(
+        &self,
+        table_ref: &TableReference<'_>,
+        columns_values: &HashMap<String, VectorRef>,
+    ) -> Result<()> {
+        let table_name = table_ref.to_string();
+        self.materialized_views
+            .insert(&table_name, columns_values, self.query_engine.as_ref())
+            .await
+            .context(UpdateMaterializedViewsSnafu { table_name })
+    }
+}
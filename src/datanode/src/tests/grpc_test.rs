@@ -67,6 +67,7 @@ async fn setup_grpc_server(
         grpc_options: Some(GrpcOptions {
             addr: fe_grpc_addr.clone(),
             runtime_size: 8,
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -115,7 +116,7 @@ async fn test_auto_create_table() {
         setup_grpc_server("auto_create_table", 3992, 3993).await;
 
     let grpc_client = Client::with_urls(vec![addr]);
-    let db = Database::new("greptime", grpc_client);
+    let db = Database::new("public", grpc_client);
     insert_and_assert(&db).await;
     let _ = fe_grpc_server.shutdown().await;
     let _ = dn_grpc_server.shutdown().await;
@@ -145,6 +146,7 @@ fn expect_data() -> (Column, Column, Column, Column) {
         null_mask: vec![2],
         semantic_type: SemanticType::Field as i32,
         datatype: ColumnDataType::Float64 as i32,
+        datatype_extension: None,
     };
     let expected_mem_col = Column {
         column_name: "memory".to_string(),
@@ -155,6 +157,7 @@ fn expect_data() -> (Column, Column, Column, Column) {
         null_mask: vec![4],
         semantic_type: SemanticType::Field as i32,
         datatype: ColumnDataType::Float64 as i32,
+        datatype_extension: None,
     };
     let expected_ts_col = Column {
         column_name: "ts".to_string(),
@@ -183,7 +186,7 @@ async fn test_insert_and_select() {
 
     let grpc_client = Client::with_urls(vec![addr]);
 
-    let db = Database::new("greptime", grpc_client.clone());
+    let db = Database::new("public", grpc_client.clone());
     let admin = Admin::new("greptime", grpc_client);
 
     // create
@@ -203,6 +206,7 @@ async fn test_insert_and_select() {
         datatype: ColumnDataType::Int64.into(),
         is_nullable: true,
         default_constraint: None,
+        datatype_extension: None,
     };
     let kind = Kind::AddColumns(AddColumns {
         add_columns: vec![AddColumn {
@@ -285,24 +289,28 @@ fn testing_create_expr() -> CreateExpr {
             datatype: ColumnDataType::String as i32,
             is_nullable: false,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "cpu".to_string(),
             datatype: ColumnDataType::Float64 as i32,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "memory".to_string(),
             datatype: ColumnDataType::Float64 as i32,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "ts".to_string(),
             datatype: 15, // timestamp
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
     ];
     CreateExpr {
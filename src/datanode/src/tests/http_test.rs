@@ -284,3 +284,62 @@ def test(n):
         unreachable!()
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_scripts_api_udf() {
+    common_telemetry::init_default_ut_logging();
+    let (app, _guard) = make_test_app_with_frontend("script_api_udf").await;
+    let client = TestClient::new(app);
+
+    // The udf is named after the script, not the python function.
+    let res = client
+        .post("/v1/scripts?db=public&name=add_one")
+        .body(
+            r#"
+@copr(args=['n'], returns=['r'], udf='scalar')
+def f(n: vector[u32]) -> vector[f64]:
+    return n + 1
+"#,
+        )
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<JsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.code(), 0);
+
+    let res = client
+        .get("/v1/sql?sql=select add_one(number) from numbers limit 2")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<JsonResponse>(&res.text().await).unwrap();
+    assert!(body.success(), "{:?}", body);
+    match &body.output().unwrap()[0] {
+        JsonOutput::Records(records) => {
+            assert_eq!(records.rows(), &vec![vec![json!(1.0)], vec![json!(2.0)]]);
+        }
+        _ => unreachable!(),
+    }
+
+    let res = client
+        .get("/v1/sql?sql=select f(number) from numbers limit 2")
+        .send()
+        .await;
+    let body = serde_json::from_str::<JsonResponse>(&res.text().await).unwrap();
+    assert!(!body.success());
+
+    // A script name must not contain '.', which separates the schema from the name.
+    let res = client
+        .post("/v1/scripts?name=a.b")
+        .body(
+            r#"
+@copr(args=['n'], returns=['r'], udf='scalar')
+def f(n: vector[u32]) -> vector[f64]:
+    return n + 1
+"#,
+        )
+        .send()
+        .await;
+    let body = serde_json::from_str::<JsonResponse>(&res.text().await).unwrap();
+    assert!(!body.success());
+}
@@ -19,6 +19,7 @@ use datafusion::arrow_print;
 use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
 use datatypes::arrow_array::StringArray;
 use datatypes::prelude::ConcreteDataType;
+use session::context::QueryContext;
 
 use crate::instance::Instance;
 use crate::tests::test_util;
@@ -32,7 +33,10 @@ async fn test_create_database_and_insert_query() {
     let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
     instance.start().await.unwrap();
 
-    let output = instance.execute_sql("create database test").await.unwrap();
+    let output = instance
+        .execute_sql("create database test", QueryContext::arc())
+        .await
+        .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
     let output = instance
@@ -44,6 +48,7 @@ async fn test_create_database_and_insert_query() {
              ts bigint,
              TIME INDEX(ts)
 )"#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -55,13 +60,14 @@ async fn test_create_database_and_insert_query() {
                            ('host1', 66.6, 1024, 1655276557000),
                            ('host2', 88.8,  333.3, 1655276558000)
                            "#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(2)));
 
     let query_output = instance
-        .execute_sql("select ts from test.demo order by ts")
+        .execute_sql("select ts from test.demo order by ts", QueryContext::arc())
         .await
         .unwrap();
 
@@ -78,6 +84,92 @@ async fn test_create_database_and_insert_query() {
         _ => unreachable!(),
     }
 }
+#[tokio::test(flavor = "multi_thread")]
+async fn test_use_database_and_set_variables() {
+    common_telemetry::init_default_ut_logging();
+
+    let (opts, _guard) = test_util::create_tmp_dir_and_datanode_opts("use_database");
+    let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
+    instance.start().await.unwrap();
+
+    let query_ctx = QueryContext::arc();
+    let output = instance
+        .execute_sql("create database my_db", query_ctx.clone())
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    assert!(instance
+        .execute_sql("use not_exist", query_ctx.clone())
+        .await
+        .is_err());
+    assert_eq!("public", query_ctx.current_schema());
+
+    let output = instance
+        .execute_sql("use my_db", query_ctx.clone())
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert_eq!("my_db", query_ctx.current_schema());
+
+    // Unqualified table names are resolved against the current database.
+    let output = instance
+        .execute_sql(
+            r#"create table demo(
+             host STRING,
+             cpu DOUBLE,
+             ts bigint,
+             TIME INDEX(ts)
+)"#,
+            query_ctx.clone(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = instance
+        .execute_sql(
+            "insert into demo(host, cpu, ts) values ('host1', 66.6, 1655276557000)",
+            query_ctx.clone(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = instance
+        .execute_sql("select ts from demo", query_ctx.clone())
+        .await
+        .unwrap();
+    match output {
+        Output::Stream(s) => {
+            let batches = util::collect(s).await.unwrap();
+            let columns = batches[0].df_recordbatch.columns();
+            assert_eq!(
+                &Int64Array::from_slice(&[1655276557000]),
+                columns[0].as_any().downcast_ref::<Int64Array>().unwrap()
+            );
+        }
+        _ => unreachable!(),
+    }
+    assert!(instance
+        .execute_sql("select ts from demo", QueryContext::arc())
+        .await
+        .is_err());
+
+    let output = instance
+        .execute_sql("set time_zone = '+08:00'", query_ctx.clone())
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = instance
+        .execute_sql("SET SESSION autocommit TO 1", query_ctx.clone())
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert_eq!(Some("+08:00".to_string()), query_ctx.time_zone());
+    assert_eq!(Some("1".to_string()), query_ctx.variable("autocommit"));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_issue477_same_table_name_in_different_databases() {
     common_telemetry::init_default_ut_logging();
@@ -88,9 +180,15 @@ async fn test_issue477_same_table_name_in_different_databases() {
     instance.start().await.unwrap();
 
     // Create database a and b
-    let output = instance.execute_sql("create database a").await.unwrap();
+    let output = instance
+        .execute_sql("create database a", QueryContext::arc())
+        .await
+        .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
-    let output = instance.execute_sql("create database b").await.unwrap();
+    let output = instance
+        .execute_sql("create database b", QueryContext::arc())
+        .await
+        .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
     // Create table a.demo and b.demo
@@ -101,6 +199,7 @@ async fn test_issue477_same_table_name_in_different_databases() {
              ts bigint,
              TIME INDEX(ts)
 )"#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -113,6 +212,7 @@ async fn test_issue477_same_table_name_in_different_databases() {
              ts bigint,
              TIME INDEX(ts)
 )"#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -124,6 +224,7 @@ async fn test_issue477_same_table_name_in_different_databases() {
             r#"insert into a.demo(host, ts) values
                            ('host1', 1655276557000)
                            "#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -133,6 +234,7 @@ async fn test_issue477_same_table_name_in_different_databases() {
             r#"insert into b.demo(host, ts) values
                            ('host2',1655276558000)
                            "#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -157,7 +259,10 @@ async fn test_issue477_same_table_name_in_different_databases() {
 }
 
 async fn assert_query_result(instance: &Instance, sql: &str, ts: i64, host: &str) {
-    let query_output = instance.execute_sql(sql).await.unwrap();
+    let query_output = instance
+        .execute_sql(sql, QueryContext::arc())
+        .await
+        .unwrap();
     match query_output {
         Output::Stream(s) => {
             let batches = util::collect(s).await.unwrap();
@@ -201,6 +306,7 @@ async fn test_execute_insert() {
                            ('host1', 66.6, 1024, 1655276557000),
                            ('host2', 88.8,  333.3, 1655276558000)
                            "#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -229,13 +335,14 @@ async fn test_execute_insert_query_with_i64_timestamp() {
                            ('host1', 66.6, 1024, 1655276557000),
                            ('host2', 88.8,  333.3, 1655276558000)
                            "#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(2)));
 
     let query_output = instance
-        .execute_sql("select ts from demo order by ts")
+        .execute_sql("select ts from demo order by ts", QueryContext::arc())
         .await
         .unwrap();
 
@@ -253,7 +360,10 @@ async fn test_execute_insert_query_with_i64_timestamp() {
     }
 
     let query_output = instance
-        .execute_sql("select ts as time from demo order by ts")
+        .execute_sql(
+            "select ts as time from demo order by ts",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
 
@@ -278,7 +388,10 @@ async fn test_execute_query() {
     instance.start().await.unwrap();
 
     let output = instance
-        .execute_sql("select sum(number) from numbers limit 20")
+        .execute_sql(
+            "select sum(number) from numbers limit 20",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
     match output {
@@ -304,7 +417,10 @@ async fn test_execute_show_databases_tables() {
     let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
     instance.start().await.unwrap();
 
-    let output = instance.execute_sql("show databases").await.unwrap();
+    let output = instance
+        .execute_sql("show databases", QueryContext::arc())
+        .await
+        .unwrap();
     match output {
         Output::RecordBatches(databases) => {
             let databases = databases.take();
@@ -321,7 +437,7 @@ async fn test_execute_show_databases_tables() {
     }
 
     let output = instance
-        .execute_sql("show databases like '%bl%'")
+        .execute_sql("show databases like '%bl%'", QueryContext::arc())
         .await
         .unwrap();
     match output {
@@ -339,7 +455,10 @@ async fn test_execute_show_databases_tables() {
         _ => unreachable!(),
     }
 
-    let output = instance.execute_sql("show tables").await.unwrap();
+    let output = instance
+        .execute_sql("show tables", QueryContext::arc())
+        .await
+        .unwrap();
     match output {
         Output::RecordBatches(databases) => {
             let databases = databases.take();
@@ -359,7 +478,10 @@ async fn test_execute_show_databases_tables() {
     .await
     .unwrap();
 
-    let output = instance.execute_sql("show tables").await.unwrap();
+    let output = instance
+        .execute_sql("show tables", QueryContext::arc())
+        .await
+        .unwrap();
     match output {
         Output::RecordBatches(databases) => {
             let databases = databases.take();
@@ -372,7 +494,7 @@ async fn test_execute_show_databases_tables() {
 
     // show tables like [string]
     let output = instance
-        .execute_sql("show tables like 'de%'")
+        .execute_sql("show tables like 'de%'", QueryContext::arc())
         .await
         .unwrap();
     match output {
@@ -409,6 +531,7 @@ pub async fn test_execute_create() {
                             TIME INDEX (ts),
                             PRIMARY KEY(host)
                         ) engine=mito with(regions=1);"#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -434,6 +557,7 @@ pub async fn test_create_table_illegal_timestamp_type() {
                             TIME INDEX (ts),
                             PRIMARY KEY(host)
                         ) engine=mito with(regions=1);"#,
+            QueryContext::arc(),
         )
         .await
         .unwrap();
@@ -445,6 +569,110 @@ pub async fn test_create_table_illegal_timestamp_type() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_materialized_view() {
+    common_telemetry::init_default_ut_logging();
+
+    let (opts, _guard) = test_util::create_tmp_dir_and_datanode_opts("materialized_view");
+    let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
+    instance.start().await.unwrap();
+
+    let output = instance
+        .execute_sql(
+            r#"create table demo(
+                            host string,
+                            cpu double,
+                            ts timestamp,
+                            TIME INDEX (ts),
+                            PRIMARY KEY(host)
+                        )"#,
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = instance
+        .execute_sql(
+            "create materialized view cpu_1s as select host, date_bin('1s', ts) as ts, \
+             avg(cpu) as cpu, count(*) as n from demo group by host, date_bin('1s', ts)",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // The second insert closes the first bucket, whose rows are written into the view.
+    for sql in [
+        "insert into demo(host, cpu, ts) values ('host1', 1.0, 0), ('host2', 2.0, 100), ('host1', 3.0, 500)",
+        "insert into demo(host, cpu, ts) values ('host1', 5.0, 1000)",
+    ] {
+        let output = instance.execute_sql(sql, QueryContext::arc()).await.unwrap();
+        assert!(matches!(output, Output::AffectedRows(_)));
+    }
+
+    let output = instance
+        .execute_sql(
+            "select host, ts, cpu, n from cpu_1s order by host",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    let expected = vec![
+        "+-------+---------------------+-----+---+",
+        "| host  | ts                  | cpu | n |",
+        "+-------+---------------------+-----+---+",
+        "| host1 | 1970-01-01 00:00:00 | 2   | 2 |",
+        "| host2 | 1970-01-01 00:00:00 | 2   | 1 |",
+        "+-------+---------------------+-----+---+",
+    ];
+    check_output_stream(output, expected).await;
+
+    // A late row computes the closed bucket again from the source table.
+    let output = instance
+        .execute_sql(
+            "insert into demo(host, cpu, ts) values ('host2', 4.0, 200)",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = instance
+        .execute_sql(
+            "select host, ts, cpu, n from cpu_1s order by host",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    let expected = vec![
+        "+-------+---------------------+-----+---+",
+        "| host  | ts                  | cpu | n |",
+        "+-------+---------------------+-----+---+",
+        "| host1 | 1970-01-01 00:00:00 | 2   | 2 |",
+        "| host2 | 1970-01-01 00:00:00 | 3   | 2 |",
+        "+-------+---------------------+-----+---+",
+    ];
+    check_output_stream(output, expected).await;
+
+    let output = instance
+        .execute_sql(
+            "create materialized view if not exists cpu_1s as select host, date_bin('1s', ts) as ts, \
+             avg(cpu) from demo group by host, date_bin('1s', ts)",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    assert!(instance
+        .execute_sql(
+            "create materialized view cpu_host as select host, avg(cpu) from demo group by host",
+            QueryContext::arc(),
+        )
+        .await
+        .is_err());
+}
+
 async fn check_output_stream(output: Output, expected: Vec<&str>) {
     match output {
         Output::Stream(stream) => {
@@ -461,6 +689,69 @@ async fn check_output_stream(output: Output, expected: Vec<&str>) {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_cache_invalidated_by_insert() {
+    let (mut opts, _guard) = test_util::create_tmp_dir_and_datanode_opts("query_cache");
+    opts.query_cache.capacity = 1024 * 1024;
+    let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
+    instance.start().await.unwrap();
+
+    test_util::create_test_table(
+        instance.catalog_manager(),
+        instance.sql_handler(),
+        ConcreteDataType::timestamp_millis_datatype(),
+    )
+    .await
+    .unwrap();
+    let sql = "select host, cpu from demo order by host";
+
+    let output = instance
+        .execute_sql(
+            "insert into demo(host, cpu, memory, ts) values ('host1', 1.1, 100, 1000)",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let expected = vec![
+        "+-------+-----+",
+        "| host  | cpu |",
+        "+-------+-----+",
+        "| host1 | 1.1 |",
+        "+-------+-----+",
+    ];
+    for _ in 0..2 {
+        let output = instance
+            .execute_sql(sql, QueryContext::arc())
+            .await
+            .unwrap();
+        check_output_stream(output, expected.clone()).await;
+    }
+
+    // The cached results are invalidated by the insertion.
+    let output = instance
+        .execute_sql(
+            "insert into demo(host, cpu, memory, ts) values ('host2', 2.2, 200, 2000)",
+            QueryContext::arc(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = instance
+        .execute_sql(sql, QueryContext::arc())
+        .await
+        .unwrap();
+    let expected = vec![
+        "+-------+-----+",
+        "| host  | cpu |",
+        "+-------+-----+",
+        "| host1 | 1.1 |",
+        "| host2 | 2.2 |",
+        "+-------+-----+",
+    ];
+    check_output_stream(output, expected).await;
+}
+
 #[tokio::test]
 async fn test_alter_table() {
     let instance = Instance::new_mock().await.unwrap();
@@ -475,30 +766,42 @@ async fn test_alter_table() {
     .unwrap();
     // make sure table insertion is ok before altering table
     instance
-        .execute_sql("insert into demo(host, cpu, memory, ts) values ('host1', 1.1, 100, 1000)")
+        .execute_sql(
+            "insert into demo(host, cpu, memory, ts) values ('host1', 1.1, 100, 1000)",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
 
     let output = instance
-        .execute_sql("alter table demo add my_tag string null")
+        .execute_sql(
+            "alter table demo add my_tag string null",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(0)));
 
     let output = instance
         .execute_sql(
-            "insert into demo(host, cpu, memory, ts, my_tag) values ('host2', 2.2, 200, 2000, 'hello')",
+            "insert into demo(host, cpu, memory, ts, my_tag) values ('host2', 2.2, 200, 2000, 'hello')", QueryContext::arc()
         )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
     let output = instance
-        .execute_sql("insert into demo(host, cpu, memory, ts) values ('host3', 3.3, 300, 3000)")
+        .execute_sql(
+            "insert into demo(host, cpu, memory, ts) values ('host3', 3.3, 300, 3000)",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
-    let output = instance.execute_sql("select * from demo").await.unwrap();
+    let output = instance
+        .execute_sql("select * from demo", QueryContext::arc())
+        .await
+        .unwrap();
     let expected = vec![
         "+-------+-----+--------+---------------------+--------+",
         "| host  | cpu | memory | ts                  | my_tag |",
@@ -526,25 +829,34 @@ async fn test_insert_with_default_value_for_type(type_name: &str) {
     ) engine=mito with(regions=1);"#,
         type_name
     );
-    let output = instance.execute_sql(&create_sql).await.unwrap();
+    let output = instance
+        .execute_sql(&create_sql, QueryContext::arc())
+        .await
+        .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
     // Insert with ts.
     instance
-        .execute_sql("insert into test_table(host, cpu, ts) values ('host1', 1.1, 1000)")
+        .execute_sql(
+            "insert into test_table(host, cpu, ts) values ('host1', 1.1, 1000)",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
     // Insert without ts, so it should be filled by default value.
     let output = instance
-        .execute_sql("insert into test_table(host, cpu) values ('host2', 2.2)")
+        .execute_sql(
+            "insert into test_table(host, cpu) values ('host2', 2.2)",
+            QueryContext::arc(),
+        )
         .await
         .unwrap();
     assert!(matches!(output, Output::AffectedRows(1)));
 
     let output = instance
-        .execute_sql("select host, cpu from test_table")
+        .execute_sql("select host, cpu from test_table", QueryContext::arc())
         .await
         .unwrap();
     let expected = vec![
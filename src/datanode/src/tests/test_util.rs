@@ -20,6 +20,7 @@ use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MIN_USER
 use datatypes::data_type::ConcreteDataType;
 use datatypes::schema::{ColumnSchema, SchemaBuilder};
 use frontend::frontend::Mode;
+use query::QueryEngineFactory;
 use snafu::ResultExt;
 use table::engine::{EngineContext, TableEngineRef};
 use table::requests::CreateTableRequest;
@@ -121,5 +122,6 @@ pub async fn create_mock_sql_handler() -> SqlHandler {
             .await
             .unwrap(),
     );
-    SqlHandler::new(mock_engine, catalog_manager)
+    let query_engine = QueryEngineFactory::new(catalog_manager.clone()).query_engine();
+    SqlHandler::new(mock_engine, catalog_manager, query_engine)
 }
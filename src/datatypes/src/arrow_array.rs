@@ -13,16 +13,21 @@
 // limitations under the License.
 
 use arrow::array::{
-    self, Array, BinaryArray as ArrowBinaryArray, ListArray,
-    MutableBinaryArray as ArrowMutableBinaryArray, MutableUtf8Array, PrimitiveArray, Utf8Array,
+    self, Array, BinaryArray as ArrowBinaryArray, DictionaryArray, ListArray,
+    MutableBinaryArray as ArrowMutableBinaryArray, MutableUtf8Array, PrimitiveArray, StructArray,
+    Utf8Array,
 };
-use arrow::datatypes::DataType as ArrowDataType;
+use arrow::datatypes::{DataType as ArrowDataType, IntervalUnit};
+use arrow::types::months_days_ns;
+use common_time::interval::Interval;
 use common_time::timestamp::Timestamp;
 use snafu::OptionExt;
 
+use crate::decimal::Decimal128;
 use crate::error::{ConversionSnafu, Result};
 use crate::prelude::ConcreteDataType;
-use crate::value::{ListValue, Value};
+use crate::types::{JSON_EXTENSION_NAME, MAP_EXTENSION_NAME};
+use crate::value::{ListValue, MapValue, StructValue, Value};
 
 pub type BinaryArray = ArrowBinaryArray<i64>;
 pub type MutableBinaryArray = ArrowMutableBinaryArray<i64>;
@@ -91,6 +96,52 @@ pub fn arrow_array_get(array: &dyn Array, idx: usize) -> Result<Value> {
                 .collect::<Result<Vec<Value>>>()?;
             Value::List(ListValue::new(Some(Box::new(values)), inner_datatype))
         }
+        ArrowDataType::Decimal(precision, scale) => {
+            let value = cast_array!(array, PrimitiveArray::<i128>).value(idx);
+            Value::Decimal128(Decimal128::new(value, *precision as u8, *scale as u8))
+        }
+        ArrowDataType::Interval(IntervalUnit::MonthDayNano) => {
+            let value = cast_array!(array, PrimitiveArray::<months_days_ns>).value(idx);
+            Value::Interval(Interval::new(value.months(), value.days(), value.ns()))
+        }
+        ArrowDataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME => {
+            Value::Json(cast_array!(array, StringArray).value(idx).into())
+        }
+        ArrowDataType::Extension(name, _, _) if name == MAP_EXTENSION_NAME => {
+            let datatype = match ConcreteDataType::try_from(array.data_type())? {
+                ConcreteDataType::Map(t) => t,
+                _ => unreachable!(),
+            };
+            let array = cast_array!(array, ListArray::<i32>);
+            let entries = cast_array!(&**array.values(), StructArray).values();
+            let offsets = array.offsets();
+            let entries = (offsets[idx] as usize..offsets[idx + 1] as usize)
+                .map(|i| {
+                    Ok((
+                        arrow_array_get(&*entries[0], i)?,
+                        arrow_array_get(&*entries[1], i)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Value::Map(MapValue::new(entries, datatype))
+        }
+        ArrowDataType::Struct(_) => {
+            let datatype = match ConcreteDataType::try_from(array.data_type())? {
+                ConcreteDataType::Struct(t) => t,
+                _ => unreachable!(),
+            };
+            let items = cast_array!(array, StructArray)
+                .values()
+                .iter()
+                .map(|field| arrow_array_get(&**field, idx))
+                .collect::<Result<Vec<Value>>>()?;
+            Value::Struct(StructValue::new(items, datatype))
+        }
+        ArrowDataType::Dictionary(..) => {
+            let array = cast_array!(array, DictionaryArray::<i32>);
+            let key = array.keys().value(idx) as usize;
+            arrow_array_get(&**array.values(), key)?
+        }
         _ => unimplemented!("Arrow array datatype: {:?}", array.data_type()),
     };
 
@@ -222,4 +273,48 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_arrow_array_access_new_types() {
+        use crate::vectors::{DecimalVector, IntervalVector, JsonVector};
+
+        let vector = DecimalVector::from_values(10, 2, vec![Some(12345), None]);
+        let array = vector.to_boxed_arrow_array();
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            arrow_array_get(&*array, 0).unwrap()
+        );
+        assert_eq!(Value::Null, arrow_array_get(&*array, 1).unwrap());
+
+        let vector = IntervalVector::from(vec![Some(Interval::new(1, 2, 3))]);
+        let array = vector.to_boxed_arrow_array();
+        assert_eq!(
+            Value::Interval(Interval::new(1, 2, 3)),
+            arrow_array_get(&*array, 0).unwrap()
+        );
+
+        let vector = JsonVector::from(vec![Some("[1]")]);
+        let array = vector.to_boxed_arrow_array();
+        assert_eq!(
+            Value::Json("[1]".into()),
+            arrow_array_get(&*array, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_arrow_array_access_dictionary() {
+        use crate::vectors::DictionaryVector;
+
+        let vector = DictionaryVector::from(vec![Some("b"), None, Some("a")]);
+        let array = vector.to_dictionary_array();
+        assert_eq!(
+            Value::String("b".into()),
+            arrow_array_get(&array, 0).unwrap()
+        );
+        assert_eq!(Value::Null, arrow_array_get(&array, 1).unwrap());
+        assert_eq!(
+            Value::String("a".into()),
+            arrow_array_get(&array, 2).unwrap()
+        );
+    }
 }
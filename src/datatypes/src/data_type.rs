@@ -14,7 +14,7 @@
 
 use std::sync::Arc;
 
-use arrow::datatypes::DataType as ArrowDataType;
+use arrow::datatypes::{DataType as ArrowDataType, IntervalUnit};
 use common_time::timestamp::TimeUnit;
 use paste::paste;
 use serde::{Deserialize, Serialize};
@@ -22,9 +22,10 @@ use serde::{Deserialize, Serialize};
 use crate::error::{self, Error, Result};
 use crate::type_id::LogicalTypeId;
 use crate::types::{
-    BinaryType, BooleanType, DateTimeType, DateType, Float32Type, Float64Type, Int16Type,
-    Int32Type, Int64Type, Int8Type, ListType, NullType, StringType, TimestampType, UInt16Type,
-    UInt32Type, UInt64Type, UInt8Type,
+    BinaryType, BooleanType, DateTimeType, DateType, DecimalType, Float32Type, Float64Type,
+    Int16Type, Int32Type, Int64Type, Int8Type, IntervalType, JsonType, ListType, MapType, NullType,
+    StringType, StructField, StructType, TimestampType, UInt16Type, UInt32Type, UInt64Type,
+    UInt8Type, JSON_EXTENSION_NAME, MAP_EXTENSION_NAME,
 };
 use crate::value::Value;
 use crate::vectors::MutableVector;
@@ -46,16 +47,21 @@ pub enum ConcreteDataType {
     UInt64(UInt64Type),
     Float32(Float32Type),
     Float64(Float64Type),
+    Decimal128(DecimalType),
 
     // String types
     Binary(BinaryType),
     String(StringType),
+    Json(JsonType),
 
     Date(DateType),
     DateTime(DateTimeType),
     Timestamp(TimestampType),
+    Interval(IntervalType),
 
     List(ListType),
+    Struct(StructType),
+    Map(MapType),
 }
 
 impl ConcreteDataType {
@@ -103,6 +109,10 @@ impl ConcreteDataType {
         )
     }
 
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, ConcreteDataType::Decimal128(_))
+    }
+
     pub fn is_timestamp(&self) -> bool {
         matches!(
             self,
@@ -163,6 +173,45 @@ impl TryFrom<&ArrowDataType> for ConcreteDataType {
             ArrowDataType::List(field) => Self::List(ListType::new(
                 ConcreteDataType::from_arrow_type(&field.data_type),
             )),
+            ArrowDataType::Decimal(precision, scale) => {
+                Self::decimal128_datatype(*precision as u8, *scale as u8)
+            }
+            ArrowDataType::Interval(IntervalUnit::MonthDayNano) => Self::interval_datatype(),
+            ArrowDataType::Extension(name, inner, _)
+                if name == JSON_EXTENSION_NAME
+                    && matches!(**inner, ArrowDataType::Utf8 | ArrowDataType::LargeUtf8) =>
+            {
+                Self::json_datatype()
+            }
+            ArrowDataType::Extension(name, inner, _) if name == MAP_EXTENSION_NAME => {
+                match MapType::try_from_list_type(inner) {
+                    Some(map_type) => Self::Map(map_type?),
+                    None => {
+                        return error::UnsupportedArrowTypeSnafu {
+                            arrow_type: dt.clone(),
+                        }
+                        .fail()
+                    }
+                }
+            }
+            // Dictionary encoded strings are still strings.
+            ArrowDataType::Dictionary(_, value_type, ..)
+                if matches!(**value_type, ArrowDataType::Utf8 | ArrowDataType::LargeUtf8) =>
+            {
+                Self::string_datatype()
+            }
+            ArrowDataType::Struct(fields) => Self::Struct(StructType::new(
+                fields
+                    .iter()
+                    .map(|field| {
+                        Ok(StructField::new(
+                            &field.name,
+                            ConcreteDataType::try_from(&field.data_type)?,
+                            field.is_nullable,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+            )),
             _ => {
                 return error::UnsupportedArrowTypeSnafu {
                     arrow_type: dt.clone(),
@@ -191,7 +240,7 @@ macro_rules! impl_new_concrete_type_functions {
 
 impl_new_concrete_type_functions!(
     Null, Boolean, UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32, Float64,
-    Binary, String, Date, DateTime
+    Binary, String, Date, DateTime, Interval, Json
 );
 
 impl ConcreteDataType {
@@ -199,6 +248,25 @@ impl ConcreteDataType {
         ConcreteDataType::List(ListType::new(inner_type))
     }
 
+    pub fn decimal128_datatype(precision: u8, scale: u8) -> ConcreteDataType {
+        ConcreteDataType::Decimal128(DecimalType::new(precision, scale))
+    }
+
+    pub fn decimal128_default_datatype() -> ConcreteDataType {
+        ConcreteDataType::Decimal128(DecimalType::default())
+    }
+
+    pub fn struct_datatype(fields: Vec<StructField>) -> ConcreteDataType {
+        ConcreteDataType::Struct(StructType::new(fields))
+    }
+
+    pub fn map_datatype(
+        key_type: ConcreteDataType,
+        value_type: ConcreteDataType,
+    ) -> ConcreteDataType {
+        ConcreteDataType::Map(MapType::new(key_type, value_type))
+    }
+
     pub fn timestamp_datatype(unit: TimeUnit) -> Self {
         ConcreteDataType::Timestamp(TimestampType::new(unit))
     }
@@ -248,7 +316,7 @@ pub type DataTypeRef = Arc<dyn DataType>;
 
 #[cfg(test)]
 mod tests {
-    use arrow::datatypes::Field;
+    use arrow::datatypes::{Field, IntegerType};
 
     use super::*;
 
@@ -342,6 +410,55 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_new_types_arrow_roundtrip() {
+        let types = vec![
+            ConcreteDataType::decimal128_datatype(10, 2),
+            ConcreteDataType::interval_datatype(),
+            ConcreteDataType::json_datatype(),
+            ConcreteDataType::struct_datatype(vec![
+                StructField::new("a", ConcreteDataType::int64_datatype(), true),
+                StructField::new("b", ConcreteDataType::decimal128_datatype(5, 1), false),
+            ]),
+            ConcreteDataType::map_datatype(
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::json_datatype(),
+            ),
+        ];
+        for data_type in types {
+            assert_eq!(
+                data_type,
+                ConcreteDataType::try_from(&data_type.as_arrow_type()).unwrap()
+            );
+        }
+
+        // Maps are lists of key-value structs.
+        assert!(ConcreteDataType::try_from(&ArrowDataType::Extension(
+            MAP_EXTENSION_NAME.to_string(),
+            Box::new(ArrowDataType::Utf8),
+            None
+        ))
+        .is_err());
+
+        // Other extension types are still unsupported.
+        assert!(ConcreteDataType::try_from(&ArrowDataType::Extension(
+            "other".to_string(),
+            Box::new(ArrowDataType::Utf8),
+            None
+        ))
+        .is_err());
+
+        assert_eq!(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::try_from(&ArrowDataType::Dictionary(
+                IntegerType::Int32,
+                Box::new(ArrowDataType::Utf8),
+                false
+            ))
+            .unwrap()
+        );
+    }
+
     #[test]
     fn test_from_arrow_timestamp() {
         assert_eq!(
@@ -0,0 +1,285 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::error::{Error, InvalidDecimalSnafu, Result};
+
+/// Max precision of a 128 bits decimal.
+pub const DECIMAL128_MAX_PRECISION: u8 = 38;
+/// Default precision used when the precision is not specified, like `DECIMAL` in SQL.
+pub const DECIMAL128_DEFAULT_PRECISION: u8 = DECIMAL128_MAX_PRECISION;
+/// Default scale used when the scale is not specified, like `DECIMAL(10)` in SQL.
+pub const DECIMAL128_DEFAULT_SCALE: u8 = 0;
+
+/// A fixed point decimal number, `value` is the unscaled integer, so the actual number is
+/// `value * 10^(-scale)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Decimal128 {
+    value: i128,
+    precision: u8,
+    scale: u8,
+}
+
+impl Decimal128 {
+    /// Creates a new decimal without checking whether `value` fits in `precision`.
+    pub fn new(value: i128, precision: u8, scale: u8) -> Self {
+        Self {
+            value,
+            precision,
+            scale,
+        }
+    }
+
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Parses `s` and rescales it to a decimal of given `precision` and `scale`, extra
+    /// fraction digits are truncated.
+    pub fn from_str_with(s: &str, precision: u8, scale: u8) -> Result<Self> {
+        check_precision_scale(s, precision, scale)?;
+
+        let parsed = Decimal128::from_str(s)?;
+        let value = rescale(parsed.value, parsed.scale, scale).ok_or_else(|| {
+            InvalidDecimalSnafu {
+                raw: s,
+                reason: "value overflows".to_string(),
+            }
+            .build()
+        })?;
+        ensure!(
+            num_digits(value) <= precision as u32,
+            InvalidDecimalSnafu {
+                raw: s,
+                reason: format!("value out of range for DECIMAL({}, {})", precision, scale),
+            }
+        );
+
+        Ok(Self::new(value, precision, scale))
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+pub(crate) fn check_precision_scale(raw: &str, precision: u8, scale: u8) -> Result<()> {
+    ensure!(
+        precision > 0 && precision <= DECIMAL128_MAX_PRECISION && scale <= precision,
+        InvalidDecimalSnafu {
+            raw,
+            reason: format!("invalid precision {} and scale {}", precision, scale),
+        }
+    );
+    Ok(())
+}
+
+/// Rescales unscaled `value` from `from` scale to `to` scale, returns `None` on overflow.
+fn rescale(value: i128, from: u8, to: u8) -> Option<i128> {
+    match from.cmp(&to) {
+        Ordering::Equal => Some(value),
+        Ordering::Less => 10i128
+            .checked_pow((to - from) as u32)
+            .and_then(|factor| value.checked_mul(factor)),
+        Ordering::Greater => 10i128
+            .checked_pow((from - to) as u32)
+            .map(|factor| value / factor),
+    }
+}
+
+fn num_digits(value: i128) -> u32 {
+    let mut value = value.unsigned_abs();
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+impl Display for Decimal128 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.value.unsigned_abs().to_string();
+        let sign = if self.value < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl FromStr for Decimal128 {
+    type Err = Error;
+
+    /// Parses a decimal literal like `-123.45`, the precision and scale of the result
+    /// are derived from the digits of the literal.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            InvalidDecimalSnafu {
+                raw: s,
+                reason: reason.to_string(),
+            }
+            .build()
+        };
+
+        let trimmed = s.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid("not a decimal number"));
+        }
+
+        let integer = integer.trim_start_matches('0');
+        let precision = (integer.len() + fraction.len()).max(1);
+        if precision > DECIMAL128_MAX_PRECISION as usize {
+            return Err(invalid("too many digits"));
+        }
+        let mut value = format!("{}{}", integer, fraction)
+            .parse::<i128>()
+            .unwrap_or(0);
+        if negative {
+            value = -value;
+        }
+
+        Ok(Self::new(value, precision as u8, fraction.len() as u8))
+    }
+}
+
+impl PartialOrd for Decimal128 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal128 {
+    /// Compares the numeric values first, decimals with the same value but different
+    /// scale or precision are then ordered by scale and precision.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        let ordering = match (
+            rescale(self.value, self.scale, scale),
+            rescale(other.value, other.scale, scale),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            _ => self
+                .to_f64()
+                .partial_cmp(&other.to_f64())
+                .unwrap_or(Ordering::Equal),
+        };
+
+        ordering
+            .then_with(|| self.scale.cmp(&other.scale))
+            .then_with(|| self.precision.cmp(&other.precision))
+    }
+}
+
+impl From<Decimal128> for serde_json::Value {
+    fn from(d: Decimal128) -> Self {
+        serde_json::Value::String(d.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_display() {
+        assert_eq!("123.45", Decimal128::new(12345, 5, 2).to_string());
+        assert_eq!("-0.05", Decimal128::new(-5, 3, 2).to_string());
+        assert_eq!("0.000", Decimal128::new(0, 3, 3).to_string());
+        assert_eq!("42", Decimal128::new(42, 10, 0).to_string());
+    }
+
+    #[test]
+    fn test_decimal_parse() {
+        assert_eq!(
+            Decimal128::new(12345, 5, 2),
+            Decimal128::from_str("123.45").unwrap()
+        );
+        assert_eq!(
+            Decimal128::new(-5, 2, 2),
+            Decimal128::from_str("-.05").unwrap()
+        );
+        assert_eq!(
+            Decimal128::new(7, 1, 0),
+            Decimal128::from_str("+007").unwrap()
+        );
+
+        assert!(Decimal128::from_str("").is_err());
+        assert!(Decimal128::from_str(".").is_err());
+        assert!(Decimal128::from_str("1.2.3").is_err());
+        assert!(Decimal128::from_str("1e5").is_err());
+        assert!(Decimal128::from_str(&"9".repeat(39)).is_err());
+    }
+
+    #[test]
+    fn test_decimal_parse_with_precision_scale() {
+        assert_eq!(
+            Decimal128::new(12340, 10, 3),
+            Decimal128::from_str_with("12.34", 10, 3).unwrap()
+        );
+        // Extra fraction digits are truncated.
+        assert_eq!(
+            Decimal128::new(1234, 10, 2),
+            Decimal128::from_str_with("12.345", 10, 2).unwrap()
+        );
+
+        assert!(Decimal128::from_str_with("1234.5", 5, 2).is_err());
+        assert!(Decimal128::from_str_with("1", 0, 0).is_err());
+        assert!(Decimal128::from_str_with("1", 39, 0).is_err());
+        assert!(Decimal128::from_str_with("1", 5, 6).is_err());
+    }
+
+    #[test]
+    fn test_decimal_ord() {
+        let one = Decimal128::new(1, 1, 0);
+        let one_and_half = Decimal128::new(150, 3, 2);
+        let two = Decimal128::new(20, 2, 1);
+        assert!(one < one_and_half);
+        assert!(one_and_half < two);
+        assert!(Decimal128::new(-1, 1, 0) < one);
+
+        // Same value with different scale is ordered by scale.
+        let one_with_scale = Decimal128::new(10, 2, 1);
+        assert_ne!(one, one_with_scale);
+        assert!(one < one_with_scale);
+    }
+}
@@ -99,6 +99,13 @@ pub enum Error {
 
     #[snafu(display("Duplicated metadata for {}", key))]
     DuplicateMeta { key: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid decimal {}, reason: {}", raw, reason))]
+    InvalidDecimal {
+        raw: String,
+        reason: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl ErrorExt for Error {
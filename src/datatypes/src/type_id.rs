@@ -29,10 +29,12 @@ pub enum LogicalTypeId {
     UInt64,
     Float32,
     Float64,
+    Decimal128,
 
     // String types:
     String,
     Binary,
+    Json,
 
     // Date & Time types:
     /// Date representing the elapsed time since UNIX epoch (1970-01-01)
@@ -43,8 +45,12 @@ pub enum LogicalTypeId {
     DateTime,
 
     Timestamp,
+    /// Interval representing a span of time in months, days and nanoseconds.
+    Interval,
 
     List,
+    Struct,
+    Map,
 }
 
 impl LogicalTypeId {
@@ -78,6 +84,14 @@ impl LogicalTypeId {
             LogicalTypeId::List => {
                 ConcreteDataType::list_datatype(ConcreteDataType::null_datatype())
             }
+            LogicalTypeId::Decimal128 => ConcreteDataType::decimal128_default_datatype(),
+            LogicalTypeId::Json => ConcreteDataType::json_datatype(),
+            LogicalTypeId::Interval => ConcreteDataType::interval_datatype(),
+            LogicalTypeId::Struct => ConcreteDataType::struct_datatype(vec![]),
+            LogicalTypeId::Map => ConcreteDataType::map_datatype(
+                ConcreteDataType::null_datatype(),
+                ConcreteDataType::null_datatype(),
+            ),
         }
     }
 }
@@ -16,18 +16,27 @@ mod binary_type;
 mod boolean_type;
 mod date;
 mod datetime;
+mod decimal_type;
+mod interval_type;
+mod json_type;
 mod list_type;
+mod map_type;
 mod null_type;
 mod primitive_traits;
 mod primitive_type;
 mod string_type;
+mod struct_type;
 mod timestamp;
 
 pub use binary_type::BinaryType;
 pub use boolean_type::BooleanType;
 pub use date::DateType;
 pub use datetime::DateTimeType;
+pub use decimal_type::DecimalType;
+pub use interval_type::IntervalType;
+pub use json_type::{JsonType, JSON_EXTENSION_NAME};
 pub use list_type::ListType;
+pub use map_type::{MapType, MAP_EXTENSION_NAME};
 pub use null_type::NullType;
 pub use primitive_traits::{OrdPrimitive, Primitive};
 pub use primitive_type::{
@@ -35,4 +44,5 @@ pub use primitive_type::{
     PrimitiveType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
 };
 pub use string_type::StringType;
+pub use struct_type::{StructField, StructType};
 pub use timestamp::TimestampType;
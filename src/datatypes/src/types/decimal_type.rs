@@ -0,0 +1,91 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use arrow::datatypes::DataType as ArrowDataType;
+use serde::{Deserialize, Serialize};
+
+use crate::data_type::DataType;
+use crate::decimal::{Decimal128, DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE};
+use crate::prelude::{LogicalTypeId, Value};
+use crate::vectors::{DecimalVectorBuilder, MutableVector};
+
+/// Fixed point decimal type with given precision and scale, backed by 128 bits integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecimalType {
+    precision: u8,
+    scale: u8,
+}
+
+impl Default for DecimalType {
+    fn default() -> Self {
+        DecimalType::new(DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE)
+    }
+}
+
+impl DecimalType {
+    pub fn new(precision: u8, scale: u8) -> Self {
+        DecimalType { precision, scale }
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+}
+
+impl DataType for DecimalType {
+    fn name(&self) -> &str {
+        "Decimal128"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Decimal128
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Decimal128(Decimal128::new(0, self.precision, self.scale))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Decimal(self.precision as usize, self.scale as usize)
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(DecimalVectorBuilder::with_type_capacity(
+            self.precision,
+            self.scale,
+            capacity,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_type() {
+        let t = DecimalType::new(10, 2);
+        assert_eq!("Decimal128", t.name());
+        assert_eq!(LogicalTypeId::Decimal128, t.logical_type_id());
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(0, 10, 2)),
+            t.default_value()
+        );
+        assert_eq!(ArrowDataType::Decimal(10, 2), t.as_arrow_type());
+        assert_eq!(DecimalType::new(38, 0), DecimalType::default());
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType as ArrowDataType, IntervalUnit};
+use serde::{Deserialize, Serialize};
+
+use crate::data_type::{DataType, DataTypeRef};
+use crate::prelude::{LogicalTypeId, Value};
+use crate::vectors::{IntervalVectorBuilder, MutableVector};
+
+/// [IntervalType] represents a span of time in months, days and nanoseconds.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntervalType;
+
+impl DataType for IntervalType {
+    fn name(&self) -> &str {
+        "Interval"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Interval
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Interval(Default::default())
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Interval(IntervalUnit::MonthDayNano)
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(IntervalVectorBuilder::with_capacity(capacity))
+    }
+}
+
+impl IntervalType {
+    pub fn arc() -> DataTypeRef {
+        Arc::new(Self)
+    }
+}
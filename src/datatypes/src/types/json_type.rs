@@ -0,0 +1,68 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use arrow::datatypes::DataType as ArrowDataType;
+use common_base::bytes::StringBytes;
+use serde::{Deserialize, Serialize};
+
+use crate::data_type::{DataType, DataTypeRef};
+use crate::prelude::{LogicalTypeId, Value};
+use crate::vectors::{JsonVectorBuilder, MutableVector};
+
+/// Name of the arrow extension type used to tag JSON columns.
+pub const JSON_EXTENSION_NAME: &str = "greptime.json";
+
+const JSON_TYPE_NAME: &str = "Json";
+
+/// [JsonType] stores JSON documents as text, the arrow type is an extension type over
+/// `Utf8` so JSON columns can be told apart from plain strings.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonType;
+
+impl JsonType {
+    pub fn arc() -> DataTypeRef {
+        Arc::new(Self)
+    }
+
+    pub fn name() -> &'static str {
+        JSON_TYPE_NAME
+    }
+}
+
+impl DataType for JsonType {
+    fn name(&self) -> &str {
+        JSON_TYPE_NAME
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Json
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Json(StringBytes::from("null"))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Extension(
+            JSON_EXTENSION_NAME.to_string(),
+            Box::new(ArrowDataType::Utf8),
+            None,
+        )
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(JsonVectorBuilder::with_capacity(capacity))
+    }
+}
@@ -38,6 +38,11 @@ impl ListType {
             inner: Box::new(datatype),
         }
     }
+
+    /// Returns the type of List's inner data.
+    pub fn item_type(&self) -> &ConcreteDataType {
+        &self.inner
+    }
 }
 
 impl DataType for ListType {
@@ -0,0 +1,156 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use arrow::datatypes::{DataType as ArrowDataType, Field, Field};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::prelude::*;
+use crate::value::MapValue;
+use crate::vectors::{MapVectorBuilder, MutableVector};
+
+pub const MAP_EXTENSION_NAME: &str = "greptime.map";
+
+/// Name of the struct field holding the entries of maps in arrow.
+const MAP_ENTRIES_NAME: &str = "entries";
+const MAP_KEY_NAME: &str = "key";
+const MAP_VALUE_NAME: &str = "value";
+
+/// Map from keys to values, the keys are not null.
+///
+/// Arrow2 has no map array, so maps are stored in the physical layout of arrow maps, a list of
+/// key-value structs, tagged with an extension type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapType {
+    key_type: Box<ConcreteDataType>,
+    value_type: Box<ConcreteDataType>,
+}
+
+impl MapType {
+    pub fn new(key_type: ConcreteDataType, value_type: ConcreteDataType) -> Self {
+        MapType {
+            key_type: Box::new(key_type),
+            value_type: Box::new(value_type),
+        }
+    }
+
+    pub fn key_type(&self) -> &ConcreteDataType {
+        &self.key_type
+    }
+
+    pub fn value_type(&self) -> &ConcreteDataType {
+        &self.value_type
+    }
+
+    /// Returns the arrow type of the key-value structs of maps.
+    pub(crate) fn entries_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Struct(vec![
+            Field::new(MAP_KEY_NAME, self.key_type.as_arrow_type(), false),
+            Field::new(MAP_VALUE_NAME, self.value_type.as_arrow_type(), true),
+        ])
+    }
+
+    /// Returns the map type stored in the arrow `list_type` of key-value structs, or `None` if
+    /// the list doesn't hold such structs.
+    pub(crate) fn try_from_list_type(list_type: &ArrowDataType) -> Option<Result<Self>> {
+        let entries = match list_type {
+            ArrowDataType::List(entries) => entries,
+            _ => return None,
+        };
+        match &entries.data_type {
+            ArrowDataType::Struct(fields) if fields.len() == 2 => Some(
+                ConcreteDataType::try_from(&fields[0].data_type).and_then(|key_type| {
+                    ConcreteDataType::try_from(&fields[1].data_type)
+                        .map(|value_type| MapType::new(key_type, value_type))
+                }),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl Default for MapType {
+    fn default() -> Self {
+        MapType::new(
+            ConcreteDataType::null_datatype(),
+            ConcreteDataType::null_datatype(),
+        )
+    }
+}
+
+impl DataType for MapType {
+    fn name(&self) -> &str {
+        "Map"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Map
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Map(MapValue::new(vec![], self.clone()))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        let entries = Field::new(MAP_ENTRIES_NAME, self.entries_arrow_type(), false);
+        ArrowDataType::Extension(
+            MAP_EXTENSION_NAME.to_string(),
+            Box::new(ArrowDataType::List(Box::new(entries))),
+            None,
+        )
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(MapVectorBuilder::with_type_capacity(self.clone(), capacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_type() {
+        let t = MapType::new(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::int64_datatype(),
+        );
+        assert_eq!("Map", t.name());
+        assert_eq!(LogicalTypeId::Map, t.logical_type_id());
+        assert_eq!(
+            Value::Map(MapValue::new(vec![], t.clone())),
+            t.default_value()
+        );
+
+        let arrow_type = t.as_arrow_type();
+        assert_eq!(
+            ArrowDataType::Extension(
+                MAP_EXTENSION_NAME.to_string(),
+                Box::new(ArrowDataType::List(Box::new(Field::new(
+                    "entries",
+                    ArrowDataType::Struct(vec![
+                        Field::new("key", ArrowDataType::Utf8, false),
+                        Field::new("value", ArrowDataType::Int64, true),
+                    ]),
+                    false
+                )))),
+                None
+            ),
+            arrow_type
+        );
+        assert_eq!(
+            ConcreteDataType::Map(t),
+            ConcreteDataType::try_from(&arrow_type).unwrap()
+        );
+    }
+}
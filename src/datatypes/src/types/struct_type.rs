@@ -0,0 +1,134 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use arrow::datatypes::{DataType as ArrowDataType, Field};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::value::StructValue;
+use crate::vectors::{MutableVector, StructVectorBuilder};
+
+/// A named field of [StructType].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructField {
+    name: String,
+    data_type: ConcreteDataType,
+    nullable: bool,
+}
+
+impl StructField {
+    pub fn new(name: impl Into<String>, data_type: ConcreteDataType, nullable: bool) -> Self {
+        StructField {
+            name: name.into(),
+            data_type,
+            nullable,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_type(&self) -> &ConcreteDataType {
+        &self.data_type
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    pub fn to_arrow_field(&self) -> Field {
+        Field::new(&self.name, self.data_type.as_arrow_type(), self.nullable)
+    }
+}
+
+/// Used to represent the Struct datatype, a sequence of named fields.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct StructType {
+    fields: Vec<StructField>,
+}
+
+impl StructType {
+    pub fn new(fields: Vec<StructField>) -> Self {
+        StructType { fields }
+    }
+
+    pub fn fields(&self) -> &[StructField] {
+        &self.fields
+    }
+}
+
+impl DataType for StructType {
+    fn name(&self) -> &str {
+        "Struct"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Struct
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Struct(StructValue::new(
+            self.fields
+                .iter()
+                .map(|field| field.data_type.default_value())
+                .collect(),
+            self.clone(),
+        ))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Struct(
+            self.fields
+                .iter()
+                .map(StructField::to_arrow_field)
+                .collect(),
+        )
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(StructVectorBuilder::with_type_capacity(
+            self.clone(),
+            capacity,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_type() {
+        let t = StructType::new(vec![
+            StructField::new("a", ConcreteDataType::int32_datatype(), true),
+            StructField::new("b", ConcreteDataType::string_datatype(), false),
+        ]);
+        assert_eq!("Struct", t.name());
+        assert_eq!(LogicalTypeId::Struct, t.logical_type_id());
+        assert_eq!(
+            Value::Struct(StructValue::new(
+                vec![Value::Int32(0), Value::String("".into())],
+                t.clone()
+            )),
+            t.default_value()
+        );
+        assert_eq!(
+            ArrowDataType::Struct(vec![
+                Field::new("a", ArrowDataType::Int32, true),
+                Field::new("b", ArrowDataType::Utf8, false),
+            ]),
+            t.as_arrow_type()
+        );
+    }
+}
@@ -17,15 +17,18 @@ use std::cmp::Ordering;
 use common_base::bytes::{Bytes, StringBytes};
 use common_time::date::Date;
 use common_time::datetime::DateTime;
+use common_time::interval::Interval;
 use common_time::timestamp::{TimeUnit, Timestamp};
 use datafusion_common::ScalarValue;
 pub use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
+use crate::decimal::Decimal128;
 use crate::error::{self, Result};
 use crate::prelude::*;
 use crate::type_id::LogicalTypeId;
-use crate::vectors::ListVector;
+use crate::types::{MapType, StructType};
+use crate::vectors::{ListVector, MapVector, StructVector};
 
 pub type OrderedF32 = OrderedFloat<f32>;
 pub type OrderedF64 = OrderedFloat<f64>;
@@ -49,17 +52,23 @@ pub enum Value {
     Int64(i64),
     Float32(OrderedF32),
     Float64(OrderedF64),
+    Decimal128(Decimal128),
 
     // String types:
     String(StringBytes),
     Binary(Bytes),
+    /// JSON document in text form.
+    Json(StringBytes),
 
     // Date & Time types:
     Date(Date),
     DateTime(DateTime),
     Timestamp(Timestamp),
+    Interval(Interval),
 
     List(ListValue),
+    Struct(StructValue),
+    Map(MapValue),
 }
 
 impl Value {
@@ -87,6 +96,11 @@ impl Value {
             Value::Date(_) => ConcreteDataType::date_datatype(),
             Value::DateTime(_) => ConcreteDataType::datetime_datatype(),
             Value::Timestamp(v) => ConcreteDataType::timestamp_datatype(v.unit()),
+            Value::Decimal128(v) => ConcreteDataType::decimal128_datatype(v.precision(), v.scale()),
+            Value::Json(_) => ConcreteDataType::json_datatype(),
+            Value::Interval(_) => ConcreteDataType::interval_datatype(),
+            Value::Struct(v) => ConcreteDataType::Struct(v.datatype().clone()),
+            Value::Map(v) => ConcreteDataType::Map(v.datatype().clone()),
         }
     }
 
@@ -107,6 +121,48 @@ impl Value {
         }
     }
 
+    /// Cast itself to [StructValue].
+    pub fn as_struct(&self) -> Result<Option<&StructValue>> {
+        match self {
+            Value::Null => Ok(None),
+            Value::Struct(v) => Ok(Some(v)),
+            other => error::CastTypeSnafu {
+                msg: format!("Failed to cast {:?} to struct value", other),
+            }
+            .fail(),
+        }
+    }
+
+    /// Cast itself to [MapValue].
+    pub fn as_map(&self) -> Result<Option<&MapValue>> {
+        match self {
+            Value::Null => Ok(None),
+            Value::Map(v) => Ok(Some(v)),
+            other => error::CastTypeSnafu {
+                msg: format!("Failed to cast {:?} to map value", other),
+            }
+            .fail(),
+        }
+    }
+
+    /// Converts a numeric value to `f64`, which may lose precision for large integers. Returns
+    /// `None` for nulls and other types.
+    pub fn as_f64_lossy(&self) -> Option<f64> {
+        match self {
+            Value::UInt8(v) => Some(*v as f64),
+            Value::UInt16(v) => Some(*v as f64),
+            Value::UInt32(v) => Some(*v as f64),
+            Value::UInt64(v) => Some(*v as f64),
+            Value::Int8(v) => Some(*v as f64),
+            Value::Int16(v) => Some(*v as f64),
+            Value::Int32(v) => Some(*v as f64),
+            Value::Int64(v) => Some(*v as f64),
+            Value::Float32(v) => Some(v.0 as f64),
+            Value::Float64(v) => Some(v.0),
+            _ => None,
+        }
+    }
+
     /// Cast itself to [ValueRef].
     pub fn as_value_ref(&self) -> ValueRef {
         match self {
@@ -128,6 +184,11 @@ impl Value {
             Value::DateTime(v) => ValueRef::DateTime(*v),
             Value::List(v) => ValueRef::List(ListValueRef::Ref { val: v }),
             Value::Timestamp(v) => ValueRef::Timestamp(*v),
+            Value::Decimal128(v) => ValueRef::Decimal128(*v),
+            Value::Json(v) => ValueRef::Json(v.as_utf8()),
+            Value::Interval(v) => ValueRef::Interval(*v),
+            Value::Struct(v) => ValueRef::Struct(StructValueRef::Ref { val: v }),
+            Value::Map(v) => ValueRef::Map(MapValueRef::Ref { val: v }),
         }
     }
 
@@ -152,6 +213,11 @@ impl Value {
             Value::Date(_) => LogicalTypeId::Date,
             Value::DateTime(_) => LogicalTypeId::DateTime,
             Value::Timestamp(_) => LogicalTypeId::Timestamp,
+            Value::Decimal128(_) => LogicalTypeId::Decimal128,
+            Value::Json(_) => LogicalTypeId::Json,
+            Value::Interval(_) => LogicalTypeId::Interval,
+            Value::Struct(_) => LogicalTypeId::Struct,
+            Value::Map(_) => LogicalTypeId::Map,
         }
     }
 }
@@ -182,6 +248,11 @@ macro_rules! impl_ord_for_value_like {
                 ($Type::DateTime(v1), $Type::DateTime(v2)) => v1.cmp(v2),
                 ($Type::Timestamp(v1), $Type::Timestamp(v2)) => v1.cmp(v2),
                 ($Type::List(v1), $Type::List(v2)) => v1.cmp(v2),
+                ($Type::Decimal128(v1), $Type::Decimal128(v2)) => v1.cmp(v2),
+                ($Type::Json(v1), $Type::Json(v2)) => v1.cmp(v2),
+                ($Type::Interval(v1), $Type::Interval(v2)) => v1.cmp(v2),
+                ($Type::Struct(v1), $Type::Struct(v2)) => v1.cmp(v2),
+                ($Type::Map(v1), $Type::Map(v2)) => v1.cmp(v2),
                 _ => panic!(
                     "Cannot compare different values {:?} and {:?}",
                     $left, $right
@@ -266,6 +337,18 @@ impl From<&[u8]> for Value {
     }
 }
 
+impl From<Decimal128> for Value {
+    fn from(v: Decimal128) -> Self {
+        Value::Decimal128(v)
+    }
+}
+
+impl From<Interval> for Value {
+    fn from(v: Interval) -> Self {
+        Value::Interval(v)
+    }
+}
+
 impl TryFrom<Value> for serde_json::Value {
     type Error = serde_json::Error;
 
@@ -289,6 +372,28 @@ impl TryFrom<Value> for serde_json::Value {
             Value::DateTime(v) => serde_json::Value::Number(v.val().into()),
             Value::List(v) => serde_json::to_value(v)?,
             Value::Timestamp(v) => serde_json::to_value(v.value())?,
+            Value::Decimal128(v) => v.into(),
+            Value::Json(v) => serde_json::from_str(v.as_utf8())?,
+            Value::Interval(v) => v.into(),
+            Value::Struct(v) => {
+                let mut object = serde_json::Map::with_capacity(v.items.len());
+                for (field, item) in v.datatype.fields().iter().zip(v.items) {
+                    object.insert(field.name().to_string(), item.try_into()?);
+                }
+                serde_json::Value::Object(object)
+            }
+            Value::Map(v) => {
+                let mut object = serde_json::Map::with_capacity(v.entries.len());
+                for (key, value) in v.entries {
+                    // JSON objects are keyed by strings, other keys are written in JSON.
+                    let key = match key {
+                        Value::String(s) => s.as_utf8().to_string(),
+                        key => serde_json::Value::try_from(key)?.to_string(),
+                    };
+                    object.insert(key, value.try_into()?);
+                }
+                serde_json::Value::Object(object)
+            }
         };
 
         Ok(json_value)
@@ -344,6 +449,84 @@ impl Ord for ListValue {
     }
 }
 
+/// Struct value, holds one value for each field of the struct.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructValue {
+    items: Vec<Value>,
+    datatype: StructType,
+}
+
+impl Eq for StructValue {}
+
+impl StructValue {
+    pub fn new(items: Vec<Value>, datatype: StructType) -> Self {
+        Self { items, datatype }
+    }
+
+    pub fn items(&self) -> &[Value] {
+        &self.items
+    }
+
+    pub fn datatype(&self) -> &StructType {
+        &self.datatype
+    }
+}
+
+impl PartialOrd for StructValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StructValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        assert_eq!(
+            self.datatype, other.datatype,
+            "Cannot compare different datatypes!"
+        );
+        self.items.cmp(&other.items)
+    }
+}
+
+/// Map value, holds the key-value entries of the map in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapValue {
+    entries: Vec<(Value, Value)>,
+    datatype: MapType,
+}
+
+impl Eq for MapValue {}
+
+impl MapValue {
+    pub fn new(entries: Vec<(Value, Value)>, datatype: MapType) -> Self {
+        Self { entries, datatype }
+    }
+
+    pub fn entries(&self) -> &[(Value, Value)] {
+        &self.entries
+    }
+
+    pub fn datatype(&self) -> &MapType {
+        &self.datatype
+    }
+}
+
+impl PartialOrd for MapValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MapValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        assert_eq!(
+            self.datatype, other.datatype,
+            "Cannot compare different datatypes!"
+        );
+        self.entries.cmp(&other.entries)
+    }
+}
+
 impl TryFrom<ScalarValue> for Value {
     type Error = error::Error;
 
@@ -393,6 +576,25 @@ impl TryFrom<ScalarValue> for Value {
             ScalarValue::TimestampNanosecond(t, _) => t
                 .map(|x| Value::Timestamp(Timestamp::new(x, TimeUnit::Nanosecond)))
                 .unwrap_or(Value::Null),
+            ScalarValue::Decimal128(d, precision, scale) => d
+                .map(|x| Value::Decimal128(Decimal128::new(x, precision as u8, scale as u8)))
+                .unwrap_or(Value::Null),
+            ScalarValue::Struct(values, fields) => match values {
+                Some(values) => {
+                    let items = values
+                        .into_iter()
+                        .map(ScalarValue::try_into)
+                        .collect::<Result<_>>()?;
+                    let datatype = match ConcreteDataType::try_from(
+                        &arrow::datatypes::DataType::Struct(*fields),
+                    )? {
+                        ConcreteDataType::Struct(t) => t,
+                        _ => unreachable!(),
+                    };
+                    Value::Struct(StructValue::new(items, datatype))
+                }
+                None => Value::Null,
+            },
             _ => {
                 return error::UnsupportedArrowTypeSnafu {
                     arrow_type: v.get_datatype(),
@@ -431,6 +633,12 @@ pub enum ValueRef<'a> {
     DateTime(DateTime),
     Timestamp(Timestamp),
     List(ListValueRef<'a>),
+
+    Decimal128(Decimal128),
+    Json(&'a str),
+    Interval(Interval),
+    Struct(StructValueRef<'a>),
+    Map(MapValueRef<'a>),
 }
 
 macro_rules! impl_as_for_value_ref {
@@ -489,6 +697,31 @@ impl<'a> ValueRef<'a> {
     pub fn as_list(&self) -> Result<Option<ListValueRef>> {
         impl_as_for_value_ref!(self, List)
     }
+
+    /// Cast itself to [Decimal128].
+    pub fn as_decimal128(&self) -> Result<Option<Decimal128>> {
+        impl_as_for_value_ref!(self, Decimal128)
+    }
+
+    /// Cast itself to JSON text.
+    pub fn as_json(&self) -> Result<Option<&str>> {
+        impl_as_for_value_ref!(self, Json)
+    }
+
+    /// Cast itself to [Interval].
+    pub fn as_interval(&self) -> Result<Option<Interval>> {
+        impl_as_for_value_ref!(self, Interval)
+    }
+
+    /// Cast itself to [StructValueRef].
+    pub fn as_struct(&self) -> Result<Option<StructValueRef>> {
+        impl_as_for_value_ref!(self, Struct)
+    }
+
+    /// Cast itself to [MapValueRef].
+    pub fn as_map(&self) -> Result<Option<MapValueRef>> {
+        impl_as_for_value_ref!(self, Map)
+    }
 }
 
 impl<'a> PartialOrd for ValueRef<'a> {
@@ -621,11 +854,91 @@ impl<'a> PartialOrd for ListValueRef<'a> {
     }
 }
 
+/// Reference to a [StructValue].
+#[derive(Debug, Clone, Copy)]
+pub enum StructValueRef<'a> {
+    Indexed {
+        vector: &'a StructVector,
+        idx: usize,
+    },
+    Ref {
+        val: &'a StructValue,
+    },
+}
+
+impl<'a> StructValueRef<'a> {
+    /// Convert self to [Value]. This method would clone the underlying data.
+    fn to_value(self) -> Value {
+        match self {
+            StructValueRef::Indexed { vector, idx } => vector.get(idx),
+            StructValueRef::Ref { val } => Value::Struct(val.clone()),
+        }
+    }
+}
+
+impl<'a> PartialEq for StructValueRef<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_value().eq(&other.to_value())
+    }
+}
+
+impl<'a> Eq for StructValueRef<'a> {}
+
+impl<'a> Ord for StructValueRef<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_value().cmp(&other.to_value())
+    }
+}
+
+impl<'a> PartialOrd for StructValueRef<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reference to a [MapValue].
+#[derive(Debug, Clone, Copy)]
+pub enum MapValueRef<'a> {
+    Indexed { vector: &'a MapVector, idx: usize },
+    Ref { val: &'a MapValue },
+}
+
+impl<'a> MapValueRef<'a> {
+    /// Convert self to [Value]. This method would clone the underlying data.
+    fn to_value(self) -> Value {
+        match self {
+            MapValueRef::Indexed { vector, idx } => vector.get(idx),
+            MapValueRef::Ref { val } => Value::Map(val.clone()),
+        }
+    }
+}
+
+impl<'a> PartialEq for MapValueRef<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_value().eq(&other.to_value())
+    }
+}
+
+impl<'a> Eq for MapValueRef<'a> {}
+
+impl<'a> Ord for MapValueRef<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_value().cmp(&other.to_value())
+    }
+}
+
+impl<'a> PartialOrd for MapValueRef<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use arrow::datatypes::DataType as ArrowDataType;
 
     use super::*;
+    use crate::types::StructField;
 
     #[test]
     fn test_try_from_scalar_value() {
@@ -821,11 +1134,22 @@ mod tests {
                 .unwrap()
         );
 
-        let result: Result<Value> = ScalarValue::Decimal128(Some(1), 0, 0).try_into();
-        result
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            ScalarValue::Decimal128(Some(12345), 10, 2)
+                .try_into()
+                .unwrap()
+        );
+        assert_eq!(
+            Value::Null,
+            ScalarValue::Decimal128(None, 10, 2).try_into().unwrap()
+        );
+
+        let result: Result<Value> = ScalarValue::IntervalYearMonth(Some(1)).try_into();
+        assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Unsupported arrow data type, type: Decimal(0, 0)");
+            .contains("Unsupported arrow data type"));
     }
 
     #[test]
@@ -949,6 +1273,38 @@ mod tests {
             &ConcreteDataType::timestamp_millis_datatype(),
             &Value::Timestamp(Timestamp::from_millis(1)),
         );
+        check_type_and_value(
+            &ConcreteDataType::decimal128_datatype(10, 2),
+            &Value::Decimal128(Decimal128::new(1, 10, 2)),
+        );
+        check_type_and_value(
+            &ConcreteDataType::json_datatype(),
+            &Value::Json(StringBytes::from("{}")),
+        );
+        check_type_and_value(
+            &ConcreteDataType::interval_datatype(),
+            &Value::Interval(Interval::new(1, 2, 3)),
+        );
+        let struct_type = StructType::new(vec![StructField::new(
+            "a",
+            ConcreteDataType::int32_datatype(),
+            true,
+        )]);
+        check_type_and_value(
+            &ConcreteDataType::Struct(struct_type.clone()),
+            &Value::Struct(StructValue::new(vec![Value::Int32(1)], struct_type)),
+        );
+        let map_type = MapType::new(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::int32_datatype(),
+        );
+        check_type_and_value(
+            &ConcreteDataType::Map(map_type.clone()),
+            &Value::Map(MapValue::new(
+                vec![(Value::from("a"), Value::Int32(1))],
+                map_type,
+            )),
+        );
     }
 
     #[test]
@@ -1045,6 +1401,44 @@ mod tests {
             to_json(Value::Timestamp(Timestamp::from_millis(1)))
         );
 
+        assert_eq!(
+            serde_json::Value::String("-1.50".to_string()),
+            to_json(Value::Decimal128(Decimal128::new(-150, 3, 2)))
+        );
+        assert_eq!(
+            serde_json::json!({"a": [1, "b"]}),
+            to_json(Value::Json(StringBytes::from(r#"{"a": [1, "b"]}"#)))
+        );
+        assert_eq!(
+            serde_json::Value::String("1 mon 2 days".to_string()),
+            to_json(Value::Interval(Interval::new(1, 2, 0)))
+        );
+        let struct_type = StructType::new(vec![
+            StructField::new("a", ConcreteDataType::int32_datatype(), true),
+            StructField::new("b", ConcreteDataType::string_datatype(), true),
+        ]);
+        assert_eq!(
+            serde_json::json!({"a": 1, "b": null}),
+            to_json(Value::Struct(StructValue::new(
+                vec![Value::Int32(1), Value::Null],
+                struct_type
+            )))
+        );
+        let map_type = MapType::new(
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::string_datatype(),
+        );
+        assert_eq!(
+            serde_json::json!({"1": "a", "2": null}),
+            to_json(Value::Map(MapValue::new(
+                vec![
+                    (Value::Int32(1), Value::from("a")),
+                    (Value::Int32(2), Value::Null)
+                ],
+                map_type
+            )))
+        );
+
         let json_value: serde_json::Value =
             serde_json::from_str(r#"{"items":[{"Int32":123}],"datatype":{"Int32":{}}}"#).unwrap();
         assert_eq!(
@@ -1076,6 +1470,17 @@ mod tests {
         assert!(ValueRef::Int32(10) > ValueRef::Null);
     }
 
+    #[test]
+    fn test_as_f64_lossy() {
+        assert_eq!(Some(1.0), Value::UInt8(1).as_f64_lossy());
+        assert_eq!(Some(-2.0), Value::Int64(-2).as_f64_lossy());
+        assert_eq!(Some(1.5), Value::Float32(OrderedFloat(1.5)).as_f64_lossy());
+        assert_eq!(Some(2.5), Value::Float64(OrderedFloat(2.5)).as_f64_lossy());
+        assert_eq!(None, Value::Null.as_f64_lossy());
+        assert_eq!(None, Value::Boolean(true).as_f64_lossy());
+        assert_eq!(None, Value::from("1").as_f64_lossy());
+    }
+
     #[test]
     fn test_as_value_ref() {
         macro_rules! check_as_value_ref {
@@ -1113,6 +1518,12 @@ mod tests {
 
         check_as_value_ref!(Date, Date::new(103));
         check_as_value_ref!(DateTime, DateTime::new(1034));
+        check_as_value_ref!(Decimal128, Decimal128::new(1034, 10, 2));
+        check_as_value_ref!(Interval, Interval::new(1, 0, 0));
+        assert_eq!(
+            ValueRef::Json("[]"),
+            Value::Json("[]".into()).as_value_ref()
+        );
 
         let list = ListValue {
             items: None,
@@ -1150,6 +1561,9 @@ mod tests {
         check_as_correct!(true, Boolean, as_boolean);
         check_as_correct!(Date::new(123), Date, as_date);
         check_as_correct!(DateTime::new(12), DateTime, as_datetime);
+        check_as_correct!(Decimal128::new(12, 3, 1), Decimal128, as_decimal128);
+        check_as_correct!("{}", Json, as_json);
+        check_as_correct!(Interval::new(0, 1, 0), Interval, as_interval);
         let list = ListValue {
             items: None,
             datatype: ConcreteDataType::int32_datatype(),
@@ -1163,6 +1577,10 @@ mod tests {
         assert!(wrong_value.as_date().is_err());
         assert!(wrong_value.as_datetime().is_err());
         assert!(wrong_value.as_list().is_err());
+        assert!(wrong_value.as_decimal128().is_err());
+        assert!(wrong_value.as_json().is_err());
+        assert!(wrong_value.as_interval().is_err());
+        assert!(wrong_value.as_struct().is_err());
     }
 
     #[test]
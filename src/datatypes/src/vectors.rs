@@ -18,22 +18,29 @@ mod builder;
 pub mod constant;
 pub mod date;
 pub mod datetime;
+mod decimal;
+mod dictionary;
 mod eq;
 mod helper;
+mod interval;
+mod json;
 mod list;
+mod map;
 pub mod mutable;
 pub mod null;
 mod operations;
 pub mod primitive;
 mod string;
+mod struct_vector;
 mod timestamp;
 
 pub mod all {
     //! All vector types.
     pub use crate::vectors::{
-        BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, Float32Vector,
-        Float64Vector, Int16Vector, Int32Vector, Int64Vector, Int8Vector, ListVector, NullVector,
-        PrimitiveVector, StringVector, TimestampVector, UInt16Vector, UInt32Vector, UInt64Vector,
+        BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, DecimalVector,
+        DictionaryVector, Float32Vector, Float64Vector, Int16Vector, Int32Vector, Int64Vector,
+        Int8Vector, IntervalVector, JsonVector, ListVector, MapVector, NullVector, PrimitiveVector,
+        StringVector, StructVector, TimestampVector, UInt16Vector, UInt32Vector, UInt64Vector,
         UInt8Vector,
     };
 }
@@ -50,14 +57,20 @@ pub use builder::VectorBuilder;
 pub use constant::*;
 pub use date::*;
 pub use datetime::*;
+pub use decimal::*;
+pub use dictionary::*;
 pub use helper::Helper;
+pub use interval::*;
+pub use json::*;
 pub use list::*;
+pub use map::*;
 pub use mutable::MutableVector;
 pub use null::*;
 pub use operations::VectorOp;
 pub use primitive::*;
 use snafu::ensure;
 pub use string::*;
+pub use struct_vector::*;
 pub use timestamp::*;
 
 use crate::data_type::ConcreteDataType;
@@ -26,10 +26,11 @@ use crate::value::Value;
 use crate::vectors::date::DateVectorBuilder;
 use crate::vectors::datetime::DateTimeVectorBuilder;
 use crate::vectors::{
-    BinaryVectorBuilder, BooleanVectorBuilder, Float32VectorBuilder, Float64VectorBuilder,
-    Int16VectorBuilder, Int32VectorBuilder, Int64VectorBuilder, Int8VectorBuilder, MutableVector,
-    NullVector, StringVectorBuilder, TimestampVectorBuilder, UInt16VectorBuilder,
-    UInt32VectorBuilder, UInt64VectorBuilder, UInt8VectorBuilder, VectorRef,
+    BinaryVectorBuilder, BooleanVectorBuilder, DecimalVectorBuilder, Float32VectorBuilder,
+    Float64VectorBuilder, Int16VectorBuilder, Int32VectorBuilder, Int64VectorBuilder,
+    Int8VectorBuilder, IntervalVectorBuilder, JsonVectorBuilder, MapVectorBuilder, MutableVector,
+    NullVector, StringVectorBuilder, StructVectorBuilder, TimestampVectorBuilder,
+    UInt16VectorBuilder, UInt32VectorBuilder, UInt64VectorBuilder, UInt8VectorBuilder, VectorRef,
 };
 
 pub enum VectorBuilder {
@@ -47,14 +48,20 @@ pub enum VectorBuilder {
     Int64(Int64VectorBuilder),
     Float32(Float32VectorBuilder),
     Float64(Float64VectorBuilder),
+    Decimal128(DecimalVectorBuilder),
 
     // String types:
     String(StringVectorBuilder),
     Binary(BinaryVectorBuilder),
+    Json(JsonVectorBuilder),
 
     Date(DateVectorBuilder),
     DateTime(DateTimeVectorBuilder),
     Timestamp(TimestampVectorBuilder),
+    Interval(IntervalVectorBuilder),
+
+    Struct(StructVectorBuilder),
+    Map(MapVectorBuilder),
 }
 
 impl VectorBuilder {
@@ -113,6 +120,21 @@ impl VectorBuilder {
             ConcreteDataType::Timestamp(_) => {
                 VectorBuilder::Timestamp(TimestampVectorBuilder::with_capacity(capacity))
             }
+            ConcreteDataType::Decimal128(t) => VectorBuilder::Decimal128(
+                DecimalVectorBuilder::with_type_capacity(t.precision(), t.scale(), capacity),
+            ),
+            ConcreteDataType::Json(_) => {
+                VectorBuilder::Json(JsonVectorBuilder::with_capacity(capacity))
+            }
+            ConcreteDataType::Interval(_) => {
+                VectorBuilder::Interval(IntervalVectorBuilder::with_capacity(capacity))
+            }
+            ConcreteDataType::Struct(t) => {
+                VectorBuilder::Struct(StructVectorBuilder::with_type_capacity(t, capacity))
+            }
+            ConcreteDataType::Map(t) => {
+                VectorBuilder::Map(MapVectorBuilder::with_type_capacity(t, capacity))
+            }
             _ => unimplemented!(),
         }
     }
@@ -136,6 +158,11 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.data_type(),
             VectorBuilder::DateTime(b) => b.data_type(),
             VectorBuilder::Timestamp(b) => b.data_type(),
+            VectorBuilder::Decimal128(b) => b.data_type(),
+            VectorBuilder::Json(b) => b.data_type(),
+            VectorBuilder::Interval(b) => b.data_type(),
+            VectorBuilder::Struct(b) => b.data_type(),
+            VectorBuilder::Map(b) => b.data_type(),
         }
     }
 
@@ -167,6 +194,21 @@ impl VectorBuilder {
             (VectorBuilder::Timestamp(b), Value::Int64(v)) => {
                 b.push(Some(Timestamp::from_millis(*v)))
             }
+            (VectorBuilder::Decimal128(b), Value::Decimal128(v)) => b
+                .push(Some(*v))
+                .unwrap_or_else(|e| panic!("Failed to push decimal {} to builder, err: {}", v, e)),
+            (VectorBuilder::Json(b), Value::Json(v)) => b.push(Some(v.as_utf8())),
+            (VectorBuilder::Interval(b), Value::Interval(v)) => b.push(Some(*v)),
+            (VectorBuilder::Struct(b), Value::Struct(_)) => {
+                b.push_value_ref(value.as_value_ref()).unwrap_or_else(|e| {
+                    panic!("Failed to push struct {:?} to builder, err: {}", value, e)
+                })
+            }
+            (VectorBuilder::Map(b), Value::Map(_)) => {
+                b.push_value_ref(value.as_value_ref()).unwrap_or_else(|e| {
+                    panic!("Failed to push map {:?} to builder, err: {}", value, e)
+                })
+            }
 
             _ => panic!(
                 "Value {:?} does not match builder type {:?}",
@@ -204,6 +246,11 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.push_value_ref(value),
             VectorBuilder::DateTime(b) => b.push_value_ref(value),
             VectorBuilder::Timestamp(b) => b.push_value_ref(value),
+            VectorBuilder::Decimal128(b) => b.push_value_ref(value),
+            VectorBuilder::Json(b) => b.push_value_ref(value),
+            VectorBuilder::Interval(b) => b.push_value_ref(value),
+            VectorBuilder::Struct(b) => b.push_value_ref(value),
+            VectorBuilder::Map(b) => b.push_value_ref(value),
         }
     }
 
@@ -226,6 +273,11 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.push(None),
             VectorBuilder::DateTime(b) => b.push(None),
             VectorBuilder::Timestamp(b) => b.push(None),
+            VectorBuilder::Decimal128(b) => b.push(None).unwrap(),
+            VectorBuilder::Json(b) => b.push(None),
+            VectorBuilder::Interval(b) => b.push(None),
+            VectorBuilder::Struct(b) => b.push_value_ref(ValueRef::Null).unwrap(),
+            VectorBuilder::Map(b) => b.push_value_ref(ValueRef::Null).unwrap(),
         }
     }
 
@@ -248,6 +300,11 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => Arc::new(b.finish()),
             VectorBuilder::DateTime(b) => Arc::new(b.finish()),
             VectorBuilder::Timestamp(b) => Arc::new(b.finish()),
+            VectorBuilder::Decimal128(b) => Arc::new(b.finish()),
+            VectorBuilder::Json(b) => Arc::new(b.finish()),
+            VectorBuilder::Interval(b) => Arc::new(b.finish()),
+            VectorBuilder::Struct(b) => Arc::new(b.finish()),
+            VectorBuilder::Map(b) => Arc::new(b.finish()),
         }
     }
 }
@@ -491,4 +548,46 @@ mod tests {
             v.to_arrow_array().data_type()
         );
     }
+
+    #[test]
+    fn test_decimal_vector_builder() {
+        use crate::decimal::Decimal128;
+
+        let data_type = ConcreteDataType::decimal128_datatype(10, 2);
+        let mut builder = VectorBuilder::with_capacity(data_type.clone(), 3);
+        assert_eq!(data_type, builder.data_type());
+        builder.push_null();
+        builder.push(&Value::Decimal128(Decimal128::new(123, 10, 2)));
+
+        let result = builder.try_push_ref(ValueRef::Boolean(true));
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Failed to cast value ref Boolean(true) to Decimal128"
+        );
+
+        builder
+            .try_push_ref(ValueRef::Decimal128(Decimal128::new(4, 1, 0)))
+            .unwrap();
+
+        let v = builder.finish();
+        assert_eq!(data_type, v.data_type());
+        assert_eq!(Value::Null, v.get(0));
+        assert_eq!(Value::Decimal128(Decimal128::new(123, 10, 2)), v.get(1));
+        assert_eq!(Value::Decimal128(Decimal128::new(400, 10, 2)), v.get(2));
+    }
+
+    #[test]
+    fn test_json_vector_builder() {
+        let data_type = ConcreteDataType::json_datatype();
+        let mut builder = VectorBuilder::new(data_type.clone());
+        assert_eq!(data_type, builder.data_type());
+        builder.push(&Value::Json(r#"{"a":1}"#.into()));
+        builder.push_null();
+
+        let v = builder.finish();
+        assert_eq!(data_type, v.data_type());
+        assert_eq!(ValueRef::Json(r#"{"a":1}"#), v.get_ref(0));
+        assert_eq!(Value::Null, v.get(1));
+    }
 }
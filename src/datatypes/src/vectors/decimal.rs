@@ -0,0 +1,342 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, MutablePrimitiveArray, PrimitiveArray};
+use arrow::datatypes::DataType as ArrowDataType;
+use serde_json::Value as JsonValue;
+use snafu::{ensure, OptionExt};
+
+use crate::data_type::ConcreteDataType;
+use crate::decimal::Decimal128;
+use crate::error::{self, Result};
+use crate::serialize::Serializable;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+
+/// Vector of [Decimal128], backed by an arrow `i128` array with decimal data type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecimalVector {
+    array: PrimitiveArray<i128>,
+    precision: u8,
+    scale: u8,
+}
+
+impl DecimalVector {
+    /// Creates a vector of given `precision` and `scale` from unscaled values.
+    pub fn from_values(precision: u8, scale: u8, values: Vec<Option<i128>>) -> Self {
+        let array = PrimitiveArray::<i128>::from(values);
+        Self {
+            array: PrimitiveArray::from_data(
+                ArrowDataType::Decimal(precision as usize, scale as usize),
+                array.values().clone(),
+                array.validity().cloned(),
+            ),
+            precision,
+            scale,
+        }
+    }
+
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        let array = array.as_ref();
+        let (precision, scale) = match array.data_type() {
+            ArrowDataType::Decimal(precision, scale) => (*precision as u8, *scale as u8),
+            other => {
+                return error::ConversionSnafu {
+                    from: format!("{:?}", other),
+                }
+                .fail()
+            }
+        };
+        let array = array
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i128>>()
+            .with_context(|| error::ConversionSnafu {
+                from: format!("{:?}", array.data_type()),
+            })?
+            .clone();
+
+        Ok(Self {
+            array,
+            precision,
+            scale,
+        })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn get_decimal(&self, index: usize) -> Option<Decimal128> {
+        if self.array.is_valid(index) {
+            Some(Decimal128::new(
+                self.array.value(index),
+                self.precision,
+                self.scale,
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter_data(&self) -> impl Iterator<Item = Option<Decimal128>> + '_ {
+        (0..self.len()).map(|i| self.get_decimal(i))
+    }
+}
+
+impl Vector for DecimalVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::decimal128_datatype(self.precision, self.scale)
+    }
+
+    fn vector_type_name(&self) -> String {
+        "DecimalVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.values().len() * std::mem::size_of::<i128>()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self {
+            array: self.array.slice(offset, length),
+            precision: self.precision,
+            scale: self.scale,
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        self.get_decimal(index)
+            .map(Value::Decimal128)
+            .unwrap_or(Value::Null)
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        self.get_decimal(index)
+            .map(ValueRef::Decimal128)
+            .unwrap_or(ValueRef::Null)
+    }
+}
+
+impl Serializable for DecimalVector {
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        Ok(self
+            .iter_data()
+            .map(|v| match v {
+                None => JsonValue::Null,
+                Some(v) => v.into(),
+            })
+            .collect())
+    }
+}
+
+pub struct DecimalVectorBuilder {
+    buffer: MutablePrimitiveArray<i128>,
+    precision: u8,
+    scale: u8,
+}
+
+impl DecimalVectorBuilder {
+    pub fn with_type_capacity(precision: u8, scale: u8, capacity: usize) -> Self {
+        Self {
+            buffer: MutablePrimitiveArray::with_capacity(capacity),
+            precision,
+            scale,
+        }
+    }
+
+    /// Pushes a decimal into the builder, the decimal is rescaled to the precision
+    /// and scale of this builder if they are different.
+    pub fn push(&mut self, value: Option<Decimal128>) -> Result<()> {
+        match value {
+            Some(v) if v.precision() == self.precision && v.scale() == self.scale => {
+                self.buffer.push(Some(v.value()))
+            }
+            Some(v) => {
+                let v = Decimal128::from_str_with(&v.to_string(), self.precision, self.scale)?;
+                self.buffer.push(Some(v.value()))
+            }
+            None => self.buffer.push(None),
+        }
+        Ok(())
+    }
+
+    pub fn finish(&mut self) -> DecimalVector {
+        let array: PrimitiveArray<i128> = std::mem::take(&mut self.buffer).into();
+        DecimalVector {
+            array: PrimitiveArray::from_data(
+                ArrowDataType::Decimal(self.precision as usize, self.scale as usize),
+                array.values().clone(),
+                array.validity().cloned(),
+            ),
+            precision: self.precision,
+            scale: self.scale,
+        }
+    }
+}
+
+impl MutableVector for DecimalVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::decimal128_datatype(self.precision, self.scale)
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        self.push(value.as_decimal128()?)
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        let concrete_vector = vector
+            .as_any()
+            .downcast_ref::<DecimalVector>()
+            .with_context(|| error::CastTypeSnafu {
+                msg: format!(
+                    "Failed to cast vector from {} to DecimalVector",
+                    vector.vector_type_name()
+                ),
+            })?;
+        ensure!(
+            concrete_vector.precision == self.precision && concrete_vector.scale == self.scale,
+            error::CastTypeSnafu {
+                msg: format!(
+                    "Failed to extend DECIMAL({}, {}) by DECIMAL({}, {})",
+                    self.precision, self.scale, concrete_vector.precision, concrete_vector.scale
+                ),
+            }
+        );
+        let slice = concrete_vector.array.slice(offset, length);
+        self.buffer
+            .extend_trusted_len(slice.iter().map(|v| v.copied()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::types::DecimalType;
+
+    #[test]
+    fn test_decimal_vector_misc() {
+        let v = DecimalVector::from_values(10, 2, vec![Some(12345), None, Some(-1)]);
+        assert_eq!(3, v.len());
+        assert_eq!("DecimalVector", v.vector_type_name());
+        assert_eq!(ConcreteDataType::decimal128_datatype(10, 2), v.data_type());
+        assert!(!v.is_null(0));
+        assert!(v.is_null(1));
+        assert_eq!(48, v.memory_size());
+
+        assert_eq!(Value::Decimal128(Decimal128::new(12345, 10, 2)), v.get(0));
+        assert_eq!(Value::Null, v.get(1));
+        assert_eq!(
+            ValueRef::Decimal128(Decimal128::new(-1, 10, 2)),
+            v.get_ref(2)
+        );
+
+        let arrow_arr = v.to_arrow_array();
+        assert_eq!(&ArrowDataType::Decimal(10, 2), arrow_arr.data_type());
+        assert_eq!(v, DecimalVector::try_from_arrow_array(arrow_arr).unwrap());
+
+        let sliced = v.slice(1, 2);
+        assert_eq!(2, sliced.len());
+        assert_eq!(Value::Null, sliced.get(0));
+
+        assert_eq!(
+            r#"["123.45",null,"-0.01"]"#,
+            serde_json::to_string(&v.serialize_to_json().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decimal_vector_builder() {
+        let mut builder = DecimalType::new(10, 2).create_mutable_vector(3);
+        builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(100, 10, 2)))
+            .unwrap();
+        // Rescaled to the scale of the builder.
+        builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(15, 3, 1)))
+            .unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+        assert!(builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(1, 38, 0)))
+            .is_ok());
+        assert!(builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(i128::MAX, 38, 0)))
+            .is_err());
+
+        let input = DecimalVector::from_values(10, 2, vec![Some(1), Some(2), Some(3)]);
+        builder.extend_slice_of(&input, 1, 2).unwrap();
+        let other = DecimalVector::from_values(5, 2, vec![Some(1)]);
+        assert!(builder.extend_slice_of(&other, 0, 1).is_err());
+
+        let vector = builder.to_vector();
+        let expect: VectorRef = Arc::new(DecimalVector::from_values(
+            10,
+            2,
+            vec![Some(100), Some(150), None, Some(100), Some(2), Some(3)],
+        ));
+        assert_eq!(expect, vector);
+    }
+}
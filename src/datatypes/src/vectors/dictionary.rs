@@ -0,0 +1,679 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, DictionaryArray, PrimitiveArray};
+use serde_json::Value as JsonValue;
+use snafu::{OptionExt, ResultExt};
+
+use crate::arrow_array::StringArray;
+use crate::data_type::ConcreteDataType;
+use crate::error::{self, Result, SerializeSnafu};
+use crate::scalars::ScalarVector;
+use crate::serialize::Serializable;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{
+    self, BooleanVector, MutableVector, StringVector, Validity, Vector, VectorRef,
+};
+
+/// Dictionary encoded vector of strings, each row stores a key to the dictionary instead of
+/// its own copy of the string.
+///
+/// The dictionary of a `DictionaryVector` is always sorted and contains no duplicate or null
+/// values, so comparing the keys of two rows is the same as comparing their strings, as long as
+/// the rows come from vectors sharing the same dictionary (see [DictionaryVector::shares_dictionary]
+/// and [DictionaryVector::share_dictionary]).
+///
+/// The logical type of the vector is still string, so it could replace a [StringVector]
+/// anywhere.
+#[derive(Debug, Clone)]
+pub struct DictionaryVector {
+    array: DictionaryArray<i32>,
+}
+
+impl DictionaryVector {
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        let array = array
+            .as_ref()
+            .as_any()
+            .downcast_ref::<DictionaryArray<i32>>()
+            .with_context(|| error::ConversionSnafu {
+                from: format!("{:?}", array.as_ref().data_type()),
+            })?;
+        let dictionary = array
+            .values()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .with_context(|| error::ConversionSnafu {
+                from: format!("{:?}", array.data_type()),
+            })?;
+
+        let is_normalized = dictionary.validity().is_none()
+            && dictionary
+                .values_iter()
+                .zip(dictionary.values_iter().skip(1))
+                .all(|(prev, next)| prev < next);
+        if is_normalized {
+            return Ok(Self {
+                array: array.clone(),
+            });
+        }
+
+        // Encodes the strings again so the dictionary is sorted and deduplicated.
+        let mut builder = DictionaryVectorBuilder::with_capacity(array.len());
+        for key in array.keys().iter() {
+            builder.push(key.and_then(|key| {
+                let key = *key as usize;
+                dictionary.is_valid(key).then(|| dictionary.value(key))
+            }));
+        }
+        Ok(builder.finish())
+    }
+
+    /// Returns the keys of the rows.
+    pub fn keys(&self) -> &PrimitiveArray<i32> {
+        self.array.keys()
+    }
+
+    /// Returns the sorted, distinct strings referenced by the keys.
+    pub fn dictionary(&self) -> &StringArray {
+        // Safety: The dictionary of the vector is always a string array, which is checked
+        // while creating the vector.
+        self.array
+            .values()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+    }
+
+    /// Returns the key of the `idx-th` row, `None` if the row is null.
+    pub fn key(&self, idx: usize) -> Option<i32> {
+        let keys = self.keys();
+        if keys.is_valid(idx) {
+            Some(keys.value(idx))
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `self` and `other` share the same dictionary, then their rows could be
+    /// compared by keys.
+    pub fn shares_dictionary(&self, other: &DictionaryVector) -> bool {
+        is_same_array(self.array.values(), other.array.values())
+    }
+
+    /// Re-encodes `self` with the `shared` dictionary, so the returned vector shares the
+    /// dictionary with other vectors re-encoded with `shared`.
+    ///
+    /// Strings of `self` missing from `shared` are added to it, which replaces `shared` with
+    /// a new dictionary, vectors re-encoded before no longer share the dictionary with the
+    /// vectors re-encoded after.
+    pub fn share_dictionary(&self, shared: &mut ArrayRef) -> DictionaryVector {
+        if let Some(vector) = self.try_encode_with(shared) {
+            return vector;
+        }
+
+        let merged = match shared.as_any().downcast_ref::<StringArray>() {
+            Some(dictionary) => merge_dictionaries(dictionary, self.dictionary()),
+            None => self.dictionary().clone(),
+        };
+        *shared = Arc::new(merged);
+        // Safety: The merged dictionary contains all strings of `self`.
+        self.try_encode_with(shared).unwrap()
+    }
+
+    /// Re-encodes `self` with `dictionary`, returns `None` if `dictionary` doesn't contain all
+    /// strings of `self`.
+    fn try_encode_with(&self, dictionary: &ArrayRef) -> Option<DictionaryVector> {
+        if is_same_array(self.array.values(), dictionary) {
+            return Some(self.clone());
+        }
+        let target = dictionary.as_any().downcast_ref::<StringArray>()?;
+        let remap = self
+            .dictionary()
+            .values_iter()
+            .map(|value| find_key(target, value))
+            .collect::<Option<Vec<_>>>()?;
+        let keys: Vec<_> = self
+            .keys()
+            .iter()
+            .map(|key| key.map(|key| remap[*key as usize]))
+            .collect();
+
+        Some(Self {
+            array: DictionaryArray::from_data(PrimitiveArray::from(keys), dictionary.clone()),
+        })
+    }
+
+    /// Returns the string of the `idx-th` row.
+    pub fn get_data(&self, idx: usize) -> Option<&str> {
+        self.key(idx)
+            .map(|key| self.dictionary().value(key as usize))
+    }
+
+    pub fn iter_data(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        let dictionary = self.dictionary();
+        self.keys()
+            .iter()
+            .map(move |key| key.map(|key| dictionary.value(*key as usize)))
+    }
+
+    /// Converts the vector into an arrow [DictionaryArray].
+    pub fn to_dictionary_array(&self) -> DictionaryArray<i32> {
+        self.array.clone()
+    }
+
+    /// Decodes the vector into a plain [StringVector].
+    pub fn decode(&self) -> StringVector {
+        StringVector::from(self.iter_data().collect::<StringArray>())
+    }
+
+    /// Creates a new vector with `keys` that refers to the dictionary of `self`.
+    pub(crate) fn with_keys(&self, keys: PrimitiveArray<i32>) -> Self {
+        Self {
+            array: DictionaryArray::from_data(keys, self.array.values().clone()),
+        }
+    }
+}
+
+/// Returns true if `left` and `right` point to the same array.
+fn is_same_array(left: &ArrayRef, right: &ArrayRef) -> bool {
+    // Only compares the data pointers, the vtable pointers of the same array may differ.
+    std::ptr::eq(
+        Arc::as_ptr(left) as *const u8,
+        Arc::as_ptr(right) as *const u8,
+    )
+}
+
+/// Returns the key of `value` in the sorted `dictionary`.
+fn find_key(dictionary: &StringArray, value: &str) -> Option<i32> {
+    let (mut low, mut high) = (0, dictionary.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match dictionary.value(mid).cmp(value) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Some(mid as i32),
+        }
+    }
+    None
+}
+
+/// Merges two sorted and distinct dictionaries into a sorted and distinct one.
+fn merge_dictionaries(left: &StringArray, right: &StringArray) -> StringArray {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        let (l, r) = (left.value(i), right.value(j));
+        match l.cmp(r) {
+            Ordering::Less => {
+                merged.push(Some(l));
+                i += 1;
+            }
+            Ordering::Greater => {
+                merged.push(Some(r));
+                j += 1;
+            }
+            Ordering::Equal => {
+                merged.push(Some(l));
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    merged.extend((i..left.len()).map(|i| Some(left.value(i))));
+    merged.extend((j..right.len()).map(|j| Some(right.value(j))));
+    merged.into_iter().collect()
+}
+
+impl PartialEq for DictionaryVector {
+    fn eq(&self, other: &DictionaryVector) -> bool {
+        self.len() == other.len() && self.iter_data().eq(other.iter_data())
+    }
+}
+
+impl From<&StringVector> for DictionaryVector {
+    fn from(vector: &StringVector) -> Self {
+        let mut builder = DictionaryVectorBuilder::with_capacity(vector.len());
+        for value in vector.iter_data() {
+            builder.push(value);
+        }
+        builder.finish()
+    }
+}
+
+impl From<Vec<Option<&str>>> for DictionaryVector {
+    fn from(data: Vec<Option<&str>>) -> Self {
+        let mut builder = DictionaryVectorBuilder::with_capacity(data.len());
+        for value in data {
+            builder.push(value);
+        }
+        builder.finish()
+    }
+}
+
+impl Vector for DictionaryVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::string_datatype()
+    }
+
+    fn vector_type_name(&self) -> String {
+        "DictionaryVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    /// Returns the decoded strings, which matches the arrow type of the string data type. Use
+    /// [DictionaryVector::to_dictionary_array] to get the encoded array.
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.iter_data().collect::<StringArray>())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.iter_data().collect::<StringArray>())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.keys())
+    }
+
+    fn memory_size(&self) -> usize {
+        let dictionary = self.dictionary();
+        self.len() * std::mem::size_of::<i32>()
+            + dictionary.len() * std::mem::size_of::<i32>()
+            + dictionary.values().len()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.keys().is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self {
+            array: self.array.slice(offset, length),
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        match self.get_data(index) {
+            Some(v) => Value::String(v.into()),
+            None => Value::Null,
+        }
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        match self.get_data(index) {
+            Some(v) => ValueRef::String(v),
+            None => ValueRef::Null,
+        }
+    }
+}
+
+impl Serializable for DictionaryVector {
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        self.iter_data()
+            .map(|v| match v {
+                None => Ok(JsonValue::Null),
+                Some(s) => serde_json::to_value(s),
+            })
+            .collect::<serde_json::Result<_>>()
+            .context(SerializeSnafu)
+    }
+}
+
+pub(crate) fn replicate_dictionary(vector: &DictionaryVector, offsets: &[usize]) -> VectorRef {
+    assert_eq!(offsets.len(), vector.len());
+
+    let mut keys = Vec::with_capacity(offsets.last().copied().unwrap_or(0));
+    let mut previous_offset = 0;
+    for (i, offset) in offsets.iter().enumerate() {
+        let key = vector.key(i);
+        keys.extend((previous_offset..*offset).map(|_| key));
+        previous_offset = *offset;
+    }
+    Arc::new(vector.with_keys(PrimitiveArray::from(keys)))
+}
+
+pub(crate) fn filter_dictionary(
+    vector: &DictionaryVector,
+    filter: &BooleanVector,
+) -> Result<VectorRef> {
+    let filtered = arrow::compute::filter::filter(vector.keys(), filter.as_boolean_array())
+        .context(error::ArrowComputeSnafu)?;
+    // Safety: Filtering a primitive array returns an array of the same type.
+    let keys = filtered
+        .as_any()
+        .downcast_ref::<PrimitiveArray<i32>>()
+        .unwrap()
+        .clone();
+    Ok(Arc::new(vector.with_keys(keys)))
+}
+
+/// Builder of [DictionaryVector], the dictionary is sorted while finishing the vector.
+pub struct DictionaryVectorBuilder {
+    keys: Vec<Option<i32>>,
+    /// Distinct strings in insertion order, shared with `lookup`.
+    values: Vec<Arc<str>>,
+    /// Maps a string to its index in `values`.
+    lookup: HashMap<Arc<str>, i32>,
+}
+
+impl DictionaryVectorBuilder {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: Vec::with_capacity(capacity),
+            values: Vec::new(),
+            lookup: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<&str>) {
+        let key = value.map(|value| self.key_of(value));
+        self.keys.push(key);
+    }
+
+    pub fn finish(&mut self) -> DictionaryVector {
+        let mut order: Vec<usize> = (0..self.values.len()).collect();
+        order.sort_unstable_by(|a, b| self.values[*a].cmp(&self.values[*b]));
+        let mut remap = vec![0; self.values.len()];
+        for (new_key, old_key) in order.iter().enumerate() {
+            remap[*old_key] = new_key as i32;
+        }
+
+        let dictionary: StringArray = order.iter().map(|key| Some(&*self.values[*key])).collect();
+        let keys: Vec<_> = self
+            .keys
+            .iter()
+            .map(|key| key.map(|key| remap[key as usize]))
+            .collect();
+
+        self.keys.clear();
+        self.values.clear();
+        self.lookup.clear();
+
+        DictionaryVector {
+            array: DictionaryArray::from_data(PrimitiveArray::from(keys), Arc::new(dictionary)),
+        }
+    }
+
+    fn key_of(&mut self, value: &str) -> i32 {
+        if let Some(key) = self.lookup.get(value) {
+            return *key;
+        }
+        let key = self.values.len() as i32;
+        let value: Arc<str> = Arc::from(value);
+        self.values.push(value.clone());
+        self.lookup.insert(value, key);
+        key
+    }
+}
+
+impl MutableVector for DictionaryVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::string_datatype()
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        self.push(value.as_string()?);
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        if let Some(vector) = vector.as_any().downcast_ref::<DictionaryVector>() {
+            let dictionary = vector.dictionary();
+            if length < dictionary.len() {
+                // The dictionary may be shared by many vectors, so it could be much larger
+                // than the slice.
+                for i in offset..offset + length {
+                    self.push(vector.get_data(i));
+                }
+                return Ok(());
+            }
+
+            // Looks up each key of the source dictionary at most once.
+            let mut key_map: Vec<Option<i32>> = vec![None; dictionary.len()];
+            for i in offset..offset + length {
+                let key = vector.key(i).map(|key| {
+                    let key = key as usize;
+                    match key_map[key] {
+                        Some(new_key) => new_key,
+                        None => {
+                            let new_key = self.key_of(dictionary.value(key));
+                            key_map[key] = Some(new_key);
+                            new_key
+                        }
+                    }
+                });
+                self.keys.push(key);
+            }
+            return Ok(());
+        }
+
+        let vector = vector
+            .as_any()
+            .downcast_ref::<StringVector>()
+            .with_context(|| error::CastTypeSnafu {
+                msg: format!(
+                    "Failed to cast vector from {} to DictionaryVector",
+                    vector.vector_type_name()
+                ),
+            })?;
+        for i in offset..offset + length {
+            self.push(vector.get_data(i));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::bitmap::MutableBitmap;
+    use arrow::datatypes::DataType as ArrowDataType;
+
+    use super::*;
+    use crate::vectors::{Helper, VectorOp};
+
+    #[test]
+    fn test_dictionary_vector_misc() {
+        let v = DictionaryVector::from(vec![Some("host2"), Some("host1"), None, Some("host2")]);
+        assert_eq!(4, v.len());
+        assert_eq!("DictionaryVector", v.vector_type_name());
+        assert_eq!(ConcreteDataType::string_datatype(), v.data_type());
+        assert_eq!(1, v.null_count());
+        assert!(v.is_null(2));
+
+        // The dictionary is sorted and deduplicated.
+        assert_eq!(2, v.dictionary().len());
+        assert_eq!("host1", v.dictionary().value(0));
+        assert_eq!(Some(1), v.key(0));
+        assert_eq!(Some(0), v.key(1));
+        assert_eq!(None, v.key(2));
+        assert_eq!(Some(1), v.key(3));
+
+        assert_eq!(Value::String("host2".into()), v.get(0));
+        assert_eq!(ValueRef::String("host1"), v.get_ref(1));
+        assert_eq!(Value::Null, v.get(2));
+
+        let expect: VectorRef = Arc::new(StringVector::from(vec![
+            Some("host2"),
+            Some("host1"),
+            None,
+            Some("host2"),
+        ]));
+        let vector: VectorRef = Arc::new(v.clone());
+        assert_eq!(expect, vector);
+        assert_eq!(&ArrowDataType::Utf8, v.to_arrow_array().data_type());
+        assert_eq!(
+            r#"["host2","host1",null,"host2"]"#,
+            serde_json::to_string(&v.serialize_to_json().unwrap()).unwrap()
+        );
+
+        let sliced = v.slice(1, 2);
+        let sliced = sliced.as_any().downcast_ref::<DictionaryVector>().unwrap();
+        assert!(sliced.shares_dictionary(&v));
+        assert_eq!(Some("host1"), sliced.get_data(0));
+    }
+
+    #[test]
+    fn test_dictionary_array_conversion() {
+        let v = DictionaryVector::from(vec![Some("b"), Some("a"), Some("b")]);
+        let array: ArrayRef = Arc::new(v.to_dictionary_array());
+        assert!(matches!(array.data_type(), ArrowDataType::Dictionary(..)));
+
+        let converted = Helper::try_into_vector(array).unwrap();
+        let converted = converted
+            .as_any()
+            .downcast_ref::<DictionaryVector>()
+            .unwrap();
+        assert!(converted.shares_dictionary(&v));
+
+        // An unsorted dictionary is encoded again.
+        let dictionary: StringArray = vec![Some("b"), Some("a"), Some("b")].into_iter().collect();
+        let array = DictionaryArray::<i32>::from_data(
+            PrimitiveArray::from(vec![Some(0), Some(1), None, Some(2)]),
+            Arc::new(dictionary),
+        );
+        let converted =
+            DictionaryVector::try_from_arrow_array(Arc::new(array) as ArrayRef).unwrap();
+        assert_eq!(2, converted.dictionary().len());
+        assert_eq!(
+            vec![Some("b"), Some("a"), None, Some("b")],
+            converted.iter_data().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_dictionary_vector_ops() {
+        let v = DictionaryVector::from(vec![Some("a"), Some("a"), Some("b"), None]);
+
+        let replicated = v.replicate(&[1, 3, 3, 4]);
+        let expect: VectorRef = Arc::new(StringVector::from(vec![
+            Some("a"),
+            Some("a"),
+            Some("a"),
+            None,
+        ]));
+        assert_eq!(expect, replicated);
+
+        let filtered = v
+            .filter(&BooleanVector::from(vec![true, false, true, true]))
+            .unwrap();
+        let expect: VectorRef = Arc::new(StringVector::from(vec![Some("a"), Some("b"), None]));
+        assert_eq!(expect, filtered);
+
+        let prev = v.slice(0, 1);
+        let mut selected = MutableBitmap::from_len_zeroed(v.len());
+        v.find_unique(&mut selected, Some(&*prev));
+        assert_eq!(
+            vec![false, false, true, true],
+            selected.iter().collect::<Vec<_>>()
+        );
+
+        // Compares strings if the previous vector isn't encoded.
+        let prev = StringVector::from(vec!["b"]);
+        let mut selected = MutableBitmap::from_len_zeroed(v.len());
+        v.find_unique(&mut selected, Some(&prev));
+        assert_eq!(
+            vec![true, false, true, true],
+            selected.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_share_dictionary() {
+        let v1 = DictionaryVector::from(vec![Some("c"), None, Some("a")]);
+        let v2 = DictionaryVector::from(vec![Some("b"), Some("d"), Some("a")]);
+        let v3 = DictionaryVector::from(vec![Some("d"), Some("c")]);
+        assert!(!v1.shares_dictionary(&v2));
+
+        let mut shared: ArrayRef = Arc::new(v1.dictionary().clone());
+        let s1 = v1.share_dictionary(&mut shared);
+        assert_eq!(v1, s1);
+        // Strings of v2 are added to the shared dictionary.
+        let s2 = v2.share_dictionary(&mut shared);
+        assert_eq!(v2, s2);
+        assert!(!s1.shares_dictionary(&s2));
+        assert_eq!(4, s2.dictionary().len());
+        // The dictionary contains all strings of v3, so it is shared.
+        let s3 = v3.share_dictionary(&mut shared);
+        assert_eq!(v3, s3);
+        assert!(s2.shares_dictionary(&s3));
+
+        // Comparing the keys is the same as comparing the strings.
+        for i in 0..s2.len() {
+            for j in 0..s3.len() {
+                assert_eq!(
+                    s2.get_data(i).cmp(&s3.get_data(j)),
+                    s2.key(i).cmp(&s3.key(j))
+                );
+            }
+        }
+
+        // Re-encoding a vector sharing the dictionary keeps the vector.
+        let sliced = s3.slice(1, 1);
+        let sliced = sliced.as_any().downcast_ref::<DictionaryVector>().unwrap();
+        assert!(sliced.share_dictionary(&mut shared).shares_dictionary(&s3));
+    }
+
+    #[test]
+    fn test_dictionary_vector_builder() {
+        let mut builder = DictionaryVectorBuilder::with_capacity(4);
+        builder.push_value_ref(ValueRef::String("b")).unwrap();
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+
+        let encoded = DictionaryVector::from(vec![Some("c"), Some("a"), None]);
+        builder.extend_slice_of(&encoded, 0, 3).unwrap();
+        // Slice of a vector with a large dictionary.
+        let large = DictionaryVector::from(vec![Some("x"), Some("y"), Some("c")]);
+        builder.extend_slice_of(&large, 2, 1).unwrap();
+        let plain = StringVector::from(vec!["a", "d"]);
+        builder.extend_slice_of(&plain, 1, 1).unwrap();
+        assert!(builder
+            .extend_slice_of(&crate::vectors::Int32Vector::from_slice(&[1]), 0, 1)
+            .is_err());
+
+        let vector = builder.finish();
+        assert_eq!(4, vector.dictionary().len());
+        assert_eq!(
+            vec![Some("b"), Some("c"), Some("a"), None, Some("c"), Some("d")],
+            vector.iter_data().collect::<Vec<_>>()
+        );
+        assert_eq!(0, builder.len());
+    }
+}
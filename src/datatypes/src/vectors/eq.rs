@@ -16,8 +16,9 @@ use std::sync::Arc;
 
 use crate::data_type::DataType;
 use crate::vectors::{
-    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, ListVector,
-    PrimitiveVector, StringVector, TimestampVector, Vector,
+    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, DecimalVector,
+    IntervalVector, JsonVector, ListVector, MapVector, PrimitiveVector, StringVector, StructVector,
+    TimestampVector, Vector,
 };
 use crate::with_match_primitive_type_id;
 
@@ -73,11 +74,23 @@ fn equal(lhs: &dyn Vector, rhs: &dyn Vector) -> bool {
         Null(_) => true,
         Boolean(_) => is_vector_eq!(BooleanVector, lhs, rhs),
         Binary(_) => is_vector_eq!(BinaryVector, lhs, rhs),
-        String(_) => is_vector_eq!(StringVector, lhs, rhs),
+        String(_) => match (
+            lhs.as_any().downcast_ref::<StringVector>(),
+            rhs.as_any().downcast_ref::<StringVector>(),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs == rhs,
+            // Either side is dictionary encoded.
+            _ => (0..lhs.len()).all(|i| lhs.get_ref(i) == rhs.get_ref(i)),
+        },
         Date(_) => is_vector_eq!(DateVector, lhs, rhs),
         DateTime(_) => is_vector_eq!(DateTimeVector, lhs, rhs),
         Timestamp(_) => is_vector_eq!(TimestampVector, lhs, rhs),
         List(_) => is_vector_eq!(ListVector, lhs, rhs),
+        Decimal128(_) => is_vector_eq!(DecimalVector, lhs, rhs),
+        Json(_) => is_vector_eq!(JsonVector, lhs, rhs),
+        Interval(_) => is_vector_eq!(IntervalVector, lhs, rhs),
+        Struct(_) => is_vector_eq!(StructVector, lhs, rhs),
+        Map(_) => is_vector_eq!(MapVector, lhs, rhs),
         UInt8(_) | UInt16(_) | UInt32(_) | UInt64(_) | Int8(_) | Int16(_) | Int32(_) | Int64(_)
         | Float32(_) | Float64(_) => {
             with_match_primitive_type_id!(lhs_type.logical_type_id(), |$T| {
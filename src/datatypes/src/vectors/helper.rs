@@ -19,13 +19,14 @@ use std::sync::Arc;
 
 use arrow::array::Array;
 use arrow::compute;
-use arrow::datatypes::DataType as ArrowDataType;
+use arrow::datatypes::{DataType as ArrowDataType, IntervalUnit};
 use datafusion_common::ScalarValue;
 use snafu::{OptionExt, ResultExt};
 
 use crate::arrow_array::StringArray;
 use crate::error::{ConversionSnafu, Result, UnknownVectorSnafu};
 use crate::scalars::*;
+use crate::types::{JSON_EXTENSION_NAME, MAP_EXTENSION_NAME};
 use crate::vectors::date::DateVector;
 use crate::vectors::datetime::DateTimeVector;
 use crate::vectors::*;
@@ -161,6 +162,14 @@ impl Helper {
             ScalarValue::Date64(v) => {
                 ConstantVector::new(Arc::new(DateTimeVector::from(vec![v])), length)
             }
+            ScalarValue::Decimal128(v, precision, scale) => ConstantVector::new(
+                Arc::new(DecimalVector::from_values(
+                    precision as u8,
+                    scale as u8,
+                    vec![v],
+                )),
+                length,
+            ),
             _ => {
                 return ConversionSnafu {
                     from: format!("Unsupported scalar value: {}", value),
@@ -202,6 +211,20 @@ impl Helper {
             ArrowDataType::Timestamp(_, _) => {
                 Arc::new(TimestampVector::try_from_arrow_array(array)?)
             }
+            ArrowDataType::Decimal(_, _) => Arc::new(DecimalVector::try_from_arrow_array(array)?),
+            ArrowDataType::Interval(IntervalUnit::MonthDayNano) => {
+                Arc::new(IntervalVector::try_from_arrow_array(array)?)
+            }
+            ArrowDataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME => {
+                Arc::new(JsonVector::try_from_arrow_array(array)?)
+            }
+            ArrowDataType::Extension(name, _, _) if name == MAP_EXTENSION_NAME => {
+                Arc::new(MapVector::try_from_arrow_array(array)?)
+            }
+            ArrowDataType::Struct(_) => Arc::new(StructVector::try_from_arrow_array(array)?),
+            ArrowDataType::Dictionary(..) => {
+                Arc::new(DictionaryVector::try_from_arrow_array(array)?)
+            }
             _ => unimplemented!("Arrow array datatype: {:?}", array.as_ref().data_type()),
         })
     }
@@ -277,6 +300,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_into_new_type_vectors() {
+        let vectors: Vec<VectorRef> = vec![
+            Arc::new(DecimalVector::from_values(10, 2, vec![Some(1), None])),
+            Arc::new(IntervalVector::from(vec![
+                Some(common_time::Interval::new(1, 2, 3)),
+                None,
+            ])),
+            Arc::new(JsonVector::from(vec![Some("{}"), None])),
+        ];
+        for vector in vectors {
+            let converted = Helper::try_into_vector(vector.to_arrow_array()).unwrap();
+            assert_eq!(vector, converted);
+        }
+    }
+
+    #[test]
+    pub fn test_try_from_scalar_decimal_value() {
+        let vector =
+            Helper::try_from_scalar_value(ScalarValue::Decimal128(Some(42), 10, 2), 3).unwrap();
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            vector.data_type()
+        );
+        assert_eq!(3, vector.len());
+        for i in 0..vector.len() {
+            assert_eq!(
+                Value::Decimal128(crate::decimal::Decimal128::new(42, 10, 2)),
+                vector.get(i)
+            );
+        }
+    }
+
     #[test]
     fn test_like_utf8() {
         fn assert_vector(expected: Vec<&str>, actual: &VectorRef) {
@@ -0,0 +1,268 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, MutablePrimitiveArray, PrimitiveArray};
+use arrow::types::months_days_ns;
+use common_time::interval::Interval;
+use serde_json::Value as JsonValue;
+use snafu::OptionExt;
+
+use crate::data_type::ConcreteDataType;
+use crate::error::{self, Result};
+use crate::serialize::Serializable;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+
+type IntervalArray = PrimitiveArray<months_days_ns>;
+
+/// Vector of [Interval], backed by arrow's month-day-nano interval array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalVector {
+    array: IntervalArray,
+}
+
+fn to_interval(v: months_days_ns) -> Interval {
+    Interval::new(v.months(), v.days(), v.ns())
+}
+
+fn from_interval(v: Interval) -> months_days_ns {
+    months_days_ns::new(v.months(), v.days(), v.nanos())
+}
+
+impl IntervalVector {
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn get_interval(&self, index: usize) -> Option<Interval> {
+        if self.array.is_valid(index) {
+            Some(to_interval(self.array.value(index)))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter_data(&self) -> impl Iterator<Item = Option<Interval>> + '_ {
+        (0..self.len()).map(|i| self.get_interval(i))
+    }
+}
+
+impl From<IntervalArray> for IntervalVector {
+    fn from(array: IntervalArray) -> Self {
+        Self { array }
+    }
+}
+
+impl From<Vec<Option<Interval>>> for IntervalVector {
+    fn from(data: Vec<Option<Interval>>) -> Self {
+        Self {
+            array: data
+                .into_iter()
+                .map(|v| v.map(from_interval))
+                .collect::<IntervalArray>(),
+        }
+    }
+}
+
+impl Vector for IntervalVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::interval_datatype()
+    }
+
+    fn vector_type_name(&self) -> String {
+        "IntervalVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.values().len() * std::mem::size_of::<months_days_ns>()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self::from(self.array.slice(offset, length)))
+    }
+
+    fn get(&self, index: usize) -> Value {
+        self.get_interval(index)
+            .map(Value::Interval)
+            .unwrap_or(Value::Null)
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        self.get_interval(index)
+            .map(ValueRef::Interval)
+            .unwrap_or(ValueRef::Null)
+    }
+}
+
+impl Serializable for IntervalVector {
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        Ok(self
+            .iter_data()
+            .map(|v| match v {
+                None => JsonValue::Null,
+                Some(v) => v.into(),
+            })
+            .collect())
+    }
+}
+
+vectors::impl_try_from_arrow_array_for_vector!(IntervalArray, IntervalVector);
+
+pub struct IntervalVectorBuilder {
+    buffer: MutablePrimitiveArray<months_days_ns>,
+}
+
+impl IntervalVectorBuilder {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: MutablePrimitiveArray::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<Interval>) {
+        self.buffer.push(value.map(from_interval))
+    }
+
+    pub fn finish(&mut self) -> IntervalVector {
+        IntervalVector {
+            array: std::mem::take(&mut self.buffer).into(),
+        }
+    }
+}
+
+impl MutableVector for IntervalVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::interval_datatype()
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        self.push(value.as_interval()?);
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        let concrete_vector = vector
+            .as_any()
+            .downcast_ref::<IntervalVector>()
+            .with_context(|| error::CastTypeSnafu {
+                msg: format!(
+                    "Failed to cast vector from {} to IntervalVector",
+                    vector.vector_type_name()
+                ),
+            })?;
+        let slice = concrete_vector.array.slice(offset, length);
+        self.buffer
+            .extend_trusted_len(slice.iter().map(|v| v.copied()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::{DataType as ArrowDataType, IntervalUnit};
+
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::types::IntervalType;
+
+    #[test]
+    fn test_interval_vector_misc() {
+        let v = IntervalVector::from(vec![
+            Some(Interval::new(1, 2, 3)),
+            None,
+            Some(Interval::new(0, 0, 1_000_000_000)),
+        ]);
+        assert_eq!(3, v.len());
+        assert_eq!("IntervalVector", v.vector_type_name());
+        assert_eq!(ConcreteDataType::interval_datatype(), v.data_type());
+        assert!(v.is_null(1));
+        assert_eq!(Value::Interval(Interval::new(1, 2, 3)), v.get(0));
+        assert_eq!(ValueRef::Null, v.get_ref(1));
+
+        let arrow_arr = v.to_arrow_array();
+        assert_eq!(
+            &ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+            arrow_arr.data_type()
+        );
+        assert_eq!(v, IntervalVector::try_from_arrow_array(arrow_arr).unwrap());
+
+        assert_eq!(
+            r#"["1 mon 2 days 00:00:00.000000003",null,"00:00:01"]"#,
+            serde_json::to_string(&v.serialize_to_json().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_interval_vector_builder() {
+        let mut builder = IntervalType::default().create_mutable_vector(3);
+        builder
+            .push_value_ref(ValueRef::Interval(Interval::new(1, 0, 0)))
+            .unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        assert!(builder.push_value_ref(ValueRef::Int64(1)).is_err());
+
+        let input = IntervalVector::from(vec![Some(Interval::new(0, 1, 0)), None]);
+        builder.extend_slice_of(&input, 0, 2).unwrap();
+
+        let expect: VectorRef = Arc::new(IntervalVector::from(vec![
+            Some(Interval::new(1, 0, 0)),
+            None,
+            Some(Interval::new(0, 1, 0)),
+            None,
+        ]));
+        assert_eq!(expect, builder.to_vector());
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, MutableArray};
+use serde_json::Value as JsonValue;
+use snafu::{OptionExt, ResultExt};
+
+use crate::arrow_array::{MutableStringArray, StringArray};
+use crate::data_type::{ConcreteDataType, DataType};
+use crate::error::{self, DeserializeSnafu, Result};
+use crate::serialize::Serializable;
+use crate::types::JsonType;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+
+/// Vector of JSON documents in text form, the underlying arrow array is an utf8 array
+/// tagged with the JSON extension type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonVector {
+    array: StringArray,
+}
+
+impl JsonVector {
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        let array = array
+            .as_ref()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .with_context(|| error::ConversionSnafu {
+                from: format!("{:?}", array.as_ref().data_type()),
+            })?;
+        Ok(Self::from(array.clone()))
+    }
+
+    pub fn iter_data(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.array.iter()
+    }
+}
+
+impl From<StringArray> for JsonVector {
+    /// Tags the string `array` with the JSON extension type, the documents are not validated.
+    fn from(array: StringArray) -> Self {
+        Self {
+            array: StringArray::from_data(
+                JsonType::default().as_arrow_type(),
+                array.offsets().clone(),
+                array.values().clone(),
+                array.validity().cloned(),
+            ),
+        }
+    }
+}
+
+impl From<Vec<Option<&str>>> for JsonVector {
+    fn from(data: Vec<Option<&str>>) -> Self {
+        Self::from(StringArray::from(data))
+    }
+}
+
+impl Vector for JsonVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::json_datatype()
+    }
+
+    fn vector_type_name(&self) -> String {
+        "JsonVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.len() * std::mem::size_of::<i64>() + self.array.values().len()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self {
+            array: self.array.slice(offset, length),
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        if self.array.is_valid(index) {
+            Value::Json(self.array.value(index).into())
+        } else {
+            Value::Null
+        }
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        if self.array.is_valid(index) {
+            ValueRef::Json(self.array.value(index))
+        } else {
+            ValueRef::Null
+        }
+    }
+}
+
+impl Serializable for JsonVector {
+    /// Serializes the documents as JSON values instead of strings.
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        self.iter_data()
+            .map(|v| match v {
+                None => Ok(JsonValue::Null),
+                Some(s) => serde_json::from_str(s).context(DeserializeSnafu { json: s }),
+            })
+            .collect()
+    }
+}
+
+pub struct JsonVectorBuilder {
+    buffer: MutableStringArray,
+}
+
+impl JsonVectorBuilder {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buffer: MutableStringArray::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: Option<&str>) {
+        self.buffer.push(value)
+    }
+
+    pub fn finish(&mut self) -> JsonVector {
+        let array: StringArray = std::mem::take(&mut self.buffer).into();
+        JsonVector::from(array)
+    }
+}
+
+impl MutableVector for JsonVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::json_datatype()
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        self.buffer.push(value.as_json()?);
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        vectors::impl_extend_for_builder!(self.buffer, vector, JsonVector, offset, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_vector_misc() {
+        let v = JsonVector::from(vec![Some(r#"{"a": 1}"#), None, Some("[1, 2]")]);
+        assert_eq!(3, v.len());
+        assert_eq!("JsonVector", v.vector_type_name());
+        assert_eq!(ConcreteDataType::json_datatype(), v.data_type());
+        assert_eq!(Value::Json(r#"{"a": 1}"#.into()), v.get(0));
+        assert_eq!(ValueRef::Null, v.get_ref(1));
+        assert_eq!(ValueRef::Json("[1, 2]"), v.get_ref(2));
+
+        let arrow_arr = v.to_arrow_array();
+        assert_eq!(&JsonType::default().as_arrow_type(), arrow_arr.data_type());
+        assert_eq!(v, JsonVector::try_from_arrow_array(arrow_arr).unwrap());
+
+        assert_eq!(
+            r#"[{"a":1},null,[1,2]]"#,
+            serde_json::to_string(&v.serialize_to_json().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_vector_builder() {
+        let mut builder = JsonType::default().create_mutable_vector(3);
+        builder.push_value_ref(ValueRef::Json("1")).unwrap();
+        // Plain strings are not accepted by JSON vectors.
+        assert!(builder.push_value_ref(ValueRef::String("1")).is_err());
+
+        let input = JsonVector::from(vec![Some("true"), None]);
+        builder.extend_slice_of(&input, 0, 2).unwrap();
+
+        let expect: VectorRef = Arc::new(JsonVector::from(vec![Some("1"), Some("true"), None]));
+        assert_eq!(expect, builder.to_vector());
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, ListArray, StructArray};
+use arrow::bitmap::MutableBitmap;
+use arrow::datatypes::{DataType as ArrowDataType, Field};
+use serde_json::Value as JsonValue;
+use snafu::prelude::*;
+
+use crate::error::{self, Result};
+use crate::prelude::*;
+use crate::serialize::Serializable;
+use crate::types::MapType;
+use crate::value::{MapValue, MapValueRef};
+use crate::vectors::impl_validity_for_vector;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapVector {
+    array: ListArray<i32>,
+    /// Keys of the entries of all maps in the `array`.
+    keys: VectorRef,
+    /// Values of the entries of all maps in the `array`.
+    values: VectorRef,
+    datatype: MapType,
+}
+
+impl MapVector {
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        let conversion_error = || error::ConversionSnafu {
+            from: format!("{:?}", array.as_ref().data_type()),
+        };
+        let datatype = match ConcreteDataType::try_from(array.as_ref().data_type())? {
+            ConcreteDataType::Map(t) => t,
+            _ => return conversion_error().fail(),
+        };
+        let array = array
+            .as_ref()
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .with_context(conversion_error)?
+            .clone();
+        let entries = array
+            .values()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .with_context(conversion_error)?;
+        let (keys, values) = match entries.values() {
+            [keys, values] => (
+                VectorHelper::try_into_vector(keys)?,
+                VectorHelper::try_into_vector(values)?,
+            ),
+            _ => return conversion_error().fail(),
+        };
+
+        Ok(Self {
+            array,
+            keys,
+            values,
+            datatype,
+        })
+    }
+
+    /// Returns the keys of the entries of all maps, which may include the entries out of the
+    /// slice of this vector.
+    pub fn keys(&self) -> &VectorRef {
+        &self.keys
+    }
+
+    /// Returns the values of the entries of all maps, which may include the entries out of the
+    /// slice of this vector.
+    pub fn values(&self) -> &VectorRef {
+        &self.values
+    }
+
+    /// Returns the range of the entries of the map at `index` in [keys](Self::keys) and
+    /// [values](Self::values).
+    pub fn entries_range(&self, index: usize) -> Range<usize> {
+        let offsets = self.array.offsets();
+        offsets[index] as usize..offsets[index + 1] as usize
+    }
+
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn map_value(&self, index: usize) -> MapValue {
+        MapValue::new(
+            self.entries_range(index)
+                .map(|i| (self.keys.get(i), self.values.get(i)))
+                .collect(),
+            self.datatype.clone(),
+        )
+    }
+}
+
+impl Vector for MapVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Map(self.datatype.clone())
+    }
+
+    fn vector_type_name(&self) -> String {
+        "MapVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.offsets().len() * std::mem::size_of::<i32>()
+            + self.keys.memory_size()
+            + self.values.memory_size()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(MapVector {
+            array: self.array.slice(offset, length),
+            keys: self.keys.clone(),
+            values: self.values.clone(),
+            datatype: self.datatype.clone(),
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        if !self.array.is_valid(index) {
+            return Value::Null;
+        }
+        Value::Map(self.map_value(index))
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        if !self.array.is_valid(index) {
+            return ValueRef::Null;
+        }
+        ValueRef::Map(MapValueRef::Indexed {
+            vector: self,
+            idx: index,
+        })
+    }
+}
+
+impl Serializable for MapVector {
+    /// Serializes each map as a JSON object, the keys which are not strings are written in JSON.
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        (0..self.len())
+            .map(|i| JsonValue::try_from(self.get(i)).context(error::SerializeSnafu))
+            .collect()
+    }
+}
+
+pub struct MapVectorBuilder {
+    datatype: MapType,
+    offsets: Vec<i32>,
+    keys: Box<dyn MutableVector>,
+    values: Box<dyn MutableVector>,
+    validity: Option<MutableBitmap>,
+}
+
+impl MapVectorBuilder {
+    pub fn with_type_capacity(datatype: MapType, capacity: usize) -> MapVectorBuilder {
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        let keys = datatype.key_type().create_mutable_vector(capacity);
+        let values = datatype.value_type().create_mutable_vector(capacity);
+
+        MapVectorBuilder {
+            datatype,
+            offsets,
+            keys,
+            values,
+            validity: None,
+        }
+    }
+
+    fn push_null(&mut self) {
+        let len = self.offsets.len() - 1;
+        self.offsets.push(self.keys.len() as i32);
+        match &mut self.validity {
+            Some(validity) => validity.push(false),
+            None => {
+                let mut validity = MutableBitmap::with_capacity(self.offsets.capacity());
+                validity.extend_constant(len, true);
+                validity.push(false);
+                self.validity = Some(validity);
+            }
+        }
+    }
+
+    fn push_entries<'a>(
+        &mut self,
+        entries: impl Iterator<Item = (ValueRef<'a>, ValueRef<'a>)>,
+    ) -> Result<()> {
+        for (key, value) in entries {
+            ensure!(
+                !key.is_null(),
+                error::CastTypeSnafu {
+                    msg: "Map keys must not be null",
+                }
+            );
+            self.keys.push_value_ref(key)?;
+            self.values.push_value_ref(value)?;
+        }
+        self.offsets.push(self.keys.len() as i32);
+        if let Some(validity) = &mut self.validity {
+            validity.push(true);
+        }
+        Ok(())
+    }
+
+    pub fn finish(&mut self) -> MapVector {
+        let keys = self.keys.to_vector();
+        let values = self.values.to_vector();
+        let entries = StructArray::from_data(
+            self.datatype.entries_arrow_type(),
+            vec![keys.to_arrow_array(), values.to_arrow_array()],
+            None,
+        );
+        let offsets = std::mem::replace(&mut self.offsets, vec![0]);
+        let array = ListArray::from_data(
+            self.datatype.as_arrow_type(),
+            offsets.into(),
+            Arc::new(entries),
+            std::mem::take(&mut self.validity).map(|x| x.into()),
+        );
+
+        MapVector {
+            array,
+            keys,
+            values,
+            datatype: self.datatype.clone(),
+        }
+    }
+}
+
+impl MutableVector for MapVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Map(self.datatype.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        match value.as_map()? {
+            Some(MapValueRef::Indexed { vector, idx }) => self.push_entries(
+                vector
+                    .entries_range(idx)
+                    .map(|i| (vector.keys.get_ref(i), vector.values.get_ref(i))),
+            ),
+            Some(MapValueRef::Ref { val }) => self.push_entries(
+                val.entries()
+                    .iter()
+                    .map(|(key, value)| (key.as_value_ref(), value.as_value_ref())),
+            ),
+            None => {
+                self.push_null();
+                Ok(())
+            }
+        }
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        for idx in offset..offset + length {
+            self.push_value_ref(vector.get_ref(idx))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn new_map_type() -> MapType {
+        MapType::new(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::int32_datatype(),
+        )
+    }
+
+    fn new_map_value(entries: Vec<(&str, Option<i32>)>) -> Value {
+        Value::Map(MapValue::new(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Value::from(key), Value::from(value)))
+                .collect(),
+            new_map_type(),
+        ))
+    }
+
+    fn new_map_vector() -> VectorRef {
+        let mut builder = new_map_type().create_mutable_vector(3);
+        let value = new_map_value(vec![("a", Some(1)), ("b", None)]);
+        builder.push_value_ref(value.as_value_ref()).unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        let value = new_map_value(vec![]);
+        builder.push_value_ref(value.as_value_ref()).unwrap();
+        builder.to_vector()
+    }
+
+    #[test]
+    fn test_map_vector() {
+        let datatype = new_map_type();
+        let vector = new_map_vector();
+        assert_eq!(3, vector.len());
+        assert_eq!("MapVector", vector.vector_type_name());
+        assert_eq!(ConcreteDataType::Map(datatype.clone()), vector.data_type());
+        assert!(!vector.is_null(0));
+        assert!(vector.is_null(1));
+
+        assert_eq!(
+            new_map_value(vec![("a", Some(1)), ("b", None)]),
+            vector.get(0)
+        );
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(new_map_value(vec![]), vector.get(2));
+        assert!(matches!(vector.get_ref(0), ValueRef::Map(_)));
+
+        let sliced = vector.slice(1, 2);
+        assert_eq!(Value::Null, sliced.get(0));
+        assert_eq!(vector.get(2), sliced.get(1));
+
+        let arrow_arr = vector.to_arrow_array();
+        assert_eq!(&datatype.as_arrow_type(), arrow_arr.data_type());
+        let converted: VectorRef = Arc::new(MapVector::try_from_arrow_array(arrow_arr).unwrap());
+        assert_eq!(vector, converted);
+
+        assert_eq!(
+            vec![json!({"a": 1, "b": null}), JsonValue::Null, json!({})],
+            vector.serialize_to_json().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_map_vector_builder() {
+        let input = new_map_vector();
+        let mut builder = new_map_type().create_mutable_vector(3);
+        builder.extend_slice_of(&*input, 1, 2).unwrap();
+        builder.extend_slice_of(&*input, 0, 1).unwrap();
+        let vector = builder.to_vector();
+        assert_eq!(Value::Null, vector.get(0));
+        assert_eq!(input.get(2), vector.get(1));
+        assert_eq!(input.get(0), vector.get(2));
+
+        let mut builder = new_map_type().create_mutable_vector(1);
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+        let null_key = Value::Map(MapValue::new(
+            vec![(Value::Null, Value::Int32(1))],
+            new_map_type(),
+        ));
+        assert!(builder.push_value_ref(null_key.as_value_ref()).is_err());
+    }
+}
@@ -79,12 +79,73 @@ impl_scalar_vector_op!(
     { BinaryVector, replicate_scalar },
     { BooleanVector, replicate_scalar },
     { ListVector, replicate_scalar },
-    { StringVector, replicate_scalar },
     { DateVector, replicate_date },
     { DateTimeVector, replicate_datetime },
     { TimestampVector, replicate_timestamp }
 );
 
+// The previous vector of a `StringVector` might be a `DictionaryVector`, which has the same
+// data type but can't be downcast to `StringVector`.
+impl VectorOp for StringVector {
+    fn replicate(&self, offsets: &[usize]) -> VectorRef {
+        replicate::replicate_scalar(self, offsets)
+    }
+
+    fn find_unique(&self, selected: &mut MutableBitmap, prev_vector: Option<&dyn Vector>) {
+        match prev_vector.map(|pv| pv.as_any().downcast_ref::<StringVector>()) {
+            Some(None) => find_unique::find_unique_value_ref(self, selected, prev_vector),
+            prev_vector => find_unique::find_unique_scalar(self, selected, prev_vector.flatten()),
+        }
+    }
+
+    fn filter(&self, filter: &BooleanVector) -> Result<VectorRef> {
+        filter::filter_non_constant!(self, StringVector, filter)
+    }
+}
+
+/// Implements [VectorOp] for vectors whose builders require the data type, these operations
+/// work on [ValueRef](crate::value::ValueRef)s instead of scalars.
+macro_rules! impl_value_ref_vector_op {
+    ($($VectorType: ident),+) => {$(
+        impl VectorOp for $VectorType {
+            fn replicate(&self, offsets: &[usize]) -> VectorRef {
+                replicate::replicate_value_ref(self, offsets)
+            }
+
+            fn find_unique(&self, selected: &mut MutableBitmap, prev_vector: Option<&dyn Vector>) {
+                let prev_vector = prev_vector.filter(|pv| pv.as_any().is::<$VectorType>());
+                find_unique::find_unique_value_ref(self, selected, prev_vector);
+            }
+
+            fn filter(&self, filter: &BooleanVector) -> Result<VectorRef> {
+                filter::filter_non_constant!(self, $VectorType, filter)
+            }
+        }
+    )+};
+}
+
+impl_value_ref_vector_op!(
+    DecimalVector,
+    IntervalVector,
+    JsonVector,
+    MapVector,
+    StructVector
+);
+
+impl VectorOp for DictionaryVector {
+    fn replicate(&self, offsets: &[usize]) -> VectorRef {
+        replicate::replicate_dictionary(self, offsets)
+    }
+
+    fn find_unique(&self, selected: &mut MutableBitmap, prev_vector: Option<&dyn Vector>) {
+        find_unique::find_unique_dictionary(self, selected, prev_vector);
+    }
+
+    fn filter(&self, filter: &BooleanVector) -> Result<VectorRef> {
+        filter::filter_dictionary(self, filter)
+    }
+}
+
 impl VectorOp for ConstantVector {
     fn replicate(&self, offsets: &[usize]) -> VectorRef {
         replicate::replicate_constant(self, offsets)
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub(crate) use crate::vectors::constant::filter_constant;
+pub(crate) use crate::vectors::dictionary::filter_dictionary;
 
 macro_rules! filter_non_constant {
     ($vector: expr, $VectorType: ty, $filter: ident) => {{
@@ -101,6 +102,18 @@ mod tests {
         assert_eq!(expect, out);
     }
 
+    #[test]
+    fn test_filter_decimal() {
+        use crate::vectors::DecimalVector;
+
+        let v = DecimalVector::from_values(10, 2, vec![Some(1), None, Some(3)]);
+        let filter = BooleanVector::from_slice(&[true, true, false]);
+        let out = v.filter(&filter).unwrap();
+
+        let expect: VectorRef = Arc::new(DecimalVector::from_values(10, 2, vec![Some(1), None]));
+        assert_eq!(expect, out);
+    }
+
     macro_rules! impl_filter_date_like_test {
         ($VectorType: ident, $ValueType: ident, $method: ident) => {{
             use std::sync::Arc;
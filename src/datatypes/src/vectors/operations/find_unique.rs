@@ -15,7 +15,7 @@
 use arrow::bitmap::MutableBitmap;
 
 use crate::scalars::ScalarVector;
-use crate::vectors::{ConstantVector, NullVector, Vector};
+use crate::vectors::{ConstantVector, DictionaryVector, NullVector, Vector};
 
 // To implement `find_unique()` correctly, we need to keep in mind that always marks an element as
 // selected when it is different from the previous one, and leaves the `selected` unchanged
@@ -61,6 +61,65 @@ pub(crate) fn find_unique_scalar<'a, T: ScalarVector>(
     }
 }
 
+/// Same as [find_unique_scalar] but compares elements by [ValueRef](crate::value::ValueRef).
+pub(crate) fn find_unique_value_ref(
+    vector: &dyn Vector,
+    selected: &mut MutableBitmap,
+    prev_vector: Option<&dyn Vector>,
+) {
+    assert!(selected.len() >= vector.len());
+
+    if vector.is_empty() {
+        return;
+    }
+
+    for i in 1..vector.len() {
+        if vector.get_ref(i - 1) != vector.get_ref(i) {
+            selected.set(i, true);
+        }
+    }
+
+    let is_first_not_duplicate = prev_vector
+        .map(|pv| pv.is_empty() || pv.get_ref(pv.len() - 1) != vector.get_ref(0))
+        .unwrap_or(true);
+    if is_first_not_duplicate {
+        selected.set(0, true);
+    }
+}
+
+/// Same as [find_unique_scalar] but compares the keys of a [DictionaryVector], the strings are
+/// compared only if the previous vector doesn't share the dictionary with `vector`.
+pub(crate) fn find_unique_dictionary(
+    vector: &DictionaryVector,
+    selected: &mut MutableBitmap,
+    prev_vector: Option<&dyn Vector>,
+) {
+    assert!(selected.len() >= vector.len());
+
+    if vector.is_empty() {
+        return;
+    }
+
+    // Keys of the same vector refer to the same dictionary.
+    for i in 1..vector.len() {
+        if vector.key(i - 1) != vector.key(i) {
+            selected.set(i, true);
+        }
+    }
+
+    let is_first_not_duplicate = match prev_vector {
+        Some(pv) if pv.is_empty() => true,
+        Some(pv) => match pv.as_any().downcast_ref::<DictionaryVector>() {
+            Some(pv) if pv.shares_dictionary(vector) => pv.key(pv.len() - 1) != vector.key(0),
+            _ => pv.get_ref(pv.len() - 1) != vector.get_ref(0),
+        },
+        None => true,
+    };
+    if is_first_not_duplicate {
+        selected.set(0, true);
+    }
+}
+
 pub(crate) fn find_unique_null(
     vector: &NullVector,
     selected: &mut MutableBitmap,
@@ -346,6 +405,21 @@ mod tests {
         check_bitmap(&expect, &selected);
     }
 
+    #[test]
+    fn test_find_unique_decimal() {
+        use crate::vectors::DecimalVector;
+
+        let input = DecimalVector::from_values(10, 2, vec![Some(1), Some(1), None, None, Some(2)]);
+        let mut selected = MutableBitmap::from_len_zeroed(5);
+        input.find_unique(&mut selected, None);
+        check_bitmap(&[true, false, true, false, true], &selected);
+
+        let prev = DecimalVector::from_values(10, 2, vec![Some(0), Some(1)]);
+        let mut selected = MutableBitmap::from_len_zeroed(5);
+        input.find_unique(&mut selected, Some(&prev));
+        check_bitmap(&[false, false, true, false, true], &selected);
+    }
+
     macro_rules! impl_find_unique_date_like_test {
         ($VectorType: ident, $ValueType: ident, $method: ident) => {{
             use common_time::$ValueType;
@@ -16,6 +16,7 @@ use crate::prelude::*;
 pub(crate) use crate::vectors::constant::replicate_constant;
 pub(crate) use crate::vectors::date::replicate_date;
 pub(crate) use crate::vectors::datetime::replicate_datetime;
+pub(crate) use crate::vectors::dictionary::replicate_dictionary;
 pub(crate) use crate::vectors::null::replicate_null;
 pub(crate) use crate::vectors::primitive::replicate_primitive;
 pub(crate) use crate::vectors::timestamp::replicate_timestamp;
@@ -39,6 +40,29 @@ pub(crate) fn replicate_scalar<C: ScalarVector>(c: &C, offsets: &[usize]) -> Vec
     builder.to_vector()
 }
 
+/// Replicates elements by pushing [ValueRef]s into a builder created from the vector's data
+/// type, used by vectors whose builders can't be created without the data type.
+pub(crate) fn replicate_value_ref(vector: &dyn Vector, offsets: &[usize]) -> VectorRef {
+    assert_eq!(offsets.len(), vector.len());
+
+    if offsets.is_empty() {
+        return vector.slice(0, 0);
+    }
+    let mut builder = vector
+        .data_type()
+        .create_mutable_vector(*offsets.last().unwrap());
+
+    let mut previous_offset = 0;
+    for (i, offset) in offsets.iter().enumerate() {
+        for _ in previous_offset..*offset {
+            // The builder has the same data type as the vector, so push never fails.
+            builder.push_value_ref(vector.get_ref(i)).unwrap();
+        }
+        previous_offset = *offset;
+    }
+    builder.to_vector()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -91,6 +115,18 @@ mod tests {
         assert_eq!(expect, v);
     }
 
+    #[test]
+    fn test_replicate_value_ref() {
+        use crate::vectors::JsonVector;
+
+        let v = JsonVector::from(vec![Some("1"), None, Some("[]")]);
+        let offsets = [2, 3, 3];
+
+        let v = v.replicate(&offsets);
+        let expect: VectorRef = Arc::new(JsonVector::from(vec![Some("1"), Some("1"), None]));
+        assert_eq!(expect, v);
+    }
+
     #[test]
     fn test_replicate_constant() {
         let v = Arc::new(StringVector::from_slice(&["hello"]));
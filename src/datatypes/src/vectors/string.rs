@@ -27,7 +27,7 @@ use crate::scalars::{ScalarVector, ScalarVectorBuilder};
 use crate::serialize::Serializable;
 use crate::types::StringType;
 use crate::value::{Value, ValueRef};
-use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+use crate::vectors::{self, DictionaryVector, MutableVector, Validity, Vector, VectorRef};
 
 /// String array wrapper
 #[derive(Debug, Clone, PartialEq)]
@@ -187,6 +187,12 @@ impl MutableVector for StringVectorBuilder {
     }
 
     fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        if let Some(vector) = vector.as_any().downcast_ref::<DictionaryVector>() {
+            for i in offset..offset + length {
+                self.buffer.push(vector.get_data(i));
+            }
+            return Ok(());
+        }
         vectors::impl_extend_for_builder!(self.buffer, vector, StringVector, offset, length)
     }
 }
@@ -323,5 +329,11 @@ mod tests {
 
         let expect: VectorRef = Arc::new(StringVector::from_slice(&["hello", "one", "two"]));
         assert_eq!(expect, vector);
+
+        let mut builder = StringType::default().create_mutable_vector(2);
+        let input = DictionaryVector::from(vec![Some("a"), None, Some("b")]);
+        builder.extend_slice_of(&input, 1, 2).unwrap();
+        let expect: VectorRef = Arc::new(StringVector::from(vec![None, Some("b")]));
+        assert_eq!(expect, builder.to_vector());
     }
 }
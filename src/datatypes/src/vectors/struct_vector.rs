@@ -0,0 +1,364 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, StructArray};
+use arrow::bitmap::MutableBitmap;
+use serde_json::Value as JsonValue;
+use snafu::prelude::*;
+
+use crate::error::{self, Result};
+use crate::prelude::*;
+use crate::serialize::Serializable;
+use crate::types::StructType;
+use crate::value::{StructValue, StructValueRef};
+use crate::vectors::impl_validity_for_vector;
+
+/// Vector of structs, backed by Arrow's `StructArray`. Each field of the struct is
+/// also kept as a vector to access the values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructVector {
+    array: StructArray,
+    fields: Vec<VectorRef>,
+    datatype: StructType,
+}
+
+impl StructVector {
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        let array = array
+            .as_ref()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .with_context(|| error::ConversionSnafu {
+                from: format!("{:?}", array.as_ref().data_type()),
+            })?
+            .clone();
+        let datatype = match ConcreteDataType::try_from(array.data_type())? {
+            ConcreteDataType::Struct(t) => t,
+            _ => unreachable!(),
+        };
+        let fields = VectorHelper::try_into_vectors(array.values())?;
+
+        Ok(Self {
+            array,
+            fields,
+            datatype,
+        })
+    }
+
+    /// Returns the vectors of the struct fields.
+    pub fn fields(&self) -> &[VectorRef] {
+        &self.fields
+    }
+
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn struct_value(&self, index: usize) -> StructValue {
+        StructValue::new(
+            self.fields.iter().map(|field| field.get(index)).collect(),
+            self.datatype.clone(),
+        )
+    }
+}
+
+impl Vector for StructVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Struct(self.datatype.clone())
+    }
+
+    fn vector_type_name(&self) -> String {
+        "StructVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.fields.iter().map(|field| field.memory_size()).sum()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(StructVector {
+            array: self.array.slice(offset, length),
+            fields: self
+                .fields
+                .iter()
+                .map(|field| field.slice(offset, length))
+                .collect(),
+            datatype: self.datatype.clone(),
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        if !self.array.is_valid(index) {
+            return Value::Null;
+        }
+        Value::Struct(self.struct_value(index))
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        if !self.array.is_valid(index) {
+            return ValueRef::Null;
+        }
+        ValueRef::Struct(StructValueRef::Indexed {
+            vector: self,
+            idx: index,
+        })
+    }
+}
+
+impl Serializable for StructVector {
+    /// Serializes each struct as a JSON object keyed by field names.
+    fn serialize_to_json(&self) -> Result<Vec<JsonValue>> {
+        (0..self.len())
+            .map(|i| JsonValue::try_from(self.get(i)).context(error::SerializeSnafu))
+            .collect()
+    }
+}
+
+pub struct StructVectorBuilder {
+    datatype: StructType,
+    fields: Vec<Box<dyn MutableVector>>,
+    validity: Option<MutableBitmap>,
+    len: usize,
+}
+
+impl StructVectorBuilder {
+    pub fn with_type_capacity(datatype: StructType, capacity: usize) -> StructVectorBuilder {
+        let fields = datatype
+            .fields()
+            .iter()
+            .map(|field| field.data_type().create_mutable_vector(capacity))
+            .collect();
+
+        StructVectorBuilder {
+            datatype,
+            fields,
+            validity: None,
+            len: 0,
+        }
+    }
+
+    fn push_null(&mut self) -> Result<()> {
+        for field in &mut self.fields {
+            field.push_value_ref(ValueRef::Null)?;
+        }
+        match &mut self.validity {
+            Some(validity) => validity.push(false),
+            None => {
+                let mut validity = MutableBitmap::with_capacity(self.len + 1);
+                validity.extend_constant(self.len, true);
+                validity.push(false);
+                self.validity = Some(validity);
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    fn push_items<'a>(&mut self, items: impl Iterator<Item = ValueRef<'a>>) -> Result<()> {
+        let mut pushed = 0;
+        for (field, item) in self.fields.iter_mut().zip(items) {
+            field.push_value_ref(item)?;
+            pushed += 1;
+        }
+        ensure!(
+            pushed == self.fields.len(),
+            error::CastTypeSnafu {
+                msg: format!(
+                    "Struct value has {} items, expect {}",
+                    pushed,
+                    self.fields.len()
+                ),
+            }
+        );
+        if let Some(validity) = &mut self.validity {
+            validity.push(true);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn finish(&mut self) -> StructVector {
+        let fields = self
+            .fields
+            .iter_mut()
+            .map(|field| field.to_vector())
+            .collect::<Vec<_>>();
+        let array = StructArray::from_data(
+            ConcreteDataType::Struct(self.datatype.clone()).as_arrow_type(),
+            fields.iter().map(|field| field.to_arrow_array()).collect(),
+            std::mem::take(&mut self.validity).map(|x| x.into()),
+        );
+        self.len = 0;
+
+        StructVector {
+            array,
+            fields,
+            datatype: self.datatype.clone(),
+        }
+    }
+}
+
+impl MutableVector for StructVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Struct(self.datatype.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        match value.as_struct()? {
+            Some(StructValueRef::Indexed { vector, idx }) => {
+                self.push_items(vector.fields.iter().map(|field| field.get_ref(idx)))
+            }
+            Some(StructValueRef::Ref { val }) => {
+                self.push_items(val.items().iter().map(Value::as_value_ref))
+            }
+            None => self.push_null(),
+        }
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        for idx in offset..offset + length {
+            self.push_value_ref(vector.get_ref(idx))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::StructField;
+
+    fn new_struct_type() -> StructType {
+        StructType::new(vec![
+            StructField::new("a", ConcreteDataType::int32_datatype(), true),
+            StructField::new("b", ConcreteDataType::string_datatype(), true),
+        ])
+    }
+
+    fn new_struct_vector() -> VectorRef {
+        let datatype = new_struct_type();
+        let mut builder = datatype.create_mutable_vector(3);
+        let value = Value::Struct(StructValue::new(
+            vec![Value::Int32(1), Value::from("x")],
+            datatype.clone(),
+        ));
+        builder.push_value_ref(value.as_value_ref()).unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        let value = Value::Struct(StructValue::new(
+            vec![Value::Null, Value::from("z")],
+            datatype,
+        ));
+        builder.push_value_ref(value.as_value_ref()).unwrap();
+        builder.to_vector()
+    }
+
+    #[test]
+    fn test_struct_vector() {
+        let datatype = new_struct_type();
+        let vector = new_struct_vector();
+        assert_eq!(3, vector.len());
+        assert_eq!("StructVector", vector.vector_type_name());
+        assert_eq!(
+            ConcreteDataType::Struct(datatype.clone()),
+            vector.data_type()
+        );
+        assert!(!vector.is_null(0));
+        assert!(vector.is_null(1));
+
+        assert_eq!(
+            Value::Struct(StructValue::new(
+                vec![Value::Int32(1), Value::from("x")],
+                datatype.clone()
+            )),
+            vector.get(0)
+        );
+        assert_eq!(Value::Null, vector.get(1));
+        assert!(matches!(vector.get_ref(2), ValueRef::Struct(_)));
+
+        let sliced = vector.slice(1, 2);
+        assert_eq!(Value::Null, sliced.get(0));
+        assert_eq!(vector.get(2), sliced.get(1));
+
+        let arrow_arr = vector.to_arrow_array();
+        assert_eq!(&datatype.as_arrow_type(), arrow_arr.data_type());
+        let converted: VectorRef = Arc::new(StructVector::try_from_arrow_array(arrow_arr).unwrap());
+        assert_eq!(vector, converted);
+
+        assert_eq!(
+            vec![
+                json!({"a": 1, "b": "x"}),
+                JsonValue::Null,
+                json!({"a": null, "b": "z"})
+            ],
+            vector.serialize_to_json().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_struct_vector_builder() {
+        let input = new_struct_vector();
+        let mut builder = new_struct_type().create_mutable_vector(3);
+        builder.extend_slice_of(&*input, 0, 3).unwrap();
+        assert_eq!(input, builder.to_vector());
+
+        let mut builder = new_struct_type().create_mutable_vector(1);
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+        let wrong_items = Value::Struct(StructValue::new(vec![Value::Int32(1)], new_struct_type()));
+        assert!(builder.push_value_ref(wrong_items.as_value_ref()).is_err());
+    }
+}
@@ -17,7 +17,10 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use catalog::error::{InvalidCatalogValueSnafu, InvalidSchemaInCatalogSnafu};
-use catalog::remote::{Kv, KvBackendRef};
+use catalog::privilege::{
+    CreateRoleRequest, CreateUserRequest, GrantRequest, GrantRoleRequest, PrivilegeManagerRef,
+};
+use catalog::remote::{Kv, KvBackendRef, RemotePrivileges};
 use catalog::{
     CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, RegisterSchemaRequest,
     RegisterSystemTableRequest, RegisterTableRequest, SchemaProvider, SchemaProviderRef,
@@ -37,6 +40,7 @@ pub struct FrontendCatalogManager {
     backend: KvBackendRef,
     table_routes: Arc<TableRoutes>,
     datanode_clients: Arc<DatanodeClients>,
+    privileges: Arc<RemotePrivileges>,
 }
 
 impl FrontendCatalogManager {
@@ -46,6 +50,7 @@ impl FrontendCatalogManager {
         datanode_clients: Arc<DatanodeClients>,
     ) -> Self {
         Self {
+            privileges: Arc::new(RemotePrivileges::new(backend.clone())),
             backend,
             table_routes,
             datanode_clients,
@@ -62,7 +67,7 @@ impl FrontendCatalogManager {
 #[async_trait::async_trait]
 impl CatalogManager for FrontendCatalogManager {
     async fn start(&self) -> catalog::error::Result<()> {
-        Ok(())
+        self.privileges.start().await
     }
 
     async fn register_table(
@@ -100,11 +105,46 @@ impl CatalogManager for FrontendCatalogManager {
 
     fn table(
         &self,
-        _catalog: &str,
-        _schema: &str,
-        _table_name: &str,
+        catalog: &str,
+        schema: &str,
+        table_name: &str,
     ) -> catalog::error::Result<Option<TableRef>> {
-        unimplemented!()
+        let catalog = match self.catalog(catalog)? {
+            Some(catalog) => catalog,
+            None => return Ok(None),
+        };
+        match catalog.schema(schema)? {
+            Some(schema) => schema.table(table_name),
+            None => Ok(None),
+        }
+    }
+
+    async fn create_user(&self, request: CreateUserRequest) -> catalog::error::Result<bool> {
+        self.privileges.create_user(request).await
+    }
+
+    async fn create_role(&self, request: CreateRoleRequest) -> catalog::error::Result<bool> {
+        self.privileges.create_role(request).await
+    }
+
+    async fn grant(&self, request: GrantRequest) -> catalog::error::Result<()> {
+        self.privileges.grant(request).await
+    }
+
+    async fn revoke(&self, request: GrantRequest) -> catalog::error::Result<()> {
+        self.privileges.revoke(request).await
+    }
+
+    async fn grant_roles(&self, request: GrantRoleRequest) -> catalog::error::Result<()> {
+        self.privileges.grant_roles(request).await
+    }
+
+    async fn revoke_roles(&self, request: GrantRoleRequest) -> catalog::error::Result<()> {
+        self.privileges.revoke_roles(request).await
+    }
+
+    fn privilege_manager(&self) -> Option<PrivilegeManagerRef> {
+        Some(self.privileges.privilege_manager())
     }
 }
 
@@ -15,7 +15,6 @@
 use std::any::Any;
 
 use common_error::prelude::*;
-use common_query::logical_plan::Expr;
 use datafusion_common::ScalarValue;
 use store_api::storage::RegionId;
 
@@ -47,6 +46,24 @@ pub enum Error {
         source: servers::error::Error,
     },
 
+    #[snafu(display("Failed to create user provider, source: {}", source))]
+    CreateUserProvider {
+        #[snafu(backtrace)]
+        source: servers::error::Error,
+    },
+
+    #[snafu(display("Failed to set up TLS, source: {}", source))]
+    SetupTls {
+        #[snafu(backtrace)]
+        source: servers::error::Error,
+    },
+
+    #[snafu(display("Failed to create gRPC channel manager, source: {}", source))]
+    CreateChannelManager {
+        #[snafu(backtrace)]
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display("Failed to parse address {}, source: {}", addr, source))]
     ParseAddr {
         addr: String,
@@ -123,12 +140,6 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
-    #[snafu(display("Failed to find regions by filters: {:?}", filters))]
-    FindRegions {
-        filters: Vec<Expr>,
-        backtrace: Backtrace,
-    },
-
     #[snafu(display("Failed to find Datanode by region: {:?}", region))]
     FindDatanode {
         region: RegionId,
@@ -184,6 +195,13 @@ pub enum Error {
         source: catalog::error::Error,
     },
 
+    #[cfg(feature = "python")]
+    #[snafu(display("Failed to start script manager, source: {}", source))]
+    StartScriptManager {
+        #[snafu(backtrace)]
+        source: script::error::Error,
+    },
+
     #[snafu(display("Failed to serialize or deserialize catalog entry: {}", source))]
     CatalogEntrySerde {
         #[snafu(backtrace)]
@@ -381,9 +399,48 @@ pub enum Error {
         source: query::error::Error,
     },
 
+    #[snafu(display("Invalid materialized view {}, source: {}", name, source))]
+    MaterializedView {
+        name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to update materialized views of table {}, source: {}",
+        table_name,
+        source
+    ))]
+    UpdateMaterializedViews {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
     #[snafu(display("Unsupported expr type: {}", name))]
     UnsupportedExpr { name: String, backtrace: Backtrace },
 
+    #[snafu(display("Not supported: {}", feat))]
+    NotSupported { feat: String, backtrace: Backtrace },
+
+    #[snafu(display("Quota exceeded: {}", reason))]
+    QuotaExceeded {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to create record batches, source: {}", source))]
+    CreateRecordBatches {
+        #[snafu(backtrace)]
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Query {} is cancelled", id))]
+    QueryCancelled { id: u32, backtrace: Backtrace },
+
+    #[snafu(display("No running query of process {}", id))]
+    ProcessNotFound { id: u32, backtrace: Backtrace },
+
     #[snafu(display("Failed to do vector computation, source: {}", source))]
     VectorComputation {
         #[snafu(backtrace)]
@@ -399,7 +456,6 @@ impl ErrorExt for Error {
             Error::ParseAddr { .. }
             | Error::InvalidSql { .. }
             | Error::FindRegion { .. }
-            | Error::FindRegions { .. }
             | Error::InvalidInsertRequest { .. }
             | Error::FindPartitionColumn { .. }
             | Error::ColumnValuesNumberMismatch { .. }
@@ -409,6 +465,10 @@ impl ErrorExt for Error {
             Error::RuntimeResource { source, .. } => source.status_code(),
 
             Error::StartServer { source, .. } => source.status_code(),
+            Error::CreateUserProvider { source } | Error::SetupTls { source } => {
+                source.status_code()
+            }
+            Error::CreateChannelManager { source } => source.status_code(),
 
             Error::ParseSql { source } => source.status_code(),
 
@@ -446,6 +506,8 @@ impl ErrorExt for Error {
 
             Error::JoinTask { .. } => StatusCode::Unexpected,
             Error::Catalog { source, .. } => source.status_code(),
+            #[cfg(feature = "python")]
+            Error::StartScriptManager { source } => source.status_code(),
             Error::CatalogEntrySerde { source, .. } => source.status_code(),
 
             Error::StartMetaClient { source } | Error::RequestMeta { source } => {
@@ -464,9 +526,16 @@ impl ErrorExt for Error {
             Error::FindNewColumnsOnInsertion { source, .. } => source.status_code(),
             Error::DeserializeInsertBatch { source, .. } => source.status_code(),
             Error::PrimaryKeyNotFound { .. } => StatusCode::InvalidArguments,
-            Error::ExecuteSql { source, .. } => source.status_code(),
+            Error::ExecuteSql { source, .. }
+            | Error::MaterializedView { source, .. }
+            | Error::UpdateMaterializedViews { source, .. } => source.status_code(),
             Error::InsertBatchToRequest { source, .. } => source.status_code(),
             Error::CreateDatabase { source, .. } => source.status_code(),
+            Error::NotSupported { .. } => StatusCode::Unsupported,
+            Error::QuotaExceeded { .. } => StatusCode::QuotaExceeded,
+            Error::CreateRecordBatches { source } => source.status_code(),
+            Error::QueryCancelled { .. } => StatusCode::QueryCancelled,
+            Error::ProcessNotFound { .. } => StatusCode::InvalidArguments,
         }
     }
 
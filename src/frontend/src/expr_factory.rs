@@ -17,8 +17,9 @@ use std::sync::Arc;
 
 use api::helper::ColumnDataTypeWrapper;
 use api::v1::codec::InsertBatch;
-use api::v1::{ColumnDataType, CreateExpr};
+use api::v1::CreateExpr;
 use datatypes::schema::ColumnSchema;
+use session::context::QueryContextRef;
 use snafu::{ensure, ResultExt};
 use sql::statements::create::{CreateTable, TIME_INDEX};
 use sql::statements::{column_def_to_schema, table_idents_to_full_name};
@@ -33,7 +34,11 @@ pub type CreateExprFactoryRef = Arc<dyn CreateExprFactory + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait CreateExprFactory {
-    async fn create_expr_by_stmt(&self, stmt: &CreateTable) -> Result<CreateExpr>;
+    async fn create_expr_by_stmt(
+        &self,
+        stmt: &CreateTable,
+        query_ctx: QueryContextRef,
+    ) -> Result<CreateExpr>;
 
     async fn create_expr_by_insert_batch(
         &self,
@@ -49,8 +54,12 @@ pub struct DefaultCreateExprFactory;
 
 #[async_trait::async_trait]
 impl CreateExprFactory for DefaultCreateExprFactory {
-    async fn create_expr_by_stmt(&self, stmt: &CreateTable) -> Result<CreateExpr> {
-        create_to_expr(None, vec![0], stmt)
+    async fn create_expr_by_stmt(
+        &self,
+        stmt: &CreateTable,
+        query_ctx: QueryContextRef,
+    ) -> Result<CreateExpr> {
+        create_to_expr(None, vec![0], stmt, query_ctx)
     }
 
     async fn create_expr_by_insert_batch(
@@ -79,9 +88,10 @@ fn create_to_expr(
     table_id: Option<u32>,
     region_ids: Vec<u32>,
     create: &CreateTable,
+    query_ctx: QueryContextRef,
 ) -> Result<CreateExpr> {
     let (catalog_name, schema_name, table_name) =
-        table_idents_to_full_name(&create.name).context(ParseSqlSnafu)?;
+        table_idents_to_full_name(&create.name, &query_ctx).context(ParseSqlSnafu)?;
 
     let time_index = find_time_index(&create.constraints)?;
     let expr = CreateExpr {
@@ -158,15 +168,15 @@ fn columns_to_expr(
         .iter()
         .map(|c| {
             ColumnDataTypeWrapper::try_from(c.data_type.clone())
-                .map(|w| w.datatype())
+                .map(ColumnDataTypeWrapper::into_parts)
                 .context(ColumnDataTypeSnafu)
         })
-        .collect::<Result<Vec<ColumnDataType>>>()?;
+        .collect::<Result<Vec<_>>>()?;
 
     column_schemas
         .iter()
         .zip(column_datatypes.into_iter())
-        .map(|(schema, datatype)| {
+        .map(|(schema, (datatype, datatype_extension))| {
             Ok(api::v1::ColumnDef {
                 name: schema.name.clone(),
                 datatype: datatype as i32,
@@ -179,6 +189,7 @@ fn columns_to_expr(
                         },
                     )?),
                 },
+                datatype_extension,
             })
         })
         .collect()
@@ -14,7 +14,9 @@
 
 use std::sync::Arc;
 
+use common_grpc::tls::ClientTlsOption;
 use serde::{Deserialize, Serialize};
+pub use servers::tls::{TlsMode, TlsOption};
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
@@ -25,11 +27,14 @@ use crate::mysql::MysqlOptions;
 use crate::opentsdb::OpentsdbOptions;
 use crate::postgres::PostgresOptions;
 use crate::prometheus::PrometheusOptions;
+use crate::quota::QuotaOptions;
 use crate::server::Services;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FrontendOptions {
     pub http_addr: Option<String>,
+    #[serde(default)]
+    pub http_tls: TlsOption,
     pub grpc_options: Option<GrpcOptions>,
     pub mysql_options: Option<MysqlOptions>,
     pub postgres_options: Option<PostgresOptions>,
@@ -39,12 +44,22 @@ pub struct FrontendOptions {
     pub mode: Mode,
     pub datanode_rpc_addr: String,
     pub metasrv_addr: Option<Vec<String>>,
+    /// Authenticates users of MySQL, Postgres and HTTP servers if set, for example,
+    /// "static_user_provider:file:/path/to/users".
+    pub user_provider: Option<String>,
+    /// Limits of resources used by users and databases.
+    #[serde(default)]
+    pub quota: QuotaOptions,
+    /// Connects to datanodes and meta servers by TLS if set.
+    #[serde(default)]
+    pub client_tls: Option<ClientTlsOption>,
 }
 
 impl Default for FrontendOptions {
     fn default() -> Self {
         Self {
             http_addr: Some("0.0.0.0:4000".to_string()),
+            http_tls: TlsOption::default(),
             grpc_options: Some(GrpcOptions::default()),
             mysql_options: Some(MysqlOptions::default()),
             postgres_options: Some(PostgresOptions::default()),
@@ -54,6 +69,9 @@ impl Default for FrontendOptions {
             mode: Mode::Standalone,
             datanode_rpc_addr: "127.0.0.1:3001".to_string(),
             metasrv_addr: None,
+            user_provider: None,
+            quota: QuotaOptions::default(),
+            client_tls: None,
         }
     }
 }
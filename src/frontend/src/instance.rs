@@ -15,7 +15,10 @@
 pub(crate) mod distributed;
 mod influxdb;
 mod opentsdb;
+mod privilege;
+mod process;
 mod prometheus;
+mod quota;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -26,12 +29,12 @@ use api::v1::alter_expr::Kind;
 use api::v1::codec::InsertBatch;
 use api::v1::object_expr::Expr;
 use api::v1::{
-    admin_expr, insert_expr, AddColumns, AdminExpr, AdminResult, AlterExpr, CreateDatabaseExpr,
-    CreateExpr, InsertExpr, ObjectExpr, ObjectResult as GrpcObjectResult,
+    admin_expr, insert_expr, select_expr, AddColumns, AdminExpr, AdminResult, AlterExpr,
+    CreateDatabaseExpr, CreateExpr, InsertExpr, ObjectExpr, ObjectResult as GrpcObjectResult,
 };
 use async_trait::async_trait;
 use catalog::remote::MetaKvBackend;
-use catalog::{CatalogManagerRef, CatalogProviderRef, SchemaProviderRef};
+use catalog::{CatalogManager, CatalogManagerRef, CatalogProviderRef, SchemaProviderRef};
 use client::admin::{admin_result_to_output, Admin};
 use client::{Client, Database, Select};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
@@ -39,6 +42,8 @@ use common_error::prelude::{BoxedError, StatusCode};
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
 use common_query::Output;
 use common_telemetry::{debug, error, info};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::VectorRef;
 use distributed::DistInstance;
 use meta_client::client::MetaClientBuilder;
 use meta_client::MetaClientOpts;
@@ -47,12 +52,17 @@ use servers::query_handler::{
     GrpcAdminHandler, GrpcQueryHandler, InfluxdbLineProtocolHandler, OpentsdbProtocolHandler,
     PrometheusProtocolHandler, ScriptHandler, ScriptHandlerRef, SqlQueryHandler,
 };
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::dialect::GenericDialect;
 use sql::parser::ParserContext;
+use sql::prepared::PreparedStatement;
+use sql::statements::alter::alter_table_to_expr;
 use sql::statements::create::Partitions;
 use sql::statements::insert::Insert;
+use sql::statements::set_variables::TIME_ZONE_VARIABLE;
 use sql::statements::statement::Statement;
+use table::engine::TableReference;
 
 use crate::catalog::FrontendCatalogManager;
 use crate::datanode::DatanodeClients;
@@ -63,6 +73,11 @@ use crate::error::{
 };
 use crate::expr_factory::{CreateExprFactoryRef, DefaultCreateExprFactory};
 use crate::frontend::{FrontendOptions, Mode};
+use crate::instance::process::describe_inserts;
+use crate::materialized_view::RemoteMaterializedViews;
+use crate::process::{ProcessList, RemoteProcessList};
+use crate::quota::QuotaManager;
+use crate::script::start_script_handler;
 use crate::sql::insert_to_request;
 use crate::table::route::TableRoutes;
 
@@ -91,7 +106,8 @@ pub struct Instance {
     client: Client,
     /// catalog manager is None in standalone mode, datanode will keep their own
     catalog_manager: Option<CatalogManagerRef>,
-    /// Script handler is None in distributed mode, only works on standalone mode.
+    /// Script handler of the datanode in standalone mode, or of the scripts in metasrv in
+    /// distributed mode.
     script_handler: Option<ScriptHandlerRef>,
     create_expr_factory: CreateExprFactoryRef,
     // TODO(fys): it should be a trait that corresponds to two implementations:
@@ -100,6 +116,12 @@ pub struct Instance {
     mode: Mode,
     // TODO(LFC): Refactor consideration: Can we split Frontend to DistInstance and EmbedInstance?
     dist_instance: Option<DistInstance>,
+    quota_manager: Arc<QuotaManager>,
+    process_list: Arc<ProcessList>,
+    /// Kills processes in other frontends, only in distributed mode.
+    remote_process_list: Option<Arc<RemoteProcessList>>,
+    /// Maintains the materialized views, only in distributed mode.
+    materialized_views: Option<Arc<RemoteMaterializedViews>>,
 }
 
 impl Default for Instance {
@@ -111,6 +133,10 @@ impl Default for Instance {
             create_expr_factory: Arc::new(DefaultCreateExprFactory {}),
             mode: Mode::Standalone,
             dist_instance: None,
+            quota_manager: Arc::new(QuotaManager::default()),
+            process_list: Arc::new(ProcessList::default()),
+            remote_process_list: None,
+            materialized_views: None,
         }
     }
 }
@@ -119,11 +145,19 @@ impl Instance {
     pub async fn try_new(opts: &FrontendOptions) -> Result<Self> {
         let mut instance = Instance {
             mode: opts.mode.clone(),
+            quota_manager: Arc::new(QuotaManager::new(opts.quota.clone())),
             ..Default::default()
         };
 
+        let mut channel_config = ChannelConfig::new();
+        if let Some(tls) = &opts.client_tls {
+            channel_config = channel_config.client_tls_config(tls.clone());
+        }
+        let channel_manager = ChannelManager::with_tls_config(channel_config)
+            .context(error::CreateChannelManagerSnafu)?;
+
         let addr = opts.datanode_grpc_addr();
-        instance.client.start(vec![addr]);
+        instance.client = Client::with_manager_and_urls(channel_manager.clone(), vec![addr]);
 
         instance.dist_instance = match &opts.mode {
             Mode::Standalone => None,
@@ -138,17 +172,20 @@ impl Instance {
                 );
 
                 let meta_config = MetaClientOpts::default();
-                let channel_config = ChannelConfig::new()
+                let mut meta_channel_config = ChannelConfig::new()
                     .timeout(Duration::from_millis(meta_config.timeout_millis))
                     .connect_timeout(Duration::from_millis(meta_config.connect_timeout_millis))
                     .tcp_nodelay(meta_config.tcp_nodelay);
-
-                let channel_manager = ChannelManager::with_config(channel_config);
+                if let Some(tls) = &opts.client_tls {
+                    meta_channel_config = meta_channel_config.client_tls_config(tls.clone());
+                }
+                let meta_channel_manager = ChannelManager::with_tls_config(meta_channel_config)
+                    .context(error::CreateChannelManagerSnafu)?;
 
                 let mut meta_client = MetaClientBuilder::new(0, 0)
                     .enable_router()
                     .enable_store()
-                    .channel_manager(channel_manager)
+                    .channel_manager(meta_channel_manager)
                     .build();
                 meta_client
                     .start(metasrv_addr)
@@ -159,21 +196,37 @@ impl Instance {
                 let meta_backend = Arc::new(MetaKvBackend {
                     client: meta_client.clone(),
                 });
+                instance.remote_process_list = Some(
+                    RemoteProcessList::start(meta_backend.clone(), instance.process_list.clone())
+                        .await?,
+                );
                 let table_routes = Arc::new(TableRoutes::new(meta_client.clone()));
-                let datanode_clients = Arc::new(DatanodeClients::new());
+                table_routes.start_watching();
+                let datanode_clients =
+                    Arc::new(DatanodeClients::with_channel_manager(channel_manager));
                 let catalog_manager = Arc::new(FrontendCatalogManager::new(
-                    meta_backend,
+                    meta_backend.clone(),
                     table_routes,
                     datanode_clients.clone(),
                 ));
+                catalog_manager.start().await.context(error::CatalogSnafu)?;
 
                 instance.catalog_manager = Some(catalog_manager.clone());
 
-                Some(DistInstance::new(
-                    meta_client,
-                    catalog_manager,
-                    datanode_clients,
-                ))
+                let dist_instance =
+                    DistInstance::new(meta_client, catalog_manager.clone(), datanode_clients);
+                instance.materialized_views = Some(
+                    RemoteMaterializedViews::start(
+                        meta_backend.clone(),
+                        catalog_manager,
+                        dist_instance.query_engine().clone(),
+                    )
+                    .await?,
+                );
+                instance.script_handler =
+                    start_script_handler(meta_backend, dist_instance.query_engine().clone())
+                        .await?;
+                Some(dist_instance)
             }
         };
         Ok(instance)
@@ -207,15 +260,20 @@ impl Instance {
         self.script_handler = Some(handler);
     }
 
-    pub async fn handle_select(&self, expr: Select, stmt: Statement) -> Result<Output> {
+    pub async fn handle_select(
+        &self,
+        expr: Select,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         if let Some(dist_instance) = &self.dist_instance {
             let Select::Sql(sql) = expr;
-            dist_instance.handle_sql(&sql, stmt).await
+            dist_instance.handle_sql(&sql, stmt, query_ctx).await
         } else {
             // TODO(LFC): Refactor consideration: Datanode should directly execute statement in standalone mode to avoid parse SQL again.
             // Find a better way to execute query between Frontend and Datanode in standalone mode.
             // Otherwise we have to parse SQL first to get schema name. Maybe not GRPC.
-            self.database(DEFAULT_SCHEMA_NAME)
+            self.database(&query_ctx.current_schema())
                 .select(expr)
                 .await
                 .and_then(Output::try_from)
@@ -223,6 +281,23 @@ impl Instance {
         }
     }
 
+    /// Switches the current database of the session, the database must exist.
+    fn handle_use(&self, db: String, query_ctx: QueryContextRef) -> Result<Output> {
+        if let Some(catalog_manager) = &self.catalog_manager {
+            let catalog = query_ctx.current_catalog();
+            ensure!(
+                catalog_manager
+                    .schema(&catalog, &db)
+                    .context(error::CatalogSnafu)?
+                    .is_some(),
+                error::SchemaNotFoundSnafu { schema_info: &db }
+            );
+        }
+
+        query_ctx.set_current_schema(&db);
+        Ok(Output::AffectedRows(0))
+    }
+
     /// Handle create expr.
     pub async fn handle_create_table(
         &self,
@@ -484,28 +559,59 @@ impl Instance {
             })
     }
 
-    async fn sql_dist_insert(&self, insert: Box<Insert>) -> Result<usize> {
-        let (catalog, schema, table) = insert.full_table_name().context(error::ParseSqlSnafu)?;
+    async fn sql_dist_insert(
+        &self,
+        insert: Box<Insert>,
+        query_ctx: QueryContextRef,
+    ) -> Result<usize> {
+        let (catalog, schema, table) = insert
+            .full_table_name(&query_ctx)
+            .context(error::ParseSqlSnafu)?;
 
         let catalog_provider = self.get_catalog(&catalog)?;
         let schema_provider = Self::get_schema(catalog_provider, &schema)?;
 
-        let insert_request = insert_to_request(&schema_provider, *insert)?;
+        let insert_request = insert_to_request(&schema_provider, *insert, query_ctx)?;
+        let columns_values = insert_request.columns_values.clone();
 
         let batch = crate::table::insert::insert_request_to_insert_batch(&insert_request)?;
 
         self.create_or_alter_table_on_demand(&catalog, &schema, &table, &[batch])
             .await?;
 
-        let table = schema_provider
+        let dist_table = schema_provider
             .table(&table)
             .context(error::CatalogSnafu)?
             .context(error::TableNotFoundSnafu { table_name: &table })?;
 
-        table
+        let affected_rows = dist_table
             .insert(insert_request)
             .await
-            .context(error::TableSnafu)
+            .context(error::TableSnafu)?;
+        self.insert_into_materialized_views(&catalog, &schema, &table, &columns_values)
+            .await?;
+        Ok(affected_rows)
+    }
+
+    /// Updates the materialized views over the table with the rows inserted into it.
+    pub(crate) async fn insert_into_materialized_views(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+        columns_values: &HashMap<String, VectorRef>,
+    ) -> Result<()> {
+        match &self.materialized_views {
+            Some(views) => {
+                let table_ref = TableReference {
+                    catalog,
+                    schema,
+                    table,
+                };
+                views.insert(&table_ref, columns_values).await
+            }
+            None => Ok(()),
+        }
     }
 }
 
@@ -527,69 +633,152 @@ impl Instance {
             create_expr_factory: Arc::new(DefaultCreateExprFactory),
             mode: Mode::Standalone,
             dist_instance: None,
+            quota_manager: Arc::new(QuotaManager::default()),
+            process_list: Arc::new(ProcessList::default()),
+            remote_process_list: None,
+            materialized_views: None,
         }
     }
 }
 
 #[async_trait]
 impl SqlQueryHandler for Instance {
-    async fn do_query(&self, query: &str) -> server_error::Result<Output> {
+    async fn do_statement_query(
+        &self,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
         let mut stmt = ParserContext::create_with_dialect(query, &GenericDialect {})
             .map_err(BoxedError::new)
             .context(server_error::ExecuteQuerySnafu { query })?;
+        // Multiple statements are split by `SqlQueryHandler::do_query` before reaching here.
         if stmt.len() != 1 {
-            // TODO(LFC): Support executing multiple SQLs,
-            // which seems to be a major change to our whole server framework?
-            return server_error::NotSupportedSnafu {
-                feat: "Only one SQL is allowed to be executed at one time.",
+            return server_error::InvalidQuerySnafu {
+                reason: format!("expect exactly one statement, found: {}", stmt.len()),
             }
             .fail();
         }
-        let stmt = stmt.remove(0);
+        self.do_parsed_statement_query(stmt.remove(0), query, query_ctx)
+            .await
+    }
 
-        match stmt {
-            Statement::Query(_) => self
-                .handle_select(Select::Sql(query.to_string()), stmt)
-                .await
+    async fn do_parsed_statement_query(
+        &self,
+        stmt: Statement,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        self.check_statement_privileges(&stmt, &query_ctx)
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteQuerySnafu { query })?;
+
+        // KILL is not listed as a process, so it never kills itself.
+        if matches!(stmt, Statement::Kill(_)) {
+            return self.execute_statement(stmt, query, query_ctx).await;
+        }
+
+        // Killing the statement drops its execution, which cancels the requests to datanodes.
+        let guard = self.process_list.register(&query_ctx, query);
+        let output = tokio::select! {
+            biased;
+            _ = guard.cancelled() => Err(guard.cancelled_error())
                 .map_err(BoxedError::new)
                 .context(server_error::ExecuteQuerySnafu { query }),
-            Statement::Insert(insert) => match self.mode {
-                Mode::Standalone => {
-                    let (_, schema_name, table_name) = insert
-                        .full_table_name()
-                        .context(error::ParseSqlSnafu)
-                        .map_err(BoxedError::new)
-                        .context(server_error::ExecuteInsertSnafu {
-                            msg: "Failed to get table name",
-                        })?;
+            output = self.execute_statement(stmt, query, query_ctx) => output,
+        }?;
+        Ok(guard.attach(output))
+    }
 
-                    let expr = InsertExpr {
-                        schema_name,
-                        table_name,
-                        expr: Some(insert_expr::Expr::Sql(query.to_string())),
-                        region_number: 0,
-                        options: HashMap::default(),
-                    };
-                    self.handle_insert(&expr)
+    fn param_types(
+        &self,
+        stmt: &PreparedStatement,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<Option<ConcreteDataType>>> {
+        match &self.catalog_manager {
+            Some(catalog_manager) => {
+                query::parameter::infer_param_types(stmt, catalog_manager, &query_ctx)
+                    .context(error::ExecuteSqlSnafu { sql: stmt.sql() })
+                    .map_err(BoxedError::new)
+                    .context(server_error::ExecuteQuerySnafu { query: stmt.sql() })
+            }
+            None => Ok(vec![None; stmt.param_num()]),
+        }
+    }
+}
+
+impl Instance {
+    /// Executes `stmt`. In standalone mode, queries and inserts are executed by sending `query`,
+    /// the SQL text of `stmt`, to datanode.
+    async fn execute_statement(
+        &self,
+        stmt: Statement,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        match stmt {
+            Statement::Query(_) | Statement::Explain(_) => {
+                let permit = self
+                    .acquire_query_permit(&query_ctx)
+                    .map_err(BoxedError::new)
+                    .context(server_error::ExecuteQuerySnafu { query })?;
+                let output = if self.reads_processes(&stmt, &query_ctx) {
+                    permit
+                        .run(self.query_processes(stmt, query, query_ctx))
                         .await
-                        .map_err(BoxedError::new)
-                        .context(server_error::ExecuteQuerySnafu { query })
-                }
-                Mode::Distributed => {
-                    let affected = self
-                        .sql_dist_insert(insert)
+                } else {
+                    permit
+                        .run(self.handle_select(Select::Sql(query.to_string()), stmt, query_ctx))
                         .await
-                        .map_err(BoxedError::new)
-                        .context(server_error::ExecuteInsertSnafu {
-                            msg: "execute insert failed",
-                        })?;
-                    Ok(Output::AffectedRows(affected))
+                };
+                output
+                    .map_err(BoxedError::new)
+                    .context(server_error::ExecuteQuerySnafu { query })
+            }
+            Statement::Insert(insert) => {
+                self.throttle_sql_insert(&insert, &query_ctx)
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(server_error::ExecuteInsertSnafu {
+                        msg: "Failed to throttle insert",
+                    })?;
+                match self.mode {
+                    Mode::Standalone => {
+                        let (_, schema_name, table_name) = insert
+                            .full_table_name(&query_ctx)
+                            .context(error::ParseSqlSnafu)
+                            .map_err(BoxedError::new)
+                            .context(server_error::ExecuteInsertSnafu {
+                                msg: "Failed to get table name",
+                            })?;
+
+                        let expr = InsertExpr {
+                            schema_name,
+                            table_name,
+                            expr: Some(insert_expr::Expr::Sql(query.to_string())),
+                            region_number: 0,
+                            options: HashMap::default(),
+                        };
+                        self.handle_insert(&expr)
+                            .await
+                            .map_err(BoxedError::new)
+                            .context(server_error::ExecuteQuerySnafu { query })
+                    }
+                    Mode::Distributed => {
+                        let affected = self
+                            .sql_dist_insert(insert, query_ctx)
+                            .await
+                            .map_err(BoxedError::new)
+                            .context(server_error::ExecuteInsertSnafu {
+                                msg: "execute insert failed",
+                            })?;
+                        Ok(Output::AffectedRows(affected))
+                    }
                 }
-            },
+            }
             Statement::CreateTable(create) => {
                 let create_expr = self
                     .create_expr_factory
-                    .create_expr_by_stmt(&create)
+                    .create_expr_by_stmt(&create, query_ctx)
                     .await
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteQuerySnafu { query })?;
@@ -601,7 +790,7 @@ impl SqlQueryHandler for Instance {
             }
 
             Statement::ShowDatabases(_) | Statement::ShowTables(_) => self
-                .handle_select(Select::Sql(query.to_string()), stmt)
+                .handle_select(Select::Sql(query.to_string()), stmt, query_ctx)
                 .await
                 .map_err(BoxedError::new)
                 .context(server_error::ExecuteQuerySnafu { query }),
@@ -617,16 +806,89 @@ impl SqlQueryHandler for Instance {
             }
             Statement::Alter(alter_stmt) => self
                 .handle_alter(
-                    AlterExpr::try_from(alter_stmt)
+                    alter_table_to_expr(alter_stmt, &query_ctx)
                         .map_err(BoxedError::new)
                         .context(server_error::ExecuteAlterSnafu { query })?,
                 )
                 .await
                 .map_err(BoxedError::new)
                 .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::CreateMaterializedView(create) => {
+                match (&self.dist_instance, &self.materialized_views) {
+                    // The frontends maintain the view on the inserts into the source table.
+                    (Some(dist_instance), Some(views)) => views
+                        .create(dist_instance, create, &query_ctx)
+                        .await
+                        .map_err(BoxedError::new)
+                        .context(server_error::ExecuteQuerySnafu { query }),
+                    // The datanode maintains the view on the inserts into the source table.
+                    _ => self
+                        .handle_select(
+                            Select::Sql(query.to_string()),
+                            Statement::CreateMaterializedView(create),
+                            query_ctx,
+                        )
+                        .await
+                        .map_err(BoxedError::new)
+                        .context(server_error::ExecuteQuerySnafu { query }),
+                }
+            }
             Statement::ShowCreateTable(_) => {
                 return server_error::NotSupportedSnafu { feat: query }.fail()
             }
+            Statement::Use(db) => self
+                .handle_use(db, query_ctx)
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::SetVariables(set_variables) => {
+                let name = set_variables.name();
+                let value = set_variables.value();
+                if name == TIME_ZONE_VARIABLE {
+                    query_ctx.set_time_zone(Some(value));
+                } else {
+                    query_ctx.set_variable(&name, value);
+                }
+                Ok(Output::AffectedRows(0))
+            }
+            Statement::CreateUser(create_user) => self
+                .handle_create_user(create_user, &query_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::Grant(grant) => self
+                .handle_grant(grant, &query_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::Revoke(revoke) => self
+                .handle_revoke(revoke, &query_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::CreateRole(create_role) => self
+                .handle_create_role(create_role)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::GrantRole(grant) => self
+                .handle_grant_role(grant)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::RevokeRole(revoke) => self
+                .handle_revoke_role(revoke)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::ShowProcesslist(show) => self
+                .show_processlist(show, &query_ctx)
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
+            Statement::Kill(kill) => self
+                .kill(kill, &query_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(server_error::ExecuteQuerySnafu { query }),
         }
         .map_err(BoxedError::new)
         .context(server_error::ExecuteQuerySnafu { query })
@@ -635,9 +897,14 @@ impl SqlQueryHandler for Instance {
 
 #[async_trait]
 impl ScriptHandler for Instance {
-    async fn insert_script(&self, name: &str, script: &str) -> server_error::Result<()> {
+    async fn insert_script(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+    ) -> server_error::Result<()> {
         if let Some(handler) = &self.script_handler {
-            handler.insert_script(name, script).await
+            handler.insert_script(schema, name, script).await
         } else {
             server_error::NotSupportedSnafu {
                 feat: "Script execution in Frontend",
@@ -646,9 +913,9 @@ impl ScriptHandler for Instance {
         }
     }
 
-    async fn execute_script(&self, script: &str) -> server_error::Result<Output> {
+    async fn execute_script(&self, schema: &str, name: &str) -> server_error::Result<Output> {
         if let Some(handler) = &self.script_handler {
-            handler.execute_script(script).await
+            handler.execute_script(schema, name).await
         } else {
             server_error::NotSupportedSnafu {
                 feat: "Script execution in Frontend",
@@ -660,11 +927,31 @@ impl ScriptHandler for Instance {
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
-    async fn do_query(&self, query: ObjectExpr) -> server_error::Result<GrpcObjectResult> {
+    async fn do_query(
+        &self,
+        query: ObjectExpr,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<GrpcObjectResult> {
         if let Some(expr) = &query.expr {
             match expr {
                 Expr::Insert(insert) => {
-                    let result = self.handle_insert(insert).await;
+                    let inserts = std::slice::from_ref(insert);
+                    let result = match self.check_insert_privileges(inserts, &query_ctx) {
+                        Ok(()) => match self.throttle_inserts(inserts, &query_ctx).await {
+                            Ok(()) => {
+                                let process = describe_inserts("gRPC", inserts);
+                                self.run_as_process(
+                                    &query_ctx,
+                                    &process,
+                                    self.handle_insert(insert),
+                                )
+                                .await
+                                .and_then(|result| result)
+                            }
+                            Err(e) => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    };
                     result
                         .map(|o| match o {
                             Output::AffectedRows(rows) => ObjectResultBuilder::new()
@@ -682,14 +969,30 @@ impl GrpcQueryHandler for Instance {
                 }
 
                 // FIXME(hl): refactor
-                _ => self
-                    .database(DEFAULT_SCHEMA_NAME)
-                    .object(query.clone())
-                    .await
-                    .map_err(BoxedError::new)
-                    .with_context(|_| server_error::ExecuteQuerySnafu {
-                        query: format!("{:?}", query),
-                    }),
+                _ => {
+                    let result = match self
+                        .check_object_expr_privileges(&query, &query_ctx)
+                        .and_then(|()| self.acquire_query_permit(&query_ctx))
+                    {
+                        Ok(permit) => {
+                            let request = permit.run_request(async {
+                                self.database(&query_ctx.current_schema())
+                                    .object(query.clone())
+                                    .await
+                                    .context(error::RequestDatanodeSnafu)
+                            });
+                            self.run_as_process(&query_ctx, &describe_object_expr(expr), request)
+                                .await
+                                .and_then(|result| result)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    result.map_err(BoxedError::new).with_context(|_| {
+                        server_error::ExecuteQuerySnafu {
+                            query: format!("{:?}", query),
+                        }
+                    })
+                }
             }
         } else {
             server_error::InvalidQuerySnafu {
@@ -700,6 +1003,24 @@ impl GrpcQueryHandler for Instance {
     }
 }
 
+/// Describes the gRPC request as the query of its process.
+fn describe_object_expr(expr: &Expr) -> String {
+    match expr {
+        Expr::Insert(insert) => describe_inserts("gRPC", std::slice::from_ref(insert)),
+        Expr::Select(select) => match &select.expr {
+            Some(select_expr::Expr::Sql(sql)) => sql.clone(),
+            Some(select_expr::Expr::LogicalPlan(_)) => "gRPC logical plan".to_string(),
+            Some(select_expr::Expr::PhysicalPlan(_)) => "gRPC physical plan".to_string(),
+            Some(select_expr::Expr::DataVersions(expr)) => {
+                format!("gRPC data versions of {}", expr.table_name)
+            }
+            None => "gRPC select".to_string(),
+        },
+        Expr::Update(_) => "gRPC update".to_string(),
+        Expr::Delete(_) => "gRPC delete".to_string(),
+    }
+}
+
 fn get_schema_name(expr: &AdminExpr) -> &str {
     let schema_name = match &expr.expr {
         Some(admin_expr::Expr::Create(expr)) => expr.schema_name.as_deref(),
@@ -711,7 +1032,16 @@ fn get_schema_name(expr: &AdminExpr) -> &str {
 
 #[async_trait]
 impl GrpcAdminHandler for Instance {
-    async fn exec_admin_request(&self, mut expr: AdminExpr) -> server_error::Result<AdminResult> {
+    async fn exec_admin_request(
+        &self,
+        mut expr: AdminExpr,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<AdminResult> {
+        self.check_admin_expr_privileges(&expr, &query_ctx)
+            .map_err(BoxedError::new)
+            .with_context(|_| server_error::ExecuteQuerySnafu {
+                query: format!("{:?}", expr),
+            })?;
         // Force the default to be `None` rather than `Some(0)` comes from gRPC decode.
         // Related issue: #480
         if let Some(api::v1::admin_expr::Expr::Create(create)) = &mut expr.expr {
@@ -730,6 +1060,7 @@ impl GrpcAdminHandler for Instance {
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
+    use std::net::SocketAddr;
 
     use api::v1::codec::{InsertBatch, SelectResult};
     use api::v1::column::SemanticType;
@@ -737,10 +1068,20 @@ mod tests {
         admin_expr, admin_result, column, object_expr, object_result, select_expr, Column,
         ColumnDataType, ColumnDef as GrpcColumnDef, ExprHeader, MutateResult, SelectExpr,
     };
+    use common_error::ext::ErrorExt;
+    use common_recordbatch::util;
+    use common_runtime::Builder as RuntimeBuilder;
     use datatypes::schema::ColumnDefaultConstraint;
     use datatypes::value::Value;
+    use mysql_async::prelude::*;
+    use servers::context::QuotaLimits;
+    use servers::mysql::server::MysqlServer;
+    use servers::server::Server;
+    use servers::tls::ServerTls;
+    use session::context::QueryContext;
 
     use super::*;
+    use crate::quota::QuotaOptions;
     use crate::tests;
 
     #[tokio::test]
@@ -756,7 +1097,10 @@ mod tests {
                             TIME INDEX (ts),
                             PRIMARY KEY(ts, host)
                         ) engine=mito with(regions=1);"#;
-        let output = SqlQueryHandler::do_query(&*instance, sql).await.unwrap();
+        let output = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
         match output {
             Output::AffectedRows(rows) => assert_eq!(rows, 1),
             _ => unreachable!(),
@@ -767,14 +1111,20 @@ mod tests {
                                 ('frontend.host2', null, null, 2000),
                                 ('frontend.host3', 3.3, 300, 3000)
                                 "#;
-        let output = SqlQueryHandler::do_query(&*instance, sql).await.unwrap();
+        let output = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
         match output {
             Output::AffectedRows(rows) => assert_eq!(rows, 3),
             _ => unreachable!(),
         }
 
         let sql = "select * from demo";
-        let output = SqlQueryHandler::do_query(&*instance, sql).await.unwrap();
+        let output = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
         match output {
             Output::RecordBatches(recordbatches) => {
                 let pretty_print = recordbatches.pretty_print();
@@ -794,7 +1144,10 @@ mod tests {
         };
 
         let sql = "select * from demo where ts>cast(1000000000 as timestamp)"; // use nanoseconds as where condition
-        let output = SqlQueryHandler::do_query(&*instance, sql).await.unwrap();
+        let output = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
         match output {
             Output::RecordBatches(recordbatches) => {
                 let pretty_print = recordbatches.pretty_print();
@@ -813,6 +1166,424 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn test_execute_multiple_statements() {
+        let instance = tests::create_frontend_instance().await;
+
+        let sql = r#"CREATE TABLE demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, TIME INDEX (ts), PRIMARY KEY(ts, host)) engine=mito with(regions=1);
+                     INSERT INTO demo(host, cpu, ts) VALUES ('host1', 1.1, 1000), ('host2', 2.2, 2000);
+                     SELECT host, cpu FROM demo ORDER BY host;"#;
+        let mut outputs = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc()).await;
+        assert_eq!(3, outputs.len());
+        assert!(matches!(outputs.remove(0), Ok(Output::AffectedRows(1))));
+        assert!(matches!(outputs.remove(0), Ok(Output::AffectedRows(2))));
+        match outputs.remove(0).unwrap() {
+            Output::RecordBatches(recordbatches) => {
+                let pretty_print = recordbatches.pretty_print();
+                let pretty_print = pretty_print.lines().collect::<Vec<&str>>();
+                let expected = vec![
+                    "+-------+-----+",
+                    "| host  | cpu |",
+                    "+-------+-----+",
+                    "| host1 | 1.1 |",
+                    "| host2 | 2.2 |",
+                    "+-------+-----+",
+                ];
+                assert_eq!(pretty_print, expected);
+            }
+            _ => unreachable!(),
+        }
+
+        // stops at the first failed statement
+        let sql = "SELECT * FROM demo; SELECT * FROM not_exist; SELECT * FROM demo";
+        let outputs = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc()).await;
+        assert_eq!(2, outputs.len());
+        assert!(outputs[0].is_ok());
+        let err = outputs[1].as_ref().unwrap_err().to_string();
+        assert!(
+            err.contains("Failed to execute statement 2 of 3"),
+            "{}",
+            err
+        );
+    }
+
+    /// Creates table `demo` in a standalone instance, and serves the instance in MySQL protocol.
+    async fn start_mysql_server() -> (Box<dyn Server>, mysql_async::Conn) {
+        let instance = tests::create_frontend_instance().await;
+        let sql = r#"CREATE TABLE demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, TIME INDEX (ts), PRIMARY KEY(ts, host)) engine=mito with(regions=1)"#;
+        let output = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(1)));
+
+        let io_runtime = Arc::new(
+            RuntimeBuilder::default()
+                .worker_threads(2)
+                .thread_name("mysql-io-handlers")
+                .build()
+                .unwrap(),
+        );
+        let mysql_server =
+            MysqlServer::create_server(instance, io_runtime, None, ServerTls::default());
+        let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let server_addr = mysql_server.start(listening).await.unwrap();
+        let opts = mysql_async::OptsBuilder::default()
+            .ip_or_hostname("127.0.0.1")
+            .tcp_port(server_addr.port())
+            .prefer_socket(false)
+            .wait_timeout(Some(1000));
+        let connection = mysql_async::Conn::new(opts).await.unwrap();
+        (mysql_server, connection)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_execute_prepared_statement() {
+        let (mysql_server, mut connection) = start_mysql_server().await;
+
+        // The statements are executed by COM_STMT_EXECUTE on datanode in standalone mode.
+        let insert = connection
+            .prep("INSERT INTO demo(host, cpu, ts) VALUES (?, ?, ?)")
+            .await
+            .unwrap();
+        connection
+            .exec_drop(&insert, ("host1", 1.1, 1000))
+            .await
+            .unwrap();
+        connection
+            .exec_drop(&insert, ("it's host2", -2.2, 2000))
+            .await
+            .unwrap();
+        assert_eq!(1, connection.affected_rows());
+
+        let select = connection
+            .prep("SELECT host, cpu FROM demo WHERE cpu < ? ORDER BY host")
+            .await
+            .unwrap();
+        let result: Vec<(String, f64)> = connection.exec(&select, (2,)).await.unwrap();
+        assert_eq!(
+            vec![("host1".to_string(), 1.1), ("it's host2".to_string(), -2.2)],
+            result
+        );
+        let result: Vec<(String, f64)> = connection.exec(&select, (-1,)).await.unwrap();
+        assert_eq!(vec![("it's host2".to_string(), -2.2)], result);
+
+        connection.close(select).await.unwrap();
+        connection.close(insert).await.unwrap();
+        mysql_server.shutdown().await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mysql_affected_rows() {
+        let (mysql_server, mut connection) = start_mysql_server().await;
+
+        connection
+            .query_drop(
+                "INSERT INTO demo(host, cpu, ts) VALUES ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+            )
+            .await
+            .unwrap();
+        assert_eq!(2, connection.affected_rows());
+
+        // The affected rows of the statements are summed up.
+        connection
+            .query_drop(
+                "INSERT INTO demo(host, cpu, ts) VALUES ('host3', 3.3, 3000); \
+                 INSERT INTO demo(host, cpu, ts) VALUES ('host4', 4.4, 4000), ('host5', 5.5, 5000)",
+            )
+            .await
+            .unwrap();
+        assert_eq!(3, connection.affected_rows());
+
+        let mut result = connection
+            .query_iter(
+                "INSERT INTO demo(host, cpu, ts) VALUES ('host6', 6.6, 6000); \
+                 SELECT host FROM demo WHERE cpu > 5 ORDER BY host",
+            )
+            .await
+            .unwrap();
+        let first: Vec<String> = result.collect().await.unwrap();
+        assert!(first.is_empty());
+        let second: Vec<String> = result.collect().await.unwrap();
+        assert_eq!(vec!["host5".to_string(), "host6".to_string()], second);
+        drop(result);
+
+        mysql_server.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_privileges() {
+        let instance = tests::create_frontend_instance().await;
+
+        let sql = r#"CREATE TABLE demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, TIME INDEX (ts), PRIMARY KEY(ts, host)) engine=mito with(regions=1);
+                     CREATE USER root;"#;
+        let outputs = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc()).await;
+        assert_eq!(2, outputs.len());
+        assert!(outputs.iter().all(|o| o.is_ok()));
+
+        // Sessions without a user are denied once any user exists.
+        let err = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+        // The first user is granted all privileges.
+        let root_ctx = QueryContext::arc();
+        root_ctx.set_current_user(Some("root".to_string()));
+        let sql = "CREATE USER alice; GRANT SELECT ON public.demo TO alice;";
+        let outputs = SqlQueryHandler::do_query(&*instance, sql, root_ctx.clone()).await;
+        assert_eq!(2, outputs.len());
+        assert!(outputs.iter().all(|o| o.is_ok()));
+
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_user(Some("alice".to_string()));
+        let output = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", query_ctx.clone())
+            .await
+            .remove(0);
+        assert!(output.is_ok());
+
+        let sql = "INSERT INTO demo(host, cpu, ts) VALUES ('host1', 1.1, 1000)";
+        let err = SqlQueryHandler::do_query(&*instance, sql, query_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+        let err = SqlQueryHandler::do_query(&*instance, "CREATE USER bob", query_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+        let sql = "REVOKE SELECT ON public.demo FROM alice";
+        let output = SqlQueryHandler::do_query(&*instance, sql, root_ctx)
+            .await
+            .remove(0);
+        assert!(output.is_ok());
+        let err = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", query_ctx)
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+    }
+
+    #[tokio::test]
+    async fn test_check_privileges_of_roles() {
+        let instance = tests::create_frontend_instance().await;
+
+        let sql = r#"CREATE TABLE demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, TIME INDEX (ts), PRIMARY KEY(ts, host)) engine=mito with(regions=1);
+                     CREATE USER root;"#;
+        let outputs = SqlQueryHandler::do_query(&*instance, sql, QueryContext::arc()).await;
+        assert!(outputs.iter().all(|o| o.is_ok()));
+
+        let root_ctx = QueryContext::arc();
+        root_ctx.set_current_user(Some("root".to_string()));
+        let sql = "CREATE USER alice; \
+                   CREATE ROLE reader; \
+                   GRANT SELECT ON public.* TO reader; \
+                   GRANT reader TO alice;";
+        let outputs = SqlQueryHandler::do_query(&*instance, sql, root_ctx.clone()).await;
+        assert_eq!(4, outputs.len());
+        assert!(outputs.iter().all(|o| o.is_ok()));
+
+        // Privileges of the role are checked for its users.
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_user(Some("alice".to_string()));
+        let output = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", query_ctx.clone())
+            .await
+            .remove(0);
+        assert!(output.is_ok());
+        let sql = "INSERT INTO demo(host, cpu, ts) VALUES ('host1', 1.1, 1000)";
+        let err = SqlQueryHandler::do_query(&*instance, sql, query_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+        // Roles can't log in.
+        let role_ctx = QueryContext::arc();
+        role_ctx.set_current_user(Some("reader".to_string()));
+        let err = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", role_ctx)
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+        let err = SqlQueryHandler::do_query(&*instance, "GRANT unknown TO alice", root_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let output = SqlQueryHandler::do_query(&*instance, "REVOKE reader FROM alice", root_ctx)
+            .await
+            .remove(0);
+        assert!(output.is_ok());
+        let err = SqlQueryHandler::do_query(&*instance, "SELECT * FROM demo", query_ctx)
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::AccessDenied, err.status_code());
+    }
+
+    #[tokio::test]
+    async fn test_query_quota() {
+        let mut instance = (*tests::create_frontend_instance().await).clone();
+        instance.quota_manager = Arc::new(QuotaManager::new(QuotaOptions {
+            default: QuotaLimits {
+                max_result_rows: Some(2),
+                max_ingest_rows_per_sec: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        }));
+
+        let sql = r#"CREATE TABLE demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, TIME INDEX (ts), PRIMARY KEY(ts, host)) engine=mito with(regions=1);
+                     INSERT INTO demo(host, cpu, ts) VALUES ('host1', 1.1, 1000), ('host2', 2.2, 2000), ('host3', 3.3, 3000);"#;
+        let outputs = SqlQueryHandler::do_query(&instance, sql, QueryContext::arc()).await;
+        assert!(outputs.iter().all(|o| o.is_ok()));
+
+        let output =
+            SqlQueryHandler::do_query(&instance, "SELECT * FROM demo LIMIT 2", QueryContext::arc())
+                .await
+                .remove(0);
+        assert!(output.is_ok());
+
+        let err = SqlQueryHandler::do_query(&instance, "SELECT * FROM demo", QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+
+        // The limits of the session given by the user provider are enforced as well.
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_quota_limits(QuotaLimits {
+            max_result_rows: Some(1),
+            ..Default::default()
+        });
+        let err = SqlQueryHandler::do_query(&instance, "SELECT * FROM demo LIMIT 2", query_ctx)
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+    }
+
+    #[tokio::test]
+    async fn test_process_list() {
+        let instance = tests::create_frontend_instance().await;
+        let query_ctx = QueryContext::arc();
+
+        // The statement itself is listed while running.
+        let output = SqlQueryHandler::do_query(&*instance, "SHOW PROCESSLIST", query_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap();
+        match output {
+            Output::RecordBatches(batches) => {
+                let batches = batches.take();
+                assert_eq!(1, batches[0].num_rows());
+            }
+            _ => unreachable!(),
+        }
+
+        let output = SqlQueryHandler::do_query(
+            &*instance,
+            "SELECT id, query FROM information_schema.processes",
+            query_ctx.clone(),
+        )
+        .await
+        .remove(0)
+        .unwrap();
+        match output {
+            Output::Stream(stream) => {
+                let batches = util::collect(stream).await.unwrap();
+                assert_eq!(1, batches.iter().map(|b| b.num_rows()).sum::<usize>());
+            }
+            _ => unreachable!(),
+        }
+        assert!(instance.process_list.processes().is_empty());
+
+        let sql = format!("KILL QUERY {}", query_ctx.conn_id());
+        let err = SqlQueryHandler::do_query(&*instance, &sql, query_ctx.clone())
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Later statements of a killed connection are cancelled.
+        let other_ctx = QueryContext::arc();
+        let guard = instance.process_list.register(&other_ctx, "SELECT 1");
+        let sql = format!("KILL {}", other_ctx.conn_id());
+        let _ = SqlQueryHandler::do_query(&*instance, &sql, query_ctx)
+            .await
+            .remove(0)
+            .unwrap();
+        assert!(guard.process().is_cancelled());
+        let err = SqlQueryHandler::do_query(&*instance, "SELECT 1", other_ctx)
+            .await
+            .remove(0)
+            .unwrap_err();
+        assert_eq!(StatusCode::QueryCancelled, err.status_code());
+    }
+
+    #[derive(Default)]
+    struct RecordingScriptHandler {
+        scripts: std::sync::Mutex<HashMap<(String, String), String>>,
+    }
+
+    #[async_trait]
+    impl ScriptHandler for RecordingScriptHandler {
+        async fn insert_script(
+            &self,
+            schema: &str,
+            name: &str,
+            script: &str,
+        ) -> server_error::Result<()> {
+            let _ = self
+                .scripts
+                .lock()
+                .unwrap()
+                .insert((schema.to_string(), name.to_string()), script.to_string());
+            Ok(())
+        }
+
+        async fn execute_script(&self, schema: &str, name: &str) -> server_error::Result<Output> {
+            let scripts = self.scripts.lock().unwrap();
+            assert!(scripts.contains_key(&(schema.to_string(), name.to_string())));
+            Ok(Output::AffectedRows(1))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_script_in_schema() {
+        let mut instance = (*tests::create_frontend_instance().await).clone();
+        let handler = Arc::new(RecordingScriptHandler::default());
+        instance.set_script_handler(handler.clone());
+
+        ScriptHandler::insert_script(&instance, "my_db", "test", "script")
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(&"script".to_string()),
+            handler
+                .scripts
+                .lock()
+                .unwrap()
+                .get(&("my_db".to_string(), "test".to_string()))
+        );
+        assert!(!handler
+            .scripts
+            .lock()
+            .unwrap()
+            .contains_key(&(DEFAULT_SCHEMA_NAME.to_string(), "test".to_string())));
+
+        let output = ScriptHandler::execute_script(&instance, "my_db", "test")
+            .await
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(1)));
+    }
+
     #[tokio::test]
     async fn test_execute_grpc() {
         let instance = tests::create_frontend_instance().await;
@@ -840,6 +1611,7 @@ mod tests {
             null_mask: vec![2],
             semantic_type: SemanticType::Field as i32,
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
         let expected_mem_col = Column {
             column_name: "memory".to_string(),
@@ -850,6 +1622,7 @@ mod tests {
             null_mask: vec![4],
             semantic_type: SemanticType::Field as i32,
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
         let expected_disk_col = Column {
             column_name: "disk_util".to_string(),
@@ -878,9 +1651,10 @@ mod tests {
             header: Some(ExprHeader::default()),
             expr: Some(admin_expr::Expr::Create(create_expr)),
         };
-        let result = GrpcAdminHandler::exec_admin_request(&*instance, admin_expr)
-            .await
-            .unwrap();
+        let result =
+            GrpcAdminHandler::exec_admin_request(&*instance, admin_expr, QueryContext::arc())
+                .await
+                .unwrap();
         assert_matches!(
             result.result,
             Some(admin_result::Result::Mutate(MutateResult {
@@ -911,7 +1685,7 @@ mod tests {
             header: Some(ExprHeader::default()),
             expr: Some(object_expr::Expr::Insert(insert_expr)),
         };
-        let result = GrpcQueryHandler::do_query(&*instance, object_expr)
+        let result = GrpcQueryHandler::do_query(&*instance, object_expr, QueryContext::arc())
             .await
             .unwrap();
         assert_matches!(
@@ -929,7 +1703,7 @@ mod tests {
                 expr: Some(select_expr::Expr::Sql("select * from demo".to_string())),
             })),
         };
-        let result = GrpcQueryHandler::do_query(&*instance, object_expr)
+        let result = GrpcQueryHandler::do_query(&*instance, object_expr, QueryContext::arc())
             .await
             .unwrap();
         match result.result {
@@ -964,18 +1738,21 @@ mod tests {
                 datatype: ColumnDataType::String as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "cpu".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "memory".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "disk_util".to_string(),
@@ -986,12 +1763,14 @@ mod tests {
                         .try_into()
                         .unwrap(),
                 ),
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "ts".to_string(),
                 datatype: ColumnDataType::Timestamp as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ];
         CreateExpr {
@@ -32,8 +32,9 @@ use meta_client::rpc::{
 };
 use query::sql::{show_databases, show_tables};
 use query::{QueryEngineFactory, QueryEngineRef};
+use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use sql::statements::create::Partitions;
+use sql::statements::create::{PartitionMethod, Partitions};
 use sql::statements::sql_value_to_value;
 use sql::statements::statement::Statement;
 use sqlparser::ast::Value as SqlValue;
@@ -46,6 +47,7 @@ use crate::error::{
     PrimaryKeyNotFoundSnafu, RequestMetaSnafu, Result, StartMetaClientSnafu,
 };
 use crate::partitioning::{PartitionBound, PartitionDef};
+use crate::table::pushdown::push_down_aggregate;
 
 #[derive(Clone)]
 pub(crate) struct DistInstance {
@@ -70,6 +72,10 @@ impl DistInstance {
         }
     }
 
+    pub(crate) fn query_engine(&self) -> &QueryEngineRef {
+        &self.query_engine
+    }
+
     pub(crate) async fn create_table(
         &self,
         create_table: &mut CreateExpr,
@@ -125,22 +131,32 @@ impl DistInstance {
         Ok(Output::AffectedRows(region_routes.len()))
     }
 
-    pub(crate) async fn handle_sql(&self, sql: &str, stmt: Statement) -> Result<Output> {
+    pub(crate) async fn handle_sql(
+        &self,
+        sql: &str,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         match stmt {
-            Statement::Query(_) => {
+            Statement::Query(_) | Statement::Explain(_) => {
                 let plan = self
                     .query_engine
-                    .statement_to_plan(stmt)
+                    .statement_to_plan(stmt, query_ctx)
                     .context(error::ExecuteSqlSnafu { sql })?;
+                let plan = push_down_aggregate(plan);
                 self.query_engine
                     .execute(&plan)
                     .await
                     .context(error::ExecuteSqlSnafu { sql })
             }
-            Statement::ShowDatabases(stmt) => show_databases(stmt, self.catalog_manager.clone())
-                .context(error::ExecuteSqlSnafu { sql }),
-            Statement::ShowTables(stmt) => show_tables(stmt, self.catalog_manager.clone())
-                .context(error::ExecuteSqlSnafu { sql }),
+            Statement::ShowDatabases(stmt) => {
+                show_databases(stmt, self.catalog_manager.clone(), query_ctx)
+                    .context(error::ExecuteSqlSnafu { sql })
+            }
+            Statement::ShowTables(stmt) => {
+                show_tables(stmt, self.catalog_manager.clone(), query_ctx)
+                    .context(error::ExecuteSqlSnafu { sql })
+            }
             _ => unreachable!(),
         }
     }
@@ -285,7 +301,9 @@ fn create_table_global_value(
 // Remove this duplication in the future
 fn create_column_schema(column_def: &api::v1::ColumnDef) -> Result<ColumnSchema> {
     let data_type =
-        ColumnDataTypeWrapper::try_new(column_def.datatype).context(error::ColumnDataTypeSnafu)?;
+        ColumnDataTypeWrapper::try_new(column_def.datatype, column_def.datatype_extension.clone())
+            .and_then(ConcreteDataType::try_from)
+            .context(error::ColumnDataTypeSnafu)?;
     let default_constraint = match &column_def.default_constraint {
         None => None,
         Some(v) => Some(ColumnDefaultConstraint::try_from(&v[..]).context(
@@ -294,15 +312,11 @@ fn create_column_schema(column_def: &api::v1::ColumnDef) -> Result<ColumnSchema>
             },
         )?),
     };
-    ColumnSchema::new(
-        column_def.name.clone(),
-        data_type.into(),
-        column_def.is_nullable,
-    )
-    .with_default_constraint(default_constraint)
-    .context(ConvertColumnDefaultConstraintSnafu {
-        column_name: &column_def.name,
-    })
+    ColumnSchema::new(column_def.name.clone(), data_type, column_def.is_nullable)
+        .with_default_constraint(default_constraint)
+        .context(ConvertColumnDefaultConstraintSnafu {
+            column_name: &column_def.name,
+        })
 }
 
 fn parse_partitions(
@@ -326,6 +340,17 @@ fn find_partition_entries(
     partition_columns: &[String],
 ) -> Result<Vec<Vec<PartitionBound>>> {
     let entries = if let Some(partitions) = partitions {
+        if let PartitionMethod::Hash { partition_num } = partitions.method {
+            return Ok((0..partition_num)
+                .map(|index| {
+                    vec![PartitionBound::Hash {
+                        index,
+                        num: partition_num,
+                    }]
+                })
+                .collect());
+        }
+
         let column_defs = partition_columns
             .iter()
             .map(|pc| {
@@ -340,9 +365,10 @@ fn find_partition_entries(
         let mut column_name_and_type = Vec::with_capacity(column_defs.len());
         for column in column_defs {
             let column_name = &column.name;
-            let data_type = ConcreteDataType::from(
-                ColumnDataTypeWrapper::try_new(column.datatype).context(ColumnDataTypeSnafu)?,
-            );
+            let data_type =
+                ColumnDataTypeWrapper::try_new(column.datatype, column.datatype_extension.clone())
+                    .and_then(ConcreteDataType::try_from)
+                    .context(ColumnDataTypeSnafu)?;
             column_name_and_type.push((column_name, data_type));
         }
 
@@ -388,6 +414,7 @@ fn find_partition_columns(
 
 #[cfg(test)]
 mod test {
+    use session::context::QueryContext;
     use sql::parser::ParserContext;
     use sql::statements::statement::Statement;
     use sqlparser::dialect::GenericDialect;
@@ -421,6 +448,13 @@ PARTITION BY RANGE COLUMNS (b, a) (
 ENGINE=mito",
                 r#"[{"column_list":"b,a","value_list":"{\"Value\":{\"String\":\"hz\"}},{\"Value\":{\"Int32\":10}}"},{"column_list":"b,a","value_list":"{\"Value\":{\"String\":\"sh\"}},{\"Value\":{\"Int32\":20}}"},{"column_list":"b,a","value_list":"\"MaxValue\",\"MaxValue\""}]"#,
             ),
+            (
+                r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b, a) PARTITIONS 2
+ENGINE=mito",
+                r#"[{"column_list":"b,a","value_list":"{\"Hash\":{\"index\":0,\"num\":2}}"},{"column_list":"b,a","value_list":"{\"Hash\":{\"index\":1,\"num\":2}}"}]"#,
+            ),
         ];
         for (sql, expected) in cases {
             let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
@@ -428,7 +462,10 @@ ENGINE=mito",
                 Statement::CreateTable(c) => {
                     common_telemetry::info!("{}", sql);
                     let factory = DefaultCreateExprFactory {};
-                    let expr = factory.create_expr_by_stmt(c).await.unwrap();
+                    let expr = factory
+                        .create_expr_by_stmt(c, QueryContext::arc())
+                        .await
+                        .unwrap();
                     let partitions = parse_partitions(&expr, c.partitions.clone()).unwrap();
                     let json = serde_json::to_string(&partitions).unwrap();
                     assert_eq!(json, expected);
@@ -24,49 +24,73 @@ use common_insert::column_to_vector;
 use servers::error as server_error;
 use servers::influxdb::InfluxdbRequest;
 use servers::query_handler::InfluxdbLineProtocolHandler;
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 use table::requests::InsertRequest;
 
 use crate::error;
 use crate::error::{DeserializeInsertBatchSnafu, InsertBatchToRequestSnafu, Result};
 use crate::frontend::Mode;
+use crate::instance::process::describe_inserts;
 use crate::instance::Instance;
 
 #[async_trait]
 impl InfluxdbLineProtocolHandler for Instance {
-    async fn exec(&self, request: &InfluxdbRequest) -> servers::error::Result<()> {
-        match self.mode {
-            Mode::Standalone => {
-                let exprs: Vec<InsertExpr> = request.try_into()?;
-                self.handle_inserts(&exprs)
+    async fn exec(
+        &self,
+        request: &InfluxdbRequest,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<()> {
+        let exprs: Vec<InsertExpr> = request.try_into()?;
+        self.check_insert_privileges(&exprs, &query_ctx)
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteQuerySnafu {
+                query: &request.lines,
+            })?;
+        self.throttle_inserts(&exprs, &query_ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteQuerySnafu {
+                query: &request.lines,
+            })?;
+
+        let query = describe_inserts("InfluxDB line protocol", &exprs);
+        self.run_as_process(&query_ctx, &query, async {
+            match self.mode {
+                Mode::Standalone => self
+                    .handle_inserts(&exprs)
                     .await
+                    .map(|_| ())
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteQuerySnafu {
                         query: &request.lines,
-                    })?;
-            }
-            Mode::Distributed => {
-                self.dist_insert(request.try_into()?)
+                    }),
+                Mode::Distributed => self
+                    .dist_insert(&exprs)
                     .await
+                    .map(|_| ())
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteInsertSnafu {
                         msg: "execute insert failed",
-                    })?;
+                    }),
             }
-        }
-
-        Ok(())
+        })
+        .await
+        .map_err(BoxedError::new)
+        .context(server_error::ExecuteQuerySnafu {
+            query: &request.lines,
+        })?
     }
 }
 
 impl Instance {
-    pub(crate) async fn dist_insert(&self, inserts: Vec<InsertExpr>) -> Result<usize> {
+    pub(crate) async fn dist_insert(&self, inserts: &[InsertExpr]) -> Result<usize> {
         let mut joins = Vec::with_capacity(inserts.len());
         let catalog_name = DEFAULT_CATALOG_NAME.to_string();
 
         for insert in inserts {
             let self_clone = self.clone();
-            let insert_batches = match &insert.expr.unwrap() {
+            let insert_batches = match insert.expr.as_ref().unwrap() {
                 Expr::Values(values) => common_insert::insert_batches(&values.values)
                     .context(DeserializeInsertBatchSnafu)?,
                 Expr::Sql(_) => unreachable!(),
@@ -105,7 +129,17 @@ impl Instance {
                             table_name: &table_name,
                         })?;
 
-                    table.insert(request).await.context(error::TableSnafu)
+                    let columns_values = request.columns_values.clone();
+                    let affected_rows = table.insert(request).await.context(error::TableSnafu)?;
+                    self_clone
+                        .insert_into_materialized_views(
+                            &catalog_name,
+                            &schema_name,
+                            &table_name,
+                            &columns_values,
+                        )
+                        .await?;
+                    Ok::<_, error::Error>(affected_rows)
                 });
                 joins.push(join);
             }
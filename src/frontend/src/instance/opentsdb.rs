@@ -12,49 +12,76 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use api::v1::InsertExpr;
 use async_trait::async_trait;
 use common_error::prelude::BoxedError;
 use servers::error as server_error;
-use servers::opentsdb::codec::DataPoint;
+use servers::opentsdb::codec::{data_points_to_grpc_inserts, DataPoint};
 use servers::query_handler::OpentsdbProtocolHandler;
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 
 use crate::error::Result;
 use crate::frontend::Mode;
+use crate::instance::process::describe_inserts;
 use crate::instance::Instance;
 
 #[async_trait]
 impl OpentsdbProtocolHandler for Instance {
-    async fn exec(&self, data_point: &DataPoint) -> server_error::Result<()> {
-        // TODO(LFC): Insert metrics in batch, then make OpentsdbLineProtocolHandler::exec received multiple data points, when
-        // metric table and tags can be created upon insertion.
-        match self.mode {
-            Mode::Standalone => {
-                self.insert_opentsdb_metric(data_point)
+    async fn exec(
+        &self,
+        data_points: &[DataPoint],
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<()> {
+        let inserts = data_points_to_grpc_inserts(data_points)?;
+        let metrics = || {
+            inserts
+                .iter()
+                .map(|insert| insert.table_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        self.check_insert_privileges(&inserts, &query_ctx)
+            .map_err(BoxedError::new)
+            .with_context(|_| server_error::PutOpentsdbDataPointsSnafu { metrics: metrics() })?;
+        self.throttle_inserts(&inserts, &query_ctx)
+            .await
+            .map_err(BoxedError::new)
+            .with_context(|_| server_error::PutOpentsdbDataPointsSnafu { metrics: metrics() })?;
+
+        let query = describe_inserts("OpenTSDB", &inserts);
+        self.run_as_process(&query_ctx, &query, async {
+            match self.mode {
+                Mode::Standalone => self
+                    .insert_opentsdb_metrics(&inserts)
                     .await
                     .map_err(BoxedError::new)
-                    .with_context(|_| server_error::PutOpentsdbDataPointSnafu {
-                        data_point: format!("{:?}", data_point),
-                    })?;
-            }
-            Mode::Distributed => {
-                self.dist_insert(vec![data_point.as_grpc_insert()])
+                    .with_context(|_| server_error::PutOpentsdbDataPointsSnafu {
+                        metrics: metrics(),
+                    }),
+                Mode::Distributed => self
+                    .dist_insert(&inserts)
                     .await
+                    .map(|_| ())
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteInsertSnafu {
                         msg: "execute insert failed",
-                    })?;
+                    }),
             }
-        }
-
-        Ok(())
+        })
+        .await
+        .map_err(BoxedError::new)
+        .with_context(|_| server_error::PutOpentsdbDataPointsSnafu { metrics: metrics() })?
     }
 }
 
 impl Instance {
-    async fn insert_opentsdb_metric(&self, data_point: &DataPoint) -> Result<()> {
-        let expr = data_point.as_grpc_insert();
-        self.handle_insert(&expr).await?;
+    /// Inserts the metrics one by one, the table and columns of a metric are created upon
+    /// insertion.
+    async fn insert_opentsdb_metrics(&self, inserts: &[InsertExpr]) -> Result<()> {
+        for expr in inserts {
+            self.handle_insert(expr).await?;
+        }
         Ok(())
     }
 }
@@ -64,6 +91,7 @@ mod tests {
     use common_query::Output;
     use datafusion::arrow_print;
     use servers::query_handler::SqlQueryHandler;
+    use session::context::QueryContext;
 
     use super::*;
     use crate::tests;
@@ -73,19 +101,68 @@ mod tests {
         let instance = tests::create_frontend_instance().await;
         instance
             .exec(
-                &DataPoint::try_create(
+                &[DataPoint::try_create(
                     "put sys.if.bytes.out 1479496100 1.3E3 host=web01 interface=eth0",
                 )
-                .unwrap(),
+                .unwrap()],
+                QueryContext::arc(),
             )
             .await
             .unwrap();
         instance
-            .exec(&DataPoint::try_create("put sys.procs.running 1479496100 42 host=web01").unwrap())
+            .exec(
+                &[
+                    DataPoint::try_create("put sys.procs.running 1479496100 42 host=web01")
+                        .unwrap(),
+                ],
+                QueryContext::arc(),
+            )
             .await
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_exec_batch() {
+        let instance = tests::create_frontend_instance().await;
+        let data_points = [
+            "put my_metric_2 1000 1 host=web01",
+            "put my_metric_3 1000 10 host=web01",
+            "put my_metric_2 2000 2 host=web02 interface=eth0",
+        ]
+        .into_iter()
+        .map(|line| DataPoint::try_create(line).unwrap())
+        .collect::<Vec<_>>();
+        instance
+            .exec(&data_points, QueryContext::arc())
+            .await
+            .unwrap();
+
+        let output = instance
+            .do_query(
+                "select greptime_value, host, interface from my_metric_2",
+                QueryContext::arc(),
+            )
+            .await
+            .remove(0)
+            .unwrap();
+        match output {
+            Output::RecordBatches(recordbatches) => {
+                let pretty_print = recordbatches.pretty_print();
+                let pretty_print = pretty_print.lines().collect::<Vec<&str>>();
+                let expected = vec![
+                    "+----------------+-------+-----------+",
+                    "| greptime_value | host  | interface |",
+                    "+----------------+-------+-----------+",
+                    "| 1              | web01 |           |",
+                    "| 2              | web02 | eth0      |",
+                    "+----------------+-------+-----------+",
+                ];
+                assert_eq!(pretty_print, expected);
+            }
+            _ => unreachable!(),
+        };
+    }
+
     #[tokio::test]
     async fn test_insert_opentsdb_metric() {
         let instance = tests::create_frontend_instance().await;
@@ -100,7 +177,9 @@ mod tests {
             ],
         );
         // should create new table "my_metric_1" directly
-        let result = instance.insert_opentsdb_metric(&data_point1).await;
+        let result = instance
+            .insert_opentsdb_metrics(&data_points_to_grpc_inserts(&[data_point1]).unwrap())
+            .await;
         assert!(result.is_ok());
 
         let data_point2 = DataPoint::new(
@@ -113,17 +192,22 @@ mod tests {
             ],
         );
         // should create new column "tagk3" directly
-        let result = instance.insert_opentsdb_metric(&data_point2).await;
+        let result = instance
+            .insert_opentsdb_metrics(&data_points_to_grpc_inserts(&[data_point2]).unwrap())
+            .await;
         assert!(result.is_ok());
 
         let data_point3 = DataPoint::new("my_metric_1".to_string(), 3000, 3.0, vec![]);
         // should handle null tags properly
-        let result = instance.insert_opentsdb_metric(&data_point3).await;
+        let result = instance
+            .insert_opentsdb_metrics(&data_points_to_grpc_inserts(&[data_point3]).unwrap())
+            .await;
         assert!(result.is_ok());
 
         let output = instance
-            .do_query("select * from my_metric_1")
+            .do_query("select * from my_metric_1", QueryContext::arc())
             .await
+            .remove(0)
             .unwrap();
         match output {
             Output::RecordBatches(recordbatches) => {
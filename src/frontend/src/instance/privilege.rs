@@ -0,0 +1,393 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Access control of users, checked before statements and requests from all protocols are
+//! planned or executed.
+
+use api::v1::object_expr::Expr;
+use api::v1::{admin_expr, select_expr, AdminExpr, InsertExpr, ObjectExpr};
+use catalog::privilege::{
+    CreateRoleRequest, CreateUserRequest, GrantObject, GrantRequest, GrantRoleRequest, Privilege,
+    PrivilegeManagerRef,
+};
+use catalog::CatalogManagerRef;
+use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+use common_query::Output;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{OptionExt, ResultExt};
+use sql::ast::ObjectName;
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
+use sql::statements::privilege::{
+    CreateRole, CreateUser, Grant, GrantObject as SqlGrantObject, GrantRole,
+    Privilege as SqlPrivilege, Revoke, RevokeRole,
+};
+use sql::statements::query::Query;
+use sql::statements::statement::Statement;
+use sql::statements::table_idents_to_full_name;
+
+use crate::error::{self, Result};
+use crate::instance::Instance;
+use crate::process::is_processes_table;
+
+impl Instance {
+    fn privilege_manager(&self) -> Option<PrivilegeManagerRef> {
+        self.catalog_manager
+            .as_ref()
+            .and_then(|catalog_manager| catalog_manager.privilege_manager())
+    }
+
+    /// Checks the `privilege` of the session user on the `object`. Sessions without a user, whose
+    /// protocol doesn't authenticate users, are denied once access control is enabled.
+    fn check_privilege(
+        &self,
+        object: &GrantObject,
+        privilege: Privilege,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let manager = match self.privilege_manager() {
+            Some(manager) => manager,
+            None => return Ok(()),
+        };
+        match query_ctx.current_user() {
+            Some(username) => manager.check(&username, object, privilege),
+            None => manager.check_unauthenticated(object, privilege),
+        }
+        .context(error::CatalogSnafu)
+    }
+
+    fn check_table_privilege(
+        &self,
+        table_name: &ObjectName,
+        privilege: Privilege,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let (catalog, schema, table) =
+            table_idents_to_full_name(table_name, query_ctx).context(error::ParseSqlSnafu)?;
+        self.check_privilege(
+            &GrantObject::table(catalog, schema, table),
+            privilege,
+            query_ctx,
+        )
+    }
+
+    fn check_query_privilege(&self, query: &Query, query_ctx: &QueryContextRef) -> Result<()> {
+        // Processes are filtered by their users when listed.
+        for table_name in query.table_names() {
+            if is_processes_table(&table_name, query_ctx) {
+                continue;
+            }
+            self.check_table_privilege(&table_name, Privilege::Select, query_ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Administrative statements, like creating databases and users or granting privileges,
+    /// require all privileges on the current catalog.
+    pub(crate) fn check_admin_privilege(&self, query_ctx: &QueryContextRef) -> Result<()> {
+        let object = GrantObject::catalog(query_ctx.current_catalog());
+        for privilege in Privilege::all() {
+            self.check_privilege(&object, privilege, query_ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the session user has the privileges required by the statement.
+    pub(crate) fn check_statement_privileges(
+        &self,
+        stmt: &Statement,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        match stmt {
+            Statement::Query(query) => self.check_query_privilege(query, query_ctx),
+            Statement::Explain(explain) => self.check_query_privilege(&explain.query, query_ctx),
+            Statement::Insert(insert) => {
+                let (catalog, schema, table) = insert
+                    .full_table_name(query_ctx)
+                    .context(error::ParseSqlSnafu)?;
+                self.check_privilege(
+                    &GrantObject::table(catalog, schema, table),
+                    Privilege::Insert,
+                    query_ctx,
+                )
+            }
+            Statement::Alter(alter) => {
+                self.check_table_privilege(alter.table_name(), Privilege::Alter, query_ctx)
+            }
+            Statement::CreateTable(create) => {
+                let (catalog, schema, _) = table_idents_to_full_name(&create.name, query_ctx)
+                    .context(error::ParseSqlSnafu)?;
+                self.check_privilege(
+                    &GrantObject::schema(catalog, schema),
+                    Privilege::Alter,
+                    query_ctx,
+                )
+            }
+            Statement::CreateMaterializedView(create) => {
+                let (catalog, schema, _) = table_idents_to_full_name(&create.name, query_ctx)
+                    .context(error::ParseSqlSnafu)?;
+                // The source table is looked up in the database of the view.
+                let view_ctx = QueryContext::with(&catalog, &schema);
+                for table_name in create.query.table_names() {
+                    let (catalog, schema, table) =
+                        table_idents_to_full_name(&table_name, &view_ctx)
+                            .context(error::ParseSqlSnafu)?;
+                    self.check_privilege(
+                        &GrantObject::table(catalog, schema, table),
+                        Privilege::Select,
+                        query_ctx,
+                    )?;
+                }
+                self.check_privilege(
+                    &GrantObject::schema(catalog, schema),
+                    Privilege::Alter,
+                    query_ctx,
+                )
+            }
+            Statement::CreateDatabase(_)
+            | Statement::CreateUser(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)
+            | Statement::CreateRole(_)
+            | Statement::GrantRole(_)
+            | Statement::RevokeRole(_) => self.check_admin_privilege(query_ctx),
+            Statement::ShowDatabases(_)
+            | Statement::ShowTables(_)
+            | Statement::ShowCreateTable(_)
+            | Statement::Use(_)
+            | Statement::SetVariables(_) => Ok(()),
+            // Processes of other users are checked when they are listed or killed.
+            Statement::ShowProcesslist(_) | Statement::Kill(_) => Ok(()),
+        }
+    }
+
+    /// Checks the INSERT privilege on the tables written by the insert exprs of line protocols.
+    pub(crate) fn check_insert_privileges(
+        &self,
+        exprs: &[InsertExpr],
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let catalog = query_ctx.current_catalog();
+        for expr in exprs {
+            let object = GrantObject::table(&catalog, &expr.schema_name, &expr.table_name);
+            self.check_privilege(&object, Privilege::Insert, query_ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the privileges required by an admin request of gRPC.
+    pub(crate) fn check_admin_expr_privileges(
+        &self,
+        expr: &AdminExpr,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let catalog = query_ctx.current_catalog();
+        match &expr.expr {
+            Some(admin_expr::Expr::Create(create)) => {
+                let object = GrantObject::schema(
+                    create.catalog_name.as_deref().unwrap_or(&catalog),
+                    create.schema_name.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+                );
+                self.check_privilege(&object, Privilege::Alter, query_ctx)
+            }
+            Some(admin_expr::Expr::Alter(alter)) => {
+                let object = GrantObject::table(
+                    alter.catalog_name.as_deref().unwrap_or(&catalog),
+                    alter.schema_name.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+                    &alter.table_name,
+                );
+                self.check_privilege(&object, Privilege::Alter, query_ctx)
+            }
+            Some(admin_expr::Expr::CreateDatabase(_)) => self.check_admin_privilege(query_ctx),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the privileges required by an object request of gRPC, other than inserts which are
+    /// checked by [Self::check_insert_privileges].
+    pub(crate) fn check_object_expr_privileges(
+        &self,
+        expr: &ObjectExpr,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        match &expr.expr {
+            Some(Expr::Insert(insert)) => {
+                self.check_insert_privileges(std::slice::from_ref(insert), query_ctx)
+            }
+            Some(Expr::Select(select)) => match &select.expr {
+                Some(select_expr::Expr::Sql(sql)) => {
+                    let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {})
+                        .context(error::ParseSqlSnafu)?;
+                    for stmt in &stmts {
+                        self.check_statement_privileges(stmt, query_ctx)?;
+                    }
+                    Ok(())
+                }
+                // The tables read by plans are not inspected, so they require all privileges.
+                Some(select_expr::Expr::LogicalPlan(_))
+                | Some(select_expr::Expr::PhysicalPlan(_))
+                | Some(select_expr::Expr::DataVersions(_))
+                | None => self.check_admin_privilege(query_ctx),
+            },
+            Some(Expr::Update(_)) | Some(Expr::Delete(_)) | None => {
+                self.check_admin_privilege(query_ctx)
+            }
+        }
+    }
+
+    /// Checks the SELECT privilege on a table read by protocols other than SQL.
+    pub(crate) fn check_select_privilege(
+        &self,
+        schema: &str,
+        table: &str,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let object = GrantObject::table(query_ctx.current_catalog(), schema, table);
+        self.check_privilege(&object, Privilege::Select, query_ctx)
+    }
+
+    fn catalog_manager_for_privileges(&self) -> Result<&CatalogManagerRef> {
+        self.catalog_manager
+            .as_ref()
+            .context(error::NotSupportedSnafu {
+                feat: "access control without catalog manager",
+            })
+    }
+
+    /// Creates a user. The first user is granted all privileges on the current catalog, since
+    /// sessions without a user are denied since then.
+    pub(crate) async fn handle_create_user(
+        &self,
+        create_user: CreateUser,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Output> {
+        let catalog_manager = self.catalog_manager_for_privileges()?;
+        let is_first = self
+            .privilege_manager()
+            .map_or(false, |manager| !manager.has_users());
+        let username = create_user.name;
+        let created = catalog_manager
+            .create_user(CreateUserRequest {
+                username: username.clone(),
+                if_not_exists: create_user.if_not_exists,
+            })
+            .await
+            .context(error::CatalogSnafu)?;
+        if created && is_first {
+            catalog_manager
+                .grant(GrantRequest {
+                    username,
+                    object: GrantObject::catalog(query_ctx.current_catalog()),
+                    privileges: Privilege::all(),
+                })
+                .await
+                .context(error::CatalogSnafu)?;
+        }
+        Ok(Output::AffectedRows(created as usize))
+    }
+
+    pub(crate) async fn handle_grant(
+        &self,
+        grant: Grant,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Output> {
+        let request = to_grant_request(grant.privileges, grant.object, grant.user, query_ctx)?;
+        self.catalog_manager_for_privileges()?
+            .grant(request)
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(crate) async fn handle_revoke(
+        &self,
+        revoke: Revoke,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Output> {
+        let request = to_grant_request(revoke.privileges, revoke.object, revoke.user, query_ctx)?;
+        self.catalog_manager_for_privileges()?
+            .revoke(request)
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(crate) async fn handle_create_role(&self, create_role: CreateRole) -> Result<Output> {
+        let created = self
+            .catalog_manager_for_privileges()?
+            .create_role(CreateRoleRequest {
+                role: create_role.name,
+                if_not_exists: create_role.if_not_exists,
+            })
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(Output::AffectedRows(created as usize))
+    }
+
+    pub(crate) async fn handle_grant_role(&self, grant: GrantRole) -> Result<Output> {
+        self.catalog_manager_for_privileges()?
+            .grant_roles(GrantRoleRequest {
+                username: grant.user,
+                roles: grant.roles,
+            })
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(crate) async fn handle_revoke_role(&self, revoke: RevokeRole) -> Result<Output> {
+        self.catalog_manager_for_privileges()?
+            .revoke_roles(GrantRoleRequest {
+                username: revoke.user,
+                roles: revoke.roles,
+            })
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+}
+
+fn to_grant_request(
+    privileges: Vec<SqlPrivilege>,
+    object: SqlGrantObject,
+    username: String,
+    query_ctx: &QueryContextRef,
+) -> Result<GrantRequest> {
+    let catalog = query_ctx.current_catalog();
+    let object = match object {
+        SqlGrantObject::AllDatabases => GrantObject::catalog(catalog),
+        SqlGrantObject::Database(database) => GrantObject::schema(
+            catalog,
+            database.unwrap_or_else(|| query_ctx.current_schema()),
+        ),
+        SqlGrantObject::Table(table_name) => {
+            let (catalog, schema, table) =
+                table_idents_to_full_name(&table_name, query_ctx).context(error::ParseSqlSnafu)?;
+            GrantObject::table(catalog, schema, table)
+        }
+    };
+    let privileges = privileges
+        .into_iter()
+        .map(|privilege| match privilege {
+            SqlPrivilege::Select => Privilege::Select,
+            SqlPrivilege::Insert => Privilege::Insert,
+            SqlPrivilege::Alter => Privilege::Alter,
+        })
+        .collect();
+    Ok(GrantRequest {
+        username,
+        object,
+        privileges,
+    })
+}
@@ -0,0 +1,147 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `SHOW PROCESSLIST`, `information_schema.processes` and `KILL`. Users without admin
+//! privileges can only see and kill their own processes.
+
+use api::v1::InsertExpr;
+use common_query::Output;
+use futures::Future;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::ResultExt;
+use sql::statements::kill::Kill;
+use sql::statements::show::ShowProcesslist;
+use sql::statements::statement::Statement;
+
+use crate::error::{self, Result};
+use crate::instance::Instance;
+use crate::process::{is_processes_table, processes_query_engine, KillResult};
+
+/// Describes the inserts of a protocol as the query of its process.
+pub(crate) fn describe_inserts(protocol: &str, inserts: &[InsertExpr]) -> String {
+    let tables = inserts
+        .iter()
+        .map(|insert| insert.table_name.as_str())
+        .collect::<Vec<_>>();
+    format!("{} write into {}", protocol, tables.join(", "))
+}
+
+impl Instance {
+    /// Returns the user whose processes are visible to the session, or `None` if all are.
+    fn visible_processes_user(&self, query_ctx: &QueryContextRef) -> Option<String> {
+        if self.check_admin_privilege(query_ctx).is_ok() {
+            None
+        } else {
+            query_ctx.current_user()
+        }
+    }
+
+    /// Returns whether the statement is a query of `information_schema.processes`.
+    pub(crate) fn reads_processes(&self, stmt: &Statement, query_ctx: &QueryContextRef) -> bool {
+        match stmt {
+            Statement::Query(query) => query
+                .table_names()
+                .iter()
+                .any(|table_name| is_processes_table(table_name, query_ctx)),
+            _ => false,
+        }
+    }
+
+    /// Runs the query of `information_schema.processes` in the frontend, where the process list
+    /// lives.
+    pub(crate) async fn query_processes(
+        &self,
+        stmt: Statement,
+        sql: &str,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let engine = processes_query_engine(
+            self.process_list.clone(),
+            self.visible_processes_user(&query_ctx),
+        )?;
+        let plan = engine
+            .statement_to_plan(stmt, query_ctx)
+            .context(error::ExecuteSqlSnafu { sql })?;
+        engine
+            .execute(&plan)
+            .await
+            .context(error::ExecuteSqlSnafu { sql })
+    }
+
+    pub(crate) fn show_processlist(
+        &self,
+        show: ShowProcesslist,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Output> {
+        let user = self.visible_processes_user(query_ctx);
+        let batches = self
+            .process_list
+            .to_record_batches(user.as_deref(), show.full)?;
+        Ok(Output::RecordBatches(batches))
+    }
+
+    /// Runs a request of protocols other than SQL as a process described by `query`, the
+    /// request is dropped and fails once killed.
+    pub(crate) async fn run_as_process<F: Future>(
+        &self,
+        query_ctx: &QueryContext,
+        query: &str,
+        request: F,
+    ) -> Result<F::Output> {
+        let guard = self.process_list.register(query_ctx, query);
+        tokio::select! {
+            biased;
+            _ = guard.cancelled() => Err(guard.cancelled_error()),
+            output = request => Ok(output),
+        }
+    }
+
+    /// Cancels the running statements of the connection, they fail with an error once killed.
+    /// Connections of other frontends are killed through metasrv.
+    pub(crate) async fn kill(&self, kill: Kill, query_ctx: &QueryContextRef) -> Result<Output> {
+        let processes = self.process_list.find(kill.id);
+        if processes.is_empty() {
+            return self.kill_remote(kill, query_ctx).await;
+        }
+
+        let user = query_ctx.current_user();
+        if processes
+            .iter()
+            .any(|process| process.user() != user.as_deref())
+        {
+            self.check_admin_privilege(query_ctx)?;
+        }
+        let _ = self.process_list.kill(kill.id, kill.connection);
+        Ok(Output::AffectedRows(0))
+    }
+
+    async fn kill_remote(&self, kill: Kill, query_ctx: &QueryContextRef) -> Result<Output> {
+        let result = match &self.remote_process_list {
+            Some(remote) => {
+                let admin = self.check_admin_privilege(query_ctx).is_ok();
+                remote.kill(&kill, query_ctx.current_user(), admin).await?
+            }
+            None => KillResult::NotFound,
+        };
+        match result {
+            KillResult::Killed => Ok(Output::AffectedRows(0)),
+            KillResult::AccessDenied => {
+                self.check_admin_privilege(query_ctx)?;
+                // Granted admin privileges meanwhile, but the processes are not killed.
+                error::ProcessNotFoundSnafu { id: kill.id }.fail()
+            }
+            KillResult::NotFound => error::ProcessNotFoundSnafu { id: kill.id }.fail(),
+        }
+    }
+}
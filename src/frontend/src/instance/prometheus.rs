@@ -22,9 +22,11 @@ use prost::Message;
 use servers::error::{self, Result as ServerResult};
 use servers::prometheus::{self, Metrics};
 use servers::query_handler::{PrometheusProtocolHandler, PrometheusResponse};
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 
 use crate::frontend::Mode;
+use crate::instance::process::describe_inserts;
 use crate::instance::Instance;
 
 const SAMPLES_RESPONSE_TYPE: i32 = ResponseType::Samples as i32;
@@ -74,13 +76,19 @@ fn object_result_to_query_result(
 }
 
 async fn handle_remote_queries(
+    instance: &Instance,
     db: &Database,
     queries: &[Query],
+    query_ctx: &QueryContextRef,
 ) -> ServerResult<Vec<(String, ObjectResult)>> {
     let mut results = Vec::with_capacity(queries.len());
 
     for q in queries {
         let (table_name, sql) = prometheus::query_to_sql(db.name(), q)?;
+        instance
+            .check_select_privilege(db.name(), &table_name, query_ctx)
+            .map_err(BoxedError::new)
+            .context(error::ExecuteQuerySnafu { query: &sql })?;
 
         logging::debug!(
             "prometheus remote read, table: {}, sql: {}",
@@ -88,8 +96,21 @@ async fn handle_remote_queries(
             sql
         );
 
-        let object_result = db
-            .select(Select::Sql(sql.clone()))
+        let permit = instance
+            .quota_manager
+            .acquire(
+                query_ctx.current_user().as_deref(),
+                &query_ctx.quota_limits(),
+                db.name(),
+            )
+            .map_err(BoxedError::new)
+            .context(error::ExecuteQuerySnafu { query: &sql })?;
+        let object_result = permit
+            .run_request(async {
+                db.select(Select::Sql(sql.clone()))
+                    .await
+                    .context(crate::error::RequestDatanodeSnafu)
+            })
             .await
             .map_err(BoxedError::new)
             .context(error::ExecuteQuerySnafu { query: sql })?;
@@ -102,43 +123,86 @@ async fn handle_remote_queries(
 
 #[async_trait]
 impl PrometheusProtocolHandler for Instance {
-    async fn write(&self, database: &str, request: WriteRequest) -> ServerResult<()> {
-        match self.mode {
-            Mode::Standalone => {
-                let exprs = prometheus::write_request_to_insert_exprs(database, request)?;
-                let futures = exprs
-                    .iter()
-                    .map(|e| self.handle_insert(e))
-                    .collect::<Vec<_>>();
-                let res = futures_util::future::join_all(futures)
-                    .await
-                    .into_iter()
-                    .collect::<Result<Vec<_>, crate::error::Error>>();
-                res.map_err(BoxedError::new)
-                    .context(error::ExecuteInsertSnafu {
-                        msg: "failed to write prometheus remote request",
-                    })?;
-            }
-            Mode::Distributed => {
-                let inserts = prometheus::write_request_to_insert_exprs(database, request)?;
-
-                self.dist_insert(inserts)
+    async fn write(
+        &self,
+        database: &str,
+        request: WriteRequest,
+        query_ctx: QueryContextRef,
+    ) -> ServerResult<()> {
+        let exprs = prometheus::write_request_to_insert_exprs(database, request)?;
+        self.check_insert_privileges(&exprs, &query_ctx)
+            .map_err(BoxedError::new)
+            .context(error::ExecuteInsertSnafu {
+                msg: "failed to write prometheus remote request",
+            })?;
+        self.throttle_inserts(&exprs, &query_ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteInsertSnafu {
+                msg: "failed to write prometheus remote request",
+            })?;
+
+        let query = describe_inserts("Prometheus remote write", &exprs);
+        self.run_as_process(&query_ctx, &query, async {
+            match self.mode {
+                Mode::Standalone => {
+                    let futures = exprs
+                        .iter()
+                        .map(|e| self.handle_insert(e))
+                        .collect::<Vec<_>>();
+                    let res = futures_util::future::join_all(futures)
+                        .await
+                        .into_iter()
+                        .collect::<Result<Vec<_>, crate::error::Error>>();
+                    res.map(|_| ())
+                        .map_err(BoxedError::new)
+                        .context(error::ExecuteInsertSnafu {
+                            msg: "failed to write prometheus remote request",
+                        })
+                }
+                Mode::Distributed => self
+                    .dist_insert(&exprs)
                     .await
+                    .map(|_| ())
                     .map_err(BoxedError::new)
                     .context(error::ExecuteInsertSnafu {
                         msg: "execute insert failed",
-                    })?;
+                    }),
             }
-        }
-
-        Ok(())
+        })
+        .await
+        .map_err(BoxedError::new)
+        .context(error::ExecuteInsertSnafu {
+            msg: "failed to write prometheus remote request",
+        })?
     }
 
-    async fn read(&self, database: &str, request: ReadRequest) -> ServerResult<PrometheusResponse> {
+    async fn read(
+        &self,
+        database: &str,
+        request: ReadRequest,
+        query_ctx: QueryContextRef,
+    ) -> ServerResult<PrometheusResponse> {
         let response_type = negotiate_response_type(&request.accepted_response_types)?;
 
         // TODO(dennis): use read_hints to speedup query if possible
-        let results = handle_remote_queries(&self.database(database), &request.queries).await?;
+        // Lists the remote read as the SQL queries it runs.
+        let query = request
+            .queries
+            .iter()
+            .filter_map(|query| prometheus::query_to_sql(database, query).ok())
+            .map(|(_, sql)| sql)
+            .collect::<Vec<_>>()
+            .join("; ");
+        let results = self
+            .run_as_process(
+                &query_ctx,
+                &query,
+                handle_remote_queries(self, &self.database(database), &request.queries, &query_ctx),
+            )
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteQuerySnafu { query: &query })??;
 
         match response_type {
             ResponseType::Samples => {
@@ -177,6 +241,7 @@ mod tests {
     use api::prometheus::remote::label_matcher::Type as MatcherType;
     use api::prometheus::remote::{Label, LabelMatcher, Sample};
     use api::v1::CreateDatabaseExpr;
+    use session::context::QueryContext;
 
     use super::*;
     use crate::tests;
@@ -200,7 +265,10 @@ mod tests {
             .await
             .unwrap();
 
-        instance.write(db, write_request).await.unwrap();
+        instance
+            .write(db, write_request, QueryContext::arc())
+            .await
+            .unwrap();
 
         let read_request = ReadRequest {
             queries: vec![
@@ -235,7 +303,10 @@ mod tests {
             ..Default::default()
         };
 
-        let resp = instance.read(db, read_request).await.unwrap();
+        let resp = instance
+            .read(db, read_request, QueryContext::arc())
+            .await
+            .unwrap();
         assert_eq!(resp.content_type, "application/x-protobuf");
         assert_eq!(resp.content_encoding, "snappy");
         let body = prometheus::snappy_decompress(&resp.body).unwrap();
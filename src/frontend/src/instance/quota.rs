@@ -0,0 +1,93 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quotas of the session user and database, enforced on queries and writes from all protocols.
+
+use api::v1::{insert_expr, InsertExpr};
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::statements::insert::Insert;
+
+use crate::error::{self, Result};
+use crate::instance::Instance;
+use crate::quota::QueryPermit;
+
+impl Instance {
+    /// Admits a query of the session under the quota of its user and current database.
+    pub(crate) fn acquire_query_permit(&self, query_ctx: &QueryContextRef) -> Result<QueryPermit> {
+        self.quota_manager.acquire(
+            query_ctx.current_user().as_deref(),
+            &query_ctx.quota_limits(),
+            &query_ctx.current_schema(),
+        )
+    }
+
+    /// Waits until the rows of `inserts` are allowed by the ingest rates of the session user and
+    /// the databases. Only rows in insert batches are counted, inserts of raw SQL are throttled
+    /// when they are parsed.
+    pub(crate) async fn throttle_inserts(
+        &self,
+        inserts: &[InsertExpr],
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let user = query_ctx.current_user();
+        let session_limits = query_ctx.quota_limits();
+        for insert in inserts {
+            let database = &insert.schema_name;
+            if !self
+                .quota_manager
+                .is_ingest_limited(user.as_deref(), &session_limits, database)
+            {
+                continue;
+            }
+
+            let rows = match &insert.expr {
+                Some(insert_expr::Expr::Values(values)) => {
+                    common_insert::insert_batches(&values.values)
+                        .context(error::DeserializeInsertBatchSnafu)?
+                        .iter()
+                        .map(|batch| batch.row_count as usize)
+                        .sum()
+                }
+                Some(insert_expr::Expr::Sql(_)) | None => 0,
+            };
+            self.quota_manager
+                .throttle_ingest(user.as_deref(), &session_limits, database, rows)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Waits until the rows of the SQL `insert` are allowed by the ingest rates of the session
+    /// user and the database.
+    pub(crate) async fn throttle_sql_insert(
+        &self,
+        insert: &Insert,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        let (_, database, _) = insert
+            .full_table_name(query_ctx)
+            .context(error::ParseSqlSnafu)?;
+        let rows = insert.value_exprs().map(|rows| rows.len()).unwrap_or(0);
+        self.quota_manager
+            .throttle_ingest(
+                query_ctx.current_user().as_deref(),
+                &query_ctx.quota_limits(),
+                &database,
+                rows,
+            )
+            .await;
+        Ok(())
+    }
+}
@@ -22,12 +22,16 @@ pub mod frontend;
 pub mod grpc;
 pub mod influxdb;
 pub mod instance;
+mod materialized_view;
 pub(crate) mod mock;
 pub mod mysql;
 pub mod opentsdb;
 pub mod partitioning;
 pub mod postgres;
+pub mod process;
 pub mod prometheus;
+pub mod quota;
+mod script;
 mod server;
 pub mod spliter;
 mod sql;
@@ -0,0 +1,334 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Materialized views in distributed mode. The views are stored in metasrv, and every frontend
+//! maintains them on the inserts into their source tables it handles. As the inserts are spread
+//! over the frontends, the closed buckets are computed again from the source tables.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::helper::ColumnDataTypeWrapper;
+use api::v1::{ColumnDef, CreateExpr};
+use catalog::error::Error as CatalogError;
+use catalog::remote::{EventIter, Kv, KvBackendRef, KvEvent};
+use catalog::CatalogManager;
+use common_query::Output;
+use common_telemetry::{error, info, warn};
+use common_time::util;
+use datatypes::schema::ColumnSchema;
+use datatypes::vectors::VectorRef;
+use futures_util::StreamExt;
+use query::materialized_view::{MaterializedView, MaterializedViews, ViewTable};
+use query::QueryEngineRef;
+use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
+use snafu::{OptionExt, ResultExt};
+use sql::statements::create::CreateMaterializedView;
+use sql::statements::query::Query;
+use sql::statements::statement::Statement;
+use sql::statements::table_idents_to_full_name;
+use table::engine::TableReference;
+
+use crate::catalog::FrontendCatalogManager;
+use crate::error::{self, Result};
+use crate::instance::distributed::DistInstance;
+
+const MATERIALIZED_VIEW_KEY_PREFIX: &str = "__materialized_view-";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MaterializedViewValue {
+    catalog_name: String,
+    schema_name: String,
+    table_name: String,
+    /// The query of the view.
+    query: String,
+    /// Creation time of the view in milliseconds. The open buckets of a view never written are
+    /// rebuilt from the rows of the source table since then.
+    created_on: i64,
+}
+
+impl MaterializedViewValue {
+    fn parse(value: &[u8]) -> Result<Self> {
+        serde_json::from_slice(value).context(error::DeserializeJsonSnafu)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).context(error::SerializeJsonSnafu)
+    }
+}
+
+fn view_key(name: &str) -> String {
+    format!("{}{}", MATERIALIZED_VIEW_KEY_PREFIX, name)
+}
+
+/// Creates the materialized views in metasrv, and maintains all of them on the inserts handled
+/// by this frontend.
+pub(crate) struct RemoteMaterializedViews {
+    backend: KvBackendRef,
+    catalog_manager: Arc<FrontendCatalogManager>,
+    query_engine: QueryEngineRef,
+    views: MaterializedViews,
+}
+
+impl RemoteMaterializedViews {
+    /// Loads the views in metasrv and watches the ones created later.
+    pub(crate) async fn start(
+        backend: KvBackendRef,
+        catalog_manager: Arc<FrontendCatalogManager>,
+        query_engine: QueryEngineRef,
+    ) -> Result<Arc<Self>> {
+        let views = Arc::new(Self {
+            backend,
+            catalog_manager,
+            query_engine,
+            views: MaterializedViews::default(),
+        });
+        let events = views.rewatch().await?;
+        common_runtime::spawn_bg(views.clone().run(events));
+        Ok(views)
+    }
+
+    /// Creates the table of the view, then stores the view in metasrv for every frontend to
+    /// maintain it.
+    pub(crate) async fn create(
+        &self,
+        dist_instance: &DistInstance,
+        stmt: CreateMaterializedView,
+        query_ctx: &QueryContext,
+    ) -> Result<Output> {
+        let (catalog_name, schema_name, table_name) =
+            table_idents_to_full_name(&stmt.name, query_ctx).context(error::ParseSqlSnafu)?;
+        let name = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+
+        let exists = self
+            .catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .context(error::CatalogSnafu)?
+            .is_some();
+        if exists && stmt.if_not_exists {
+            return Ok(Output::AffectedRows(0));
+        }
+
+        let (_, view) = self.new_view(&name, &catalog_name, &schema_name, &stmt.query)?;
+        let schema = view.schema();
+        let column_schemas = schema.column_schemas();
+        let mut create_expr = CreateExpr {
+            catalog_name: Some(catalog_name.clone()),
+            schema_name: Some(schema_name.clone()),
+            table_name: table_name.clone(),
+            desc: None,
+            column_defs: column_schemas
+                .iter()
+                .map(column_def)
+                .collect::<Result<Vec<_>>>()?,
+            // The time bucket is always the time index of the view.
+            time_index: schema.timestamp_column().unwrap().name.clone(),
+            primary_keys: view
+                .primary_key_indices()
+                .into_iter()
+                .map(|i| column_schemas[i].name.clone())
+                .collect(),
+            create_if_not_exists: false,
+            table_options: HashMap::new(),
+            table_id: None,
+            region_ids: vec![],
+        };
+        let output = dist_instance.create_table(&mut create_expr, None).await?;
+
+        let value = MaterializedViewValue {
+            catalog_name,
+            schema_name,
+            table_name,
+            query: stmt.query.inner.to_string(),
+            created_on: util::current_time_millis(),
+        };
+        self.backend
+            .set(view_key(&name).as_bytes(), &value.encode()?)
+            .await
+            .context(error::CatalogSnafu)?;
+        self.load(&name, &value).await?;
+        Ok(output)
+    }
+
+    /// Updates the views over the table with the rows inserted into it.
+    pub(crate) async fn insert(
+        &self,
+        table_ref: &TableReference<'_>,
+        columns_values: &HashMap<String, VectorRef>,
+    ) -> Result<()> {
+        let table_name = table_ref.to_string();
+        self.views
+            .insert(&table_name, columns_values, self.query_engine.as_ref())
+            .await
+            .context(error::UpdateMaterializedViewsSnafu { table_name })
+    }
+
+    /// Creates the view stored in metasrv, and rebuilds its open buckets.
+    async fn load(&self, name: &str, value: &MaterializedViewValue) -> Result<()> {
+        let query = match self
+            .query_engine
+            .sql_to_statement(&value.query)
+            .context(error::ExecuteSqlSnafu { sql: &value.query })?
+        {
+            Statement::Query(query) => query,
+            _ => {
+                return error::InvalidSqlSnafu {
+                    err_msg: format!("the query of materialized view {} is not a SELECT", name),
+                }
+                .fail()
+            }
+        };
+        let table = self
+            .catalog_manager
+            .table(&value.catalog_name, &value.schema_name, &value.table_name)
+            .context(error::CatalogSnafu)?
+            .context(error::TableNotFoundSnafu { table_name: name })?;
+        let (source, view) =
+            self.new_view(name, &value.catalog_name, &value.schema_name, &query)?;
+
+        let view = ViewTable::new(name.to_string(), source, table, view, true);
+        view.rebuild(value.created_on, self.query_engine.as_ref())
+            .await
+            .context(error::MaterializedViewSnafu { name })?;
+        info!(
+            "Loaded materialized view {} over table {}",
+            name,
+            view.source()
+        );
+        self.views.register(view);
+        Ok(())
+    }
+
+    /// Creates the view of the query, returns it with the full name of its source table. An
+    /// unqualified source table is looked up in the catalog and schema of the view.
+    fn new_view(
+        &self,
+        name: &str,
+        catalog_name: &str,
+        schema_name: &str,
+        query: &Query,
+    ) -> Result<(String, MaterializedView)> {
+        let source =
+            MaterializedView::source_table(query).context(error::MaterializedViewSnafu { name })?;
+        let (catalog_name, schema_name, table_name) =
+            table_idents_to_full_name(source, &QueryContext::with(catalog_name, schema_name))
+                .context(error::ParseSqlSnafu)?;
+        let source = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+
+        let table = self
+            .catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .context(error::CatalogSnafu)?
+            .context(error::TableNotFoundSnafu {
+                table_name: &source,
+            })?;
+        let view = MaterializedView::try_new(query, &table.schema(), self.query_engine.as_ref())
+            .context(error::MaterializedViewSnafu { name })?;
+        Ok((source, view))
+    }
+
+    async fn handle_put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let key = String::from_utf8_lossy(key);
+        let name = match key.strip_prefix(MATERIALIZED_VIEW_KEY_PREFIX) {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+        // The view created here is loaded already.
+        if self.views.contains(name) {
+            return Ok(());
+        }
+        self.load(name, &MaterializedViewValue::parse(value)?).await
+    }
+
+    async fn run(self: Arc<Self>, mut events: EventIter<'static, CatalogError>) {
+        loop {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(KvEvent::Put(Kv(key, value))) => {
+                        if let Err(e) = self.handle_put(&key, &value).await {
+                            error!(e; "Failed to load materialized view");
+                        }
+                    }
+                    Ok(KvEvent::Delete(key)) => {
+                        let key = String::from_utf8_lossy(&key);
+                        if let Some(name) = key.strip_prefix(MATERIALIZED_VIEW_KEY_PREFIX) {
+                            self.views.remove(name);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Materialized view watch interrupted, err: {:?}", e);
+                        break;
+                    }
+                }
+            }
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                match self.rewatch().await {
+                    Ok(new_events) => {
+                        events = new_events;
+                        break;
+                    }
+                    Err(e) => error!(e; "Failed to rewatch materialized views on metasrv"),
+                }
+            }
+        }
+    }
+
+    /// Watches the views, then loads the existing ones, so that no view is missed in between.
+    async fn rewatch(&self) -> Result<EventIter<'static, CatalogError>> {
+        let events = self
+            .backend
+            .watch_prefix(MATERIALIZED_VIEW_KEY_PREFIX.as_bytes())
+            .await
+            .context(error::CatalogSnafu)?;
+
+        let mut views = Vec::new();
+        let mut iter = self.backend.range(MATERIALIZED_VIEW_KEY_PREFIX.as_bytes());
+        while let Some(kv) = iter.next().await {
+            views.push(kv.context(error::CatalogSnafu)?);
+        }
+        for Kv(key, value) in views {
+            if let Err(e) = self.handle_put(&key, &value).await {
+                error!(e; "Failed to load materialized view");
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn column_def(column: &ColumnSchema) -> Result<ColumnDef> {
+    let (datatype, datatype_extension) = ColumnDataTypeWrapper::try_from(column.data_type.clone())
+        .context(error::ColumnDataTypeSnafu)?
+        .into_parts();
+    Ok(ColumnDef {
+        name: column.name.clone(),
+        datatype: datatype as i32,
+        is_nullable: column.is_nullable(),
+        default_constraint: None,
+        datatype_extension,
+    })
+}
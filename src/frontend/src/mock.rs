@@ -14,26 +14,32 @@
 
 // FIXME(LFC): no mock
 
+use std::collections::HashSet;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
-use api::v1::InsertExpr;
-use client::{Database, ObjectResult, Select};
+use api::v1::codec::OperatorMetrics;
+use api::v1::{DataVersionsExpr, InsertExpr};
+use client::{Database, ObjectResult};
 use common_query::prelude::Expr;
 use common_query::Output;
-use common_recordbatch::{util, RecordBatches};
-use datafusion::logical_plan::{LogicalPlan as DfLogicPlan, LogicalPlanBuilder};
-use datafusion_expr::Expr as DfExpr;
-use datatypes::prelude::Value;
-use datatypes::schema::SchemaRef;
+use common_recordbatch::{util, RecordBatchStream, RecordBatches};
+use datafusion::logical_plan::{
+    col, unnormalize_col, Expr as DfExpr, LogicalPlan as DfLogicPlan, LogicalPlanBuilder,
+};
+use datafusion::optimizer::utils;
+use datatypes::value::Value;
 use meta_client::rpc::TableName;
-use query::plan::LogicalPlan;
+use snafu::ResultExt;
+use store_api::storage::DataVersion;
+use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
 use table::table::adapter::DfTableProviderAdapter;
 use table::TableRef;
 
+use crate::error::{self, Result};
+
 #[derive(Clone)]
 pub struct DatanodeInstance {
-    table: TableRef,
     db: Database,
 }
 
@@ -44,128 +50,171 @@ impl std::fmt::Debug for DatanodeInstance {
 }
 
 impl DatanodeInstance {
-    pub(crate) fn new(table: TableRef, db: Database) -> Self {
-        Self { table, db }
+    pub(crate) fn new(db: Database) -> Self {
+        Self { db }
     }
 
     pub(crate) async fn grpc_insert(&self, request: InsertExpr) -> client::Result<ObjectResult> {
         self.db.insert(request).await
     }
 
-    pub(crate) async fn grpc_table_scan(&self, plan: TableScanPlan) -> RecordBatches {
-        let logical_plan = self.build_logical_plan(&plan);
-        common_telemetry::info!("logical_plan: {:?}", logical_plan);
-        // TODO(LFC): Directly pass in logical plan to GRPC interface when our substrait codec supports filter.
-        let sql = to_sql(logical_plan);
-        let result = self.db.select(Select::Sql(sql)).await.unwrap();
+    /// Executes the logical plan on datanode, the plan is encoded in substrait. Returns the
+    /// results with the metrics of the operators executing the plan on datanode.
+    pub(crate) async fn grpc_logical_plan(
+        &self,
+        plan: DfLogicPlan,
+    ) -> (RecordBatches, Vec<OperatorMetrics>) {
+        common_telemetry::debug!("logical_plan: {:?}", plan);
+        let plan_bytes = DFLogicalSubstraitConvertor.encode(plan).unwrap();
+        let result = self.db.logical_plan(plan_bytes.to_vec()).await.unwrap();
+        let metrics = match &result {
+            ObjectResult::Select(select) => select.metrics.clone(),
+            ObjectResult::Mutate(_) => vec![],
+        };
 
         let output: Output = result.try_into().unwrap();
         let recordbatches = match output {
-            Output::Stream(stream) => util::collect(stream).await.unwrap(),
-            Output::RecordBatches(x) => x.take(),
+            Output::Stream(stream) => {
+                // Takes the schema from stream, as datanode may return no record batches.
+                let schema = stream.schema();
+                let recordbatches = util::collect(stream).await.unwrap();
+                RecordBatches::try_new(schema, recordbatches).unwrap()
+            }
+            Output::RecordBatches(x) => x,
             _ => unreachable!(),
         };
-
-        let schema = recordbatches.first().unwrap().schema.clone();
-        RecordBatches::try_new(schema, recordbatches).unwrap()
+        (recordbatches, metrics)
     }
 
-    fn build_logical_plan(&self, table_scan: &TableScanPlan) -> LogicalPlan {
-        let table_provider = Arc::new(DfTableProviderAdapter::new(self.table.clone()));
-
-        let mut builder = LogicalPlanBuilder::scan_with_filters(
-            &table_scan.table_name.to_string(),
-            table_provider,
-            table_scan.projection.clone(),
-            table_scan
-                .filters
-                .iter()
-                .map(|x| x.df_expr().clone())
-                .collect::<Vec<_>>(),
-        )
-        .unwrap();
-        if let Some(limit) = table_scan.limit {
-            builder = builder.limit(limit).unwrap();
-        }
+    /// Selects the versions of the data in the regions of the table on datanode.
+    pub(crate) async fn grpc_data_versions(
+        &self,
+        table_name: &TableName,
+    ) -> Result<Vec<DataVersion>> {
+        let expr = DataVersionsExpr {
+            catalog_name: table_name.catalog_name.clone(),
+            schema_name: table_name.schema_name.clone(),
+            table_name: table_name.table_name.clone(),
+        };
+        let output = self
+            .db
+            .data_versions(expr)
+            .await
+            .and_then(Output::try_from)
+            .context(error::RequestDatanodeSnafu)?;
+        let recordbatches = match output {
+            Output::RecordBatches(x) => x,
+            _ => {
+                return error::IncompleteGrpcResultSnafu {
+                    err_msg: "data versions are not selected as record batches",
+                }
+                .fail()
+            }
+        };
 
-        let plan = builder.build().unwrap();
-        LogicalPlan::DfPlan(plan)
+        let mut versions = Vec::new();
+        for batch in recordbatches.take() {
+            for row in batch.rows() {
+                let row = row.context(error::CreateRecordBatchesSnafu)?;
+                match &row[..] {
+                    [Value::UInt64(manifest_version), Value::UInt64(committed_sequence)] => {
+                        versions.push(DataVersion {
+                            manifest_version: *manifest_version,
+                            committed_sequence: *committed_sequence,
+                        })
+                    }
+                    row => {
+                        return error::IncompleteGrpcResultSnafu {
+                            err_msg: format!("invalid data versions: {:?}", row),
+                        }
+                        .fail()
+                    }
+                }
+            }
+        }
+        Ok(versions)
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct TableScanPlan {
-    pub table_name: TableName,
-    pub projection: Option<Vec<usize>>,
-    pub filters: Vec<Expr>,
-    pub limit: Option<usize>,
-}
-
-fn to_sql(plan: LogicalPlan) -> String {
-    let LogicalPlan::DfPlan(plan) = plan;
-    let table_scan = match plan {
-        DfLogicPlan::TableScan(table_scan) => table_scan,
-        _ => unreachable!("unknown plan: {:?}", plan),
-    };
-
-    let schema: SchemaRef = Arc::new(table_scan.source.schema().try_into().unwrap());
-    let projection = table_scan
-        .projection
-        .map(|x| {
-            x.iter()
-                .map(|i| schema.column_name_by_index(*i).to_string())
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_else(|| {
-            schema
-                .column_schemas()
-                .iter()
-                .map(|x| x.name.clone())
-                .collect::<Vec<String>>()
-        })
-        .join(", ");
-
-    let mut sql = format!("select {} from {}", projection, &table_scan.table_name);
-
+/// Builds the logical plan of scanning `table` on datanode. The filters that can be executed on
+/// datanodes are applied there, followed by the projection and the limit.
+pub(crate) fn build_table_scan_plan(table: TableRef, table_scan: &TableScanPlan) -> DfLogicPlan {
+    // The qualifiers of columns are dropped as the table is scanned under its full name.
     let filters = table_scan
         .filters
         .iter()
-        .map(expr_to_sql)
-        .collect::<Vec<String>>()
-        .join(" AND ");
-    if !filters.is_empty() {
-        sql.push_str(" where ");
-        sql.push_str(&filters);
+        .map(|x| unnormalize_col(x.df_expr().clone()))
+        .collect::<Vec<_>>();
+    let (remote_filters, local_filters): (Vec<_>, Vec<_>) = filters
+        .into_iter()
+        .partition(|filter| is_remote_filter(table.clone(), filter));
+
+    // Columns referenced by the filters are scanned as well, and projected away after filtering.
+    let schema = table.schema();
+    let mut scan_projection = table_scan.projection.clone();
+    if let Some(projection) = &mut scan_projection {
+        let mut columns = HashSet::new();
+        for filter in remote_filters.iter() {
+            utils::expr_to_columns(filter, &mut columns).unwrap();
+        }
+        for column in columns {
+            if let Some(index) = schema.column_index_by_name(&column.name) {
+                if !projection.contains(&index) {
+                    projection.push(index);
+                }
+            }
+        }
     }
-
-    if let Some(limit) = table_scan.limit {
-        sql.push_str(" limit ");
-        sql.push_str(&limit.to_string());
+    let need_projection = scan_projection != table_scan.projection;
+
+    let table_provider = Arc::new(DfTableProviderAdapter::new(table));
+    let mut builder = LogicalPlanBuilder::scan_with_filters(
+        &table_scan.table_name.to_string(),
+        table_provider,
+        scan_projection,
+        remote_filters.clone(),
+    )
+    .unwrap();
+    if let Some(filter) = remote_filters
+        .into_iter()
+        .reduce(|acc, filter| acc.and(filter))
+    {
+        builder = builder.filter(filter).unwrap();
     }
-    sql
+    if let Some(projection) = table_scan.projection.as_ref().filter(|_| need_projection) {
+        let exprs = projection
+            .iter()
+            .map(|i| col(schema.column_name_by_index(*i)))
+            .collect::<Vec<_>>();
+        builder = builder.project(exprs).unwrap();
+    }
+    // The limit is only correct on datanodes if all filters are applied there.
+    if let Some(limit) = table_scan.limit.filter(|_| local_filters.is_empty()) {
+        builder = builder.limit(limit).unwrap();
+    }
+
+    builder.build().unwrap()
 }
 
-fn expr_to_sql(expr: &DfExpr) -> String {
-    match expr {
-        DfExpr::BinaryExpr {
-            ref left,
-            ref right,
-            ref op,
-        } => format!(
-            "{} {} {}",
-            expr_to_sql(left.as_ref()),
-            op,
-            expr_to_sql(right.as_ref())
-        ),
-        DfExpr::Column(c) => c.name.clone(),
-        DfExpr::Literal(sv) => {
-            let v: Value = Value::try_from(sv.clone()).unwrap();
-            if v.data_type().is_string() {
-                format!("'{}'", sv)
-            } else {
-                format!("{}", sv)
-            }
-        }
-        _ => unimplemented!("not implemented for {:?}", expr),
-    }
+/// Returns whether `filter` can be executed on datanodes, i.e. the plan filtering `table` by it
+/// can be encoded in substrait.
+pub(crate) fn is_remote_filter(table: TableRef, filter: &DfExpr) -> bool {
+    let table_name = table.table_info().name.clone();
+    LogicalPlanBuilder::scan(
+        &table_name,
+        Arc::new(DfTableProviderAdapter::new(table)),
+        None,
+    )
+    .and_then(|builder| builder.filter(filter.clone()))
+    .and_then(|builder| builder.build())
+    .map(|plan| DFLogicalSubstraitConvertor.encode(plan).is_ok())
+    .unwrap_or(false)
+}
+
+#[derive(Debug)]
+pub(crate) struct TableScanPlan {
+    pub table_name: TableName,
+    pub projection: Option<Vec<usize>>,
+    pub filters: Vec<Expr>,
+    pub limit: Option<usize>,
 }
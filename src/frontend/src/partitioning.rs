@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub(crate) mod columns;
+pub(crate) mod hash;
 pub(crate) mod range;
 
 use std::any::Any;
@@ -49,6 +50,12 @@ pub trait PartitionRule: Sync + Send {
 pub(crate) enum PartitionBound {
     Value(Value),
     MaxValue,
+    /// Not a range bound, but marks the partition as the `index`-th of all the `num` partitions
+    /// of hash partitioning.
+    Hash {
+        index: u32,
+        num: u32,
+    },
 }
 
 #[derive(Debug)]
@@ -131,7 +138,7 @@ impl TryFrom<PartitionDef> for MetaPartition {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PartitionExpr {
     column: String,
     op: Operator,
@@ -201,5 +208,17 @@ mod tests {
         let b3 = PartitionBound::MaxValue;
         assert!(b1 < b2);
         assert!(b2 < b3);
+
+        let h1 = PartitionBound::Hash { index: 0, num: 2 };
+        let h2 = PartitionBound::Hash { index: 1, num: 2 };
+        assert!(h1 < h2);
+        assert_eq!(
+            r#"{"Hash":{"index":1,"num":2}}"#,
+            serde_json::to_string(&h2).unwrap()
+        );
+        assert_eq!(
+            h2,
+            serde_json::from_str(r#"{"Hash":{"index":1,"num":2}}"#).unwrap()
+        );
     }
 }
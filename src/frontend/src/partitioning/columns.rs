@@ -50,28 +50,6 @@ pub struct RangeColumnsPartitionRule {
     column_list: Vec<String>,
     value_lists: Vec<Vec<PartitionBound>>,
     regions: Vec<RegionNumber>,
-
-    // TODO(LFC): Implement finding regions by all partitioning columns, not by the first one only.
-    // Singled out the first partitioning column's bounds for finding regions by range.
-    //
-    // Theoretically, finding regions in `value_list`s should use all the partition columns values
-    // as a whole in the comparison (think of how Rust's vector is compared to each other). And
-    // this is how we do it if provided with concrete values (see `find_region` method).
-    //
-    // However, when we need to find regions by range, for example, a filter of "x < 100" defined
-    // in SQL, currently I'm not quite sure how that could be implemented. Especially facing the complex
-    // filter expression like "a < 1 AND (b > 2 OR c != 3)".
-    //
-    // So I decided to use the first partitioning column temporarily in finding regions by range,
-    // and further investigate how MySQL (and others) implemented this feature in detail.
-    //
-    // Finding regions only using the first partitioning column is fine. It might return regions that
-    // actually do not contain the range's value (causing unnecessary table scans), but will
-    // not lose any data that should have been scanned.
-    //
-    // The following two fields are acted as caches, so we don't need to recalculate them every time.
-    first_column_bounds: Vec<PartitionBound>,
-    first_column_regions: Vec<Vec<RegionNumber>>,
 }
 
 impl RangeColumnsPartitionRule {
@@ -82,44 +60,10 @@ impl RangeColumnsPartitionRule {
         value_lists: Vec<Vec<PartitionBound>>,
         regions: Vec<RegionNumber>,
     ) -> Self {
-        // An example range columns partition rule to calculate the first column bounds and regions:
-        // SQL:
-        //   PARTITION p1 VALUES LESS THAN (10, 'c'),
-        //   PARTITION p2 VALUES LESS THAN (20, 'h'),
-        //   PARTITION p3 VALUES LESS THAN (20, 'm'),
-        //   PARTITION p4 VALUES LESS THAN (50, 'p'),
-        //   PARTITION p5 VALUES LESS THAN (MAXVALUE, 'x'),
-        //   PARTITION p6 VALUES LESS THAN (MAXVALUE, MAXVALUE),
-        // first column bounds:
-        //   [10, 20, 50, MAXVALUE]
-        // first column regions:
-        //   [[1], [2, 3], [4], [5, 6]]
-
-        let first_column_bounds = value_lists
-            .iter()
-            .map(|x| &x[0])
-            .collect::<Vec<&PartitionBound>>();
-
-        let mut distinct_bounds = Vec::<PartitionBound>::new();
-        distinct_bounds.push(first_column_bounds[0].clone());
-        let mut first_column_regions = Vec::<Vec<RegionNumber>>::new();
-        first_column_regions.push(vec![regions[0]]);
-
-        for i in 1..first_column_bounds.len() {
-            if first_column_bounds[i] == &distinct_bounds[distinct_bounds.len() - 1] {
-                first_column_regions[distinct_bounds.len() - 1].push(regions[i]);
-            } else {
-                distinct_bounds.push(first_column_bounds[i].clone());
-                first_column_regions.push(vec![regions[i]]);
-            }
-        }
-
         Self {
             column_list,
             value_lists,
             regions,
-            first_column_bounds: distinct_bounds,
-            first_column_regions,
         }
     }
 
@@ -137,6 +81,94 @@ impl RangeColumnsPartitionRule {
     pub(crate) fn regions(&self) -> &Vec<RegionNumber> {
         &self.regions
     }
+
+    /// Converts the value list of a partition to a key, which is the (exclusive) upper bound of
+    /// all the tuples in that partition.
+    fn upper_key(&self, value_list: &[PartitionBound]) -> Vec<KeyBound> {
+        value_list
+            .iter()
+            .map(|x| match x {
+                PartitionBound::Value(v) => KeyBound::Value(v.clone()),
+                PartitionBound::MaxValue | PartitionBound::Hash { .. } => KeyBound::MaxValue,
+            })
+            .chain(std::iter::once(KeyBound::MinValue))
+            .collect()
+    }
+}
+
+/// A column value bound used in building keys to compare with tuples of partitioning column
+/// values lexicographically.
+///
+/// Each key has one more bound than the number of partitioning columns. The extra bound (and any
+/// bounds after a column without constraints) are padded with [KeyBound::MinValue] or
+/// [KeyBound::MaxValue], so that no actual tuple equals to a key. This makes comparing tuples
+/// with inclusive and exclusive bounds uniform.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum KeyBound {
+    MinValue,
+    Value(Value),
+    MaxValue,
+}
+
+/// The value range of a partitioning column, derived from partition exprs. `None` means unbounded,
+/// and the bool indicates whether the bound is inclusive.
+#[derive(Debug, Clone, Default)]
+struct ColumnRange {
+    lower: Option<(Value, bool)>,
+    upper: Option<(Value, bool)>,
+}
+
+impl ColumnRange {
+    fn intersect(&mut self, op: &Operator, value: &Value) {
+        match op {
+            Operator::Eq => {
+                self.tighten_lower(value, true);
+                self.tighten_upper(value, true);
+            }
+            Operator::Lt => self.tighten_upper(value, false),
+            Operator::LtEq => self.tighten_upper(value, true),
+            Operator::Gt => self.tighten_lower(value, false),
+            Operator::GtEq => self.tighten_lower(value, true),
+            // "NotEq" (and other operators) can not narrow the range.
+            _ => {}
+        }
+    }
+
+    fn tighten_lower(&mut self, value: &Value, inclusive: bool) {
+        let tighter = match &self.lower {
+            Some((v, inc)) => value > v || (value == v && *inc && !inclusive),
+            None => true,
+        };
+        if tighter {
+            self.lower = Some((value.clone(), inclusive));
+        }
+    }
+
+    fn tighten_upper(&mut self, value: &Value, inclusive: bool) {
+        let tighter = match &self.upper {
+            Some((v, inc)) => value < v || (value == v && *inc && !inclusive),
+            None => true,
+        };
+        if tighter {
+            self.upper = Some((value.clone(), inclusive));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.upper) {
+            (Some((lower, lower_inc)), Some((upper, upper_inc))) => {
+                lower > upper || (lower == upper && !(*lower_inc && *upper_inc))
+            }
+            _ => false,
+        }
+    }
+
+    fn point(&self) -> Option<&Value> {
+        match (&self.lower, &self.upper) {
+            (Some((lower, true)), Some((upper, true))) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
 }
 
 impl PartitionRule for RangeColumnsPartitionRule {
@@ -171,46 +203,75 @@ impl PartitionRule for RangeColumnsPartitionRule {
         })
     }
 
+    /// Finds the regions that may contain the tuples satisfying all the `exprs`.
+    ///
+    /// The range of each partitioning column is derived from `exprs` first. Then the tuples are
+    /// bounded by keys made up of the leading columns that are pinned to a single value, followed
+    /// by the range of the next column. For example, with partitioning columns `(a, b, c)`,
+    /// "a = 1 AND b > 2 AND c < 3" is bounded by `(1, 2, MAXVALUE, MAXVALUE)` and
+    /// `(1, MAXVALUE, MAXVALUE, MAXVALUE)`, while "c < 3" can not narrow the regions at all.
     fn find_regions(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>, Self::Error> {
-        let regions = if exprs.iter().all(|x| self.column_list.contains(&x.column)) {
-            let PartitionExpr {
-                column: _,
-                op,
-                value,
-            } = exprs
-                .iter()
-                .find(|x| x.column == self.column_list[0])
-                // "unwrap" is safe because we have checked that "self.column_list" contains all columns in "exprs"
-                .unwrap();
-
-            let regions = &self.first_column_regions;
-            match self
-                .first_column_bounds
-                .binary_search(&PartitionBound::Value(value.clone()))
-            {
-                Ok(i) => match op {
-                    Operator::Lt => &regions[..=i],
-                    Operator::LtEq => &regions[..=(i + 1)],
-                    Operator::Eq => &regions[(i + 1)..=(i + 1)],
-                    Operator::Gt | Operator::GtEq => &regions[(i + 1)..],
-                    Operator::NotEq => &regions[..],
-                    _ => unimplemented!(),
-                },
-                Err(i) => match op {
-                    Operator::Lt | Operator::LtEq => &regions[..=i],
-                    Operator::Eq => &regions[i..=i],
-                    Operator::Gt | Operator::GtEq => &regions[i..],
-                    Operator::NotEq => &regions[..],
-                    _ => unimplemented!(),
-                },
+        let mut ranges = vec![ColumnRange::default(); self.column_list.len()];
+        for PartitionExpr { column, op, value } in exprs {
+            if let Some(i) = self.column_list.iter().position(|x| x == column) {
+                ranges[i].intersect(op, value);
             }
-            .iter()
-            .flatten()
-            .cloned()
-            .collect::<Vec<RegionNumber>>()
-        } else {
-            self.regions.clone()
-        };
+        }
+        if ranges.iter().any(|x| x.is_empty()) {
+            return Ok(vec![]);
+        }
+
+        let key_len = self.column_list.len() + 1;
+        let mut lower = Vec::with_capacity(key_len);
+        let mut upper = Vec::with_capacity(key_len);
+        for range in ranges.iter() {
+            if let Some(v) = range.point() {
+                lower.push(KeyBound::Value(v.clone()));
+                upper.push(KeyBound::Value(v.clone()));
+                continue;
+            }
+
+            let lower_padding = match &range.lower {
+                Some((v, inclusive)) => {
+                    lower.push(KeyBound::Value(v.clone()));
+                    if *inclusive {
+                        KeyBound::MinValue
+                    } else {
+                        KeyBound::MaxValue
+                    }
+                }
+                None => KeyBound::MinValue,
+            };
+            let upper_padding = match &range.upper {
+                Some((v, inclusive)) => {
+                    upper.push(KeyBound::Value(v.clone()));
+                    if *inclusive {
+                        KeyBound::MaxValue
+                    } else {
+                        KeyBound::MinValue
+                    }
+                }
+                None => KeyBound::MaxValue,
+            };
+            lower.resize(key_len, lower_padding);
+            upper.resize(key_len, upper_padding);
+            break;
+        }
+        lower.resize(key_len, KeyBound::MinValue);
+        upper.resize(key_len, KeyBound::MaxValue);
+
+        // The i-th region holds the tuples in range ["value_lists[i - 1]", "value_lists[i]"),
+        // select it if the range is overlapped with ("lower", "upper"). All keys are padded, so
+        // both sides are compared strictly.
+        let mut regions = Vec::new();
+        let mut region_lower = vec![KeyBound::MinValue; key_len];
+        for (value_list, region) in self.value_lists.iter().zip(self.regions.iter()) {
+            let region_upper = self.upper_key(value_list);
+            if region_lower < upper && lower < region_upper {
+                regions.push(*region);
+            }
+            region_lower = region_upper;
+        }
         Ok(regions)
     }
 }
@@ -260,8 +321,8 @@ mod tests {
 
         let test = |op: Operator, value: &str, expected_regions: Vec<RegionNumber>| {
             let exprs = vec![
-                // Intentionally fix column b's partition expr to "b < 1". If we support finding
-                // regions by both columns("a" and "b") in the future, some test cases should fail.
+                // Intentionally fix column b's partition expr to "b < 1", which narrows the
+                // regions when column a's expr is "=".
                 PartitionExpr {
                     column: "b".to_string(),
                     op: Operator::Lt,
@@ -284,11 +345,11 @@ mod tests {
         test(Operator::NotEq, "what", vec![1, 2, 3, 4, 5, 6]);
 
         test(Operator::GtEq, "ab", vec![1, 2, 3, 4, 5, 6]);
-        test(Operator::GtEq, "hz", vec![2, 3, 4, 5, 6]);
+        test(Operator::GtEq, "hz", vec![1, 2, 3, 4, 5, 6]);
         test(Operator::GtEq, "ijk", vec![2, 3, 4, 5, 6]);
-        test(Operator::GtEq, "sh", vec![4, 5, 6]);
+        test(Operator::GtEq, "sh", vec![2, 3, 4, 5, 6]);
         test(Operator::GtEq, "ssh", vec![4, 5, 6]);
-        test(Operator::GtEq, "sz", vec![5, 6]);
+        test(Operator::GtEq, "sz", vec![4, 5, 6]);
         test(Operator::GtEq, "zz", vec![5, 6]);
 
         test(Operator::Gt, "ab", vec![1, 2, 3, 4, 5, 6]);
@@ -300,30 +361,30 @@ mod tests {
         test(Operator::Gt, "zz", vec![5, 6]);
 
         test(Operator::Eq, "ab", vec![1]);
-        test(Operator::Eq, "hz", vec![2, 3]);
-        test(Operator::Eq, "ijk", vec![2, 3]);
-        test(Operator::Eq, "sh", vec![4]);
+        test(Operator::Eq, "hz", vec![1]);
+        test(Operator::Eq, "ijk", vec![2]);
+        test(Operator::Eq, "sh", vec![2]);
         test(Operator::Eq, "ssh", vec![4]);
-        test(Operator::Eq, "sz", vec![5, 6]);
-        test(Operator::Eq, "zz", vec![5, 6]);
+        test(Operator::Eq, "sz", vec![4]);
+        test(Operator::Eq, "zz", vec![5]);
 
         test(Operator::Lt, "ab", vec![1]);
         test(Operator::Lt, "hz", vec![1]);
-        test(Operator::Lt, "ijk", vec![1, 2, 3]);
-        test(Operator::Lt, "sh", vec![1, 2, 3]);
+        test(Operator::Lt, "ijk", vec![1, 2]);
+        test(Operator::Lt, "sh", vec![1, 2]);
         test(Operator::Lt, "ssh", vec![1, 2, 3, 4]);
         test(Operator::Lt, "sz", vec![1, 2, 3, 4]);
-        test(Operator::Lt, "zz", vec![1, 2, 3, 4, 5, 6]);
+        test(Operator::Lt, "zz", vec![1, 2, 3, 4, 5]);
 
         test(Operator::LtEq, "ab", vec![1]);
-        test(Operator::LtEq, "hz", vec![1, 2, 3]);
-        test(Operator::LtEq, "ijk", vec![1, 2, 3]);
+        test(Operator::LtEq, "hz", vec![1, 2]);
+        test(Operator::LtEq, "ijk", vec![1, 2]);
         test(Operator::LtEq, "sh", vec![1, 2, 3, 4]);
         test(Operator::LtEq, "ssh", vec![1, 2, 3, 4]);
-        test(Operator::LtEq, "sz", vec![1, 2, 3, 4, 5, 6]);
-        test(Operator::LtEq, "zz", vec![1, 2, 3, 4, 5, 6]);
+        test(Operator::LtEq, "sz", vec![1, 2, 3, 4, 5]);
+        test(Operator::LtEq, "zz", vec![1, 2, 3, 4, 5]);
 
-        // If trying to find regions that is not partitioning column, return all regions.
+        // Exprs of columns that are not partitioning columns are ignored.
         let exprs = vec![
             PartitionExpr {
                 column: "c".to_string(),
@@ -337,9 +398,130 @@ mod tests {
             },
         ];
         let regions = rule.find_regions(&exprs).unwrap();
+        assert_eq!(regions, vec![1]);
+        let exprs = vec![PartitionExpr {
+            column: "c".to_string(),
+            op: Operator::Lt,
+            value: 1_i32.into(),
+        }];
+        let regions = rule.find_regions(&exprs).unwrap();
         assert_eq!(regions, vec![1, 2, 3, 4, 5, 6]);
     }
 
+    #[test]
+    fn test_find_regions_by_all_columns() {
+        // PARTITION BY RANGE COLUMNS(a, b)
+        //   PARTITION p1 VALUES LESS THAN ('hz', 10),
+        //   PARTITION p2 VALUES LESS THAN ('sh', 20),
+        //   PARTITION p3 VALUES LESS THAN ('sh', 50),
+        //   PARTITION p4 VALUES LESS THAN ('sz', 100),
+        //   PARTITION p5 VALUES LESS THAN (MAXVALUE, 200),
+        //   PARTITION p6 VALUES LESS THAN (MAXVALUE, MAXVALUE),
+        let rule = RangeColumnsPartitionRule::new(
+            vec!["a".to_string(), "b".to_string()],
+            vec![
+                vec![
+                    PartitionBound::Value("hz".into()),
+                    PartitionBound::Value(10_i32.into()),
+                ],
+                vec![
+                    PartitionBound::Value("sh".into()),
+                    PartitionBound::Value(20_i32.into()),
+                ],
+                vec![
+                    PartitionBound::Value("sh".into()),
+                    PartitionBound::Value(50_i32.into()),
+                ],
+                vec![
+                    PartitionBound::Value("sz".into()),
+                    PartitionBound::Value(100_i32.into()),
+                ],
+                vec![
+                    PartitionBound::MaxValue,
+                    PartitionBound::Value(200_i32.into()),
+                ],
+                vec![PartitionBound::MaxValue, PartitionBound::MaxValue],
+            ],
+            vec![1, 2, 3, 4, 5, 6],
+        );
+
+        let test = |exprs: Vec<(&str, Operator, Value)>, expected_regions: Vec<RegionNumber>| {
+            let exprs = exprs
+                .into_iter()
+                .map(|(column, op, value)| PartitionExpr::new(column, op, value))
+                .collect::<Vec<_>>();
+            let regions = rule.find_regions(&exprs).unwrap();
+            assert_eq!(regions, expected_regions);
+        };
+
+        test(vec![], vec![1, 2, 3, 4, 5, 6]);
+        test(
+            vec![("b", Operator::Eq, 20_i32.into())],
+            vec![1, 2, 3, 4, 5, 6],
+        );
+
+        test(
+            vec![
+                ("a", Operator::Eq, "sh".into()),
+                ("b", Operator::Eq, 20_i32.into()),
+            ],
+            vec![3],
+        );
+        test(
+            vec![
+                ("a", Operator::Eq, "sh".into()),
+                ("b", Operator::GtEq, 20_i32.into()),
+            ],
+            vec![3, 4],
+        );
+        test(
+            vec![
+                ("a", Operator::Eq, "sh".into()),
+                ("b", Operator::Gt, 20_i32.into()),
+                ("b", Operator::Lt, 50_i32.into()),
+            ],
+            vec![3],
+        );
+        test(
+            vec![
+                ("a", Operator::Eq, "sh".into()),
+                ("b", Operator::LtEq, 20_i32.into()),
+            ],
+            vec![2, 3],
+        );
+        test(
+            vec![
+                ("a", Operator::Eq, "hz".into()),
+                ("b", Operator::GtEq, 10_i32.into()),
+            ],
+            vec![2],
+        );
+        test(
+            vec![
+                ("a", Operator::Gt, "hz".into()),
+                ("a", Operator::Lt, "sz".into()),
+            ],
+            vec![2, 3, 4],
+        );
+
+        // Contradictory exprs.
+        test(
+            vec![
+                ("a", Operator::Gt, "sz".into()),
+                ("a", Operator::Lt, "hz".into()),
+            ],
+            vec![],
+        );
+        test(
+            vec![
+                ("a", Operator::Eq, "sh".into()),
+                ("a", Operator::NotEq, "sh".into()),
+                ("a", Operator::Eq, "sz".into()),
+            ],
+            vec![],
+        );
+    }
+
     #[test]
     fn test_find_region() {
         // PARTITION BY RANGE COLUMNS(a) (
@@ -0,0 +1,260 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use crc::{Crc, CRC_32_ISCSI};
+use datafusion_expr::Operator;
+use datatypes::value::Value;
+use snafu::ensure;
+use store_api::storage::RegionNumber;
+
+use crate::error::{self, Error};
+use crate::partitioning::{PartitionExpr, PartitionRule};
+
+const CRC_ALGO: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// [HashPartitionRule] distributes rows to regions by the hash of the partitioning columns'
+/// values. It's generated from create table request:
+///
+/// ```SQL
+/// CREATE TABLE table_name (
+///     columns definition
+/// )
+/// PARTITION BY HASH (column_list) PARTITIONS num
+/// ```
+///
+/// It spreads the rows of high-cardinality keys (like host IDs) evenly among regions. But unlike
+/// range partitioning, only the filters that pin all the partitioning columns to single values can
+/// be used in pruning regions.
+pub struct HashPartitionRule {
+    column_list: Vec<String>,
+    // The i-th region holds the rows whose hash modulo the number of regions is i.
+    regions: Vec<RegionNumber>,
+}
+
+impl HashPartitionRule {
+    pub(crate) fn new(column_list: Vec<String>, regions: Vec<RegionNumber>) -> Self {
+        Self {
+            column_list,
+            regions,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn regions(&self) -> &Vec<RegionNumber> {
+        &self.regions
+    }
+}
+
+impl PartitionRule for HashPartitionRule {
+    type Error = Error;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn partition_columns(&self) -> Vec<String> {
+        self.column_list.clone()
+    }
+
+    fn find_region(&self, values: &[Value]) -> Result<RegionNumber, Self::Error> {
+        ensure!(
+            values.len() == self.column_list.len(),
+            error::RegionKeysSizeSnafu {
+                expect: self.column_list.len(),
+                actual: values.len(),
+            }
+        );
+
+        let mut digest = CRC_ALGO.digest();
+        for value in values {
+            hash_value(value, &mut |bytes| digest.update(bytes));
+        }
+        let index = digest.finalize() as usize % self.regions.len();
+        Ok(self.regions[index])
+    }
+
+    /// Finds the regions that may contain the rows satisfying all the `exprs`. Only when every
+    /// partitioning column is compared with "=", the exact region can be found.
+    fn find_regions(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>, Self::Error> {
+        let values = self
+            .column_list
+            .iter()
+            .map(|column| {
+                exprs
+                    .iter()
+                    .find(|x| &x.column == column && x.op == Operator::Eq)
+                    .map(|x| x.value.clone())
+            })
+            .collect::<Option<Vec<Value>>>();
+
+        match values {
+            Some(values) => Ok(vec![self.find_region(&values)?]),
+            None => Ok(self.regions.clone()),
+        }
+    }
+}
+
+/// Feeds the bytes of `value` into the hash. The bytes are stable across versions and machines,
+/// and values that are equal but of different types (like `Int32(1)` and `Int64(1)`, which is
+/// quite common between column values and filter literals) are hashed to the same bytes.
+fn hash_value(value: &Value, update: &mut impl FnMut(&[u8])) {
+    match value {
+        Value::Null => update(&[0]),
+        Value::Boolean(v) => update(&[1, *v as u8]),
+        Value::UInt8(v) => hash_integer(*v as i128, update),
+        Value::UInt16(v) => hash_integer(*v as i128, update),
+        Value::UInt32(v) => hash_integer(*v as i128, update),
+        Value::UInt64(v) => hash_integer(*v as i128, update),
+        Value::Int8(v) => hash_integer(*v as i128, update),
+        Value::Int16(v) => hash_integer(*v as i128, update),
+        Value::Int32(v) => hash_integer(*v as i128, update),
+        Value::Int64(v) => hash_integer(*v as i128, update),
+        Value::Float32(v) => hash_float(v.0 as f64, update),
+        Value::Float64(v) => hash_float(v.0, update),
+        Value::String(v) => {
+            update(&[4]);
+            update(v.as_utf8().as_bytes());
+        }
+        Value::Binary(v) => {
+            update(&[5]);
+            update(&v[..]);
+        }
+        Value::Date(v) => {
+            update(&[6]);
+            update(&v.val().to_le_bytes());
+        }
+        Value::DateTime(v) => {
+            update(&[7]);
+            update(&v.val().to_le_bytes());
+        }
+        Value::Timestamp(v) => {
+            update(&[8]);
+            let nanos = v.value() as i128 * v.unit().factor() as i128;
+            update(&nanos.to_le_bytes());
+        }
+        Value::List(v) => {
+            update(&[9]);
+            if let Some(items) = v.items() {
+                for item in items.iter() {
+                    hash_value(item, update);
+                }
+            }
+        }
+        Value::Decimal128(v) => {
+            update(&[10]);
+            update(&v.value().to_le_bytes());
+            update(&[v.scale()]);
+        }
+        Value::Json(v) => {
+            update(&[11]);
+            update(v.as_utf8().as_bytes());
+        }
+        Value::Interval(v) => {
+            update(&[12]);
+            update(&v.months().to_le_bytes());
+            update(&v.days().to_le_bytes());
+            update(&v.nanos().to_le_bytes());
+        }
+        Value::Struct(v) => {
+            update(&[13]);
+            for item in v.items() {
+                hash_value(item, update);
+            }
+        }
+        Value::Map(v) => {
+            update(&[14]);
+            for (key, value) in v.entries() {
+                hash_value(key, update);
+                hash_value(value, update);
+            }
+        }
+    }
+}
+
+fn hash_integer(v: i128, update: &mut impl FnMut(&[u8])) {
+    update(&[2]);
+    update(&v.to_le_bytes());
+}
+
+fn hash_float(v: f64, update: &mut impl FnMut(&[u8])) {
+    update(&[3]);
+    update(&v.to_bits().to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_find_region() {
+        let rule = HashPartitionRule::new(vec!["a".to_string(), "b".to_string()], vec![0, 1, 2, 3]);
+
+        assert_matches!(
+            rule.find_region(&["foo".into()]),
+            Err(error::Error::RegionKeysSize {
+                expect: 2,
+                actual: 1,
+                ..
+            })
+        );
+
+        let mut found = vec![];
+        for i in 0..100 {
+            let region = rule
+                .find_region(&[format!("host_{}", i).as_str().into(), 1_i32.into()])
+                .unwrap();
+            assert!(rule.regions().contains(&region));
+            found.push(region);
+        }
+        // Rows are spread among all regions.
+        found.sort_unstable();
+        found.dedup();
+        assert_eq!(found, vec![0, 1, 2, 3]);
+
+        // Equal values of different types are in the same region.
+        assert_eq!(
+            rule.find_region(&["foo".into(), 1_i32.into()]).unwrap(),
+            rule.find_region(&["foo".into(), 1_i64.into()]).unwrap(),
+        );
+        assert_eq!(
+            rule.find_region(&["foo".into(), 1_u8.into()]).unwrap(),
+            rule.find_region(&["foo".into(), 1_i16.into()]).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_find_regions() {
+        let rule = HashPartitionRule::new(vec!["a".to_string(), "b".to_string()], vec![0, 1, 2, 3]);
+        let region = rule.find_region(&["foo".into(), 1_i32.into()]).unwrap();
+
+        let exprs = vec![
+            PartitionExpr::new("a", Operator::Eq, "foo".into()),
+            PartitionExpr::new("c", Operator::Lt, 1_i32.into()),
+            PartitionExpr::new("b", Operator::Eq, 1_i64.into()),
+        ];
+        assert_eq!(rule.find_regions(&exprs).unwrap(), vec![region]);
+
+        let exprs = vec![
+            PartitionExpr::new("a", Operator::Eq, "foo".into()),
+            PartitionExpr::new("b", Operator::Lt, 1_i32.into()),
+        ];
+        assert_eq!(rule.find_regions(&exprs).unwrap(), vec![0, 1, 2, 3]);
+
+        assert_eq!(rule.find_regions(&[]).unwrap(), vec![0, 1, 2, 3]);
+    }
+}
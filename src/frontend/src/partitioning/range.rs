@@ -16,10 +16,9 @@ use std::any::Any;
 
 use datatypes::prelude::*;
 use serde::{Deserialize, Serialize};
-use snafu::OptionExt;
 use store_api::storage::RegionNumber;
 
-use crate::error::{self, Error};
+use crate::error::Error;
 use crate::partitioning::{Operator, PartitionExpr, PartitionRule};
 
 /// [RangePartitionRule] manages the distribution of partitions partitioning by some column's value
@@ -122,55 +121,52 @@ impl PartitionRule for RangePartitionRule {
         })
     }
 
+    /// Finds the regions that may contain the values satisfying all the `exprs`.
     fn find_regions(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>, Self::Error> {
-        if exprs.is_empty() {
-            return Ok(self.regions.clone());
+        let mut regions = self.all_regions().clone();
+        for expr in exprs {
+            let expr_regions = self.find_regions_by_expr(expr);
+            regions.retain(|x| expr_regions.contains(x));
         }
-        debug_assert_eq!(
-            exprs.len(),
-            1,
-            "RangePartitionRule can only handle one partition expr, actual {}",
-            exprs.len()
-        );
-
-        let PartitionExpr { column, op, value } =
-            exprs.first().context(error::FindRegionSnafu {
-                reason: "no partition expr is provided",
-            })?;
-        let regions = if column == self.column_name() {
-            // an example of bounds and regions:
-            // SQL:
-            //   PARTITION p1 VALUES LESS THAN (10),
-            //   PARTITION p2 VALUES LESS THAN (20),
-            //   PARTITION p3 VALUES LESS THAN (50),
-            //   PARTITION p4 VALUES LESS THAN (MAXVALUE),
-            // bounds: [10, 20, 50]
-            // regions: [1, 2, 3, 4]
-            match self.bounds.binary_search(value) {
-                Ok(i) => match op {
-                    Operator::Lt => &self.regions[..=i],
-                    Operator::LtEq => &self.regions[..=(i + 1)],
-                    Operator::Eq => &self.regions[(i + 1)..=(i + 1)],
-                    Operator::Gt | Operator::GtEq => &self.regions[(i + 1)..],
-                    Operator::NotEq => &self.regions[..],
-                    _ => unimplemented!(),
-                },
-                Err(i) => match op {
-                    Operator::Lt | Operator::LtEq => &self.regions[..=i],
-                    Operator::Eq => &self.regions[i..=i],
-                    Operator::Gt | Operator::GtEq => &self.regions[i..],
-                    Operator::NotEq => &self.regions[..],
-                    _ => unimplemented!(),
-                },
-            }
-            .to_vec()
-        } else {
-            self.all_regions().clone()
-        };
         Ok(regions)
     }
 }
 
+impl RangePartitionRule {
+    fn find_regions_by_expr(&self, expr: &PartitionExpr) -> &[RegionNumber] {
+        let PartitionExpr { column, op, value } = expr;
+        if column != self.column_name() {
+            return self.all_regions();
+        }
+
+        // an example of bounds and regions:
+        // SQL:
+        //   PARTITION p1 VALUES LESS THAN (10),
+        //   PARTITION p2 VALUES LESS THAN (20),
+        //   PARTITION p3 VALUES LESS THAN (50),
+        //   PARTITION p4 VALUES LESS THAN (MAXVALUE),
+        // bounds: [10, 20, 50]
+        // regions: [1, 2, 3, 4]
+        match self.bounds.binary_search(value) {
+            Ok(i) => match op {
+                Operator::Lt => &self.regions[..=i],
+                Operator::LtEq => &self.regions[..=(i + 1)],
+                Operator::Eq => &self.regions[(i + 1)..=(i + 1)],
+                Operator::Gt | Operator::GtEq => &self.regions[(i + 1)..],
+                Operator::NotEq => &self.regions[..],
+                _ => unimplemented!(),
+            },
+            Err(i) => match op {
+                Operator::Lt | Operator::LtEq => &self.regions[..=i],
+                Operator::Eq => &self.regions[i..=i],
+                Operator::Gt | Operator::GtEq => &self.regions[i..],
+                Operator::NotEq => &self.regions[..],
+                _ => unimplemented!(),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -247,5 +243,18 @@ mod test {
         test("a", Operator::LtEq, "zz", vec![1, 2, 3, 4]);
 
         test("b", Operator::Lt, "1", vec![1, 2, 3, 4]);
+
+        // Regions satisfying all exprs are returned.
+        let exprs = vec![
+            PartitionExpr::new("a", Operator::Gt, "ijk".into()),
+            PartitionExpr::new("a", Operator::Lt, "ssh".into()),
+            PartitionExpr::new("b", Operator::Eq, "1".into()),
+        ];
+        assert_eq!(rule.find_regions(&exprs).unwrap(), vec![2, 3]);
+        let exprs = vec![
+            PartitionExpr::new("a", Operator::Gt, "sz".into()),
+            PartitionExpr::new("a", Operator::Lt, "hz".into()),
+        ];
+        assert!(rule.find_regions(&exprs).unwrap().is_empty());
     }
 }
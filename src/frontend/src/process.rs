@@ -0,0 +1,575 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statements running in the frontend. They are listed by `SHOW PROCESSLIST` and the
+//! `information_schema.processes` table, and cancelled by `KILL`.
+
+mod remote;
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashSet};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+use catalog::local::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
+use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, INFORMATION_SCHEMA_NAME};
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_query::Output;
+use common_recordbatch::error::{Error as RecordBatchError, Result as RecordBatchResult};
+use common_recordbatch::{
+    RecordBatch, RecordBatchStream, RecordBatches, SendableRecordBatchStream,
+};
+use common_time::util::current_time_millis;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVector, TimestampVector, UInt32Vector, UInt64Vector, VectorRef};
+use futures::{Future, Stream, StreamExt};
+use query::{QueryEngineFactory, QueryEngineRef};
+use session::context::{Channel, QueryContext};
+use snafu::ResultExt;
+use sql::ast::ObjectName;
+use sql::statements::table_idents_to_full_name;
+use table::metadata::TableInfoRef;
+use table::table::scan::SimpleTableScan;
+use table::Table;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+pub use self::remote::{KillResult, RemoteProcessList};
+use crate::error::{self, Result};
+
+pub const PROCESSES_TABLE_NAME: &str = "processes";
+
+/// Queries are truncated to this number of characters unless `SHOW FULL PROCESSLIST`.
+const QUERY_DISPLAY_LEN: usize = 100;
+
+/// A statement running in the frontend.
+#[derive(Debug)]
+pub struct Process {
+    /// Id of the connection running the statement, shared by all statements of a connection.
+    id: u32,
+    user: Option<String>,
+    client_addr: Option<String>,
+    channel: Option<Channel>,
+    database: String,
+    query: String,
+    /// Start time in milliseconds since UNIX epoch.
+    start_time: i64,
+    cancel_token: CancellationToken,
+}
+
+impl Process {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Cancels the statement, whose execution is stopped and an error is returned instead.
+    pub fn cancel(&self) {
+        self.cancel_token.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_token.is_cancelled()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ProcessList {
+    next_seq: AtomicU64,
+    processes: RwLock<BTreeMap<u64, Arc<Process>>>,
+    /// Killed connections, whose statements are cancelled once registered.
+    killed_connections: RwLock<HashSet<u32>>,
+}
+
+impl ProcessList {
+    /// Registers the `query` of the session, it's listed until the returned guard is dropped.
+    /// Queries of killed connections are registered as cancelled.
+    pub fn register(self: &Arc<Self>, query_ctx: &QueryContext, query: &str) -> ProcessGuard {
+        let process = Arc::new(Process {
+            id: query_ctx.conn_id(),
+            user: query_ctx.current_user(),
+            client_addr: query_ctx.client_addr(),
+            channel: query_ctx.channel(),
+            database: query_ctx.current_schema(),
+            query: query.to_string(),
+            start_time: current_time_millis(),
+            cancel_token: CancellationToken::new(),
+        });
+        if self
+            .killed_connections
+            .read()
+            .unwrap()
+            .contains(&process.id)
+        {
+            process.cancel();
+        }
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let _ = self.processes.write().unwrap().insert(seq, process.clone());
+
+        ProcessGuard {
+            seq,
+            process,
+            process_list: self.clone(),
+        }
+    }
+
+    /// Returns the running statements, in the order they are started.
+    pub fn processes(&self) -> Vec<Arc<Process>> {
+        self.processes.read().unwrap().values().cloned().collect()
+    }
+
+    /// Returns the running statements of the connection `id`.
+    pub fn find(&self, id: u32) -> Vec<Arc<Process>> {
+        self.processes
+            .read()
+            .unwrap()
+            .values()
+            .filter(|process| process.id == id)
+            .cloned()
+            .collect()
+    }
+
+    /// Cancels the running statements of the connection `id`, also the later ones if
+    /// `connection`. Returns the cancelled statements.
+    pub fn kill(&self, id: u32, connection: bool) -> Vec<Arc<Process>> {
+        if connection {
+            let _ = self.killed_connections.write().unwrap().insert(id);
+        }
+        let processes = self.find(id);
+        for process in &processes {
+            process.cancel();
+        }
+        processes
+    }
+
+    /// Lists the processes visible to `user` as record batches, all processes are visible if
+    /// `user` is `None`. Queries are truncated unless `full`.
+    pub fn to_record_batches(&self, user: Option<&str>, full: bool) -> Result<RecordBatches> {
+        let processes = self
+            .processes()
+            .into_iter()
+            .filter(|process| user.is_none() || process.user() == user)
+            .collect::<Vec<_>>();
+        let now = current_time_millis();
+
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(UInt32Vector::from_values(
+                processes.iter().map(|process| process.id),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.user.clone())
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.client_addr.clone())
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.database.clone())
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| process.channel.map(|channel| channel.to_string()))
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(TimestampVector::from_values(
+                processes.iter().map(|process| process.start_time),
+            )),
+            Arc::new(UInt64Vector::from_values(
+                processes
+                    .iter()
+                    .map(|process| (now - process.start_time).max(0) as u64),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|process| display_query(&process.query, full))
+                    .collect::<Vec<_>>(),
+            )),
+        ];
+        RecordBatches::try_from_columns(processes_schema(), columns)
+            .context(error::CreateRecordBatchesSnafu)
+    }
+}
+
+fn display_query(query: &str, full: bool) -> String {
+    if full || query.chars().count() <= QUERY_DISPLAY_LEN {
+        query.to_string()
+    } else {
+        query.chars().take(QUERY_DISPLAY_LEN).collect()
+    }
+}
+
+fn processes_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        ColumnSchema::new("id", ConcreteDataType::uint32_datatype(), false),
+        ColumnSchema::new("user", ConcreteDataType::string_datatype(), true),
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+        ColumnSchema::new("db", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("protocol", ConcreteDataType::string_datatype(), true),
+        ColumnSchema::new(
+            "start_time",
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        ),
+        ColumnSchema::new("elapsed_ms", ConcreteDataType::uint64_datatype(), false),
+        ColumnSchema::new("query", ConcreteDataType::string_datatype(), false),
+    ]))
+}
+
+/// A registered statement, which is removed from the process list once dropped.
+pub struct ProcessGuard {
+    seq: u64,
+    process: Arc<Process>,
+    process_list: Arc<ProcessList>,
+}
+
+impl ProcessGuard {
+    pub fn process(&self) -> &Arc<Process> {
+        &self.process
+    }
+
+    /// Completes when the statement is killed.
+    pub fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.process.cancel_token.cancelled()
+    }
+
+    pub fn cancelled_error(&self) -> error::Error {
+        error::QueryCancelledSnafu {
+            id: self.process.id,
+        }
+        .build()
+    }
+
+    /// Keeps the statement listed until its output stream is exhausted or dropped, the stream
+    /// can still be cancelled meanwhile.
+    pub fn attach(self, output: Output) -> Output {
+        match output {
+            Output::Stream(stream) => {
+                Output::Stream(Box::pin(ProcessRecordBatchStream::new(stream, self)))
+            }
+            output => output,
+        }
+    }
+}
+
+impl Drop for ProcessGuard {
+    fn drop(&mut self) {
+        let _ = self
+            .process_list
+            .processes
+            .write()
+            .unwrap()
+            .remove(&self.seq);
+    }
+}
+
+/// Results of a registered statement. Once the statement is killed, the underlying stream of the
+/// query engine is dropped to cancel the execution, and an error is returned instead.
+struct ProcessRecordBatchStream {
+    schema: SchemaRef,
+    stream: Option<SendableRecordBatchStream>,
+    cancelled: Pin<Box<dyn Future<Output = ()> + Send>>,
+    guard: Option<ProcessGuard>,
+}
+
+impl ProcessRecordBatchStream {
+    fn new(stream: SendableRecordBatchStream, guard: ProcessGuard) -> Self {
+        let cancel_token = guard.process.cancel_token.clone();
+        Self {
+            schema: stream.schema(),
+            stream: Some(stream),
+            cancelled: Box::pin(async move { cancel_token.cancelled().await }),
+            guard: Some(guard),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.stream = None;
+        self.guard = None;
+    }
+}
+
+impl RecordBatchStream for ProcessRecordBatchStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for ProcessRecordBatchStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let stream = match this.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(None),
+        };
+
+        if this.cancelled.as_mut().poll(cx).is_ready() {
+            let err = this
+                .guard
+                .as_ref()
+                .map(|guard| guard.cancelled_error())
+                .expect("guard is held until the stream is finished");
+            this.finish();
+            return Poll::Ready(Some(Err(RecordBatchError::new(err))));
+        }
+
+        match stream.poll_next_unpin(cx) {
+            Poll::Ready(None) => {
+                this.finish();
+                Poll::Ready(None)
+            }
+            poll => poll,
+        }
+    }
+}
+
+/// Returns whether `table_name` refers to `information_schema.processes`.
+pub(crate) fn is_processes_table(table_name: &ObjectName, query_ctx: &QueryContext) -> bool {
+    match table_idents_to_full_name(table_name, query_ctx) {
+        Ok((catalog, schema, table)) => {
+            catalog == DEFAULT_CATALOG_NAME
+                && schema.eq_ignore_ascii_case(INFORMATION_SCHEMA_NAME)
+                && table.eq_ignore_ascii_case(PROCESSES_TABLE_NAME)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Creates a query engine over the `information_schema.processes` table that lists processes
+/// visible to `user`. Queries reading the table run on it in the frontend, so they can't read
+/// other tables at the same time.
+pub(crate) fn processes_query_engine(
+    process_list: Arc<ProcessList>,
+    user: Option<String>,
+) -> Result<QueryEngineRef> {
+    let schema = Arc::new(MemorySchemaProvider::new());
+    let _ = schema
+        .register_table(
+            PROCESSES_TABLE_NAME.to_string(),
+            Arc::new(ProcessesTable::new(process_list, user)),
+        )
+        .context(error::CatalogSnafu)?;
+    let catalog = Arc::new(MemoryCatalogProvider::new());
+    let _ = catalog
+        .register_schema(INFORMATION_SCHEMA_NAME.to_string(), schema)
+        .context(error::CatalogSnafu)?;
+    let catalog_list = Arc::new(MemoryCatalogManager::default());
+    let _ = catalog_list
+        .register_catalog(DEFAULT_CATALOG_NAME.to_string(), catalog)
+        .context(error::CatalogSnafu)?;
+
+    Ok(QueryEngineFactory::new(catalog_list).query_engine())
+}
+
+/// The `information_schema.processes` table, a snapshot of the process list when scanned.
+pub struct ProcessesTable {
+    schema: SchemaRef,
+    process_list: Arc<ProcessList>,
+    user: Option<String>,
+}
+
+impl ProcessesTable {
+    pub fn new(process_list: Arc<ProcessList>, user: Option<String>) -> Self {
+        Self {
+            schema: processes_schema(),
+            process_list,
+            user,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ProcessesTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        unreachable!("ProcessesTable does not support table_info method")
+    }
+
+    async fn scan(
+        &self,
+        _projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> table::error::Result<PhysicalPlanRef> {
+        let batches = self
+            .process_list
+            .to_record_batches(self.user.as_deref(), true)
+            .map_err(common_error::ext::BoxedError::new)
+            .context(table::error::TablesRecordBatchSnafu)?;
+        Ok(Arc::new(SimpleTableScan::new(batches.as_stream())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common_recordbatch::util;
+
+    use super::*;
+
+    fn new_query_ctx(user: &str) -> Arc<QueryContext> {
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_user(Some(user.to_string()));
+        query_ctx.set_channel(Channel::Mysql);
+        query_ctx
+    }
+
+    /// A stream that never completes, like a query waiting for datanodes.
+    struct PendingStream;
+
+    impl RecordBatchStream for PendingStream {
+        fn schema(&self) -> SchemaRef {
+            processes_schema()
+        }
+    }
+
+    impl Stream for PendingStream {
+        type Item = RecordBatchResult<RecordBatch>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_register_process() {
+        let process_list = Arc::new(ProcessList::default());
+        let query_ctx = new_query_ctx("alice");
+        let guard = process_list.register(&query_ctx, "SELECT 1");
+        let _other = process_list.register(&new_query_ctx("bob"), "SELECT 2");
+
+        let processes = process_list.find(query_ctx.conn_id());
+        assert_eq!(1, processes.len());
+        assert_eq!(Some("alice"), processes[0].user());
+        assert_eq!("SELECT 1", processes[0].query());
+        assert_eq!(2, process_list.processes().len());
+
+        let batches = process_list.to_record_batches(Some("alice"), true).unwrap();
+        let batches = batches.take();
+        assert_eq!(1, batches[0].num_rows());
+
+        drop(guard);
+        assert!(process_list.find(query_ctx.conn_id()).is_empty());
+        assert_eq!(1, process_list.processes().len());
+    }
+
+    #[test]
+    fn test_kill() {
+        let process_list = Arc::new(ProcessList::default());
+        let query_ctx = new_query_ctx("alice");
+        let query = process_list.register(&query_ctx, "SELECT 1");
+        let killed = process_list.kill(query_ctx.conn_id(), false);
+        assert_eq!(1, killed.len());
+        assert!(query.process().is_cancelled());
+        // Later queries of the connection still run.
+        let query = process_list.register(&query_ctx, "SELECT 2");
+        assert!(!query.process().is_cancelled());
+
+        let killed = process_list.kill(query_ctx.conn_id(), true);
+        assert_eq!(1, killed.len());
+        assert!(query.process().is_cancelled());
+        let query = process_list.register(&query_ctx, "SELECT 3");
+        assert!(query.process().is_cancelled());
+        let other = process_list.register(&new_query_ctx("alice"), "SELECT 4");
+        assert!(!other.process().is_cancelled());
+    }
+
+    #[test]
+    fn test_display_query() {
+        let query = "x".repeat(QUERY_DISPLAY_LEN + 1);
+        assert_eq!(QUERY_DISPLAY_LEN, display_query(&query, false).len());
+        assert_eq!(query, display_query(&query, true));
+        assert_eq!("SELECT 1", display_query("SELECT 1", false));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stream() {
+        let process_list = Arc::new(ProcessList::default());
+        let query_ctx = new_query_ctx("alice");
+        let guard = process_list.register(&query_ctx, "SELECT * FROM numbers");
+
+        let output = guard.attach(Output::Stream(Box::pin(PendingStream)));
+
+        let process_list_clone = process_list.clone();
+        let id = query_ctx.conn_id();
+        let _ = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            for process in process_list_clone.find(id) {
+                process.cancel();
+            }
+        });
+
+        let stream = match output {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let err = util::collect(stream).await.unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{}", err);
+        assert!(process_list.find(id).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_processes_table() {
+        let process_list = Arc::new(ProcessList::default());
+        let _alice = process_list.register(&new_query_ctx("alice"), "SELECT 1");
+        let _bob = process_list.register(&new_query_ctx("bob"), "SELECT 2");
+
+        let engine = processes_query_engine(process_list, Some("alice".to_string())).unwrap();
+        let plan = engine
+            .sql_to_plan(
+                "SELECT user, query FROM information_schema.processes",
+                QueryContext::arc(),
+            )
+            .unwrap();
+        let output = engine.execute(&plan).await.unwrap();
+        let batches = match output {
+            Output::Stream(stream) => util::collect(stream).await.unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            1,
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>()
+        );
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Kills processes running in other frontends. The frontend running a connection is told by the
+//! node id of the connection id. A kill request is put to metasrv, and the frontend running the
+//! connection watches it, kills the processes and writes back the result.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use catalog::error::Error as CatalogError;
+use catalog::remote::{EventIter, Kv, KvBackendRef, KvEvent};
+use common_telemetry::{error, warn};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use session::context::{conn_node_id, set_conn_node_id, MAX_CONN_NODES};
+use snafu::ResultExt;
+use sql::statements::kill::Kill;
+
+use crate::error::{self, Result};
+use crate::process::ProcessList;
+
+const KILL_KEY_PREFIX: &str = "__kill-";
+/// Number of frontends started, which allocates node ids of connections.
+const CONN_NODE_SEQ_KEY: &str = "__conn_node_seq";
+
+/// How long to wait for the frontend running the connection to handle a kill request.
+const KILL_TIMEOUT: Duration = Duration::from_secs(3);
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KillResult {
+    Killed,
+    /// Some processes belong to other users, which requires admin privileges.
+    AccessDenied,
+    NotFound,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct KillValue {
+    connection: bool,
+    /// The user killing the processes.
+    user: Option<String>,
+    /// Whether the user has admin privileges.
+    admin: bool,
+    /// Set by the frontend running the connection once handled.
+    result: Option<KillResult>,
+}
+
+impl KillValue {
+    fn parse(value: &[u8]) -> Result<Self> {
+        serde_json::from_slice(value).context(error::DeserializeJsonSnafu)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).context(error::SerializeJsonSnafu)
+    }
+}
+
+/// Returns the connection id in the key of a kill request, `__kill-{conn_id}-{request_id}`.
+fn parse_conn_id(key: &[u8]) -> Option<u32> {
+    String::from_utf8_lossy(key)
+        .strip_prefix(KILL_KEY_PREFIX)?
+        .split('-')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Sends kill requests of connections in other frontends, and handles the ones of connections
+/// in this frontend.
+pub struct RemoteProcessList {
+    backend: KvBackendRef,
+    process_list: Arc<ProcessList>,
+    node_id: u32,
+    next_request_id: AtomicU64,
+}
+
+impl RemoteProcessList {
+    /// Allocates the node id of connections in this frontend, and starts handling kill requests
+    /// of them. Must be called before any connection is created.
+    pub async fn start(backend: KvBackendRef, process_list: Arc<ProcessList>) -> Result<Arc<Self>> {
+        let node_id = allocate_node_id(&backend).await?;
+        set_conn_node_id(node_id);
+
+        let remote = Arc::new(Self {
+            backend,
+            process_list,
+            node_id,
+            next_request_id: AtomicU64::new(0),
+        });
+        let events = remote.rewatch().await?;
+        common_runtime::spawn_bg(remote.clone().run(events));
+        Ok(remote)
+    }
+
+    /// Kills the processes of connection `kill.id` running in another frontend, on behalf of
+    /// `user`.
+    pub async fn kill(&self, kill: &Kill, user: Option<String>, admin: bool) -> Result<KillResult> {
+        // Connections of this frontend are already looked up locally.
+        if conn_node_id(kill.id) == self.node_id {
+            return Ok(KillResult::NotFound);
+        }
+
+        let key = format!(
+            "{}{}-{}-{}",
+            KILL_KEY_PREFIX,
+            kill.id,
+            self.node_id,
+            self.next_request_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let value = KillValue {
+            connection: kill.connection,
+            user,
+            admin,
+            result: None,
+        };
+        self.backend
+            .set(key.as_bytes(), &value.encode()?)
+            .await
+            .context(error::CatalogSnafu)?;
+
+        let deadline = Instant::now() + KILL_TIMEOUT;
+        let result = loop {
+            let value = self
+                .backend
+                .get(key.as_bytes())
+                .await
+                .context(error::CatalogSnafu)?;
+            if let Some(Kv(_, value)) = value {
+                if let Some(result) = KillValue::parse(&value)?.result {
+                    break result;
+                }
+            }
+            // The frontend running the connection is gone if it doesn't respond.
+            if Instant::now() >= deadline {
+                break KillResult::NotFound;
+            }
+            tokio::time::sleep(KILL_POLL_INTERVAL).await;
+        };
+        self.backend
+            .delete(key.as_bytes())
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(result)
+    }
+
+    async fn run(self: Arc<Self>, mut events: EventIter<'static, CatalogError>) {
+        loop {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(KvEvent::Put(Kv(key, value))) => {
+                        if let Err(e) = self.handle_request(&key, &value).await {
+                            error!(e; "Failed to handle kill request");
+                        }
+                    }
+                    Ok(KvEvent::Delete(_)) => {}
+                    Err(e) => {
+                        warn!("Kill request watch interrupted, err: {:?}", e);
+                        break;
+                    }
+                }
+            }
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                match self.rewatch().await {
+                    Ok(new_events) => {
+                        events = new_events;
+                        break;
+                    }
+                    Err(e) => error!(e; "Failed to rewatch kill requests on metasrv"),
+                }
+            }
+        }
+    }
+
+    /// Watches the kill requests, then handles the pending ones, so that no request is missed in
+    /// between.
+    async fn rewatch(&self) -> Result<EventIter<'static, CatalogError>> {
+        let events = self
+            .backend
+            .watch_prefix(KILL_KEY_PREFIX.as_bytes())
+            .await
+            .context(error::CatalogSnafu)?;
+
+        let mut requests = Vec::new();
+        let mut iter = self.backend.range(KILL_KEY_PREFIX.as_bytes());
+        while let Some(kv) = iter.next().await {
+            requests.push(kv.context(error::CatalogSnafu)?);
+        }
+        for Kv(key, value) in requests {
+            if let Err(e) = self.handle_request(&key, &value).await {
+                error!(e; "Failed to handle kill request");
+            }
+        }
+        Ok(events)
+    }
+
+    async fn handle_request(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let conn_id = match parse_conn_id(key) {
+            Some(conn_id) if conn_node_id(conn_id) == self.node_id => conn_id,
+            _ => return Ok(()),
+        };
+        let mut request = KillValue::parse(value)?;
+        if request.result.is_some() {
+            return Ok(());
+        }
+
+        let processes = self.process_list.find(conn_id);
+        let result = if processes.is_empty() {
+            KillResult::NotFound
+        } else if !request.admin
+            && processes
+                .iter()
+                .any(|process| process.user() != request.user.as_deref())
+        {
+            KillResult::AccessDenied
+        } else {
+            let _ = self.process_list.kill(conn_id, request.connection);
+            KillResult::Killed
+        };
+        request.result = Some(result);
+
+        // The request is gone if the killer has given up waiting.
+        let _ = self
+            .backend
+            .compare_and_set(key, value, &request.encode()?)
+            .await
+            .context(error::CatalogSnafu)?;
+        Ok(())
+    }
+}
+
+async fn allocate_node_id(backend: &KvBackendRef) -> Result<u32> {
+    let key = CONN_NODE_SEQ_KEY.as_bytes();
+    let mut current = backend
+        .get(key)
+        .await
+        .context(error::CatalogSnafu)?
+        .map(|Kv(_, v)| v);
+    loop {
+        let seq = match &current {
+            Some(value) => String::from_utf8_lossy(value).parse::<u64>().unwrap_or(0),
+            None => 0,
+        };
+        let expect = current.clone().unwrap_or_default();
+        match backend
+            .compare_and_set(key, &expect, (seq + 1).to_string().as_bytes())
+            .await
+            .context(error::CatalogSnafu)?
+        {
+            Ok(()) => return Ok((seq % MAX_CONN_NODES as u64) as u32),
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_value() {
+        let value = KillValue {
+            connection: true,
+            user: Some("alice".to_string()),
+            admin: false,
+            result: Some(KillResult::AccessDenied),
+        };
+        assert_eq!(value, KillValue::parse(&value.encode().unwrap()).unwrap());
+
+        assert_eq!(Some(42), parse_conn_id(b"__kill-42-1-0"));
+        assert_eq!(None, parse_conn_id(b"__kill-abc-1-0"));
+        assert_eq!(None, parse_conn_id(b"__u-alice"));
+    }
+}
@@ -0,0 +1,636 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quotas of users and databases: the number of concurrent queries, the rows returned and the
+//! duration of a query, and the rate of ingested rows.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use common_query::Output;
+use common_recordbatch::error::{Error as RecordBatchError, Result as RecordBatchResult};
+use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStream};
+use datatypes::schema::SchemaRef;
+use futures::{Future, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use servers::context::QuotaLimits;
+use snafu::ensure;
+use tokio::time::Sleep;
+
+use crate::error::{self, Result};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaOptions {
+    /// Limits of users without their own limits, sessions without users share them as well.
+    pub default: QuotaLimits,
+    /// Limits of users, by user name.
+    pub users: HashMap<String, QuotaLimits>,
+    /// Limits of databases, by database name, shared by all users of the database.
+    pub databases: HashMap<String, QuotaLimits>,
+}
+
+/// The owner of a quota, resources are accounted separately for each of them.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum QuotaKey {
+    User(Option<String>),
+    Database(String),
+}
+
+impl fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaKey::User(Some(user)) => write!(f, "user {}", user),
+            QuotaKey::User(None) => write!(f, "anonymous user"),
+            QuotaKey::Database(database) => write!(f, "database {}", database),
+        }
+    }
+}
+
+type RunningQueries = Arc<Mutex<HashMap<QuotaKey, usize>>>;
+
+pub struct QuotaManager {
+    options: QuotaOptions,
+    running_queries: RunningQueries,
+    ingest_limiters: Mutex<HashMap<QuotaKey, Arc<RateLimiter>>>,
+}
+
+impl Default for QuotaManager {
+    fn default() -> Self {
+        Self::new(QuotaOptions::default())
+    }
+}
+
+impl QuotaManager {
+    pub fn new(options: QuotaOptions) -> Self {
+        Self {
+            options,
+            running_queries: Arc::new(Mutex::new(HashMap::new())),
+            ingest_limiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the quotas a request of the `user` on the `database` is accounted to. The limits
+    /// of the user are the configured ones intersected with `session_limits`, the limits of the
+    /// user's session from [Quota](servers::context::Quota) of the request context.
+    fn quotas(
+        &self,
+        user: Option<&str>,
+        session_limits: &QuotaLimits,
+        database: &str,
+    ) -> Vec<(QuotaKey, QuotaLimits)> {
+        let user_limits = user
+            .and_then(|user| self.options.users.get(user))
+            .unwrap_or(&self.options.default)
+            .intersect(session_limits);
+        let mut quotas = vec![(QuotaKey::User(user.map(String::from)), user_limits)];
+        if let Some(database_limits) = self.options.databases.get(database) {
+            quotas.push((
+                QuotaKey::Database(database.to_string()),
+                database_limits.clone(),
+            ));
+        }
+        quotas
+    }
+
+    /// Returns the limits of requests of the `user` on the `database`, which is the stricter one
+    /// of the user's and the database's.
+    pub fn limits(
+        &self,
+        user: Option<&str>,
+        session_limits: &QuotaLimits,
+        database: &str,
+    ) -> QuotaLimits {
+        self.quotas(user, session_limits, database)
+            .into_iter()
+            .fold(QuotaLimits::default(), |acc, (_, limits)| {
+                acc.intersect(&limits)
+            })
+    }
+
+    /// Admits a query of the `user` on the `database`, fails if either of them already runs the
+    /// max number of concurrent queries. The query is counted as running until the returned
+    /// permit is dropped.
+    pub fn acquire(
+        &self,
+        user: Option<&str>,
+        session_limits: &QuotaLimits,
+        database: &str,
+    ) -> Result<QueryPermit> {
+        let quotas = self.quotas(user, session_limits, database);
+
+        let mut running_queries = self.running_queries.lock().unwrap();
+        for (key, limits) in &quotas {
+            if let Some(max) = limits.max_concurrent_queries {
+                let running = running_queries.get(key).copied().unwrap_or(0);
+                ensure!(
+                    running < max,
+                    error::QuotaExceededSnafu {
+                        reason: format!(
+                            "{} has reached the limit of {} concurrent queries",
+                            key, max
+                        ),
+                    }
+                );
+            }
+        }
+        for (key, _) in &quotas {
+            *running_queries.entry(key.clone()).or_default() += 1;
+        }
+
+        Ok(QueryPermit {
+            limits: self.limits(user, session_limits, database),
+            keys: quotas.into_iter().map(|(key, _)| key).collect(),
+            running_queries: self.running_queries.clone(),
+        })
+    }
+
+    /// Returns whether the rows ingested by the `user` into the `database` are rate limited.
+    pub fn is_ingest_limited(
+        &self,
+        user: Option<&str>,
+        session_limits: &QuotaLimits,
+        database: &str,
+    ) -> bool {
+        self.quotas(user, session_limits, database)
+            .iter()
+            .any(|(_, limits)| limits.max_ingest_rows_per_sec.is_some())
+    }
+
+    /// Waits until the `rows` ingested by the `user` into the `database` are allowed by the
+    /// ingest rates of them.
+    pub async fn throttle_ingest(
+        &self,
+        user: Option<&str>,
+        session_limits: &QuotaLimits,
+        database: &str,
+        rows: usize,
+    ) {
+        let delay = {
+            let mut limiters = self.ingest_limiters.lock().unwrap();
+            self.quotas(user, session_limits, database)
+                .into_iter()
+                .filter_map(|(key, limits)| {
+                    limits.max_ingest_rows_per_sec.map(|rate| {
+                        limiters
+                            .entry(key)
+                            .or_insert_with(|| Arc::new(RateLimiter::new(rate)))
+                            .clone()
+                    })
+                })
+                .map(|limiter| limiter.reserve(rows))
+                .max()
+                .unwrap_or_default()
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// A running query admitted by the [QuotaManager], it's no longer counted once dropped.
+pub struct QueryPermit {
+    limits: QuotaLimits,
+    keys: Vec<QuotaKey>,
+    running_queries: RunningQueries,
+}
+
+impl QueryPermit {
+    pub fn limits(&self) -> &QuotaLimits {
+        &self.limits
+    }
+
+    /// Runs the `query` within the timeout, then limits the rows of its output. The permit is
+    /// held by the output stream until it's exhausted or dropped.
+    pub async fn run<F>(self, query: F) -> Result<Output>
+    where
+        F: Future<Output = Result<Output>>,
+    {
+        let deadline = self
+            .limits
+            .query_timeout()
+            .map(|timeout| tokio::time::Instant::now() + timeout);
+        let output = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, query)
+                .await
+                .map_err(|_| self.timeout_error())??,
+            None => query.await?,
+        };
+
+        match output {
+            Output::AffectedRows(_) => Ok(output),
+            Output::RecordBatches(batches) => {
+                let rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+                if let Some(max_rows) = self.limits.max_result_rows {
+                    ensure!(
+                        rows <= max_rows,
+                        error::QuotaExceededSnafu {
+                            reason: too_many_rows_reason(max_rows),
+                        }
+                    );
+                }
+                Ok(Output::RecordBatches(batches))
+            }
+            Output::Stream(stream) => Ok(Output::Stream(Box::pin(QuotaRecordBatchStream::new(
+                stream, self, deadline,
+            )))),
+        }
+    }
+
+    /// Runs the `request` within the timeout, for requests whose results are not rows.
+    pub async fn run_request<T, F>(self, request: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        match self.limits.query_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, request)
+                .await
+                .map_err(|_| self.timeout_error())?,
+            None => request.await,
+        }
+    }
+
+    fn timeout_error(&self) -> error::Error {
+        error::QuotaExceededSnafu {
+            reason: format!(
+                "query is cancelled after the timeout of {}ms",
+                self.limits.query_timeout_millis.unwrap_or_default()
+            ),
+        }
+        .build()
+    }
+}
+
+impl Drop for QueryPermit {
+    fn drop(&mut self) {
+        let mut running_queries = self.running_queries.lock().unwrap();
+        for key in &self.keys {
+            if let Some(running) = running_queries.get_mut(key) {
+                *running -= 1;
+                if *running == 0 {
+                    running_queries.remove(key);
+                }
+            }
+        }
+    }
+}
+
+fn too_many_rows_reason(max_rows: usize) -> String {
+    format!(
+        "query is cancelled for returning more than {} rows",
+        max_rows
+    )
+}
+
+/// Results of a query running under a quota. Once the query returns too many rows or runs out of
+/// time, the underlying stream of the query engine is dropped to cancel the execution, and an
+/// error is returned instead.
+pub struct QuotaRecordBatchStream {
+    schema: SchemaRef,
+    stream: Option<SendableRecordBatchStream>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    rows: usize,
+    permit: Option<QueryPermit>,
+}
+
+impl QuotaRecordBatchStream {
+    fn new(
+        stream: SendableRecordBatchStream,
+        permit: QueryPermit,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Self {
+        Self {
+            schema: stream.schema(),
+            stream: Some(stream),
+            deadline: deadline.map(|deadline| Box::pin(tokio::time::sleep_until(deadline))),
+            rows: 0,
+            permit: Some(permit),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.stream = None;
+        self.deadline = None;
+        self.permit = None;
+    }
+
+    fn cancel(&mut self, err: error::Error) -> Poll<Option<RecordBatchResult<RecordBatch>>> {
+        self.finish();
+        Poll::Ready(Some(Err(RecordBatchError::new(err))))
+    }
+}
+
+impl RecordBatchStream for QuotaRecordBatchStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for QuotaRecordBatchStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let timed_out = this
+            .deadline
+            .as_mut()
+            .map(|deadline| deadline.as_mut().poll(cx).is_ready())
+            .unwrap_or(false);
+        if timed_out {
+            if let Some(permit) = &this.permit {
+                let err = permit.timeout_error();
+                return this.cancel(err);
+            }
+        }
+
+        let stream = match this.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(None),
+        };
+        match stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(batch))) => {
+                this.rows += batch.num_rows();
+                let max_rows = this
+                    .permit
+                    .as_ref()
+                    .and_then(|permit| permit.limits.max_result_rows);
+                match max_rows {
+                    Some(max_rows) if this.rows > max_rows => this.cancel(
+                        error::QuotaExceededSnafu {
+                            reason: too_many_rows_reason(max_rows),
+                        }
+                        .build(),
+                    ),
+                    _ => Poll::Ready(Some(Ok(batch))),
+                }
+            }
+            Poll::Ready(None) => {
+                this.finish();
+                Poll::Ready(None)
+            }
+            poll => poll,
+        }
+    }
+}
+
+/// Token bucket of ingested rows, allows bursts of up to one second's rows. Rows over the rate
+/// are still allowed, but the caller has to wait for the tokens they borrowed.
+struct RateLimiter {
+    rows_per_sec: f64,
+    state: Mutex<RateLimiterState>,
+}
+
+struct RateLimiterState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    fn new(rows_per_sec: u64) -> Self {
+        let rows_per_sec = rows_per_sec.max(1) as f64;
+        Self {
+            rows_per_sec,
+            state: Mutex::new(RateLimiterState {
+                tokens: rows_per_sec,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes the tokens of `rows`, returns how long to wait until they are paid off.
+    fn reserve(&self, rows: usize) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let refilled = now.duration_since(state.last_refill).as_secs_f64() * self.rows_per_sec;
+        state.tokens = (state.tokens + refilled).min(self.rows_per_sec);
+        state.last_refill = now;
+
+        state.tokens -= rows as f64;
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.rows_per_sec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_error::prelude::{ErrorExt, StatusCode};
+    use common_recordbatch::RecordBatches;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{UInt32Vector, VectorRef};
+
+    use super::*;
+
+    fn quota_options() -> QuotaOptions {
+        QuotaOptions {
+            default: QuotaLimits {
+                max_concurrent_queries: Some(2),
+                max_result_rows: Some(100),
+                ..Default::default()
+            },
+            users: HashMap::from([(
+                "alice".to_string(),
+                QuotaLimits {
+                    max_concurrent_queries: Some(1),
+                    query_timeout_millis: Some(10),
+                    ..Default::default()
+                },
+            )]),
+            databases: HashMap::from([(
+                "metrics".to_string(),
+                QuotaLimits {
+                    max_concurrent_queries: Some(3),
+                    max_result_rows: Some(10),
+                    max_ingest_rows_per_sec: Some(1000),
+                    ..Default::default()
+                },
+            )]),
+        }
+    }
+
+    fn record_batches(rows: u32) -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "n",
+            ConcreteDataType::uint32_datatype(),
+            false,
+        )]));
+        let batch = RecordBatch::new(
+            schema.clone(),
+            vec![Arc::new(UInt32Vector::from_vec((0..rows).collect())) as VectorRef],
+        )
+        .unwrap();
+        RecordBatches::try_new(schema, vec![batch.clone(), batch]).unwrap()
+    }
+
+    #[test]
+    fn test_quota_limits() {
+        let manager = QuotaManager::new(quota_options());
+        let unlimited = QuotaLimits::default();
+
+        let limits = manager.limits(None, &unlimited, "public");
+        assert_eq!(limits.max_concurrent_queries, Some(2));
+        assert_eq!(limits.max_result_rows, Some(100));
+
+        let limits = manager.limits(Some("alice"), &unlimited, "metrics");
+        assert_eq!(limits.max_concurrent_queries, Some(1));
+        assert_eq!(limits.max_result_rows, Some(10));
+        assert_eq!(limits.query_timeout_millis, Some(10));
+        assert_eq!(limits.max_ingest_rows_per_sec, Some(1000));
+
+        assert!(!manager.is_ingest_limited(Some("bob"), &unlimited, "public"));
+        assert!(manager.is_ingest_limited(Some("bob"), &unlimited, "metrics"));
+    }
+
+    #[test]
+    fn test_session_quota_limits() {
+        let manager = QuotaManager::new(quota_options());
+        let session_limits = QuotaLimits {
+            max_concurrent_queries: Some(1),
+            max_result_rows: Some(50),
+            query_timeout_millis: Some(100),
+            ..Default::default()
+        };
+
+        // The limits of the session are intersected with the configured ones.
+        let limits = manager.limits(Some("bob"), &session_limits, "public");
+        assert_eq!(limits.max_concurrent_queries, Some(1));
+        assert_eq!(limits.max_result_rows, Some(50));
+        assert_eq!(limits.query_timeout_millis, Some(100));
+        let limits = manager.limits(Some("alice"), &session_limits, "metrics");
+        assert_eq!(limits.max_result_rows, Some(10));
+        assert_eq!(limits.query_timeout_millis, Some(10));
+
+        let _permit = manager
+            .acquire(Some("bob"), &session_limits, "public")
+            .unwrap();
+        let err = manager
+            .acquire(Some("bob"), &session_limits, "public")
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+        // Sessions without limits of their own are still under the configured ones.
+        assert!(manager
+            .acquire(Some("bob"), &QuotaLimits::default(), "public")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_queries() {
+        let manager = QuotaManager::new(quota_options());
+        let unlimited = QuotaLimits::default();
+
+        let permit = manager
+            .acquire(Some("alice"), &unlimited, "public")
+            .unwrap();
+        let err = manager
+            .acquire(Some("alice"), &unlimited, "public")
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+        drop(permit);
+        let _permit = manager
+            .acquire(Some("alice"), &unlimited, "public")
+            .unwrap();
+
+        // Users share the quota of the database.
+        let _p1 = manager.acquire(Some("bob"), &unlimited, "metrics").unwrap();
+        let _p2 = manager.acquire(Some("bob"), &unlimited, "metrics").unwrap();
+        let _p3 = manager
+            .acquire(Some("carol"), &unlimited, "metrics")
+            .unwrap();
+        assert!(manager
+            .acquire(Some("dave"), &unlimited, "metrics")
+            .is_err());
+        assert!(manager.acquire(Some("dave"), &unlimited, "public").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_limit_result_rows() {
+        let manager = QuotaManager::new(quota_options());
+        let unlimited = QuotaLimits::default();
+
+        let permit = manager.acquire(None, &unlimited, "metrics").unwrap();
+        let output = permit
+            .run(async { Ok(Output::RecordBatches(record_batches(5))) })
+            .await
+            .unwrap();
+        assert!(matches!(output, Output::RecordBatches(_)));
+
+        let permit = manager.acquire(None, &unlimited, "metrics").unwrap();
+        let err = permit
+            .run(async { Ok(Output::RecordBatches(record_batches(6))) })
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+
+        let permit = manager.acquire(None, &unlimited, "metrics").unwrap();
+        let output = permit
+            .run(async { Ok(Output::Stream(record_batches(6).as_stream())) })
+            .await
+            .unwrap();
+        let mut stream = match output {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        assert_eq!(6, stream.next().await.unwrap().unwrap().num_rows());
+        let err = stream.next().await.unwrap().err().unwrap();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+        assert!(stream.next().await.is_none());
+        // The permit is released once the query is cancelled.
+        assert!(manager.running_queries.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() {
+        let manager = QuotaManager::new(quota_options());
+        let unlimited = QuotaLimits::default();
+
+        let permit = manager
+            .acquire(Some("alice"), &unlimited, "public")
+            .unwrap();
+        let err = permit
+            .run(async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(Output::AffectedRows(0))
+            })
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::QuotaExceeded, err.status_code());
+
+        let permit = manager
+            .acquire(Some("alice"), &unlimited, "public")
+            .unwrap();
+        let result = permit.run_request(async { Ok(1) }).await.unwrap();
+        assert_eq!(1, result);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(1000);
+        assert_eq!(Duration::ZERO, limiter.reserve(1000));
+        let delay = limiter.reserve(1000);
+        assert!(delay > Duration::from_millis(900) && delay <= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scripts of the frontend in distributed mode, which are stored in metasrv and shared by all the
+//! frontends. In standalone mode, the scripts are handled by the datanode.
+use catalog::remote::KvBackendRef;
+use query::QueryEngineRef;
+
+use crate::error::Result;
+
+#[cfg(not(feature = "python"))]
+mod dummy {
+    use servers::query_handler::ScriptHandlerRef;
+
+    use super::*;
+
+    /// Scripts are not supported without python.
+    pub async fn start_script_handler(
+        _backend: KvBackendRef,
+        _query_engine: QueryEngineRef,
+    ) -> Result<Option<ScriptHandlerRef>> {
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "python")]
+mod python {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use common_error::prelude::BoxedError;
+    use common_query::Output;
+    use common_telemetry::logging::error;
+    use script::remote::RemoteScriptManager;
+    use servers::query_handler::{ScriptHandler, ScriptHandlerRef};
+    use snafu::ResultExt;
+
+    use super::*;
+
+    struct ScriptExecutor {
+        script_manager: Arc<RemoteScriptManager>,
+    }
+
+    /// Loads the scripts in metasrv, so the ones declared as udf are registered to the query
+    /// engine, and returns the handler of scripts.
+    pub async fn start_script_handler(
+        backend: KvBackendRef,
+        query_engine: QueryEngineRef,
+    ) -> Result<Option<ScriptHandlerRef>> {
+        let script_manager = RemoteScriptManager::start(backend, query_engine)
+            .await
+            .context(crate::error::StartScriptManagerSnafu)?;
+        Ok(Some(Arc::new(ScriptExecutor { script_manager })))
+    }
+
+    #[async_trait]
+    impl ScriptHandler for ScriptExecutor {
+        async fn insert_script(
+            &self,
+            schema: &str,
+            name: &str,
+            script: &str,
+        ) -> servers::error::Result<()> {
+            let _s = self
+                .script_manager
+                .insert_and_compile(schema, name, script)
+                .await
+                .map_err(|e| {
+                    error!(e; "Frontend failed to insert script");
+                    BoxedError::new(e)
+                })
+                .context(servers::error::InsertScriptSnafu { name })?;
+
+            Ok(())
+        }
+
+        async fn execute_script(&self, schema: &str, name: &str) -> servers::error::Result<Output> {
+            self.script_manager
+                .execute(schema, name)
+                .await
+                .map_err(|e| {
+                    error!(e; "Frontend failed to execute script");
+                    BoxedError::new(e)
+                })
+                .context(servers::error::ExecuteScriptSnafu { name })
+        }
+    }
+}
+
+#[cfg(not(feature = "python"))]
+pub use self::dummy::*;
+#[cfg(feature = "python")]
+pub use self::python::*;
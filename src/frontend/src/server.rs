@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_runtime::Builder as RuntimeBuilder;
 use common_telemetry::info;
+use servers::auth::user_provider_from_option;
 use servers::grpc::GrpcServer;
 use servers::http::HttpServer;
 use servers::mysql::server::MysqlServer;
@@ -40,6 +41,13 @@ impl Services {
         T: FrontendInstance,
     {
         info!("Starting frontend servers");
+        let user_provider = opts
+            .user_provider
+            .as_ref()
+            .map(|opt| user_provider_from_option(opt))
+            .transpose()
+            .context(error::CreateUserProviderSnafu)?;
+
         let grpc_server_and_addr = if let Some(opts) = &opts.grpc_options {
             let grpc_addr = parse_addr(&opts.addr)?;
 
@@ -51,7 +59,13 @@ impl Services {
                     .context(error::RuntimeResourceSnafu)?,
             );
 
-            let grpc_server = GrpcServer::new(instance.clone(), instance.clone(), grpc_runtime);
+            let mut grpc_server = GrpcServer::new(instance.clone(), instance.clone(), grpc_runtime);
+            grpc_server
+                .set_tls(&opts.tls)
+                .context(error::SetupTlsSnafu)?;
+            if let Some(user_provider) = user_provider.clone() {
+                grpc_server.set_user_provider(user_provider);
+            }
 
             Some((Box::new(grpc_server) as _, grpc_addr))
         } else {
@@ -69,7 +83,12 @@ impl Services {
                     .context(error::RuntimeResourceSnafu)?,
             );
 
-            let mysql_server = MysqlServer::create_server(instance.clone(), mysql_io_runtime);
+            let mysql_server = MysqlServer::create_server(
+                instance.clone(),
+                mysql_io_runtime,
+                user_provider.clone(),
+                opts.tls.setup().context(error::SetupTlsSnafu)?,
+            );
 
             Some((mysql_server, mysql_addr))
         } else {
@@ -89,7 +108,9 @@ impl Services {
 
             let pg_server = Box::new(PostgresServer::new(
                 instance.clone(),
-                opts.check_pwd,
+                user_provider.clone(),
+                opts.auth_method,
+                opts.tls.setup().context(error::SetupTlsSnafu)?,
                 pg_io_runtime,
             )) as Box<dyn Server>;
 
@@ -137,6 +158,10 @@ impl Services {
                 http_server.set_prom_handler(instance.clone());
             }
             http_server.set_script_handler(instance.clone());
+            http_server.set_tls(opts.http_tls.setup().context(error::SetupTlsSnafu)?);
+            if let Some(user_provider) = user_provider.clone() {
+                http_server.set_user_provider(user_provider);
+            }
 
             Some((Box::new(http_server) as _, http_addr))
         } else {
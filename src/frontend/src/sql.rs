@@ -16,6 +16,7 @@ use catalog::SchemaProviderRef;
 use common_error::snafu::ensure;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::vectors::VectorBuilder;
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 use sql::ast::Value as SqlValue;
 use sql::statements;
@@ -29,11 +30,13 @@ use crate::error::{self, Result};
 pub(crate) fn insert_to_request(
     schema_provider: &SchemaProviderRef,
     stmt: Insert,
+    query_ctx: QueryContextRef,
 ) -> Result<InsertRequest> {
     let columns = stmt.columns();
     let values = stmt.values().context(error::ParseSqlSnafu)?;
-    let (catalog_name, schema_name, table_name) =
-        stmt.full_table_name().context(error::ParseSqlSnafu)?;
+    let (catalog_name, schema_name, table_name) = stmt
+        .full_table_name(&query_ctx)
+        .context(error::ParseSqlSnafu)?;
 
     let table = schema_provider
         .table(&table_name)
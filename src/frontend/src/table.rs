@@ -12,41 +12,51 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod filter;
+pub(crate) mod pushdown;
 pub(crate) mod route;
 
 use std::any::Any;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
+use api::v1::codec::OperatorMetrics;
 use async_trait::async_trait;
 use client::Database;
 use common_query::error::Result as QueryResult;
 use common_query::logical_plan::Expr;
-use common_query::physical_plan::{PhysicalPlan, PhysicalPlanRef};
+use common_query::physical_plan::{
+    DisplayFormatType, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet, PhysicalPlan,
+    PhysicalPlanAdapter, PhysicalPlanRef,
+};
 use common_recordbatch::{RecordBatches, SendableRecordBatchStream};
+use common_telemetry::warn;
 use datafusion::execution::runtime_env::RuntimeEnv;
-use datafusion::logical_plan::Expr as DfExpr;
+use datafusion::logical_plan::{unnormalize_col, LogicalPlan as DfLogicalPlan};
+use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::Partitioning;
 use datatypes::prelude::Value;
 use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
 use meta_client::rpc::{Peer, TableName};
 use snafu::prelude::*;
-use store_api::storage::RegionNumber;
+use store_api::storage::{DataVersion, RegionNumber};
 use table::error::Error as TableError;
-use table::metadata::{FilterPushDownType, TableInfoRef};
+use table::metadata::{FilterPushDownType, TableInfoBuilder, TableInfoRef, TableMetaBuilder};
 use table::requests::InsertRequest;
 use table::Table;
 use tokio::sync::RwLock;
 
 use crate::datanode::DatanodeClients;
 use crate::error::{self, Error, Result};
-use crate::mock::{DatanodeInstance, TableScanPlan};
+use crate::mock::{build_table_scan_plan, is_remote_filter, DatanodeInstance, TableScanPlan};
 use crate::partitioning::columns::RangeColumnsPartitionRule;
+use crate::partitioning::hash::HashPartitionRule;
 use crate::partitioning::range::RangePartitionRule;
-use crate::partitioning::{
-    Operator, PartitionBound, PartitionDef, PartitionExpr, PartitionRuleRef,
-};
+use crate::partitioning::{PartitionBound, PartitionDef, PartitionRuleRef};
 use crate::spliter::WriteSpliter;
+use crate::table::filter::PartitionFilter;
 use crate::table::route::TableRoutes;
 pub mod insert;
 
@@ -69,7 +79,7 @@ impl Table for DistTable {
     }
 
     fn table_info(&self) -> TableInfoRef {
-        unimplemented!()
+        build_table_info(&self.table_name, self.schema.clone())
     }
 
     async fn insert(&self, request: InsertRequest) -> table::Result<usize> {
@@ -90,137 +100,115 @@ impl Table for DistTable {
         filters: &[Expr],
         limit: Option<usize>,
     ) -> table::Result<PhysicalPlanRef> {
-        let partition_rule = self.find_partition_rule().await.map_err(TableError::new)?;
-
-        let regions = self
-            .find_regions(partition_rule, filters)
-            .map_err(TableError::new)?;
-        let datanodes = self
-            .find_datanodes(regions)
+        let plan = build_table_scan_plan(
+            Arc::new(self.clone()),
+            &TableScanPlan {
+                table_name: self.table_name.clone(),
+                projection: projection.clone(),
+                filters: filters.to_vec(),
+                limit,
+            },
+        );
+        let schema = project_schema(self.schema(), projection);
+        self.scan_with_plan(plan, schema, filters)
             .await
-            .map_err(TableError::new)?;
+            .map_err(TableError::new)
+    }
+
+    fn supports_filter_pushdown(&self, filter: &Expr) -> table::Result<FilterPushDownType> {
+        // Filters executed on datanodes are not needed to be evaluated again in frontend.
+        let filter = unnormalize_col(filter.df_expr().clone());
+        if is_remote_filter(Arc::new(self.clone()), &filter) {
+            Ok(FilterPushDownType::Exact)
+        } else {
+            Ok(FilterPushDownType::Inexact)
+        }
+    }
+
+    async fn data_versions(&self) -> Option<Vec<DataVersion>> {
+        match self.find_data_versions().await {
+            Ok(versions) => Some(versions),
+            Err(e) => {
+                warn!(
+                    "Failed to find data versions of table {}, error: {}",
+                    self.table_name, e
+                );
+                None
+            }
+        }
+    }
+}
+
+impl DistTable {
+    /// Executes `plan` on all datanodes holding the regions selected by `filters`.
+    /// `schema` is the output schema of `plan`.
+    pub(crate) async fn scan_with_plan(
+        &self,
+        plan: DfLogicalPlan,
+        schema: SchemaRef,
+        filters: &[Expr],
+    ) -> Result<PhysicalPlanRef> {
+        let partition_rule = self.find_partition_rule().await?;
+
+        let regions = self.find_regions(partition_rule, filters)?;
+        // Contradictory filters may prune all the regions, in which case nothing is to be scanned.
+        if regions.is_empty() {
+            let empty = EmptyExec::new(false, schema.arrow_schema().clone());
+            return Ok(Arc::new(PhysicalPlanAdapter::new(schema, Arc::new(empty))));
+        }
+        let total_regions = self
+            .table_routes
+            .get_route(&self.table_name)
+            .await?
+            .region_routes
+            .len();
+        let datanodes = self.find_datanodes(regions.clone()).await?;
 
         let mut partition_execs = Vec::with_capacity(datanodes.len());
         for (datanode, _regions) in datanodes.iter() {
             let client = self.datanode_clients.get_client(datanode).await;
             let db = Database::new(&self.table_name.schema_name, client);
-            let datanode_instance = DatanodeInstance::new(Arc::new(self.clone()) as _, db);
+            let datanode_instance = DatanodeInstance::new(db);
 
             // TODO(LFC): Pass in "regions" when Datanode supports multi regions for a table.
             partition_execs.push(PartitionExec {
-                table_name: self.table_name.clone(),
+                datanode: datanode.clone(),
                 datanode_instance,
-                projection: projection.clone(),
-                filters: filters.to_vec(),
-                limit,
+                plan: plan.clone(),
                 batches: Arc::new(RwLock::new(None)),
             })
         }
 
         let dist_scan = DistTableScan {
-            schema: project_schema(self.schema(), projection),
+            table_name: self.table_name.clone(),
+            schema,
+            regions,
+            total_regions,
             partition_execs,
+            metrics: ExecutionPlanMetricsSet::new(),
         };
         Ok(Arc::new(dist_scan))
     }
 
-    fn supports_filter_pushdown(&self, _filter: &Expr) -> table::Result<FilterPushDownType> {
-        Ok(FilterPushDownType::Inexact)
-    }
-}
-
-impl DistTable {
-    // TODO(LFC): Finding regions now seems less efficient, should be further looked into.
     fn find_regions(
         &self,
         partition_rule: PartitionRuleRef<Error>,
         filters: &[Expr],
     ) -> Result<Vec<RegionNumber>> {
-        let regions = if let Some((first, rest)) = filters.split_first() {
-            let mut target = self.find_regions0(partition_rule.clone(), first)?;
-            for filter in rest {
-                let regions = self.find_regions0(partition_rule.clone(), filter)?;
-
-                // When all filters are provided as a collection, it often implicitly states that
-                // "all filters must be satisfied". So we join all the results here.
-                target.retain(|x| regions.contains(x));
-
-                // Failed fast, empty collection join any is empty.
-                if target.is_empty() {
-                    break;
-                }
-            }
-            target.into_iter().collect::<Vec<_>>()
-        } else {
-            partition_rule.find_regions(&[])?
-        };
-        ensure!(
-            !regions.is_empty(),
-            error::FindRegionsSnafu {
-                filters: filters.to_vec()
-            }
+        // When all filters are provided as a collection, it often implicitly states that
+        // "all filters must be satisfied". So they are treated as a conjunction here.
+        let filter = PartitionFilter::and(
+            filters
+                .iter()
+                .map(|x| PartitionFilter::analyze(x.df_expr()))
+                .collect::<Result<Vec<_>>>()?,
         );
-        Ok(regions)
-    }
-
-    // TODO(LFC): Support other types of filter expr:
-    //   - BETWEEN and IN (maybe more)
-    //   - expr with arithmetic like "a + 1 < 10" (should have been optimized in logic plan?)
-    //   - not comparison or neither "AND" nor "OR" operations, for example, "a LIKE x"
-    fn find_regions0(
-        &self,
-        partition_rule: PartitionRuleRef<Error>,
-        filter: &Expr,
-    ) -> Result<HashSet<RegionNumber>> {
-        let expr = filter.df_expr();
-        match expr {
-            DfExpr::BinaryExpr { left, op, right } if is_compare_op(op) => {
-                let column_op_value = match (left.as_ref(), right.as_ref()) {
-                    (DfExpr::Column(c), DfExpr::Literal(v)) => Some((&c.name, *op, v)),
-                    (DfExpr::Literal(v), DfExpr::Column(c)) => {
-                        Some((&c.name, reverse_operator(op), v))
-                    }
-                    _ => None,
-                };
-                if let Some((column, op, sv)) = column_op_value {
-                    let value = sv
-                        .clone()
-                        .try_into()
-                        .with_context(|_| error::ConvertScalarValueSnafu { value: sv.clone() })?;
-                    return Ok(partition_rule
-                        .find_regions(&[PartitionExpr::new(column, op, value)])?
-                        .into_iter()
-                        .collect::<HashSet<RegionNumber>>());
-                }
-            }
-            DfExpr::BinaryExpr { left, op, right }
-                if matches!(op, Operator::And | Operator::Or) =>
-            {
-                let left_regions =
-                    self.find_regions0(partition_rule.clone(), &(*left.clone()).into())?;
-                let right_regions =
-                    self.find_regions0(partition_rule.clone(), &(*right.clone()).into())?;
-                let regions = match op {
-                    Operator::And => left_regions
-                        .intersection(&right_regions)
-                        .cloned()
-                        .collect::<HashSet<RegionNumber>>(),
-                    Operator::Or => left_regions
-                        .union(&right_regions)
-                        .cloned()
-                        .collect::<HashSet<RegionNumber>>(),
-                    _ => unreachable!(),
-                };
-                return Ok(regions);
-            }
-            _ => (),
-        }
-
-        // Returns all regions for not supported partition expr as a safety hatch.
-        Ok(partition_rule
-            .find_regions(&[])?
+        let mut regions = filter
+            .find_regions(&partition_rule)?
             .into_iter()
-            .collect::<HashSet<RegionNumber>>())
+            .collect::<Vec<_>>();
+        regions.sort_unstable();
+        Ok(regions)
     }
 
     async fn find_datanodes(
@@ -250,6 +238,36 @@ impl DistTable {
         Ok(datanodes)
     }
 
+    /// Selects the data versions of all regions of the table from the datanodes holding them,
+    /// in the order of datanode ids.
+    async fn find_data_versions(&self) -> Result<Vec<DataVersion>> {
+        let route = self.table_routes.get_route(&self.table_name).await?;
+        let regions = route
+            .region_routes
+            .iter()
+            .map(|x| x.region.id as RegionNumber)
+            .collect::<Vec<_>>();
+        let mut datanodes = self
+            .find_datanodes(regions)
+            .await?
+            .into_keys()
+            .collect::<Vec<_>>();
+        datanodes.sort_unstable_by_key(|x| x.id);
+
+        let mut versions = Vec::new();
+        for datanode in datanodes.iter() {
+            let client = self.datanode_clients.get_client(datanode).await;
+            let db = Database::new(&self.table_name.schema_name, client);
+            let datanode_instance = DatanodeInstance::new(db);
+            versions.extend(
+                datanode_instance
+                    .grpc_data_versions(&self.table_name)
+                    .await?,
+            );
+        }
+        Ok(versions)
+    }
+
     async fn find_partition_rule(&self) -> Result<PartitionRuleRef<Error>> {
         let route = self.table_routes.get_route(&self.table_name).await?;
         ensure!(
@@ -298,6 +316,25 @@ impl DistTable {
             .collect::<Vec<RegionNumber>>();
 
         // TODO(LFC): Serializing and deserializing partition rule is ugly, must find a much more elegant way.
+        if let PartitionBound::Hash { num, .. } = &partitions[0].1.partition_bounds()[0] {
+            ensure!(
+                partitions.len() == *num as usize,
+                error::IllegalTableRoutesDataSnafu {
+                    table_name: self.table_name.to_string(),
+                    err_msg: format!(
+                        "expect {} hash partitions, actual {}",
+                        num,
+                        partitions.len()
+                    ),
+                }
+            );
+            // Regions are sorted by their hash partition indexes above.
+            return Ok(Arc::new(HashPartitionRule::new(
+                partition_columns.clone(),
+                regions,
+            )));
+        }
+
         let partition_rule: PartitionRuleRef<Error> = match partition_columns.len() {
             1 => {
                 // Omit the last "MAXVALUE".
@@ -305,7 +342,7 @@ impl DistTable {
                     .iter()
                     .filter_map(|(_, p)| match &p.partition_bounds()[0] {
                         PartitionBound::Value(v) => Some(v.clone()),
-                        PartitionBound::MaxValue => None,
+                        PartitionBound::MaxValue | PartitionBound::Hash { .. } => None,
                     })
                     .collect::<Vec<Value>>();
                 Arc::new(RangePartitionRule::new(
@@ -330,7 +367,27 @@ impl DistTable {
     }
 }
 
-fn project_schema(table_schema: SchemaRef, projection: &Option<Vec<usize>>) -> SchemaRef {
+/// Builds the table info of tables living in frontend. Only the names and schema are filled,
+/// which are enough for encoding the plans sent to datanodes.
+pub(crate) fn build_table_info(table_name: &TableName, schema: SchemaRef) -> TableInfoRef {
+    let meta = TableMetaBuilder::default()
+        .schema(schema)
+        .primary_key_indices(vec![])
+        .next_column_id(0)
+        .build()
+        .expect("all required fields of table meta are set");
+    let table_info = TableInfoBuilder::new(&table_name.table_name, meta)
+        .catalog_name(&table_name.catalog_name)
+        .schema_name(&table_name.schema_name)
+        .build()
+        .expect("all required fields of table info are set");
+    Arc::new(table_info)
+}
+
+pub(crate) fn project_schema(
+    table_schema: SchemaRef,
+    projection: &Option<Vec<usize>>,
+) -> SchemaRef {
     if let Some(projection) = &projection {
         let columns = table_schema.column_schemas();
         let projected = projection
@@ -343,32 +400,15 @@ fn project_schema(table_schema: SchemaRef, projection: &Option<Vec<usize>>) -> S
     }
 }
 
-fn is_compare_op(op: &Operator) -> bool {
-    matches!(
-        *op,
-        Operator::Eq
-            | Operator::NotEq
-            | Operator::Lt
-            | Operator::LtEq
-            | Operator::Gt
-            | Operator::GtEq
-    )
-}
-
-fn reverse_operator(op: &Operator) -> Operator {
-    match *op {
-        Operator::Lt => Operator::Gt,
-        Operator::Gt => Operator::Lt,
-        Operator::LtEq => Operator::GtEq,
-        Operator::GtEq => Operator::LtEq,
-        _ => *op,
-    }
-}
-
 #[derive(Debug)]
 struct DistTableScan {
+    table_name: TableName,
     schema: SchemaRef,
+    /// The regions to scan, selected by the filters out of `total_regions`.
+    regions: Vec<RegionNumber>,
+    total_regions: usize,
     partition_execs: Vec<PartitionExec>,
+    metrics: ExecutionPlanMetricsSet,
 }
 
 #[async_trait]
@@ -399,40 +439,107 @@ impl PhysicalPlan for DistTableScan {
         _runtime: Arc<RuntimeEnv>,
     ) -> QueryResult<SendableRecordBatchStream> {
         let exec = &self.partition_execs[partition];
-        exec.maybe_init().await;
+        // Labels the metrics by datanode, so that they are not merged across partitions.
+        let datanode = exec.datanode.id.to_string();
+        let timer = MetricBuilder::new(&self.metrics)
+            .with_new_label("datanode", datanode.clone())
+            .subset_time("elapsed", partition)
+            .timer();
+        let remote_metrics = exec.maybe_init().await;
+        timer.done();
+
+        MetricBuilder::new(&self.metrics)
+            .with_new_label("datanode", datanode.clone())
+            .counter("rows", partition)
+            .add(exec.num_rows().await);
+        self.record_remote_metrics(&datanode, partition, remote_metrics);
         Ok(exec.as_stream().await)
     }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let datanodes = self
+            .partition_execs
+            .iter()
+            .map(|exec| exec.datanode.id.to_string())
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "DistTableScan: table={}, regions={:?} of {}, datanodes=[{}]",
+            self.table_name,
+            self.regions,
+            self.total_regions,
+            datanodes.join(", ")
+        )
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DistTableScan {
+    /// Merges the metrics of the operators executing the plan on the datanode, like the storage
+    /// metrics of its table scan, labeled by the datanode and the operator.
+    fn record_remote_metrics(
+        &self,
+        datanode: &str,
+        partition: usize,
+        remote_metrics: Vec<OperatorMetrics>,
+    ) {
+        for operator in remote_metrics {
+            let operator_name = operator.operator;
+            let builder = || {
+                MetricBuilder::new(&self.metrics)
+                    .with_new_label("datanode", datanode.to_string())
+                    .with_new_label("operator", operator_name.clone())
+            };
+            for (name, value) in operator.counters {
+                builder().counter(name, partition).add(value as usize);
+            }
+            for (name, value) in operator.times {
+                builder()
+                    .subset_time(name, partition)
+                    .add_duration(Duration::from_nanos(value));
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 struct PartitionExec {
-    table_name: TableName,
+    datanode: Peer,
     datanode_instance: DatanodeInstance,
-    projection: Option<Vec<usize>>,
-    filters: Vec<Expr>,
-    limit: Option<usize>,
+    plan: DfLogicalPlan,
     batches: Arc<RwLock<Option<RecordBatches>>>,
 }
 
 impl PartitionExec {
-    async fn maybe_init(&self) {
+    /// Executes the plan on the datanode if not yet, returns the metrics of the execution.
+    async fn maybe_init(&self) -> Vec<OperatorMetrics> {
         if self.batches.read().await.is_some() {
-            return;
+            return vec![];
         }
 
         let mut batches = self.batches.write().await;
         if batches.is_some() {
-            return;
+            return vec![];
         }
 
-        let plan = TableScanPlan {
-            table_name: self.table_name.clone(),
-            projection: self.projection.clone(),
-            filters: self.filters.clone(),
-            limit: self.limit,
-        };
-        let result = self.datanode_instance.grpc_table_scan(plan).await;
+        let (result, metrics) = self
+            .datanode_instance
+            .grpc_logical_plan(self.plan.clone())
+            .await;
         let _ = batches.insert(result);
+        metrics
+    }
+
+    async fn num_rows(&self) -> usize {
+        self.batches
+            .read()
+            .await
+            .as_ref()
+            .map(|batches| batches.iter().map(|batch| batch.num_rows()).sum())
+            .unwrap_or(0)
     }
 
     async fn as_stream(&self) -> SendableRecordBatchStream {
@@ -470,9 +577,11 @@ mod test {
     use meta_srv::mocks::MockInfo;
     use meta_srv::service::store::kv::KvStoreRef;
     use meta_srv::service::store::memory::MemStore;
+    use session::context::QueryContext;
     use sql::parser::ParserContext;
     use sql::statements::statement::Statement;
     use sqlparser::dialect::GenericDialect;
+    use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
     use table::TableRef;
     use tempdir::TempDir;
 
@@ -481,6 +590,7 @@ mod test {
     use crate::expr_factory::{CreateExprFactory, DefaultCreateExprFactory};
     use crate::instance::distributed::DistInstance;
     use crate::partitioning::range::RangePartitionRule;
+    use crate::partitioning::Operator;
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_find_partition_rule() {
@@ -667,6 +777,62 @@ mod test {
         assert_eq!(range_columns_rule.regions(), &vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_build_table_scan_plan() {
+        let column_schemas = vec![
+            ColumnSchema::new("ts", ConcreteDataType::int64_datatype(), false),
+            ColumnSchema::new("a", ConcreteDataType::int32_datatype(), true),
+            ColumnSchema::new("row_id", ConcreteDataType::int32_datatype(), true),
+        ];
+        let table_name = TableName::new("greptime", "public", "dist_numbers");
+        let table = DistTable {
+            table_name: table_name.clone(),
+            schema: Arc::new(Schema::new(column_schemas)),
+            table_routes: Arc::new(TableRoutes::new(Arc::new(MetaClient::default()))),
+            datanode_clients: Arc::new(DatanodeClients::new()),
+        };
+        let filter: Expr = binary_expr(col("a"), Operator::Lt, lit(10)).into();
+        assert_eq!(
+            FilterPushDownType::Exact,
+            table.supports_filter_pushdown(&filter).unwrap()
+        );
+
+        // select row_id from dist_numbers where a < 10 limit 5
+        let plan = build_table_scan_plan(
+            Arc::new(table),
+            &TableScanPlan {
+                table_name,
+                projection: Some(vec![2]),
+                filters: vec![filter],
+                limit: Some(5),
+            },
+        );
+        // The filter, projection and limit are all executed on datanodes.
+        let limit = match &plan {
+            DfLogicalPlan::Limit(limit) => limit,
+            _ => unreachable!(),
+        };
+        assert_eq!(5, limit.n);
+        let projection = match limit.input.as_ref() {
+            DfLogicalPlan::Projection(projection) => projection,
+            _ => unreachable!(),
+        };
+        assert_eq!(1, projection.schema.fields().len());
+        assert_eq!("row_id", projection.schema.field(0).name());
+        let filter = match projection.input.as_ref() {
+            DfLogicalPlan::Filter(filter) => filter,
+            _ => unreachable!(),
+        };
+        // Column "a" is scanned for the filter and projected away.
+        match filter.input.as_ref() {
+            DfLogicalPlan::TableScan(table_scan) => {
+                assert_eq!(Some(vec![2, 1]), table_scan.projection)
+            }
+            _ => unreachable!(),
+        }
+        assert!(DFLogicalSubstraitConvertor.encode(plan).is_ok());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_dist_table_scan() {
         common_telemetry::init_default_ut_logging();
@@ -713,6 +879,27 @@ mod test {
         )
         .into()];
         exec_table_scan(table.clone(), projection, filters, None).await;
+
+        // should scan no regions and return nothing
+        // select a, row_id from numbers where a < 1 and a > 5
+        let projection = Some(vec![1, 2]);
+        let filters = vec![and(
+            binary_expr(col("a"), Operator::Lt, lit(1)),
+            binary_expr(col("a"), Operator::Gt, lit(5)),
+        )
+        .into()];
+        let table_scan = table
+            .scan(&projection, filters.as_slice(), None)
+            .await
+            .unwrap();
+        for partition in 0..table_scan.output_partitioning().partition_count() {
+            let result = table_scan
+                .execute(partition, Arc::new(RuntimeEnv::default()))
+                .await
+                .unwrap();
+            let recordbatches = util::collect(result).await.unwrap();
+            assert!(recordbatches.iter().all(|r| r.num_rows() == 0));
+        }
     }
 
     async fn exec_table_scan(
@@ -743,13 +930,20 @@ mod test {
             let pretty_print = pretty_print.lines().collect::<Vec<&str>>();
             pretty_print.iter().for_each(|x| println!("{}", x));
         }
+
+        // The metrics of the plans executed on datanodes are merged into the scan.
+        let metrics = table_scan.metrics().unwrap();
+        assert!(metrics.iter().any(|metric| metric
+            .labels()
+            .iter()
+            .any(|label| label.name() == "operator")));
     }
 
     async fn new_dist_table() -> DistTable {
         let column_schemas = vec![
-            ColumnSchema::new("ts", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("ts", ConcreteDataType::int64_datatype(), false),
             ColumnSchema::new("a", ConcreteDataType::int32_datatype(), true),
-            ColumnSchema::new("row_id", ConcreteDataType::uint32_datatype(), true),
+            ColumnSchema::new("row_id", ConcreteDataType::int32_datatype(), true),
         ];
         let schema = Arc::new(Schema::new(column_schemas.clone()));
 
@@ -825,7 +1019,10 @@ mod test {
         wait_datanodes_alive(kv_store).await;
 
         let factory = DefaultCreateExprFactory {};
-        let mut expr = factory.create_expr_by_stmt(&create_table).await.unwrap();
+        let mut expr = factory
+            .create_expr_by_stmt(&create_table, QueryContext::arc())
+            .await
+            .unwrap();
         let _result = dist_instance
             .create_table(&mut expr, create_table.partitions)
             .await
@@ -1074,19 +1271,21 @@ mod test {
             vec![0, 1],
         );
 
-        // test failed to find regions by contradictory filters
-        let regions = table.find_regions(
-            partition_rule,
+        // test finding no regions by contradictory filters
+        test(
             vec![and(
                 binary_expr(col("a"), Operator::Lt, lit(20)),
                 binary_expr(col("a"), Operator::GtEq, lit(20)),
             )
-            .into()]
-            .as_slice(),
-        ); // a < 20 AND a >= 20
-        assert!(matches!(
-            regions.unwrap_err(),
-            error::Error::FindRegions { .. }
-        ));
+            .into()], // a < 20 AND a >= 20
+            vec![],
+        );
+        test(
+            vec![
+                binary_expr(col("a"), Operator::Lt, lit(1)).into(),
+                binary_expr(col("a"), Operator::Gt, lit(5)).into(),
+            ], // [a < 1, a > 5]
+            vec![],
+        );
     }
 }
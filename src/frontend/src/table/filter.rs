@@ -0,0 +1,511 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Analyzes the filters of queries to find the regions of a [DistTable](crate::table::DistTable)
+//! that may contain the rows satisfying them.
+
+use std::collections::HashSet;
+use std::slice;
+use std::sync::Arc;
+
+use arrow::compute::cast::{self, CastOptions};
+use datafusion::logical_plan::Expr as DfExpr;
+use datafusion_common::ScalarValue;
+use datatypes::prelude::Value;
+use snafu::ResultExt;
+use store_api::storage::RegionNumber;
+
+use crate::error::{self, Error, Result};
+use crate::partitioning::{Operator, PartitionExpr, PartitionRuleRef};
+
+/// A filter normalized for finding regions. "NOT"s are pushed down into the comparisons, and
+/// "IN" and "BETWEEN" are expanded into comparisons.
+#[derive(Debug, PartialEq)]
+pub(crate) enum PartitionFilter {
+    /// The filter can not be used in finding regions, like filters of functions or columns
+    /// compared with each other, so rows in any region may satisfy it.
+    Unknown,
+    /// No row satisfies the filter.
+    Never,
+    /// Comparison between a column and a literal.
+    Compare(PartitionExpr),
+    And(Vec<PartitionFilter>),
+    Or(Vec<PartitionFilter>),
+}
+
+impl PartitionFilter {
+    pub(crate) fn analyze(expr: &DfExpr) -> Result<Self> {
+        analyze(expr, false)
+    }
+
+    /// Conjunction of the filters, nested conjunctions are flattened so that all comparisons in
+    /// them are handed to the partition rule together.
+    pub(crate) fn and(filters: Vec<PartitionFilter>) -> Self {
+        let mut flattened = Vec::with_capacity(filters.len());
+        for filter in filters {
+            match filter {
+                PartitionFilter::And(filters) => flattened.extend(filters),
+                PartitionFilter::Unknown => {}
+                filter => flattened.push(filter),
+            }
+        }
+        PartitionFilter::And(flattened)
+    }
+
+    pub(crate) fn or(filters: Vec<PartitionFilter>) -> Self {
+        let mut flattened = Vec::with_capacity(filters.len());
+        for filter in filters {
+            match filter {
+                PartitionFilter::Or(filters) => flattened.extend(filters),
+                PartitionFilter::Never => {}
+                filter => flattened.push(filter),
+            }
+        }
+        PartitionFilter::Or(flattened)
+    }
+
+    /// Finds the regions that may contain the rows satisfying the filter.
+    ///
+    /// Regions of a conjunction are the intersection of the regions of its parts, and those of a
+    /// disjunction are the union. Comparisons in a conjunction are handed to the partition rule
+    /// together, so that it can prune regions by all partitioning columns at once.
+    pub(crate) fn find_regions(
+        &self,
+        partition_rule: &PartitionRuleRef<Error>,
+    ) -> Result<HashSet<RegionNumber>> {
+        let regions = match self {
+            PartitionFilter::Unknown => partition_rule.find_regions(&[])?.into_iter().collect(),
+            PartitionFilter::Never => HashSet::new(),
+            PartitionFilter::Compare(expr) => partition_rule
+                .find_regions(slice::from_ref(expr))?
+                .into_iter()
+                .collect(),
+            PartitionFilter::And(filters) => {
+                let exprs = filters
+                    .iter()
+                    .filter_map(|filter| match filter {
+                        PartitionFilter::Compare(expr) => Some(expr.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let mut regions = partition_rule
+                    .find_regions(&exprs)?
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                for filter in filters {
+                    // Failed fast, empty collection join any is empty.
+                    if regions.is_empty() {
+                        break;
+                    }
+                    if !matches!(filter, PartitionFilter::Compare(_)) {
+                        let filter_regions = filter.find_regions(partition_rule)?;
+                        regions.retain(|x| filter_regions.contains(x));
+                    }
+                }
+                regions
+            }
+            PartitionFilter::Or(filters) => {
+                let mut regions = HashSet::new();
+                for filter in filters {
+                    regions.extend(filter.find_regions(partition_rule)?);
+                }
+                regions
+            }
+        };
+        Ok(regions)
+    }
+}
+
+/// Analyzes `expr`, or "NOT `expr`" if `negated`.
+///
+/// Negating a comparison is fine for finding regions even for NULLs, since rows of NULLs
+/// satisfy neither the comparison nor its negation.
+fn analyze(expr: &DfExpr, negated: bool) -> Result<PartitionFilter> {
+    let filter = match expr {
+        DfExpr::Alias(expr, _) => analyze(expr, negated)?,
+        DfExpr::Not(expr) => analyze(expr, !negated)?,
+        DfExpr::Literal(ScalarValue::Boolean(Some(v))) => {
+            if *v != negated {
+                PartitionFilter::Unknown
+            } else {
+                PartitionFilter::Never
+            }
+        }
+        DfExpr::BinaryExpr { left, op, right } if matches!(op, Operator::And | Operator::Or) => {
+            let filters = vec![analyze(left, negated)?, analyze(right, negated)?];
+            // De Morgan's laws.
+            if (*op == Operator::And) != negated {
+                PartitionFilter::and(filters)
+            } else {
+                PartitionFilter::or(filters)
+            }
+        }
+        DfExpr::BinaryExpr { left, op, right } if is_compare_op(op) => {
+            match (left.as_ref(), right.as_ref()) {
+                (DfExpr::Column(c), value) => compare(&c.name, *op, value, negated)?,
+                (value, DfExpr::Column(c)) => {
+                    compare(&c.name, reverse_operator(op), value, negated)?
+                }
+                _ => PartitionFilter::Unknown,
+            }
+        }
+        DfExpr::Between {
+            expr,
+            negated: between_negated,
+            low,
+            high,
+        } => match expr.as_ref() {
+            DfExpr::Column(c) => {
+                let negated = negated != *between_negated;
+                let filters = vec![
+                    compare(&c.name, Operator::GtEq, low, negated)?,
+                    compare(&c.name, Operator::LtEq, high, negated)?,
+                ];
+                if negated {
+                    PartitionFilter::or(filters)
+                } else {
+                    PartitionFilter::and(filters)
+                }
+            }
+            _ => PartitionFilter::Unknown,
+        },
+        DfExpr::InList {
+            expr,
+            list,
+            negated: in_negated,
+        } => match expr.as_ref() {
+            DfExpr::Column(c) => {
+                let negated = negated != *in_negated;
+                let filters = list
+                    .iter()
+                    .map(|value| compare(&c.name, Operator::Eq, value, negated))
+                    .collect::<Result<Vec<_>>>()?;
+                if negated {
+                    PartitionFilter::and(filters)
+                } else {
+                    PartitionFilter::or(filters)
+                }
+            }
+            _ => PartitionFilter::Unknown,
+        },
+        _ => PartitionFilter::Unknown,
+    };
+    Ok(filter)
+}
+
+fn compare(column: &str, op: Operator, value: &DfExpr, negated: bool) -> Result<PartitionFilter> {
+    let value = match literal_value(value)? {
+        Some(value) => value,
+        None => return Ok(PartitionFilter::Unknown),
+    };
+    let op = if negated { negate_operator(&op) } else { op };
+    Ok(PartitionFilter::Compare(PartitionExpr::new(
+        column, op, value,
+    )))
+}
+
+/// Returns the value of a literal, which may be cast to another type, like the literals
+/// converted to the types of the columns they are compared with.
+fn literal_value(expr: &DfExpr) -> Result<Option<Value>> {
+    let value = match expr {
+        DfExpr::Literal(v) => v.clone(),
+        DfExpr::Cast { expr, data_type } | DfExpr::TryCast { expr, data_type } => {
+            let v = match expr.as_ref() {
+                DfExpr::Literal(v) => v,
+                _ => return Ok(None),
+            };
+            let array = v.to_array();
+            // The query fails anyway if the literal can not be cast.
+            let array = match cast::cast(array.as_ref(), data_type, CastOptions::default()) {
+                Ok(array) => array,
+                Err(_) => return Ok(None),
+            };
+            match ScalarValue::try_from_array(&Arc::from(array), 0) {
+                Ok(v) => v,
+                Err(_) => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    let value = value
+        .clone()
+        .try_into()
+        .with_context(|_| error::ConvertScalarValueSnafu { value })?;
+    Ok(Some(value))
+}
+
+fn is_compare_op(op: &Operator) -> bool {
+    matches!(
+        *op,
+        Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq
+    )
+}
+
+fn reverse_operator(op: &Operator) -> Operator {
+    match *op {
+        Operator::Lt => Operator::Gt,
+        Operator::Gt => Operator::Lt,
+        Operator::LtEq => Operator::GtEq,
+        Operator::GtEq => Operator::LtEq,
+        _ => *op,
+    }
+}
+
+fn negate_operator(op: &Operator) -> Operator {
+    match *op {
+        Operator::Eq => Operator::NotEq,
+        Operator::NotEq => Operator::Eq,
+        Operator::Lt => Operator::GtEq,
+        Operator::LtEq => Operator::Gt,
+        Operator::Gt => Operator::LtEq,
+        Operator::GtEq => Operator::Lt,
+        _ => unreachable!("not a comparison: {:?}", op),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::DataType;
+    use datafusion_expr::expr_fn::{and, binary_expr, col, or};
+    use datafusion_expr::lit;
+
+    use super::*;
+    use crate::partitioning::columns::RangeColumnsPartitionRule;
+    use crate::partitioning::range::RangePartitionRule;
+    use crate::partitioning::PartitionBound;
+
+    fn not(expr: DfExpr) -> DfExpr {
+        DfExpr::Not(Box::new(expr))
+    }
+
+    fn cast(expr: DfExpr, data_type: DataType) -> DfExpr {
+        DfExpr::Cast {
+            expr: Box::new(expr),
+            data_type,
+        }
+    }
+
+    fn in_list(expr: DfExpr, list: Vec<DfExpr>, negated: bool) -> DfExpr {
+        DfExpr::InList {
+            expr: Box::new(expr),
+            list,
+            negated,
+        }
+    }
+
+    fn between(expr: DfExpr, low: DfExpr, high: DfExpr, negated: bool) -> DfExpr {
+        DfExpr::Between {
+            expr: Box::new(expr),
+            negated,
+            low: Box::new(low),
+            high: Box::new(high),
+        }
+    }
+
+    fn find_regions(partition_rule: &PartitionRuleRef<Error>, filter: DfExpr) -> Vec<RegionNumber> {
+        let mut regions = PartitionFilter::analyze(&filter)
+            .unwrap()
+            .find_regions(partition_rule)
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>();
+        regions.sort_unstable();
+        regions
+    }
+
+    #[test]
+    fn test_analyze() {
+        let compare = |column: &str, op, value: i32| {
+            PartitionFilter::Compare(PartitionExpr::new(column, op, value.into()))
+        };
+
+        // NOT (a < 10 AND b = 1) => a >= 10 OR b != 1
+        let filter = not(and(
+            binary_expr(col("a"), Operator::Lt, lit(10)),
+            binary_expr(col("b"), Operator::Eq, lit(1)),
+        ));
+        assert_eq!(
+            PartitionFilter::Or(vec![
+                compare("a", Operator::GtEq, 10),
+                compare("b", Operator::NotEq, 1),
+            ]),
+            PartitionFilter::analyze(&filter).unwrap()
+        );
+
+        // a NOT BETWEEN 10 AND 20 => a < 10 OR a > 20
+        let filter = between(col("a"), lit(10), lit(20), true);
+        assert_eq!(
+            PartitionFilter::Or(vec![
+                compare("a", Operator::Lt, 10),
+                compare("a", Operator::Gt, 20),
+            ]),
+            PartitionFilter::analyze(&filter).unwrap()
+        );
+
+        // NOT (a IN (1, 2)) => a != 1 AND a != 2
+        let filter = not(in_list(col("a"), vec![lit(1), lit(2)], false));
+        assert_eq!(
+            PartitionFilter::And(vec![
+                compare("a", Operator::NotEq, 1),
+                compare("a", Operator::NotEq, 2),
+            ]),
+            PartitionFilter::analyze(&filter).unwrap()
+        );
+
+        // Nested conjunctions are flattened, and filters can not be analyzed are dropped from
+        // them: a > 1 AND (b < 2 AND a + b = 3) => a > 1 AND b < 2
+        let filter = and(
+            binary_expr(col("a"), Operator::Gt, lit(1)),
+            and(
+                binary_expr(col("b"), Operator::Lt, lit(2)),
+                binary_expr(
+                    binary_expr(col("a"), Operator::Plus, col("b")),
+                    Operator::Eq,
+                    lit(3),
+                ),
+            ),
+        );
+        assert_eq!(
+            PartitionFilter::And(vec![
+                compare("a", Operator::Gt, 1),
+                compare("b", Operator::Lt, 2),
+            ]),
+            PartitionFilter::analyze(&filter).unwrap()
+        );
+
+        // The literal is cast to the type of the column: a = CAST(10 AS Int32)
+        let filter = binary_expr(col("a"), Operator::Eq, cast(lit(10_i64), DataType::Int32));
+        assert_eq!(
+            compare("a", Operator::Eq, 10),
+            PartitionFilter::analyze(&filter).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_find_regions_by_range_rule() {
+        // PARTITION BY RANGE (a) (
+        //   PARTITION r1 VALUES LESS THAN (10),
+        //   PARTITION r2 VALUES LESS THAN (20),
+        //   PARTITION r3 VALUES LESS THAN (50),
+        //   PARTITION r4 VALUES LESS THAN (MAXVALUE),
+        // )
+        let partition_rule: PartitionRuleRef<Error> = Arc::new(RangePartitionRule::new(
+            "a",
+            vec![10_i32.into(), 20_i32.into(), 50_i32.into()],
+            vec![0_u32, 1, 2, 3],
+        ));
+
+        // a IN (5, 45)
+        let filter = in_list(col("a"), vec![lit(5_i32), lit(45_i32)], false);
+        assert_eq!(vec![0, 2], find_regions(&partition_rule, filter));
+
+        // a BETWEEN 12 AND 25
+        let filter = between(col("a"), lit(12_i32), lit(25_i32), false);
+        assert_eq!(vec![1, 2], find_regions(&partition_rule, filter));
+
+        // a NOT BETWEEN 12 AND 60
+        let filter = between(col("a"), lit(12_i32), lit(60_i32), true);
+        assert_eq!(vec![0, 1, 3], find_regions(&partition_rule, filter));
+
+        // NOT (a >= 20)
+        let filter = not(binary_expr(col("a"), Operator::GtEq, lit(20_i32)));
+        assert_eq!(vec![0, 1], find_regions(&partition_rule, filter));
+
+        // a < 5 OR (a > 60 AND b = 1)
+        let filter = or(
+            binary_expr(col("a"), Operator::Lt, lit(5_i32)),
+            and(
+                binary_expr(col("a"), Operator::Gt, lit(60_i32)),
+                binary_expr(col("b"), Operator::Eq, lit(1_i32)),
+            ),
+        );
+        assert_eq!(vec![0, 3], find_regions(&partition_rule, filter));
+
+        // a < 5 OR b = 1, rows of any region may satisfy "b = 1".
+        let filter = or(
+            binary_expr(col("a"), Operator::Lt, lit(5_i32)),
+            binary_expr(col("b"), Operator::Eq, lit(1_i32)),
+        );
+        assert_eq!(vec![0, 1, 2, 3], find_regions(&partition_rule, filter));
+
+        // NOT (a LIKE 'x%'), can not be analyzed even negated.
+        let filter = not(binary_expr(col("a"), Operator::Like, lit("x%")));
+        assert_eq!(vec![0, 1, 2, 3], find_regions(&partition_rule, filter));
+
+        // a = CAST(15 AS Int32)
+        let filter = binary_expr(col("a"), Operator::Eq, cast(lit(15_i64), DataType::Int32));
+        assert_eq!(vec![1], find_regions(&partition_rule, filter));
+
+        // a IN (1, 2) AND a IN (30)
+        let filter = and(
+            in_list(col("a"), vec![lit(1_i32), lit(2_i32)], false),
+            in_list(col("a"), vec![lit(30_i32)], false),
+        );
+        assert!(find_regions(&partition_rule, filter).is_empty());
+
+        let filter = lit(false);
+        assert!(find_regions(&partition_rule, filter).is_empty());
+    }
+
+    #[test]
+    fn test_find_regions_by_columns_rule() {
+        // PARTITION BY RANGE COLUMNS (a, b) (
+        //   PARTITION r1 VALUES LESS THAN (10, 'hz'),
+        //   PARTITION r2 VALUES LESS THAN (10, MAXVALUE),
+        //   PARTITION r3 VALUES LESS THAN (MAXVALUE, MAXVALUE),
+        // )
+        let partition_rule: PartitionRuleRef<Error> = Arc::new(RangeColumnsPartitionRule::new(
+            vec!["a".to_string(), "b".to_string()],
+            vec![
+                vec![
+                    PartitionBound::Value(10_i32.into()),
+                    PartitionBound::Value("hz".into()),
+                ],
+                vec![
+                    PartitionBound::Value(10_i32.into()),
+                    PartitionBound::MaxValue,
+                ],
+                vec![PartitionBound::MaxValue, PartitionBound::MaxValue],
+            ],
+            vec![0_u32, 1, 2],
+        ));
+
+        // a = 10 AND b BETWEEN 'a' AND 'b', both columns prune the regions together.
+        let filter = and(
+            binary_expr(col("a"), Operator::Eq, lit(10_i32)),
+            between(col("b"), lit("a"), lit("b"), false),
+        );
+        assert_eq!(vec![0], find_regions(&partition_rule, filter));
+
+        // a = 10 AND NOT (b >= 'hz'), the negated comparison is handed to the rule together.
+        let filter = and(
+            binary_expr(col("a"), Operator::Eq, lit(10_i32)),
+            not(binary_expr(col("b"), Operator::GtEq, lit("hz"))),
+        );
+        assert_eq!(vec![0], find_regions(&partition_rule, filter));
+
+        // a IN (5, 20)
+        let filter = in_list(col("a"), vec![lit(5_i32), lit(20_i32)], false);
+        assert_eq!(vec![0, 2], find_regions(&partition_rule, filter));
+
+        // NOT (a <= 10)
+        let filter = not(binary_expr(col("a"), Operator::LtEq, lit(10_i32)));
+        assert_eq!(vec![2], find_regions(&partition_rule, filter));
+    }
+}
@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::sync::Arc;
 
 use api::helper::ColumnDataTypeWrapper;
 use api::v1::codec::InsertBatch;
@@ -54,7 +53,7 @@ impl DistTable {
 
             let client = self.datanode_clients.get_client(&datanode).await;
             let db = Database::new(&self.table_name.schema_name, client);
-            let instance = DatanodeInstance::new(Arc::new(self.clone()) as _, db);
+            let instance = DatanodeInstance::new(db);
 
             // TODO(fys): a separate runtime should be used here.
             let join = tokio::spawn(async move {
@@ -102,10 +101,10 @@ pub fn insert_request_to_insert_batch(insert: &InsertRequest) -> Result<InsertBa
                 None => row_count = Some(vector.len()),
             }
 
-            let datatype: ColumnDataTypeWrapper = vector
-                .data_type()
-                .try_into()
-                .context(error::ColumnDataTypeSnafu)?;
+            let (datatype, datatype_extension) =
+                ColumnDataTypeWrapper::try_from(vector.data_type())
+                    .context(error::ColumnDataTypeSnafu)?
+                    .into_parts();
 
             // TODO(hl): need refactor
             let semantic_type =
@@ -118,11 +117,14 @@ pub fn insert_request_to_insert_batch(insert: &InsertRequest) -> Result<InsertBa
             let mut column = Column {
                 column_name: column_name.clone(),
                 semantic_type: semantic_type.into(),
-                datatype: datatype.datatype() as i32,
+                datatype: datatype as i32,
+                datatype_extension,
                 ..Default::default()
             };
 
-            column.push_vals(0, vector.clone());
+            column
+                .push_vals(0, vector.clone())
+                .context(error::ColumnDataTypeSnafu)?;
             Ok(column)
         })
         .collect::<Result<Vec<_>>>()?;
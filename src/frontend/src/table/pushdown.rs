@@ -0,0 +1,488 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pushes aggregations over [DistTable] down to datanodes.
+//!
+//! An aggregation is split into two phases: the partial aggregation runs on each datanode
+//! along with the filters and projections under it, and the final aggregation merges the
+//! partial results in frontend. For example, `SELECT host, avg(cpu) FROM t WHERE ... GROUP BY host`
+//! runs `SELECT host, sum(cpu), count(cpu) FROM t WHERE ... GROUP BY host` on datanodes, and
+//! divides the summed sum by the summed count in frontend.
+//!
+//! Scans without aggregations push their filters, projections and limits down to datanodes
+//! in [DistTable] itself.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_telemetry::{debug, warn};
+use datafusion::arrow::datatypes::{DataType, Schema as ArrowSchema};
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::logical_plan::plan::Aggregate;
+use datafusion::logical_plan::{
+    lit, Column, Expr as DfExpr, LogicalPlan as DfLogicalPlan, LogicalPlanBuilder, Operator,
+};
+use datafusion::optimizer::utils;
+use datafusion::physical_plan::aggregates::AggregateFunction;
+use datatypes::schema::{Schema, SchemaRef};
+use query::plan::{Explain, LogicalPlan};
+use snafu::ResultExt;
+use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
+use table::error::{DatafusionSnafu, Error as TableError};
+use table::metadata::TableInfoRef;
+use table::table::adapter::DfTableProviderAdapter;
+use table::Table;
+
+use crate::table::{build_table_info, project_schema, DistTable};
+
+/// Rewrites `plan` to push aggregations over [DistTable] down to datanodes. The original plan
+/// is returned if it cannot be rewritten.
+pub(crate) fn push_down_aggregate(plan: LogicalPlan) -> LogicalPlan {
+    let df_plan = match plan {
+        LogicalPlan::DfPlan(df_plan) => df_plan,
+        // The input of range queries has no aggregation.
+        LogicalPlan::RangeSelect(_) => return plan,
+        LogicalPlan::Explain(explain) => {
+            return LogicalPlan::Explain(Explain {
+                plan: Box::new(push_down_aggregate(*explain.plan)),
+                ..explain
+            })
+        }
+    };
+    match rewrite_plan(&df_plan) {
+        Ok(new_plan) => LogicalPlan::DfPlan(new_plan),
+        Err(e) => {
+            warn!(
+                "Failed to push down aggregation, fallback to the original plan, error: {}",
+                e
+            );
+            LogicalPlan::DfPlan(df_plan)
+        }
+    }
+}
+
+fn rewrite_plan(plan: &DfLogicalPlan) -> DfResult<DfLogicalPlan> {
+    if let DfLogicalPlan::Aggregate(aggregate) = plan {
+        if let Some(new_plan) = split_aggregate(aggregate)? {
+            return Ok(new_plan);
+        }
+    }
+
+    let inputs = plan.inputs();
+    if inputs.is_empty() {
+        return Ok(plan.clone());
+    }
+    let new_inputs = inputs
+        .into_iter()
+        .map(rewrite_plan)
+        .collect::<DfResult<Vec<_>>>()?;
+    utils::from_plan(plan, &plan.expressions(), &new_inputs)
+}
+
+/// How the final aggregation merges the partial results of one aggregate expression,
+/// the indices point to the partial aggregate expressions.
+enum Merge {
+    Single(AggregateFunction, usize),
+    /// Counts are summed, and no rows are counted as 0 instead of NULL.
+    Count(usize),
+    Avg {
+        sum: usize,
+        count: usize,
+    },
+}
+
+/// Splits the aggregation into partial and final phases if it aggregates a [DistTable] directly,
+/// returns `None` if it cannot be split.
+fn split_aggregate(aggregate: &Aggregate) -> DfResult<Option<DfLogicalPlan>> {
+    let mut filters = vec![];
+    let (table_name, table) = match find_dist_table_scan(&aggregate.input, &mut filters) {
+        Some(scan) => scan,
+        None => return Ok(None),
+    };
+
+    let mut partial_aggr_exprs = vec![];
+    let mut merges = Vec::with_capacity(aggregate.aggr_expr.len());
+    for expr in aggregate.aggr_expr.iter() {
+        let merge = match expr {
+            DfExpr::AggregateFunction {
+                fun,
+                args,
+                distinct: false,
+            } => match fun {
+                AggregateFunction::Count => {
+                    Merge::Count(position_or_push(&mut partial_aggr_exprs, expr.clone()))
+                }
+                AggregateFunction::Sum => Merge::Single(
+                    AggregateFunction::Sum,
+                    position_or_push(&mut partial_aggr_exprs, expr.clone()),
+                ),
+                AggregateFunction::Min | AggregateFunction::Max => Merge::Single(
+                    fun.clone(),
+                    position_or_push(&mut partial_aggr_exprs, expr.clone()),
+                ),
+                AggregateFunction::Avg => {
+                    let sum = DfExpr::AggregateFunction {
+                        fun: AggregateFunction::Sum,
+                        args: args.clone(),
+                        distinct: false,
+                    };
+                    let count = DfExpr::AggregateFunction {
+                        fun: AggregateFunction::Count,
+                        args: args.clone(),
+                        distinct: false,
+                    };
+                    Merge::Avg {
+                        sum: position_or_push(&mut partial_aggr_exprs, sum),
+                        count: position_or_push(&mut partial_aggr_exprs, count),
+                    }
+                }
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        merges.push(merge);
+    }
+
+    let partial_plan = LogicalPlanBuilder::from(aggregate.input.as_ref().clone())
+        .aggregate(aggregate.group_expr.clone(), partial_aggr_exprs)?
+        .build()?;
+    if let Err(e) = DFLogicalSubstraitConvertor.encode(partial_plan.clone()) {
+        debug!(
+            "Partial aggregation cannot be executed on datanodes, plan: {:?}, error: {}",
+            partial_plan, e
+        );
+        return Ok(None);
+    }
+
+    let partial_schema: ArrowSchema = partial_plan.schema().as_ref().clone().into();
+    let partial_schema = Schema::try_from(Arc::new(partial_schema))
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let partial_column = |index: usize| {
+        DfExpr::Column(Column {
+            relation: Some(table_name.clone()),
+            name: partial_schema.column_schemas()[index].name.clone(),
+        })
+    };
+
+    // Builds the final aggregation over the partial results.
+    let group_len = aggregate.group_expr.len();
+    let final_group_exprs = (0..group_len).map(partial_column).collect::<Vec<_>>();
+    let mut final_aggr_exprs = vec![];
+    let mut final_aggr = |fun: AggregateFunction, partial_index: usize| {
+        let expr = DfExpr::AggregateFunction {
+            fun,
+            args: vec![partial_column(group_len + partial_index)],
+            distinct: false,
+        };
+        position_or_push(&mut final_aggr_exprs, expr)
+    };
+    let merges = merges
+        .into_iter()
+        .map(|merge| match merge {
+            Merge::Single(fun, index) => Merge::Single(fun.clone(), final_aggr(fun, index)),
+            Merge::Count(index) => Merge::Count(final_aggr(AggregateFunction::Sum, index)),
+            Merge::Avg { sum, count } => Merge::Avg {
+                sum: final_aggr(AggregateFunction::Sum, sum),
+                count: final_aggr(AggregateFunction::Sum, count),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let partial_table = PartialAggregateTable {
+        table,
+        plan: partial_plan,
+        filters: filters.into_iter().map(Into::into).collect(),
+        schema: Arc::new(partial_schema.clone()),
+    };
+    let provider = Arc::new(DfTableProviderAdapter::new(Arc::new(partial_table)));
+    let final_plan = LogicalPlanBuilder::scan(&table_name, provider, None)?
+        .aggregate(final_group_exprs, final_aggr_exprs)?
+        .build()?;
+
+    // Restores the output fields of the original aggregation.
+    let final_column = |index: usize| {
+        DfExpr::Column(Column::from_name(
+            final_plan.schema().field(group_len + index).name(),
+        ))
+    };
+    let original_fields = aggregate.schema.fields();
+    let mut exprs = Vec::with_capacity(original_fields.len());
+    for (index, field) in original_fields.iter().take(group_len).enumerate() {
+        let expr = partial_column(index);
+        if field.qualifier() == Some(&table_name) {
+            exprs.push(expr);
+        } else {
+            exprs.push(DfExpr::Alias(Box::new(expr), field.name().clone()));
+        }
+    }
+    for (merge, field) in merges
+        .into_iter()
+        .zip(original_fields.iter().skip(group_len))
+    {
+        let expr = match merge {
+            Merge::Single(_, index) => final_column(index),
+            Merge::Count(index) => DfExpr::Case {
+                expr: None,
+                when_then_expr: vec![(
+                    Box::new(DfExpr::IsNull(Box::new(final_column(index)))),
+                    Box::new(lit(0u64)),
+                )],
+                else_expr: Some(Box::new(final_column(index))),
+            },
+            Merge::Avg { sum, count } => DfExpr::BinaryExpr {
+                left: Box::new(DfExpr::Cast {
+                    expr: Box::new(final_column(sum)),
+                    data_type: DataType::Float64,
+                }),
+                op: Operator::Divide,
+                right: Box::new(DfExpr::Cast {
+                    expr: Box::new(final_column(count)),
+                    data_type: DataType::Float64,
+                }),
+            },
+        };
+        exprs.push(DfExpr::Alias(Box::new(expr), field.name().clone()));
+    }
+
+    let plan = LogicalPlanBuilder::from(final_plan)
+        .project(exprs)?
+        .build()?;
+    Ok(Some(plan))
+}
+
+fn position_or_push(exprs: &mut Vec<DfExpr>, expr: DfExpr) -> usize {
+    match exprs.iter().position(|e| e == &expr) {
+        Some(index) => index,
+        None => {
+            exprs.push(expr);
+            exprs.len() - 1
+        }
+    }
+}
+
+/// Finds the scan of [DistTable] under `plan`, only filters and projections are allowed in
+/// between. Predicates of the filters are collected for selecting regions. Returns the name
+/// of the scan and the table.
+fn find_dist_table_scan(
+    plan: &DfLogicalPlan,
+    filters: &mut Vec<DfExpr>,
+) -> Option<(String, DistTable)> {
+    match plan {
+        DfLogicalPlan::Filter(filter) => {
+            filters.push(filter.predicate.clone());
+            find_dist_table_scan(&filter.input, filters)
+        }
+        DfLogicalPlan::Projection(projection) => find_dist_table_scan(&projection.input, filters),
+        DfLogicalPlan::TableScan(table_scan) => {
+            let adapter = table_scan
+                .source
+                .as_any()
+                .downcast_ref::<DfTableProviderAdapter>()?;
+            let table = adapter
+                .table()
+                .as_any()
+                .downcast_ref::<DistTable>()?
+                .clone();
+            filters.extend(table_scan.filters.iter().cloned());
+            Some((table_scan.table_name.clone(), table))
+        }
+        _ => None,
+    }
+}
+
+/// A table whose rows are the partial aggregation results computed by datanodes.
+struct PartialAggregateTable {
+    table: DistTable,
+    /// The partial aggregation plan executed on datanodes.
+    plan: DfLogicalPlan,
+    /// Filters under the partial aggregation, used for selecting regions.
+    filters: Vec<Expr>,
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl Table for PartialAggregateTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        build_table_info(&self.table.table_name, self.schema.clone())
+    }
+
+    async fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> table::Result<PhysicalPlanRef> {
+        // Projections are executed on datanodes, too.
+        let plan = match projection {
+            Some(projection) => {
+                let exprs = projection
+                    .iter()
+                    .map(|i| DfExpr::Column(self.plan.schema().field(*i).qualified_column()))
+                    .collect::<Vec<_>>();
+                LogicalPlanBuilder::from(self.plan.clone())
+                    .project(exprs)
+                    .and_then(|builder| builder.build())
+                    .context(DatafusionSnafu)?
+            }
+            None => self.plan.clone(),
+        };
+        let schema = project_schema(self.schema.clone(), projection);
+
+        self.table
+            .scan_with_plan(plan, schema, &self.filters)
+            .await
+            .map_err(TableError::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::logical_plan::{avg, col, count, count_distinct, max};
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::ColumnSchema;
+    use meta_client::client::MetaClient;
+    use meta_client::rpc::TableName;
+
+    use super::*;
+    use crate::datanode::DatanodeClients;
+    use crate::table::route::TableRoutes;
+
+    fn dist_table_provider() -> Arc<DfTableProviderAdapter> {
+        let column_schemas = vec![
+            ColumnSchema::new("ts", ConcreteDataType::int64_datatype(), false),
+            ColumnSchema::new("a", ConcreteDataType::int32_datatype(), true),
+            ColumnSchema::new("row_id", ConcreteDataType::int32_datatype(), true),
+        ];
+        let table = DistTable {
+            table_name: TableName::new("greptime", "public", "dist_numbers"),
+            schema: Arc::new(Schema::new(column_schemas)),
+            table_routes: Arc::new(TableRoutes::new(Arc::new(MetaClient::default()))),
+            datanode_clients: Arc::new(DatanodeClients::new()),
+        };
+        Arc::new(DfTableProviderAdapter::new(Arc::new(table)))
+    }
+
+    fn find_partial_table(plan: &DfLogicalPlan) -> Option<DfLogicalPlan> {
+        if let DfLogicalPlan::TableScan(table_scan) = plan {
+            let adapter = table_scan
+                .source
+                .as_any()
+                .downcast_ref::<DfTableProviderAdapter>()?;
+            let table = adapter.table();
+            let partial_table = table.as_any().downcast_ref::<PartialAggregateTable>()?;
+            return Some(partial_table.plan.clone());
+        }
+        plan.inputs().into_iter().find_map(find_partial_table)
+    }
+
+    #[test]
+    fn test_push_down_aggregate() {
+        let plan = LogicalPlanBuilder::scan("dist_numbers", dist_table_provider(), None)
+            .unwrap()
+            .filter(col("a").lt(lit(10)))
+            .unwrap()
+            .aggregate(
+                vec![col("row_id")],
+                vec![count(col("a")), avg(col("a")), max(col("ts"))],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let new_plan = match push_down_aggregate(LogicalPlan::DfPlan(plan.clone())) {
+            LogicalPlan::DfPlan(new_plan) => new_plan,
+            _ => unreachable!(),
+        };
+        // The output fields are not changed.
+        assert_eq!(plan.schema().fields(), new_plan.schema().fields());
+
+        let partial_plan = find_partial_table(&new_plan).unwrap();
+        let partial_aggregate = match &partial_plan {
+            DfLogicalPlan::Aggregate(aggregate) => aggregate,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            vec![DfExpr::Column(Column::new(Some("dist_numbers"), "row_id"))],
+            partial_aggregate.group_expr
+        );
+        // "avg" is split into "sum" and "count", and the "count" is shared.
+        assert_eq!(3, partial_aggregate.aggr_expr.len());
+        assert!(matches!(
+            partial_aggregate.input.as_ref(),
+            DfLogicalPlan::Filter(_)
+        ));
+    }
+
+    #[test]
+    fn test_merge_count() {
+        let plan = LogicalPlanBuilder::scan("dist_numbers", dist_table_provider(), None)
+            .unwrap()
+            .aggregate(Vec::<DfExpr>::new(), vec![count(col("a"))])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let new_plan = match push_down_aggregate(LogicalPlan::DfPlan(plan.clone())) {
+            LogicalPlan::DfPlan(new_plan) => new_plan,
+            _ => unreachable!(),
+        };
+        assert_eq!(plan.schema().fields(), new_plan.schema().fields());
+
+        // Summing the partial counts of no rows returns NULL, which is replaced by 0.
+        let projection = match &new_plan {
+            DfLogicalPlan::Projection(projection) => projection,
+            _ => unreachable!(),
+        };
+        let expr = match &projection.expr[0] {
+            DfExpr::Alias(expr, _) => expr.as_ref(),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            expr,
+            DfExpr::Case {
+                expr: None,
+                else_expr: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_not_push_down_aggregate() {
+        let plan = LogicalPlanBuilder::scan("dist_numbers", dist_table_provider(), None)
+            .unwrap()
+            .aggregate(vec![col("row_id")], vec![count_distinct(col("a"))])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let new_plan = match push_down_aggregate(LogicalPlan::DfPlan(plan.clone())) {
+            LogicalPlan::DfPlan(new_plan) => new_plan,
+            _ => unreachable!(),
+        };
+        assert!(find_partial_table(&new_plan).is_none());
+        assert_eq!(format!("{:?}", plan), format!("{:?}", new_plan));
+    }
+}
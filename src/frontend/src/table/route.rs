@@ -15,13 +15,20 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use api::v1::meta::TableRouteValue;
+use common_catalog::{build_tables_global_prefix, TableGlobalKey};
+use common_telemetry::{debug, info, warn};
 use meta_client::client::MetaClient;
-use meta_client::rpc::{RouteRequest, TableName, TableRoute};
+use meta_client::rpc::{EventType, RouteRequest, TableName, TableRoute, WatchRequest};
 use moka::future::{Cache, CacheBuilder};
+use prost::Message;
 use snafu::{ensure, ResultExt};
 
 use crate::error::{self, Result};
 
+/// Prefix of table route keys in metasrv, keep it consistent with metasrv.
+const TABLE_ROUTE_PREFIX: &str = "__meta_table_route";
+
 pub(crate) struct TableRoutes {
     meta_client: Arc<MetaClient>,
     cache: Cache<TableName, Arc<TableRoute>>,
@@ -50,6 +57,70 @@ impl TableRoutes {
             })
     }
 
+    /// Invalidates the cached route of the table.
+    pub(crate) async fn invalidate_table_route(&self, table_name: &TableName) {
+        self.cache.invalidate(table_name).await
+    }
+
+    /// Spawns a background task which watches the changes of table routes and table global
+    /// infos in metasrv, and invalidates the affected cache entries.
+    pub(crate) fn start_watching(self: &Arc<Self>) {
+        let table_routes = self.clone();
+        common_runtime::spawn_bg(async move {
+            let route_prefix = format!("{}-", TABLE_ROUTE_PREFIX);
+            let table_prefix = build_tables_global_prefix();
+            loop {
+                let res = tokio::select! {
+                    res = table_routes.watch_prefix(&route_prefix) => res,
+                    res = table_routes.watch_prefix(&table_prefix) => res,
+                };
+                if let Err(e) = res {
+                    warn!("Table route watch interrupted, err: {:?}", e);
+                }
+                // Routes changed while the watch is broken are unknown, drop them all.
+                table_routes.cache.invalidate_all();
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        });
+    }
+
+    async fn watch_prefix(&self, prefix: &str) -> Result<()> {
+        // Previous kv is required to find out the table of a deleted route.
+        let mut watcher = self
+            .meta_client
+            .watch(WatchRequest::new().with_prefix(prefix).with_prev_kv())
+            .await
+            .context(error::RequestMetaSnafu)?;
+        info!("Watching table route changes, prefix: {}", prefix);
+
+        while let Some(mut resp) = watcher.message().await.context(error::RequestMetaSnafu)? {
+            for mut event in resp.take_events() {
+                let kv = match event.event_type() {
+                    EventType::Put => event.take_kv(),
+                    EventType::Delete => event.take_prev_kv().or_else(|| event.take_kv()),
+                };
+                let (key, value) = match kv {
+                    Some(mut kv) => (
+                        String::from_utf8_lossy(&kv.take_key()).to_string(),
+                        kv.take_value(),
+                    ),
+                    None => continue,
+                };
+                match parse_table_name(&key, &value) {
+                    Some(table_name) => {
+                        debug!("Invalidate route of table {}, key: {}", table_name, key);
+                        self.invalidate_table_route(&table_name).await;
+                    }
+                    None => {
+                        warn!("Unknown table of key: {}, invalidate all routes", key);
+                        self.cache.invalidate_all();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn get_from_meta(&self, table_name: &TableName) -> Result<Arc<TableRoute>> {
         let mut resp = self
             .meta_client
@@ -77,3 +148,80 @@ impl TableRoutes {
         self.cache.insert(table_name, table_route).await
     }
 }
+
+/// Parses table name from table route kv or table global key.
+///
+/// Names in table route keys (`__meta_table_route-{catalog}-{schema}-{table}-{id}`) are joined
+/// by '-' without escaping, so the name is decoded from the route value instead of the key.
+fn parse_table_name(key: &str, value: &[u8]) -> Option<TableName> {
+    if key.starts_with(&format!("{}-", TABLE_ROUTE_PREFIX)) {
+        let value = TableRouteValue::decode(value).ok()?;
+        let table_name = value.table_route?.table?.table_name?;
+        return Some(TableName::new(
+            table_name.catalog_name,
+            table_name.schema_name,
+            table_name.table_name,
+        ));
+    }
+
+    TableGlobalKey::parse(key)
+        .ok()
+        .map(|key| TableName::new(key.catalog_name, key.schema_name, key.table_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::meta::{Table, TableName as PbTableName, TableRoute as PbTableRoute};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_table_name() {
+        let route_value = |catalog_name: &str, schema_name: &str, table_name: &str| {
+            TableRouteValue {
+                peers: vec![],
+                table_route: Some(PbTableRoute {
+                    table: Some(Table {
+                        id: 1024,
+                        table_name: Some(PbTableName {
+                            catalog_name: catalog_name.to_string(),
+                            schema_name: schema_name.to_string(),
+                            table_name: table_name.to_string(),
+                        }),
+                        table_schema: vec![],
+                    }),
+                    region_routes: vec![],
+                }),
+            }
+            .encode_to_vec()
+        };
+
+        let expected = TableName::new("greptime", "public", "my_table");
+        assert_eq!(
+            Some(expected.clone()),
+            parse_table_name(
+                "__meta_table_route-greptime-public-my_table-1024",
+                &route_value("greptime", "public", "my_table")
+            )
+        );
+        assert_eq!(
+            Some(expected),
+            parse_table_name("__tg-greptime-public-my_table", &[])
+        );
+
+        // Names containing the delimiter.
+        assert_eq!(
+            Some(TableName::new("my-catalog", "my-schema", "my-table")),
+            parse_table_name(
+                "__meta_table_route-my-catalog-my-schema-my-table-1024",
+                &route_value("my-catalog", "my-schema", "my-table")
+            )
+        );
+
+        assert_eq!(
+            None,
+            parse_table_name("__meta_table_route-greptime-1024", &[1, 2, 3])
+        );
+        assert_eq!(None, parse_table_name("__s-greptime-public", &[]));
+    }
+}
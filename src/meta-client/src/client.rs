@@ -25,12 +25,13 @@ use snafu::OptionExt;
 use store::Client as StoreClient;
 
 pub use self::heartbeat::{HeartbeatSender, HeartbeatStream};
+pub use self::store::WatchStream;
 use crate::error;
 use crate::error::Result;
 use crate::rpc::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse, CreateRequest,
     DeleteRangeRequest, DeleteRangeResponse, PutRequest, PutResponse, RangeRequest, RangeResponse,
-    RouteRequest, RouteResponse,
+    RouteRequest, RouteResponse, WatchRequest,
 };
 
 pub type Id = (u64, u64);
@@ -241,6 +242,12 @@ impl MetaClient {
             .try_into()
     }
 
+    /// Watch watches for changes on the given key or range, the returned
+    /// stream keeps receiving events from `metasrv` until it is dropped.
+    pub async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        self.store_client()?.watch(req.into()).await
+    }
+
     #[inline]
     pub fn heartbeat_client(&self) -> Result<HeartbeatClient> {
         self.heartbeat.clone().context(error::NotStartedSnafu {
@@ -284,7 +291,7 @@ mod tests {
 
     use super::*;
     use crate::mocks;
-    use crate::rpc::{Partition, TableName};
+    use crate::rpc::{EventType, Partition, TableName};
 
     #[tokio::test]
     async fn test_meta_client_builder() {
@@ -675,6 +682,36 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_watch() {
+        let client = mocks::mock_client_with_memstore().await;
+
+        let req = WatchRequest::new().with_prefix(b"key-".to_vec());
+        let mut stream = client.watch(req).await.unwrap();
+
+        gen_data(&client).await;
+        let req = DeleteRangeRequest::new().with_key(b"key-0".to_vec());
+        let _ = client.delete_range(req).await.unwrap();
+
+        for i in 0..10 {
+            let mut res = stream.message().await.unwrap().unwrap();
+            let mut events = res.take_events();
+            assert_eq!(1, events.len());
+            let event = events.get_mut(0).unwrap();
+            assert_eq!(EventType::Put, event.event_type());
+            let mut kv = event.take_kv().unwrap();
+            assert_eq!(format!("{}-{}", "key", i).into_bytes(), kv.take_key());
+            assert_eq!(format!("{}-{}", "value", i).into_bytes(), kv.take_value());
+        }
+
+        let mut res = stream.message().await.unwrap().unwrap();
+        let mut events = res.take_events();
+        assert_eq!(1, events.len());
+        let event = events.get_mut(0).unwrap();
+        assert_eq!(EventType::Delete, event.event_type());
+        assert_eq!(b"key-0".to_vec(), event.take_kv().unwrap().take_key());
+    }
+
     #[tokio::test]
     async fn test_delete_with_range() {
         let client = mocks::mock_client_with_memstore().await;
@@ -19,15 +19,48 @@ use api::v1::meta::store_client::StoreClient;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, PutRequest, PutResponse, RangeRequest, RangeResponse,
+    WatchRequest, WatchResponse,
 };
 use common_grpc::channel_manager::ChannelManager;
 use snafu::{ensure, OptionExt, ResultExt};
 use tokio::sync::RwLock;
 use tonic::transport::Channel;
+use tonic::Streaming;
 
 use crate::client::{load_balance as lb, Id};
 use crate::error;
 use crate::error::Result;
+use crate::rpc::WatchResponse as RpcWatchResponse;
+
+#[derive(Debug)]
+pub struct WatchStream {
+    id: Id,
+    stream: Streaming<WatchResponse>,
+}
+
+impl WatchStream {
+    #[inline]
+    fn new(id: Id, stream: Streaming<WatchResponse>) -> Self {
+        Self { id, stream }
+    }
+
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Fetch the next message from this stream, returns `None` if the
+    /// stream is closed by the server.
+    #[inline]
+    pub async fn message(&mut self) -> Result<Option<RpcWatchResponse>> {
+        let res = self
+            .stream
+            .message()
+            .await
+            .context(error::TonicStatusSnafu)?;
+        res.map(RpcWatchResponse::try_from).transpose()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Client {
@@ -86,6 +119,11 @@ impl Client {
         let inner = self.inner.read().await;
         inner.delete_range(req).await
     }
+
+    pub async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let inner = self.inner.read().await;
+        inner.watch(req).await
+    }
 }
 
 #[derive(Debug)]
@@ -171,6 +209,18 @@ impl Inner {
         Ok(res.into_inner())
     }
 
+    async fn watch(&self, mut req: WatchRequest) -> Result<WatchStream> {
+        let mut client = self.random_client()?;
+        req.set_header(self.id);
+        let stream = client
+            .watch(req)
+            .await
+            .context(error::TonicStatusSnafu)?
+            .into_inner();
+
+        Ok(WatchStream::new(self.id, stream))
+    }
+
     fn random_client(&self) -> Result<StoreClient<Channel>> {
         let len = self.peers.len();
         let peer = lb::random_get(len, |i| Some(&self.peers[i])).context(
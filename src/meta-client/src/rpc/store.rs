@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use api::v1::meta::event::EventType as PbEventType;
 use api::v1::meta::{
     BatchPutRequest as PbBatchPutRequest, BatchPutResponse as PbBatchPutResponse,
     CompareAndPutRequest as PbCompareAndPutRequest,
     CompareAndPutResponse as PbCompareAndPutResponse, DeleteRangeRequest as PbDeleteRangeRequest,
-    DeleteRangeResponse as PbDeleteRangeResponse, KeyValue as PbKeyValue,
+    DeleteRangeResponse as PbDeleteRangeResponse, Event as PbEvent, KeyValue as PbKeyValue,
     PutRequest as PbPutRequest, PutResponse as PbPutResponse, RangeRequest as PbRangeRequest,
-    RangeResponse as PbRangeResponse,
+    RangeResponse as PbRangeResponse, WatchRequest as PbWatchRequest,
+    WatchResponse as PbWatchResponse,
 };
 
 use crate::error;
@@ -521,15 +523,165 @@ impl DeleteRangeResponse {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct WatchRequest {
+    /// key is the key to register for watching.
+    pub key: Vec<u8>,
+    /// range_end is the end of the range [key, range_end) to watch.
+    /// If range_end is not given, only the key argument is watched.
+    /// If range_end is '\0', all keys greater than or equal to the key
+    /// argument are watched.
+    /// If range_end is one bit larger than the given key, then all keys with
+    /// the prefix (the given key) will be watched.
+    pub range_end: Vec<u8>,
+    /// If prev_kv is set, the watched events will carry the previous
+    /// key-value pair before the event happens.
+    pub prev_kv: bool,
+}
+
+impl From<WatchRequest> for PbWatchRequest {
+    fn from(req: WatchRequest) -> Self {
+        Self {
+            header: None,
+            key: req.key,
+            range_end: req.range_end,
+            prev_kv: req.prev_kv,
+        }
+    }
+}
+
+impl WatchRequest {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            key: vec![],
+            range_end: vec![],
+            prev_kv: false,
+        }
+    }
+
+    /// key is the key to register for watching. If range_end is not given,
+    /// only the key argument is watched.
+    #[inline]
+    pub fn with_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// key is the key to register for watching.
+    ///
+    /// range_end is the end of the range [key, range_end) to watch.
+    /// If range_end is not given, only the key argument is watched.
+    /// If range_end is '\0', all keys greater than or equal to the key
+    /// argument are watched.
+    /// If range_end is one bit larger than the given key, then all keys with
+    /// the prefix (the given key) will be watched.
+    #[inline]
+    pub fn with_range(mut self, key: impl Into<Vec<u8>>, range_end: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self.range_end = range_end.into();
+        self
+    }
+
+    /// Watches all keys prefixed with key.
+    /// range_end is one bit larger than the given key.
+    #[inline]
+    pub fn with_prefix(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self.range_end = util::get_prefix_end_key(&self.key);
+        self
+    }
+
+    /// If prev_kv is set, the watched events will carry the previous
+    /// key-value pair before the event happens.
+    #[inline]
+    pub fn with_prev_kv(mut self) -> Self {
+        self.prev_kv = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchResponse(PbWatchResponse);
+
+impl TryFrom<PbWatchResponse> for WatchResponse {
+    type Error = error::Error;
+
+    fn try_from(pb: PbWatchResponse) -> Result<Self> {
+        util::check_response_header(pb.header.as_ref())?;
+
+        Ok(Self::new(pb))
+    }
+}
+
+impl WatchResponse {
+    #[inline]
+    pub fn new(res: PbWatchResponse) -> Self {
+        Self(res)
+    }
+
+    #[inline]
+    pub fn take_header(&mut self) -> Option<ResponseHeader> {
+        self.0.header.take().map(ResponseHeader::new)
+    }
+
+    #[inline]
+    pub fn take_events(&mut self) -> Vec<Event> {
+        self.0.events.drain(..).map(Event::new).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    Put,
+    Delete,
+}
+
+#[derive(Debug, Clone)]
+pub struct Event(PbEvent);
+
+impl Event {
+    #[inline]
+    pub fn new(event: PbEvent) -> Self {
+        Self(event)
+    }
+
+    #[inline]
+    pub fn event_type(&self) -> EventType {
+        match self.0.r#type() {
+            PbEventType::Put => EventType::Put,
+            PbEventType::Delete => EventType::Delete,
+        }
+    }
+
+    /// The key-value pair of this event. A PUT event contains the current
+    /// key-value pair, a DELETE event contains the deleted key with an empty
+    /// value.
+    #[inline]
+    pub fn take_kv(&mut self) -> Option<KeyValue> {
+        self.0.kv.take().map(KeyValue::new)
+    }
+
+    /// The key-value pair before the event happens, only presents when
+    /// prev_kv is set in the watch request.
+    #[inline]
+    pub fn take_prev_kv(&mut self) -> Option<KeyValue> {
+        self.0.prev_kv.take().map(KeyValue::new)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use api::v1::meta::event::EventType as PbEventType;
     use api::v1::meta::{
         BatchPutRequest as PbBatchPutRequest, BatchPutResponse as PbBatchPutResponse,
         CompareAndPutRequest as PbCompareAndPutRequest,
         CompareAndPutResponse as PbCompareAndPutResponse,
         DeleteRangeRequest as PbDeleteRangeRequest, DeleteRangeResponse as PbDeleteRangeResponse,
-        KeyValue as PbKeyValue, PutRequest as PbPutRequest, PutResponse as PbPutResponse,
-        RangeRequest as PbRangeRequest, RangeResponse as PbRangeResponse,
+        Event as PbEvent, KeyValue as PbKeyValue, PutRequest as PbPutRequest,
+        PutResponse as PbPutResponse, RangeRequest as PbRangeRequest,
+        RangeResponse as PbRangeResponse, WatchRequest as PbWatchRequest,
+        WatchResponse as PbWatchResponse,
     };
 
     use super::*;
@@ -775,4 +927,60 @@ mod tests {
         assert_eq!(b"v2".to_vec(), kv1.value().to_vec());
         assert_eq!(b"v2".to_vec(), kv1.take_value());
     }
+
+    #[test]
+    fn test_watch_prefix_request_trans() {
+        let key = b"test_key1".to_vec();
+
+        let req = WatchRequest::new().with_prefix(key.clone()).with_prev_kv();
+
+        let into_req: PbWatchRequest = req.into();
+        assert!(into_req.header.is_none());
+        assert_eq!(key, into_req.key);
+        assert_eq!(b"test_key2".to_vec(), into_req.range_end);
+        assert!(into_req.prev_kv);
+    }
+
+    #[test]
+    fn test_watch_response_trans() {
+        let pb_res = PbWatchResponse {
+            header: None,
+            events: vec![
+                PbEvent {
+                    r#type: PbEventType::Put as i32,
+                    kv: Some(PbKeyValue {
+                        key: b"k1".to_vec(),
+                        value: b"v1".to_vec(),
+                    }),
+                    prev_kv: None,
+                },
+                PbEvent {
+                    r#type: PbEventType::Delete as i32,
+                    kv: Some(PbKeyValue {
+                        key: b"k2".to_vec(),
+                        value: vec![],
+                    }),
+                    prev_kv: Some(PbKeyValue {
+                        key: b"k2".to_vec(),
+                        value: b"v2".to_vec(),
+                    }),
+                },
+            ],
+        };
+
+        let mut res: WatchResponse = pb_res.try_into().unwrap();
+        assert!(res.take_header().is_none());
+        let mut events = res.take_events();
+        assert_eq!(2, events.len());
+
+        let event0 = events.get_mut(0).unwrap();
+        assert_eq!(EventType::Put, event0.event_type());
+        assert_eq!(b"v1".to_vec(), event0.take_kv().unwrap().take_value());
+        assert!(event0.take_prev_kv().is_none());
+
+        let event1 = events.get_mut(1).unwrap();
+        assert_eq!(EventType::Delete, event1.event_type());
+        assert_eq!(b"k2".to_vec(), event1.take_kv().unwrap().take_key());
+        assert_eq!(b"v2".to_vec(), event1.take_prev_kv().unwrap().take_value());
+    }
 }
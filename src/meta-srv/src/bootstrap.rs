@@ -37,10 +37,23 @@ pub async fn bootstrap_meta_srv(opts: MetaSrvOptions) -> crate::Result<()> {
         })?;
     let listener = TcpListenerStream::new(listener);
 
+    let tls_config = opts
+        .tls
+        .as_ref()
+        .map(|tls| tls.setup())
+        .transpose()
+        .context(error::SetupTlsSnafu)?;
+
     let meta_srv = MetaSrv::new(opts, kv_store, None, Some(election)).await;
     meta_srv.start().await;
 
-    tonic::transport::Server::builder()
+    let mut builder = tonic::transport::Server::builder();
+    if let Some(tls_config) = tls_config {
+        builder = builder
+            .tls_config(tls_config)
+            .context(error::StartGrpcSnafu)?;
+    }
+    builder
         .accept_http1(true) // for admin services
         .add_service(HeartbeatServer::new(meta_srv.clone()))
         .add_service(RouterServer::new(meta_srv.clone()))
@@ -49,6 +49,12 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Failed to setup TLS for gRPC server, source: {}", source))]
+    SetupTls {
+        #[snafu(backtrace)]
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display("Empty table name"))]
     EmptyTableName { backtrace: Backtrace },
 
@@ -61,6 +67,18 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Invalid datanode maintenance key: {}", key))]
+    InvalidMaintenanceKey { key: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to parse datanode maintenance key from utf8: {}", source))]
+    MaintenanceKeyFromUtf8 {
+        source: std::string::FromUtf8Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Missing required parameter, param: {:?}", param))]
+    MissingRequiredParameter { param: String, backtrace: Backtrace },
+
     #[snafu(display("Failed to serialize to json: {}", input))]
     SerializeToJson {
         input: String,
@@ -156,14 +174,18 @@ impl ErrorExt for Error {
             Error::EmptyKey { .. }
             | Error::EmptyTableName { .. }
             | Error::InvalidLeaseKey { .. }
+            | Error::InvalidMaintenanceKey { .. }
+            | Error::MissingRequiredParameter { .. }
             | Error::ParseNum { .. }
             | Error::InvalidArguments { .. } => StatusCode::InvalidArguments,
             Error::LeaseKeyFromUtf8 { .. }
+            | Error::MaintenanceKeyFromUtf8 { .. }
             | Error::UnexceptedSequenceValue { .. }
             | Error::TableRouteNotFound { .. }
             | Error::NextSequence { .. }
             | Error::InvalidTxnResult { .. } => StatusCode::Unexpected,
             Error::InvalidCatalogValue { source, .. } => source.status_code(),
+            Error::SetupTls { source } => source.status_code(),
         }
     }
 }
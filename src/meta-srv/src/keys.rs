@@ -27,11 +27,17 @@ use crate::error::Result;
 pub(crate) const DN_LEASE_PREFIX: &str = "__meta_dnlease";
 pub(crate) const SEQ_PREFIX: &str = "__meta_seq";
 pub(crate) const TABLE_ROUTE_PREFIX: &str = "__meta_table_route";
+pub(crate) const MAINTENANCE_PREFIX: &str = "__meta_maintenance";
 
 lazy_static! {
     static ref DATANODE_KEY_PATTERN: Regex =
         Regex::new(&format!("^{}-([0-9]+)-([0-9]+)$", DN_LEASE_PREFIX)).unwrap();
 }
+
+lazy_static! {
+    static ref MAINTENANCE_KEY_PATTERN: Regex =
+        Regex::new(&format!("^{}-([0-9]+)-([0-9]+)$", MAINTENANCE_PREFIX)).unwrap();
+}
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct LeaseKey {
     pub cluster_id: u64,
@@ -123,6 +129,55 @@ impl TryFrom<LeaseValue> for Vec<u8> {
     }
 }
 
+/// Key of a datanode in maintenance, selectors will not allocate new regions to it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MaintenanceKey {
+    pub cluster_id: u64,
+    pub node_id: u64,
+}
+
+impl FromStr for MaintenanceKey {
+    type Err = error::Error;
+
+    fn from_str(key: &str) -> Result<Self> {
+        let caps = MAINTENANCE_KEY_PATTERN
+            .captures(key)
+            .context(error::InvalidMaintenanceKeySnafu { key })?;
+
+        ensure!(caps.len() == 3, error::InvalidMaintenanceKeySnafu { key });
+
+        let cluster_id = caps[1].to_string();
+        let node_id = caps[2].to_string();
+        let cluster_id: u64 = cluster_id.parse().context(error::ParseNumSnafu {
+            err_msg: format!("invalid cluster_id: {}", cluster_id),
+        })?;
+        let node_id: u64 = node_id.parse().context(error::ParseNumSnafu {
+            err_msg: format!("invalid node_id: {}", node_id),
+        })?;
+
+        Ok(Self {
+            cluster_id,
+            node_id,
+        })
+    }
+}
+
+impl TryFrom<Vec<u8>> for MaintenanceKey {
+    type Error = error::Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self> {
+        String::from_utf8(bytes)
+            .context(error::MaintenanceKeyFromUtf8Snafu {})
+            .map(|x| x.parse())?
+    }
+}
+
+impl From<MaintenanceKey> for Vec<u8> {
+    fn from(key: MaintenanceKey) -> Self {
+        format!("{}-{}-{}", MAINTENANCE_PREFIX, key.cluster_id, key.node_id).into_bytes()
+    }
+}
+
 pub struct TableRouteKey<'a> {
     pub table_id: u64,
     pub catalog_name: &'a str,
@@ -190,4 +245,18 @@ mod tests {
 
         assert_eq!(new_value, value);
     }
+
+    #[test]
+    fn test_maintenance_key() {
+        let key = MaintenanceKey {
+            cluster_id: 0,
+            node_id: 1,
+        };
+
+        let key_bytes: Vec<u8> = key.clone().into();
+        assert_eq!(b"__meta_maintenance-0-1".to_vec(), key_bytes);
+        let new_key: MaintenanceKey = key_bytes.try_into().unwrap();
+
+        assert_eq!(new_key, key);
+    }
 }
@@ -19,6 +19,7 @@ pub mod error;
 pub mod handler;
 mod keys;
 pub mod lease;
+pub mod maintenance;
 pub mod metasrv;
 #[cfg(feature = "mock")]
 pub mod mocks;
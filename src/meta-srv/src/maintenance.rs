@@ -0,0 +1,113 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use api::v1::meta::{DeleteRangeRequest, PutRequest, RangeRequest};
+use common_time::util as time_util;
+
+use crate::error::Result;
+use crate::keys::{MaintenanceKey, MAINTENANCE_PREFIX};
+use crate::service::store::kv::KvStoreRef;
+use crate::util;
+
+/// Marks a datanode as in maintenance (or not), a datanode in maintenance is
+/// still alive but will not be chosen by selectors.
+pub async fn set_maintenance(
+    cluster_id: u64,
+    node_id: u64,
+    maintenance: bool,
+    kv_store: &KvStoreRef,
+) -> Result<()> {
+    let key: Vec<u8> = MaintenanceKey {
+        cluster_id,
+        node_id,
+    }
+    .into();
+
+    if maintenance {
+        let req = PutRequest {
+            key,
+            value: time_util::current_time_millis().to_string().into_bytes(),
+            ..Default::default()
+        };
+        let _ = kv_store.put(req).await?;
+    } else {
+        let req = DeleteRangeRequest {
+            key,
+            ..Default::default()
+        };
+        let _ = kv_store.delete_range(req).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the ids of datanodes in maintenance.
+pub async fn maintenance_datanodes(cluster_id: u64, kv_store: &KvStoreRef) -> Result<HashSet<u64>> {
+    let key = get_maintenance_prefix(cluster_id);
+    let range_end = util::get_prefix_end_key(&key);
+    let req = RangeRequest {
+        key,
+        range_end,
+        keys_only: true,
+        ..Default::default()
+    };
+
+    let res = kv_store.range(req).await?;
+
+    let mut node_ids = HashSet::with_capacity(res.kvs.len());
+    for kv in res.kvs {
+        let key: MaintenanceKey = kv.key.try_into()?;
+        node_ids.insert(key.node_id);
+    }
+
+    Ok(node_ids)
+}
+
+#[inline]
+pub fn get_maintenance_prefix(cluster_id: u64) -> Vec<u8> {
+    format!("{}-{}-", MAINTENANCE_PREFIX, cluster_id).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::service::store::memory::MemStore;
+
+    #[tokio::test]
+    async fn test_set_maintenance() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+
+        set_maintenance(0, 1, true, &kv_store).await.unwrap();
+        set_maintenance(0, 2, true, &kv_store).await.unwrap();
+        set_maintenance(1, 3, true, &kv_store).await.unwrap();
+        assert_eq!(
+            HashSet::from([1, 2]),
+            maintenance_datanodes(0, &kv_store).await.unwrap()
+        );
+
+        set_maintenance(0, 1, false, &kv_store).await.unwrap();
+        assert_eq!(
+            HashSet::from([2]),
+            maintenance_datanodes(0, &kv_store).await.unwrap()
+        );
+        assert_eq!(
+            HashSet::from([3]),
+            maintenance_datanodes(1, &kv_store).await.unwrap()
+        );
+    }
+}
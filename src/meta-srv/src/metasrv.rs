@@ -16,6 +16,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use api::v1::meta::Peer;
+use common_grpc::tls::ServerTlsOption;
 use common_telemetry::{info, warn};
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +38,8 @@ pub struct MetaSrvOptions {
     pub server_addr: String,
     pub store_addr: String,
     pub datanode_lease_secs: i64,
+    #[serde(default)]
+    pub tls: Option<ServerTlsOption>,
 }
 
 impl Default for MetaSrvOptions {
@@ -46,6 +49,7 @@ impl Default for MetaSrvOptions {
             server_addr: "0.0.0.0:3002".to_string(),
             store_addr: "0.0.0.0:2379".to_string(),
             datanode_lease_secs: 15,
+            tls: None,
         }
     }
 }
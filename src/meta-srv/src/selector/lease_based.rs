@@ -17,9 +17,9 @@ use common_time::util as time_util;
 
 use crate::error::Result;
 use crate::keys::{LeaseKey, LeaseValue};
-use crate::lease;
 use crate::metasrv::Context;
 use crate::selector::{Namespace, Selector};
+use crate::{lease, maintenance};
 
 pub struct LeaseBasedSelector;
 
@@ -34,6 +34,9 @@ impl Selector for LeaseBasedSelector {
             time_util::current_time_millis() - v.timestamp_millis < ctx.datanode_lease_secs * 1000
         };
         let mut lease_kvs = lease::alive_datanodes(ns, &ctx.kv_store, lease_filter).await?;
+        // datanodes in maintenance are alive but should not be allocated new regions
+        let maintenance_nodes = maintenance::maintenance_datanodes(ns, &ctx.kv_store).await?;
+        lease_kvs.retain(|(k, _)| !maintenance_nodes.contains(&k.node_id));
         // TODO(jiachun): At the moment we are just pushing the latest to the forefront,
         // and it is better to use load-based strategies in the future.
         lease_kvs.sort_by(|a, b| b.1.timestamp_millis.cmp(&a.1.timestamp_millis));
@@ -205,6 +205,13 @@ mod tests {
             ) -> Result<api::v1::meta::DeleteRangeResponse> {
                 unreachable!()
             }
+
+            async fn watch(
+                &self,
+                _: api::v1::meta::WatchRequest,
+            ) -> Result<crate::service::store::kv::WatchStream> {
+                unreachable!()
+            }
         }
 
         let kv_store = Arc::new(Noop {});
@@ -12,21 +12,70 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod catalog;
 mod health;
+mod leader;
+mod maintenance;
+mod nodes;
+mod route;
+mod sequence;
 
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::fmt::Debug;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use serde::Serialize;
+use snafu::ResultExt;
 use tonic::body::BoxBody;
 use tonic::codegen::{empty_body, http, BoxFuture, Service};
 use tonic::transport::NamedService;
 
+use crate::error::{self, Result};
 use crate::metasrv::MetaSrv;
 
-pub fn make_admin_service(_: MetaSrv) -> Admin {
-    let router = Router::new().route("/health", health::HealthHandler);
+pub fn make_admin_service(meta_srv: MetaSrv) -> Admin {
+    let router = Router::new()
+        .route("/health", health::HealthHandler)
+        .route(
+            "/nodes",
+            nodes::NodesHandler {
+                kv_store: meta_srv.kv_store(),
+                datanode_lease_secs: meta_srv.options().datanode_lease_secs,
+            },
+        )
+        .route(
+            "/routes",
+            route::RouteHandler {
+                kv_store: meta_srv.kv_store(),
+            },
+        )
+        .route(
+            "/catalogs",
+            catalog::CatalogsHandler {
+                kv_store: meta_srv.kv_store(),
+            },
+        )
+        .route(
+            "/sequences",
+            sequence::SequencesHandler {
+                kv_store: meta_srv.kv_store(),
+            },
+        )
+        .route(
+            "/leader",
+            leader::LeaderHandler {
+                election: meta_srv.election(),
+                server_addr: meta_srv.options().server_addr.clone(),
+            },
+        )
+        .route(
+            "/maintenance",
+            maintenance::MaintenanceHandler {
+                kv_store: meta_srv.kv_store(),
+            },
+        );
 
     let router = Router::nest("/admin", router);
 
@@ -38,8 +87,9 @@ pub trait HttpHandler: Send + Sync {
     async fn handle(
         &self,
         path: &str,
+        method: http::Method,
         params: &HashMap<String, String>,
-    ) -> crate::Result<http::Response<String>>;
+    ) -> Result<http::Response<String>>;
 }
 
 #[derive(Clone)]
@@ -86,7 +136,8 @@ where
             })
             .unwrap_or_else(HashMap::new);
         let path = req.uri().path().to_owned();
-        Box::pin(async move { router.call(&path, query_params).await })
+        let method = req.method().clone();
+        Box::pin(async move { router.call(&path, method, query_params).await })
     }
 }
 
@@ -125,6 +176,7 @@ impl Router {
     pub async fn call(
         &self,
         path: &str,
+        method: http::Method,
         params: HashMap<String, String>,
     ) -> Result<http::Response<BoxBody>, Infallible> {
         let handler = match self.handlers.get(path) {
@@ -137,7 +189,7 @@ impl Router {
             }
         };
 
-        let res = match handler.handle(path, &params).await {
+        let res = match handler.handle(path, http::Method::GET, &params).await {
             Ok(res) => res.map(boxed),
             Err(e) => http::Response::builder()
                 .status(http::StatusCode::INTERNAL_SERVER_ERROR)
@@ -149,6 +201,33 @@ impl Router {
     }
 }
 
+/// Parses an optional numeric parameter.
+fn parse_num_param(params: &HashMap<String, String>, name: &str) -> Result<Option<u64>> {
+    params
+        .get(name)
+        .map(|v| {
+            v.parse::<u64>().context(error::ParseNumSnafu {
+                err_msg: format!("invalid {}: {}", name, v),
+            })
+        })
+        .transpose()
+}
+
+fn to_json_response<T>(value: &T) -> Result<http::Response<String>>
+where
+    T: Serialize + Debug,
+{
+    let body = serde_json::to_string(value).context(error::SerializeToJsonSnafu {
+        input: format!("{:?}", value),
+    })?;
+
+    Ok(http::Response::builder()
+        .status(http::StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap())
+}
+
 fn check_path(path: &str) {
     if path.is_empty() || !path.starts_with('/') {
         panic!("paths must start with a `/`")
@@ -173,6 +252,7 @@ mod tests {
         async fn handle(
             &self,
             _: &str,
+            _: http::Method,
             _: &HashMap<String, String>,
         ) -> crate::Result<http::Response<String>> {
             Ok(http::Response::builder()
@@ -188,6 +268,7 @@ mod tests {
         async fn handle(
             &self,
             _: &str,
+            _: http::Method,
             _: &HashMap<String, String>,
         ) -> crate::Result<http::Response<String>> {
             error::EmptyKeySnafu {}.fail()
@@ -223,7 +304,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -235,7 +320,11 @@ mod tests {
         let router = Router::new();
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -249,7 +338,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
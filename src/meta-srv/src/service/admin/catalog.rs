@@ -0,0 +1,160 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+
+use api::v1::meta::RangeRequest;
+use common_catalog::{
+    build_catalog_prefix, build_schemas_prefix, build_tables_global_prefix, CatalogKey, SchemaKey,
+    TableGlobalKey, TableGlobalValue,
+};
+use serde::Serialize;
+use snafu::ResultExt;
+use tonic::codegen::http;
+
+use crate::error::{self, Result};
+use crate::service::admin::{to_json_response, HttpHandler};
+use crate::service::store::kv::KvStoreRef;
+use crate::util;
+
+pub struct CatalogsHandler {
+    pub kv_store: KvStoreRef,
+}
+
+#[derive(Debug, Serialize)]
+struct TableInfo {
+    table_name: String,
+    table_id: u32,
+}
+
+/// catalog name -> schema name -> tables
+type Catalogs = BTreeMap<String, BTreeMap<String, Vec<TableInfo>>>;
+
+#[async_trait::async_trait]
+impl HttpHandler for CatalogsHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        _: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let mut catalogs = Catalogs::new();
+
+        for (k, _) in self.range_prefix(build_catalog_prefix()).await? {
+            let key = CatalogKey::parse(&k).context(error::InvalidCatalogValueSnafu)?;
+            catalogs.entry(key.catalog_name).or_default();
+        }
+
+        for (k, _) in self.range_prefix(build_schemas_prefix()).await? {
+            let key = SchemaKey::parse(&k).context(error::InvalidCatalogValueSnafu)?;
+            catalogs
+                .entry(key.catalog_name)
+                .or_default()
+                .entry(key.schema_name)
+                .or_default();
+        }
+
+        for (k, v) in self.range_prefix(build_tables_global_prefix()).await? {
+            let key = TableGlobalKey::parse(&k).context(error::InvalidCatalogValueSnafu)?;
+            let value = TableGlobalValue::parse(&v).context(error::InvalidCatalogValueSnafu)?;
+            catalogs
+                .entry(key.catalog_name)
+                .or_default()
+                .entry(key.schema_name)
+                .or_default()
+                .push(TableInfo {
+                    table_name: key.table_name,
+                    table_id: value.id,
+                });
+        }
+
+        to_json_response(&catalogs)
+    }
+}
+
+impl CatalogsHandler {
+    async fn range_prefix(&self, prefix: String) -> Result<Vec<(String, String)>> {
+        let key = prefix.into_bytes();
+        let range_end = util::get_prefix_end_key(&key);
+        let req = RangeRequest {
+            key,
+            range_end,
+            ..Default::default()
+        };
+        let res = self.kv_store.range(req).await?;
+
+        Ok(res
+            .kvs
+            .into_iter()
+            .map(|kv| {
+                (
+                    String::from_utf8_lossy(&kv.key).to_string(),
+                    String::from_utf8_lossy(&kv.value).to_string(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::meta::PutRequest;
+
+    use super::*;
+    use crate::service::store::memory::MemStore;
+
+    async fn put(kv_store: &KvStoreRef, key: &str, value: &str) {
+        let req = PutRequest {
+            key: key.as_bytes().to_vec(),
+            value: value.as_bytes().to_vec(),
+            ..Default::default()
+        };
+        kv_store.put(req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_catalogs_handle() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+        put(&kv_store, "__c-greptime", "{}").await;
+        put(&kv_store, "__c-empty_catalog", "{}").await;
+        put(&kv_store, "__s-greptime-public", "{}").await;
+        put(&kv_store, "__s-greptime-empty_schema", "{}").await;
+        put(
+            &kv_store,
+            "__tg-greptime-public-demo",
+            r#"{"node_id":1,"regions_id_map":{"1":[0]},"id":1024,"meta":{"schema":{"column_schemas":[],"timestamp_index":null,"version":0},"primary_key_indices":[],"value_indices":[],"engine":"mito","next_column_id":0,"region_numbers":[0],"engine_options":{},"options":{},"created_on":"2022-09-09T09:09:09Z"}}"#,
+        )
+        .await;
+        let handler = CatalogsHandler { kv_store };
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+        let catalogs: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "empty_catalog": {},
+                "greptime": {
+                    "empty_schema": [],
+                    "public": [{"table_name": "demo", "table_id": 1024}]
+                }
+            }),
+            catalogs
+        );
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tonic::codegen::http;
+
+use crate::error::Result;
+use crate::metasrv::ElectionRef;
+use crate::service::admin::{to_json_response, HttpHandler};
+
+pub struct LeaderHandler {
+    pub election: Option<ElectionRef>,
+    pub server_addr: String,
+}
+
+#[derive(Debug, Serialize)]
+struct LeaderInfo {
+    leader: String,
+    is_leader: bool,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for LeaderHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        _: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let info = match &self.election {
+            Some(election) => LeaderInfo {
+                leader: election.leader().await?.0,
+                is_leader: election.is_leader(),
+            },
+            // without election, current node is the only metasrv
+            None => LeaderInfo {
+                leader: self.server_addr.clone(),
+                is_leader: true,
+            },
+        };
+
+        to_json_response(&info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_leader_handle_without_election() {
+        let handler = LeaderHandler {
+            election: None,
+            server_addr: "127.0.0.1:3002".to_string(),
+        };
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+        assert_eq!(
+            r#"{"leader":"127.0.0.1:3002","is_leader":true}"#,
+            res.body()
+        );
+    }
+}
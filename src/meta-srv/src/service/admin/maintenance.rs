@@ -0,0 +1,169 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use snafu::{ensure, OptionExt};
+use tonic::codegen::http;
+
+use crate::error::{self, Result};
+use crate::maintenance;
+use crate::service::admin::{parse_num_param, to_json_response, HttpHandler};
+use crate::service::store::kv::KvStoreRef;
+
+pub struct MaintenanceHandler {
+    pub kv_store: KvStoreRef,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for MaintenanceHandler {
+    /// Lists ids of all datanodes in maintenance on `GET`. Sets maintenance status of
+    /// datanode `node_id` to `enable` on `POST` or `PUT`, then lists them as well.
+    async fn handle(
+        &self,
+        _: &str,
+        method: http::Method,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let cluster_id = parse_num_param(params, "cluster_id")?.unwrap_or(0);
+
+        match method {
+            http::Method::GET => {
+                // GET must not change the cluster state.
+                ensure!(
+                    !params.contains_key("enable"),
+                    error::InvalidArgumentsSnafu {
+                        err_msg: "use POST or PUT to set maintenance mode",
+                    }
+                );
+            }
+            http::Method::POST | http::Method::PUT => {
+                let enable = params
+                    .get("enable")
+                    .context(error::MissingRequiredParameterSnafu { param: "enable" })?;
+                let enable = enable.parse::<bool>().map_err(|_| {
+                    error::InvalidArgumentsSnafu {
+                        err_msg: format!("invalid enable: {}", enable),
+                    }
+                    .build()
+                })?;
+                let node_id = parse_num_param(params, "node_id")?
+                    .context(error::MissingRequiredParameterSnafu { param: "node_id" })?;
+                maintenance::set_maintenance(cluster_id, node_id, enable, &self.kv_store).await?;
+            }
+            _ => {
+                return Ok(http::Response::builder()
+                    .status(http::StatusCode::METHOD_NOT_ALLOWED)
+                    .header(http::header::ALLOW, "GET, POST, PUT")
+                    .body(String::new())
+                    .unwrap());
+            }
+        }
+
+        let mut node_ids = maintenance::maintenance_datanodes(cluster_id, &self.kv_store)
+            .await?
+            .into_iter()
+            .collect::<Vec<_>>();
+        node_ids.sort_unstable();
+
+        to_json_response(&node_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::service::store::memory::MemStore;
+
+    #[tokio::test]
+    async fn test_maintenance_handle() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+        let handler = MaintenanceHandler { kv_store };
+
+        let params = HashMap::from([
+            ("node_id".to_string(), "2".to_string()),
+            ("enable".to_string(), "true".to_string()),
+        ]);
+        let res = handler
+            .handle("", http::Method::POST, &params)
+            .await
+            .unwrap();
+        assert_eq!("[2]", res.body());
+
+        let params = HashMap::from([
+            ("node_id".to_string(), "1".to_string()),
+            ("enable".to_string(), "true".to_string()),
+        ]);
+        let res = handler
+            .handle("", http::Method::PUT, &params)
+            .await
+            .unwrap();
+        assert_eq!("[1,2]", res.body());
+
+        let params = HashMap::from([
+            ("node_id".to_string(), "2".to_string()),
+            ("enable".to_string(), "false".to_string()),
+        ]);
+        let res = handler
+            .handle("", http::Method::POST, &params)
+            .await
+            .unwrap();
+        assert_eq!("[1]", res.body());
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert_eq!("[1]", res.body());
+
+        let params = HashMap::from([("enable".to_string(), "true".to_string())]);
+        assert!(handler
+            .handle("", http::Method::POST, &params)
+            .await
+            .is_err());
+        let params = HashMap::from([("node_id".to_string(), "1".to_string())]);
+        assert!(handler
+            .handle("", http::Method::POST, &params)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_get_is_read_only() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+        let handler = MaintenanceHandler { kv_store };
+
+        let params = HashMap::from([
+            ("node_id".to_string(), "2".to_string()),
+            ("enable".to_string(), "true".to_string()),
+        ]);
+        assert!(handler
+            .handle("", http::Method::GET, &params)
+            .await
+            .is_err());
+        let res = handler
+            .handle("", http::Method::DELETE, &params)
+            .await
+            .unwrap();
+        assert_eq!(http::StatusCode::METHOD_NOT_ALLOWED, res.status());
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert_eq!("[]", res.body());
+    }
+}
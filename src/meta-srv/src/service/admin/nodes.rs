@@ -0,0 +1,135 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_time::util as time_util;
+use serde::Serialize;
+use tonic::codegen::http;
+
+use crate::error::Result;
+use crate::service::admin::{parse_num_param, to_json_response, HttpHandler};
+use crate::service::store::kv::KvStoreRef;
+use crate::{lease, maintenance};
+
+pub struct NodesHandler {
+    pub kv_store: KvStoreRef,
+    pub datanode_lease_secs: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct NodeStatus {
+    cluster_id: u64,
+    node_id: u64,
+    addr: String,
+    last_heartbeat_millis: i64,
+    lease_age_millis: i64,
+    alive: bool,
+    maintenance: bool,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for NodesHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let cluster_id = parse_num_param(params, "cluster_id")?.unwrap_or(0);
+
+        let lease_kvs = lease::alive_datanodes(cluster_id, &self.kv_store, |_, _| true).await?;
+        let maintenance_nodes =
+            maintenance::maintenance_datanodes(cluster_id, &self.kv_store).await?;
+
+        let now = time_util::current_time_millis();
+        let mut nodes = lease_kvs
+            .into_iter()
+            .map(|(k, v)| {
+                let lease_age_millis = now - v.timestamp_millis;
+                NodeStatus {
+                    cluster_id: k.cluster_id,
+                    node_id: k.node_id,
+                    addr: v.node_addr,
+                    last_heartbeat_millis: v.timestamp_millis,
+                    lease_age_millis,
+                    alive: lease_age_millis < self.datanode_lease_secs * 1000,
+                    maintenance: maintenance_nodes.contains(&k.node_id),
+                }
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|n| n.node_id);
+
+        to_json_response(&nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::meta::PutRequest;
+
+    use super::*;
+    use crate::keys::{LeaseKey, LeaseValue};
+    use crate::maintenance::set_maintenance;
+    use crate::service::store::memory::MemStore;
+
+    async fn put_lease(kv_store: &KvStoreRef, node_id: u64, timestamp_millis: i64) {
+        let key = LeaseKey {
+            cluster_id: 0,
+            node_id,
+        };
+        let value = LeaseValue {
+            timestamp_millis,
+            node_addr: format!("127.0.0.1:{}", 3000 + node_id),
+        };
+        let req = PutRequest {
+            key: key.try_into().unwrap(),
+            value: value.try_into().unwrap(),
+            ..Default::default()
+        };
+        kv_store.put(req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_nodes_handle() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+        let now = time_util::current_time_millis();
+        put_lease(&kv_store, 1, now).await;
+        put_lease(&kv_store, 2, now - 60 * 1000).await;
+        set_maintenance(0, 1, true, &kv_store).await.unwrap();
+
+        let handler = NodesHandler {
+            kv_store,
+            datanode_lease_secs: 15,
+        };
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+
+        let nodes: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        let nodes = nodes.as_array().unwrap();
+        assert_eq!(2, nodes.len());
+        assert_eq!(1, nodes[0]["node_id"]);
+        assert_eq!("127.0.0.1:3001", nodes[0]["addr"]);
+        assert!(nodes[0]["alive"].as_bool().unwrap());
+        assert!(nodes[0]["maintenance"].as_bool().unwrap());
+        assert_eq!(2, nodes[1]["node_id"]);
+        assert!(!nodes[1]["alive"].as_bool().unwrap());
+        assert!(!nodes[1]["maintenance"].as_bool().unwrap());
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use api::v1::meta::{Peer, RangeRequest, TableRouteValue};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use serde::Serialize;
+use snafu::{ensure, ResultExt};
+use tonic::codegen::http;
+
+use crate::error::{self, Result};
+use crate::keys::{TableRouteKey, TABLE_ROUTE_PREFIX};
+use crate::service::admin::{to_json_response, HttpHandler};
+use crate::service::store::kv::KvStoreRef;
+use crate::util;
+
+pub struct RouteHandler {
+    pub kv_store: KvStoreRef,
+}
+
+#[derive(Debug, Serialize)]
+struct TableRouteInfo {
+    table_id: u64,
+    catalog_name: String,
+    schema_name: String,
+    table_name: String,
+    region_routes: Vec<RegionRouteInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct RegionRouteInfo {
+    region_id: u64,
+    region_name: String,
+    leader: Option<PeerInfo>,
+    followers: Vec<PeerInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct PeerInfo {
+    id: u64,
+    addr: String,
+}
+
+impl From<&Peer> for PeerInfo {
+    fn from(peer: &Peer) -> Self {
+        Self {
+            id: peer.id,
+            addr: peer.addr.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for RouteHandler {
+    /// Lists routes of the table given by `table` parameter, which can be either a
+    /// full name like `catalog.schema.table` or a table name in default schema.
+    /// Routes of all tables are listed if `table` is absent.
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let key = match params.get("table") {
+            Some(table) => {
+                let (catalog_name, schema_name, table_name) = parse_table_name(table)?;
+                let key = TableRouteKey {
+                    table_id: 0,
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                };
+                format!("{}-", key.prefix())
+            }
+            None => format!("{}-", TABLE_ROUTE_PREFIX),
+        };
+        let key = key.into_bytes();
+        let range_end = util::get_prefix_end_key(&key);
+        let req = RangeRequest {
+            key,
+            range_end,
+            ..Default::default()
+        };
+        let res = self.kv_store.range(req).await?;
+
+        let mut routes = Vec::with_capacity(res.kvs.len());
+        for kv in res.kvs {
+            let value: TableRouteValue = kv
+                .value
+                .as_slice()
+                .try_into()
+                .context(error::DecodeTableRouteSnafu)?;
+            if let Some(route) = to_route_info(value) {
+                routes.push(route);
+            }
+        }
+
+        to_json_response(&routes)
+    }
+}
+
+fn parse_table_name(name: &str) -> Result<(&str, &str, &str)> {
+    let parts = name.split('.').collect::<Vec<_>>();
+    ensure!(
+        parts.iter().all(|p| !p.is_empty()),
+        error::InvalidArgumentsSnafu {
+            err_msg: format!("invalid table name: {}", name),
+        }
+    );
+    match parts[..] {
+        [table] => Ok((DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, table)),
+        [schema, table] => Ok((DEFAULT_CATALOG_NAME, schema, table)),
+        [catalog, schema, table] => Ok((catalog, schema, table)),
+        _ => error::InvalidArgumentsSnafu {
+            err_msg: format!("invalid table name: {}", name),
+        }
+        .fail(),
+    }
+}
+
+fn to_route_info(value: TableRouteValue) -> Option<TableRouteInfo> {
+    let TableRouteValue { peers, table_route } = value;
+    let table_route = table_route?;
+    let table = table_route.table?;
+    let table_name = table.table_name?;
+
+    let region_routes = table_route
+        .region_routes
+        .into_iter()
+        .map(|rr| {
+            let (region_id, region_name) = rr
+                .region
+                .map(|r| (r.id, r.name))
+                .unwrap_or_else(|| (0, String::new()));
+            RegionRouteInfo {
+                region_id,
+                region_name,
+                leader: peers.get(rr.leader_peer_index as usize).map(Into::into),
+                followers: rr
+                    .follower_peer_indexes
+                    .iter()
+                    .filter_map(|i| peers.get(*i as usize).map(Into::into))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Some(TableRouteInfo {
+        table_id: table.id,
+        catalog_name: table_name.catalog_name,
+        schema_name: table_name.schema_name,
+        table_name: table_name.table_name,
+        region_routes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::meta::{PutRequest, Region, RegionRoute, Table, TableName, TableRoute};
+
+    use super::*;
+    use crate::service::store::memory::MemStore;
+
+    async fn put_route(kv_store: &KvStoreRef, table_id: u64, table_name: &str) {
+        let table_name = TableName {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: table_name.to_string(),
+        };
+        let key = TableRouteKey::with_table_name(table_id, &table_name).key();
+        let value = TableRouteValue {
+            peers: vec![
+                Peer {
+                    id: 1,
+                    addr: "127.0.0.1:3001".to_string(),
+                },
+                Peer {
+                    id: 2,
+                    addr: "127.0.0.1:3002".to_string(),
+                },
+            ],
+            table_route: Some(TableRoute {
+                table: Some(Table {
+                    id: table_id,
+                    table_name: Some(table_name.clone()),
+                    ..Default::default()
+                }),
+                region_routes: vec![
+                    RegionRoute {
+                        region: Some(Region {
+                            id: 0,
+                            name: "r0".to_string(),
+                            ..Default::default()
+                        }),
+                        leader_peer_index: 0,
+                        follower_peer_indexes: vec![],
+                    },
+                    RegionRoute {
+                        region: Some(Region {
+                            id: 1,
+                            name: "r1".to_string(),
+                            ..Default::default()
+                        }),
+                        leader_peer_index: 1,
+                        follower_peer_indexes: vec![0],
+                    },
+                ],
+            }),
+        };
+        let req = PutRequest {
+            key: key.into_bytes(),
+            value: value.into(),
+            ..Default::default()
+        };
+        kv_store.put(req).await.unwrap();
+    }
+
+    #[test]
+    fn test_parse_table_name() {
+        assert_eq!(("greptime", "public", "t"), parse_table_name("t").unwrap());
+        assert_eq!(("greptime", "s", "t"), parse_table_name("s.t").unwrap());
+        assert_eq!(("c", "s", "t"), parse_table_name("c.s.t").unwrap());
+        assert!(parse_table_name("c..t").is_err());
+        assert!(parse_table_name("a.c.s.t").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_handle() {
+        let kv_store = Arc::new(MemStore::new()) as _;
+        put_route(&kv_store, 1024, "t").await;
+        put_route(&kv_store, 1025, "t2").await;
+        let handler = RouteHandler { kv_store };
+
+        let params = HashMap::from([("table".to_string(), "t".to_string())]);
+        let res = handler
+            .handle("", http::Method::GET, &params)
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+        let routes: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        let routes = routes.as_array().unwrap();
+        assert_eq!(1, routes.len());
+        assert_eq!(1024, routes[0]["table_id"]);
+        let region_routes = routes[0]["region_routes"].as_array().unwrap();
+        assert_eq!(2, region_routes.len());
+        assert_eq!("127.0.0.1:3001", region_routes[0]["leader"]["addr"]);
+        assert_eq!(2, region_routes[1]["leader"]["id"]);
+        assert_eq!(1, region_routes[1]["followers"][0]["id"]);
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        let routes: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        assert_eq!(2, routes.as_array().unwrap().len());
+    }
+}
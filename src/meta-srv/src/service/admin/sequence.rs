@@ -0,0 +1,145 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use api::v1::meta::RangeRequest;
+use serde::Serialize;
+use snafu::{ensure, OptionExt};
+use tonic::codegen::http;
+
+use crate::error::{self, Result};
+use crate::keys::SEQ_PREFIX;
+use crate::service::admin::{to_json_response, HttpHandler};
+use crate::service::store::kv::KvStoreRef;
+use crate::util;
+
+pub struct SequencesHandler {
+    pub kv_store: KvStoreRef,
+}
+
+#[derive(Debug, Serialize)]
+struct SequenceInfo {
+    name: String,
+    /// Start of the next range to allocate, values less than it are already allocated.
+    next: u64,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for SequencesHandler {
+    /// Reads the sequence given by `name` parameter, or lists all sequences if `name` is
+    /// absent.
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let prefix = format!("{}-", SEQ_PREFIX);
+        let key = match params.get("name") {
+            Some(name) => format!("{}{}", prefix, name),
+            None => prefix.clone(),
+        }
+        .into_bytes();
+        let range_end = if params.contains_key("name") {
+            vec![]
+        } else {
+            util::get_prefix_end_key(&key)
+        };
+        let req = RangeRequest {
+            key,
+            range_end,
+            ..Default::default()
+        };
+        let res = self.kv_store.range(req).await?;
+
+        let mut sequences = Vec::with_capacity(res.kvs.len());
+        for kv in res.kvs {
+            let key = String::from_utf8_lossy(&kv.key).to_string();
+            let name = key.strip_prefix(&prefix).unwrap_or(&key).to_string();
+            let value: [u8; 8] =
+                kv.value
+                    .try_into()
+                    .ok()
+                    .context(error::UnexceptedSequenceValueSnafu {
+                        err_msg: format!("invalid value of sequence {}", name),
+                    })?;
+            sequences.push(SequenceInfo {
+                name,
+                next: u64::from_le_bytes(value),
+            });
+        }
+
+        if let Some(name) = params.get("name") {
+            ensure!(
+                !sequences.is_empty(),
+                error::InvalidArgumentsSnafu {
+                    err_msg: format!("sequence not found: {}", name),
+                }
+            );
+        }
+
+        to_json_response(&sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::sequence::Sequence;
+    use crate::service::store::memory::MemStore;
+
+    #[tokio::test]
+    async fn test_sequences_handle() {
+        let kv_store = Arc::new(MemStore::new()) as KvStoreRef;
+        let table_id = Sequence::new("table_id", 1024, 10, kv_store.clone());
+        assert_eq!(1024, table_id.next().await.unwrap());
+        let region_id = Sequence::new("region_id", 0, 100, kv_store.clone());
+        assert_eq!(0, region_id.next().await.unwrap());
+        let handler = SequencesHandler { kv_store };
+
+        let res = handler
+            .handle("", http::Method::GET, &HashMap::default())
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+        let sequences: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        assert_eq!(
+            serde_json::json!([
+                {"name": "region_id", "next": 100},
+                {"name": "table_id", "next": 1034},
+            ]),
+            sequences
+        );
+
+        let params = HashMap::from([("name".to_string(), "table_id".to_string())]);
+        let res = handler
+            .handle("", http::Method::GET, &params)
+            .await
+            .unwrap();
+        let sequences: serde_json::Value = serde_json::from_str(res.body()).unwrap();
+        assert_eq!(
+            serde_json::json!([{"name": "table_id", "next": 1034}]),
+            sequences
+        );
+
+        let params = HashMap::from([("name".to_string(), "unknown".to_string())]);
+        assert!(handler
+            .handle("", http::Method::GET, &params)
+            .await
+            .is_err());
+    }
+}
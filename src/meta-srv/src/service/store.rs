@@ -19,14 +19,18 @@ pub mod memory;
 use api::v1::meta::{
     store_server, BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, PutRequest, PutResponse, RangeRequest, RangeResponse,
+    WatchRequest, WatchResponse,
 };
-use tonic::{Request, Response};
+use futures::StreamExt;
+use tonic::{Request, Response, Status};
 
 use crate::metasrv::MetaSrv;
-use crate::service::GrpcResult;
+use crate::service::{GrpcResult, GrpcStream};
 
 #[async_trait::async_trait]
 impl store_server::Store for MetaSrv {
+    type WatchStream = GrpcStream<WatchResponse>;
+
     async fn range(&self, req: Request<RangeRequest>) -> GrpcResult<RangeResponse> {
         let req = req.into_inner();
         let res = self.kv_store().range(req).await?;
@@ -67,6 +71,14 @@ impl store_server::Store for MetaSrv {
 
         Ok(Response::new(res))
     }
+
+    async fn watch(&self, req: Request<WatchRequest>) -> GrpcResult<Self::WatchStream> {
+        let req = req.into_inner();
+        let stream = self.kv_store().watch(req).await?;
+        let stream = stream.map(|res| res.map_err(Status::from));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
 }
 
 #[cfg(test)]
@@ -130,4 +142,30 @@ mod tests {
 
         assert!(res.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let kv_store = Arc::new(MemStore::new());
+        let meta_srv = MetaSrv::new(MetaSrvOptions::default(), kv_store, None, None).await;
+        let req = WatchRequest {
+            key: b"key".to_vec(),
+            ..Default::default()
+        };
+        let mut stream = meta_srv
+            .watch(req.into_request())
+            .await
+            .unwrap()
+            .into_inner();
+
+        let req = PutRequest {
+            key: b"key".to_vec(),
+            value: b"value".to_vec(),
+            ..Default::default()
+        };
+        meta_srv.put(req.into_request()).await.unwrap();
+
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(1, res.events.len());
+        assert_eq!(b"value".to_vec(), res.events[0].kv.as_ref().unwrap().value);
+    }
 }
@@ -14,19 +14,24 @@
 
 use std::sync::Arc;
 
+use api::v1::meta::event::EventType;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
-    DeleteRangeRequest, DeleteRangeResponse, KeyValue, PutRequest, PutResponse, RangeRequest,
-    RangeResponse, ResponseHeader,
+    DeleteRangeRequest, DeleteRangeResponse, Event, KeyValue, PutRequest, PutResponse,
+    RangeRequest, RangeResponse, ResponseHeader, WatchRequest, WatchResponse,
 };
 use common_error::prelude::*;
+use common_telemetry::{info, warn};
 use etcd_client::{
     Client, Compare, CompareOp, DeleteOptions, GetOptions, PutOptions, Txn, TxnOp, TxnOpResponse,
+    WatchOptions,
 };
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::error;
 use crate::error::Result;
-use crate::service::store::kv::{KvStore, KvStoreRef};
+use crate::service::store::kv::{KvStore, KvStoreRef, WatchStream};
 
 #[derive(Clone)]
 pub struct EtcdStore {
@@ -226,6 +231,72 @@ impl KvStore for EtcdStore {
             prev_kvs,
         })
     }
+
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let Watch {
+            cluster_id,
+            key,
+            options,
+        } = req.try_into()?;
+
+        let (mut watcher, mut stream) = self
+            .client
+            .watch_client()
+            .watch(key, options)
+            .await
+            .context(error::EtcdFailedSnafu)?;
+
+        let (tx, rx) = mpsc::channel(128);
+        common_runtime::spawn_bg(async move {
+            loop {
+                let res = tokio::select! {
+                    res = stream.message() => res,
+                    _ = tx.closed() => break,
+                };
+                let res = match res {
+                    Ok(Some(res)) => res,
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = tx.send(Err(e).context(error::EtcdFailedSnafu)).await;
+                        break;
+                    }
+                };
+                if res.canceled() {
+                    warn!("Etcd watcher {} canceled", res.watch_id());
+                    break;
+                }
+
+                let events = res.events().iter().map(to_event).collect::<Vec<_>>();
+                let header = Some(ResponseHeader::success(cluster_id));
+                if tx.send(Ok(WatchResponse { header, events })).await.is_err() {
+                    break;
+                }
+            }
+
+            if let Err(e) = watcher.cancel().await {
+                warn!(
+                    "Failed to cancel etcd watcher {}, error: {}",
+                    watcher.watch_id(),
+                    e
+                );
+            }
+            info!("Etcd watcher {} stopped", watcher.watch_id());
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
+
+fn to_event(event: &etcd_client::Event) -> Event {
+    let event_type = match event.event_type() {
+        etcd_client::EventType::Put => EventType::Put,
+        etcd_client::EventType::Delete => EventType::Delete,
+    };
+    Event {
+        r#type: event_type as i32,
+        kv: event.kv().map(|kv| KvPair::new(kv).into()),
+        prev_kv: event.prev_kv().map(|kv| KvPair::new(kv).into()),
+    }
 }
 
 struct Get {
@@ -392,6 +463,41 @@ impl TryFrom<DeleteRangeRequest> for Delete {
     }
 }
 
+struct Watch {
+    cluster_id: u64,
+    key: Vec<u8>,
+    options: Option<WatchOptions>,
+}
+
+impl TryFrom<WatchRequest> for Watch {
+    type Error = error::Error;
+
+    fn try_from(req: WatchRequest) -> Result<Self> {
+        let WatchRequest {
+            header,
+            key,
+            range_end,
+            prev_kv,
+        } = req;
+
+        ensure!(!key.is_empty(), error::EmptyKeySnafu);
+
+        let mut options = WatchOptions::default();
+        if !range_end.is_empty() {
+            options = options.with_range(range_end);
+        }
+        if prev_kv {
+            options = options.with_prev_key();
+        }
+
+        Ok(Watch {
+            cluster_id: header.map_or(0, |h| h.cluster_id),
+            key,
+            options: Some(options),
+        })
+    }
+}
+
 struct KvPair<'a>(&'a etcd_client::KeyValue);
 
 impl<'a> KvPair<'a> {
@@ -496,4 +602,19 @@ mod tests {
         assert_eq!(b"test_key".to_vec(), delete.key);
         assert!(delete.options.is_some());
     }
+
+    #[test]
+    fn test_parse_watch() {
+        let req = WatchRequest {
+            key: b"test_key".to_vec(),
+            range_end: b"test_range_end".to_vec(),
+            prev_kv: true,
+            ..Default::default()
+        };
+
+        let watch: Watch = req.try_into().unwrap();
+
+        assert_eq!(b"test_key".to_vec(), watch.key);
+        assert!(watch.options.is_some());
+    }
 }
@@ -12,16 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::pin::Pin;
 use std::sync::Arc;
 
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, PutRequest, PutResponse, RangeRequest, RangeResponse,
+    WatchRequest, WatchResponse,
 };
+use futures::Stream;
 
 use crate::error::Result;
 
 pub type KvStoreRef = Arc<dyn KvStore>;
+pub type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchResponse>> + Send + Sync + 'static>>;
 
 #[async_trait::async_trait]
 pub trait KvStore: Send + Sync {
@@ -34,4 +38,8 @@ pub trait KvStore: Send + Sync {
     async fn compare_and_put(&self, req: CompareAndPutRequest) -> Result<CompareAndPutResponse>;
 
     async fn delete_range(&self, req: DeleteRangeRequest) -> Result<DeleteRangeResponse>;
+
+    /// Watches the changes of the given key or range, the returned stream keeps
+    /// yielding events until it is dropped.
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream>;
 }
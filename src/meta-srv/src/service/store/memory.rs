@@ -17,20 +17,44 @@ use std::collections::BTreeMap;
 use std::ops::Range;
 use std::sync::Arc;
 
+use api::v1::meta::event::EventType;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
-    DeleteRangeRequest, DeleteRangeResponse, KeyValue, PutRequest, PutResponse, RangeRequest,
-    RangeResponse, ResponseHeader,
+    DeleteRangeRequest, DeleteRangeResponse, Event, KeyValue, PutRequest, PutResponse,
+    RangeRequest, RangeResponse, ResponseHeader, WatchRequest, WatchResponse,
 };
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::error::Result;
-use crate::service::store::kv::KvStore;
+use crate::service::store::kv::{KvStore, WatchStream};
 
 /// Only for mock test
 #[derive(Clone)]
 pub struct MemStore {
     inner: Arc<RwLock<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    watchers: Arc<Mutex<Vec<Watcher>>>,
+}
+
+struct Watcher {
+    cluster_id: u64,
+    key: Vec<u8>,
+    range_end: Vec<u8>,
+    prev_kv: bool,
+    sender: mpsc::UnboundedSender<Result<WatchResponse>>,
+}
+
+impl Watcher {
+    fn is_watching(&self, key: &[u8]) -> bool {
+        if self.range_end.is_empty() {
+            key == self.key
+        } else if self.range_end == [0] {
+            key >= self.key.as_slice()
+        } else {
+            key >= self.key.as_slice() && key < self.range_end.as_slice()
+        }
+    }
 }
 
 impl Default for MemStore {
@@ -43,7 +67,68 @@ impl MemStore {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(Default::default())),
+            watchers: Arc::new(Mutex::new(Default::default())),
+        }
+    }
+
+    /// Sends events to the watchers which are interested in them, watchers whose
+    /// stream has been dropped are removed.
+    fn notify(&self, events: Vec<Event>) {
+        if events.is_empty() {
+            return;
         }
+
+        let mut watchers = self.watchers.lock();
+        watchers.retain(|watcher| {
+            let events = events
+                .iter()
+                .filter(|event| {
+                    event
+                        .kv
+                        .as_ref()
+                        .map_or(false, |kv| watcher.is_watching(&kv.key))
+                })
+                .map(|event| {
+                    let mut event = event.clone();
+                    if !watcher.prev_kv {
+                        event.prev_kv = None;
+                    }
+                    event
+                })
+                .collect::<Vec<_>>();
+            if events.is_empty() {
+                return !watcher.sender.is_closed();
+            }
+
+            let header = Some(ResponseHeader::success(watcher.cluster_id));
+            watcher
+                .sender
+                .send(Ok(WatchResponse { header, events }))
+                .is_ok()
+        });
+    }
+}
+
+fn put_event(key: Vec<u8>, value: Vec<u8>, prev_value: Option<Vec<u8>>) -> Event {
+    let prev_kv = prev_value.map(|value| KeyValue {
+        key: key.clone(),
+        value,
+    });
+    Event {
+        r#type: EventType::Put as i32,
+        kv: Some(KeyValue { key, value }),
+        prev_kv,
+    }
+}
+
+fn delete_event(prev_kv: KeyValue) -> Event {
+    Event {
+        r#type: EventType::Delete as i32,
+        kv: Some(KeyValue {
+            key: prev_kv.key.clone(),
+            value: vec![],
+        }),
+        prev_kv: Some(prev_kv),
     }
 }
 
@@ -102,12 +187,18 @@ impl KvStore for MemStore {
         } = req;
 
         let mut memory = self.inner.write();
-        let prev_value = memory.insert(key.clone(), value);
+        let prev_value = memory.insert(key.clone(), value.clone());
+        drop(memory);
+
         let prev_kv = if prev_kv {
-            prev_value.map(|value| KeyValue { key, value })
+            prev_value.clone().map(|value| KeyValue {
+                key: key.clone(),
+                value,
+            })
         } else {
             None
         };
+        self.notify(vec![put_event(key, value, prev_value)]);
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
         let header = Some(ResponseHeader::success(cluster_id));
@@ -122,21 +213,23 @@ impl KvStore for MemStore {
         } = req;
 
         let mut memory = self.inner.write();
-        let prev_kvs = if prev_kv {
-            kvs.into_iter()
-                .map(|kv| (kv.key.clone(), memory.insert(kv.key, kv.value)))
-                .filter(|(_, v)| v.is_some())
-                .map(|(key, value)| KeyValue {
-                    key,
-                    value: value.unwrap(),
-                })
-                .collect()
-        } else {
-            for kv in kvs.into_iter() {
-                memory.insert(kv.key, kv.value);
+        let mut events = Vec::with_capacity(kvs.len());
+        let mut prev_kvs = vec![];
+        for kv in kvs.into_iter() {
+            let prev_value = memory.insert(kv.key.clone(), kv.value.clone());
+            if prev_kv {
+                if let Some(value) = &prev_value {
+                    prev_kvs.push(KeyValue {
+                        key: kv.key.clone(),
+                        value: value.clone(),
+                    });
+                }
             }
-            vec![]
-        };
+            events.push(put_event(kv.key, kv.value, prev_value));
+        }
+        drop(memory);
+
+        self.notify(events);
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
         let header = Some(ResponseHeader::success(cluster_id));
@@ -153,11 +246,11 @@ impl KvStore for MemStore {
 
         let mut memory = self.inner.write();
 
-        let (success, prev_kv) = match memory.entry(key) {
+        let (success, prev_kv) = match memory.entry(key.clone()) {
             Entry::Vacant(e) => {
                 let success = expect.is_empty();
                 if success {
-                    e.insert(value);
+                    e.insert(value.clone());
                 }
                 (success, None)
             }
@@ -166,11 +259,17 @@ impl KvStore for MemStore {
                 let prev_val = e.get().clone();
                 let success = prev_val == expect;
                 if success {
-                    e.insert(value);
+                    e.insert(value.clone());
                 }
                 (success, Some((key, prev_val)))
             }
         };
+        drop(memory);
+
+        if success {
+            let prev_value = prev_kv.as_ref().map(|(_, value)| value.clone());
+            self.notify(vec![put_event(key, value, prev_value)]);
+        }
 
         let prev_kv = prev_kv.map(|(key, value)| KeyValue { key, value });
 
@@ -206,6 +305,9 @@ impl KvStore for MemStore {
                 .map(|(key, value)| KeyValue { key, value })
                 .collect::<Vec<_>>()
         };
+        drop(memory);
+
+        self.notify(prev_kvs.iter().cloned().map(delete_event).collect());
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
         let header = Some(ResponseHeader::success(cluster_id));
@@ -219,4 +321,112 @@ impl KvStore for MemStore {
             },
         })
     }
+
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let WatchRequest {
+            header,
+            key,
+            range_end,
+            prev_kv,
+        } = req;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let watcher = Watcher {
+            cluster_id: header.map_or(0, |h| h.cluster_id),
+            key,
+            range_end,
+            prev_kv,
+            sender,
+        };
+        self.watchers.lock().push(watcher);
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watch_prefix() {
+        let store = MemStore::new();
+        let mut stream = store
+            .watch(WatchRequest {
+                key: b"key".to_vec(),
+                range_end: b"kez".to_vec(),
+                prev_kv: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let req = PutRequest {
+            key: b"key1".to_vec(),
+            value: b"value1".to_vec(),
+            ..Default::default()
+        };
+        store.put(req).await.unwrap();
+        // not watched
+        let req = PutRequest {
+            key: b"other".to_vec(),
+            value: b"value".to_vec(),
+            ..Default::default()
+        };
+        store.put(req).await.unwrap();
+        let req = PutRequest {
+            key: b"key1".to_vec(),
+            value: b"value2".to_vec(),
+            ..Default::default()
+        };
+        store.put(req).await.unwrap();
+        let req = DeleteRangeRequest {
+            key: b"key1".to_vec(),
+            ..Default::default()
+        };
+        store.delete_range(req).await.unwrap();
+
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(1, res.events.len());
+        let event = &res.events[0];
+        assert_eq!(EventType::Put as i32, event.r#type);
+        assert_eq!(b"key1".to_vec(), event.kv.as_ref().unwrap().key);
+        assert!(event.prev_kv.is_none());
+
+        let res = stream.next().await.unwrap().unwrap();
+        let event = &res.events[0];
+        assert_eq!(EventType::Put as i32, event.r#type);
+        assert_eq!(b"value2".to_vec(), event.kv.as_ref().unwrap().value);
+        assert_eq!(b"value1".to_vec(), event.prev_kv.as_ref().unwrap().value);
+
+        let res = stream.next().await.unwrap().unwrap();
+        let event = &res.events[0];
+        assert_eq!(EventType::Delete as i32, event.r#type);
+        assert_eq!(b"key1".to_vec(), event.kv.as_ref().unwrap().key);
+        assert_eq!(b"value2".to_vec(), event.prev_kv.as_ref().unwrap().value);
+    }
+
+    #[tokio::test]
+    async fn test_drop_watch_stream() {
+        let store = MemStore::new();
+        let stream = store
+            .watch(WatchRequest {
+                key: b"key".to_vec(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, store.watchers.lock().len());
+
+        drop(stream);
+        let req = PutRequest {
+            key: b"key".to_vec(),
+            value: b"value".to_vec(),
+            ..Default::default()
+        };
+        store.put(req).await.unwrap();
+        assert!(store.watchers.lock().is_empty());
+    }
 }
@@ -0,0 +1,513 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An optional cache of query results.
+//!
+//! The results of a query are cached by its optimized logical plan and the data versions of the
+//! tables it reads, so writes to the tables invalidate them. Range queries also cache the
+//! windows that have been immutable for a while, to only read the rows of the other windows when
+//! the query is repeated over a moving time range until the table is written.
+//!
+//! The results are cached while they are streamed to the client, and are dropped once they
+//! exceed the memory budget. The cached values are evicted in LRU order when they exceed the
+//! memory budget.
+
+use std::collections::{BTreeMap, HashMap};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use arrow::compute::aggregate::estimated_bytes_size;
+use common_recordbatch::error::Result as RecordBatchResult;
+use common_recordbatch::{
+    RecordBatch, RecordBatchStream, RecordBatches, SendableRecordBatchStream,
+};
+use datafusion::logical_plan::{
+    Expr, ExprVisitable, ExpressionVisitor, LogicalPlan as DfLogicalPlan, Recursion,
+};
+use datafusion::physical_plan::functions::Volatility;
+use datatypes::schema::SchemaRef;
+use futures::Stream;
+use metrics::{gauge, increment_counter};
+use serde::{Deserialize, Serialize};
+use store_api::storage::DataVersion;
+use table::table::adapter::DfTableProviderAdapter;
+use table::TableRef;
+
+use crate::metric;
+use crate::plan::LogicalPlan;
+use crate::range_select::RangeWindows;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryCacheOptions {
+    /// Memory budget of the cache in bytes, the cache is disabled if it's 0.
+    pub capacity: usize,
+    /// Windows of range queries are only cached once they end this long before now, rows may
+    /// still be written into the later windows.
+    pub immutable_after_millis: i64,
+}
+
+impl Default for QueryCacheOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            immutable_after_millis: 5 * 60 * 1000,
+        }
+    }
+}
+
+impl QueryCacheOptions {
+    pub fn enabled(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+/// Counters of the cache, since it was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum CachedValue {
+    Results(Arc<RecordBatches>),
+    Windows(Arc<RangeWindows>),
+}
+
+struct CacheEntry {
+    value: CachedValue,
+    bytes: usize,
+    /// Tick of the last access.
+    tick: u64,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<String, CacheEntry>,
+    /// Keys of the entries by the tick of their last access, the least recently used first.
+    lru: BTreeMap<u64, String>,
+    tick: u64,
+    bytes: usize,
+    stats: QueryCacheStats,
+}
+
+impl CacheInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.lru.remove(&entry.tick);
+        self.bytes -= entry.bytes;
+        Some(entry)
+    }
+}
+
+pub struct QueryCache {
+    capacity: usize,
+    immutable_after_millis: i64,
+    inner: Mutex<CacheInner>,
+}
+
+pub type QueryCacheRef = Arc<QueryCache>;
+
+impl QueryCache {
+    pub fn new(options: &QueryCacheOptions) -> Self {
+        Self {
+            capacity: options.capacity,
+            immutable_after_millis: options.immutable_after_millis,
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    pub fn stats(&self) -> QueryCacheStats {
+        self.inner.lock().unwrap().stats
+    }
+
+    /// Returns the bytes of the cached values.
+    pub fn bytes(&self) -> usize {
+        self.inner.lock().unwrap().bytes
+    }
+
+    pub(crate) fn immutable_after_millis(&self) -> i64 {
+        self.immutable_after_millis
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<CachedValue> {
+        let mut inner = self.inner.lock().unwrap();
+        let tick = inner.next_tick();
+        let value = match inner.entries.get_mut(key) {
+            Some(entry) => {
+                let old_tick = std::mem::replace(&mut entry.tick, tick);
+                Some((entry.value.clone(), old_tick))
+            }
+            None => None,
+        };
+        match value {
+            Some((value, old_tick)) => {
+                let key = inner.lru.remove(&old_tick).unwrap();
+                inner.lru.insert(tick, key);
+                inner.stats.hits += 1;
+                increment_counter!(metric::METRIC_QUERY_CACHE_HIT);
+                Some(value)
+            }
+            None => {
+                inner.stats.misses += 1;
+                increment_counter!(metric::METRIC_QUERY_CACHE_MISS);
+                None
+            }
+        }
+    }
+
+    /// Caches the value of `bytes`, the least recently used values are evicted to keep the cache
+    /// in its memory budget. The value is not cached if it's larger than the budget.
+    pub(crate) fn insert(&self, key: String, value: CachedValue, bytes: usize) {
+        let bytes = bytes + key.len();
+        if bytes > self.capacity {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        let _ = inner.remove(&key);
+        while inner.bytes + bytes > self.capacity {
+            let oldest = match inner.lru.keys().next() {
+                Some(tick) => inner.lru[tick].clone(),
+                None => break,
+            };
+            let _ = inner.remove(&oldest);
+            inner.stats.evictions += 1;
+            increment_counter!(metric::METRIC_QUERY_CACHE_EVICT);
+        }
+
+        let tick = inner.next_tick();
+        inner.lru.insert(tick, key.clone());
+        inner.entries.insert(key, CacheEntry { value, bytes, tick });
+        inner.bytes += bytes;
+        gauge!(metric::METRIC_QUERY_CACHE_BYTES, inner.bytes as f64);
+    }
+
+    pub(crate) fn get_results(&self, key: &str) -> Option<Arc<RecordBatches>> {
+        match self.get(key)? {
+            CachedValue::Results(results) => Some(results),
+            CachedValue::Windows(_) => None,
+        }
+    }
+
+    /// Caches the results of the stream once it ends, while they are streamed to the client.
+    pub(crate) fn tee_results(
+        self: &Arc<Self>,
+        key: String,
+        stream: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        Box::pin(CachingStream {
+            schema: stream.schema(),
+            stream,
+            cache: self.clone(),
+            key,
+            batches: Some(Vec::new()),
+            bytes: 0,
+        })
+    }
+
+    pub(crate) fn get_windows(&self, key: &str) -> Option<Arc<RangeWindows>> {
+        match self.get(key)? {
+            CachedValue::Windows(windows) => Some(windows),
+            CachedValue::Results(_) => None,
+        }
+    }
+
+    pub(crate) fn insert_windows(&self, key: String, windows: RangeWindows) {
+        let bytes = windows.estimated_bytes();
+        self.insert(key, CachedValue::Windows(Arc::new(windows)), bytes);
+    }
+}
+
+/// Returns the key to cache the results of the optimized `plan`, `None` if they can't be cached.
+///
+/// The key lists the operators of the plan with the types of their outputs, then the ids and
+/// data versions of the tables they scan, in the same order.
+pub(crate) async fn results_key(plan: &LogicalPlan) -> Option<String> {
+    let (input, range_select) = match plan {
+        LogicalPlan::DfPlan(plan) => (plan, None),
+        LogicalPlan::RangeSelect(range_select) => (&range_select.input, Some(range_select)),
+        LogicalPlan::Explain(_) => return None,
+    };
+    let mut operators = Vec::new();
+    if let Some(range_select) = range_select {
+        operators.push(format!(
+            "RangeSelect: align={}, by={}, aggregates={:?}, output={:?}, order_by={:?}, offset={}, limit={:?}",
+            range_select.align_millis,
+            range_select.by_count,
+            range_select.aggregates,
+            range_select.output,
+            range_select.order_by,
+            range_select.offset,
+            range_select.limit
+        ));
+    }
+    let mut tables = Vec::new();
+    collect_operators(input, &mut operators, &mut tables)?;
+    let versions = data_versions(&tables).await?;
+    Some(format!("results:{}\n{:?}", operators.join("\n"), versions))
+}
+
+/// Returns the full names with the ids, and the data versions of the tables scanned by the
+/// `plan`. The names tell apart the tables of frontends, which have no ids.
+///
+/// Returns `None` if the results of the plan can't be cached, that is, it scans a table without
+/// data versions or calls a function that is not immutable.
+pub(crate) async fn scanned_tables(
+    plan: &DfLogicalPlan,
+) -> Option<Vec<(String, Vec<DataVersion>)>> {
+    let mut tables = Vec::new();
+    collect_operators(plan, &mut Vec::new(), &mut tables)?;
+    data_versions(&tables).await
+}
+
+/// Collects the operators of the `plan` and the tables they scan.
+fn collect_operators(
+    plan: &DfLogicalPlan,
+    operators: &mut Vec<String>,
+    tables: &mut Vec<TableRef>,
+) -> Option<()> {
+    if plan.expressions().iter().any(is_volatile) {
+        return None;
+    }
+    if let DfLogicalPlan::TableScan(scan) = plan {
+        let table = scan
+            .source
+            .as_any()
+            .downcast_ref::<DfTableProviderAdapter>()?
+            .table();
+        tables.push(table);
+    }
+    // The operator alone doesn't tell the types of its expressions, like the type of a literal
+    // or a function of the same name.
+    let fields = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| format!("{}: {:?}", field.qualified_name(), field.data_type()))
+        .collect::<Vec<_>>();
+    operators.push(format!("{} [{}]", plan.display(), fields.join(", ")));
+    for input in plan.inputs() {
+        collect_operators(input, operators, tables)?;
+    }
+    Some(())
+}
+
+async fn data_versions(tables: &[TableRef]) -> Option<Vec<(String, Vec<DataVersion>)>> {
+    let mut versions = Vec::with_capacity(tables.len());
+    for table in tables {
+        let info = table.table_info();
+        let name = format!(
+            "{}.{}.{}#{}",
+            info.catalog_name, info.schema_name, info.name, info.ident.table_id
+        );
+        versions.push((name, table.data_versions().await?));
+    }
+    Some(versions)
+}
+
+fn batch_bytes(batch: &RecordBatch) -> usize {
+    batch
+        .df_recordbatch
+        .columns()
+        .iter()
+        .map(|array| estimated_bytes_size(array.as_ref()))
+        .sum()
+}
+
+/// Streams the results of a query, and caches them once the stream ends. The results are not
+/// cached if they exceed the memory budget of the cache, or the stream fails.
+struct CachingStream {
+    schema: SchemaRef,
+    stream: SendableRecordBatchStream,
+    cache: QueryCacheRef,
+    key: String,
+    /// The batches streamed so far, `None` once they are not to be cached.
+    batches: Option<Vec<RecordBatch>>,
+    bytes: usize,
+}
+
+impl RecordBatchStream for CachingStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for CachingStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.stream).poll_next(cx);
+        let this = &mut *self;
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => {
+                if let Some(batches) = &mut this.batches {
+                    this.bytes += batch_bytes(batch);
+                    if this.bytes + this.key.len() > this.cache.capacity {
+                        this.batches = None;
+                    } else {
+                        batches.push(batch.clone());
+                    }
+                }
+            }
+            Poll::Ready(Some(Err(_))) => this.batches = None,
+            Poll::Ready(None) => {
+                if let Some(batches) = this.batches.take() {
+                    if let Ok(results) = RecordBatches::try_new(this.schema.clone(), batches) {
+                        this.cache.insert(
+                            std::mem::take(&mut this.key),
+                            CachedValue::Results(Arc::new(results)),
+                            this.bytes,
+                        );
+                    }
+                }
+            }
+            Poll::Pending => {}
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// Whether the `expr` calls a function whose result may change between queries.
+pub(crate) fn is_volatile(expr: &Expr) -> bool {
+    expr.accept(VolatileVisitor::default())
+        .map(|visitor| visitor.volatile)
+        .unwrap_or(true)
+}
+
+#[derive(Default)]
+struct VolatileVisitor {
+    volatile: bool,
+}
+
+impl ExpressionVisitor for VolatileVisitor {
+    fn pre_visit(mut self, expr: &Expr) -> datafusion::error::Result<Recursion<Self>> {
+        self.volatile = match expr {
+            Expr::ScalarFunction { fun, .. } => fun.volatility() != Volatility::Immutable,
+            Expr::ScalarUDF { fun, .. } => fun.signature.volatility != Volatility::Immutable,
+            _ => false,
+        };
+        if self.volatile {
+            Ok(Recursion::Stop(self))
+        } else {
+            Ok(Recursion::Continue(self))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_recordbatch::util;
+    use datatypes::prelude::*;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::StringVector;
+
+    use super::*;
+
+    fn results(value: &str) -> RecordBatches {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "v",
+            ConcreteDataType::string_datatype(),
+            false,
+        )]));
+        RecordBatches::try_from_columns(
+            schema,
+            vec![Arc::new(StringVector::from(vec![value])) as VectorRef],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_query_cache_lru() {
+        let cache = QueryCache::new(&QueryCacheOptions {
+            capacity: 30,
+            ..Default::default()
+        });
+        let value = CachedValue::Results(Arc::new(results("v")));
+        // Every entry takes 10 bytes with its key.
+        for key in ["a", "b", "c"] {
+            cache.insert(key.to_string(), value.clone(), 9);
+        }
+        assert_eq!(30, cache.bytes());
+        assert!(cache.get_results("a").is_some());
+        assert!(cache.get_results("x").is_none());
+
+        // "b" is the least recently used.
+        cache.insert("d".to_string(), value.clone(), 9);
+        assert_eq!(30, cache.bytes());
+        assert!(cache.get_results("b").is_none());
+        for key in ["a", "c", "d"] {
+            assert!(cache.get_results(key).is_some());
+        }
+        assert_eq!(
+            QueryCacheStats {
+                hits: 4,
+                misses: 2,
+                evictions: 1,
+            },
+            cache.stats()
+        );
+
+        // Replaces the value of the key.
+        cache.insert("a".to_string(), value.clone(), 4);
+        assert_eq!(25, cache.bytes());
+
+        // Too large to be cached.
+        cache.insert("e".to_string(), value, 30);
+        assert!(cache.get_results("e").is_none());
+        assert_eq!(25, cache.bytes());
+    }
+
+    #[tokio::test]
+    async fn test_cache_results() {
+        let cache = Arc::new(QueryCache::new(&QueryCacheOptions {
+            capacity: 1024,
+            ..Default::default()
+        }));
+        let stream = cache.tee_results("a".to_string(), results("hello").as_stream());
+        // Cached once the stream ends.
+        assert!(cache.get_results("a").is_none());
+        let batches = util::collect(stream).await.unwrap();
+        assert_eq!(results("hello").take(), batches);
+        assert!(cache.bytes() > "a".len());
+        let cached = cache.get_results("a").unwrap();
+        assert_eq!(
+            results("hello").take(),
+            cached.iter().cloned().collect::<Vec<_>>()
+        );
+
+        // Stops caching the results exceeding the budget, but still streams them.
+        let cache = Arc::new(QueryCache::new(&QueryCacheOptions {
+            capacity: 2,
+            ..Default::default()
+        }));
+        let stream = cache.tee_results("a".to_string(), results("hello").as_stream());
+        let batches = util::collect(stream).await.unwrap();
+        assert_eq!(results("hello").take(), batches);
+        assert!(cache.get_results("a").is_none());
+        assert_eq!(0, cache.bytes());
+    }
+}
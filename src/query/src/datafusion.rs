@@ -16,6 +16,7 @@
 
 mod catalog_adapter;
 mod error;
+mod explain;
 mod planner;
 
 use std::sync::Arc;
@@ -28,15 +29,17 @@ use common_query::physical_plan::{DfPhysicalPlanAdapter, PhysicalPlan, PhysicalP
 use common_query::prelude::ScalarUdf;
 use common_query::Output;
 use common_recordbatch::adapter::RecordBatchStreamAdapter;
-use common_recordbatch::{EmptyRecordBatchStream, SendableRecordBatchStream};
+use common_recordbatch::{EmptyRecordBatchStream, RecordBatches, SendableRecordBatchStream};
 use common_telemetry::timer;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion::physical_plan::ExecutionPlan;
-use snafu::{OptionExt, ResultExt};
+use session::context::QueryContextRef;
+use snafu::{ensure, OptionExt, ResultExt};
 use sql::dialect::GenericDialect;
 use sql::parser::ParserContext;
 use sql::statements::statement::Statement;
 
+use crate::cache::{self, QueryCacheOptions};
 pub use crate::datafusion::catalog_adapter::DfCatalogListAdapter;
 use crate::datafusion::planner::{DfContextProviderAdapter, DfPlanner};
 use crate::error::Result;
@@ -44,9 +47,10 @@ use crate::executor::QueryExecutor;
 use crate::logical_optimizer::LogicalOptimizer;
 use crate::physical_optimizer::PhysicalOptimizer;
 use crate::physical_planner::PhysicalPlanner;
-use crate::plan::LogicalPlan;
+use crate::plan::{Explain, LogicalPlan, OperatorMetrics};
 use crate::planner::Planner;
 use crate::query_engine::{QueryContext, QueryEngineState};
+use crate::range_select::{RangeSelect, RangeSelectExec, WindowReuse};
 use crate::{metric, QueryEngine};
 
 pub(crate) struct DatafusionQueryEngine {
@@ -54,9 +58,9 @@ pub(crate) struct DatafusionQueryEngine {
 }
 
 impl DatafusionQueryEngine {
-    pub fn new(catalog_list: CatalogListRef) -> Self {
+    pub fn new(catalog_list: CatalogListRef, cache_options: &QueryCacheOptions) -> Self {
         Self {
-            state: QueryEngineState::new(catalog_list.clone()),
+            state: QueryEngineState::new(catalog_list, cache_options),
         }
     }
 }
@@ -70,33 +74,61 @@ impl QueryEngine for DatafusionQueryEngine {
     fn sql_to_statement(&self, sql: &str) -> Result<Statement> {
         let mut statement = ParserContext::create_with_dialect(sql, &GenericDialect {})
             .context(error::ParseSqlSnafu)?;
-        // TODO(dennis): supports multi statement in one sql?
-        assert!(1 == statement.len());
+        // Multiple statements in one SQL should be split and executed one by one by callers.
+        ensure!(
+            statement.len() == 1,
+            error::MultipleStatementsSnafu {
+                sql,
+                num: statement.len(),
+            }
+        );
         Ok(statement.remove(0))
     }
 
-    fn statement_to_plan(&self, stmt: Statement) -> Result<LogicalPlan> {
-        let context_provider = DfContextProviderAdapter::new(self.state.clone());
+    fn statement_to_plan(
+        &self,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<LogicalPlan> {
+        let context_provider = DfContextProviderAdapter::new(self.state.clone(), query_ctx);
         let planner = DfPlanner::new(&context_provider);
 
         planner.statement_to_plan(stmt)
     }
 
-    fn sql_to_plan(&self, sql: &str) -> Result<LogicalPlan> {
+    fn sql_to_plan(&self, sql: &str, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
         let _timer = timer!(metric::METRIC_PARSE_SQL_ELAPSED);
         let stmt = self.sql_to_statement(sql)?;
-        self.statement_to_plan(stmt)
+        self.statement_to_plan(stmt, query_ctx)
     }
 
     async fn execute(&self, plan: &LogicalPlan) -> Result<Output> {
+        if let LogicalPlan::Explain(explain) = plan {
+            return self.explain(explain).await;
+        }
+
         let mut ctx = QueryContext::new(self.state.clone());
         let logical_plan = self.optimize_logical_plan(&mut ctx, plan)?;
+        let cached = match self.state.query_cache() {
+            Some(query_cache) => cache::results_key(&logical_plan)
+                .await
+                .map(|key| (query_cache, key)),
+            None => None,
+        };
+        if let Some((cache, key)) = &cached {
+            if let Some(results) = cache.get_results(key) {
+                return Ok(Output::Stream(results.as_stream()));
+            }
+        }
+
         let physical_plan = self.create_physical_plan(&mut ctx, &logical_plan).await?;
         let physical_plan = self.optimize_physical_plan(&mut ctx, physical_plan)?;
+        let stream = self.execute_stream(&ctx, &physical_plan).await?;
 
-        Ok(Output::Stream(
-            self.execute_stream(&ctx, &physical_plan).await?,
-        ))
+        match cached {
+            Some((cache, key)) => Ok(Output::Stream(cache.tee_results(key, stream))),
+            None => Ok(Output::Stream(stream)),
+        }
     }
 
     async fn execute_physical(&self, plan: &Arc<dyn PhysicalPlan>) -> Result<Output> {
@@ -104,6 +136,13 @@ impl QueryEngine for DatafusionQueryEngine {
         Ok(Output::Stream(self.execute_stream(&ctx, plan).await?))
     }
 
+    async fn execute_with_metrics(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<(RecordBatches, Vec<OperatorMetrics>)> {
+        self.execute_analyzed(plan).await
+    }
+
     fn register_udf(&self, udf: ScalarUdf) {
         self.state.register_udf(udf);
     }
@@ -119,6 +158,10 @@ impl QueryEngine for DatafusionQueryEngine {
         self.state.register_aggregate_function(func);
     }
 
+    fn aggregate_function(&self, name: &str) -> Option<AggregateFunctionMetaRef> {
+        self.state.aggregate_function(name)
+    }
+
     fn register_function(&self, func: FunctionRef) {
         self.state.register_udf(create_udf(func));
     }
@@ -127,7 +170,7 @@ impl QueryEngine for DatafusionQueryEngine {
 impl LogicalOptimizer for DatafusionQueryEngine {
     fn optimize_logical_plan(
         &self,
-        _ctx: &mut QueryContext,
+        ctx: &mut QueryContext,
         plan: &LogicalPlan,
     ) -> Result<LogicalPlan> {
         let _timer = timer!(metric::METRIC_OPTIMIZE_LOGICAL_ELAPSED);
@@ -143,6 +186,22 @@ impl LogicalOptimizer for DatafusionQueryEngine {
 
                 Ok(LogicalPlan::DfPlan(optimized_plan))
             }
+            LogicalPlan::RangeSelect(range_select) => {
+                let input = match self
+                    .optimize_logical_plan(ctx, &LogicalPlan::DfPlan(range_select.input.clone()))?
+                {
+                    LogicalPlan::DfPlan(input) => input,
+                    _ => unreachable!(),
+                };
+                Ok(LogicalPlan::RangeSelect(RangeSelect {
+                    input,
+                    ..range_select.clone()
+                }))
+            }
+            LogicalPlan::Explain(explain) => Ok(LogicalPlan::Explain(Explain {
+                plan: Box::new(self.optimize_logical_plan(ctx, &explain.plan)?),
+                ..explain.clone()
+            })),
         }
     }
 }
@@ -151,7 +210,7 @@ impl LogicalOptimizer for DatafusionQueryEngine {
 impl PhysicalPlanner for DatafusionQueryEngine {
     async fn create_physical_plan(
         &self,
-        _ctx: &mut QueryContext,
+        ctx: &mut QueryContext,
         logical_plan: &LogicalPlan,
     ) -> Result<Arc<dyn PhysicalPlan>> {
         let _timer = timer!(metric::METRIC_CREATE_PHYSICAL_ELAPSED);
@@ -176,6 +235,23 @@ impl PhysicalPlanner for DatafusionQueryEngine {
                     physical_plan,
                 )))
             }
+            LogicalPlan::RangeSelect(range_select) => {
+                let (window_reuse, input) =
+                    match WindowReuse::try_new(&self.state, range_select).await {
+                        Some((window_reuse, input)) => (Some(window_reuse), input),
+                        None => (None, range_select.input.clone()),
+                    };
+                let input = self
+                    .create_physical_plan(ctx, &LogicalPlan::DfPlan(input))
+                    .await?;
+                let exec = RangeSelectExec::try_new(range_select, input, &self.state)?;
+                Ok(Arc::new(match window_reuse {
+                    Some(window_reuse) => exec.with_window_reuse(window_reuse),
+                    None => exec,
+                }))
+            }
+            // The plans of EXPLAIN are rendered as rows by `execute`.
+            LogicalPlan::Explain(_) => error::UnsupportedExplainSnafu {}.fail().map_err(Into::into),
         }
     }
 }
@@ -183,10 +259,17 @@ impl PhysicalPlanner for DatafusionQueryEngine {
 impl PhysicalOptimizer for DatafusionQueryEngine {
     fn optimize_physical_plan(
         &self,
-        _ctx: &mut QueryContext,
+        ctx: &mut QueryContext,
         plan: Arc<dyn PhysicalPlan>,
     ) -> Result<Arc<dyn PhysicalPlan>> {
         let _timer = timer!(metric::METRIC_OPTIMIZE_PHYSICAL_ELAPSED);
+        if let Some(range_select) = plan.as_any().downcast_ref::<RangeSelectExec>() {
+            let input = self.optimize_physical_plan(ctx, range_select.input())?;
+            return plan
+                .with_new_children(vec![input])
+                .context(error::ExecutePhysicalPlanSnafu);
+        }
+
         let config = &self.state.df_context().state.lock().config;
         let optimizers = &config.physical_optimizers;
 
@@ -251,6 +334,8 @@ mod tests {
     use common_query::Output;
     use common_recordbatch::util;
     use datafusion::field_util::{FieldExt, SchemaExt};
+    use datatypes::value::Value;
+    use session::context::QueryContext;
     use table::table::numbers::NumbersTable;
 
     use crate::query_engine::{QueryEngineFactory, QueryEngineRef};
@@ -278,7 +363,7 @@ mod tests {
         let engine = create_test_engine();
         let sql = "select sum(number) from numbers limit 20";
 
-        let plan = engine.sql_to_plan(sql).unwrap();
+        let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
 
         assert_eq!(
             format!("{:?}", plan),
@@ -294,7 +379,7 @@ mod tests {
         let engine = create_test_engine();
         let sql = "select sum(number) from numbers limit 20";
 
-        let plan = engine.sql_to_plan(sql).unwrap();
+        let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
         let output = engine.execute(&plan).await.unwrap();
 
         match output {
@@ -320,4 +405,88 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    async fn explain(engine: &QueryEngineRef, sql: &str) -> Vec<(String, String)> {
+        let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
+        let batches = match engine.execute(&plan).await.unwrap() {
+            Output::RecordBatches(batches) => batches,
+            _ => unreachable!(),
+        };
+        let mut rows = Vec::new();
+        for batch in batches.take() {
+            for row in batch.rows() {
+                match &row.unwrap()[..] {
+                    [Value::String(plan_type), Value::String(plan)] => {
+                        rows.push((plan_type.as_utf8().to_string(), plan.as_utf8().to_string()))
+                    }
+                    row => unreachable!("{:?}", row),
+                }
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let engine = create_test_engine();
+        let sql = "explain select sum(number) from numbers where number < 10";
+        let rows = explain(&engine, sql).await;
+        assert_eq!(2, rows.len());
+        assert_eq!("logical_plan", rows[0].0);
+        assert!(
+            rows[0].1.contains("Aggregate: groupBy=[[]]"),
+            "{}",
+            rows[0].1
+        );
+        assert!(rows[0].1.contains("TableScan: numbers"), "{}", rows[0].1);
+        assert_eq!("physical_plan", rows[1].0);
+        assert!(rows[1].1.contains("SimpleTableScan"), "{}", rows[1].1);
+
+        // The verbose plans include the plans after each optimizer rule.
+        let sql = "explain verbose select sum(number) from numbers where number < 10";
+        let rows = explain(&engine, sql).await;
+        assert_eq!("initial_logical_plan", rows[0].0);
+        assert!(rows
+            .iter()
+            .any(|(plan_type, _)| plan_type.starts_with("logical_plan after")));
+        assert_eq!("physical_plan", rows.last().unwrap().0);
+
+        let sql = "explain analyze select sum(number) from numbers where number < 10";
+        let rows = explain(&engine, sql).await;
+        assert_eq!(1, rows.len());
+        assert_eq!("Plan with Metrics", rows[0].0);
+        let plan = &rows[0].1;
+        // Every operator reports its output.
+        for line in plan.lines() {
+            assert!(line.contains("output_rows="), "{}", plan);
+            assert!(line.contains("output_bytes="), "{}", plan);
+            assert!(line.contains("elapsed="), "{}", plan);
+        }
+        assert!(plan.contains("output_rows=10"), "{}", plan);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_metrics() {
+        let engine = create_test_engine();
+        let sql = "select sum(number) from numbers where number < 10";
+        let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
+        let (results, metrics) = engine.execute_with_metrics(&plan).await.unwrap();
+
+        let batches = results.take();
+        assert_eq!(1, batches.len());
+        assert_eq!(1, batches[0].num_rows());
+        // The operators are ordered from the root to the table scan.
+        assert!(metrics.len() > 1);
+        assert!(metrics[0].operator.contains("Projection"), "{:?}", metrics);
+        assert!(
+            metrics.last().unwrap().operator.contains("SimpleTableScan"),
+            "{:?}",
+            metrics
+        );
+        let filter = metrics
+            .iter()
+            .find(|m| m.operator.starts_with("FilterExec"))
+            .unwrap();
+        assert_eq!(Some(&10), filter.counters.get("output_rows"));
+    }
 }
@@ -30,6 +30,9 @@ pub enum InnerError {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Cannot create physical plan of EXPLAIN, it should be executed directly"))]
+    UnsupportedExplain { backtrace: Backtrace },
+
     #[snafu(display("PhysicalPlan downcast failed"))]
     PhysicalPlanDowncast { backtrace: Backtrace },
 
@@ -40,6 +43,13 @@ pub enum InnerError {
         source: sql::error::Error,
     },
 
+    #[snafu(display("Expect exactly one statement, found {} in SQL: {}", num, sql))]
+    MultipleStatements {
+        sql: String,
+        num: usize,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Cannot plan SQL: {}, source: {}", sql, source))]
     PlanSql {
         sql: String,
@@ -73,6 +83,12 @@ pub enum InnerError {
         #[snafu(backtrace)]
         source: common_query::error::Error,
     },
+
+    #[snafu(display("Failed to collect record batches, source: {}", source))]
+    CollectRecordBatches {
+        #[snafu(backtrace)]
+        source: common_recordbatch::error::Error,
+    },
 }
 
 impl ErrorExt for InnerError {
@@ -87,8 +103,11 @@ impl ErrorExt for InnerError {
                 StatusCode::Unexpected
             }
             ParseSql { source, .. } => source.status_code(),
+            MultipleStatements { .. } | UnsupportedExplain { .. } => StatusCode::InvalidArguments,
             PlanSql { .. } => StatusCode::PlanQuery,
-            ConvertDfRecordBatchStream { source } => source.status_code(),
+            ConvertDfRecordBatchStream { source } | CollectRecordBatches { source } => {
+                source.status_code()
+            }
             ExecutePhysicalPlan { source } => source.status_code(),
         }
     }
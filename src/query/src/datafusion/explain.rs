@@ -0,0 +1,388 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `EXPLAIN` shows the optimized logical plan and the physical plan of a query, `EXPLAIN
+//! ANALYZE` executes the query and shows the physical plan with the metrics of each operator.
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::compute::aggregate::estimated_bytes_size;
+use common_query::physical_plan::{
+    DfPhysicalPlanAdapter, DisplayFormatType, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+    Partitioning, PhysicalPlan, PhysicalPlanAdapter, RuntimeEnv,
+};
+use common_query::Output;
+use common_recordbatch::{util, RecordBatches};
+use datafusion::arrow::datatypes::SchemaRef as DfSchemaRef;
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::logical_plan::{LogicalPlan as DfLogicalPlan, LogicalPlanBuilder};
+use datafusion::physical_plan::display::DisplayableExecutionPlan;
+use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{Count, MetricValue, Time};
+use datafusion::physical_plan::{
+    ExecutionPlan, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
+use datatypes::arrow::error::Result as ArrowResult;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::StringVector;
+use futures::{Stream, StreamExt};
+use snafu::ResultExt;
+
+use crate::datafusion::{error, DatafusionQueryEngine};
+use crate::error::{CreateRecordBatchSnafu, Result, UnsupportedExprSnafu};
+use crate::executor::QueryExecutor;
+use crate::logical_optimizer::LogicalOptimizer;
+use crate::physical_optimizer::PhysicalOptimizer;
+use crate::physical_planner::PhysicalPlanner;
+use crate::plan::{Explain, LogicalPlan, OperatorMetrics};
+use crate::query_engine::QueryContext;
+
+const PLAN_TYPE_COLUMN: &str = "plan_type";
+const PLAN_COLUMN: &str = "plan";
+
+impl DatafusionQueryEngine {
+    pub(crate) async fn explain(&self, explain: &Explain) -> Result<Output> {
+        let mut plans = Vec::new();
+        let mut ctx = QueryContext::new(self.state.clone());
+        let logical_plan = self.optimize_logical_plan(&mut ctx, &explain.plan)?;
+        if !explain.analyze {
+            if explain.verbose {
+                plans.extend(self.optimizer_steps(&explain.plan)?);
+            }
+            plans.push((
+                "logical_plan".to_string(),
+                display_logical_plan(&logical_plan),
+            ));
+        }
+
+        let physical_plan = self.create_physical_plan(&mut ctx, &logical_plan).await?;
+        let physical_plan = self.optimize_physical_plan(&mut ctx, physical_plan)?;
+        let physical_plan = to_df_plan(physical_plan);
+        if explain.analyze {
+            let physical_plan = instrument(physical_plan).context(error::DatafusionSnafu {
+                msg: "Fail to instrument physical plan",
+            })?;
+            for partition in 0..physical_plan.output_partitioning().partition_count() {
+                let mut stream = physical_plan
+                    .execute(partition, ctx.state().runtime())
+                    .await
+                    .context(error::DatafusionSnafu {
+                        msg: "Fail to execute physical plan",
+                    })?;
+                while let Some(batch) = stream.next().await {
+                    let _ = batch.map_err(DataFusionError::ArrowError).context(
+                        error::DatafusionSnafu {
+                            msg: "Fail to execute physical plan",
+                        },
+                    )?;
+                }
+            }
+            let plan = DisplayableExecutionPlan::with_metrics(physical_plan.as_ref()).indent();
+            plans.push(("Plan with Metrics".to_string(), plan.to_string()));
+        } else {
+            let plan = DisplayableExecutionPlan::new(physical_plan.as_ref()).indent();
+            plans.push(("physical_plan".to_string(), plan.to_string()));
+        }
+
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new(PLAN_TYPE_COLUMN, ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(PLAN_COLUMN, ConcreteDataType::string_datatype(), false),
+        ]));
+        let (plan_types, plans): (Vec<_>, Vec<_>) = plans.into_iter().unzip();
+        let records = RecordBatches::try_from_columns(
+            schema,
+            vec![
+                Arc::new(StringVector::from(plan_types)),
+                Arc::new(StringVector::from(plans)),
+            ],
+        )
+        .context(CreateRecordBatchSnafu)?;
+        Ok(Output::RecordBatches(records))
+    }
+
+    pub(crate) async fn execute_analyzed(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<(RecordBatches, Vec<OperatorMetrics>)> {
+        let mut ctx = QueryContext::new(self.state.clone());
+        let logical_plan = self.optimize_logical_plan(&mut ctx, plan)?;
+        let physical_plan = self.create_physical_plan(&mut ctx, &logical_plan).await?;
+        let physical_plan = self.optimize_physical_plan(&mut ctx, physical_plan)?;
+
+        let stream = self.execute_stream(&ctx, &physical_plan).await?;
+        let schema = stream.schema();
+        let batches = util::collect(stream)
+            .await
+            .context(error::CollectRecordBatchesSnafu)?;
+        let results =
+            RecordBatches::try_new(schema, batches).context(error::CollectRecordBatchesSnafu)?;
+
+        let mut metrics = Vec::new();
+        collect_metrics(to_df_plan(physical_plan), &mut metrics);
+        Ok((results, metrics))
+    }
+
+    /// Returns the initial logical plan and the plans after each optimizer rule, including our
+    /// own rules registered in DataFusion.
+    fn optimizer_steps(&self, plan: &LogicalPlan) -> Result<Vec<(String, String)>> {
+        match plan {
+            LogicalPlan::DfPlan(df_plan) => {
+                let explain = LogicalPlanBuilder::from(df_plan.clone())
+                    .explain(true, false)
+                    .and_then(|builder| builder.build())
+                    .context(error::DatafusionSnafu {
+                        msg: "Fail to build explain plan",
+                    })?;
+                let explain =
+                    self.state
+                        .df_context()
+                        .optimize(&explain)
+                        .context(error::DatafusionSnafu {
+                            msg: "Fail to optimize logical plan",
+                        })?;
+                match explain {
+                    DfLogicalPlan::Explain(explain) => Ok(explain
+                        .stringified_plans
+                        .iter()
+                        .map(|plan| (plan.plan_type.to_string(), plan.plan.to_string()))
+                        .collect()),
+                    _ => unreachable!(),
+                }
+            }
+            LogicalPlan::RangeSelect(range_select) => {
+                let steps =
+                    self.optimizer_steps(&LogicalPlan::DfPlan(range_select.input.clone()))?;
+                Ok(steps
+                    .into_iter()
+                    .map(|(plan_type, plan)| {
+                        (plan_type, format!("{}\n{}", range_select, indent(&plan)))
+                    })
+                    .collect())
+            }
+            LogicalPlan::Explain(_) => UnsupportedExprSnafu {
+                name: "nested EXPLAIN",
+            }
+            .fail()
+            .map_err(Into::into),
+        }
+    }
+}
+
+fn display_logical_plan(plan: &LogicalPlan) -> String {
+    match plan {
+        LogicalPlan::DfPlan(df_plan) => df_plan.display_indent().to_string(),
+        LogicalPlan::RangeSelect(range_select) => format!(
+            "{}\n{}",
+            range_select,
+            indent(&range_select.input.display_indent().to_string())
+        ),
+        LogicalPlan::Explain(explain) => display_logical_plan(&explain.plan),
+    }
+}
+
+fn indent(plan: &str) -> String {
+    plan.lines()
+        .map(|line| format!("  {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_df_plan(plan: Arc<dyn PhysicalPlan>) -> Arc<dyn ExecutionPlan> {
+    match plan.as_any().downcast_ref::<PhysicalPlanAdapter>() {
+        Some(adapter) => adapter.df_plan(),
+        None => Arc::new(DfPhysicalPlanAdapter(plan)),
+    }
+}
+
+/// Collects the metrics of every operator of the plan, in the order `EXPLAIN` shows them.
+fn collect_metrics(plan: Arc<dyn ExecutionPlan>, metrics: &mut Vec<OperatorMetrics>) {
+    // Unwraps our plans adapted back and forth, so that the metrics of their operators are
+    // collected.
+    let plan = match plan.as_any().downcast_ref::<DfPhysicalPlanAdapter>() {
+        Some(adapter) => to_df_plan(adapter.0.clone()),
+        None => plan,
+    };
+    let mut operator_metrics = OperatorMetrics {
+        operator: OneLine(plan.as_ref()).to_string(),
+        ..Default::default()
+    };
+    if let Some(plan_metrics) = plan.metrics() {
+        for metric in plan_metrics.iter() {
+            let value = metric.value();
+            let values = match value {
+                MetricValue::ElapsedCompute(_) | MetricValue::Time { .. } => {
+                    &mut operator_metrics.times
+                }
+                MetricValue::StartTimestamp(_) | MetricValue::EndTimestamp(_) => continue,
+                _ => &mut operator_metrics.counters,
+            };
+            *values.entry(value.name().to_string()).or_default() += value.as_usize();
+        }
+    }
+    metrics.push(operator_metrics);
+
+    for child in plan.children() {
+        collect_metrics(child, metrics);
+    }
+}
+
+/// Displays the operator of a plan on one line, without its inputs.
+struct OneLine<'a>(&'a dyn ExecutionPlan);
+
+impl std::fmt::Display for OneLine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt_as(DisplayFormatType::Default, f)
+    }
+}
+
+/// Wraps every operator of the plan in an [AnalyzeExec].
+fn instrument(plan: Arc<dyn ExecutionPlan>) -> DfResult<Arc<dyn ExecutionPlan>> {
+    // Unwraps our plans adapted back and forth, so that their operators are instrumented.
+    let plan = match plan.as_any().downcast_ref::<DfPhysicalPlanAdapter>() {
+        Some(adapter) => to_df_plan(adapter.0.clone()),
+        None => plan,
+    };
+    let children = plan.children();
+    // Leaves, like table scans, may not support replacing their children.
+    let plan = if children.is_empty() {
+        plan
+    } else {
+        let children = children
+            .into_iter()
+            .map(instrument)
+            .collect::<DfResult<Vec<_>>>()?;
+        plan.with_new_children(children)?
+    };
+    Ok(Arc::new(AnalyzeExec {
+        input: plan,
+        metrics: ExecutionPlanMetricsSet::new(),
+    }))
+}
+
+/// Records the rows, bytes and elapsed time of the output of its input. It is shown as its
+/// input, with its metrics added to the metrics of the input.
+#[derive(Debug)]
+struct AnalyzeExec {
+    input: Arc<dyn ExecutionPlan>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+#[async_trait::async_trait]
+impl ExecutionPlan for AnalyzeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> DfSchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        self.input.children()
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(AnalyzeExec {
+            input: self.input.with_new_children(children)?,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+
+    async fn execute(
+        &self,
+        partition: usize,
+        runtime: Arc<RuntimeEnv>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        let stream = self.input.execute(partition, runtime).await?;
+        Ok(Box::pin(AnalyzeStream {
+            stream,
+            output_rows: MetricBuilder::new(&self.metrics).output_rows(partition),
+            output_bytes: MetricBuilder::new(&self.metrics).counter("output_bytes", partition),
+            // Includes the time spent by the inputs.
+            elapsed: MetricBuilder::new(&self.metrics).subset_time("elapsed", partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        let mut metrics = self.metrics.clone_inner();
+        if let Some(input_metrics) = self.input.metrics() {
+            for metric in input_metrics.iter() {
+                metrics.push(metric.clone());
+            }
+        }
+        Some(metrics)
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.input.fmt_as(t, f)
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+}
+
+struct AnalyzeStream {
+    stream: SendableRecordBatchStream,
+    output_rows: Count,
+    output_bytes: Count,
+    elapsed: Time,
+}
+
+impl RecordBatchStream for AnalyzeStream {
+    fn schema(&self) -> DfSchemaRef {
+        self.stream.schema()
+    }
+}
+
+impl Stream for AnalyzeStream {
+    type Item = ArrowResult<DfRecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let timer = self.elapsed.timer();
+        let poll = Pin::new(&mut self.stream).poll_next(cx);
+        timer.done();
+        if let Poll::Ready(Some(Ok(batch))) = &poll {
+            self.output_rows.add(batch.num_rows());
+            self.output_bytes.add(
+                batch
+                    .columns()
+                    .iter()
+                    .map(|column| estimated_bytes_size(column.as_ref()))
+                    .sum(),
+            );
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
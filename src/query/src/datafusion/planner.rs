@@ -21,29 +21,42 @@ use datafusion::datasource::TableProvider;
 use datafusion::physical_plan::udaf::AggregateUDF;
 use datafusion::physical_plan::udf::ScalarUDF;
 use datafusion::sql::planner::{ContextProvider, SqlToRel};
-use snafu::ResultExt;
-use sql::statements::query::Query;
+use datatypes::schema::Schema;
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use sql::ast::Query as SpQuery;
+use sql::statements::query::{Query, RangeSelect as SqlRangeSelect};
 use sql::statements::statement::Statement;
+use table::table::adapter::DfTableProviderAdapter;
 
 use crate::datafusion::error;
-use crate::error::Result;
-use crate::plan::LogicalPlan;
+use crate::error::{InvalidRangeQuerySnafu, Result, TableNotFoundSnafu};
+use crate::plan::{Explain, LogicalPlan};
 use crate::planner::Planner;
-use crate::query_engine::QueryEngineState;
+use crate::query_engine::{schema_function_name, QueryEngineState};
+use crate::range_select::{plan_range_select, range_table_name, RangeTable};
 
 pub struct DfPlanner<'a, S: ContextProvider> {
     sql_to_rel: SqlToRel<'a, S>,
+    schema_provider: &'a S,
 }
 
 impl<'a, S: ContextProvider + Send + Sync> DfPlanner<'a, S> {
     /// Creates a DataFusion planner instance
     pub fn new(schema_provider: &'a S) -> Self {
         let rel = SqlToRel::new(schema_provider);
-        Self { sql_to_rel: rel }
+        Self {
+            sql_to_rel: rel,
+            schema_provider,
+        }
     }
 
     /// Converts QUERY statement to logical plan.
     pub fn query_to_plan(&self, query: Box<Query>) -> Result<LogicalPlan> {
+        if let Some(range_select) = &query.range_select {
+            return self.range_query_to_plan(&query.inner, range_select);
+        }
+
         // todo(hl): original SQL should be provided as an argument
         let sql = query.inner.to_string();
         let result = self
@@ -53,6 +66,79 @@ impl<'a, S: ContextProvider + Send + Sync> DfPlanner<'a, S> {
 
         Ok(LogicalPlan::DfPlan(result))
     }
+
+    /// Plans the range query over the DataFusion plan reading its input from the table.
+    fn range_query_to_plan(
+        &self,
+        query: &SpQuery,
+        range_select: &SqlRangeSelect,
+    ) -> Result<LogicalPlan> {
+        let table = self.range_table(query)?;
+        let plan = plan_range_select(query, range_select, &table, |input| {
+            let sql = input.to_string();
+            self.sql_to_rel
+                .query_to_plan(input)
+                .context(error::PlanSqlSnafu { sql })
+                .map_err(Into::into)
+        })?;
+        Ok(LogicalPlan::RangeSelect(plan))
+    }
+
+    fn range_table(&self, query: &SpQuery) -> Result<RangeTable> {
+        let names = range_table_name(query)?
+            .iter()
+            .map(|ident| ident.value.as_str())
+            .collect::<Vec<_>>();
+        let table_name = names.join(".");
+        let table_ref = match names[..] {
+            [table] => TableReference::Bare { table },
+            [schema, table] => TableReference::Partial { schema, table },
+            [catalog, schema, table] => TableReference::Full {
+                catalog,
+                schema,
+                table,
+            },
+            _ => {
+                return TableNotFoundSnafu { table: table_name }
+                    .fail()
+                    .map_err(Into::into)
+            }
+        };
+        let provider = self
+            .schema_provider
+            .get_table_provider(table_ref)
+            .context(TableNotFoundSnafu { table: &table_name })?;
+
+        let (schema, primary_keys) = match provider
+            .as_any()
+            .downcast_ref::<DfTableProviderAdapter>()
+        {
+            Some(adapter) => {
+                let table = adapter.table();
+                let primary_keys = table
+                    .table_info()
+                    .meta
+                    .row_key_column_names()
+                    .cloned()
+                    .collect();
+                (table.schema(), primary_keys)
+            }
+            None => (
+                Arc::new(Schema::try_from(provider.schema()).context(error::ConvertSchemaSnafu)?),
+                vec![],
+            ),
+        };
+        let time_index = schema
+            .timestamp_column()
+            .map(|column| column.name.clone())
+            .context(InvalidRangeQuerySnafu {
+                reason: format!("table {} has no time index", table_name),
+            })?;
+        Ok(RangeTable {
+            time_index,
+            primary_keys,
+        })
+    }
 }
 
 impl<'a, S> Planner for DfPlanner<'a, S>
@@ -63,12 +149,28 @@ where
     fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
             Statement::Query(qb) => self.query_to_plan(qb),
+            Statement::Explain(explain) => Ok(LogicalPlan::Explain(Explain {
+                analyze: explain.analyze,
+                verbose: explain.verbose,
+                plan: Box::new(self.query_to_plan(explain.query)?),
+            })),
             Statement::ShowTables(_)
             | Statement::ShowDatabases(_)
             | Statement::ShowCreateTable(_)
             | Statement::CreateTable(_)
             | Statement::CreateDatabase(_)
+            | Statement::CreateMaterializedView(_)
             | Statement::Alter(_)
+            | Statement::Use(_)
+            | Statement::SetVariables(_)
+            | Statement::CreateUser(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)
+            | Statement::CreateRole(_)
+            | Statement::GrantRole(_)
+            | Statement::RevokeRole(_)
+            | Statement::ShowProcesslist(_)
+            | Statement::Kill(_)
             | Statement::Insert(_) => unreachable!(),
         }
     }
@@ -76,11 +178,12 @@ where
 
 pub(crate) struct DfContextProviderAdapter {
     state: QueryEngineState,
+    query_ctx: QueryContextRef,
 }
 
 impl DfContextProviderAdapter {
-    pub(crate) fn new(state: QueryEngineState) -> Self {
-        Self { state }
+    pub(crate) fn new(state: QueryEngineState, query_ctx: QueryContextRef) -> Self {
+        Self { state, query_ctx }
     }
 }
 
@@ -88,6 +191,23 @@ impl DfContextProviderAdapter {
 ///                           manage UDFs, UDAFs, variables by ourself in future.
 impl ContextProvider for DfContextProviderAdapter {
     fn get_table_provider(&self, name: TableReference) -> Option<Arc<dyn TableProvider>> {
+        // Resolves partially qualified table names against the current catalog and schema of
+        // the session, instead of the defaults of DataFusion.
+        let catalog = self.query_ctx.current_catalog();
+        let schema = self.query_ctx.current_schema();
+        let name = match name {
+            TableReference::Bare { table } => TableReference::Full {
+                catalog: &catalog,
+                schema: &schema,
+                table,
+            },
+            TableReference::Partial { schema, table } => TableReference::Full {
+                catalog: &catalog,
+                schema,
+                table,
+            },
+            full => full,
+        };
         self.state
             .df_context()
             .state
@@ -96,11 +216,20 @@ impl ContextProvider for DfContextProviderAdapter {
     }
 
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
-        self.state.df_context().state.lock().get_function_meta(name)
+        let schema_name = schema_function_name(&self.query_ctx.current_schema(), name);
+        let state = self.state.df_context().state.lock();
+        state
+            .get_function_meta(&schema_name)
+            .or_else(|| state.get_function_meta(name))
     }
 
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
-        self.state.aggregate_function(name).map(|func| {
+        let schema_name = schema_function_name(&self.query_ctx.current_schema(), name);
+        let func = self
+            .state
+            .aggregate_function(&schema_name)
+            .or_else(|| self.state.aggregate_function(name));
+        func.map(|func| {
             Arc::new(
                 create_aggregate_function(func.name(), func.args_count(), func.create()).into(),
             )
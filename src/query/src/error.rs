@@ -55,6 +55,47 @@ pub enum InnerError {
         #[snafu(backtrace)]
         source: common_recordbatch::error::Error,
     },
+
+    #[snafu(display("Cannot parse SQL, source: {}", source))]
+    ParseSql {
+        #[snafu(backtrace)]
+        source: sql::error::Error,
+    },
+
+    #[snafu(display("Table not found: {}", table))]
+    TableNotFound { table: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid range query: {}", reason))]
+    InvalidRangeQuery {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid materialized view: {}", reason))]
+    InvalidMaterializedView {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid columns of materialized view, source: {}", source))]
+    MaterializedViewSchema {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
+    #[snafu(display("Failed to insert into materialized view {}, source: {}", name, source))]
+    InsertMaterializedView {
+        name: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
+    #[snafu(display("Failed to update materialized view {}, source: {}", name, source))]
+    UpdateMaterializedView {
+        name: String,
+        #[snafu(backtrace)]
+        source: Error,
+    },
 }
 
 impl ErrorExt for InnerError {
@@ -62,12 +103,20 @@ impl ErrorExt for InnerError {
         use InnerError::*;
 
         match self {
-            UnsupportedExpr { .. } | CatalogNotFound { .. } | SchemaNotFound { .. } => {
-                StatusCode::InvalidArguments
-            }
+            UnsupportedExpr { .. }
+            | CatalogNotFound { .. }
+            | SchemaNotFound { .. }
+            | InvalidRangeQuery { .. }
+            | InvalidMaterializedView { .. } => StatusCode::InvalidArguments,
+            TableNotFound { .. } => StatusCode::TableNotFound,
             Catalog { source } => source.status_code(),
-            VectorComputation { source } => source.status_code(),
+            VectorComputation { source } | MaterializedViewSchema { source } => {
+                source.status_code()
+            }
             CreateRecordBatch { source } => source.status_code(),
+            InsertMaterializedView { source, .. } => source.status_code(),
+            UpdateMaterializedView { source, .. } => source.status_code(),
+            ParseSql { source } => source.status_code(),
         }
     }
 
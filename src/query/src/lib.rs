@@ -12,18 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod cache;
 mod datafusion;
 pub mod error;
 pub mod executor;
 mod function;
 pub mod logical_optimizer;
+pub mod materialized_view;
 mod metric;
 mod optimizer;
+pub mod parameter;
 pub mod physical_optimizer;
 pub mod physical_planner;
 pub mod plan;
 pub mod planner;
 pub mod query_engine;
+pub mod range_select;
 pub mod sql;
 
-pub use crate::query_engine::{QueryContext, QueryEngine, QueryEngineFactory, QueryEngineRef};
+pub use crate::query_engine::{
+    schema_function_name, QueryContext, QueryEngine, QueryEngineFactory, QueryEngineRef,
+};
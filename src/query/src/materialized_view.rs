@@ -0,0 +1,1084 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Materialized views, like `CREATE MATERIALIZED VIEW cpu_1m AS SELECT host, date_bin('1m', ts)
+//! AS ts, avg(cpu) FROM monitor GROUP BY host, date_bin('1m', ts)`, aggregate the rows inserted
+//! into the source table incrementally.
+//!
+//! A [MaterializedView] keeps the states of the aggregates of each group in each time bucket. A
+//! bucket is closed once a row at or after its end is inserted, then its rows are emitted to be
+//! written into the table of the view. A closed bucket that rows are inserted into later is
+//! computed again from the rows of the source table, which replace the rows written before as
+//! they have the same keys and time.
+//!
+//! The states of the open buckets are only kept in memory, a [ViewTable] rebuilds them from the
+//! rows of the source table after the last bucket written into the table of the view.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreatorRef};
+use common_query::Output;
+use common_recordbatch::util as record_util;
+use common_time::Timestamp;
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{Helper, VectorBuilder};
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, Select, SelectItem, SetExpr,
+    TableFactor, Value as SqlValue,
+};
+use sql::parsers::parse_duration_millis;
+use sql::statements::query::Query;
+use table::requests::InsertRequest;
+use table::TableRef;
+
+use crate::error::{
+    CreateRecordBatchSnafu, Error, InsertMaterializedViewSnafu, InvalidMaterializedViewSnafu,
+    MaterializedViewSchemaSnafu, Result, UpdateMaterializedViewSnafu, VectorComputationSnafu,
+};
+use crate::range_select::aggregate::BuiltinAggregate;
+use crate::range_select::{function_name, is_column, time_millis};
+use crate::QueryEngine;
+
+/// Functions that put the time index into buckets.
+const BUCKET_FUNCTIONS: [&str; 2] = ["date_bin", "time_bucket"];
+
+/// States of the aggregates, by the start of the bucket and the group keys.
+type States = BTreeMap<(i64, Vec<Value>), Vec<Box<dyn Accumulator>>>;
+
+/// Rows grouped by the start of their bucket and the group keys.
+type Groups = BTreeMap<(i64, Vec<Value>), Vec<usize>>;
+
+/// Changes of a view made by the rows inserted into its source table.
+#[derive(Default)]
+pub struct ViewUpdate {
+    /// Rows of the buckets closed by the insert, by the names of the columns of the view.
+    pub rows: Option<HashMap<String, VectorRef>>,
+    /// Starts of the buckets closed by the insert.
+    pub closed_buckets: Vec<i64>,
+    /// Starts of the buckets closed before, which the insert has late rows for. They are
+    /// computed again from the rows of the source table.
+    pub late_buckets: Vec<i64>,
+}
+
+pub struct MaterializedView {
+    /// The table read by the view, as written in the query.
+    source: ObjectName,
+    /// Time index of the source table.
+    time_index: String,
+    stride_millis: i64,
+    /// Columns of the source table to group by, besides the time bucket.
+    keys: Vec<String>,
+    aggregates: Vec<ViewAggregate>,
+    /// Columns of the view, in the order of the schema.
+    output: Vec<ViewColumn>,
+    schema: SchemaRef,
+    states: States,
+    /// The latest time of the inserted rows.
+    watermark: Option<i64>,
+}
+
+struct ViewAggregate {
+    function: ViewFunction,
+    /// Columns of the source table as the arguments.
+    args: Vec<String>,
+    input_types: Vec<ConcreteDataType>,
+}
+
+enum ViewFunction {
+    Builtin(BuiltinAggregate),
+    Registered(AggregateFunctionCreatorRef),
+}
+
+impl ViewAggregate {
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        match &self.function {
+            ViewFunction::Builtin(builtin) => {
+                builtin.create(&self.input_types[0]).map_err(Error::new)
+            }
+            ViewFunction::Registered(creator) => {
+                creator.creator()(&self.input_types).map_err(Error::new)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ViewColumn {
+    /// The start time of the bucket.
+    Bucket,
+    /// The group key at the index.
+    Key(usize),
+    /// The aggregate at the index.
+    Aggregate(usize),
+}
+
+impl MaterializedView {
+    /// Returns the only table the query of a view reads.
+    pub fn source_table(query: &Query) -> Result<&ObjectName> {
+        let select = plain_select(query)?;
+        ensure!(
+            select.from.len() == 1 && select.from[0].joins.is_empty(),
+            InvalidMaterializedViewSnafu {
+                reason: "a materialized view reads exactly one table",
+            }
+        );
+        match &select.from[0].relation {
+            TableFactor::Table { name, .. } => Ok(name),
+            _ => invalid("a materialized view reads exactly one table"),
+        }
+    }
+
+    /// Creates the view of the query over the source table, the aggregates are looked up in the
+    /// `query_engine`.
+    pub fn try_new(
+        query: &Query,
+        source_schema: &Schema,
+        query_engine: &dyn QueryEngine,
+    ) -> Result<Self> {
+        let source = Self::source_table(query)?.clone();
+        let select = plain_select(query)?;
+        ensure!(
+            select.selection.is_none() && select.having.is_none() && !select.distinct,
+            InvalidMaterializedViewSnafu {
+                reason: "WHERE, HAVING and DISTINCT are not allowed in a materialized view",
+            }
+        );
+        let inner = &query.inner;
+        ensure!(
+            inner.with.is_none()
+                && inner.order_by.is_empty()
+                && inner.limit.is_none()
+                && inner.offset.is_none()
+                && inner.fetch.is_none(),
+            InvalidMaterializedViewSnafu {
+                reason:
+                    "WITH, ORDER BY, LIMIT, OFFSET and FETCH are not allowed in a materialized view",
+            }
+        );
+        let time_index =
+            source_schema
+                .timestamp_column()
+                .context(InvalidMaterializedViewSnafu {
+                    reason: format!("the source table {} has no time index", source),
+                })?;
+        let find_column = |expr: &Expr| {
+            source_schema
+                .column_schemas()
+                .iter()
+                .find(|column| is_column(expr, &column.name))
+        };
+
+        let mut stride_millis = None;
+        let mut keys = Vec::new();
+        let mut aggregates = Vec::new();
+        let mut output = Vec::with_capacity(select.projection.len());
+        let mut column_schemas = Vec::with_capacity(select.projection.len());
+        for item in &select.projection {
+            let (expr, name) = match item {
+                SelectItem::UnnamedExpr(expr) => (expr, expr.to_string()),
+                SelectItem::ExprWithAlias { expr, alias } => (expr, alias.value.clone()),
+                _ => return invalid("wildcards are not allowed in a materialized view"),
+            };
+            match expr {
+                Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+                    let column = find_column(expr).with_context(|| InvalidMaterializedViewSnafu {
+                        reason: format!("column {} not found in {}", expr, source),
+                    })?;
+                    ensure!(
+                        select.group_by.iter().any(|e| is_column(e, &column.name)),
+                        InvalidMaterializedViewSnafu {
+                            reason: format!("column {} must be in GROUP BY", expr),
+                        }
+                    );
+                    output.push(ViewColumn::Key(keys.len()));
+                    keys.push(column.name.clone());
+                    column_schemas.push(ColumnSchema::new(
+                        name,
+                        column.data_type.clone(),
+                        column.is_nullable(),
+                    ));
+                }
+                Expr::Function(function)
+                    if BUCKET_FUNCTIONS.contains(&function_name(function).as_str()) =>
+                {
+                    ensure!(
+                        stride_millis.is_none(),
+                        InvalidMaterializedViewSnafu {
+                            reason: "a materialized view selects exactly one time bucket",
+                        }
+                    );
+                    ensure!(
+                        select.group_by.contains(expr),
+                        InvalidMaterializedViewSnafu {
+                            reason: format!("{} must be in GROUP BY", expr),
+                        }
+                    );
+                    stride_millis = Some(parse_bucket(function, &time_index.name)?);
+                    output.push(ViewColumn::Bucket);
+                    column_schemas.push(
+                        ColumnSchema::new(name, ConcreteDataType::timestamp_millis_datatype(), false)
+                            .with_time_index(true),
+                    );
+                }
+                Expr::Function(function) => {
+                    let aggregate = parse_aggregate(function, time_index, &find_column, query_engine)?;
+                    let output_type = match &aggregate.function {
+                        ViewFunction::Builtin(builtin) => builtin
+                            .output_type(&aggregate.input_types[0])
+                            .map_err(Error::new)?,
+                        ViewFunction::Registered(creator) => {
+                            creator.output_type().map_err(Error::new)?
+                        }
+                    };
+                    output.push(ViewColumn::Aggregate(aggregates.len()));
+                    aggregates.push(aggregate);
+                    column_schemas.push(ColumnSchema::new(name, output_type, true));
+                }
+                _ => {
+                    return invalid(format!(
+                        "expect a column, a time bucket or an aggregate in a materialized view, found: {}",
+                        expr
+                    ))
+                }
+            }
+        }
+        let stride_millis = stride_millis.context(InvalidMaterializedViewSnafu {
+            reason: format!(
+                "a materialized view groups by date_bin or time_bucket over {}",
+                time_index.name
+            ),
+        })?;
+        ensure!(
+            select.group_by.len() == keys.len() + 1,
+            InvalidMaterializedViewSnafu {
+                reason: "GROUP BY must only have the selected columns and the time bucket",
+            }
+        );
+
+        let schema = Schema::try_new(column_schemas).context(MaterializedViewSchemaSnafu)?;
+        Ok(Self {
+            source,
+            time_index: time_index.name.clone(),
+            stride_millis,
+            keys,
+            aggregates,
+            output,
+            schema: Arc::new(schema),
+            states: BTreeMap::new(),
+            watermark: None,
+        })
+    }
+
+    pub fn source(&self) -> &ObjectName {
+        &self.source
+    }
+
+    /// Returns the schema of the table of the view, whose time index is the time bucket.
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Returns the indices of the group keys in the schema, which are the primary key of the
+    /// table of the view.
+    pub fn primary_key_indices(&self) -> Vec<usize> {
+        self.output
+            .iter()
+            .enumerate()
+            .filter_map(|(i, column)| matches!(column, ViewColumn::Key(_)).then(|| i))
+            .collect()
+    }
+
+    pub fn stride_millis(&self) -> i64 {
+        self.stride_millis
+    }
+
+    /// Updates the aggregates with the rows inserted into the source table, and closes the
+    /// buckets ending at or before the latest time of the inserted rows. The rows of the buckets
+    /// closed before are not aggregated, but returned as late buckets.
+    pub fn insert(&mut self, columns_values: &HashMap<String, VectorRef>) -> Result<ViewUpdate> {
+        let (mut groups, latest) = self.group_rows(columns_values);
+        let mut late_buckets = BTreeSet::new();
+        if let Some(first_open) = self.first_open_bucket() {
+            groups.retain(|(bucket, _), _| {
+                if *bucket < first_open {
+                    let _ = late_buckets.insert(*bucket);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        self.update(columns_values, groups, latest)?;
+
+        let mut update = self.close_buckets()?;
+        update.late_buckets = late_buckets.into_iter().collect();
+        Ok(update)
+    }
+
+    /// Updates the aggregates with rows read from the source table, without closing any
+    /// bucket, e.g. to rebuild the states of the open buckets.
+    pub fn replay(&mut self, columns_values: &HashMap<String, VectorRef>) -> Result<()> {
+        let (groups, latest) = self.group_rows(columns_values);
+        self.update(columns_values, groups, latest)
+    }
+
+    /// Removes the buckets ending at or before the watermark, returns their rows.
+    pub fn close_buckets(&mut self) -> Result<ViewUpdate> {
+        let first_open = match self.first_open_bucket() {
+            Some(first_open) => first_open,
+            None => return Ok(ViewUpdate::default()),
+        };
+        let open = self.states.split_off(&(first_open, Vec::new()));
+        let closed = std::mem::replace(&mut self.states, open);
+        let mut closed_buckets = closed.keys().map(|(bucket, _)| *bucket).collect::<Vec<_>>();
+        closed_buckets.dedup();
+        Ok(ViewUpdate {
+            rows: self.emit(closed)?,
+            closed_buckets,
+            late_buckets: Vec::new(),
+        })
+    }
+
+    /// Returns the rows of the buckets of the given rows, aggregated from scratch without
+    /// touching the states of the view.
+    pub fn aggregate(
+        &self,
+        batches: &[HashMap<String, VectorRef>],
+    ) -> Result<Option<HashMap<String, VectorRef>>> {
+        let mut states = States::new();
+        for columns_values in batches {
+            let (groups, _) = self.group_rows(columns_values);
+            self.accumulate(&mut states, columns_values, groups)?;
+        }
+        self.emit(states)
+    }
+
+    /// Returns the sql reading the columns the view needs from the source table, whose full
+    /// name is `source`, over `[start, end)`.
+    pub fn source_sql(&self, source: &str, start: Option<i64>, end: Option<i64>) -> String {
+        let mut seen = HashSet::new();
+        let columns = self
+            .keys
+            .iter()
+            .chain(self.aggregates.iter().flat_map(|aggregate| &aggregate.args))
+            .chain(std::iter::once(&self.time_index))
+            .filter(|column| seen.insert(*column))
+            .map(|column| quote(column))
+            .collect::<Vec<_>>();
+        let time_index = quote(&self.time_index);
+        let filters = start
+            .map(|start| format!("{} >= {}", time_index, start))
+            .into_iter()
+            .chain(end.map(|end| format!("{} < {}", time_index, end)))
+            .collect::<Vec<_>>();
+
+        let mut sql = format!("SELECT {} FROM {}", columns.join(", "), source);
+        if !filters.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&filters.join(" AND "));
+        }
+        sql
+    }
+
+    /// Returns the sql reading the start of the last bucket written into the table of the view,
+    /// whose full name is `table`.
+    pub fn last_bucket_sql(&self, table: &str) -> String {
+        let bucket = self
+            .schema
+            .timestamp_column()
+            .map(|column| quote(&column.name))
+            .unwrap_or_default();
+        format!("SELECT max({}) FROM {}", bucket, table)
+    }
+
+    fn first_open_bucket(&self) -> Option<i64> {
+        self.watermark
+            .map(|watermark| watermark.div_euclid(self.stride_millis) * self.stride_millis)
+    }
+
+    /// Groups the rows by their buckets and keys, returns the groups with the latest time of
+    /// the rows. The rows without time are skipped.
+    fn group_rows(&self, columns_values: &HashMap<String, VectorRef>) -> (Groups, Option<i64>) {
+        let mut groups = Groups::new();
+        let mut latest = None;
+        let times = match columns_values.get(&self.time_index) {
+            Some(times) => times,
+            None => return (groups, latest),
+        };
+        let stride = self.stride_millis;
+        for row in 0..times.len() {
+            let time = match time_millis(&times.get(row)) {
+                Some(time) => time,
+                None => continue,
+            };
+            latest = Some(latest.map_or(time, |latest: i64| latest.max(time)));
+            let key = self
+                .keys
+                .iter()
+                .map(|key| columns_values.get(key).map_or(Value::Null, |v| v.get(row)))
+                .collect();
+            groups
+                .entry((time.div_euclid(stride) * stride, key))
+                .or_default()
+                .push(row);
+        }
+        (groups, latest)
+    }
+
+    /// Updates the states with the groups, and raises the watermark to the latest time.
+    fn update(
+        &mut self,
+        columns_values: &HashMap<String, VectorRef>,
+        groups: Groups,
+        latest: Option<i64>,
+    ) -> Result<()> {
+        let mut states = std::mem::take(&mut self.states);
+        let result = self.accumulate(&mut states, columns_values, groups);
+        self.states = states;
+        result?;
+
+        if let Some(latest) = latest {
+            self.watermark = Some(self.watermark.map_or(latest, |w| w.max(latest)));
+        }
+        Ok(())
+    }
+
+    fn accumulate(
+        &self,
+        states: &mut States,
+        columns_values: &HashMap<String, VectorRef>,
+        groups: Groups,
+    ) -> Result<()> {
+        for (group, rows) in groups {
+            let accumulators = match states.entry(group) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    self.aggregates
+                        .iter()
+                        .map(ViewAggregate::create_accumulator)
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+            };
+            for (aggregate, accumulator) in self.aggregates.iter().zip(accumulators.iter_mut()) {
+                let args = aggregate
+                    .args
+                    .iter()
+                    .zip(&aggregate.input_types)
+                    .map(|(arg, data_type)| {
+                        let column = columns_values.get(arg);
+                        let mut builder =
+                            VectorBuilder::with_capacity(data_type.clone(), rows.len());
+                        for row in &rows {
+                            builder.push(&column.map_or(Value::Null, |v| v.get(*row)));
+                        }
+                        builder.finish()
+                    })
+                    .collect::<Vec<_>>();
+                accumulator.update_batch(&args).map_err(Error::new)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the rows of the buckets in the states, by the names of the columns of the view.
+    fn emit(&self, states: States) -> Result<Option<HashMap<String, VectorRef>>> {
+        if states.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builders = self
+            .schema
+            .column_schemas()
+            .iter()
+            .map(|column| VectorBuilder::with_capacity(column.data_type.clone(), states.len()))
+            .collect::<Vec<_>>();
+        for ((bucket, key), accumulators) in states {
+            for (builder, column) in builders.iter_mut().zip(&self.output) {
+                let value = match column {
+                    ViewColumn::Bucket => Value::Timestamp(Timestamp::from_millis(bucket)),
+                    ViewColumn::Key(i) => key[*i].clone(),
+                    ViewColumn::Aggregate(i) => accumulators[*i].evaluate().map_err(Error::new)?,
+                };
+                builder.push(&value);
+            }
+        }
+        Ok(Some(
+            self.schema
+                .column_schemas()
+                .iter()
+                .zip(builders.iter_mut())
+                .map(|(column, builder)| (column.name.clone(), builder.finish()))
+                .collect(),
+        ))
+    }
+}
+
+/// A materialized view maintained on the inserts into its source table, and the table the rows
+/// of the view are written into.
+pub struct ViewTable {
+    /// Full name of the table of the view.
+    name: String,
+    /// Full name of the source table.
+    source: String,
+    table: TableRef,
+    view: Mutex<MaterializedView>,
+    /// Whether the closed buckets are computed again from the source table instead of emitted
+    /// from the states, which only have the rows inserted here if the inserts into the source
+    /// table are spread over several frontends.
+    recompute_closed: bool,
+}
+
+impl ViewTable {
+    pub fn new(
+        name: String,
+        source: String,
+        table: TableRef,
+        view: MaterializedView,
+        recompute_closed: bool,
+    ) -> Self {
+        Self {
+            name,
+            source,
+            table,
+            view: Mutex::new(view),
+            recompute_closed,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Updates the view with the rows inserted into the source table, the rows must have been
+    /// written into the source table.
+    pub async fn insert(
+        &self,
+        columns_values: &HashMap<String, VectorRef>,
+        query_engine: &dyn QueryEngine,
+    ) -> Result<()> {
+        let update = self.view.lock().unwrap().insert(columns_values)?;
+        self.apply(update, query_engine).await
+    }
+
+    /// Rebuilds the states of the open buckets from the rows of the source table after the last
+    /// bucket written into the table of the view, or since `created_on` if none is written yet,
+    /// then writes the buckets closed by them.
+    pub async fn rebuild(&self, created_on: i64, query_engine: &dyn QueryEngine) -> Result<()> {
+        let (sql, stride) = {
+            let view = self.view.lock().unwrap();
+            (view.last_bucket_sql(&self.name), view.stride_millis())
+        };
+        let last_bucket = query_columns(query_engine, &sql)
+            .await?
+            .iter()
+            .flat_map(|columns_values| columns_values.values())
+            .find_map(|vector| (!vector.is_empty()).then(|| time_millis(&vector.get(0)))?);
+        let start = match last_bucket {
+            Some(last_bucket) => last_bucket + stride,
+            None => created_on.div_euclid(stride) * stride,
+        };
+
+        let sql = self
+            .view
+            .lock()
+            .unwrap()
+            .source_sql(&self.source, Some(start), None);
+        let batches = query_columns(query_engine, &sql).await?;
+        let update = {
+            let mut view = self.view.lock().unwrap();
+            for columns_values in &batches {
+                view.replay(columns_values)?;
+            }
+            view.close_buckets()?
+        };
+        self.apply(update, query_engine).await
+    }
+
+    async fn apply(&self, update: ViewUpdate, query_engine: &dyn QueryEngine) -> Result<()> {
+        let mut buckets = update.late_buckets;
+        if self.recompute_closed {
+            buckets.extend(update.closed_buckets);
+        } else if let Some(rows) = update.rows {
+            self.write(rows).await?;
+        }
+        for bucket in buckets {
+            self.recompute(bucket, query_engine).await?;
+        }
+        Ok(())
+    }
+
+    /// Computes the rows of the bucket from the rows of the source table again.
+    async fn recompute(&self, bucket: i64, query_engine: &dyn QueryEngine) -> Result<()> {
+        let sql = {
+            let view = self.view.lock().unwrap();
+            view.source_sql(
+                &self.source,
+                Some(bucket),
+                Some(bucket + view.stride_millis()),
+            )
+        };
+        let batches = query_columns(query_engine, &sql).await?;
+        let rows = self.view.lock().unwrap().aggregate(&batches)?;
+        match rows {
+            Some(rows) => self.write(rows).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn write(&self, columns_values: HashMap<String, VectorRef>) -> Result<()> {
+        let table_info = self.table.table_info();
+        let _ = self
+            .table
+            .insert(InsertRequest {
+                catalog_name: table_info.catalog_name.clone(),
+                schema_name: table_info.schema_name.clone(),
+                table_name: table_info.name.clone(),
+                columns_values,
+            })
+            .await
+            .context(InsertMaterializedViewSnafu { name: &self.name })?;
+        Ok(())
+    }
+}
+
+/// The materialized views, by the full names of their source tables.
+#[derive(Default)]
+pub struct MaterializedViews {
+    views: RwLock<HashMap<String, Vec<Arc<ViewTable>>>>,
+}
+
+impl MaterializedViews {
+    /// Registers the view, the view registered under the same name is replaced.
+    pub fn register(&self, view: ViewTable) {
+        let mut views = self.views.write().unwrap();
+        for source_views in views.values_mut() {
+            source_views.retain(|v| v.name != view.name);
+        }
+        views
+            .entry(view.source.clone())
+            .or_default()
+            .push(Arc::new(view));
+    }
+
+    /// Returns whether a view is registered under the name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.views
+            .read()
+            .unwrap()
+            .values()
+            .flatten()
+            .any(|view| view.name == name)
+    }
+
+    pub fn remove(&self, name: &str) {
+        for source_views in self.views.write().unwrap().values_mut() {
+            source_views.retain(|view| view.name != name);
+        }
+    }
+
+    /// Updates the views over the source table with the rows inserted into it.
+    pub async fn insert(
+        &self,
+        source: &str,
+        columns_values: &HashMap<String, VectorRef>,
+        query_engine: &dyn QueryEngine,
+    ) -> Result<()> {
+        let views = self
+            .views
+            .read()
+            .unwrap()
+            .get(source)
+            .cloned()
+            .unwrap_or_default();
+        for view in views {
+            view.insert(columns_values, query_engine)
+                .await
+                .context(UpdateMaterializedViewSnafu { name: view.name() })?;
+        }
+        Ok(())
+    }
+}
+
+/// Executes the query, returns the rows of each batch by the names of the columns.
+async fn query_columns(
+    query_engine: &dyn QueryEngine,
+    sql: &str,
+) -> Result<Vec<HashMap<String, VectorRef>>> {
+    let plan = query_engine.sql_to_plan(sql, QueryContext::arc())?;
+    let batches = match query_engine.execute(&plan).await? {
+        Output::Stream(stream) => record_util::collect(stream)
+            .await
+            .context(CreateRecordBatchSnafu)?,
+        Output::RecordBatches(batches) => batches.take(),
+        Output::AffectedRows(_) => unreachable!(),
+    };
+    batches
+        .iter()
+        .map(|batch| {
+            batch
+                .schema
+                .column_schemas()
+                .iter()
+                .zip(batch.df_recordbatch.columns())
+                .map(|(column, array)| {
+                    let vector =
+                        Helper::try_into_vector(array.clone()).context(VectorComputationSnafu)?;
+                    Ok((column.name.clone(), vector))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn quote(name: &str) -> String {
+    Ident::with_quote('"', name).to_string()
+}
+
+fn plain_select(query: &Query) -> Result<&Select> {
+    match &query.inner.body {
+        SetExpr::Select(select) => Ok(select),
+        _ => invalid("only a plain SELECT can be a materialized view"),
+    }
+}
+
+/// Returns the stride of the time bucket, in milliseconds.
+fn parse_bucket(function: &Function, time_index: &str) -> Result<i64> {
+    let args = function
+        .args
+        .iter()
+        .map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => Some(arg),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    let stride = match args.as_deref() {
+        Some([stride, time]) if is_column(time, time_index) => match stride {
+            Expr::Value(SqlValue::SingleQuotedString(s)) => parse_duration_millis(s),
+            Expr::Value(SqlValue::Number(n, _)) => n.parse::<i64>().ok(),
+            _ => None,
+        },
+        _ => None,
+    };
+    match stride {
+        Some(stride) if stride > 0 => Ok(stride),
+        _ => invalid(format!(
+            "expect {}(stride, {}) with a positive stride, found: {}",
+            function_name(function),
+            time_index,
+            function
+        )),
+    }
+}
+
+fn parse_aggregate<'a>(
+    function: &Function,
+    time_index: &'a ColumnSchema,
+    find_column: impl Fn(&Expr) -> Option<&'a ColumnSchema>,
+    query_engine: &dyn QueryEngine,
+) -> Result<ViewAggregate> {
+    ensure!(
+        function.over.is_none() && !function.distinct,
+        InvalidMaterializedViewSnafu {
+            reason: format!(
+                "window functions and DISTINCT are not allowed in {}",
+                function
+            ),
+        }
+    );
+    let name = function_name(function);
+    let builtin = BuiltinAggregate::from_name(&name);
+    let mut args = Vec::with_capacity(function.args.len());
+    let mut input_types = Vec::with_capacity(function.args.len());
+    for arg in &function.args {
+        let column = match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => find_column(arg),
+            // Counts the rows, whose time index is never null.
+            FunctionArg::Unnamed(FunctionArgExpr::Wildcard)
+                if builtin == Some(BuiltinAggregate::Count) =>
+            {
+                Some(time_index)
+            }
+            _ => None,
+        }
+        .with_context(|| InvalidMaterializedViewSnafu {
+            reason: format!(
+                "the arguments of {} must be columns of the source table",
+                function
+            ),
+        })?;
+        args.push(column.name.clone());
+        input_types.push(column.data_type.clone());
+    }
+
+    let function = match builtin {
+        Some(builtin) => {
+            ensure!(
+                args.len() == 1,
+                InvalidMaterializedViewSnafu {
+                    reason: format!("{} expects 1 argument", name),
+                }
+            );
+            ViewFunction::Builtin(builtin)
+        }
+        None => {
+            let meta = query_engine.aggregate_function(&name).with_context(|| {
+                InvalidMaterializedViewSnafu {
+                    reason: format!("unknown aggregate function: {}", name),
+                }
+            })?;
+            ensure!(
+                args.len() == meta.args_count() as usize,
+                InvalidMaterializedViewSnafu {
+                    reason: format!("{} expects {} arguments", name, meta.args_count()),
+                }
+            );
+            let creator = meta.create();
+            creator
+                .set_input_types(input_types.clone())
+                .map_err(Error::new)?;
+            ViewFunction::Registered(creator)
+        }
+    };
+    Ok(ViewAggregate {
+        function,
+        args,
+        input_types,
+    })
+}
+
+fn invalid<T>(reason: impl Into<String>) -> Result<T> {
+    InvalidMaterializedViewSnafu {
+        reason: reason.into(),
+    }
+    .fail()
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::{Float64Vector, StringVector, TimestampVector};
+    use sql::dialect::GenericDialect;
+    use sql::parser::ParserContext;
+    use sql::statements::statement::Statement;
+
+    use super::*;
+    use crate::QueryEngineFactory;
+
+    fn source_schema() -> Schema {
+        Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+            ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+                .with_time_index(true),
+        ])
+    }
+
+    fn create_view(sql: &str) -> Result<MaterializedView> {
+        let query = match ParserContext::create_with_dialect(sql, &GenericDialect {})
+            .unwrap()
+            .remove(0)
+        {
+            Statement::CreateMaterializedView(create) => create.query,
+            _ => unreachable!(),
+        };
+        let catalog_list = catalog::local::new_memory_catalog_list().unwrap();
+        let query_engine = QueryEngineFactory::new(catalog_list).query_engine();
+        MaterializedView::try_new(&query, &source_schema(), query_engine.as_ref())
+    }
+
+    fn rows(hosts: Vec<&str>, cpus: Vec<f64>, times: Vec<i64>) -> HashMap<String, VectorRef> {
+        HashMap::from([
+            (
+                "host".to_string(),
+                Arc::new(StringVector::from(hosts)) as VectorRef,
+            ),
+            (
+                "cpu".to_string(),
+                Arc::new(Float64Vector::from_vec(cpus)) as _,
+            ),
+            (
+                "ts".to_string(),
+                Arc::new(TimestampVector::from_values(times)) as _,
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_create_materialized_view() {
+        let view = create_view(
+            "CREATE MATERIALIZED VIEW cpu_1m AS SELECT host, date_bin('1m', ts) AS ts, avg(cpu), count(*) AS n \
+             FROM monitor GROUP BY host, date_bin('1m', ts)",
+        )
+        .unwrap();
+        assert_eq!("monitor", view.source().to_string());
+        assert_eq!(vec![0], view.primary_key_indices());
+        let schema = view.schema();
+        let columns = schema
+            .column_schemas()
+            .iter()
+            .map(|column| (column.name.as_str(), column.data_type.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("host", ConcreteDataType::string_datatype()),
+                ("ts", ConcreteDataType::timestamp_millis_datatype()),
+                ("avg(cpu)", ConcreteDataType::float64_datatype()),
+                ("n", ConcreteDataType::uint64_datatype()),
+            ],
+            columns
+        );
+        assert_eq!(Some(1), schema.timestamp_index());
+
+        let cases = [
+            (
+                "SELECT host, avg(cpu) FROM monitor GROUP BY host",
+                "groups by date_bin or time_bucket",
+            ),
+            (
+                "SELECT host, date_bin('1m', ts), avg(cpu) FROM monitor GROUP BY date_bin('1m', ts)",
+                "column host must be in GROUP BY",
+            ),
+            (
+                "SELECT date_bin('1m', cpu), avg(cpu) FROM monitor GROUP BY date_bin('1m', cpu)",
+                "with a positive stride",
+            ),
+            (
+                "SELECT date_bin('1m', ts), avg(cpu) FROM monitor WHERE cpu > 0 GROUP BY date_bin('1m', ts)",
+                "WHERE, HAVING and DISTINCT are not allowed",
+            ),
+            (
+                "SELECT date_bin('1m', ts), no_such_func(cpu) FROM monitor GROUP BY date_bin('1m', ts)",
+                "unknown aggregate function: no_such_func",
+            ),
+        ];
+        for (query, reason) in cases {
+            let sql = format!("CREATE MATERIALIZED VIEW v AS {}", query);
+            let err = create_view(&sql).err().unwrap();
+            assert!(err.to_string().contains(reason), "{}: {}", sql, err);
+        }
+    }
+
+    #[test]
+    fn test_insert_materialized_view() {
+        let mut view = create_view(
+            "CREATE MATERIALIZED VIEW cpu_1m AS SELECT host, date_bin('1m', ts) AS ts, avg(cpu) AS cpu, count(*) AS n \
+             FROM monitor GROUP BY host, date_bin('1m', ts)",
+        )
+        .unwrap();
+
+        // The first bucket is still open.
+        let update = view
+            .insert(&rows(
+                vec!["a", "b", "a"],
+                vec![1.0, 2.0, 3.0],
+                vec![0, 10_000, 59_999],
+            ))
+            .unwrap();
+        assert!(update.rows.is_none());
+        assert!(update.closed_buckets.is_empty());
+
+        // A row at the end of the first bucket closes it.
+        let update = view
+            .insert(&rows(vec!["a", "a"], vec![5.0, 7.0], vec![20_000, 60_000]))
+            .unwrap();
+        assert_eq!(vec![0], update.closed_buckets);
+        assert!(update.late_buckets.is_empty());
+        let closed = update.rows.unwrap();
+        assert_eq!(2, closed["host"].len());
+        assert_eq!(Value::from("a"), closed["host"].get(0));
+        assert_eq!(Value::from("b"), closed["host"].get(1));
+        assert_eq!(
+            Value::Timestamp(Timestamp::from_millis(0)),
+            closed["ts"].get(0)
+        );
+        assert_eq!(Value::from(3.0), closed["cpu"].get(0));
+        assert_eq!(Value::from(2.0), closed["cpu"].get(1));
+        assert_eq!(Value::from(3u64), closed["n"].get(0));
+        assert_eq!(Value::from(1u64), closed["n"].get(1));
+
+        // The rows of the closed bucket are reported as late, not aggregated.
+        let update = view
+            .insert(&rows(
+                vec!["a", "a"],
+                vec![100.0, 9.0],
+                vec![1_000, 130_000],
+            ))
+            .unwrap();
+        assert_eq!(vec![0], update.late_buckets);
+        assert_eq!(vec![60_000], update.closed_buckets);
+        let closed = update.rows.unwrap();
+        assert_eq!(1, closed["host"].len());
+        assert_eq!(
+            Value::Timestamp(Timestamp::from_millis(60_000)),
+            closed["ts"].get(0)
+        );
+        assert_eq!(Value::from(7.0), closed["cpu"].get(0));
+        assert_eq!(Value::from(1u64), closed["n"].get(0));
+    }
+
+    #[test]
+    fn test_rebuild_materialized_view() {
+        let mut view = create_view(
+            "CREATE MATERIALIZED VIEW cpu_1m AS SELECT host, date_bin('1m', ts) AS ts, avg(cpu) AS cpu \
+             FROM monitor GROUP BY host, date_bin('1m', ts)",
+        )
+        .unwrap();
+        assert_eq!(
+            r#"SELECT "host", "cpu", "ts" FROM greptime.public.monitor WHERE "ts" >= 0 AND "ts" < 60000"#,
+            view.source_sql("greptime.public.monitor", Some(0), Some(60_000))
+        );
+        assert_eq!(
+            r#"SELECT "host", "cpu", "ts" FROM monitor"#,
+            view.source_sql("monitor", None, None)
+        );
+        assert_eq!(
+            r#"SELECT max("ts") FROM cpu_1m"#,
+            view.last_bucket_sql("cpu_1m")
+        );
+
+        // Replaying rows out of order closes no bucket.
+        view.replay(&rows(vec!["a", "a"], vec![5.0, 1.0], vec![60_000, 0]))
+            .unwrap();
+        view.replay(&rows(vec!["a"], vec![3.0], vec![30_000]))
+            .unwrap();
+        let update = view.close_buckets().unwrap();
+        assert_eq!(vec![0], update.closed_buckets);
+        let closed = update.rows.unwrap();
+        assert_eq!(1, closed["host"].len());
+        assert_eq!(Value::from(2.0), closed["cpu"].get(0));
+
+        // Aggregating late rows from scratch leaves the open buckets alone.
+        let aggregated = view
+            .aggregate(&[rows(vec!["a", "b"], vec![1.0, 4.0], vec![0, 1_000])])
+            .unwrap()
+            .unwrap();
+        assert_eq!(2, aggregated["host"].len());
+        assert_eq!(Value::from(1.0), aggregated["cpu"].get(0));
+        assert_eq!(Value::from(4.0), aggregated["cpu"].get(1));
+        let update = view
+            .insert(&rows(vec!["a"], vec![0.0], vec![120_000]))
+            .unwrap();
+        assert_eq!(vec![60_000], update.closed_buckets);
+        assert_eq!(Value::from(5.0), update.rows.unwrap()["cpu"].get(0));
+    }
+}
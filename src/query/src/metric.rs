@@ -19,3 +19,7 @@ pub static METRIC_OPTIMIZE_LOGICAL_ELAPSED: &str = "query.optimize_logicalplan_e
 pub static METRIC_OPTIMIZE_PHYSICAL_ELAPSED: &str = "query.optimize_physicalplan_elapsed";
 pub static METRIC_CREATE_PHYSICAL_ELAPSED: &str = "query.create_physicalplan_elapsed";
 pub static METRIC_EXEC_PLAN_ELAPSED: &str = "query.execute_plan_elapsed";
+pub static METRIC_QUERY_CACHE_HIT: &str = "query.cache.hit";
+pub static METRIC_QUERY_CACHE_MISS: &str = "query.cache.miss";
+pub static METRIC_QUERY_CACHE_EVICT: &str = "query.cache.evict";
+pub static METRIC_QUERY_CACHE_BYTES: &str = "query.cache.bytes";
@@ -0,0 +1,258 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Type inference of parameters in prepared statements.
+
+use catalog::CatalogManagerRef;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::SchemaRef;
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::ast::{Expr, ObjectName, Query, SetExpr, TableFactor};
+use sql::prepared::{expr_placeholder_index, PreparedStatement};
+use sql::statements::statement::Statement;
+use sql::statements::table_idents_to_full_name;
+
+use crate::error::{self, Result};
+
+/// Infers the data types of parameters in `stmt` from the columns they are compared with or
+/// inserted into. `None` is returned for the parameters whose types cannot be determined.
+pub fn infer_param_types(
+    stmt: &PreparedStatement,
+    catalog_manager: &CatalogManagerRef,
+    query_ctx: &QueryContextRef,
+) -> Result<Vec<Option<ConcreteDataType>>> {
+    let mut inferer = ParamTypeInferer {
+        catalog_manager,
+        query_ctx,
+        tables: Vec::new(),
+        types: vec![None; stmt.param_num()],
+    };
+
+    match stmt.statement() {
+        Statement::Query(query) => inferer.visit_query(&query.inner)?,
+        Statement::Insert(insert) => {
+            let table_name = insert
+                .full_table_name(query_ctx)
+                .context(error::ParseSqlSnafu)?;
+            if let Some(schema) = inferer.table_schema(&table_name)? {
+                let columns = insert.columns();
+                let column_types = if columns.is_empty() {
+                    schema
+                        .column_schemas()
+                        .iter()
+                        .map(|c| Some(c.data_type.clone()))
+                        .collect::<Vec<_>>()
+                } else {
+                    columns
+                        .iter()
+                        .map(|c| schema.column_schema_by_name(c).map(|c| c.data_type.clone()))
+                        .collect()
+                };
+
+                for row in insert.value_exprs().into_iter().flatten() {
+                    for (expr, data_type) in row.iter().zip(column_types.iter()) {
+                        inferer.set_type(expr, data_type.clone());
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(inferer.types)
+}
+
+struct ParamTypeInferer<'a> {
+    catalog_manager: &'a CatalogManagerRef,
+    query_ctx: &'a QueryContextRef,
+    // Schemas of the tables in the `FROM` clause being visited.
+    tables: Vec<SchemaRef>,
+    types: Vec<Option<ConcreteDataType>>,
+}
+
+impl<'a> ParamTypeInferer<'a> {
+    fn table_schema(&self, table_name: &(String, String, String)) -> Result<Option<SchemaRef>> {
+        let (catalog, schema, table) = table_name;
+        let table = self
+            .catalog_manager
+            .table(catalog, schema, table)
+            .context(error::CatalogSnafu)?;
+        Ok(table.map(|t| t.schema()))
+    }
+
+    fn visit_query(&mut self, query: &Query) -> Result<()> {
+        self.visit_set_expr(&query.body)?;
+
+        let int64 = Some(ConcreteDataType::int64_datatype());
+        if let Some(limit) = &query.limit {
+            self.set_type(limit, int64.clone());
+        }
+        if let Some(offset) = &query.offset {
+            self.set_type(&offset.value, int64);
+        }
+        Ok(())
+    }
+
+    fn visit_set_expr(&mut self, set_expr: &SetExpr) -> Result<()> {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let outer_tables = self.tables.len();
+                for table_with_joins in &select.from {
+                    let relations = std::iter::once(&table_with_joins.relation)
+                        .chain(table_with_joins.joins.iter().map(|j| &j.relation));
+                    for relation in relations {
+                        match relation {
+                            TableFactor::Table { name, .. } => self.add_table(name)?,
+                            TableFactor::Derived { subquery, .. } => self.visit_query(subquery)?,
+                            _ => {}
+                        }
+                    }
+                }
+
+                if let Some(selection) = &select.selection {
+                    self.visit_expr(selection)?;
+                }
+                if let Some(having) = &select.having {
+                    self.visit_expr(having)?;
+                }
+                self.tables.truncate(outer_tables);
+            }
+            SetExpr::Query(query) => self.visit_query(query)?,
+            SetExpr::SetOperation { left, right, .. } => {
+                self.visit_set_expr(left)?;
+                self.visit_set_expr(right)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn add_table(&mut self, name: &ObjectName) -> Result<()> {
+        // Unknown tables are left to the planner to complain about when the statement is executed.
+        if let Ok(table_name) = table_idents_to_full_name(name, self.query_ctx) {
+            if let Some(schema) = self.table_schema(&table_name)? {
+                self.tables.push(schema);
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) -> Result<()> {
+        match expr {
+            Expr::BinaryOp { left, right, .. } => {
+                let left_type = self.column_type(left);
+                let right_type = self.column_type(right);
+                self.set_type(left, right_type);
+                self.set_type(right, left_type);
+                self.visit_expr(left)?;
+                self.visit_expr(right)?;
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                let data_type = self.column_type(expr);
+                self.set_type(low, data_type.clone());
+                self.set_type(high, data_type);
+            }
+            Expr::InList { expr, list, .. } => {
+                let data_type = self.column_type(expr);
+                for item in list {
+                    self.set_type(item, data_type.clone());
+                }
+            }
+            Expr::InSubquery { subquery, .. } | Expr::Subquery(subquery) => {
+                self.visit_query(subquery)?
+            }
+            Expr::Exists { subquery, .. } => self.visit_query(subquery)?,
+            Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => self.visit_expr(expr)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn column_type(&self, expr: &Expr) -> Option<ConcreteDataType> {
+        let column = match expr {
+            Expr::Identifier(ident) => ident,
+            Expr::CompoundIdentifier(idents) => idents.last()?,
+            Expr::Nested(expr) => return self.column_type(expr),
+            _ => return None,
+        };
+        self.tables.iter().find_map(|schema| {
+            schema
+                .column_schema_by_name(&column.value)
+                .map(|c| c.data_type.clone())
+        })
+    }
+
+    fn set_type(&mut self, expr: &Expr, data_type: Option<ConcreteDataType>) {
+        if let (Some(index), Some(data_type)) = (expr_placeholder_index(expr), data_type) {
+            if self.types[index].is_none() {
+                self.types[index] = Some(data_type);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use catalog::local::new_memory_catalog_list;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use session::context::QueryContext;
+    use table::table::numbers::NumbersTable;
+
+    use super::*;
+
+    fn infer(sql: &str) -> Vec<Option<ConcreteDataType>> {
+        let catalog_manager: CatalogManagerRef = new_memory_catalog_list().unwrap();
+        catalog_manager
+            .catalog(DEFAULT_CATALOG_NAME)
+            .unwrap()
+            .unwrap()
+            .schema(DEFAULT_SCHEMA_NAME)
+            .unwrap()
+            .unwrap()
+            .register_table("numbers".to_string(), Arc::new(NumbersTable::default()))
+            .unwrap();
+
+        let stmt = PreparedStatement::new(sql).unwrap();
+        infer_param_types(&stmt, &catalog_manager, &QueryContext::arc()).unwrap()
+    }
+
+    #[test]
+    fn test_infer_param_types() {
+        let uint32 = Some(ConcreteDataType::uint32_datatype());
+        let int64 = Some(ConcreteDataType::int64_datatype());
+
+        assert_eq!(
+            vec![uint32.clone(), uint32.clone(), None, int64.clone()],
+            infer("SELECT * FROM numbers WHERE number > ? AND (? = numbers.number OR ? IS NULL) LIMIT ?")
+        );
+        assert_eq!(
+            vec![uint32.clone(), uint32.clone(), uint32.clone(), int64],
+            infer(
+                "SELECT * FROM numbers WHERE number BETWEEN $1 AND $2 OR number IN ($3) OFFSET $4"
+            )
+        );
+        assert_eq!(
+            vec![uint32, None],
+            infer("INSERT INTO numbers VALUES (?, ?)")
+        );
+        assert_eq!(
+            vec![None],
+            infer("SELECT * FROM not_exists WHERE number = ?")
+        );
+    }
+}
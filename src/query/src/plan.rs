@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use datafusion::logical_plan::LogicalPlan as DfLogicalPlan;
 
+use crate::range_select::RangeSelect;
+
 /// A LogicalPlan represents the different types of relational
 /// operators (such as Projection, Filter, etc) and can be created by
 /// the SQL query planner.
@@ -27,4 +30,29 @@ use datafusion::logical_plan::LogicalPlan as DfLogicalPlan;
 #[derive(Clone, Debug)]
 pub enum LogicalPlan {
     DfPlan(DfLogicalPlan),
+    /// A range query over the plan of its input.
+    RangeSelect(RangeSelect),
+    /// `EXPLAIN` of the plan.
+    Explain(Explain),
+}
+
+/// The logical plan of `EXPLAIN [ANALYZE] [VERBOSE]`.
+#[derive(Clone, Debug)]
+pub struct Explain {
+    /// Executes the plan and reports the metrics of each operator.
+    pub analyze: bool,
+    /// Also reports the logical plans after each optimizer rule.
+    pub verbose: bool,
+    pub plan: Box<LogicalPlan>,
+}
+
+/// The metrics of an operator of an executed plan.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperatorMetrics {
+    /// The operator, as shown by `EXPLAIN`.
+    pub operator: String,
+    /// Counters like the output rows and the SSTs read, summed over partitions.
+    pub counters: HashMap<String, usize>,
+    /// Elapsed times in nanoseconds, summed over partitions.
+    pub times: HashMap<String, usize>,
 }
@@ -23,11 +23,14 @@ use common_function::scalars::{FunctionRef, FUNCTION_REGISTRY};
 use common_query::physical_plan::PhysicalPlan;
 use common_query::prelude::ScalarUdf;
 use common_query::Output;
+use common_recordbatch::RecordBatches;
+use session::context::QueryContextRef;
 use sql::statements::statement::Statement;
 
+use crate::cache::QueryCacheOptions;
 use crate::datafusion::DatafusionQueryEngine;
 use crate::error::Result;
-use crate::plan::LogicalPlan;
+use crate::plan::{LogicalPlan, OperatorMetrics};
 pub use crate::query_engine::context::QueryContext;
 pub use crate::query_engine::state::QueryEngineState;
 
@@ -37,28 +40,50 @@ pub trait QueryEngine: Send + Sync {
 
     fn sql_to_statement(&self, sql: &str) -> Result<Statement>;
 
-    fn statement_to_plan(&self, stmt: Statement) -> Result<LogicalPlan>;
+    fn statement_to_plan(&self, stmt: Statement, query_ctx: QueryContextRef)
+        -> Result<LogicalPlan>;
 
-    fn sql_to_plan(&self, sql: &str) -> Result<LogicalPlan>;
+    fn sql_to_plan(&self, sql: &str, query_ctx: QueryContextRef) -> Result<LogicalPlan>;
 
     async fn execute(&self, plan: &LogicalPlan) -> Result<Output>;
 
     async fn execute_physical(&self, plan: &Arc<dyn PhysicalPlan>) -> Result<Output>;
 
+    /// Executes the plan to the end, returns its results along with the metrics of its
+    /// operators, for the frontend to report the metrics of the plans run on datanodes.
+    async fn execute_with_metrics(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<(RecordBatches, Vec<OperatorMetrics>)>;
+
     fn register_udf(&self, udf: ScalarUdf);
 
     fn register_aggregate_function(&self, func: AggregateFunctionMetaRef);
 
+    /// Returns the registered aggregate function of the name.
+    fn aggregate_function(&self, name: &str) -> Option<AggregateFunctionMetaRef>;
+
     fn register_function(&self, func: FunctionRef);
 }
 
+/// Returns the name a function defined in a schema is registered as, such as the functions of
+/// scripts. An unqualified function call is resolved against the current schema first.
+pub fn schema_function_name(schema: &str, name: &str) -> String {
+    format!("{}.{}", schema, name)
+}
+
 pub struct QueryEngineFactory {
     query_engine: Arc<dyn QueryEngine>,
 }
 
 impl QueryEngineFactory {
     pub fn new(catalog_list: CatalogListRef) -> Self {
-        let query_engine = Arc::new(DatafusionQueryEngine::new(catalog_list));
+        Self::new_with_cache(catalog_list, &QueryCacheOptions::default())
+    }
+
+    /// Creates the query engine, which caches the query results by the `cache_options`.
+    pub fn new_with_cache(catalog_list: CatalogListRef, cache_options: &QueryCacheOptions) -> Self {
+        let query_engine = Arc::new(DatafusionQueryEngine::new(catalog_list, cache_options));
 
         for func in FUNCTION_REGISTRY.functions() {
             query_engine.register_function(func);
@@ -30,6 +30,7 @@ use datafusion::optimizer::single_distinct_to_groupby::SingleDistinctToGroupBy;
 use datafusion::optimizer::to_approx_perc::ToApproxPerc;
 use datafusion::prelude::{ExecutionConfig, ExecutionContext};
 
+use crate::cache::{QueryCache, QueryCacheOptions, QueryCacheRef};
 use crate::datafusion::DfCatalogListAdapter;
 use crate::optimizer::TypeConversionRule;
 
@@ -42,6 +43,7 @@ pub struct QueryEngineState {
     df_context: ExecutionContext,
     catalog_list: CatalogListRef,
     aggregate_functions: Arc<RwLock<HashMap<String, AggregateFunctionMetaRef>>>,
+    query_cache: Option<QueryCacheRef>,
 }
 
 impl fmt::Debug for QueryEngineState {
@@ -52,7 +54,7 @@ impl fmt::Debug for QueryEngineState {
 }
 
 impl QueryEngineState {
-    pub(crate) fn new(catalog_list: CatalogListRef) -> Self {
+    pub(crate) fn new(catalog_list: CatalogListRef, cache_options: &QueryCacheOptions) -> Self {
         let config = ExecutionConfig::new()
             .with_default_catalog_and_schema(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME)
             .with_optimizer_rules(vec![
@@ -77,6 +79,9 @@ impl QueryEngineState {
             df_context,
             catalog_list,
             aggregate_functions: Arc::new(RwLock::new(HashMap::new())),
+            query_cache: cache_options
+                .enabled()
+                .then(|| Arc::new(QueryCache::new(cache_options))),
         }
     }
 
@@ -117,6 +122,12 @@ impl QueryEngineState {
         &self.df_context
     }
 
+    /// Returns the cache of query results, `None` if it's disabled.
+    #[inline]
+    pub fn query_cache(&self) -> Option<&QueryCacheRef> {
+        self.query_cache.as_ref()
+    }
+
     #[inline]
     pub(crate) fn runtime(&self) -> Arc<RuntimeEnv> {
         self.df_context.runtime_env()
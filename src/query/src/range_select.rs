@@ -0,0 +1,389 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Range queries, like `SELECT host, avg(cpu) RANGE '5m' FROM t ALIGN '1m' BY (host) FILL PREV`,
+//! compute the aggregates over the windows of the range that start at every aligned time, for
+//! each series.
+//!
+//! A range query is planned as a [RangeSelect] over a DataFusion plan, which reads the time
+//! index, the series keys and the arguments of the aggregates from the table. The windows are
+//! computed by the [RangeSelectExec].
+
+pub(crate) mod aggregate;
+mod cache;
+mod exec;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use datafusion::logical_plan::LogicalPlan as DfLogicalPlan;
+use snafu::ensure;
+use sql::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Ident, Query as SpQuery, SelectItem, SetExpr,
+    TableFactor, Value,
+};
+use sql::statements::query::{Fill, RangeSelect as SqlRangeSelect};
+
+use crate::error::{InvalidRangeQuerySnafu, Result};
+use crate::range_select::aggregate::BuiltinAggregate;
+pub(crate) use crate::range_select::cache::{RangeWindows, WindowReuse};
+pub(crate) use crate::range_select::exec::time_millis;
+pub use crate::range_select::exec::RangeSelectExec;
+
+/// The logical plan of a range query.
+///
+/// Columns of the input are the time index first, then the series keys, then the arguments of
+/// the aggregates.
+#[derive(Clone, Debug)]
+pub struct RangeSelect {
+    pub input: DfLogicalPlan,
+    pub align_millis: i64,
+    /// Number of the series keys in the input.
+    pub by_count: usize,
+    pub aggregates: Vec<RangeAggregate>,
+    pub output: Vec<RangeOutput>,
+    /// Indexes of the output columns to sort by, and whether in ascending order.
+    pub order_by: Vec<(usize, bool)>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl fmt::Display for RangeSelect {
+    /// Formats the range query without its input, as a line of `EXPLAIN`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RangeSelect: ")?;
+        fmt_range_select(
+            f,
+            self.align_millis,
+            self.by_count,
+            self.aggregates.iter().map(|aggregate| {
+                (
+                    aggregate.function.as_str(),
+                    aggregate.args.as_slice(),
+                    aggregate.range_millis,
+                    aggregate.fill.as_ref(),
+                )
+            }),
+        )
+    }
+}
+
+/// Formats the windows of a range query, the aggregates are given by their function names,
+/// indexes of the arguments in the input, ranges and fills.
+pub(crate) fn fmt_range_select<'a>(
+    f: &mut fmt::Formatter,
+    align_millis: i64,
+    by_count: usize,
+    aggregates: impl Iterator<Item = (&'a str, &'a [usize], i64, Option<&'a Fill>)>,
+) -> fmt::Result {
+    write!(f, "align={}ms, by={}, aggregates=[", align_millis, by_count)?;
+    for (i, (function, args, range_millis, fill)) in aggregates.enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        let args = args
+            .iter()
+            .map(|arg| format!("#{}", arg))
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "{}({}) range={}ms",
+            function,
+            args.join(", "),
+            range_millis
+        )?;
+        if let Some(fill) = fill {
+            write!(f, " fill={:?}", fill)?;
+        }
+    }
+    write!(f, "]")
+}
+
+/// An aggregate computed over the windows of a range query.
+#[derive(Clone, Debug)]
+pub struct RangeAggregate {
+    pub function: String,
+    /// Indexes of the arguments in the input.
+    pub args: Vec<usize>,
+    pub range_millis: i64,
+    pub fill: Option<Fill>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeOutput {
+    pub name: String,
+    pub column: RangeColumn,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeColumn {
+    /// The start time of the window.
+    Time,
+    /// The series key at the index.
+    By(usize),
+    /// The aggregate at the index.
+    Aggregate(usize),
+}
+
+/// The table read by a range query.
+pub(crate) struct RangeTable {
+    pub time_index: String,
+    pub primary_keys: Vec<String>,
+}
+
+/// Returns the only table the range query reads.
+pub(crate) fn range_table_name(query: &SpQuery) -> Result<&[Ident]> {
+    let select = match &query.body {
+        SetExpr::Select(select) => select,
+        _ => return invalid("only a plain SELECT can be a range query"),
+    };
+    ensure!(
+        select.from.len() == 1 && select.from[0].joins.is_empty(),
+        InvalidRangeQuerySnafu {
+            reason: "a range query reads exactly one table",
+        }
+    );
+    match &select.from[0].relation {
+        TableFactor::Table { name, .. } => Ok(&name.0),
+        _ => invalid("a range query reads exactly one table"),
+    }
+}
+
+/// Plans the range query, `plan_input` plans the query that reads the input from the table.
+pub(crate) fn plan_range_select(
+    query: &SpQuery,
+    range_select: &SqlRangeSelect,
+    table: &RangeTable,
+    plan_input: impl FnOnce(SpQuery) -> Result<DfLogicalPlan>,
+) -> Result<RangeSelect> {
+    let select = match &query.body {
+        SetExpr::Select(select) => select,
+        _ => return invalid("only a plain SELECT can be a range query"),
+    };
+    ensure!(
+        select.group_by.is_empty() && select.having.is_none() && !select.distinct,
+        InvalidRangeQuerySnafu {
+            reason: "GROUP BY, HAVING and DISTINCT are not allowed in a range query",
+        }
+    );
+    ensure!(
+        query.fetch.is_none(),
+        InvalidRangeQuerySnafu {
+            reason: "FETCH is not allowed in a range query",
+        }
+    );
+
+    let by = range_select.by.clone().unwrap_or_else(|| {
+        table
+            .primary_keys
+            .iter()
+            .map(|key| Expr::Identifier(Ident::with_quote('"', key)))
+            .collect()
+    });
+    let mut input_items = Vec::with_capacity(1 + by.len());
+    input_items.push(SelectItem::ExprWithAlias {
+        expr: Expr::Identifier(Ident::with_quote('"', &table.time_index)),
+        alias: Ident::new("__range_time"),
+    });
+    for (i, expr) in by.iter().enumerate() {
+        input_items.push(SelectItem::ExprWithAlias {
+            expr: expr.clone(),
+            alias: Ident::new(format!("__range_by_{}", i)),
+        });
+    }
+
+    let ranges = range_select
+        .ranges
+        .iter()
+        .map(|range| (range.item, range))
+        .collect::<HashMap<_, _>>();
+    let mut aggregates = Vec::with_capacity(ranges.len());
+    let mut output = Vec::with_capacity(select.projection.len());
+    // Expressions of the output columns, to resolve ORDER BY.
+    let mut output_exprs = Vec::with_capacity(select.projection.len());
+    for (i, item) in select.projection.iter().enumerate() {
+        let (expr, alias) = match item {
+            SelectItem::UnnamedExpr(expr) => (expr, None),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+            _ => return invalid("wildcards are not allowed in a range query"),
+        };
+
+        let column = if let Some(range) = ranges.get(&i) {
+            let function = match expr {
+                Expr::Function(function) => function,
+                _ => return invalid(format!("RANGE must follow an aggregate, found: {}", expr)),
+            };
+            ensure!(
+                function.over.is_none() && !function.distinct,
+                InvalidRangeQuerySnafu {
+                    reason: format!(
+                        "window functions and DISTINCT are not allowed in {}",
+                        function
+                    ),
+                }
+            );
+            let name = function_name(function);
+            let builtin = BuiltinAggregate::from_name(&name);
+            let mut args = Vec::with_capacity(function.args.len());
+            for arg in &function.args {
+                let arg = match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => arg.clone(),
+                    // Counts the rows, whose time index is never null.
+                    FunctionArg::Unnamed(FunctionArgExpr::Wildcard)
+                        if builtin == Some(BuiltinAggregate::Count) =>
+                    {
+                        args.push(0);
+                        continue;
+                    }
+                    _ => return invalid(format!("unsupported arguments of {}", function)),
+                };
+                args.push(input_items.len());
+                input_items.push(SelectItem::ExprWithAlias {
+                    expr: arg,
+                    alias: Ident::new(format!("__range_arg_{}", input_items.len())),
+                });
+            }
+
+            aggregates.push(RangeAggregate {
+                function: name,
+                args,
+                range_millis: range.range_millis,
+                fill: range.fill.clone().or_else(|| range_select.fill.clone()),
+            });
+            let name = alias.unwrap_or_else(|| format!("{} RANGE {}", function, range.range));
+            RangeOutput {
+                name,
+                column: RangeColumn::Aggregate(aggregates.len() - 1),
+            }
+        } else if is_column(expr, &table.time_index) {
+            RangeOutput {
+                name: alias.unwrap_or_else(|| table.time_index.clone()),
+                column: RangeColumn::Time,
+            }
+        } else if let Some(j) = by.iter().position(|by_expr| by_expr == expr) {
+            RangeOutput {
+                name: alias.unwrap_or_else(|| expr.to_string()),
+                column: RangeColumn::By(j),
+            }
+        } else {
+            return invalid(format!(
+                "{} must be an aggregate with RANGE, the time index or an expression in BY",
+                expr
+            ));
+        };
+        output.push(column);
+        output_exprs.push(expr);
+    }
+
+    let mut order_by = Vec::with_capacity(query.order_by.len());
+    for order_by_expr in &query.order_by {
+        let expr = &order_by_expr.expr;
+        let index = match expr {
+            Expr::Value(Value::Number(n, _)) => n
+                .parse::<usize>()
+                .ok()
+                .filter(|n| (1..=output.len()).contains(n))
+                .map(|n| n - 1),
+            Expr::Identifier(ident) => output
+                .iter()
+                .position(|output| output.name == ident.value)
+                .or_else(|| output_exprs.iter().position(|e| *e == expr)),
+            _ => output_exprs.iter().position(|e| *e == expr),
+        };
+        match index {
+            Some(index) => order_by.push((index, order_by_expr.asc.unwrap_or(true))),
+            None => {
+                return invalid(format!(
+                    "ORDER BY {} must refer to the output of the range query",
+                    expr
+                ))
+            }
+        }
+    }
+    let limit = query
+        .limit
+        .as_ref()
+        .map(|limit| parse_count(limit, "LIMIT"))
+        .transpose()?;
+    let offset = query
+        .offset
+        .as_ref()
+        .map(|offset| parse_count(&offset.value, "OFFSET"))
+        .transpose()?
+        .unwrap_or_default();
+
+    let mut input_select = select.clone();
+    input_select.projection = input_items;
+    let mut input_query = query.clone();
+    input_query.body = SetExpr::Select(input_select);
+    input_query.order_by.clear();
+    input_query.limit = None;
+    input_query.offset = None;
+    let input = plan_input(input_query)?;
+
+    Ok(RangeSelect {
+        input,
+        align_millis: range_select.align_millis,
+        by_count: by.len(),
+        aggregates,
+        output,
+        order_by,
+        offset,
+        limit,
+    })
+}
+
+fn invalid<T>(reason: impl Into<String>) -> Result<T> {
+    InvalidRangeQuerySnafu {
+        reason: reason.into(),
+    }
+    .fail()
+    .map_err(Into::into)
+}
+
+/// Returns the name of the function, which is case insensitive unless quoted.
+pub(crate) fn function_name(function: &Function) -> String {
+    match function.name.0.last() {
+        Some(ident) if ident.quote_style.is_some() => ident.value.clone(),
+        Some(ident) => ident.value.to_lowercase(),
+        None => String::new(),
+    }
+}
+
+pub(crate) fn is_column(expr: &Expr, column: &str) -> bool {
+    let ident = match expr {
+        Expr::Identifier(ident) => ident,
+        Expr::CompoundIdentifier(idents) => match idents.last() {
+            Some(ident) => ident,
+            None => return false,
+        },
+        _ => return false,
+    };
+    if ident.quote_style.is_some() {
+        ident.value == column
+    } else {
+        ident.value.eq_ignore_ascii_case(column)
+    }
+}
+
+fn parse_count(expr: &Expr, clause: &str) -> Result<usize> {
+    match expr {
+        Expr::Value(Value::Number(n, _)) => n.parse::<usize>().ok(),
+        _ => None,
+    }
+    .map_or_else(
+        || invalid(format!("{} expects a number, found: {}", clause, expr)),
+        Ok,
+    )
+}
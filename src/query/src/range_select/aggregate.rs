@@ -0,0 +1,193 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The aggregates of DataFusion are not in the function registry, so range queries and
+//! materialized views run the accumulators of the common ones through [BuiltinAggregate].
+
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, Schema as ArrowSchema};
+use common_query::error::{ExecuteFunctionSnafu, FromScalarValueSnafu, Result};
+use common_query::logical_plan::Accumulator;
+use datafusion::physical_plan::aggregates::{self, AggregateFunction};
+use datafusion::physical_plan::expressions::Column;
+use datafusion::physical_plan::Accumulator as DfAccumulator;
+use datafusion_common::ScalarValue;
+use datatypes::prelude::*;
+use snafu::ResultExt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BuiltinAggregate {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl BuiltinAggregate {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(BuiltinAggregate::Count),
+            "sum" => Some(BuiltinAggregate::Sum),
+            "avg" => Some(BuiltinAggregate::Avg),
+            "min" => Some(BuiltinAggregate::Min),
+            "max" => Some(BuiltinAggregate::Max),
+            _ => None,
+        }
+    }
+
+    fn function(&self) -> AggregateFunction {
+        match self {
+            BuiltinAggregate::Count => AggregateFunction::Count,
+            BuiltinAggregate::Sum => AggregateFunction::Sum,
+            BuiltinAggregate::Avg => AggregateFunction::Avg,
+            BuiltinAggregate::Min => AggregateFunction::Min,
+            BuiltinAggregate::Max => AggregateFunction::Max,
+        }
+    }
+
+    /// Returns the output type of the aggregate over the values of `input_type`.
+    pub(crate) fn output_type(&self, input_type: &ConcreteDataType) -> Result<ConcreteDataType> {
+        let output_type = aggregates::return_type(&self.function(), &[input_type.as_arrow_type()])
+            .context(ExecuteFunctionSnafu)?;
+        Ok(ConcreteDataType::from_arrow_type(&output_type))
+    }
+
+    pub(crate) fn create(&self, input_type: &ConcreteDataType) -> Result<Box<dyn Accumulator>> {
+        let schema = ArrowSchema::new(vec![Field::new("arg", input_type.as_arrow_type(), true)]);
+        let accumulator = aggregates::create_aggregate_expr(
+            &self.function(),
+            false,
+            &[Arc::new(Column::new("arg", 0))],
+            &schema,
+            "arg",
+        )
+        .and_then(|expr| expr.create_accumulator())
+        .context(ExecuteFunctionSnafu)?;
+        Ok(Box::new(BuiltinAccumulator { accumulator }))
+    }
+}
+
+/// Runs an accumulator of DataFusion over vectors.
+#[derive(Debug)]
+struct BuiltinAccumulator {
+    accumulator: Box<dyn DfAccumulator>,
+}
+
+fn to_value(value: ScalarValue) -> Result<Value> {
+    Ok(Value::try_from(value).context(FromScalarValueSnafu)?)
+}
+
+impl Accumulator for BuiltinAccumulator {
+    fn state(&self) -> Result<Vec<Value>> {
+        self.accumulator
+            .state()
+            .context(ExecuteFunctionSnafu)?
+            .into_iter()
+            .map(to_value)
+            .collect()
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> Result<()> {
+        let arrays = values
+            .iter()
+            .map(|vector| vector.to_arrow_array())
+            .collect::<Vec<_>>();
+        Ok(self
+            .accumulator
+            .update_batch(&arrays)
+            .context(ExecuteFunctionSnafu)?)
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> Result<()> {
+        let arrays = states
+            .iter()
+            .map(|vector| vector.to_arrow_array())
+            .collect::<Vec<_>>();
+        Ok(self
+            .accumulator
+            .merge_batch(&arrays)
+            .context(ExecuteFunctionSnafu)?)
+    }
+
+    fn evaluate(&self) -> Result<Value> {
+        to_value(self.accumulator.evaluate().context(ExecuteFunctionSnafu)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::{Float64Vector, Int64Vector};
+
+    use super::*;
+
+    fn aggregate(
+        builtin: BuiltinAggregate,
+        input_type: ConcreteDataType,
+        batches: Vec<VectorRef>,
+    ) -> Value {
+        let mut accumulator = builtin.create(&input_type).unwrap();
+        for batch in batches {
+            accumulator.update_batch(&[batch]).unwrap();
+        }
+        accumulator.evaluate().unwrap()
+    }
+
+    #[test]
+    fn test_builtin_aggregate() {
+        let ints = || -> Vec<VectorRef> {
+            vec![
+                Arc::new(Int64Vector::from(vec![Some(1), None, Some(3)])),
+                Arc::new(Int64Vector::from(vec![Some(i64::MAX - 10), Some(-2)])),
+            ]
+        };
+        let int64 = ConcreteDataType::int64_datatype;
+        // Integers are summed without loss of precision.
+        assert_eq!(
+            Value::Int64(i64::MAX - 8),
+            aggregate(BuiltinAggregate::Sum, int64(), ints())
+        );
+        assert_eq!(
+            Value::UInt64(4),
+            aggregate(BuiltinAggregate::Count, int64(), ints())
+        );
+        assert_eq!(
+            Value::Int64(-2),
+            aggregate(BuiltinAggregate::Min, int64(), ints())
+        );
+        assert_eq!(
+            Value::Int64(i64::MAX - 10),
+            aggregate(BuiltinAggregate::Max, int64(), ints())
+        );
+        assert_eq!(
+            int64(),
+            BuiltinAggregate::Max.output_type(&int64()).unwrap()
+        );
+
+        let floats: Vec<VectorRef> = vec![Arc::new(Float64Vector::from_vec(vec![1.0, 2.0]))];
+        assert_eq!(
+            Value::from(1.5),
+            aggregate(
+                BuiltinAggregate::Avg,
+                ConcreteDataType::float64_datatype(),
+                floats
+            )
+        );
+        assert_eq!(
+            Value::Null,
+            aggregate(BuiltinAggregate::Sum, int64(), vec![])
+        );
+    }
+}
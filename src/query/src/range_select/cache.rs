@@ -0,0 +1,314 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reuse of the cached windows of range queries.
+//!
+//! A window `[t, t + range)` is cached once it ends long enough before now, since no more rows
+//! are expected to be written into it. When the query is repeated, the windows it shares with the
+//! cached ones are taken from the cache, and only the rows of the other windows are read.
+//!
+//! The windows are cached with the data versions of the table, as late rows may still be written
+//! into them, so they are only reused until the table is written.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, TimeUnit};
+use common_time::util::current_time_millis;
+use datafusion::logical_plan::{Expr, LogicalPlan as DfLogicalPlan, LogicalPlanBuilder, Operator};
+use datafusion::optimizer::utils::split_conjunction;
+use datafusion_common::{Column, ScalarValue};
+use datatypes::value::Value;
+
+use crate::cache::{self, QueryCacheRef};
+use crate::query_engine::QueryEngineState;
+use crate::range_select::RangeSelect;
+
+/// The values of the aggregates of the windows of a series, by the start time of the windows.
+/// The value of an aggregate is `None` if it has no rows in the window.
+pub(crate) type SeriesWindows = BTreeMap<i64, Vec<Option<Value>>>;
+
+/// The cached windows of a range query.
+#[derive(Debug)]
+pub(crate) struct RangeWindows {
+    /// Start times of the cached windows, the windows absent from `series` have no rows.
+    coverage: Range<i64>,
+    series: BTreeMap<Vec<Value>, SeriesWindows>,
+}
+
+impl RangeWindows {
+    pub(crate) fn estimated_bytes(&self) -> usize {
+        self.series
+            .iter()
+            .map(|(key, windows)| {
+                let values = windows
+                    .values()
+                    .flatten()
+                    .map(|value| value.as_ref().map(value_bytes).unwrap_or_default())
+                    .sum::<usize>();
+                key.iter().map(value_bytes).sum::<usize>()
+                    + windows.len() * std::mem::size_of::<(i64, Vec<Option<Value>>)>()
+                    + values
+            })
+            .sum()
+    }
+}
+
+fn value_bytes(value: &Value) -> usize {
+    std::mem::size_of::<Value>()
+        + match value {
+            Value::String(s) => s.as_utf8().len(),
+            Value::Binary(b) => b.len(),
+            _ => 0,
+        }
+}
+
+/// Reuses the cached windows of a range query, then caches its windows that won't change.
+#[derive(Clone)]
+pub(crate) struct WindowReuse {
+    cache: QueryCacheRef,
+    key: String,
+    /// Start times of the windows taken from the cache.
+    reused: Option<(Range<i64>, Arc<RangeWindows>)>,
+    /// Start times of the windows to cache after the query.
+    cacheable: Range<i64>,
+}
+
+impl fmt::Debug for WindowReuse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WindowReuse")
+            .field("key", &self.key)
+            .field("reused", &self.reused.as_ref().map(|(range, _)| range))
+            .field("cacheable", &self.cacheable)
+            .finish()
+    }
+}
+
+impl WindowReuse {
+    /// Plans the reuse of the cached windows of the optimized `plan`, returns it with the input
+    /// that only reads the rows of the windows not taken from the cache.
+    ///
+    /// Returns `None` if the windows of the plan can't be cached, the windows are only cached if
+    /// the input reads the rows of a table after a lower bound of the time index, which is a
+    /// timestamp in milliseconds or an integer.
+    pub(crate) async fn try_new(
+        state: &QueryEngineState,
+        plan: &RangeSelect,
+    ) -> Option<(Self, DfLogicalPlan)> {
+        let cache = state.query_cache()?;
+        let projection = match &plan.input {
+            DfLogicalPlan::Projection(projection) => projection,
+            _ => return None,
+        };
+        let time = match &projection.expr[0] {
+            Expr::Alias(expr, _) => match expr.as_ref() {
+                Expr::Column(column) => column,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let literal: fn(i64) -> ScalarValue = match projection
+            .input
+            .schema()
+            .field_from_column(time)
+            .ok()?
+            .data_type()
+        {
+            DataType::Timestamp(TimeUnit::Millisecond, None) => {
+                |v| ScalarValue::TimestampMillisecond(Some(v), None)
+            }
+            DataType::Int64 => |v| ScalarValue::Int64(Some(v)),
+            _ => return None,
+        };
+        let mut predicates = Vec::new();
+        let scan = match projection.input.as_ref() {
+            DfLogicalPlan::Filter(filter) => {
+                split_conjunction(&filter.predicate, &mut predicates);
+                filter.input.as_ref()
+            }
+            input => input,
+        };
+        if !matches!(scan, DfLogicalPlan::TableScan(_)) {
+            return None;
+        }
+        // Scanning the only table, whose data can be cached.
+        let (table, versions) = cache::scanned_tables(&plan.input).await?.pop()?;
+
+        // Rows in [lower, upper) are read.
+        let mut lower = None;
+        let mut upper = i64::MAX;
+        let mut filters = Vec::with_capacity(predicates.len());
+        for predicate in predicates {
+            match time_bound(predicate, time) {
+                Some(TimeBound::Lower(v)) => lower = Some(lower.unwrap_or(v).max(v)),
+                Some(TimeBound::Upper(v)) => upper = upper.min(v),
+                None => filters.push(predicate),
+            }
+        }
+        let lower = lower?;
+
+        let align = plan.align_millis;
+        let range = plan.aggregates.iter().map(|a| a.range_millis).max()?;
+        let aggregates = plan
+            .aggregates
+            .iter()
+            .map(|a| (&a.function, &a.args, a.range_millis))
+            .collect::<Vec<_>>();
+        let key = format!(
+            "windows:{}:{:?}:{:?}:{:?}:{}:{}:{:?}",
+            table, versions, projection.expr, filters, align, plan.by_count, aggregates
+        );
+
+        // Windows starting in [first, end) are complete in the rows read.
+        let first = align_up(lower, align)?;
+        let end = upper.saturating_sub(range).saturating_add(1);
+        let immutable_end = upper
+            .min(current_time_millis() - cache.immutable_after_millis())
+            .saturating_sub(range)
+            .saturating_add(1);
+        let reused = cache.get_windows(&key).and_then(|windows| {
+            let reused = windows.coverage.start.max(first)..windows.coverage.end.min(end);
+            // The rows in [reused.start - align + range, reused.end) are skipped.
+            (reused.start < reused.end && reused.start - align + range < reused.end)
+                .then(|| (reused, windows))
+        });
+
+        let input = match &reused {
+            Some((reused, _)) => {
+                let time = Expr::Column(time.clone());
+                let skipped = binary(
+                    time.clone(),
+                    Operator::Lt,
+                    literal(reused.start - align + range),
+                )
+                .or(binary(time, Operator::GtEq, literal(reused.end)));
+                let input = LogicalPlanBuilder::from(projection.input.as_ref().clone())
+                    .filter(skipped)
+                    .ok()?
+                    .project(projection.expr.clone())
+                    .ok()?
+                    .build()
+                    .ok()?;
+                state.df_context().optimize(&input).ok()?
+            }
+            None => plan.input.clone(),
+        };
+
+        let reuse = Self {
+            cache: cache.clone(),
+            key,
+            reused,
+            cacheable: first..immutable_end.max(first),
+        };
+        Some((reuse, input))
+    }
+
+    /// Replaces the windows whose rows are skipped by the cached ones, then caches the windows
+    /// that won't change.
+    pub(crate) fn merge_and_cache(&self, series: &mut BTreeMap<Vec<Value>, SeriesWindows>) {
+        let mut coverage = self.cacheable.clone();
+        if let Some((reused, cached)) = &self.reused {
+            for windows in series.values_mut() {
+                windows.retain(|start, _| !reused.contains(start));
+            }
+            for (key, cached_windows) in &cached.series {
+                let mut cached_windows = cached_windows.range(reused.clone()).peekable();
+                if cached_windows.peek().is_some() {
+                    series
+                        .entry(key.clone())
+                        .or_default()
+                        .extend(cached_windows.map(|(start, values)| (*start, values.clone())));
+                }
+            }
+            series.retain(|_, windows| !windows.is_empty());
+
+            if reused.start <= coverage.end {
+                coverage.end = coverage.end.max(reused.end);
+            }
+        }
+        if coverage.is_empty() {
+            return;
+        }
+
+        let series = series
+            .iter()
+            .filter_map(|(key, windows)| {
+                let windows = windows
+                    .range(coverage.clone())
+                    .map(|(start, values)| (*start, values.clone()))
+                    .collect::<SeriesWindows>();
+                (!windows.is_empty()).then(|| (key.clone(), windows))
+            })
+            .collect();
+        self.cache
+            .insert_windows(self.key.clone(), RangeWindows { coverage, series });
+    }
+}
+
+enum TimeBound {
+    /// The time is at or after the value.
+    Lower(i64),
+    /// The time is before the value.
+    Upper(i64),
+}
+
+/// Returns the bound of the time index given by the `predicate`, `None` if it's not a comparison
+/// between the time index and a literal.
+fn time_bound(predicate: &Expr, time: &Column) -> Option<TimeBound> {
+    let (left, op, right) = match predicate {
+        Expr::BinaryExpr { left, op, right } => (left.as_ref(), *op, right.as_ref()),
+        _ => return None,
+    };
+    let (op, value) = match (left, right) {
+        (Expr::Column(column), Expr::Literal(value)) if column.name == time.name => (op, value),
+        (Expr::Literal(value), Expr::Column(column)) if column.name == time.name => {
+            let op = match op {
+                Operator::Lt => Operator::Gt,
+                Operator::LtEq => Operator::GtEq,
+                Operator::Gt => Operator::Lt,
+                Operator::GtEq => Operator::LtEq,
+                op => op,
+            };
+            (op, value)
+        }
+        _ => return None,
+    };
+    let value = match value {
+        ScalarValue::TimestampMillisecond(Some(v), _) | ScalarValue::Int64(Some(v)) => *v,
+        _ => return None,
+    };
+    match op {
+        Operator::GtEq => Some(TimeBound::Lower(value)),
+        Operator::Gt => value.checked_add(1).map(TimeBound::Lower),
+        Operator::Lt => Some(TimeBound::Upper(value)),
+        Operator::LtEq => value.checked_add(1).map(TimeBound::Upper),
+        _ => None,
+    }
+}
+
+fn binary(left: Expr, op: Operator, right: ScalarValue) -> Expr {
+    Expr::BinaryExpr {
+        left: Box::new(left),
+        op,
+        right: Box::new(Expr::Literal(right)),
+    }
+}
+
+/// Returns the first multiple of `align` at or after `v`.
+fn align_up(v: i64, align: i64) -> Option<i64> {
+    v.checked_add(align - 1)
+        .map(|v| v.div_euclid(align) * align)
+}
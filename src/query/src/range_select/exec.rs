@@ -0,0 +1,572 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::UInt32Array;
+use arrow::compute::take;
+use async_trait::async_trait;
+use common_query::error::Error as PlanError;
+use common_query::logical_plan::{Accumulator, AggregateFunctionCreatorRef};
+use common_query::physical_plan::{
+    DisplayFormatType, Partitioning, PhysicalPlan, PhysicalPlanRef, RuntimeEnv,
+};
+use common_recordbatch::{RecordBatch, RecordBatches, SendableRecordBatchStream};
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datatypes::arrow_array::arrow_array_get;
+use datatypes::error::ArrowComputeSnafu;
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::value::OrderedFloat;
+use datatypes::vectors::Helper;
+use futures::StreamExt;
+use snafu::{ensure, ResultExt};
+use sql::statements::query::Fill;
+
+use crate::error::{Error, InvalidRangeQuerySnafu, Result};
+use crate::query_engine::QueryEngineState;
+use crate::range_select::aggregate::BuiltinAggregate;
+use crate::range_select::cache::{SeriesWindows, WindowReuse};
+use crate::range_select::{fmt_range_select, RangeColumn, RangeSelect};
+
+/// Executes a [RangeSelect] over the rows of its input.
+///
+/// The rows of each series are put into the windows `[t, t + range)` of each aggregate, where `t`
+/// is a multiple of the align interval. Every window with rows gives a row of the output, the
+/// windows without rows between them are only returned if an aggregate is filled.
+///
+/// The input is streamed into the accumulators of the windows, so only the states of the
+/// aggregates are kept in memory, not the rows.
+#[derive(Debug, Clone)]
+pub struct RangeSelectExec {
+    input: PhysicalPlanRef,
+    align_millis: i64,
+    by_count: usize,
+    aggregates: Vec<AggregateExec>,
+    output: Vec<RangeColumn>,
+    order_by: Vec<(usize, bool)>,
+    offset: usize,
+    limit: Option<usize>,
+    schema: SchemaRef,
+    window_reuse: Option<WindowReuse>,
+}
+
+/// Accumulators of the aggregates of the windows of a series, by the start time of the windows.
+/// The accumulator of an aggregate is `None` if it has no rows in the window.
+type WindowAccumulators = BTreeMap<i64, Vec<Option<Box<dyn Accumulator>>>>;
+
+#[derive(Debug, Clone)]
+struct AggregateExec {
+    name: String,
+    function: RangeFunction,
+    args: Vec<usize>,
+    input_types: Vec<ConcreteDataType>,
+    output_type: ConcreteDataType,
+    range_millis: i64,
+    fill: Option<Fill>,
+}
+
+#[derive(Debug, Clone)]
+enum RangeFunction {
+    Builtin(BuiltinAggregate),
+    Registered(AggregateFunctionCreatorRef),
+}
+
+impl AggregateExec {
+    fn create_accumulator(&self) -> common_query::error::Result<Box<dyn Accumulator>> {
+        match &self.function {
+            RangeFunction::Builtin(builtin) => builtin.create(&self.input_types[0]),
+            RangeFunction::Registered(creator) => creator.creator()(&self.input_types),
+        }
+    }
+}
+
+impl RangeSelectExec {
+    /// Creates the execution of `plan` over `input`, the aggregates are looked up in the `state`.
+    pub(crate) fn try_new(
+        plan: &RangeSelect,
+        input: PhysicalPlanRef,
+        state: &QueryEngineState,
+    ) -> Result<Self> {
+        let input_schema = input.schema();
+        let column_types = input_schema
+            .column_schemas()
+            .iter()
+            .map(|column| column.data_type.clone())
+            .collect::<Vec<_>>();
+        let time_type = if column_types[0].is_timestamp() {
+            ConcreteDataType::timestamp_millis_datatype()
+        } else if column_types[0].is_signed() || column_types[0].is_unsigned() {
+            ConcreteDataType::int64_datatype()
+        } else {
+            return InvalidRangeQuerySnafu {
+                reason: format!(
+                    "the time index must be a timestamp or an integer, found: {:?}",
+                    column_types[0]
+                ),
+            }
+            .fail()
+            .map_err(Into::into);
+        };
+
+        let mut aggregates = Vec::with_capacity(plan.aggregates.len());
+        for aggregate in &plan.aggregates {
+            let input_types = aggregate
+                .args
+                .iter()
+                .map(|arg| column_types[*arg].clone())
+                .collect::<Vec<_>>();
+            let (function, output_type) = match BuiltinAggregate::from_name(&aggregate.function) {
+                Some(builtin) => {
+                    ensure!(
+                        input_types.len() == 1,
+                        InvalidRangeQuerySnafu {
+                            reason: format!("{} expects 1 argument", aggregate.function),
+                        }
+                    );
+                    let output_type = builtin.output_type(&input_types[0]).map_err(Error::new)?;
+                    (RangeFunction::Builtin(builtin), output_type)
+                }
+                None => {
+                    let meta = match state.aggregate_function(&aggregate.function) {
+                        Some(meta) => meta,
+                        None => {
+                            return InvalidRangeQuerySnafu {
+                                reason: format!(
+                                    "unknown aggregate function: {}",
+                                    aggregate.function
+                                ),
+                            }
+                            .fail()
+                            .map_err(Into::into)
+                        }
+                    };
+                    ensure!(
+                        input_types.len() == meta.args_count() as usize,
+                        InvalidRangeQuerySnafu {
+                            reason: format!(
+                                "{} expects {} arguments",
+                                aggregate.function,
+                                meta.args_count()
+                            ),
+                        }
+                    );
+                    let creator = meta.create();
+                    creator
+                        .set_input_types(input_types.clone())
+                        .map_err(Error::new)?;
+                    let output_type = creator.output_type().map_err(Error::new)?;
+                    (RangeFunction::Registered(creator), output_type)
+                }
+            };
+            aggregates.push(AggregateExec {
+                name: aggregate.function.clone(),
+                function,
+                args: aggregate.args.clone(),
+                input_types,
+                output_type,
+                range_millis: aggregate.range_millis,
+                fill: aggregate.fill.clone(),
+            });
+        }
+
+        let column_schemas = plan
+            .output
+            .iter()
+            .map(|output| {
+                let (data_type, nullable) = match output.column {
+                    RangeColumn::Time => (time_type.clone(), false),
+                    RangeColumn::By(i) => (column_types[1 + i].clone(), true),
+                    RangeColumn::Aggregate(i) => (aggregates[i].output_type.clone(), true),
+                };
+                ColumnSchema::new(&output.name, data_type, nullable)
+            })
+            .collect();
+
+        Ok(Self {
+            input,
+            align_millis: plan.align_millis,
+            by_count: plan.by_count,
+            aggregates,
+            output: plan.output.iter().map(|output| output.column).collect(),
+            order_by: plan.order_by.clone(),
+            offset: plan.offset,
+            limit: plan.limit,
+            schema: Arc::new(Schema::new(column_schemas)),
+            window_reuse: None,
+        })
+    }
+
+    pub fn input(&self) -> PhysicalPlanRef {
+        self.input.clone()
+    }
+
+    pub(crate) fn with_window_reuse(mut self, window_reuse: WindowReuse) -> Self {
+        self.window_reuse = Some(window_reuse);
+        self
+    }
+
+    /// Updates the accumulators of the windows with the rows of the batch, the rows with a null
+    /// time are ignored.
+    fn update_windows(
+        &self,
+        series: &mut BTreeMap<Vec<Value>, WindowAccumulators>,
+        batch: &RecordBatch,
+    ) -> common_query::error::Result<()> {
+        let arrays = batch.df_recordbatch.columns();
+        let value = |column: usize, row: usize| {
+            arrow_array_get(arrays[column].as_ref(), row).map_err(PlanError::new)
+        };
+
+        // The series of the rows, as indices into `keys`.
+        let mut keys = Vec::new();
+        let mut key_indices = BTreeMap::new();
+        let mut rows = Vec::with_capacity(batch.num_rows());
+        for row in 0..batch.num_rows() {
+            let ts = match time_millis(&value(0, row)?) {
+                Some(ts) => ts,
+                None => continue,
+            };
+            let key = (1..1 + self.by_count)
+                .map(|column| value(column, row))
+                .collect::<common_query::error::Result<Vec<_>>>()?;
+            let key_index = *key_indices.entry(key.clone()).or_insert_with(|| {
+                keys.push(key);
+                keys.len() - 1
+            });
+            rows.push((row as u32, key_index, ts));
+        }
+
+        for (j, aggregate) in self.aggregates.iter().enumerate() {
+            // (series, window start) to the rows in the window.
+            let mut windows = BTreeMap::<(usize, i64), Vec<u32>>::new();
+            for (row, key_index, ts) in &rows {
+                let mut start = (ts - aggregate.range_millis).div_euclid(self.align_millis)
+                    * self.align_millis
+                    + self.align_millis;
+                while start <= *ts {
+                    windows.entry((*key_index, start)).or_default().push(*row);
+                    start += self.align_millis;
+                }
+            }
+
+            for ((key_index, start), window) in windows {
+                let indices = UInt32Array::from_vec(window);
+                let args = aggregate
+                    .args
+                    .iter()
+                    .map(|arg| {
+                        let array = take::take(arrays[*arg].as_ref(), &indices)
+                            .context(ArrowComputeSnafu)
+                            .map_err(PlanError::new)?;
+                        Helper::try_into_vector(array).map_err(PlanError::new)
+                    })
+                    .collect::<common_query::error::Result<Vec<_>>>()?;
+
+                if !series.contains_key(&keys[key_index]) {
+                    series.insert(keys[key_index].clone(), WindowAccumulators::new());
+                }
+                let accumulators = series
+                    .get_mut(&keys[key_index])
+                    .unwrap()
+                    .entry(start)
+                    .or_insert_with(|| (0..self.aggregates.len()).map(|_| None).collect());
+                if accumulators[j].is_none() {
+                    accumulators[j] = Some(aggregate.create_accumulator()?);
+                }
+                accumulators[j].as_mut().unwrap().update_batch(&args)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the accumulators of the windows of a series.
+    fn evaluate_windows(
+        &self,
+        accumulators: WindowAccumulators,
+    ) -> common_query::error::Result<SeriesWindows> {
+        accumulators
+            .into_iter()
+            .map(|(start, accumulators)| {
+                let values = accumulators
+                    .iter()
+                    .map(|accumulator| accumulator.as_ref().map(|a| a.evaluate()).transpose())
+                    .collect::<common_query::error::Result<Vec<_>>>()?;
+                Ok((start, values))
+            })
+            .collect()
+    }
+
+    /// Returns the output rows of the windows of the series.
+    fn series_rows(&self, key: &[Value], windows: &SeriesWindows) -> Vec<Vec<Value>> {
+        let filled = self.aggregates.iter().any(|a| a.fill.is_some());
+        let times = match (filled, windows.keys().next(), windows.keys().next_back()) {
+            (true, Some(first), Some(last)) => (*first..=*last)
+                .step_by(self.align_millis as usize)
+                .collect::<Vec<_>>(),
+            _ => windows.keys().copied().collect(),
+        };
+
+        let columns = self
+            .aggregates
+            .iter()
+            .enumerate()
+            .map(|(j, aggregate)| {
+                let values = times
+                    .iter()
+                    .map(|time| windows.get(time).and_then(|values| values[j].clone()))
+                    .collect();
+                fill_values(
+                    &times,
+                    values,
+                    aggregate.fill.as_ref(),
+                    &aggregate.output_type,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let time_is_timestamp =
+            self.schema
+                .column_schemas()
+                .iter()
+                .zip(&self.output)
+                .any(|(column, output)| {
+                    *output == RangeColumn::Time && column.data_type.is_timestamp()
+                });
+        times
+            .iter()
+            .enumerate()
+            .map(|(i, time)| {
+                self.output
+                    .iter()
+                    .map(|output| match output {
+                        RangeColumn::Time if time_is_timestamp => {
+                            Value::Timestamp(Timestamp::from_millis(*time))
+                        }
+                        RangeColumn::Time => Value::Int64(*time),
+                        RangeColumn::By(j) => key[*j].clone(),
+                        RangeColumn::Aggregate(j) => columns[*j][i].clone(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Fills the values of the windows without rows.
+fn fill_values(
+    times: &[i64],
+    values: Vec<Option<Value>>,
+    fill: Option<&Fill>,
+    output_type: &ConcreteDataType,
+) -> Vec<Value> {
+    match fill {
+        None | Some(Fill::Null) => values
+            .into_iter()
+            .map(|value| value.unwrap_or(Value::Null))
+            .collect(),
+        Some(Fill::Prev) => {
+            let mut prev = Value::Null;
+            values
+                .into_iter()
+                .map(|value| {
+                    if let Some(value) = value {
+                        prev = value;
+                    }
+                    prev.clone()
+                })
+                .collect()
+        }
+        Some(Fill::Linear) => {
+            let points = times
+                .iter()
+                .zip(&values)
+                .filter_map(|(time, value)| {
+                    value
+                        .as_ref()
+                        .and_then(Value::as_f64_lossy)
+                        .map(|value| (*time, value))
+                })
+                .collect::<Vec<_>>();
+            times
+                .iter()
+                .zip(values)
+                .map(|(time, value)| {
+                    if let Some(value) = value {
+                        return value;
+                    }
+                    let next = points.partition_point(|(t, _)| t < time);
+                    if next == 0 || next == points.len() {
+                        return Value::Null;
+                    }
+                    let (t0, v0) = points[next - 1];
+                    let (t1, v1) = points[next];
+                    let v = v0 + (v1 - v0) * (time - t0) as f64 / (t1 - t0) as f64;
+                    f64_to_value(v, output_type)
+                })
+                .collect()
+        }
+        Some(Fill::Const(n)) => {
+            let constant = n
+                .parse::<f64>()
+                .map(|n| f64_to_value(n, output_type))
+                .unwrap_or(Value::Null);
+            values
+                .into_iter()
+                .map(|value| value.unwrap_or_else(|| constant.clone()))
+                .collect()
+        }
+    }
+}
+
+/// Converts the filled value to the output type of the aggregate, `Null` if it is not numeric.
+fn f64_to_value(v: f64, data_type: &ConcreteDataType) -> Value {
+    match data_type {
+        ConcreteDataType::UInt8(_) => Value::UInt8(v as u8),
+        ConcreteDataType::UInt16(_) => Value::UInt16(v as u16),
+        ConcreteDataType::UInt32(_) => Value::UInt32(v as u32),
+        ConcreteDataType::UInt64(_) => Value::UInt64(v as u64),
+        ConcreteDataType::Int8(_) => Value::Int8(v as i8),
+        ConcreteDataType::Int16(_) => Value::Int16(v as i16),
+        ConcreteDataType::Int32(_) => Value::Int32(v as i32),
+        ConcreteDataType::Int64(_) => Value::Int64(v as i64),
+        ConcreteDataType::Float32(_) => Value::Float32(OrderedFloat(v as f32)),
+        ConcreteDataType::Float64(_) => Value::Float64(OrderedFloat(v)),
+        _ => Value::Null,
+    }
+}
+
+pub(crate) fn time_millis(value: &Value) -> Option<i64> {
+    match value {
+        Value::Timestamp(ts) => Some(ts.convert_to(TimeUnit::Millisecond)),
+        value => value.as_f64_lossy().map(|v| v as i64),
+    }
+}
+
+#[async_trait]
+impl PhysicalPlan for RangeSelectExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn children(&self) -> Vec<PhysicalPlanRef> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<PhysicalPlanRef>,
+    ) -> common_query::error::Result<PhysicalPlanRef> {
+        let mut plan = self.clone();
+        plan.input = children[0].clone();
+        Ok(Arc::new(plan))
+    }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RangeSelectExec: ")?;
+        fmt_range_select(
+            f,
+            self.align_millis,
+            self.by_count,
+            self.aggregates.iter().map(|aggregate| {
+                (
+                    aggregate.name.as_str(),
+                    aggregate.args.as_slice(),
+                    aggregate.range_millis,
+                    aggregate.fill.as_ref(),
+                )
+            }),
+        )
+    }
+
+    async fn execute(
+        &self,
+        _partition: usize,
+        runtime: Arc<RuntimeEnv>,
+    ) -> common_query::error::Result<SendableRecordBatchStream> {
+        let mut series = BTreeMap::new();
+        for partition in 0..self.input.output_partitioning().partition_count() {
+            let mut stream = self.input.execute(partition, runtime.clone()).await?;
+            while let Some(batch) = stream.next().await {
+                let batch = batch.map_err(PlanError::new)?;
+                self.update_windows(&mut series, &batch)?;
+            }
+        }
+
+        let mut windows = BTreeMap::new();
+        for (key, accumulators) in series {
+            windows.insert(key, self.evaluate_windows(accumulators)?);
+        }
+        if let Some(window_reuse) = &self.window_reuse {
+            window_reuse.merge_and_cache(&mut windows);
+        }
+
+        let mut rows = Vec::new();
+        for (key, windows) in &windows {
+            rows.extend(self.series_rows(key, windows));
+        }
+        if !self.order_by.is_empty() {
+            rows.sort_by(|a, b| {
+                self.order_by
+                    .iter()
+                    .map(|(i, asc)| {
+                        let ordering = a[*i].cmp(&b[*i]);
+                        if *asc {
+                            ordering
+                        } else {
+                            ordering.reverse()
+                        }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        let rows = rows
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+
+        let columns = self
+            .schema
+            .column_schemas()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let mut builder =
+                    VectorBuilder::with_capacity(column.data_type.clone(), rows.len());
+                for row in &rows {
+                    builder.push(&row[i]);
+                }
+                builder.finish()
+            })
+            .collect::<Vec<_>>();
+        let batch = RecordBatch::new(self.schema.clone(), columns).map_err(PlanError::new)?;
+        let batches =
+            RecordBatches::try_new(self.schema.clone(), vec![batch]).map_err(PlanError::new)?;
+        Ok(batches.as_stream())
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use catalog::local::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
+use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_query::Output;
+use common_recordbatch::{util, RecordBatch};
+use common_time::Timestamp;
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{Float64Vector, StringVector, TimestampVector};
+use query::cache::QueryCacheOptions;
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use store_api::storage::DataVersion;
+use table::metadata::TableInfoRef;
+use table::test_util::MemTable;
+use table::Table;
+
+/// A table whose data can be replaced, with the version of its data.
+struct VersionedTable {
+    inner: RwLock<Arc<MemTable>>,
+    version: AtomicU64,
+    scans: AtomicUsize,
+}
+
+impl VersionedTable {
+    fn new(rows: &[(i64, &str, f64)]) -> Self {
+        Self {
+            inner: RwLock::new(Arc::new(mem_table(rows))),
+            version: AtomicU64::new(0),
+            scans: AtomicUsize::new(0),
+        }
+    }
+
+    fn inner(&self) -> Arc<MemTable> {
+        self.inner.read().unwrap().clone()
+    }
+
+    fn replace(&self, rows: &[(i64, &str, f64)]) {
+        self.replace_keeping_version(rows);
+        let _ = self.version.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Replaces the rows without changing the version, to tell the cached values from the
+    /// values read from the table.
+    fn replace_keeping_version(&self, rows: &[(i64, &str, f64)]) {
+        *self.inner.write().unwrap() = Arc::new(mem_table(rows));
+    }
+
+    fn scans(&self) -> usize {
+        self.scans.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl Table for VersionedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.inner().schema()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.inner().table_info()
+    }
+
+    async fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> table::Result<PhysicalPlanRef> {
+        let _ = self.scans.fetch_add(1, Ordering::Relaxed);
+        self.inner().scan(projection, filters, limit).await
+    }
+
+    async fn data_versions(&self) -> Option<Vec<DataVersion>> {
+        Some(vec![DataVersion {
+            manifest_version: 0,
+            committed_sequence: self.version.load(Ordering::Relaxed),
+        }])
+    }
+}
+
+fn mem_table(rows: &[(i64, &str, f64)]) -> MemTable {
+    let column_schemas = vec![
+        ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+            .with_time_index(true),
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+    ];
+    let schema = Arc::new(Schema::new(column_schemas));
+    let columns: Vec<VectorRef> = vec![
+        Arc::new(TimestampVector::from_values(
+            rows.iter().map(|row| row.0).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringVector::from(
+            rows.iter().map(|row| row.1).collect::<Vec<_>>(),
+        )),
+        Arc::new(Float64Vector::from_vec(
+            rows.iter().map(|row| row.2).collect::<Vec<_>>(),
+        )),
+    ];
+    MemTable::new("cpu", RecordBatch::new(schema, columns).unwrap())
+}
+
+fn create_engine(table: Arc<VersionedTable>) -> QueryEngineRef {
+    let schema_provider = Arc::new(MemorySchemaProvider::new());
+    let catalog_provider = Arc::new(MemoryCatalogProvider::new());
+    let catalog_list = Arc::new(MemoryCatalogManager::default());
+    schema_provider
+        .register_table("cpu".to_string(), table)
+        .unwrap();
+    catalog_provider
+        .register_schema(DEFAULT_SCHEMA_NAME.to_string(), schema_provider)
+        .unwrap();
+    catalog_list
+        .register_catalog(DEFAULT_CATALOG_NAME.to_string(), catalog_provider)
+        .unwrap();
+
+    let options = QueryCacheOptions {
+        capacity: 1024 * 1024,
+        immutable_after_millis: 0,
+    };
+    query::QueryEngineFactory::new_with_cache(catalog_list, &options).query_engine()
+}
+
+async fn execute(engine: &QueryEngineRef, sql: &str) -> Vec<Vec<Value>> {
+    let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
+    let stream = match engine.execute(&plan).await.unwrap() {
+        Output::Stream(stream) => stream,
+        _ => unreachable!(),
+    };
+    util::collect(stream)
+        .await
+        .unwrap()
+        .iter()
+        .flat_map(|batch| batch.rows().map(|row| row.unwrap()).collect::<Vec<_>>())
+        .collect()
+}
+
+fn rows(expected: &[(i64, &str, f64)]) -> Vec<Vec<Value>> {
+    expected
+        .iter()
+        .map(|(t, host, v)| {
+            vec![
+                Value::Timestamp(Timestamp::from_millis(*t)),
+                Value::from(*host),
+                Value::from(*v),
+            ]
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_cache_results() {
+    let table = Arc::new(VersionedTable::new(&[
+        (0, "a", 1.0),
+        (1, "b", 2.0),
+        (2, "a", 3.0),
+    ]));
+    let engine = create_engine(table.clone());
+    let sql = "SELECT ts, host, cpu FROM cpu WHERE host = 'a' ORDER BY ts";
+
+    let expected = rows(&[(0, "a", 1.0), (2, "a", 3.0)]);
+    assert_eq!(expected, execute(&engine, sql).await);
+    assert_eq!(1, table.scans());
+    // Hits the cache.
+    assert_eq!(expected, execute(&engine, sql).await);
+    assert_eq!(1, table.scans());
+
+    // The results are invalidated by writes.
+    table.replace(&[(0, "a", 1.0), (3, "a", 4.0)]);
+    let expected = rows(&[(0, "a", 1.0), (3, "a", 4.0)]);
+    assert_eq!(expected, execute(&engine, sql).await);
+    assert_eq!(2, table.scans());
+    assert_eq!(expected, execute(&engine, sql).await);
+    assert_eq!(2, table.scans());
+
+    // Queries calling volatile functions are not cached.
+    let sql = "SELECT ts, host, cpu FROM cpu WHERE ts < now()";
+    let _ = execute(&engine, sql).await;
+    let _ = execute(&engine, sql).await;
+    assert_eq!(4, table.scans());
+}
+
+#[tokio::test]
+async fn test_reuse_range_windows() {
+    let table = Arc::new(VersionedTable::new(&[
+        (0, "a", 1.0),
+        (60_000, "a", 2.0),
+        (120_000, "a", 3.0),
+        (180_000, "a", 4.0),
+    ]));
+    let engine = create_engine(table.clone());
+    let sql = |from: i64, to: i64| {
+        format!(
+            "SELECT ts, host, max(cpu) RANGE '1m' FROM cpu WHERE ts >= {} AND ts < {} ALIGN '1m' BY (host)",
+            from, to
+        )
+    };
+
+    assert_eq!(
+        rows(&[
+            (0, "a", 1.0),
+            (60_000, "a", 2.0),
+            (120_000, "a", 3.0),
+            (180_000, "a", 4.0),
+        ]),
+        execute(&engine, &sql(0, 240_000)).await
+    );
+
+    // The windows in [60s, 240s) are taken from the cache, so the new values of their rows are
+    // not read.
+    let new_rows = [
+        (0, "a", 10.0),
+        (60_000, "a", 20.0),
+        (120_000, "a", 30.0),
+        (180_000, "a", 40.0),
+        (240_000, "a", 50.0),
+    ];
+    table.replace_keeping_version(&new_rows);
+    assert_eq!(
+        rows(&[
+            (60_000, "a", 2.0),
+            (120_000, "a", 3.0),
+            (180_000, "a", 4.0),
+            (240_000, "a", 50.0),
+        ]),
+        execute(&engine, &sql(60_000, 300_000)).await
+    );
+
+    // The cached windows are invalidated by writes, which may be late rows of the windows.
+    table.replace(&new_rows);
+    assert_eq!(
+        rows(&[
+            (60_000, "a", 20.0),
+            (120_000, "a", 30.0),
+            (180_000, "a", 40.0),
+            (240_000, "a", 50.0),
+        ]),
+        execute(&engine, &sql(60_000, 300_000)).await
+    );
+}
@@ -0,0 +1,210 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use catalog::local::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
+use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_query::Output;
+use common_recordbatch::{util, RecordBatch};
+use common_time::Timestamp;
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{Float64Vector, StringVector, TimestampVector};
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use table::test_util::MemTable;
+
+fn create_engine() -> QueryEngineRef {
+    let column_schemas = vec![
+        ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+            .with_time_index(true),
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+    ];
+    let schema = Arc::new(Schema::new(column_schemas));
+    let columns: Vec<VectorRef> = vec![
+        Arc::new(TimestampVector::from_values(vec![
+            0, 60_000, 180_000, 0, 60_000,
+        ])),
+        Arc::new(StringVector::from(vec!["a", "a", "a", "b", "b"])),
+        Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 4.0, 10.0, 20.0])),
+    ];
+    let recordbatch = RecordBatch::new(schema, columns).unwrap();
+    let table = Arc::new(MemTable::new("cpu", recordbatch));
+
+    let schema_provider = Arc::new(MemorySchemaProvider::new());
+    let catalog_provider = Arc::new(MemoryCatalogProvider::new());
+    let catalog_list = Arc::new(MemoryCatalogManager::default());
+    schema_provider
+        .register_table("cpu".to_string(), table)
+        .unwrap();
+    catalog_provider
+        .register_schema(DEFAULT_SCHEMA_NAME.to_string(), schema_provider)
+        .unwrap();
+    catalog_list
+        .register_catalog(DEFAULT_CATALOG_NAME.to_string(), catalog_provider)
+        .unwrap();
+
+    query::QueryEngineFactory::new(catalog_list).query_engine()
+}
+
+async fn execute(engine: &QueryEngineRef, sql: &str) -> Vec<Vec<Value>> {
+    let plan = engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
+    let stream = match engine.execute(&plan).await.unwrap() {
+        Output::Stream(stream) => stream,
+        _ => unreachable!(),
+    };
+    util::collect(stream)
+        .await
+        .unwrap()
+        .iter()
+        .flat_map(|batch| batch.rows().map(|row| row.unwrap()).collect::<Vec<_>>())
+        .collect()
+}
+
+fn ts(millis: i64) -> Value {
+    Value::Timestamp(Timestamp::from_millis(millis))
+}
+
+fn float(v: Option<f64>) -> Value {
+    v.map(Value::from).unwrap_or(Value::Null)
+}
+
+fn rows(expected: &[(i64, &str, Option<f64>)]) -> Vec<Vec<Value>> {
+    expected
+        .iter()
+        .map(|(t, host, v)| vec![ts(*t), Value::from(*host), float(*v)])
+        .collect()
+}
+
+#[tokio::test]
+async fn test_range_select() {
+    let engine = create_engine();
+
+    let result = execute(
+        &engine,
+        "SELECT ts, host, avg(cpu) RANGE '2m' FROM cpu ALIGN '1m' BY (host)",
+    )
+    .await;
+    assert_eq!(
+        rows(&[
+            (-60_000, "a", Some(1.0)),
+            (0, "a", Some(1.5)),
+            (60_000, "a", Some(2.0)),
+            (120_000, "a", Some(4.0)),
+            (180_000, "a", Some(4.0)),
+            (-60_000, "b", Some(10.0)),
+            (0, "b", Some(15.0)),
+            (60_000, "b", Some(20.0)),
+        ]),
+        result
+    );
+
+    // Aggregates in the function registry.
+    let result = execute(
+        &engine,
+        "SELECT ts, host, mean(cpu) RANGE '1m' FROM cpu WHERE host = 'b' ALIGN '1m' BY (host)",
+    )
+    .await;
+    assert_eq!(
+        rows(&[(0, "b", Some(10.0)), (60_000, "b", Some(20.0))]),
+        result
+    );
+
+    let result = execute(
+        &engine,
+        "SELECT ts, count(*) RANGE '1m' AS c FROM cpu ALIGN '1m' BY () ORDER BY c DESC, ts LIMIT 2",
+    )
+    .await;
+    assert_eq!(
+        vec![
+            vec![ts(0), Value::from(2u64)],
+            vec![ts(60_000), Value::from(2u64)],
+        ],
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_range_select_fill() {
+    let engine = create_engine();
+    let sql = |fill: &str| {
+        format!(
+            "SELECT ts, host, max(cpu) RANGE '1m' FROM cpu WHERE host = 'a' ALIGN '1m' BY (host) {}",
+            fill
+        )
+    };
+
+    let result = execute(&engine, &sql("")).await;
+    assert_eq!(
+        rows(&[
+            (0, "a", Some(1.0)),
+            (60_000, "a", Some(2.0)),
+            (180_000, "a", Some(4.0)),
+        ]),
+        result
+    );
+
+    for (fill, filled) in [
+        ("FILL NULL", None),
+        ("FILL PREV", Some(2.0)),
+        ("FILL LINEAR", Some(3.0)),
+        ("FILL -1", Some(-1.0)),
+    ] {
+        let result = execute(&engine, &sql(fill)).await;
+        assert_eq!(
+            rows(&[
+                (0, "a", Some(1.0)),
+                (60_000, "a", Some(2.0)),
+                (120_000, "a", filled),
+                (180_000, "a", Some(4.0)),
+            ]),
+            result,
+            "{}",
+            fill
+        );
+    }
+
+    // The fill of the aggregate overrides the one of ALIGN.
+    let result = execute(
+        &engine,
+        "SELECT ts, max(cpu) RANGE '1m' FILL 0 FROM cpu WHERE host = 'a' ALIGN '1m' FILL PREV",
+    )
+    .await;
+    assert_eq!(vec![ts(120_000), float(Some(0.0))], result[2]);
+}
+
+#[tokio::test]
+async fn test_invalid_range_select() {
+    let engine = create_engine();
+    let plan = |sql| {
+        engine
+            .sql_to_plan(sql, QueryContext::arc())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert!(
+        plan("SELECT cpu, avg(cpu) RANGE '1m' FROM cpu ALIGN '1m' BY (host)")
+            .contains("cpu must be an aggregate with RANGE, the time index or an expression in BY")
+    );
+    assert!(
+        plan("SELECT avg(cpu) RANGE '1m' FROM cpu GROUP BY host ALIGN '1m'")
+            .contains("GROUP BY, HAVING and DISTINCT are not allowed in a range query")
+    );
+    assert!(plan("SELECT avg(cpu) RANGE '1m' FROM not_exist ALIGN '1m'")
+        .contains("Table not found: not_exist"));
+}
@@ -15,6 +15,7 @@
 //! Script engine
 
 use std::any::Any;
+use std::collections::HashMap;
 
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
@@ -53,7 +54,12 @@ pub trait ScriptEngine {
 
 /// Evalute script context
 #[derive(Debug, Default)]
-pub struct EvalContext {}
+pub struct EvalContext {
+    /// Values of the `$name` placeholders in the sql of the script.
+    pub params: HashMap<String, String>,
+    /// Schema the sql of the script runs in, the default schema if absent.
+    pub schema: Option<String>,
+}
 
 /// Compile script context
 #[derive(Debug, Default)]
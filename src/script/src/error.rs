@@ -15,7 +15,7 @@
 use std::any::Any;
 
 use common_error::ext::ErrorExt;
-use common_error::prelude::{Snafu, StatusCode};
+use common_error::prelude::{BoxedError, Snafu, StatusCode};
 use snafu::{Backtrace, ErrorCompat};
 
 #[derive(Debug, Snafu)]
@@ -71,6 +71,73 @@ pub enum Error {
         source: query::error::Error,
     },
 
+    #[snafu(display("Failed to find runs of script {}, source: {}", name, source))]
+    FindScriptRuns {
+        name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
+    #[snafu(display("Failed to find scripts, source: {}", source))]
+    FindScripts {
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
+    #[snafu(display("Failed to register script {} as udf, source: {}", name, source))]
+    RegisterUdf {
+        name: String,
+        #[snafu(backtrace)]
+        source: crate::python::error::Error,
+    },
+
+    #[snafu(display("Failed to register script runs table, source: {}", source))]
+    RegisterScriptRunsTable {
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Failed to find table {}, source: {}", table, source))]
+    FindTable {
+        table: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Table not found: {}", table))]
+    TableNotFound { table: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Failed to insert run of script {} to script runs table, source: {}",
+        name,
+        source
+    ))]
+    InsertScriptRun {
+        name: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to insert results of script {} into table {}, source: {}",
+        name,
+        table,
+        source
+    ))]
+    InsertScheduledResult {
+        name: String,
+        table: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to convert column {} into vector, source: {}", column, source))]
+    ConvertColumn {
+        column: String,
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display("Failed to collect record batch, source: {}", source))]
     CollectRecords {
         #[snafu(backtrace)]
@@ -79,6 +146,18 @@ pub enum Error {
 
     #[snafu(display("Failed to cast type, msg: {}", msg))]
     CastType { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid script name: {}, it must not contain '.'", name))]
+    InvalidScriptName { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Scheduled script {} is not supported in distributed mode", name))]
+    ScheduleNotSupported { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to access scripts in metasrv, source: {}", source))]
+    AccessMetaScripts {
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -88,13 +167,25 @@ impl ErrorExt for Error {
         use Error::*;
         match self {
             CastType { .. } => StatusCode::Unexpected,
-            ScriptsTableNotFound { .. } => StatusCode::TableNotFound,
-            RegisterScriptsTable { source } | FindScriptsTable { source } => source.status_code(),
-            InsertScript { source, .. } => source.status_code(),
-            CompilePython { source, .. } | ExecutePython { source, .. } => source.status_code(),
-            FindScript { source, .. } => source.status_code(),
+            ScriptsTableNotFound { .. } | TableNotFound { .. } => StatusCode::TableNotFound,
+            RegisterScriptsTable { source }
+            | FindScriptsTable { source }
+            | RegisterScriptRunsTable { source }
+            | FindTable { source, .. }
+            | AccessMetaScripts { source } => source.status_code(),
+            InsertScript { source, .. }
+            | InsertScriptRun { source, .. }
+            | InsertScheduledResult { source, .. } => source.status_code(),
+            ConvertColumn { source, .. } => source.status_code(),
+            CompilePython { source, .. }
+            | ExecutePython { source, .. }
+            | RegisterUdf { source, .. } => source.status_code(),
+            FindScript { source, .. } | FindScriptRuns { source, .. } | FindScripts { source } => {
+                source.status_code()
+            }
             CollectRecords { source } => source.status_code(),
-            ScriptNotFound { .. } => StatusCode::InvalidArguments,
+            ScriptNotFound { .. } | InvalidScriptName { .. } => StatusCode::InvalidArguments,
+            ScheduleNotSupported { .. } => StatusCode::Unsupported,
         }
     }
 
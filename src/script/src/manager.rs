@@ -19,88 +19,154 @@ use std::sync::{Arc, RwLock};
 use catalog::CatalogManagerRef;
 use common_query::Output;
 use common_telemetry::logging;
-use query::QueryEngineRef;
-use snafu::{OptionExt, ResultExt};
+use query::{schema_function_name, QueryEngineRef};
+use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
-use crate::error::{CompilePythonSnafu, ExecutePythonSnafu, Result, ScriptNotFoundSnafu};
+use crate::error::{
+    CompilePythonSnafu, ExecutePythonSnafu, InvalidScriptNameSnafu, RegisterUdfSnafu, Result,
+    ScriptNotFoundSnafu,
+};
 use crate::python::{PyEngine, PyScript};
-use crate::table::ScriptsTable;
+use crate::scheduler::{InsertHandlerRef, ScriptScheduler};
+use crate::table::{parse_script_key, script_key, ScriptRunsTable, ScriptsTable};
 
 pub struct ScriptManager {
+    /// Compiled scripts by their keys in the scripts table.
     compiled: RwLock<HashMap<String, Arc<PyScript>>>,
     py_engine: PyEngine,
     table: ScriptsTable,
+    scheduler: ScriptScheduler,
 }
 
 impl ScriptManager {
     pub async fn new(
         catalog_manager: CatalogManagerRef,
         query_engine: QueryEngineRef,
+        insert_handler: InsertHandlerRef,
     ) -> Result<Self> {
+        let runs_table =
+            Arc::new(ScriptRunsTable::new(catalog_manager.clone(), query_engine.clone()).await?);
         Ok(Self {
             compiled: RwLock::new(HashMap::default()),
             py_engine: PyEngine::new(query_engine.clone()),
             table: ScriptsTable::new(catalog_manager, query_engine).await?,
+            scheduler: ScriptScheduler::new(insert_handler, runs_table),
         })
     }
 
     async fn compile(&self, name: &str, script: &str) -> Result<Arc<PyScript>> {
-        let script = Arc::new(
-            self.py_engine
-                .compile(script, CompileContext::default())
-                .await
-                .context(CompilePythonSnafu { name })?,
-        );
+        let script = self
+            .py_engine
+            .compile(script, CompileContext::default())
+            .await
+            .context(CompilePythonSnafu { name })?;
+        Ok(Arc::new(script))
+    }
+
+    /// Registers the compiled script `name` of the `schema` as a SQL function of the schema if
+    /// it's declared as udf, runs it periodically if it's declared with `schedule`, and caches
+    /// it. The script must be persisted, so that it's activated again on restart.
+    fn activate(&self, schema: &str, name: &str, script: Arc<PyScript>) -> Result<()> {
+        let key = script_key(schema, name);
+        if script
+            .register_udf(&schema_function_name(schema, name))
+            .context(RegisterUdfSnafu { name: &key })?
+        {
+            logging::info!("Registered script {} as udf", key);
+        }
+        if self.scheduler.schedule(&key, script.clone()) {
+            logging::info!("Scheduled script {}", key);
+        }
 
         let mut compiled = self.compiled.write().unwrap();
-        compiled.insert(name.to_string(), script.clone());
+        compiled.insert(key.clone(), script);
 
-        logging::info!("Compiled and cached script: {}", name);
+        logging::info!("Compiled and cached script: {}", key);
+        Ok(())
+    }
 
-        Ok(script)
+    /// Compiles the scripts in the scripts table, so the ones declared as udf are registered to
+    /// the query engine and the ones declared with `schedule` start running. A script failed to
+    /// compile is skipped.
+    pub async fn load_scripts(&self) -> Result<()> {
+        for (key, script) in self.table.find_all_scripts().await? {
+            let (schema, name) = parse_script_key(&key);
+            let result = match self.compile(&key, &script).await {
+                Ok(script) => self.activate(schema, name, script),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                logging::error!(e; "Failed to load script {}", key);
+            }
+        }
+        Ok(())
     }
 
-    pub async fn insert_and_compile(&self, name: &str, script: &str) -> Result<Arc<PyScript>> {
-        let compiled_script = self.compile(name, script).await?;
-        self.table.insert(name, script).await?;
+    /// Inserts the script `name` into the `schema`, it's only registered or scheduled once
+    /// persisted.
+    pub async fn insert_and_compile(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+    ) -> Result<Arc<PyScript>> {
+        ensure!(!name.contains('.'), InvalidScriptNameSnafu { name });
+        let key = script_key(schema, name);
+        let compiled_script = self.compile(&key, script).await?;
+        self.table.insert(&key, script).await?;
+        self.activate(schema, name, compiled_script.clone())?;
         Ok(compiled_script)
     }
 
-    pub async fn execute(&self, name: &str) -> Result<Output> {
+    pub async fn execute(&self, schema: &str, name: &str) -> Result<Output> {
+        let key = script_key(schema, name);
         let script = {
-            let s = self.compiled.read().unwrap().get(name).cloned();
+            let s = self.compiled.read().unwrap().get(&key).cloned();
 
             if s.is_some() {
                 s
             } else {
-                self.try_find_script_and_compile(name).await?
+                self.try_find_script_and_compile(schema, name).await?
             }
         };
 
-        let script = script.context(ScriptNotFoundSnafu { name })?;
+        let script = script.context(ScriptNotFoundSnafu { name: &key })?;
 
         script
-            .execute(EvalContext::default())
+            .execute(EvalContext {
+                schema: Some(schema.to_string()),
+                ..Default::default()
+            })
             .await
-            .context(ExecutePythonSnafu { name })
+            .context(ExecutePythonSnafu { name: key })
     }
 
-    async fn try_find_script_and_compile(&self, name: &str) -> Result<Option<Arc<PyScript>>> {
-        let script = self.table.find_script_by_name(name).await?;
-
-        Ok(Some(self.compile(name, &script).await?))
+    async fn try_find_script_and_compile(
+        &self,
+        schema: &str,
+        name: &str,
+    ) -> Result<Option<Arc<PyScript>>> {
+        let key = script_key(schema, name);
+        let script = self.table.find_script_by_name(&key).await?;
+        let script = self.compile(&key, &script).await?;
+        self.activate(schema, name, script.clone())?;
+        Ok(Some(script))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use catalog::CatalogManager;
+    use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+    use common_error::prelude::BoxedError;
     use query::QueryEngineFactory;
+    use table::requests::InsertRequest;
     use table_engine::config::EngineConfig as TableEngineConfig;
     use table_engine::table::test_util::new_test_object_store;
 
     use super::*;
+    use crate::scheduler::InsertHandler;
     type DefaultEngine = MitoEngine<EngineImpl<LocalFileLogStore>>;
     use log_store::fs::config::LogConfig;
     use log_store::fs::log::LocalFileLogStore;
@@ -109,6 +175,20 @@ mod tests {
     use table_engine::engine::MitoEngine;
     use tempdir::TempDir;
 
+    struct MockInsertHandler;
+
+    #[async_trait::async_trait]
+    impl InsertHandler for MockInsertHandler {
+        async fn insert(&self, request: InsertRequest) -> std::result::Result<usize, BoxedError> {
+            Ok(request
+                .columns_values
+                .values()
+                .next()
+                .map(|vector| vector.len())
+                .unwrap_or(0))
+        }
+    }
+
     #[tokio::test]
     async fn test_insert_find_compile_script() {
         let wal_dir = TempDir::new("test_insert_find_compile_script_wal").unwrap();
@@ -141,9 +221,13 @@ mod tests {
 
         let factory = QueryEngineFactory::new(catalog_manager.clone());
         let query_engine = factory.query_engine();
-        let mgr = ScriptManager::new(catalog_manager.clone(), query_engine)
-            .await
-            .unwrap();
+        let mgr = ScriptManager::new(
+            catalog_manager.clone(),
+            query_engine,
+            Arc::new(MockInsertHandler),
+        )
+        .await
+        .unwrap();
         catalog_manager.start().await.unwrap();
 
         let name = "test";
@@ -165,12 +249,46 @@ def test(n):
         }
 
         // try to find and compile
-        let script = mgr.try_find_script_and_compile(name).await.unwrap();
+        let script = mgr
+            .try_find_script_and_compile(DEFAULT_SCHEMA_NAME, name)
+            .await
+            .unwrap();
         assert!(script.is_some());
 
         {
             let cached = mgr.compiled.read().unwrap();
             assert!(cached.get(name).is_some());
         }
+        assert!(!mgr.scheduler.is_scheduled(name));
+
+        // inserting a scheduled script starts its task, and inserting it again without
+        // `schedule` cancels the task.
+        let _ = mgr
+            .insert_and_compile(
+                DEFAULT_SCHEMA_NAME,
+                name,
+                r#"
+@copr(sql='select number from numbers where number >= $window_start', args=['number'], returns=['n'], schedule='1h', into='numbers_1h')
+def test(n):
+    return n + 1;
+"#,
+            )
+            .await
+            .unwrap();
+        assert!(mgr.scheduler.is_scheduled(name));
+
+        let _ = mgr
+            .insert_and_compile(
+                DEFAULT_SCHEMA_NAME,
+                name,
+                r#"
+@copr(sql='select number from numbers limit 10', args=['number'], returns=['n'])
+def test(n):
+    return n + 1;
+"#,
+            )
+            .await
+            .unwrap();
+        assert!(!mgr.scheduler.is_scheduled(name));
     }
 }
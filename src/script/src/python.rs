@@ -20,6 +20,7 @@ mod engine;
 pub mod error;
 #[cfg(test)]
 mod test;
+pub(crate) mod udf;
 pub(crate) mod utils;
 mod vector;
 
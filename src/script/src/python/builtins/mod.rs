@@ -440,6 +440,87 @@ pub(crate) mod greptime_builtin {
         eval_aggr_func("scipystatsnormpdf", &[v0, v1], vm)
     }
 
+    #[pyfunction]
+    fn date_bin(
+        stride: PyVectorRef,
+        ts: PyVectorRef,
+        origin: OptionalArg<PyVectorRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyVector> {
+        match origin {
+            OptionalArg::Present(origin) => eval_func("date_bin", &[stride, ts, origin], vm),
+            OptionalArg::Missing => eval_func("date_bin", &[stride, ts], vm),
+        }
+    }
+
+    #[pyfunction]
+    fn time_bucket(
+        stride: PyVectorRef,
+        ts: PyVectorRef,
+        origin: OptionalArg<PyVectorRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyVector> {
+        match origin {
+            OptionalArg::Present(origin) => eval_func("time_bucket", &[stride, ts, origin], vm),
+            OptionalArg::Missing => eval_func("time_bucket", &[stride, ts], vm),
+        }
+    }
+
+    #[pyfunction]
+    fn rate(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("rate", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn irate(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("irate", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn delta(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("delta", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn increase(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("increase", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn first_value(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("first_value", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn last_value(v: PyVectorRef, ts: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("last_value", &[v, ts], vm)
+    }
+
+    #[pyfunction]
+    fn moving_average(
+        v: PyVectorRef,
+        ts: PyVectorRef,
+        window: PyVectorRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyObjectRef> {
+        eval_aggr_func("moving_average", &[v, ts, window], vm)
+    }
+
+    #[pyfunction]
+    fn histogram_quantile(
+        le: PyVectorRef,
+        count: PyVectorRef,
+        q: PyVectorRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyObjectRef> {
+        eval_aggr_func("histogram_quantile", &[le, count, q], vm)
+    }
+
+    #[pyfunction]
+    fn approx_count_distinct(v: PyVectorRef, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        eval_aggr_func("approx_count_distinct", &[v], vm)
+    }
+
     // The math function return a general PyObjectRef
     // so it can return both PyVector or a scalar PyInt/Float/Bool
 
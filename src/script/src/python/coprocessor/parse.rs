@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::time::Duration;
 
 use datatypes::arrow::datatypes::DataType;
 use rustpython_parser::ast::{Arguments, Location};
@@ -24,14 +25,28 @@ use snafu::{OptionExt, ResultExt};
 use crate::python::coprocessor::{compile, AnnotationInfo, Coprocessor};
 use crate::python::error::{ensure, CoprParseSnafu, PyParseSnafu, Result};
 
+/// The kind of SQL function a coprocessor registers as, given by the `udf` keyword.
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UdfKind {
+    /// `udf="scalar"`, called on the columns of a batch and returns a column of the same length.
+    Scalar,
+    /// `udf="aggregate"`, called on all the rows of a group and returns a single value.
+    Aggregate,
+}
+
 #[cfg_attr(test, derive(Deserialize))]
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct DecoratorArgs {
     pub arg_names: Vec<String>,
     pub ret_names: Vec<String>,
     pub sql: Option<String>,
+    pub udf: Option<UdfKind>,
+    /// Interval of running the coprocessor periodically, given by `schedule="1m"`.
+    pub schedule: Option<Duration>,
+    /// Table to insert the returned columns of a scheduled run into, given by `into="cpu_5m"`.
+    pub into: Option<String>,
     // maybe add a URL for connecting or what?
-    // also predicate for timed triggered or conditional triggered?
 }
 
 /// Return a CoprParseSnafu for you to chain fail() to return correct err Result type
@@ -263,8 +278,8 @@ fn parse_annotation(sub: &ast::Expr<()>) -> Result<AnnotationInfo> {
 /// parse a list of keyword and return args and returns list from keywords
 fn parse_keywords(keywords: &Vec<ast::Keyword<()>>) -> Result<DecoratorArgs> {
     // more keys maybe add to this list of `avail_key`(like `sql` for querying and maybe config for connecting to database?), for better extension using a `HashSet` in here
-    let avail_key = HashSet::from(["args", "returns", "sql"]);
-    let opt_keys = HashSet::from(["sql"]);
+    let avail_key = HashSet::from(["args", "returns", "sql", "udf", "schedule", "into"]);
+    let opt_keys = HashSet::from(["sql", "udf", "schedule", "into"]);
     let mut visited_key = HashSet::new();
     let len_min = avail_key.len() - opt_keys.len();
     let len_max = avail_key.len();
@@ -302,6 +317,33 @@ fn parse_keywords(keywords: &Vec<ast::Keyword<()>>) -> Result<DecoratorArgs> {
                     "args" => ret_args.arg_names = pylist_to_vec(&kw.node.value)?,
                     "returns" => ret_args.ret_names = pylist_to_vec(&kw.node.value)?,
                     "sql" => ret_args.sql = Some(py_str_to_string(&kw.node.value)?),
+                    "udf" => {
+                        ret_args.udf = match py_str_to_string(&kw.node.value)?.as_str() {
+                            "scalar" => Some(UdfKind::Scalar),
+                            "aggregate" => Some(UdfKind::Aggregate),
+                            other => {
+                                return fail_parse_error!(
+                                    format!(
+                                "Expect `udf` to be \"scalar\" or \"aggregate\", found \"{other}\""
+                            ),
+                                    Some(kw.location),
+                                )
+                            }
+                        }
+                    }
+                    "schedule" => {
+                        let schedule = py_str_to_string(&kw.node.value)?;
+                        ret_args.schedule = match humantime::parse_duration(&schedule) {
+                            Ok(interval) if !interval.is_zero() => Some(interval),
+                            _ => {
+                                return fail_parse_error!(
+                                    format!("Expect `schedule` to be a non-zero duration like \"1m\", found \"{schedule}\""),
+                                    Some(kw.location),
+                                )
+                            }
+                        };
+                    }
+                    "into" => ret_args.into = Some(py_str_to_string(&kw.node.value)?),
                     _ => unreachable!(),
                 }
             }
@@ -365,6 +407,58 @@ fn parse_decorator(decorator: &ast::Expr<()>) -> Result<DecoratorArgs> {
     }
 }
 
+/// check a coprocessor registered as a SQL function, whose input and output types must be known
+/// before running it
+fn check_udf(
+    deco_args: &DecoratorArgs,
+    arg_types: &[Option<AnnotationInfo>],
+    return_types: &[Option<AnnotationInfo>],
+    loc: Location,
+) -> Result<()> {
+    ensure!(
+        deco_args.sql.is_none(),
+        CoprParseSnafu {
+            reason: "Expect no `sql` in a coprocessor registered as `udf`",
+            loc: Some(loc)
+        }
+    );
+    ensure!(
+        !deco_args.arg_names.is_empty() && deco_args.ret_names.len() == 1,
+        CoprParseSnafu {
+            reason: "Expect at least one arg and exactly one return in a coprocessor registered as `udf`",
+            loc: Some(loc)
+        }
+    );
+    let annotated = |anno: &Option<AnnotationInfo>| matches!(anno, Some(AnnotationInfo { datatype: Some(ty), .. }) if *ty != DataType::Float16);
+    ensure!(
+        arg_types.iter().all(annotated) && return_types.iter().all(annotated),
+        CoprParseSnafu {
+            reason: "Expect the types of all args and returns annotated(except `_` and `f16`) in a coprocessor registered as `udf`",
+            loc: Some(loc)
+        }
+    );
+    Ok(())
+}
+
+/// check a coprocessor running periodically, whose results are inserted into the `into` table
+fn check_schedule(deco_args: &DecoratorArgs, loc: Location) -> Result<()> {
+    ensure!(
+        deco_args.schedule.is_some() && deco_args.into.is_some(),
+        CoprParseSnafu {
+            reason: "Expect both `schedule` and `into` in a scheduled coprocessor",
+            loc: Some(loc)
+        }
+    );
+    ensure!(
+        deco_args.sql.is_some() && deco_args.udf.is_none(),
+        CoprParseSnafu {
+            reason: "Expect `sql` and no `udf` in a scheduled coprocessor",
+            loc: Some(loc)
+        }
+    );
+    Ok(())
+}
+
 // get type annotaion in arguments
 fn get_arg_annotations(args: &Arguments) -> Result<Vec<Option<AnnotationInfo>>> {
     // get arg types from type annotation>
@@ -496,6 +590,12 @@ pub fn parse_and_compile_copr(script: &str) -> Result<Coprocessor> {
                         loc: None
                     }
                 );
+                if deco_args.udf.is_some() {
+                    check_udf(&deco_args, &arg_types, &return_types, decorator.location)?;
+                }
+                if deco_args.schedule.is_some() || deco_args.into.is_some() {
+                    check_schedule(&deco_args, decorator.location)?;
+                }
                 coprocessor = Some(Coprocessor {
                     code_obj: Some(compile::compile_script(name, &deco_args, script)?),
                     name: name.to_string(),
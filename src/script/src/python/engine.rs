@@ -17,6 +17,7 @@ use std::any::Any;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use common_error::prelude::BoxedError;
@@ -26,12 +27,14 @@ use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStre
 use datatypes::schema::SchemaRef;
 use futures::Stream;
 use query::QueryEngineRef;
+use session::context::QueryContext;
 use snafu::{ensure, ResultExt};
 use sql::statements::statement::Statement;
 
 use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
 use crate::python::coprocessor::{exec_parsed, parse, CoprocessorRef};
 use crate::python::error::{self, Result};
+use crate::python::udf;
 
 const PY_ENGINE: &str = "python";
 
@@ -40,6 +43,20 @@ pub struct PyScript {
     copr: CoprocessorRef,
 }
 
+impl PyScript {
+    /// Registers the script as a SQL function named `name` if its coprocessor is declared with
+    /// `udf`, returns whether it's registered.
+    pub fn register_udf(&self, name: &str) -> Result<bool> {
+        udf::register_udf(&self.copr, &self.query_engine, name)
+    }
+
+    /// Returns the interval and the target table if the script is declared with `schedule`.
+    pub fn schedule(&self) -> Option<(Duration, &str)> {
+        let deco_args = &self.copr.deco_args;
+        deco_args.schedule.zip(deco_args.into.as_deref())
+    }
+}
+
 pub struct CoprStream {
     stream: SendableRecordBatchStream,
     copr: CoprocessorRef,
@@ -86,14 +103,21 @@ impl Script for PyScript {
         self
     }
 
-    async fn execute(&self, _ctx: EvalContext) -> Result<Output> {
+    async fn execute(&self, ctx: EvalContext) -> Result<Output> {
         if let Some(sql) = &self.copr.deco_args.sql {
+            let sql = &ctx.params.iter().fold(sql.clone(), |sql, (name, value)| {
+                sql.replace(&format!("${name}"), value)
+            });
             let stmt = self.query_engine.sql_to_statement(sql)?;
             ensure!(
                 matches!(stmt, Statement::Query { .. }),
                 error::UnsupportedSqlSnafu { sql }
             );
-            let plan = self.query_engine.statement_to_plan(stmt)?;
+            let query_ctx = QueryContext::arc();
+            if let Some(schema) = &ctx.schema {
+                query_ctx.set_current_schema(schema);
+            }
+            let plan = self.query_engine.statement_to_plan(stmt, query_ctx)?;
             let res = self.query_engine.execute(&plan).await?;
             let copr = self.copr.clone();
             match res {
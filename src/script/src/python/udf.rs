@@ -0,0 +1,445 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registers coprocessors declared with `udf="scalar"` or `udf="aggregate"` as SQL functions.
+use std::sync::{Arc, RwLock};
+
+use common_function::scalars::aggregate::AggregateFunctionMeta;
+use common_query::error::{
+    BadAccumulatorImplSnafu, DowncastVectorSnafu, Error as QueryError, FromScalarValueSnafu,
+    InvalidInputStateSnafu, Result as QueryResult,
+};
+use common_query::logical_plan::accumulator::AggrFuncTypeStore;
+use common_query::logical_plan::{
+    Accumulator, AggregateFunctionCreator, AggregateFunctionCreatorRef,
+};
+use common_query::prelude::{
+    create_udf, make_scalar_function, AccumulatorCreatorFunction, Volatility,
+};
+use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
+use datatypes::arrow::compute::cast::{self, CastOptions};
+use datatypes::arrow::datatypes::{Field, Schema as ArrowSchema};
+use datatypes::prelude::*;
+use datatypes::value::ListValue;
+use datatypes::vectors::{Helper, ListVector};
+use query::QueryEngineRef;
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::python::coprocessor::parse::UdfKind;
+use crate::python::coprocessor::{exec_parsed, AnnotationInfo, Coprocessor, CoprocessorRef};
+use crate::python::error::{ArrowSnafu, OtherSnafu, Result, TypeCastSnafu};
+
+/// Returns the annotated type, which is checked when parsing a coprocessor declared as `udf`.
+fn annotated_type(anno: &Option<AnnotationInfo>) -> Result<ConcreteDataType> {
+    let datatype = anno
+        .as_ref()
+        .and_then(|anno| anno.datatype.as_ref())
+        .context(OtherSnafu {
+            reason: "Expect type annotation in udf",
+        })?;
+    ConcreteDataType::try_from(datatype).context(TypeCastSnafu)
+}
+
+/// Registers the coprocessor as a SQL function named `name`, if it's declared as `udf`. Returns
+/// whether the coprocessor is registered.
+pub(crate) fn register_udf(
+    copr: &CoprocessorRef,
+    query_engine: &QueryEngineRef,
+    name: &str,
+) -> Result<bool> {
+    match copr.deco_args.udf {
+        Some(UdfKind::Scalar) => {
+            let input_types = copr
+                .arg_types
+                .iter()
+                .map(annotated_type)
+                .collect::<Result<Vec<_>>>()?;
+            let return_type = annotated_type(&copr.return_types[0])?;
+            let udf_copr = copr.clone();
+            let fun = make_scalar_function(move |args: &[VectorRef]| {
+                exec_on_vectors(&udf_copr, args).map_err(QueryError::new)
+            });
+            query_engine.register_udf(create_udf(
+                name,
+                input_types,
+                Arc::new(return_type),
+                Volatility::Immutable,
+                fun,
+            ));
+        }
+        Some(UdfKind::Aggregate) => {
+            let udaf_copr = copr.clone();
+            query_engine.register_aggregate_function(Arc::new(AggregateFunctionMeta::new(
+                name,
+                copr.deco_args.arg_names.len() as u8,
+                Arc::new(move || {
+                    Arc::new(PyUdafCreator::new(udaf_copr.clone())) as AggregateFunctionCreatorRef
+                }),
+            )));
+        }
+        None => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Runs the coprocessor with the vectors as its args, and returns the only column it returns.
+/// The args are casted to the annotated types if they differ.
+fn exec_on_vectors(copr: &Coprocessor, args: &[VectorRef]) -> Result<VectorRef> {
+    ensure!(
+        args.len() == copr.deco_args.arg_names.len(),
+        OtherSnafu {
+            reason: format!(
+                "Expect {} args in udf {}, found {}",
+                copr.deco_args.arg_names.len(),
+                copr.name,
+                args.len()
+            ),
+        }
+    );
+    let mut fields = Vec::with_capacity(args.len());
+    let mut columns = Vec::with_capacity(args.len());
+    for ((name, arg), anno) in copr
+        .deco_args
+        .arg_names
+        .iter()
+        .zip(args)
+        .zip(&copr.arg_types)
+    {
+        let mut array = arg.to_arrow_array();
+        let mut is_nullable = true;
+        if let Some(AnnotationInfo {
+            datatype,
+            is_nullable: nullable,
+        }) = anno
+        {
+            is_nullable = *nullable;
+            if let Some(datatype) = datatype {
+                if array.data_type() != datatype {
+                    array = cast::cast(array.as_ref(), datatype, CastOptions::default())
+                        .context(ArrowSnafu)?
+                        .into();
+                }
+            }
+        }
+        fields.push(Field::new(name, array.data_type().clone(), is_nullable));
+        columns.push(array);
+    }
+    let rb =
+        DfRecordBatch::try_new(Arc::new(ArrowSchema::from(fields)), columns).context(ArrowSnafu)?;
+    let result = exec_parsed(copr, &rb)?;
+    Helper::try_into_vector(result.df_recordbatch.column(0).clone()).context(TypeCastSnafu)
+}
+
+/// Creates the accumulators of a coprocessor declared with `udf="aggregate"`.
+#[derive(Debug)]
+struct PyUdafCreator {
+    copr: CoprocessorRef,
+    input_types: RwLock<Option<Vec<ConcreteDataType>>>,
+}
+
+impl PyUdafCreator {
+    fn new(copr: CoprocessorRef) -> Self {
+        Self {
+            copr,
+            input_types: RwLock::new(None),
+        }
+    }
+}
+
+impl AggrFuncTypeStore for PyUdafCreator {
+    fn input_types(&self) -> QueryResult<Vec<ConcreteDataType>> {
+        let input_types = self.input_types.read().unwrap();
+        input_types
+            .clone()
+            .context(InvalidInputStateSnafu)
+            .map_err(Into::into)
+    }
+
+    fn set_input_types(&self, input_types: Vec<ConcreteDataType>) -> QueryResult<()> {
+        let mut current = self.input_types.write().unwrap();
+        if let Some(current) = current.as_ref() {
+            ensure!(*current == input_types, InvalidInputStateSnafu);
+        }
+        *current = Some(input_types);
+        Ok(())
+    }
+}
+
+impl AggregateFunctionCreator for PyUdafCreator {
+    fn creator(&self) -> AccumulatorCreatorFunction {
+        let copr = self.copr.clone();
+        let creator: AccumulatorCreatorFunction = Arc::new(move |types: &[ConcreteDataType]| {
+            ensure!(
+                types.len() == copr.deco_args.arg_names.len(),
+                InvalidInputStateSnafu
+            );
+            Ok(Box::new(PyUdafAccumulator {
+                copr: copr.clone(),
+                input_types: types.to_vec(),
+                values: vec![Vec::new(); types.len()],
+            }))
+        });
+        creator
+    }
+
+    fn output_type(&self) -> QueryResult<ConcreteDataType> {
+        annotated_type(&self.copr.return_types[0]).map_err(QueryError::new)
+    }
+
+    /// The states are the lists of the values of each arg.
+    fn state_types(&self) -> QueryResult<Vec<ConcreteDataType>> {
+        Ok(self
+            .input_types()?
+            .into_iter()
+            .map(ConcreteDataType::list_datatype)
+            .collect())
+    }
+}
+
+/// Collects the values of the args, and runs the coprocessor over all of them on evaluation.
+#[derive(Debug)]
+struct PyUdafAccumulator {
+    copr: CoprocessorRef,
+    input_types: Vec<ConcreteDataType>,
+    values: Vec<Vec<Value>>,
+}
+
+impl Accumulator for PyUdafAccumulator {
+    fn state(&self) -> QueryResult<Vec<Value>> {
+        Ok(self
+            .values
+            .iter()
+            .zip(&self.input_types)
+            .map(|(values, input_type)| {
+                Value::List(ListValue::new(
+                    Some(Box::new(values.clone())),
+                    input_type.clone(),
+                ))
+            })
+            .collect())
+    }
+
+    fn update_batch(&mut self, values: &[VectorRef]) -> QueryResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        ensure!(values.len() == self.values.len(), InvalidInputStateSnafu);
+        for (column, values) in values.iter().zip(self.values.iter_mut()) {
+            values.extend((0..column.len()).map(|i| column.get(i)));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[VectorRef]) -> QueryResult<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            states.len() == self.values.len(),
+            BadAccumulatorImplSnafu {
+                err_msg: format!(
+                    "expect {} states in `merge_batch`, found {}",
+                    self.values.len(),
+                    states.len()
+                ),
+            }
+        );
+        for (state, values) in states.iter().zip(self.values.iter_mut()) {
+            let lists = state
+                .as_any()
+                .downcast_ref::<ListVector>()
+                .with_context(|| DowncastVectorSnafu {
+                    err_msg: format!(
+                        "expect ListVector, got vector type {}",
+                        state.vector_type_name()
+                    ),
+                })?;
+            for list in lists.values_iter() {
+                let list = list.context(FromScalarValueSnafu)?;
+                values.extend((0..list.len()).map(|i| list.get(i)));
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> QueryResult<Value> {
+        if self.values.iter().all(|values| values.is_empty()) {
+            return Ok(Value::Null);
+        }
+        let args = self
+            .values
+            .iter()
+            .zip(&self.input_types)
+            .map(|(values, input_type)| {
+                let mut builder = VectorBuilder::with_capacity(input_type.clone(), values.len());
+                values.iter().for_each(|value| builder.push(value));
+                builder.finish()
+            })
+            .collect::<Vec<_>>();
+        let result = exec_on_vectors(&self.copr, &args).map_err(QueryError::new)?;
+        // A constant returned by the coprocessor is expanded to a column of the input's length.
+        Ok(if result.is_empty() {
+            Value::Null
+        } else {
+            result.get(0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catalog::local::{MemoryCatalogProvider, MemorySchemaProvider};
+    use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_query::Output;
+    use common_recordbatch::util;
+    use query::{schema_function_name, QueryEngineFactory};
+    use session::context::QueryContext;
+    use table::table::numbers::NumbersTable;
+
+    use super::*;
+    use crate::engine::{CompileContext, ScriptEngine};
+    use crate::python::PyEngine;
+
+    fn create_query_engine() -> QueryEngineRef {
+        let catalog_list = catalog::local::new_memory_catalog_list().unwrap();
+        let default_schema = Arc::new(MemorySchemaProvider::new());
+        default_schema
+            .register_table("numbers".to_string(), Arc::new(NumbersTable::default()))
+            .unwrap();
+        let default_catalog = Arc::new(MemoryCatalogProvider::new());
+        default_catalog
+            .register_schema(DEFAULT_SCHEMA_NAME.to_string(), default_schema)
+            .unwrap();
+        catalog_list
+            .register_catalog(DEFAULT_CATALOG_NAME.to_string(), default_catalog)
+            .unwrap();
+        QueryEngineFactory::new(catalog_list).query_engine()
+    }
+
+    async fn query(query_engine: &QueryEngineRef, sql: &str) -> Vec<Value> {
+        let plan = query_engine.sql_to_plan(sql, QueryContext::arc()).unwrap();
+        let stream = match query_engine.execute(&plan).await.unwrap() {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        util::collect(stream)
+            .await
+            .unwrap()
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .rows()
+                    .map(|row| row.unwrap().remove(0))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_register_udf() {
+        let query_engine = create_query_engine();
+        let script_engine = PyEngine::new(query_engine.clone());
+
+        let script = r#"
+@copr(args=["n"], returns=["r"], udf="scalar")
+def add_half(n: vector[u32]) -> vector[f64]:
+    return n + 0.5
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        assert!(script
+            .register_udf(&schema_function_name(DEFAULT_SCHEMA_NAME, "half"))
+            .unwrap());
+        assert_eq!(
+            vec![Value::from(0.5), Value::from(1.5), Value::from(2.5)],
+            query(&query_engine, "select half(number) from numbers limit 3").await
+        );
+        // Functions are resolved against the current schema, and registered in a schema only.
+        assert_eq!(
+            vec![Value::from(0.5)],
+            query(
+                &query_engine,
+                "select public.half(number) from numbers limit 1"
+            )
+            .await
+        );
+        let plan =
+            query_engine.sql_to_plan("select add_half(number) from numbers", QueryContext::arc());
+        assert!(plan.is_err());
+
+        let script = r#"
+from greptime import sum
+
+@copr(args=["n"], returns=["r"], udf="aggregate")
+def py_sum(n: vector[u32]) -> vector[f64]:
+    return sum(n)
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        assert!(script
+            .register_udf(&schema_function_name(DEFAULT_SCHEMA_NAME, "py_sum"))
+            .unwrap());
+        assert_eq!(
+            vec![Value::from(45.0)],
+            query(
+                &query_engine,
+                "select py_sum(number) from numbers where number < 10"
+            )
+            .await
+        );
+
+        // Scripts with the same python function don't overwrite each other.
+        let script = r#"
+@copr(args=["n"], returns=["r"], udf="scalar")
+def add_half(n: vector[u32]) -> vector[f64]:
+    return n - 0.5
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        assert!(script
+            .register_udf(&schema_function_name(DEFAULT_SCHEMA_NAME, "minus_half"))
+            .unwrap());
+        assert_eq!(
+            vec![Value::from(-0.5)],
+            query(
+                &query_engine,
+                "select minus_half(number) from numbers limit 1"
+            )
+            .await
+        );
+        assert_eq!(
+            vec![Value::from(0.5)],
+            query(&query_engine, "select half(number) from numbers limit 1").await
+        );
+
+        // Not declared as udf.
+        let script = r#"
+@copr(args=["n"], returns=["r"], sql="select number as n from numbers")
+def test(n):
+    return n
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        assert!(!script.register_udf("test").unwrap());
+    }
+}
@@ -18,6 +18,7 @@ use std::sync::Arc;
 use common_time::date::Date;
 use common_time::datetime::DateTime;
 use common_time::timestamp::Timestamp;
+use common_time::Interval;
 use datatypes::arrow::array::{Array, ArrayRef, BooleanArray, PrimitiveArray};
 use datatypes::arrow::compute;
 use datatypes::arrow::compute::cast::{self, CastOptions};
@@ -25,6 +26,7 @@ use datatypes::arrow::compute::{arithmetics, comparison};
 use datatypes::arrow::datatypes::DataType;
 use datatypes::arrow::scalar::{PrimitiveScalar, Scalar};
 use datatypes::data_type::ConcreteDataType;
+use datatypes::decimal::Decimal128;
 use datatypes::prelude::Value;
 use datatypes::value::OrderedFloat;
 use datatypes::vectors::{Helper, NullVector, VectorBuilder, VectorRef};
@@ -854,7 +856,39 @@ pub fn pyobj_try_to_typed_val(
                     None
                 }
             }
-            ConcreteDataType::List(_) => unreachable!(),
+            ConcreteDataType::Decimal128(ty) => {
+                if is_instance::<PyStr>(&obj, vm) {
+                    obj.try_into_value::<String>(vm).ok().and_then(|v| {
+                        Decimal128::from_str_with(&v, ty.precision(), ty.scale())
+                            .ok()
+                            .map(value::Value::Decimal128)
+                    })
+                } else {
+                    None
+                }
+            }
+            ConcreteDataType::Interval(_) => {
+                if is_instance::<PyStr>(&obj, vm) {
+                    obj.try_into_value::<String>(vm)
+                        .ok()
+                        .and_then(|v| v.parse::<Interval>().ok())
+                        .map(value::Value::Interval)
+                } else {
+                    None
+                }
+            }
+            ConcreteDataType::Json(_) => {
+                if is_instance::<PyStr>(&obj, vm) {
+                    obj.try_into_value::<String>(vm)
+                        .ok()
+                        .map(|v| value::Value::Json(v.into()))
+                } else {
+                    None
+                }
+            }
+            ConcreteDataType::List(_) | ConcreteDataType::Struct(_) | ConcreteDataType::Map(_) => {
+                None
+            }
             ConcreteDataType::Date(_)
             | ConcreteDataType::DateTime(_)
             | ConcreteDataType::Timestamp(_) => {
@@ -940,6 +974,34 @@ pub fn val_to_pyobj(val: value::Value, vm: &VirtualMachine) -> PyObjectRef {
         value::Value::DateTime(v) => vm.ctx.new_int(v.val()).into(),
         // FIXME(dennis): lose the timestamp unit here
         Value::Timestamp(v) => vm.ctx.new_int(v.value()).into(),
+        value::Value::Decimal128(v) => vm.ctx.new_float(v.to_f64()).into(),
+        value::Value::Json(v) => vm.ctx.new_str(v.as_utf8()).into(),
+        value::Value::Interval(v) => vm.ctx.new_str(v.to_string()).into(),
+        value::Value::Struct(v) => {
+            let items: Vec<_> = v
+                .items()
+                .iter()
+                .map(|v| val_to_pyobj(v.clone(), vm))
+                .collect();
+            vm.ctx.new_tuple(items).into()
+        }
+        // Maps are converted to lists of `(key, value)` tuples, as their keys may be unhashable
+        // in python.
+        value::Value::Map(v) => {
+            let entries: Vec<_> = v
+                .entries()
+                .iter()
+                .map(|(key, value)| {
+                    vm.ctx
+                        .new_tuple(vec![
+                            val_to_pyobj(key.clone(), vm),
+                            val_to_pyobj(value.clone(), vm),
+                        ])
+                        .into()
+                })
+                .collect();
+            vm.ctx.new_list(entries).into()
+        }
         value::Value::List(list) => {
             let list = list.items().as_ref();
             match list {
@@ -0,0 +1,342 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scripts stored in metasrv, which are shared by the frontends in distributed mode. Every
+//! frontend watches the scripts, so the ones declared as udf are callable from all of them.
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use catalog::error::Error as CatalogError;
+use catalog::remote::{EventIter, Kv, KvBackendRef, KvEvent};
+use common_query::Output;
+use common_telemetry::logging;
+use futures_util::StreamExt;
+use query::{schema_function_name, QueryEngineRef};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
+use crate::error::{
+    AccessMetaScriptsSnafu, CompilePythonSnafu, ExecutePythonSnafu, InvalidScriptNameSnafu,
+    RegisterUdfSnafu, Result, ScheduleNotSupportedSnafu, ScriptNotFoundSnafu,
+};
+use crate::python::{PyEngine, PyScript};
+use crate::table::{parse_script_key, script_key};
+
+const SCRIPT_KEY_PREFIX: &str = "__script-";
+
+/// Returns the key in metasrv of a script by its name in the scripts table.
+fn meta_key(key: &str) -> String {
+    format!("{}{}", SCRIPT_KEY_PREFIX, key)
+}
+
+pub struct RemoteScriptManager {
+    backend: KvBackendRef,
+    py_engine: PyEngine,
+    /// Compiled scripts by their names in the scripts table.
+    compiled: RwLock<HashMap<String, Arc<PyScript>>>,
+}
+
+impl RemoteScriptManager {
+    /// Loads the scripts in metasrv and keeps watching them, the ones declared as udf are
+    /// registered to the query engine.
+    pub async fn start(backend: KvBackendRef, query_engine: QueryEngineRef) -> Result<Arc<Self>> {
+        let manager = Arc::new(Self {
+            backend,
+            py_engine: PyEngine::new(query_engine),
+            compiled: RwLock::new(HashMap::default()),
+        });
+        let events = manager.rewatch().await?;
+        common_runtime::spawn_bg(manager.clone().run(events));
+        Ok(manager)
+    }
+
+    /// Inserts the script `name` into the `schema`, it's only registered once persisted.
+    /// Scheduled scripts are not supported, as the runs are recorded in a table of datanode.
+    pub async fn insert_and_compile(
+        &self,
+        schema: &str,
+        name: &str,
+        script: &str,
+    ) -> Result<Arc<PyScript>> {
+        ensure!(!name.contains('.'), InvalidScriptNameSnafu { name });
+        let key = script_key(schema, name);
+        let compiled_script = self.compile(&key, script).await?;
+        ensure!(
+            compiled_script.schedule().is_none(),
+            ScheduleNotSupportedSnafu { name: &key }
+        );
+
+        self.backend
+            .set(meta_key(&key).as_bytes(), script.as_bytes())
+            .await
+            .context(AccessMetaScriptsSnafu)?;
+        self.activate(&key, compiled_script.clone())?;
+        Ok(compiled_script)
+    }
+
+    pub async fn execute(&self, schema: &str, name: &str) -> Result<Output> {
+        let key = script_key(schema, name);
+        let cached = self.compiled.read().unwrap().get(&key).cloned();
+        let script = match cached {
+            Some(script) => script,
+            None => {
+                // The script may be inserted by another frontend, before it's watched here.
+                let Kv(_, script) = self
+                    .backend
+                    .get(meta_key(&key).as_bytes())
+                    .await
+                    .context(AccessMetaScriptsSnafu)?
+                    .context(ScriptNotFoundSnafu { name: &key })?;
+                let script = self
+                    .compile(&key, &String::from_utf8_lossy(&script))
+                    .await?;
+                self.activate(&key, script.clone())?;
+                script
+            }
+        };
+
+        script
+            .execute(EvalContext {
+                schema: Some(schema.to_string()),
+                ..Default::default()
+            })
+            .await
+            .context(ExecutePythonSnafu { name: key })
+    }
+
+    async fn compile(&self, name: &str, script: &str) -> Result<Arc<PyScript>> {
+        let script = self
+            .py_engine
+            .compile(script, CompileContext::default())
+            .await
+            .context(CompilePythonSnafu { name })?;
+        Ok(Arc::new(script))
+    }
+
+    /// Registers the compiled script as a SQL function of its schema if it's declared as udf,
+    /// and caches it.
+    fn activate(&self, key: &str, script: Arc<PyScript>) -> Result<()> {
+        let (schema, name) = parse_script_key(key);
+        if script
+            .register_udf(&schema_function_name(schema, name))
+            .context(RegisterUdfSnafu { name: key })?
+        {
+            logging::info!("Registered script {} as udf", key);
+        }
+
+        let mut compiled = self.compiled.write().unwrap();
+        compiled.insert(key.to_string(), script);
+        Ok(())
+    }
+
+    /// Compiles and activates a script put to metasrv.
+    async fn load(&self, meta_key: &[u8], script: &[u8]) {
+        let meta_key = String::from_utf8_lossy(meta_key);
+        let key = match meta_key.strip_prefix(SCRIPT_KEY_PREFIX) {
+            Some(key) => key,
+            None => return,
+        };
+        let result = match self.compile(key, &String::from_utf8_lossy(script)).await {
+            Ok(script) => self.activate(key, script),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            logging::error!(e; "Failed to load script {}", key);
+        }
+    }
+
+    async fn run(self: Arc<Self>, mut events: EventIter<'static, CatalogError>) {
+        loop {
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(KvEvent::Put(Kv(key, value))) => self.load(&key, &value).await,
+                    Ok(KvEvent::Delete(_)) => {}
+                    Err(e) => {
+                        logging::warn!("Scripts watch interrupted, err: {:?}", e);
+                        break;
+                    }
+                }
+            }
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                match self.rewatch().await {
+                    Ok(new_events) => {
+                        events = new_events;
+                        break;
+                    }
+                    Err(e) => logging::error!(e; "Failed to rewatch scripts on metasrv"),
+                }
+            }
+        }
+    }
+
+    /// Watches the scripts, then loads all of them, so that no script is missed in between.
+    async fn rewatch(&self) -> Result<EventIter<'static, CatalogError>> {
+        let events = self
+            .backend
+            .watch_prefix(SCRIPT_KEY_PREFIX.as_bytes())
+            .await
+            .context(AccessMetaScriptsSnafu)?;
+
+        let mut scripts = Vec::new();
+        let mut iter = self.backend.range(SCRIPT_KEY_PREFIX.as_bytes());
+        while let Some(kv) = iter.next().await {
+            scripts.push(kv.context(AccessMetaScriptsSnafu)?);
+        }
+        for Kv(key, value) in scripts {
+            self.load(&key, &value).await;
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    use catalog::local::{MemoryCatalogProvider, MemorySchemaProvider};
+    use catalog::remote::{KvBackend, ValueIter};
+    use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use query::QueryEngineFactory;
+    use session::context::QueryContext;
+    use table::table::numbers::NumbersTable;
+
+    use super::*;
+
+    /// A backend keeping the values in memory, whose watches never yield.
+    #[derive(Default)]
+    struct MemoryKvBackend {
+        kvs: Mutex<BTreeMap<Vec<u8>, Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl KvBackend for MemoryKvBackend {
+        fn range<'a, 'b>(&'a self, key: &[u8]) -> ValueIter<'b, CatalogError>
+        where
+            'a: 'b,
+        {
+            let kvs = self
+                .kvs
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(k, _)| k.starts_with(key))
+                .map(|(k, v)| Ok(Kv(k.clone(), v.clone())))
+                .collect::<Vec<_>>();
+            Box::pin(futures::stream::iter(kvs))
+        }
+
+        async fn set(&self, key: &[u8], val: &[u8]) -> std::result::Result<(), CatalogError> {
+            self.kvs.lock().unwrap().insert(key.to_vec(), val.to_vec());
+            Ok(())
+        }
+
+        async fn compare_and_set(
+            &self,
+            _key: &[u8],
+            _expect: &[u8],
+            _val: &[u8],
+        ) -> std::result::Result<std::result::Result<(), Option<Vec<u8>>>, CatalogError> {
+            unimplemented!()
+        }
+
+        async fn delete_range(
+            &self,
+            key: &[u8],
+            _end: &[u8],
+        ) -> std::result::Result<(), CatalogError> {
+            self.kvs.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn watch_prefix(
+            &self,
+            _prefix: &[u8],
+        ) -> std::result::Result<EventIter<'static, CatalogError>, CatalogError> {
+            Ok(Box::pin(futures::stream::pending::<
+                std::result::Result<KvEvent, CatalogError>,
+            >()))
+        }
+    }
+
+    fn create_query_engine() -> QueryEngineRef {
+        let catalog_list = catalog::local::new_memory_catalog_list().unwrap();
+        let default_schema = Arc::new(MemorySchemaProvider::new());
+        default_schema
+            .register_table("numbers".to_string(), Arc::new(NumbersTable::default()))
+            .unwrap();
+        let default_catalog = Arc::new(MemoryCatalogProvider::new());
+        default_catalog
+            .register_schema(DEFAULT_SCHEMA_NAME.to_string(), default_schema)
+            .unwrap();
+        catalog_list
+            .register_catalog(DEFAULT_CATALOG_NAME.to_string(), default_catalog)
+            .unwrap();
+        QueryEngineFactory::new(catalog_list).query_engine()
+    }
+
+    #[tokio::test]
+    async fn test_remote_scripts() {
+        let backend: KvBackendRef = Arc::new(MemoryKvBackend::default());
+        let query_engine = create_query_engine();
+        let manager = RemoteScriptManager::start(backend.clone(), query_engine.clone())
+            .await
+            .unwrap();
+
+        let script = r#"
+@copr(args=["n"], returns=["r"], udf="scalar")
+def f(n: vector[u32]) -> vector[f64]:
+    return n + 0.5
+"#;
+        let _ = manager
+            .insert_and_compile(DEFAULT_SCHEMA_NAME, "add_half", script)
+            .await
+            .unwrap();
+        assert!(backend.get(b"__script-add_half").await.unwrap().is_some());
+        assert!(query_engine
+            .sql_to_plan("select add_half(number) from numbers", QueryContext::arc())
+            .is_ok());
+
+        // Another frontend loads the script on start.
+        let other_engine = create_query_engine();
+        let _other = RemoteScriptManager::start(backend.clone(), other_engine.clone())
+            .await
+            .unwrap();
+        assert!(other_engine
+            .sql_to_plan("select add_half(number) from numbers", QueryContext::arc())
+            .is_ok());
+
+        let scheduled = r#"
+@copr(sql='select number from numbers', args=['number'], returns=['n'], schedule='1h', into='t')
+def test(n):
+    return n
+"#;
+        assert!(manager
+            .insert_and_compile(DEFAULT_SCHEMA_NAME, "scheduled", scheduled)
+            .await
+            .is_err());
+        assert!(manager
+            .insert_and_compile(DEFAULT_SCHEMA_NAME, "a.b", script)
+            .await
+            .is_err());
+        assert!(manager
+            .execute(DEFAULT_SCHEMA_NAME, "not_exist")
+            .await
+            .is_err());
+    }
+}
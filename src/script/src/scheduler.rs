@@ -0,0 +1,291 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduler running the scripts declared with `schedule` periodically
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_error::prelude::BoxedError;
+use common_query::Output;
+use common_recordbatch::util as record_util;
+use common_telemetry::logging;
+use common_time::util;
+use datatypes::vectors::{Helper, VectorRef};
+use snafu::ResultExt;
+use table::requests::InsertRequest;
+use tokio::task::JoinHandle;
+
+use crate::engine::{EvalContext, Script};
+use crate::error::{
+    CollectRecordsSnafu, ConvertColumnSnafu, ExecutePythonSnafu, InsertScheduledResultSnafu, Result,
+};
+use crate::python::PyScript;
+use crate::table::{parse_script_key, ScriptRun, ScriptRunsTable};
+
+/// Name of the placeholder in the sql of a scheduled script, replaced by the start of the time
+/// window a run covers in milliseconds, e.g. `where ts >= $window_start`.
+pub const WINDOW_START: &str = "window_start";
+/// Name of the placeholder replaced by the (exclusive) end of the time window in milliseconds.
+pub const WINDOW_END: &str = "window_end";
+
+/// Inserts the results of scheduled scripts through the insert path of the instance, the same as
+/// the inserts of users.
+#[async_trait]
+pub trait InsertHandler: Send + Sync {
+    /// Returns the number of inserted rows.
+    async fn insert(&self, request: InsertRequest) -> std::result::Result<usize, BoxedError>;
+}
+
+pub type InsertHandlerRef = Arc<dyn InsertHandler>;
+
+pub struct ScriptScheduler {
+    insert_handler: InsertHandlerRef,
+    runs_table: Arc<ScriptRunsTable>,
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl ScriptScheduler {
+    pub fn new(insert_handler: InsertHandlerRef, runs_table: Arc<ScriptRunsTable>) -> Self {
+        Self {
+            insert_handler,
+            runs_table,
+            tasks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs the script periodically if it's declared with `schedule`, the task previously
+    /// scheduled under the same name is cancelled. Returns whether the script is scheduled.
+    ///
+    /// The name is the one in the scripts table, the script runs in its schema.
+    pub fn schedule(&self, name: &str, script: Arc<PyScript>) -> bool {
+        let mut tasks = self.tasks.lock().unwrap();
+        if let Some(task) = tasks.remove(name) {
+            task.abort();
+        }
+
+        let interval = match script.schedule() {
+            Some((interval, _)) => interval,
+            None => return false,
+        };
+        let task = ScheduledTask {
+            name: name.to_string(),
+            script,
+            insert_handler: self.insert_handler.clone(),
+            runs_table: self.runs_table.clone(),
+        };
+        let _ = tasks.insert(name.to_string(), tokio::spawn(task.run(interval)));
+        true
+    }
+
+    /// Returns whether a task is scheduled under the name.
+    pub fn is_scheduled(&self, name: &str) -> bool {
+        self.tasks.lock().unwrap().contains_key(name)
+    }
+}
+
+impl Drop for ScriptScheduler {
+    fn drop(&mut self) {
+        for task in self.tasks.get_mut().unwrap().values() {
+            task.abort();
+        }
+    }
+}
+
+/// Returns the start of the first window to run, which follows the last successful window, or is
+/// the last complete window aligned to the interval if the script has never run successfully.
+fn first_window_start(last_window_end: Option<i64>, now: i64, interval: i64) -> i64 {
+    match last_window_end {
+        Some(end) => end,
+        None => now.div_euclid(interval) * interval - interval,
+    }
+}
+
+/// Returns the catalog, the schema and the name of the `into` table of a script in the schema,
+/// which is either `table`, `schema.table` or `catalog.schema.table`.
+fn target_table(schema: &str, into: &str) -> (String, String, String) {
+    let parts = into.split('.').collect::<Vec<_>>();
+    match parts.as_slice() {
+        [catalog, schema, table] => (catalog.to_string(), schema.to_string(), table.to_string()),
+        [schema, table] => (
+            DEFAULT_CATALOG_NAME.to_string(),
+            schema.to_string(),
+            table.to_string(),
+        ),
+        _ => (
+            DEFAULT_CATALOG_NAME.to_string(),
+            schema.to_string(),
+            into.to_string(),
+        ),
+    }
+}
+
+struct ScheduledTask {
+    name: String,
+    script: Arc<PyScript>,
+    insert_handler: InsertHandlerRef,
+    runs_table: Arc<ScriptRunsTable>,
+}
+
+impl ScheduledTask {
+    /// Runs the script over consecutive windows of the interval, each run starts once its window
+    /// is complete. A window is retried after a failed run until it succeeds, and the task resumes
+    /// after the last successful window recorded in the runs table, so every window is covered.
+    ///
+    /// A window may be run more than once, e.g. if the task stops after inserting the results but
+    /// before recording the run. The windows are fixed and the rows of a window are the same in
+    /// every run, which replace the previous ones in the target table as they have the same keys
+    /// and timestamps.
+    async fn run(self, interval: Duration) {
+        let interval = interval.as_millis() as i64;
+        let last_window_end = match self.runs_table.last_window_end(&self.name).await {
+            Ok(end) => end,
+            Err(e) => {
+                logging::error!(e; "Failed to find last run of scheduled script {}", self.name);
+                None
+            }
+        };
+        let mut window_start =
+            first_window_start(last_window_end, util::current_time_millis(), interval);
+
+        loop {
+            let window_end = window_start + interval;
+            let now = util::current_time_millis();
+            if window_end > now {
+                tokio::time::sleep(Duration::from_millis((window_end - now) as u64)).await;
+            }
+
+            let start = util::current_time_millis();
+            let result = self.run_once(window_start, window_end).await;
+            let run = ScriptRun {
+                name: &self.name,
+                start,
+                window_start,
+                window_end,
+                elapsed_ms: util::current_time_millis() - start,
+                rows: *result.as_ref().unwrap_or(&0),
+                error: result.as_ref().err().map(|e| e.to_string()),
+            };
+            if let Err(e) = self.runs_table.insert(&run).await {
+                logging::error!(e; "Failed to record run of scheduled script {}", self.name);
+            }
+
+            match &result {
+                Ok(rows) => {
+                    logging::debug!("Scheduled script {} inserted {} rows", self.name, rows);
+                    window_start = window_end;
+                }
+                Err(e) => {
+                    logging::error!(e; "Failed to run scheduled script {}", self.name);
+                    // Retries the window after an interval.
+                    tokio::time::sleep(Duration::from_millis(interval as u64)).await;
+                }
+            }
+        }
+    }
+
+    /// Runs the script over `[window_start, window_end)` and inserts its results into the
+    /// target table, returns the number of inserted rows.
+    async fn run_once(&self, window_start: i64, window_end: i64) -> Result<u64> {
+        let name = &self.name;
+        let (schema, _) = parse_script_key(name);
+        // Only the scripts with a target table are scheduled.
+        let (_, into) = self.script.schedule().unwrap();
+        let (catalog_name, schema_name, table_name) = target_table(schema, into);
+
+        let params = HashMap::from([
+            (WINDOW_START.to_string(), window_start.to_string()),
+            (WINDOW_END.to_string(), window_end.to_string()),
+        ]);
+        let stream = match self
+            .script
+            .execute(EvalContext {
+                params,
+                schema: Some(schema.to_string()),
+            })
+            .await
+            .context(ExecutePythonSnafu { name })?
+        {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let records = record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?;
+
+        let mut rows = 0;
+        for record in records.iter().filter(|record| record.num_rows() > 0) {
+            let columns_values = record
+                .schema
+                .column_schemas()
+                .iter()
+                .zip(record.df_recordbatch.columns())
+                .map(|(column, array)| {
+                    let vector: VectorRef =
+                        Helper::try_into_vector(array.clone()).context(ConvertColumnSnafu {
+                            column: &column.name,
+                        })?;
+                    Ok((column.name.clone(), vector))
+                })
+                .collect::<Result<HashMap<_, _>>>()?;
+
+            let _ = self
+                .insert_handler
+                .insert(InsertRequest {
+                    catalog_name: catalog_name.clone(),
+                    schema_name: schema_name.clone(),
+                    table_name: table_name.clone(),
+                    columns_values,
+                })
+                .await
+                .context(InsertScheduledResultSnafu { name, table: into })?;
+            rows += record.num_rows() as u64;
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+
+    use super::*;
+
+    #[test]
+    fn test_first_window_start() {
+        // Resumes after the last successful window.
+        assert_eq!(1000, first_window_start(Some(1000), 5500, 1000));
+        // Runs the last complete window at once if never run.
+        assert_eq!(4000, first_window_start(None, 5500, 1000));
+        assert_eq!(4000, first_window_start(None, 5000, 1000));
+    }
+
+    #[test]
+    fn test_target_table() {
+        let table = |catalog: &str, schema: &str, table: &str| {
+            (catalog.to_string(), schema.to_string(), table.to_string())
+        };
+        assert_eq!(
+            table(DEFAULT_CATALOG_NAME, "db", "cpu_5m"),
+            target_table("db", "cpu_5m")
+        );
+        assert_eq!(
+            table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "cpu_5m"),
+            target_table("db", "public.cpu_5m")
+        );
+        assert_eq!(table("c", "s", "t"), target_table("db", "c.s.t"));
+    }
+}
@@ -17,26 +17,47 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use catalog::{CatalogManagerRef, RegisterSystemTableRequest};
-use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, SCRIPTS_TABLE_ID};
+use common_catalog::consts::{
+    DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, SCRIPTS_TABLE_ID, SCRIPT_RUNS_TABLE_ID,
+};
 use common_query::Output;
 use common_recordbatch::util as record_util;
 use common_telemetry::logging;
 use common_time::timestamp::Timestamp;
 use common_time::util;
-use datatypes::arrow::array::Utf8Array;
+use datatypes::arrow::array::{PrimitiveArray, Utf8Array};
 use datatypes::prelude::{ConcreteDataType, ScalarVector};
 use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
-use datatypes::vectors::{StringVector, TimestampVector, VectorRef};
+use datatypes::vectors::{Int64Vector, StringVector, TimestampVector, UInt64Vector, VectorRef};
 use query::QueryEngineRef;
+use session::context::QueryContext;
 use snafu::{ensure, OptionExt, ResultExt};
 use table::requests::{CreateTableRequest, InsertRequest};
 
 use crate::error::{
-    CastTypeSnafu, CollectRecordsSnafu, FindScriptSnafu, FindScriptsTableSnafu, InsertScriptSnafu,
-    RegisterScriptsTableSnafu, Result, ScriptNotFoundSnafu, ScriptsTableNotFoundSnafu,
+    CastTypeSnafu, CollectRecordsSnafu, FindScriptRunsSnafu, FindScriptSnafu, FindScriptsSnafu,
+    FindScriptsTableSnafu, FindTableSnafu, InsertScriptRunSnafu, InsertScriptSnafu,
+    RegisterScriptRunsTableSnafu, RegisterScriptsTableSnafu, Result, ScriptNotFoundSnafu,
+    ScriptsTableNotFoundSnafu, TableNotFoundSnafu,
 };
 
 pub const SCRIPTS_TABLE_NAME: &str = "scripts";
+pub const SCRIPT_RUNS_TABLE_NAME: &str = "script_runs";
+
+/// Returns the name of a script of the schema in the scripts table, `<schema>.<name>`. The scripts
+/// of the default schema are stored by their names only, as they were before scripts had schemas.
+pub(crate) fn script_key(schema: &str, name: &str) -> String {
+    if schema == DEFAULT_SCHEMA_NAME {
+        name.to_string()
+    } else {
+        format!("{}.{}", schema, name)
+    }
+}
+
+/// Returns the schema and the name of a script by its name in the scripts table.
+pub(crate) fn parse_script_key(key: &str) -> (&str, &str) {
+    key.split_once('.').unwrap_or((DEFAULT_SCHEMA_NAME, key))
+}
 
 pub struct ScriptsTable {
     catalog_manager: CatalogManagerRef,
@@ -151,7 +172,7 @@ impl ScriptsTable {
 
         let plan = self
             .query_engine
-            .sql_to_plan(&sql)
+            .sql_to_plan(&sql, QueryContext::arc())
             .context(FindScriptSnafu { name })?;
 
         let stream = match self
@@ -189,12 +210,224 @@ impl ScriptsTable {
         Ok(script_column.value(0).to_string())
     }
 
+    /// Returns the names and the scripts of all the scripts.
+    pub async fn find_all_scripts(&self) -> Result<Vec<(String, String)>> {
+        let sql = format!("select name, script from {}", self.name());
+
+        let plan = self
+            .query_engine
+            .sql_to_plan(&sql, QueryContext::arc())
+            .context(FindScriptsSnafu)?;
+
+        let stream = match self
+            .query_engine
+            .execute(&plan)
+            .await
+            .context(FindScriptsSnafu)?
+        {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let records = record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?;
+
+        let mut scripts = Vec::new();
+        for record in &records {
+            let record = &record.df_recordbatch;
+            let string_column = |i: usize| {
+                record
+                    .column(i)
+                    .as_any()
+                    .downcast_ref::<Utf8Array<i32>>()
+                    .context(CastTypeSnafu {
+                        msg: format!(
+                            "can't downcast {:?} array into utf8 array",
+                            record.column(i).data_type()
+                        ),
+                    })
+            };
+            let (names, script_column) = (string_column(0)?, string_column(1)?);
+            scripts.extend(
+                names
+                    .values_iter()
+                    .zip(script_column.values_iter())
+                    .map(|(name, script)| (name.to_string(), script.to_string())),
+            );
+        }
+        Ok(scripts)
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
     }
 }
 
+/// A run of a scheduled script.
+#[derive(Debug)]
+pub struct ScriptRun<'a> {
+    pub name: &'a str,
+    /// When the run started, in milliseconds.
+    pub start: i64,
+    /// The time window `[window_start, window_end)` the run covers, in milliseconds.
+    pub window_start: i64,
+    pub window_end: i64,
+    pub elapsed_ms: i64,
+    /// Number of rows inserted into the target table.
+    pub rows: u64,
+    pub error: Option<String>,
+}
+
+/// The run history of scheduled scripts
+pub struct ScriptRunsTable {
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+}
+
+impl ScriptRunsTable {
+    pub async fn new(
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+    ) -> Result<Self> {
+        let schema = Arc::new(build_script_runs_schema());
+        let request = CreateTableRequest {
+            id: SCRIPT_RUNS_TABLE_ID,
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: SCRIPT_RUNS_TABLE_NAME.to_string(),
+            desc: Some("Script runs table".to_string()),
+            schema,
+            region_numbers: vec![0],
+            // name and timestamp as primary key
+            primary_key_indices: vec![0, 1],
+            create_if_not_exists: true,
+            table_options: HashMap::default(),
+        };
+
+        catalog_manager
+            .register_system_table(RegisterSystemTableRequest {
+                create_table_request: request,
+                open_hook: None,
+            })
+            .await
+            .context(RegisterScriptRunsTableSnafu)?;
+
+        Ok(Self {
+            catalog_manager,
+            query_engine,
+        })
+    }
+
+    /// Returns the end of the last window the script ran successfully over.
+    pub async fn last_window_end(&self, name: &str) -> Result<Option<i64>> {
+        let sql = format!(
+            "select max(window_end) from {} where name='{}' and status='success'",
+            SCRIPT_RUNS_TABLE_NAME,
+            name.replace('\'', "''")
+        );
+
+        let plan = self
+            .query_engine
+            .sql_to_plan(&sql, QueryContext::arc())
+            .context(FindScriptRunsSnafu { name })?;
+        let stream = match self
+            .query_engine
+            .execute(&plan)
+            .await
+            .context(FindScriptRunsSnafu { name })?
+        {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let records = record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?;
+
+        let column = match records.first() {
+            Some(record) if record.num_rows() > 0 => record.df_recordbatch.column(0),
+            _ => return Ok(None),
+        };
+        let window_end = column
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .context(CastTypeSnafu {
+                msg: format!(
+                    "can't downcast {:?} array into i64 array",
+                    column.data_type()
+                ),
+            })?;
+        Ok(window_end.is_valid(0).then(|| window_end.value(0)))
+    }
+
+    pub async fn insert(&self, run: &ScriptRun<'_>) -> Result<()> {
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(8);
+        columns_values.insert(
+            "name".to_string(),
+            Arc::new(StringVector::from(vec![run.name])) as _,
+        );
+        for (column, millis) in [
+            ("timestamp", run.start),
+            ("window_start", run.window_start),
+            ("window_end", run.window_end),
+        ] {
+            columns_values.insert(
+                column.to_string(),
+                Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                    millis,
+                )])) as _,
+            );
+        }
+        columns_values.insert(
+            "elapsed_ms".to_string(),
+            Arc::new(Int64Vector::from_slice(&[run.elapsed_ms])) as _,
+        );
+        columns_values.insert(
+            "rows".to_string(),
+            Arc::new(UInt64Vector::from_slice(&[run.rows])) as _,
+        );
+        let status = if run.error.is_none() {
+            "success"
+        } else {
+            "failed"
+        };
+        columns_values.insert(
+            "status".to_string(),
+            Arc::new(StringVector::from(vec![status])) as _,
+        );
+        columns_values.insert(
+            "error".to_string(),
+            Arc::new(StringVector::from(vec![run.error.as_deref()])) as _,
+        );
+
+        let table = self
+            .catalog_manager
+            .table(
+                DEFAULT_CATALOG_NAME,
+                DEFAULT_SCHEMA_NAME,
+                SCRIPT_RUNS_TABLE_NAME,
+            )
+            .context(FindTableSnafu {
+                table: SCRIPT_RUNS_TABLE_NAME,
+            })?
+            .context(TableNotFoundSnafu {
+                table: SCRIPT_RUNS_TABLE_NAME,
+            })?;
+
+        let _ = table
+            .insert(InsertRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: SCRIPT_RUNS_TABLE_NAME.to_string(),
+                columns_values,
+            })
+            .await
+            .context(InsertScriptRunSnafu { name: run.name })?;
+
+        Ok(())
+    }
+}
+
 /// Build scripts table
 fn build_scripts_schema() -> Schema {
     let cols = vec![
@@ -234,3 +467,66 @@ fn build_scripts_schema() -> Schema {
     // Schema is always valid here
     SchemaBuilder::try_from(cols).unwrap().build().unwrap()
 }
+
+/// Build script runs table
+fn build_script_runs_schema() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "timestamp".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        )
+        .with_time_index(true),
+        ColumnSchema::new(
+            "window_start".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "window_end".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "elapsed_ms".to_string(),
+            ConcreteDataType::int64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "rows".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "status".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "error".to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
+    ];
+
+    // Schema is always valid here
+    SchemaBuilder::try_from(cols).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_key() {
+        assert_eq!("test", script_key(DEFAULT_SCHEMA_NAME, "test"));
+        assert_eq!((DEFAULT_SCHEMA_NAME, "test"), parse_script_key("test"));
+        assert_eq!("db.test", script_key("db", "test"));
+        assert_eq!(("db", "test"), parse_script_key("db.test"));
+    }
+}
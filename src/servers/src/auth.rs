@@ -0,0 +1,155 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod user_provider;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use snafu::OptionExt;
+
+use crate::auth::user_provider::{StaticUserProvider, STATIC_USER_PROVIDER};
+use crate::context::QuotaLimits;
+use crate::error::{self, InvalidUserProviderConfigSnafu, Result};
+
+#[async_trait]
+pub trait UserProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Checks whether the `password` is valid for the user identified by `id`.
+    async fn auth(&self, id: Identity<'_>, password: Password<'_>) -> Result<UserInfo>;
+}
+
+pub type UserProviderRef = Arc<dyn UserProvider>;
+
+type Username<'a> = &'a str;
+type HostOrIp<'a> = &'a str;
+
+#[derive(Debug, Clone)]
+pub enum Identity<'a> {
+    UserId(Username<'a>, Option<HostOrIp<'a>>),
+}
+
+pub type HashedPassword<'a> = &'a [u8];
+pub type Salt<'a> = &'a [u8];
+
+/// The password sent by client, in the form of the authentication method of each protocol.
+#[derive(Debug, Clone)]
+pub enum Password<'a> {
+    PlainText(&'a str),
+    /// The scrambled auth data of MySQL's `mysql_native_password` plugin.
+    MysqlNativePassword(HashedPassword<'a>, Salt<'a>),
+    /// The `"md5" + md5(md5(password + username) + salt)` response of Postgres' MD5 auth.
+    PgMD5(HashedPassword<'a>, Salt<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserInfo {
+    username: String,
+    quota_limits: QuotaLimits,
+}
+
+impl UserInfo {
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            quota_limits: QuotaLimits::default(),
+        }
+    }
+
+    /// Sets the limits of the user's sessions, which are put into [Quota](crate::context::Quota)
+    /// of the request context.
+    pub fn with_quota_limits(mut self, quota_limits: QuotaLimits) -> Self {
+        self.quota_limits = quota_limits;
+        self
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn quota_limits(&self) -> &QuotaLimits {
+        &self.quota_limits
+    }
+}
+
+/// Creates a [UserProvider] from the config option, in the form of `<provider_name>:<provider_config>`.
+/// For example, `static_user_provider:file:/path/to/users`.
+pub fn user_provider_from_option(opt: &str) -> Result<UserProviderRef> {
+    match opt.split_once(':') {
+        Some((STATIC_USER_PROVIDER, content)) => {
+            let provider = StaticUserProvider::try_from(content)?;
+            Ok(Arc::new(provider))
+        }
+        _ => InvalidUserProviderConfigSnafu {
+            value: opt,
+            msg: "Unknown user provider",
+        }
+        .fail(),
+    }
+}
+
+/// Authenticates by the value of a "Basic" authorization header, returns the user on success.
+pub async fn auth_basic(header: &str, user_provider: &UserProviderRef) -> Result<UserInfo> {
+    let (username, password) = decode_basic_auth(header)?;
+    user_provider
+        .auth(
+            Identity::UserId(&username, None),
+            Password::PlainText(&password),
+        )
+        .await
+}
+
+fn decode_basic_auth(header: &str) -> Result<(String, String)> {
+    let credential = match header.split_once(' ') {
+        Some((scheme, credential)) if scheme.eq_ignore_ascii_case("Basic") => credential,
+        _ => {
+            return error::InvalidAuthHeaderSnafu {
+                msg: "unsupported authorization scheme",
+            }
+            .fail()
+        }
+    };
+
+    let decoded = base64::decode(credential.trim())
+        .ok()
+        .and_then(|x| String::from_utf8(x).ok())
+        .context(error::InvalidAuthHeaderSnafu {
+            msg: "invalid base64 credential",
+        })?;
+    let (username, password) = decoded
+        .split_once(':')
+        .context(error::InvalidAuthHeaderSnafu {
+            msg: "credential is not in the form of 'username:password'",
+        })?;
+    Ok((username.to_string(), password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_basic_auth() {
+        // base64 of "greptime:123:456"
+        let (username, password) = decode_basic_auth("Basic Z3JlcHRpbWU6MTIzOjQ1Ng==").unwrap();
+        assert_eq!(username, "greptime");
+        assert_eq!(password, "123:456");
+
+        assert!(decode_basic_auth("Bearer Z3JlcHRpbWU6MTIzOjQ1Ng==").is_err());
+        assert!(decode_basic_auth("Basic not-base64").is_err());
+        // base64 of "greptime"
+        assert!(decode_basic_auth("Basic Z3JlcHRpbWU=").is_err());
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fs;
+
+use async_trait::async_trait;
+use sha1::{Digest, Sha1};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::auth::{Identity, Password, UserInfo, UserProvider};
+use crate::error::{
+    InvalidUserProviderConfigSnafu, ReadUserProviderFileSnafu, Result,
+    UnsupportedPasswordTypeSnafu, UserNotFoundSnafu, UserPasswordMismatchSnafu,
+};
+
+pub const STATIC_USER_PROVIDER: &str = "static_user_provider";
+
+const MYSQL_NATIVE_PASSWORD_PREFIX: &str = "*";
+const PG_MD5_PASSWORD_PREFIX: &str = "md5";
+
+/// The password of a user stored in the static user file.
+#[derive(Debug, PartialEq, Eq)]
+enum StoredPassword {
+    PlainText(String),
+    /// `SHA1(SHA1(password))`, stored like "*<40 hex digits>" (the format of MySQL's
+    /// `authentication_string`).
+    DoubleSha1(Vec<u8>),
+    /// `md5(password + username)` in hex, stored like "md5<32 hex digits>" (the format of
+    /// Postgres' `pg_authid.rolpassword`).
+    Md5(String),
+}
+
+impl StoredPassword {
+    fn parse(s: &str) -> Self {
+        if let Some(hash) = s.strip_prefix(MYSQL_NATIVE_PASSWORD_PREFIX) {
+            if hash.len() == 40 {
+                if let Ok(hash) = hex::decode(hash) {
+                    return StoredPassword::DoubleSha1(hash);
+                }
+            }
+        }
+        if let Some(hash) = s.strip_prefix(PG_MD5_PASSWORD_PREFIX) {
+            if hash.len() == 32 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return StoredPassword::Md5(hash.to_ascii_lowercase());
+            }
+        }
+        StoredPassword::PlainText(s.to_string())
+    }
+}
+
+/// A [UserProvider] that reads users from a static file, each line of which is in the form of
+/// `username=password`. The password can be the plain text, or one of the hashes:
+/// - "*" followed by the hex of `SHA1(SHA1(password))`, usable for MySQL and plain text auth;
+/// - "md5" followed by the hex of `md5(password + username)`, usable for Postgres and plain text
+///   auth.
+///
+/// Empty lines and lines starting with "#" are ignored.
+pub struct StaticUserProvider {
+    users: HashMap<String, StoredPassword>,
+}
+
+impl TryFrom<&str> for StaticUserProvider {
+    type Error = crate::error::Error;
+
+    /// Creates the provider from config "file:<path_to_users_file>".
+    fn try_from(value: &str) -> Result<Self> {
+        let path = value
+            .strip_prefix("file:")
+            .context(InvalidUserProviderConfigSnafu {
+                value,
+                msg: "Expect 'file:<path_to_users_file>'",
+            })?;
+        let content = fs::read_to_string(path).context(ReadUserProviderFileSnafu { path })?;
+        Self::parse(&content)
+    }
+}
+
+impl StaticUserProvider {
+    fn parse(content: &str) -> Result<Self> {
+        let mut users = HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (username, password) =
+                line.split_once('=')
+                    .context(InvalidUserProviderConfigSnafu {
+                        value: line,
+                        msg: "Expect 'username=password'",
+                    })?;
+            let username = username.trim();
+            ensure!(
+                !username.is_empty(),
+                InvalidUserProviderConfigSnafu {
+                    value: line,
+                    msg: "Username must not be empty",
+                }
+            );
+            let _ = users.insert(username.to_string(), StoredPassword::parse(password.trim()));
+        }
+        Ok(Self { users })
+    }
+}
+
+#[async_trait]
+impl UserProvider for StaticUserProvider {
+    fn name(&self) -> &str {
+        STATIC_USER_PROVIDER
+    }
+
+    async fn auth(&self, id: Identity<'_>, password: Password<'_>) -> Result<UserInfo> {
+        let Identity::UserId(username, _) = id;
+        let stored = self
+            .users
+            .get(username)
+            .context(UserNotFoundSnafu { username })?;
+
+        let matched = match password {
+            Password::PlainText(password) => match stored {
+                StoredPassword::PlainText(p) => p == password,
+                StoredPassword::DoubleSha1(hash) => double_sha1(password.as_bytes()) == *hash,
+                StoredPassword::Md5(hash) => pg_md5(password, username) == *hash,
+            },
+            Password::MysqlNativePassword(auth_data, salt) => {
+                let hash = match stored {
+                    StoredPassword::PlainText(p) if p.is_empty() => {
+                        // client sends no auth data if the password is empty
+                        return if auth_data.is_empty() {
+                            Ok(UserInfo::new(username))
+                        } else {
+                            UserPasswordMismatchSnafu { username }.fail()
+                        };
+                    }
+                    StoredPassword::PlainText(p) => double_sha1(p.as_bytes()),
+                    StoredPassword::DoubleSha1(hash) => hash.clone(),
+                    StoredPassword::Md5(_) => {
+                        return UnsupportedPasswordTypeSnafu {
+                            password_type: "mysql_native_password",
+                        }
+                        .fail()
+                    }
+                };
+                check_mysql_native_password(auth_data, salt, &hash)
+            }
+            Password::PgMD5(response, salt) => {
+                let hash = match stored {
+                    StoredPassword::PlainText(p) => pg_md5(p, username),
+                    StoredPassword::Md5(hash) => hash.clone(),
+                    StoredPassword::DoubleSha1(_) => {
+                        return UnsupportedPasswordTypeSnafu {
+                            password_type: "md5",
+                        }
+                        .fail()
+                    }
+                };
+                let mut salted = hash.into_bytes();
+                salted.extend_from_slice(salt);
+                let expected = format!("{}{:x}", PG_MD5_PASSWORD_PREFIX, md5::compute(salted));
+                expected.as_bytes() == response
+            }
+        };
+        ensure!(matched, UserPasswordMismatchSnafu { username });
+        Ok(UserInfo::new(username))
+    }
+}
+
+fn double_sha1(password: &[u8]) -> Vec<u8> {
+    Sha1::digest(Sha1::digest(password)).to_vec()
+}
+
+fn pg_md5(password: &str, username: &str) -> String {
+    format!("{:x}", md5::compute(format!("{}{}", password, username)))
+}
+
+/// The client sends `SHA1(password) XOR SHA1(salt + SHA1(SHA1(password)))`, so we can recover
+/// `SHA1(password)` from it with the stored `SHA1(SHA1(password))`, and check it against the
+/// stored hash again.
+fn check_mysql_native_password(auth_data: &[u8], salt: &[u8], double_sha1_hash: &[u8]) -> bool {
+    if auth_data.len() != double_sha1_hash.len() {
+        return false;
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(salt);
+    hasher.update(double_sha1_hash);
+    let mask = hasher.finalize();
+
+    let sha1_hash = auth_data
+        .iter()
+        .zip(mask.iter())
+        .map(|(a, b)| a ^ b)
+        .collect::<Vec<u8>>();
+    Sha1::digest(sha1_hash).as_slice() == double_sha1_hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn mysql_native_auth_data(password: &str, salt: &[u8]) -> Vec<u8> {
+        let sha1_hash = Sha1::digest(password.as_bytes());
+        let mut hasher = Sha1::new();
+        hasher.update(salt);
+        hasher.update(double_sha1(password.as_bytes()));
+        let mask = hasher.finalize();
+        sha1_hash
+            .iter()
+            .zip(mask.iter())
+            .map(|(a, b)| a ^ b)
+            .collect()
+    }
+
+    fn pg_md5_response(password: &str, username: &str, salt: &[u8]) -> Vec<u8> {
+        let mut salted = pg_md5(password, username).into_bytes();
+        salted.extend_from_slice(salt);
+        format!("md5{:x}", md5::compute(salted)).into_bytes()
+    }
+
+    fn test_provider() -> StaticUserProvider {
+        let content = format!(
+            r"
+# comments and empty lines are ignored
+
+root=123456
+mysql_user=*{}
+pg_user=md5{}
+empty=
+",
+            hex::encode_upper(double_sha1(b"mysql_pwd")),
+            pg_md5("pg_pwd", "pg_user"),
+        );
+        StaticUserProvider::parse(&content).unwrap()
+    }
+
+    async fn auth(provider: &StaticUserProvider, username: &str, password: Password<'_>) -> bool {
+        provider
+            .auth(Identity::UserId(username, None), password)
+            .await
+            .is_ok()
+    }
+
+    #[test]
+    fn test_parse() {
+        let provider = test_provider();
+        assert_eq!(provider.users.len(), 4);
+        assert_eq!(
+            provider.users.get("root"),
+            Some(&StoredPassword::PlainText("123456".to_string()))
+        );
+        assert_eq!(
+            provider.users.get("mysql_user"),
+            Some(&StoredPassword::DoubleSha1(double_sha1(b"mysql_pwd")))
+        );
+        assert_eq!(
+            provider.users.get("pg_user"),
+            Some(&StoredPassword::Md5(pg_md5("pg_pwd", "pg_user")))
+        );
+        assert_eq!(
+            provider.users.get("empty"),
+            Some(&StoredPassword::PlainText("".to_string()))
+        );
+
+        assert!(StaticUserProvider::parse("root").is_err());
+        assert!(StaticUserProvider::parse("=123456").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auth_plain_text() {
+        let provider = test_provider();
+        assert!(auth(&provider, "root", Password::PlainText("123456")).await);
+        assert!(!auth(&provider, "root", Password::PlainText("654321")).await);
+        assert!(auth(&provider, "mysql_user", Password::PlainText("mysql_pwd")).await);
+        assert!(auth(&provider, "pg_user", Password::PlainText("pg_pwd")).await);
+        assert!(!auth(&provider, "pg_user", Password::PlainText("mysql_pwd")).await);
+
+        let result = provider
+            .auth(
+                Identity::UserId("not_exist", None),
+                Password::PlainText("123456"),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::UserNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auth_mysql_native_password() {
+        let provider = test_provider();
+        let salt = b"01234567890123456789";
+
+        let auth_data = mysql_native_auth_data("123456", salt);
+        assert!(
+            auth(
+                &provider,
+                "root",
+                Password::MysqlNativePassword(&auth_data, salt)
+            )
+            .await
+        );
+        let auth_data = mysql_native_auth_data("mysql_pwd", salt);
+        assert!(
+            auth(
+                &provider,
+                "mysql_user",
+                Password::MysqlNativePassword(&auth_data, salt)
+            )
+            .await
+        );
+        assert!(
+            !auth(
+                &provider,
+                "root",
+                Password::MysqlNativePassword(&auth_data, salt)
+            )
+            .await
+        );
+        assert!(!auth(&provider, "root", Password::MysqlNativePassword(&[], salt)).await);
+        assert!(auth(&provider, "empty", Password::MysqlNativePassword(&[], salt)).await);
+
+        let result = provider
+            .auth(
+                Identity::UserId("pg_user", None),
+                Password::MysqlNativePassword(&auth_data, salt),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::UnsupportedPasswordType { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auth_pg_md5() {
+        let provider = test_provider();
+        let salt = b"salt";
+
+        let response = pg_md5_response("123456", "root", salt);
+        assert!(auth(&provider, "root", Password::PgMD5(&response, salt)).await);
+        let response = pg_md5_response("pg_pwd", "pg_user", salt);
+        assert!(auth(&provider, "pg_user", Password::PgMD5(&response, salt)).await);
+        assert!(!auth(&provider, "pg_user", Password::PgMD5(&response, b"tlas")).await);
+        assert!(!auth(&provider, "root", Password::PgMD5(&response, salt)).await);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = TempDir::new("test_from_file").unwrap();
+        let path = dir.path().join("users");
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(b"root=123456\n").unwrap();
+        file.flush().unwrap();
+
+        let provider =
+            StaticUserProvider::try_from(format!("file:{}", path.to_str().unwrap()).as_str())
+                .unwrap();
+        assert_eq!(provider.users.len(), 1);
+
+        assert!(StaticUserProvider::try_from("cmd:root=123456").is_err());
+        assert!(StaticUserProvider::try_from("file:/not/exist").is_err());
+    }
+}
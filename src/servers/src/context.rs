@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+pub use session::context::QuotaLimits;
 use snafu::OptionExt;
 
 use crate::error::{BuildingContextSnafu, Result};
@@ -45,6 +46,7 @@ pub struct CtxBuilder {
     username: Option<String>,
     from_channel: Option<Channel>,
     auth_method: Option<AuthMethod>,
+    quota_limits: QuotaLimits,
 }
 
 impl CtxBuilder {
@@ -72,6 +74,11 @@ impl CtxBuilder {
         self
     }
 
+    pub fn set_quota_limits(mut self, quota_limits: QuotaLimits) -> CtxBuilder {
+        self.quota_limits = quota_limits;
+        self
+    }
+
     pub fn build(self) -> Result<Context> {
         Ok(Context {
             client_info: ClientInfo {
@@ -90,7 +97,10 @@ impl CtxBuilder {
             },
 
             exec_info: ExecInfo::default(),
-            quota: Quota::default(),
+            quota: Quota {
+                limits: self.quota_limits,
+                ..Default::default()
+            },
             predicates: vec![],
         })
     }
@@ -163,6 +173,10 @@ pub struct Quota {
     pub total: u64,
     pub consumed: u64,
     pub estimated: u64,
+    /// Limits of the session user, given by the user provider. The frontend enforces them
+    /// together with its configured limits of the user and the database.
+    #[serde(default)]
+    pub limits: QuotaLimits,
 }
 
 #[cfg(test)]
@@ -172,7 +186,7 @@ mod test {
 
     use crate::context::AuthMethod::Token;
     use crate::context::Channel::HTTP;
-    use crate::context::{Channel, Context, CtxBuilder, UserInfo};
+    use crate::context::{Channel, Context, CtxBuilder, QuotaLimits, UserInfo};
 
     #[test]
     fn test_predicate() {
@@ -207,6 +221,10 @@ mod test {
             .client_addr(Some("127.0.0.1:4001".to_string()))
             .set_channel(Some(HTTP))
             .set_auth_method(Some(Token("HELLO".to_string())))
+            .set_quota_limits(QuotaLimits {
+                max_result_rows: Some(10),
+                ..Default::default()
+            })
             .build()
             .unwrap();
 
@@ -224,6 +242,7 @@ mod test {
         assert_eq!(ctx.quota.total, 0);
         assert_eq!(ctx.quota.consumed, 0);
         assert_eq!(ctx.quota.estimated, 0);
+        assert_eq!(ctx.quota.limits.max_result_rows, Some(10));
 
         assert_eq!(ctx.predicates.capacity(), 0);
     }
@@ -144,16 +144,38 @@ pub enum Error {
     },
 
     #[snafu(display(
-        "Failed to put OpenTSDB data point: {:?}, source: {}",
-        data_point,
+        "Failed to put OpenTSDB data points of metrics: {}, source: {}",
+        metrics,
         source
     ))]
-    PutOpentsdbDataPoint {
-        data_point: String,
+    PutOpentsdbDataPoints {
+        metrics: String,
         #[snafu(backtrace)]
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to write OpenTSDB data points, source: {}", source))]
+    OpentsdbLinesWrite {
+        #[snafu(backtrace)]
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("Failed to decompress InfluxDB request, source: {}", source))]
+    DecompressInfluxdbRequest {
+        backtrace: Backtrace,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Invalid InfluxDB request, msg: {}", msg))]
+    InvalidInfluxdbRequest { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid InfluxQL: {}, msg: {}", query, msg))]
+    InvalidInfluxql {
+        query: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to decode prometheus remote request, source: {}", source))]
     DecodePromRemoteRequest {
         backtrace: Backtrace,
@@ -192,6 +214,90 @@ pub enum Error {
         err_msg: String,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Invalid user provider config: {}, msg: {}", value, msg))]
+    InvalidUserProviderConfig {
+        value: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to read user provider file: {}, source: {}", path, source))]
+    ReadUserProviderFile {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("User not found, username: {}", username))]
+    UserNotFound {
+        username: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unsupported password type: {}", password_type))]
+    UnsupportedPasswordType {
+        password_type: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Username and password does not match, username: {}", username))]
+    UserPasswordMismatch {
+        username: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Not found HTTP authorization header"))]
+    AuthHeaderNotFound { backtrace: Backtrace },
+
+    #[snafu(display("Invalid HTTP authorization header: {}", msg))]
+    InvalidAuthHeader { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to prepare statement: {}, source: {}", query, source))]
+    PrepareStatement {
+        query: String,
+        #[snafu(backtrace)]
+        source: sql::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to bind parameters of statement: {}, source: {}",
+        query,
+        source
+    ))]
+    BindParams {
+        query: String,
+        #[snafu(backtrace)]
+        source: sql::error::Error,
+    },
+
+    #[snafu(display("Failed to read TLS file: {}, source: {}", path, source))]
+    ReadTlsFile {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid TLS config: {}", msg))]
+    InvalidTlsConfig { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to set up TLS, source: {}", source))]
+    SetupTls {
+        source: rustls::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to set up TLS of gRPC server, source: {}", source))]
+    SetupGrpcTls {
+        #[snafu(backtrace)]
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("{} server requires clients to connect by TLS", server))]
+    TlsRequired {
+        server: String,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -211,18 +317,22 @@ impl ErrorExt for Error {
             | InvalidPromRemoteReadQueryResult { .. }
             | TcpBind { .. }
             | GrpcReflectionService { .. }
-            | BuildingContext { .. } => StatusCode::Internal,
+            | BuildingContext { .. }
+            | ReadUserProviderFile { .. } => StatusCode::Internal,
 
             InsertScript { source, .. }
             | ExecuteScript { source, .. }
             | ExecuteQuery { source, .. }
             | ExecuteInsert { source, .. }
             | ExecuteAlter { source, .. }
-            | PutOpentsdbDataPoint { source, .. } => source.status_code(),
+            | PutOpentsdbDataPoints { source, .. } => source.status_code(),
 
             NotSupported { .. }
             | InvalidQuery { .. }
             | InfluxdbLineProtocol { .. }
+            | DecompressInfluxdbRequest { .. }
+            | InvalidInfluxdbRequest { .. }
+            | InvalidInfluxql { .. }
             | ConnResetByPeer { .. }
             | InvalidOpentsdbLine { .. }
             | InvalidOpentsdbJsonRequest { .. }
@@ -230,11 +340,27 @@ impl ErrorExt for Error {
             | DecompressPromRemoteRequest { .. }
             | InvalidPromRemoteRequest { .. }
             | DecodeRegionNumber { .. }
-            | TimePrecision { .. } => StatusCode::InvalidArguments,
-
-            InfluxdbLinesWrite { source, .. } => source.status_code(),
+            | TimePrecision { .. }
+            | InvalidUserProviderConfig { .. }
+            | ReadTlsFile { .. }
+            | InvalidTlsConfig { .. }
+            | SetupTls { .. }
+            | TlsRequired { .. } => StatusCode::InvalidArguments,
+
+            UserNotFound { .. } => StatusCode::UserNotFound,
+            UnsupportedPasswordType { .. } => StatusCode::UnsupportedPasswordType,
+            UserPasswordMismatch { .. } => StatusCode::UserPasswordMismatch,
+            AuthHeaderNotFound { .. } => StatusCode::AuthHeaderNotFound,
+            InvalidAuthHeader { .. } => StatusCode::InvalidAuthHeader,
+
+            InfluxdbLinesWrite { source, .. } | OpentsdbLinesWrite { source, .. } => {
+                source.status_code()
+            }
             Hyper { .. } => StatusCode::Unknown,
             StartFrontend { source, .. } => source.status_code(),
+            PrepareStatement { source, .. } | BindParams { source, .. } => source.status_code(),
+            ExecuteStatement { source, .. } => source.status_code(),
+            SetupGrpcTls { source, .. } => source.status_code(),
         }
     }
 
@@ -264,6 +390,10 @@ impl IntoResponse for Error {
         let (status, error_message) = match self {
             Error::InfluxdbLineProtocol { .. }
             | Error::InfluxdbLinesWrite { .. }
+            | Error::OpentsdbLinesWrite { .. }
+            | Error::DecompressInfluxdbRequest { .. }
+            | Error::InvalidInfluxdbRequest { .. }
+            | Error::InvalidInfluxql { .. }
             | Error::InvalidOpentsdbLine { .. }
             | Error::InvalidOpentsdbJsonRequest { .. }
             | Error::DecodePromRemoteRequest { .. }
@@ -271,6 +401,11 @@ impl IntoResponse for Error {
             | Error::InvalidPromRemoteRequest { .. }
             | Error::InvalidQuery { .. }
             | Error::TimePrecision { .. } => (HttpStatusCode::BAD_REQUEST, self.to_string()),
+            Error::UserNotFound { .. }
+            | Error::UnsupportedPasswordType { .. }
+            | Error::UserPasswordMismatch { .. }
+            | Error::AuthHeaderNotFound { .. }
+            | Error::InvalidAuthHeader { .. } => (HttpStatusCode::UNAUTHORIZED, self.to_string()),
             _ => (HttpStatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
         let body = Json(json!({
@@ -27,18 +27,23 @@ use tokio::net::TcpListener;
 use tokio::sync::oneshot::{self, Sender};
 use tokio::sync::Mutex;
 use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::ServerTlsConfig;
 use tonic::{Request, Response, Status};
 
+use crate::auth::{self, UserInfo, UserProviderRef};
 use crate::error::{self, AlreadyStartedSnafu, Result, StartGrpcSnafu, TcpBindSnafu};
 use crate::grpc::handler::BatchHandler;
 use crate::query_handler::{GrpcAdminHandlerRef, GrpcQueryHandlerRef};
 use crate::server::Server;
+use crate::tls::TlsOption;
 
 pub struct GrpcServer {
     query_handler: GrpcQueryHandlerRef,
     admin_handler: GrpcAdminHandlerRef,
     shutdown_tx: Mutex<Option<Sender<()>>>,
     runtime: Arc<Runtime>,
+    tls_config: Option<ServerTlsConfig>,
+    user_provider: Option<UserProviderRef>,
 }
 
 impl GrpcServer {
@@ -52,9 +57,30 @@ impl GrpcServer {
             admin_handler,
             shutdown_tx: Mutex::new(None),
             runtime,
+            tls_config: None,
+            user_provider: None,
         }
     }
 
+    /// Requires requests to be authenticated by the "Basic" `authorization` metadata.
+    pub fn set_user_provider(&mut self, user_provider: UserProviderRef) {
+        debug_assert!(
+            self.user_provider.is_none(),
+            "User provider can be set only once!"
+        );
+        self.user_provider.get_or_insert(user_provider);
+    }
+
+    /// Serves by TLS unless it's disabled in `tls`.
+    pub fn set_tls(&mut self, tls: &TlsOption) -> Result<()> {
+        self.tls_config = tls
+            .grpc_tls_option()
+            .map(|option| option.setup())
+            .transpose()
+            .context(error::SetupGrpcTlsSnafu)?;
+        Ok(())
+    }
+
     pub fn create_service(&self) -> greptime_server::GreptimeServer<GrpcService> {
         let service = GrpcService {
             handler: BatchHandler::new(
@@ -62,6 +88,7 @@ impl GrpcServer {
                 self.admin_handler.clone(),
                 self.runtime.clone(),
             ),
+            user_provider: self.user_provider.clone(),
         };
         greptime_server::GreptimeServer::new(service)
     }
@@ -69,6 +96,30 @@ impl GrpcServer {
 
 pub struct GrpcService {
     handler: BatchHandler,
+    user_provider: Option<UserProviderRef>,
+}
+
+impl GrpcService {
+    /// Authenticates the request if a user provider is set, returns the user.
+    async fn authenticate<T>(
+        &self,
+        req: &Request<T>,
+    ) -> std::result::Result<Option<UserInfo>, Status> {
+        let user_provider = match &self.user_provider {
+            Some(user_provider) => user_provider,
+            None => return Ok(None),
+        };
+        let header = req
+            .metadata()
+            .get("authorization")
+            .ok_or_else(|| Status::unauthenticated("authorization metadata not found"))?
+            .to_str()
+            .map_err(|_| Status::unauthenticated("authorization metadata is not valid ASCII"))?;
+        auth::auth_basic(header, user_provider)
+            .await
+            .map(Some)
+            .map_err(|e| Status::unauthenticated(e.to_string()))
+    }
 }
 
 #[tonic::async_trait]
@@ -77,8 +128,9 @@ impl greptime_server::Greptime for GrpcService {
         &self,
         req: Request<BatchRequest>,
     ) -> std::result::Result<Response<BatchResponse>, Status> {
+        let user = self.authenticate(&req).await?;
         let req = req.into_inner();
-        let res = self.handler.batch(req).await?;
+        let res = self.handler.batch(req, user).await?;
         Ok(Response::new(res))
     }
 }
@@ -123,8 +175,15 @@ impl Server for GrpcServer {
             .build()
             .context(error::GrpcReflectionServiceSnafu)?;
 
+        let mut builder = tonic::transport::Server::builder();
+        if let Some(tls_config) = &self.tls_config {
+            builder = builder
+                .tls_config(tls_config.clone())
+                .context(StartGrpcSnafu)?;
+        }
+
         // Would block to serve requests.
-        tonic::transport::Server::builder()
+        builder
             .add_service(self.create_service())
             .add_service(reflection_service)
             .serve_with_incoming_shutdown(TcpListenerStream::new(listener), rx.map(drop))
@@ -16,8 +16,10 @@ use std::sync::Arc;
 
 use api::v1::{AdminResponse, BatchRequest, BatchResponse, DatabaseResponse};
 use common_runtime::Runtime;
+use session::context::{Channel, QueryContext};
 use tokio::sync::oneshot;
 
+use crate::auth::UserInfo;
 use crate::error::Result;
 use crate::query_handler::{GrpcAdminHandlerRef, GrpcQueryHandlerRef};
 
@@ -41,7 +43,12 @@ impl BatchHandler {
         }
     }
 
-    pub async fn batch(&self, batch_req: BatchRequest) -> Result<BatchResponse> {
+    /// Executes the requests on behalf of the authenticated user, if any.
+    pub async fn batch(
+        &self,
+        batch_req: BatchRequest,
+        user: Option<UserInfo>,
+    ) -> Result<BatchResponse> {
         let (tx, rx) = oneshot::channel();
         let query_handler = self.query_handler.clone();
         let admin_handler = self.admin_handler.clone();
@@ -51,11 +58,24 @@ impl BatchHandler {
             let mut admin_resp = AdminResponse::default();
             let mut db_resp = DatabaseResponse::default();
 
+            let new_query_ctx = || {
+                let query_ctx = QueryContext::arc();
+                query_ctx.set_channel(Channel::Grpc);
+                if let Some(user) = &user {
+                    query_ctx.set_current_user(Some(user.username().to_string()));
+                    query_ctx.set_quota_limits(user.quota_limits().clone());
+                }
+                query_ctx
+            };
+
             for admin_req in batch_req.admins {
                 admin_resp.results.reserve(admin_req.exprs.len());
 
+                let query_ctx = new_query_ctx();
                 for admin_expr in admin_req.exprs {
-                    let admin_result = admin_handler.exec_admin_request(admin_expr).await?;
+                    let admin_result = admin_handler
+                        .exec_admin_request(admin_expr, query_ctx.clone())
+                        .await?;
                     admin_resp.results.push(admin_result);
                 }
             }
@@ -64,8 +84,14 @@ impl BatchHandler {
             for db_req in batch_req.databases {
                 db_resp.results.reserve(db_req.exprs.len());
 
+                // Requests of a database are resolved against it, or the default one if absent.
+                let query_ctx = new_query_ctx();
+                if !db_req.name.is_empty() {
+                    query_ctx.set_current_schema(&db_req.name);
+                }
+
                 for obj_expr in db_req.exprs {
-                    let object_resp = query_handler.do_query(obj_expr).await?;
+                    let object_resp = query_handler.do_query(obj_expr, query_ctx.clone()).await?;
 
                     db_resp.results.push(object_resp);
                 }
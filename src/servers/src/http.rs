@@ -34,28 +34,38 @@ use common_error::prelude::ErrorExt;
 use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
-use common_telemetry::logging::info;
+use common_telemetry::logging::{info, warn};
 use datatypes::data_type::DataType;
-use futures::FutureExt;
+use futures::{FutureExt, StreamExt};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use snafu::{ensure, ResultExt};
+use tokio::net::TcpListener;
 use tokio::sync::oneshot::{self, Sender};
 use tokio::sync::Mutex;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::TcpListenerStream;
 use tower::timeout::TimeoutLayer;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
-use self::influxdb::influxdb_write;
-use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu};
+use self::influxdb::{
+    influxdb_health, influxdb_ping, influxdb_write, influxdb_write_v2, influxql_query,
+    InfluxdbState,
+};
+use crate::auth::UserProviderRef;
+use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu, TcpBindSnafu};
 use crate::query_handler::{
     InfluxdbLineProtocolHandlerRef, OpentsdbProtocolHandlerRef, PrometheusProtocolHandlerRef,
     ScriptHandlerRef, SqlQueryHandlerRef,
 };
 use crate::server::Server;
+use crate::tls::ServerTls;
 
 const HTTP_API_VERSION: &str = "v1";
+// Max number of TLS handshakes of incoming connections in progress at the same time.
+const TLS_HANDSHAKE_CONCURRENCY: usize = 128;
 
 pub struct HttpServer {
     sql_handler: SqlQueryHandlerRef,
@@ -63,6 +73,8 @@ pub struct HttpServer {
     opentsdb_handler: Option<OpentsdbProtocolHandlerRef>,
     prom_handler: Option<PrometheusProtocolHandlerRef>,
     script_handler: Option<ScriptHandlerRef>,
+    user_provider: Option<UserProviderRef>,
+    tls: ServerTls,
     shutdown_tx: Mutex<Option<Sender<()>>>,
 }
 
@@ -210,28 +222,47 @@ impl JsonResponse {
         self
     }
 
-    /// Create a json response from query result
-    async fn from_output(output: Result<Output>) -> Self {
-        match output {
-            Ok(Output::AffectedRows(rows)) => {
-                Self::with_output(Some(vec![JsonOutput::AffectedRows(rows)]))
+    /// Create a json response from the results of statements, the outputs of succeeded
+    /// statements are kept even if a later statement failed.
+    async fn from_output(outputs: Vec<Result<Output>>) -> Self {
+        let mut results = Vec::with_capacity(outputs.len());
+        for output in outputs {
+            match Self::to_json_output(output).await {
+                Ok(output) => results.push(output),
+                Err(mut resp) => {
+                    if !results.is_empty() {
+                        resp.output = Some(results);
+                    }
+                    return resp;
+                }
             }
+        }
+        Self::with_output(Some(results))
+    }
+
+    async fn to_json_output(output: Result<Output>) -> std::result::Result<JsonOutput, Self> {
+        match output {
+            Ok(Output::AffectedRows(rows)) => Ok(JsonOutput::AffectedRows(rows)),
             Ok(Output::Stream(stream)) => match util::collect(stream).await {
                 Ok(rows) => match HttpRecordsOutput::try_from(rows) {
-                    Ok(rows) => Self::with_output(Some(vec![JsonOutput::Records(rows)])),
-                    Err(err) => Self::with_error(err, StatusCode::Internal),
+                    Ok(rows) => Ok(JsonOutput::Records(rows)),
+                    Err(err) => Err(Self::with_error(err, StatusCode::Internal)),
                 },
-                Err(e) => Self::with_error(format!("Recordbatch error: {}", e), e.status_code()),
+                Err(e) => Err(Self::with_error(
+                    format!("Recordbatch error: {}", e),
+                    e.status_code(),
+                )),
             },
             Ok(Output::RecordBatches(recordbatches)) => {
                 match HttpRecordsOutput::try_from(recordbatches.take()) {
-                    Ok(rows) => Self::with_output(Some(vec![JsonOutput::Records(rows)])),
-                    Err(err) => Self::with_error(err, StatusCode::Internal),
+                    Ok(rows) => Ok(JsonOutput::Records(rows)),
+                    Err(err) => Err(Self::with_error(err, StatusCode::Internal)),
                 }
             }
-            Err(e) => {
-                Self::with_error(format!("Query engine output error: {}", e), e.status_code())
-            }
+            Err(e) => Err(Self::with_error(
+                format!("Query engine output error: {}", e),
+                e.status_code(),
+            )),
         }
     }
 
@@ -278,6 +309,8 @@ impl HttpServer {
             influxdb_handler: None,
             prom_handler: None,
             script_handler: None,
+            user_provider: None,
+            tls: ServerTls::default(),
             shutdown_tx: Mutex::new(None),
         }
     }
@@ -314,6 +347,20 @@ impl HttpServer {
         self.prom_handler.get_or_insert(handler);
     }
 
+    /// Requests are required to be authorized by HTTP Basic auth if the user provider is set.
+    pub fn set_user_provider(&mut self, user_provider: UserProviderRef) {
+        debug_assert!(
+            self.user_provider.is_none(),
+            "User provider can be set only once!"
+        );
+        self.user_provider.get_or_insert(user_provider);
+    }
+
+    /// Serves HTTPS only if TLS is enabled.
+    pub fn set_tls(&mut self, tls: ServerTls) {
+        self.tls = tls;
+    }
+
     pub fn make_app(&self) -> Router {
         let mut api = OpenApi {
             info: Info {
@@ -363,8 +410,15 @@ impl HttpServer {
         }
 
         if let Some(influxdb_handler) = self.influxdb_handler.clone() {
-            let influxdb_router =
-                Router::with_state(influxdb_handler).route("/write", routing::post(influxdb_write));
+            let influxdb_router = Router::with_state(InfluxdbState {
+                influxdb_handler,
+                sql_handler: self.sql_handler.clone(),
+            })
+            .route("/write", routing::post(influxdb_write))
+            .route("/api/v2/write", routing::post(influxdb_write_v2))
+            .route("/query", routing::get(influxql_query).post(influxql_query))
+            .route("/ping", routing::get(influxdb_ping).head(influxdb_ping))
+            .route("/health", routing::get(influxdb_health));
 
             router = router.nest(&format!("/{}/influxdb", HTTP_API_VERSION), influxdb_router);
         }
@@ -388,9 +442,56 @@ impl HttpServer {
                     // TODO(LFC): make timeout configurable
                     .layer(TimeoutLayer::new(Duration::from_secs(30)))
                     // custom layer
+                    .layer(Extension(self.user_provider.clone()))
                     .layer(middleware::from_fn(context::build_ctx)),
             )
     }
+
+    async fn start_tls(&self, listening: SocketAddr, acceptor: TlsAcceptor) -> Result<SocketAddr> {
+        let (tx, rx) = oneshot::channel();
+        let (app, listener) = {
+            let mut shutdown_tx = self.shutdown_tx.lock().await;
+            ensure!(
+                shutdown_tx.is_none(),
+                AlreadyStartedSnafu { server: "HTTP" }
+            );
+
+            let listener = TcpListener::bind(listening)
+                .await
+                .context(TcpBindSnafu { addr: listening })?;
+            *shutdown_tx = Some(tx);
+
+            (self.make_app(), listener)
+        };
+        let listening = listener
+            .local_addr()
+            .context(TcpBindSnafu { addr: listening })?;
+        info!("HTTPS server is bound to {}", listening);
+
+        // Connections failed in TLS handshakes are dropped, instead of stopping the server.
+        let incoming = TcpListenerStream::new(listener)
+            .map(move |stream| {
+                let acceptor = acceptor.clone();
+                async move { acceptor.accept(stream?).await }
+            })
+            .buffer_unordered(TLS_HANDSHAKE_CONCURRENCY)
+            .filter_map(|stream| async move {
+                match stream {
+                    Ok(stream) => Some(Ok::<_, std::io::Error>(stream)),
+                    Err(e) => {
+                        warn!("Failed to accept HTTPS connection: {}", e);
+                        None
+                    }
+                }
+            });
+        let server = axum::Server::builder(hyper::server::accept::from_stream(incoming))
+            .serve(app.into_make_service());
+
+        let graceful = server.with_graceful_shutdown(rx.map(drop));
+        graceful.await.context(StartHttpSnafu)?;
+
+        Ok(listening)
+    }
 }
 
 #[async_trait]
@@ -408,6 +509,10 @@ impl Server for HttpServer {
     }
 
     async fn start(&self, listening: SocketAddr) -> Result<SocketAddr> {
+        if let Some(acceptor) = self.tls.acceptor() {
+            return self.start_tls(listening, acceptor).await;
+        }
+
         let (tx, rx) = oneshot::channel();
         let server = {
             let mut shutdown_tx = self.shutdown_tx.lock().await;
@@ -471,7 +576,8 @@ mod test {
         let recordbatch = RecordBatch::new(schema.clone(), columns).unwrap();
         let recordbatches = RecordBatches::try_new(schema.clone(), vec![recordbatch]).unwrap();
 
-        let json_resp = JsonResponse::from_output(Ok(Output::RecordBatches(recordbatches))).await;
+        let json_resp =
+            JsonResponse::from_output(vec![Ok(Output::RecordBatches(recordbatches))]).await;
 
         let json_output = &json_resp.output.unwrap()[0];
         if let JsonOutput::Records(r) = json_output {
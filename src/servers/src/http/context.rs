@@ -13,14 +13,37 @@
 // limitations under the License.
 
 use axum::http;
-use axum::http::{Request, StatusCode};
+use axum::http::{HeaderValue, Request, StatusCode};
 use axum::middleware::Next;
-use axum::response::Response;
+use axum::response::{IntoResponse, Response};
 use common_telemetry::error;
+use session::context::{Channel as SessionChannel, QueryContext};
+use snafu::OptionExt;
 
+use crate::auth::{self, UserInfo, UserProviderRef};
 use crate::context::{AuthMethod, Channel, CtxBuilder};
+use crate::error::{self, Result};
+
+pub async fn build_ctx<B>(mut req: Request<B>, next: Next<B>) -> Response {
+    let user_provider = req
+        .extensions()
+        .get::<Option<UserProviderRef>>()
+        .cloned()
+        .flatten();
+    let user = if let Some(user_provider) = user_provider {
+        let header = req.headers().get(http::header::AUTHORIZATION).cloned();
+        match authorize(header, user_provider).await {
+            Ok(user) => Some(user),
+            Err(e) => return e.into_response(),
+        }
+    } else {
+        None
+    };
+    let username = user.as_ref().map(|user| user.username().to_string());
+    let quota_limits = user
+        .map(|user| user.quota_limits().clone())
+        .unwrap_or_default();
 
-pub async fn build_ctx<B>(mut req: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
     let auth_option = req
         .headers()
         .get(http::header::AUTHORIZATION)
@@ -45,16 +68,39 @@ pub async fn build_ctx<B>(mut req: Request<B>, next: Next<B>) -> Result<Response
                 .map(|h| h.to_string()),
         )
         .set_channel(Some(Channel::HTTP))
+        .set_username(username.clone())
         .set_auth_method(auth_option)
+        .set_quota_limits(quota_limits.clone())
         .build()
     {
         Ok(ctx) => {
             req.extensions_mut().insert(ctx);
-            Ok(next.run(req).await)
+            // Each HTTP request is a session of its own.
+            let query_ctx = QueryContext::arc();
+            query_ctx.set_current_user(username);
+            query_ctx.set_quota_limits(quota_limits);
+            query_ctx.set_channel(SessionChannel::Http);
+            req.extensions_mut().insert(query_ctx);
+            next.run(req).await
         }
         Err(e) => {
             error!(e; "fail to create context");
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
         }
     }
 }
+
+/// Authorizes the request by its "Basic" authorization header, returns the user on success.
+async fn authorize(
+    header: Option<HeaderValue>,
+    user_provider: UserProviderRef,
+) -> Result<UserInfo> {
+    let header = header.context(error::AuthHeaderNotFoundSnafu)?;
+    let header = header
+        .to_str()
+        .ok()
+        .context(error::InvalidAuthHeaderSnafu {
+            msg: "header is not valid ASCII",
+        })?;
+    auth::auth_basic(header, &user_provider).await
+}
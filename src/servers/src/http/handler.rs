@@ -17,16 +17,20 @@ use std::time::Instant;
 
 use aide::transform::TransformOperation;
 use axum::extract::{Json, Query, State};
+use axum::Extension;
 use common_error::status_code::StatusCode;
 use common_telemetry::metric;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContextRef;
 
 use crate::http::{ApiState, JsonResponse};
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SqlQuery {
-    pub database: Option<String>,
+    /// The database (schema) that unqualified table names in the SQL are resolved against.
+    #[serde(alias = "database")]
+    pub db: Option<String>,
     pub sql: Option<String>,
 }
 
@@ -34,12 +38,16 @@ pub struct SqlQuery {
 #[axum_macros::debug_handler]
 pub async fn sql(
     State(state): State<ApiState>,
+    Extension(query_ctx): Extension<QueryContextRef>,
     Query(params): Query<SqlQuery>,
 ) -> Json<JsonResponse> {
     let sql_handler = &state.sql_handler;
     let start = Instant::now();
     let resp = if let Some(sql) = &params.sql {
-        JsonResponse::from_output(sql_handler.do_query(sql).await).await
+        if let Some(db) = &params.db {
+            query_ctx.set_current_schema(db);
+        }
+        JsonResponse::from_output(sql_handler.do_query(sql, query_ctx).await).await
     } else {
         JsonResponse::with_error(
             "sql parameter is required.".to_string(),
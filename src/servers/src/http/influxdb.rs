@@ -13,43 +13,344 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::io::Read;
 
+use axum::body::Bytes;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use chrono::{SecondsFormat, TimeZone, Utc};
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_grpc::writer::Precision;
+use common_query::Output;
+use common_recordbatch::util;
+use common_time::util::current_time_millis;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use serde_json::Value;
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::parser::ParserContext;
 
-use crate::error::{Result, TimePrecisionSnafu};
-use crate::influxdb::InfluxdbRequest;
-use crate::query_handler::InfluxdbLineProtocolHandlerRef;
+use crate::error::{
+    CollectRecordbatchSnafu, DecompressInfluxdbRequestSnafu, InternalSnafu,
+    InvalidInfluxdbRequestSnafu, Result, TimePrecisionSnafu,
+};
+use crate::http::HttpRecordsOutput;
+use crate::influxdb::{InfluxdbRequest, INFLUXDB_TIMESTAMP_COLUMN_NAME};
+use crate::influxql::{influxql_to_sql, INFLUXQL_TIME_COLUMN_NAME};
+use crate::query_handler::{InfluxdbLineProtocolHandlerRef, SqlQueryHandlerRef};
+
+/// The InfluxDB version reported to clients, whose APIs are served.
+const INFLUXDB_VERSION: &str = "1.8.10";
+
+/// Max size of a gzip request body after decompression, which protects the server from
+/// decompression bombs.
+const MAX_DECOMPRESSED_BODY_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct InfluxdbState {
+    pub influxdb_handler: InfluxdbLineProtocolHandlerRef,
+    /// Executes the SQL translated from InfluxQL queries.
+    pub sql_handler: SqlQueryHandlerRef,
+}
 
 #[axum_macros::debug_handler]
 pub async fn influxdb_write(
-    State(handler): State<InfluxdbLineProtocolHandlerRef>,
+    State(state): State<InfluxdbState>,
+    Extension(query_ctx): Extension<QueryContextRef>,
     Query(mut params): Query<HashMap<String, String>>,
-    lines: String,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<(StatusCode, ())> {
     let db = params
         .remove("db")
         .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+    write_lines(
+        &state,
+        query_ctx,
+        db,
+        params.get("precision"),
+        &headers,
+        body,
+    )
+    .await
+}
+
+/// The InfluxDB v2 write API, where points are written into the database named by the bucket.
+/// Organizations are ignored.
+#[axum_macros::debug_handler]
+pub async fn influxdb_write_v2(
+    State(state): State<InfluxdbState>,
+    Extension(query_ctx): Extension<QueryContextRef>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, ())> {
+    let bucket = params
+        .get("bucket")
+        .filter(|bucket| !bucket.is_empty())
+        .ok_or_else(|| {
+            InvalidInfluxdbRequestSnafu {
+                msg: "bucket is required",
+            }
+            .build()
+        })?;
+    // Buckets mapped from v1 databases are named as `<db>/<retention policy>`.
+    let db = bucket
+        .split_once('/')
+        .map(|(db, _)| db)
+        .unwrap_or(bucket)
+        .to_string();
+    write_lines(
+        &state,
+        query_ctx,
+        db,
+        params.get("precision"),
+        &headers,
+        body,
+    )
+    .await
+}
 
-    let precision = params
-        .get("precision")
-        .map(|val| parse_time_precision(val))
-        .transpose()?;
+async fn write_lines(
+    state: &InfluxdbState,
+    query_ctx: QueryContextRef,
+    db: String,
+    precision: Option<&String>,
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, ())> {
+    let precision = precision.map(|val| parse_time_precision(val)).transpose()?;
     let request = InfluxdbRequest {
         precision,
-        lines,
+        lines: decode_body(headers, body)?,
         db,
     };
-    handler.exec(&request).await?;
+    state.influxdb_handler.exec(&request, query_ctx).await?;
     Ok((StatusCode::NO_CONTENT, ()))
 }
 
+/// Decodes the lines in the body, which may be compressed by gzip.
+fn decode_body(headers: &HeaderMap, body: Bytes) -> Result<String> {
+    let gzipped = headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|encoding| encoding.to_str().ok())
+        .map(|encoding| encoding.eq_ignore_ascii_case("gzip"))
+        .unwrap_or(false);
+    if gzipped {
+        let mut lines = String::new();
+        let size = GzDecoder::new(&body[..])
+            .take(MAX_DECOMPRESSED_BODY_SIZE + 1)
+            .read_to_string(&mut lines)
+            .context(DecompressInfluxdbRequestSnafu)?;
+        if size as u64 > MAX_DECOMPRESSED_BODY_SIZE {
+            return InvalidInfluxdbRequestSnafu {
+                msg: format!(
+                    "decompressed body exceeds {} bytes",
+                    MAX_DECOMPRESSED_BODY_SIZE
+                ),
+            }
+            .fail();
+        }
+        Ok(lines)
+    } else {
+        String::from_utf8(body.to_vec()).map_err(|e| {
+            InvalidInfluxdbRequestSnafu {
+                msg: format!("lines are not valid UTF-8: {}", e),
+            }
+            .build()
+        })
+    }
+}
+
+#[axum_macros::debug_handler]
+pub async fn influxdb_ping() -> impl IntoResponse {
+    (
+        StatusCode::NO_CONTENT,
+        [("X-Influxdb-Version", INFLUXDB_VERSION)],
+    )
+}
+
+#[axum_macros::debug_handler]
+pub async fn influxdb_health() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "name": "influxdb",
+        "message": "ready for queries and writes",
+        "status": "pass",
+        "checks": [],
+        "version": INFLUXDB_VERSION,
+    }))
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct InfluxqlResponse {
+    results: Vec<InfluxqlResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InfluxqlResult {
+    statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    series: Vec<InfluxqlSeries>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InfluxqlSeries {
+    name: String,
+    columns: Vec<String>,
+    values: Vec<Vec<Value>>,
+}
+
+/// Adds the parameters in a `application/x-www-form-urlencoded` body, where POST requests may
+/// put them instead of the query string.
+fn add_form_params(
+    params: &mut HashMap<String, String>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<()> {
+    let is_form = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(|content_type| content_type.starts_with("application/x-www-form-urlencoded"))
+        .unwrap_or(false);
+    if is_form {
+        let form: Vec<(String, String)> = serde_urlencoded::from_bytes(body).map_err(|e| {
+            InvalidInfluxdbRequestSnafu {
+                msg: format!("invalid form body: {}", e),
+            }
+            .build()
+        })?;
+        params.extend(form);
+    }
+    Ok(())
+}
+
+/// The InfluxDB v1 query API for basic InfluxQL `SELECT`s, see [crate::influxql]. Results of a
+/// query are returned as a single series, whose columns include the tags grouped by.
+#[axum_macros::debug_handler]
+pub async fn influxql_query(
+    State(state): State<InfluxdbState>,
+    Extension(query_ctx): Extension<QueryContextRef>,
+    Query(mut params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<InfluxqlResponse>> {
+    add_form_params(&mut params, &headers, &body)?;
+    let query = params.get("q").ok_or_else(|| {
+        InvalidInfluxdbRequestSnafu {
+            msg: "missing required parameter \"q\"",
+        }
+        .build()
+    })?;
+    if let Some(db) = params.get("db") {
+        query_ctx.set_current_schema(db);
+    }
+    let epoch = params.get("epoch").map(String::as_str);
+
+    let mut response = InfluxqlResponse::default();
+    for (statement_id, statement) in ParserContext::split_statements(query)
+        .into_iter()
+        .enumerate()
+    {
+        let result = match execute_influxql(&state, statement, epoch, query_ctx.clone()).await {
+            Ok(series) => InfluxqlResult {
+                statement_id,
+                series: vec![series],
+                error: None,
+            },
+            Err(e) => InfluxqlResult {
+                statement_id,
+                series: vec![],
+                error: Some(e.to_string()),
+            },
+        };
+        response.results.push(result);
+    }
+    Ok(Json(response))
+}
+
+async fn execute_influxql(
+    state: &InfluxdbState,
+    query: &str,
+    epoch: Option<&str>,
+    query_ctx: QueryContextRef,
+) -> Result<InfluxqlSeries> {
+    let epoch = epoch.map(parse_time_precision).transpose()?;
+    let query = influxql_to_sql(query, current_time_millis())?;
+    let output = state
+        .sql_handler
+        .do_statement_query(&query.sql, query_ctx)
+        .await?;
+    let batches = match output {
+        Output::Stream(stream) => util::collect(stream)
+            .await
+            .context(CollectRecordbatchSnafu)?,
+        Output::RecordBatches(batches) => batches.take(),
+        Output::AffectedRows(_) => vec![],
+    };
+    let records = HttpRecordsOutput::try_from(batches)
+        .map_err(|err_msg| InternalSnafu { err_msg }.build())?;
+
+    let columns = records
+        .schema
+        .map(|schema| {
+            schema
+                .column_schemas
+                .into_iter()
+                .map(|column| {
+                    if column.name == INFLUXDB_TIMESTAMP_COLUMN_NAME {
+                        INFLUXQL_TIME_COLUMN_NAME.to_string()
+                    } else {
+                        column.name
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let time_index = columns
+        .iter()
+        .position(|column| column == INFLUXQL_TIME_COLUMN_NAME);
+    let mut values = records.rows;
+    if let Some(time_index) = time_index {
+        for row in &mut values {
+            if let Some(millis) = row[time_index].as_i64() {
+                row[time_index] = format_time(millis, epoch);
+            }
+        }
+    }
+
+    Ok(InfluxqlSeries {
+        name: query.measurement,
+        columns,
+        values,
+    })
+}
+
+/// Formats the time in milliseconds as RFC3339, or as the number of `epoch` units.
+fn format_time(millis: i64, epoch: Option<Precision>) -> Value {
+    match epoch {
+        Some(Precision::NANOSECOND) => Value::from(millis * 1_000_000),
+        Some(Precision::MICROSECOND) => Value::from(millis * 1000),
+        Some(Precision::MILLISECOND) => Value::from(millis),
+        Some(Precision::SECOND) => Value::from(millis / 1000),
+        Some(Precision::MINUTE) => Value::from(millis / (60 * 1000)),
+        Some(Precision::HOUR) => Value::from(millis / (60 * 60 * 1000)),
+        None => Value::from(
+            Utc.timestamp_millis(millis)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        ),
+    }
+}
+
 fn parse_time_precision(value: &str) -> Result<Precision> {
     match value {
-        "n" => Ok(Precision::NANOSECOND),
-        "u" => Ok(Precision::MICROSECOND),
+        // v2 APIs name nanoseconds and microseconds as "ns" and "us".
+        "n" | "ns" => Ok(Precision::NANOSECOND),
+        "u" | "us" => Ok(Precision::MICROSECOND),
         "ms" => Ok(Precision::MILLISECOND),
         "s" => Ok(Precision::SECOND),
         "m" => Ok(Precision::MINUTE),
@@ -63,9 +364,14 @@ fn parse_time_precision(value: &str) -> Result<Precision> {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
+    use axum::http::HeaderValue;
     use common_grpc::writer::Precision;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
 
-    use crate::http::influxdb::parse_time_precision;
+    use super::*;
 
     #[test]
     fn test_parse_time_precision() {
@@ -75,6 +381,65 @@ mod tests {
         assert_eq!(Precision::SECOND, parse_time_precision("s").unwrap());
         assert_eq!(Precision::MINUTE, parse_time_precision("m").unwrap());
         assert_eq!(Precision::HOUR, parse_time_precision("h").unwrap());
+        assert_eq!(Precision::NANOSECOND, parse_time_precision("ns").unwrap());
+        assert_eq!(Precision::MICROSECOND, parse_time_precision("us").unwrap());
         assert!(parse_time_precision("unknown").is_err());
     }
+
+    #[test]
+    fn test_decode_body() {
+        let lines = "monitor,host=host1 cpu=1.2 1664370459457010101";
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            lines,
+            decode_body(&headers, Bytes::from(lines.to_string())).unwrap()
+        );
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(lines.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert_eq!(lines, decode_body(&headers, Bytes::from(gzipped)).unwrap());
+
+        assert!(decode_body(&headers, Bytes::from(lines.to_string())).is_err());
+
+        // decompression bomb
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        let zeros = vec![b'0'; 1024 * 1024];
+        for _ in 0..=MAX_DECOMPRESSED_BODY_SIZE / zeros.len() as u64 {
+            encoder.write_all(&zeros).unwrap();
+        }
+        let gzipped = encoder.finish().unwrap();
+        assert!(decode_body(&headers, Bytes::from(gzipped)).is_err());
+    }
+
+    #[test]
+    fn test_add_form_params() {
+        let mut params = HashMap::from([("db".to_string(), "public".to_string())]);
+        let body = b"q=SELECT+mean%28cpu%29+FROM+monitor&db=influxdb";
+        let mut headers = HeaderMap::new();
+        add_form_params(&mut params, &headers, body).unwrap();
+        assert_eq!(1, params.len());
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        add_form_params(&mut params, &headers, body).unwrap();
+        assert_eq!("SELECT mean(cpu) FROM monitor", params["q"]);
+        assert_eq!("influxdb", params["db"]);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(
+            Value::from(1_500),
+            format_time(1_500, Some(Precision::MILLISECOND))
+        );
+        assert_eq!(Value::from(1), format_time(1_500, Some(Precision::SECOND)));
+        assert_eq!(
+            Value::from("1970-01-01T00:00:01.500Z"),
+            format_time(1_500, None)
+        );
+    }
 }
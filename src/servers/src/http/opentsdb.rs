@@ -16,13 +16,14 @@ use std::collections::HashMap;
 
 use axum::extract::{Query, RawBody, State};
 use axum::http::StatusCode as HttpStatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use hyper::Body;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContextRef;
 use snafu::ResultExt;
 
 use crate::error::{self, Error, Result};
-use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::codec::{group_by_metric, DataPoint};
 use crate::query_handler::OpentsdbProtocolHandlerRef;
 
 #[derive(Serialize, Deserialize)]
@@ -75,6 +76,7 @@ pub enum OpentsdbPutResponse {
 #[axum_macros::debug_handler]
 pub async fn put(
     State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Extension(query_ctx): Extension<QueryContextRef>,
     Query(params): Query<HashMap<String, String>>,
     RawBody(body): RawBody,
 ) -> Result<(HttpStatusCode, Json<OpentsdbPutResponse>)> {
@@ -82,10 +84,22 @@ pub async fn put(
     let details = params.contains_key("details");
 
     let data_points = parse_data_points(body).await?;
+    let errors = if details {
+        Some(Vec::with_capacity(data_points.len()))
+    } else {
+        None
+    };
+
+    // The data points of each metric are put as one batch, and succeed or fail together.
+    let groups = group_by_metric(data_points, |data_point| data_point.metric.as_str());
 
     let response = if !summary && !details {
-        for data_point in data_points.into_iter() {
-            if let Err(e) = opentsdb_handler.exec(&data_point.into()).await {
+        for group in groups.into_iter() {
+            let data_points = group
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<DataPoint>>();
+            if let Err(e) = opentsdb_handler.exec(&data_points, query_ctx.clone()).await {
                 // Not debugging purpose, failed fast.
                 return error::InternalSnafu {
                     err_msg: e.to_string(),
@@ -98,19 +112,22 @@ pub async fn put(
         let mut response = OpentsdbDebuggingResponse {
             success: 0,
             failed: 0,
-            errors: if details {
-                Some(Vec::with_capacity(data_points.len()))
-            } else {
-                None
-            },
+            errors,
         };
 
-        for data_point in data_points.into_iter() {
-            let result = opentsdb_handler.exec(&data_point.clone().into()).await;
+        for group in groups.into_iter() {
+            let data_points = group
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect::<Vec<DataPoint>>();
+            let result = opentsdb_handler.exec(&data_points, query_ctx.clone()).await;
             match result {
-                Ok(()) => response.on_success(),
+                Ok(()) => response.on_success(group.len()),
                 Err(e) => {
-                    response.on_failed(data_point, e);
+                    for data_point in group {
+                        response.on_failed(data_point, &e);
+                    }
                 }
             }
         }
@@ -146,11 +163,11 @@ pub struct OpentsdbDebuggingResponse {
 }
 
 impl OpentsdbDebuggingResponse {
-    fn on_success(&mut self) {
-        self.success += 1;
+    fn on_success(&mut self, count: usize) {
+        self.success += count as i32;
     }
 
-    fn on_failed(&mut self, datapoint: DataPointRequest, error: Error) {
+    fn on_failed(&mut self, datapoint: DataPointRequest, error: &Error) {
         self.failed += 1;
 
         if let Some(details) = self.errors.as_mut() {
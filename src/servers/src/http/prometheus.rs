@@ -16,11 +16,13 @@ use api::prometheus::remote::{ReadRequest, WriteRequest};
 use axum::extract::{Query, RawBody, State};
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
+use axum::Extension;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use hyper::Body;
 use prost::Message;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
@@ -43,13 +45,18 @@ impl Default for DatabaseQuery {
 #[axum_macros::debug_handler]
 pub async fn remote_write(
     State(handler): State<PrometheusProtocolHandlerRef>,
+    Extension(query_ctx): Extension<QueryContextRef>,
     Query(params): Query<DatabaseQuery>,
     RawBody(body): RawBody,
 ) -> Result<(StatusCode, ())> {
     let request = decode_remote_write_request(body).await?;
 
     handler
-        .write(params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME), request)
+        .write(
+            params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+            request,
+            query_ctx,
+        )
         .await?;
 
     Ok((StatusCode::NO_CONTENT, ()))
@@ -71,13 +78,18 @@ impl IntoResponse for PrometheusResponse {
 #[axum_macros::debug_handler]
 pub async fn remote_read(
     State(handler): State<PrometheusProtocolHandlerRef>,
+    Extension(query_ctx): Extension<QueryContextRef>,
     Query(params): Query<DatabaseQuery>,
     RawBody(body): RawBody,
 ) -> Result<PrometheusResponse> {
     let request = decode_remote_read_request(body).await?;
 
     handler
-        .read(params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME), request)
+        .read(
+            params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+            request,
+            query_ctx,
+        )
         .await
 }
 
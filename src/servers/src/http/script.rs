@@ -15,6 +15,7 @@
 use std::time::Instant;
 
 use axum::extract::{Json, Query, RawBody, State};
+use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_error::ext::ErrorExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -60,7 +61,10 @@ pub async fn scripts(
 
         let script = unwrap_or_json_err!(String::from_utf8(bytes.to_vec()));
 
-        let body = match script_handler.insert_script(name.unwrap(), &script).await {
+        let body = match script_handler
+            .insert_script(params.schema(), name.unwrap(), &script)
+            .await
+        {
             Ok(()) => JsonResponse::with_output(None),
             Err(e) => json_err!(format!("Insert script error: {}", e), e.status_code()),
         };
@@ -73,9 +77,18 @@ pub async fn scripts(
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ScriptQuery {
+    /// The database (schema) of the script, whose udf is only callable in the database.
+    #[serde(alias = "database")]
+    pub db: Option<String>,
     pub name: Option<String>,
 }
 
+impl ScriptQuery {
+    fn schema(&self) -> &str {
+        self.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME)
+    }
+}
+
 /// Handler to execute script
 #[axum_macros::debug_handler]
 pub async fn run_script(
@@ -90,8 +103,10 @@ pub async fn run_script(
             json_err!("invalid name");
         }
 
-        let output = script_handler.execute_script(name.unwrap()).await;
-        let resp = JsonResponse::from_output(output).await;
+        let output = script_handler
+            .execute_script(params.schema(), name.unwrap())
+            .await;
+        let resp = JsonResponse::from_output(vec![output]).await;
 
         Json(resp.with_execution_time(start.elapsed().as_millis()))
     } else {
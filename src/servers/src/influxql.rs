@@ -0,0 +1,397 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Translates basic InfluxQL queries into SQL over the tables created by the InfluxDB line
+//! protocol, like `SELECT mean(cpu) FROM monitor WHERE time > now() - 1h GROUP BY time(1m), host`.
+//!
+//! Durations and `now()` are evaluated to milliseconds, `time` refers to the timestamp column,
+//! and `GROUP BY time(<interval>)` groups rows into buckets of the interval.
+
+use snafu::{ensure, OptionExt};
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Ident, OrderByExpr, SelectItem, SetExpr,
+    Statement, Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace, Word};
+
+use crate::error::{InvalidInfluxqlSnafu, Result};
+use crate::influxdb::INFLUXDB_TIMESTAMP_COLUMN_NAME;
+
+/// Name of the time column in InfluxQL.
+pub const INFLUXQL_TIME_COLUMN_NAME: &str = "time";
+
+/// An InfluxQL query translated into SQL.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InfluxqlQuery {
+    pub sql: String,
+    /// The measurement (table) queried, which names the series of the results.
+    pub measurement: String,
+}
+
+/// Translates the InfluxQL `query` into SQL, with `now()` evaluated to `now_millis`.
+pub fn influxql_to_sql(query: &str, now_millis: i64) -> Result<InfluxqlQuery> {
+    let dialect = GenericDialect {};
+    let tokens = Tokenizer::new(&dialect, query)
+        .tokenize()
+        .map_err(|e| invalid_influxql(query, e.to_string()))?;
+    let tokens = rewrite_tokens(query, tokens, now_millis)?;
+
+    let statement = Parser::new(tokens, &dialect)
+        .parse_statement()
+        .map_err(|e| invalid_influxql(query, e.to_string()))?;
+    let mut query_ast = match statement {
+        Statement::Query(query_ast) => query_ast,
+        _ => {
+            return InvalidInfluxqlSnafu {
+                query,
+                msg: "only SELECT is supported",
+            }
+            .fail()
+        }
+    };
+    let select = match &mut query_ast.body {
+        SetExpr::Select(select) => select,
+        _ => {
+            return InvalidInfluxqlSnafu {
+                query,
+                msg: "only SELECT is supported",
+            }
+            .fail()
+        }
+    };
+    ensure!(
+        select.from.len() == 1 && select.from[0].joins.is_empty(),
+        InvalidInfluxqlSnafu {
+            query,
+            msg: "expect exactly one measurement",
+        }
+    );
+
+    // `FROM "db"."rp"."measurement"` reads the measurement in the database, retention policies
+    // are ignored.
+    let measurement = match &mut select.from[0].relation {
+        sqlparser::ast::TableFactor::Table { name, .. } => {
+            if name.0.len() == 3 {
+                let _ = name.0.remove(1);
+            }
+            name.0
+                .last()
+                .map(|ident| ident.value.clone())
+                .context(InvalidInfluxqlSnafu {
+                    query,
+                    msg: "measurement is required",
+                })?
+        }
+        _ => {
+            return InvalidInfluxqlSnafu {
+                query,
+                msg: "subqueries are not supported",
+            }
+            .fail()
+        }
+    };
+
+    let mut interval = None;
+    let mut group_by = Vec::with_capacity(select.group_by.len());
+    for expr in select.group_by.drain(..) {
+        match time_interval(&expr) {
+            Some(millis) => interval = Some(millis.map_err(|msg| invalid_influxql(query, msg))?),
+            None => group_by.push(expr),
+        }
+    }
+    select.group_by = group_by;
+
+    let mut has_aggregation = false;
+    let mut has_wildcard = false;
+    for item in &mut select.projection {
+        match item {
+            SelectItem::UnnamedExpr(Expr::Function(function)) => {
+                has_aggregation = true;
+                // InfluxDB names the result columns after the functions.
+                let alias = Ident::new(function_name(function));
+                rename_function(function);
+                *item = SelectItem::ExprWithAlias {
+                    expr: Expr::Function(function.clone()),
+                    alias,
+                };
+            }
+            SelectItem::ExprWithAlias {
+                expr: Expr::Function(function),
+                ..
+            } => {
+                has_aggregation = true;
+                rename_function(function);
+            }
+            SelectItem::Wildcard | SelectItem::QualifiedWildcard(_) => has_wildcard = true,
+            _ => {}
+        }
+    }
+
+    match interval {
+        Some(interval) => {
+            let bucket = time_bucket(interval);
+            select.projection.insert(
+                0,
+                SelectItem::ExprWithAlias {
+                    expr: bucket.clone(),
+                    alias: Ident::new(INFLUXQL_TIME_COLUMN_NAME),
+                },
+            );
+            select.group_by.insert(0, bucket.clone());
+            for order_by in &mut query_ast.order_by {
+                if is_timestamp_column(&order_by.expr) {
+                    order_by.expr = bucket.clone();
+                }
+            }
+            if query_ast.order_by.is_empty() {
+                query_ast.order_by.push(OrderByExpr {
+                    expr: bucket,
+                    asc: None,
+                    nulls_first: None,
+                });
+            }
+        }
+        // Raw points always come with their time.
+        None if !has_aggregation && !has_wildcard => {
+            select.projection.insert(
+                0,
+                SelectItem::ExprWithAlias {
+                    expr: Expr::Identifier(Ident::new(INFLUXDB_TIMESTAMP_COLUMN_NAME)),
+                    alias: Ident::new(INFLUXQL_TIME_COLUMN_NAME),
+                },
+            );
+        }
+        None => {}
+    }
+
+    Ok(InfluxqlQuery {
+        sql: query_ast.to_string(),
+        measurement,
+    })
+}
+
+fn invalid_influxql(query: &str, msg: impl Into<String>) -> crate::error::Error {
+    InvalidInfluxqlSnafu {
+        query,
+        msg: msg.into(),
+    }
+    .build()
+}
+
+/// Rewrites the InfluxQL specific tokens: durations, `now()`, `time` and `fill()`.
+fn rewrite_tokens(query: &str, tokens: Vec<Token>, now_millis: i64) -> Result<Vec<Token>> {
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1)) {
+            // The tokenizer splits a duration like `5m` into a number and a word.
+            (Token::Number(n, _), Some(Token::Word(unit))) if unit.quote_style.is_none() => {
+                let millis = duration_to_millis(n, &unit.value)
+                    .map_err(|msg| invalid_influxql(query, msg))?;
+                rewritten.push(Token::Number(millis.to_string(), false));
+                i += 2;
+            }
+            (Token::Word(word), Some(Token::LParen))
+                if word.quote_style.is_none() && word.value.eq_ignore_ascii_case("now") =>
+            {
+                ensure!(
+                    tokens.get(i + 2) == Some(&Token::RParen),
+                    InvalidInfluxqlSnafu {
+                        query,
+                        msg: "now() takes no arguments",
+                    }
+                );
+                rewritten.push(Token::Number(now_millis.to_string(), false));
+                i += 3;
+            }
+            (Token::Word(word), Some(Token::LParen))
+                if word.quote_style.is_none() && word.value.eq_ignore_ascii_case("fill") =>
+            {
+                let end = tokens[i..]
+                    .iter()
+                    .position(|token| token == &Token::RParen)
+                    .context(InvalidInfluxqlSnafu {
+                        query,
+                        msg: "unclosed fill()",
+                    })?;
+                let option = tokens[i + 2..i + end]
+                    .iter()
+                    .filter(|token| !matches!(token, Token::Whitespace(_)))
+                    .map(|token| token.to_string())
+                    .collect::<String>();
+                // Empty buckets are never returned, which is the same as `fill(none)`.
+                ensure!(
+                    option.eq_ignore_ascii_case("none") || option.eq_ignore_ascii_case("null"),
+                    InvalidInfluxqlSnafu {
+                        query,
+                        msg: format!("fill({}) is not supported", option),
+                    }
+                );
+                i += end + 1;
+            }
+            (Token::Word(word), next)
+                if word.value.eq_ignore_ascii_case(INFLUXQL_TIME_COLUMN_NAME)
+                    && next != Some(&Token::LParen) =>
+            {
+                rewritten.push(Token::Word(Word {
+                    value: INFLUXDB_TIMESTAMP_COLUMN_NAME.to_string(),
+                    quote_style: None,
+                    keyword: sqlparser::keywords::Keyword::NoKeyword,
+                }));
+                i += 1;
+            }
+            (token, _) => {
+                rewritten.push(token.clone());
+                i += 1;
+            }
+        }
+    }
+    // Keeps the tokens separated where the removed ones were.
+    rewritten.push(Token::Whitespace(Whitespace::Space));
+    Ok(rewritten)
+}
+
+/// Converts the InfluxQL duration `<n><unit>` to milliseconds.
+fn duration_to_millis(n: &str, unit: &str) -> std::result::Result<i64, String> {
+    let n = n
+        .parse::<i64>()
+        .map_err(|_| format!("invalid duration: {}{}", n, unit))?;
+    let millis = match unit {
+        "ns" => n / 1_000_000,
+        "u" | "µ" => n / 1000,
+        "ms" => n,
+        "s" => n * 1000,
+        "m" => n * 60 * 1000,
+        "h" => n * 60 * 60 * 1000,
+        "d" => n * 24 * 60 * 60 * 1000,
+        "w" => n * 7 * 24 * 60 * 60 * 1000,
+        _ => return Err(format!("invalid duration: {}{}", n, unit)),
+    };
+    Ok(millis)
+}
+
+/// Returns the interval in milliseconds if `expr` is `time(<interval>)`.
+fn time_interval(expr: &Expr) -> Option<std::result::Result<i64, String>> {
+    let function = match expr {
+        Expr::Function(function) if function_name(function) == INFLUXQL_TIME_COLUMN_NAME => {
+            function
+        }
+        _ => return None,
+    };
+    let interval = match &function.args[..] {
+        [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::Number(n, _))))] => n
+            .parse::<i64>()
+            .ok()
+            .filter(|interval| *interval > 0)
+            .ok_or_else(|| format!("invalid interval of time(): {}", n)),
+        _ => Err("time() expects an interval like time(1m)".to_string()),
+    };
+    Some(interval)
+}
+
+/// The start of the bucket of the interval that rows fall into, in milliseconds.
+fn time_bucket(interval: i64) -> Expr {
+    let sql = format!(
+        "(CAST({} AS BIGINT) / {}) * {}",
+        INFLUXDB_TIMESTAMP_COLUMN_NAME, interval, interval
+    );
+    let dialect = GenericDialect {};
+    // Safety: the expression is always valid.
+    let tokens = Tokenizer::new(&dialect, &sql).tokenize().unwrap();
+    Parser::new(tokens, &dialect).parse_expr().unwrap()
+}
+
+fn is_timestamp_column(expr: &Expr) -> bool {
+    matches!(expr, Expr::Identifier(ident) if ident.value == INFLUXDB_TIMESTAMP_COLUMN_NAME)
+}
+
+fn function_name(function: &Function) -> String {
+    function.name.to_string().to_lowercase()
+}
+
+/// Maps InfluxQL functions to their SQL counterparts.
+fn rename_function(function: &mut Function) {
+    if function_name(function) == "mean" {
+        function.name = sqlparser::ast::ObjectName(vec![Ident::new("avg")]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000_000_000;
+
+    fn to_sql(query: &str) -> String {
+        influxql_to_sql(query, NOW).unwrap().sql
+    }
+
+    #[test]
+    fn test_raw_query() {
+        let query = influxql_to_sql("SELECT cpu, host FROM \"monitor\"", NOW).unwrap();
+        assert_eq!("monitor", query.measurement);
+        assert_eq!("SELECT ts AS time, cpu, host FROM \"monitor\"", query.sql);
+
+        assert_eq!(
+            "SELECT * FROM monitor WHERE ts > 1000000000 - 3600000 ORDER BY ts DESC LIMIT 10",
+            to_sql("SELECT * FROM monitor WHERE time > now() - 1h ORDER BY time DESC LIMIT 10")
+        );
+        assert_eq!(
+            "SELECT * FROM public.monitor",
+            to_sql("SELECT * FROM \"public\".\"autogen\".monitor").replace('"', "")
+        );
+    }
+
+    #[test]
+    fn test_group_by_time() {
+        assert_eq!(
+            "SELECT (CAST(ts AS BIGINT) / 60000) * 60000 AS time, avg(cpu) AS mean, max(cpu) AS peak \
+             FROM monitor WHERE ts >= 1000000000 - 86400000 \
+             GROUP BY (CAST(ts AS BIGINT) / 60000) * 60000, host \
+             ORDER BY (CAST(ts AS BIGINT) / 60000) * 60000",
+            to_sql(
+                "SELECT mean(cpu), max(cpu) AS peak FROM monitor WHERE time >= now() - 1d \
+                 GROUP BY time(1m), host fill(none)"
+            )
+        );
+        assert_eq!(
+            "SELECT count(cpu) AS count FROM monitor",
+            to_sql("SELECT count(cpu) FROM monitor")
+        );
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        assert!(influxql_to_sql("SHOW MEASUREMENTS", NOW).is_err());
+        assert!(influxql_to_sql("SELECT * FROM a, b", NOW).is_err());
+        assert!(influxql_to_sql("SELECT mean(cpu) FROM m GROUP BY time(host)", NOW).is_err());
+        assert!(influxql_to_sql("SELECT mean(cpu) FROM m GROUP BY time(1m) fill(0)", NOW).is_err());
+        assert!(influxql_to_sql("SELECT * FROM m WHERE time > now() - 1y", NOW).is_err());
+    }
+
+    #[test]
+    fn test_duration_to_millis() {
+        assert_eq!(Ok(1), duration_to_millis("1000000", "ns"));
+        assert_eq!(Ok(1), duration_to_millis("1000", "u"));
+        assert_eq!(Ok(5), duration_to_millis("5", "ms"));
+        assert_eq!(Ok(5000), duration_to_millis("5", "s"));
+        assert_eq!(Ok(300_000), duration_to_millis("5", "m"));
+        assert_eq!(Ok(3_600_000), duration_to_millis("1", "h"));
+        assert_eq!(Ok(86_400_000), duration_to_millis("1", "d"));
+        assert_eq!(Ok(604_800_000), duration_to_millis("1", "w"));
+        assert!(duration_to_millis("1", "y").is_err());
+    }
+}
@@ -14,11 +14,13 @@
 
 #![feature(assert_matches)]
 
+pub mod auth;
 pub mod context;
 pub mod error;
 pub mod grpc;
 pub mod http;
 pub mod influxdb;
+pub mod influxql;
 pub mod line_writer;
 pub mod mysql;
 pub mod opentsdb;
@@ -27,3 +29,4 @@ pub mod prometheus;
 pub mod query_handler;
 pub mod server;
 mod shutdown;
+pub mod tls;
@@ -26,6 +26,7 @@ use datatypes::vectors::StringVector;
 use once_cell::sync::Lazy;
 use regex::bytes::RegexSet;
 use regex::Regex;
+use session::context::QueryContextRef;
 
 // TODO(LFC): Include GreptimeDB's version and git commit tag etc.
 const MYSQL_VERSION: &str = "8.0.26";
@@ -41,6 +42,8 @@ static SHOW_VARIABLES_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new("(?i)^(SHOW VARIABLES(.*))").unwrap());
 static SELECT_VERSION_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)^(SELECT VERSION\(\s*\))").unwrap());
+static SELECT_DATABASE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(SELECT DATABASE\(\s*\))").unwrap());
 
 // SELECT TIMEDIFF(NOW(), UTC_TIMESTAMP());
 static SELECT_TIME_DIFF_FUNC_PATTERN: Lazy<Regex> =
@@ -164,7 +167,19 @@ fn show_variables(name: &str, value: &str) -> RecordBatches {
         .unwrap()
 }
 
-fn select_variable(query: &str) -> Option<Output> {
+// Looks up the value of a variable, session variables set by `SET` take precedence over the
+// faked ones.
+fn variable_value(name: &str, query_ctx: &QueryContextRef) -> String {
+    let session_name = name.strip_prefix("session.").unwrap_or(name);
+    let session_value = if session_name == "time_zone" {
+        query_ctx.time_zone()
+    } else {
+        query_ctx.variable(session_name)
+    };
+    session_value.unwrap_or_else(|| VAR_VALUES.get(name).unwrap_or(&"0").to_string())
+}
+
+fn select_variable(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     let mut fields = vec![];
     let mut values = vec![];
 
@@ -190,8 +205,8 @@ fn select_variable(query: &str) -> Option<Output> {
         match var_as.len() {
             1 => {
                 // @@aa
-                let value = VAR_VALUES.get(var_as[0]).unwrap_or(&"0");
-                values.push(Arc::new(StringVector::from(vec![*value])) as _);
+                let value = variable_value(var_as[0], query_ctx);
+                values.push(Arc::new(StringVector::from(vec![value.as_str()])) as _);
 
                 // field is '@@aa'
                 fields.push(ColumnSchema::new(
@@ -203,8 +218,8 @@ fn select_variable(query: &str) -> Option<Output> {
             2 => {
                 // @@bb as cc:
                 // var is 'bb'.
-                let value = VAR_VALUES.get(var_as[0]).unwrap_or(&"0");
-                values.push(Arc::new(StringVector::from(vec![*value])) as _);
+                let value = variable_value(var_as[0], query_ctx);
+                values.push(Arc::new(StringVector::from(vec![value.as_str()])) as _);
 
                 // field is 'cc'.
                 fields.push(ColumnSchema::new(
@@ -223,12 +238,12 @@ fn select_variable(query: &str) -> Option<Output> {
     Some(Output::RecordBatches(batches))
 }
 
-fn check_select_variable(query: &str) -> Option<Output> {
+fn check_select_variable(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     if vec![&SELECT_VAR_PATTERN, &MYSQL_CONN_JAVA_PATTERN]
         .iter()
         .any(|r| r.is_match(query))
     {
-        select_variable(query)
+        select_variable(query, query_ctx)
     } else {
         None
     }
@@ -248,13 +263,15 @@ fn check_show_variables(query: &str) -> Option<Output> {
 }
 
 // Check for SET or others query, this is the final check of the federated query.
-fn check_others(query: &str) -> Option<Output> {
+fn check_others(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     if OTHER_NOT_SUPPORTED_STMT.is_match(query.as_bytes()) {
         return Some(Output::RecordBatches(RecordBatches::empty()));
     }
 
     let recordbatches = if SELECT_VERSION_PATTERN.is_match(query) {
         Some(select_function("version()", MYSQL_VERSION))
+    } else if SELECT_DATABASE_PATTERN.is_match(query) {
+        Some(select_function("DATABASE()", &query_ctx.current_schema()))
     } else if SELECT_TIME_DIFF_FUNC_PATTERN.is_match(query) {
         Some(select_function(
             "TIMEDIFF(NOW(), UTC_TIMESTAMP())",
@@ -268,9 +285,9 @@ fn check_others(query: &str) -> Option<Output> {
 
 // Check whether the query is a federated or driver setup command,
 // and return some faked results if there are any.
-pub fn check(query: &str) -> Option<Output> {
+pub fn check(query: &str, query_ctx: QueryContextRef) -> Option<Output> {
     // First to check the query is like "select @@variables".
-    let output = check_select_variable(query);
+    let output = check_select_variable(query, &query_ctx);
     if output.is_some() {
         return output;
     }
@@ -282,25 +299,27 @@ pub fn check(query: &str) -> Option<Output> {
     }
 
     // Last check.
-    check_others(query)
+    check_others(query, &query_ctx)
 }
 
 #[cfg(test)]
 mod test {
+    use session::context::QueryContext;
+
     use super::*;
 
     #[test]
     fn test_check() {
         let query = "select 1";
-        let result = check(query);
+        let result = check(query, QueryContext::arc());
         assert!(result.is_none());
 
         let query = "select versiona";
-        let output = check(query);
+        let output = check(query, QueryContext::arc());
         assert!(output.is_none());
 
         fn test(query: &str, expected: Vec<&str>) {
-            let output = check(query);
+            let output = check(query, QueryContext::arc());
             match output.unwrap() {
                 Output::RecordBatches(r) => {
                     assert_eq!(r.pretty_print().lines().collect::<Vec<_>>(), expected)
@@ -385,4 +404,41 @@ mod test {
         ];
         test(query, expected);
     }
+
+    #[test]
+    fn test_check_with_session() {
+        fn test(query: &str, query_ctx: QueryContextRef, expected: Vec<&str>) {
+            match check(query, query_ctx).unwrap() {
+                Output::RecordBatches(r) => {
+                    assert_eq!(r.pretty_print().lines().collect::<Vec<_>>(), expected)
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_schema("my_db");
+        query_ctx.set_time_zone(Some("+08:00".to_string()));
+        query_ctx.set_variable("autocommit", "1".to_string());
+
+        let query = "SELECT DATABASE()";
+        let expected = vec![
+            "+------------+",
+            "| DATABASE() |",
+            "+------------+",
+            "| my_db      |",
+            "+------------+",
+        ];
+        test(query, query_ctx.clone(), expected);
+
+        let query = "select @@time_zone, @@session.autocommit";
+        let expected = vec![
+            "+-------------+----------------------+",
+            "| @@time_zone | @@session.autocommit |",
+            "+-------------+----------------------+",
+            "| +08:00      | 1                    |",
+            "+-------------+----------------------+",
+        ];
+        test(query, query_ctx, expected);
+    }
 }
@@ -12,22 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use common_telemetry::error;
+use common_telemetry::{error, warn};
+use datatypes::prelude::ConcreteDataType;
 use opensrv_mysql::{
-    AsyncMysqlShim, ErrorKind, ParamParser, QueryResultWriter, StatementMetaWriter,
+    AsyncMysqlShim, ErrorKind, InitWriter, ParamParser, QueryResultWriter, StatementMetaWriter,
+    ValueInner,
 };
 use rand::RngCore;
+use session::context::{Channel, QueryContext, QueryContextRef};
+use snafu::{OptionExt, ResultExt};
+use sql::ast::Value as SqlValue;
+use sql::prepared::PreparedStatement;
+use sql::statements::statement::Statement;
+use tokio::io::AsyncWrite;
 use tokio::sync::RwLock;
 
+use crate::auth::{Identity, Password, UserProviderRef};
 use crate::context::AuthHashMethod::DoubleSha1;
 use crate::context::Channel::MYSQL;
-use crate::context::{AuthMethod, Context, CtxBuilder};
+use crate::context::{AuthMethod, Context, CtxBuilder, QuotaLimits};
 use crate::error::{self, Result};
-use crate::mysql::writer::MysqlResultWriter;
+use crate::mysql::writer::{create_mysql_param_def, MysqlResultWriter};
 use crate::query_handler::SqlQueryHandlerRef;
 
 // An intermediate shim for executing MySQL queries.
@@ -36,10 +45,20 @@ pub struct MysqlInstanceShim {
     salt: [u8; 20],
     client_addr: String,
     ctx: Arc<RwLock<Option<Context>>>,
+    user_provider: Option<UserProviderRef>,
+    // The session of this connection.
+    query_ctx: QueryContextRef,
+    // Prepared statements of this connection, keyed by statement id.
+    prepared_stmts: HashMap<u32, PreparedStatement>,
+    next_stmt_id: u32,
 }
 
 impl MysqlInstanceShim {
-    pub fn create(query_handler: SqlQueryHandlerRef, client_addr: String) -> MysqlInstanceShim {
+    pub fn create(
+        query_handler: SqlQueryHandlerRef,
+        client_addr: String,
+        user_provider: Option<UserProviderRef>,
+    ) -> MysqlInstanceShim {
         // init a random salt
         let mut bs = vec![0u8; 20];
         let mut rng = rand::thread_rng();
@@ -53,26 +72,122 @@ impl MysqlInstanceShim {
             }
         }
 
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_client_addr(Some(client_addr.clone()));
+        query_ctx.set_channel(Channel::Mysql);
+
         MysqlInstanceShim {
             query_handler,
             salt: scramble,
             client_addr,
             ctx: Arc::new(RwLock::new(None)),
+            user_provider,
+            query_ctx,
+            prepared_stmts: HashMap::new(),
+            next_stmt_id: 1,
+        }
+    }
+
+    fn prepare(&self, query: &str) -> Result<(PreparedStatement, Vec<Option<ConcreteDataType>>)> {
+        let stmt = PreparedStatement::new(query).context(error::PrepareStatementSnafu { query })?;
+        let param_types = self
+            .query_handler
+            .param_types(&stmt, self.query_ctx.clone())?;
+        Ok((stmt, param_types))
+    }
+
+    /// Binds the parameters to the prepared statement, returns the statement to execute along
+    /// with its bound SQL text.
+    fn bind(&self, stmt_id: u32, params: ParamParser) -> Result<(Statement, String)> {
+        let stmt = self
+            .prepared_stmts
+            .get(&stmt_id)
+            .with_context(|| error::InvalidQuerySnafu {
+                reason: format!("unknown prepared statement id: {}", stmt_id),
+            })?;
+        let params = params
+            .into_iter()
+            .map(|param| mysql_value_to_sql_value(param.value.into_inner()))
+            .collect::<Result<Vec<_>>>()?;
+        let bound = stmt
+            .bind(&params)
+            .context(error::BindParamsSnafu { query: stmt.sql() })?;
+        // The SQL text is bound as well, as it's executed instead of the statement somewhere,
+        // e.g. on datanode in standalone mode.
+        let sql = stmt
+            .bind_sql(&params)
+            .context(error::BindParamsSnafu { query: stmt.sql() })?;
+        Ok((bound, sql))
+    }
+}
+
+fn mysql_value_to_sql_value(value: ValueInner) -> Result<SqlValue> {
+    let value = match value {
+        ValueInner::NULL => SqlValue::Null,
+        ValueInner::Bytes(bytes) => {
+            SqlValue::SingleQuotedString(String::from_utf8_lossy(bytes).to_string())
+        }
+        ValueInner::Int(v) => SqlValue::Number(v.to_string(), false),
+        ValueInner::UInt(v) => SqlValue::Number(v.to_string(), false),
+        ValueInner::Double(v) => SqlValue::Number(v.to_string(), false),
+        ValueInner::Date(bytes) | ValueInner::Datetime(bytes) => {
+            SqlValue::SingleQuotedString(mysql_datetime_to_string(bytes)?)
+        }
+        ValueInner::Time(_) => {
+            return error::NotSupportedSnafu {
+                feat: "TIME parameter in prepared statement",
+            }
+            .fail()
+        }
+    };
+    Ok(value)
+}
+
+// Decodes DATE or DATETIME in MySQL binary protocol, the length of bytes is 0, 4, 7 or 11,
+// trailing fields of zero are omitted.
+fn mysql_datetime_to_string(bytes: &[u8]) -> Result<String> {
+    let mut fields = [0u32; 7];
+    match bytes.len() {
+        0 => {}
+        4 | 7 | 11 => {
+            fields[0] = u16::from_le_bytes([bytes[0], bytes[1]]) as u32;
+            for (i, b) in bytes[2..bytes.len().min(7)].iter().enumerate() {
+                fields[i + 1] = *b as u32;
+            }
+            if bytes.len() == 11 {
+                fields[6] = u32::from_le_bytes([bytes[7], bytes[8], bytes[9], bytes[10]]);
+            }
+        }
+        len => {
+            return error::InvalidQuerySnafu {
+                reason: format!("invalid length of MySQL datetime parameter: {}", len),
+            }
+            .fail()
         }
     }
+    Ok(format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+        fields[0], fields[1], fields[2], fields[3], fields[4], fields[5], fields[6]
+    ))
 }
 
 #[async_trait]
-impl<W: io::Write + Send + Sync> AsyncMysqlShim<W> for MysqlInstanceShim {
+impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShim {
     type Error = error::Error;
 
     fn salt(&self) -> [u8; 20] {
         self.salt
     }
 
+    // The connection id is also the process id of its queries, so `KILL QUERY <id>` sent by
+    // clients (e.g. on Ctrl+C) cancels the running query of this connection.
+    fn connect_id(&self) -> u32 {
+        self.query_ctx.conn_id()
+    }
+
     async fn authenticate(
         &self,
-        _auth_plugin: &str,
+        auth_plugin: &str,
         username: &[u8],
         salt: &[u8],
         auth_data: &[u8],
@@ -80,6 +195,36 @@ impl<W: io::Write + Send + Sync> AsyncMysqlShim<W> for MysqlInstanceShim {
         // if not specified then **root** will be used
         let username = String::from_utf8_lossy(username);
         let client_addr = self.client_addr.clone();
+
+        let mut quota_limits = QuotaLimits::default();
+        if let Some(user_provider) = &self.user_provider {
+            if auth_plugin != "mysql_native_password" {
+                warn!(
+                    "Unsupported MySQL auth plugin: {}, user: {}",
+                    auth_plugin, username
+                );
+                return false;
+            }
+
+            let host = client_addr
+                .rsplit_once(':')
+                .map(|(host, _)| host)
+                .unwrap_or(&client_addr);
+            match user_provider
+                .auth(
+                    Identity::UserId(&username, Some(host)),
+                    Password::MysqlNativePassword(auth_data, salt),
+                )
+                .await
+            {
+                Ok(user_info) => quota_limits = user_info.quota_limits().clone(),
+                Err(e) => {
+                    warn!("Failed to auth MySQL user {}: {}", username, e);
+                    return false;
+                }
+            }
+        }
+
         let auth_method = match auth_data.len() {
             0 => AuthMethod::None,
             _ => AuthMethod::Password {
@@ -94,11 +239,14 @@ impl<W: io::Write + Send + Sync> AsyncMysqlShim<W> for MysqlInstanceShim {
             .set_channel(Some(MYSQL))
             .set_username(Some(username.to_string()))
             .set_auth_method(Some(auth_method))
+            .set_quota_limits(quota_limits.clone())
             .build()
         {
             Ok(ctx) => {
                 let mut a = self.ctx.write().await;
                 *a = Some(ctx);
+                self.query_ctx.set_current_user(Some(username.to_string()));
+                self.query_ctx.set_quota_limits(quota_limits);
                 true
             }
             Err(e) => {
@@ -110,34 +258,54 @@ impl<W: io::Write + Send + Sync> AsyncMysqlShim<W> for MysqlInstanceShim {
 
     async fn on_prepare<'a>(
         &'a mut self,
-        _: &'a str,
+        query: &'a str,
         writer: StatementMetaWriter<'a, W>,
     ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "prepare statement is not supported yet".as_bytes(),
-        )?;
+        match self.prepare(query) {
+            Ok((stmt, param_types)) => {
+                let stmt_id = self.next_stmt_id;
+                self.next_stmt_id = self.next_stmt_id.wrapping_add(1).max(1);
+
+                // Columns of the result are sent when the statement is executed.
+                writer
+                    .reply(stmt_id, &create_mysql_param_def(&param_types), &[])
+                    .await?;
+                self.prepared_stmts.insert(stmt_id, stmt);
+            }
+            Err(e) => {
+                writer
+                    .error(ErrorKind::ER_INTERNAL_ERROR, e.to_string().as_bytes())
+                    .await?
+            }
+        }
         Ok(())
     }
 
     async fn on_execute<'a>(
         &'a mut self,
-        _: u32,
-        _: ParamParser<'a>,
+        stmt_id: u32,
+        params: ParamParser<'a>,
         writer: QueryResultWriter<'a, W>,
     ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "prepare statement is not supported yet".as_bytes(),
-        )?;
-        Ok(())
+        let output = match self.bind(stmt_id, params) {
+            Ok((stmt, query)) => {
+                self.query_handler
+                    .do_parsed_statement_query(stmt, &query, self.query_ctx.clone())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        // Results are encoded in binary protocol by the writer of a prepared statement.
+        let mut writer = MysqlResultWriter::new(writer);
+        writer.write(vec![output]).await
     }
 
-    async fn on_close<'a>(&'a mut self, _stmt_id: u32)
+    async fn on_close<'a>(&'a mut self, stmt_id: u32)
     where
         W: 'async_trait,
     {
-        // do nothing because we haven't implemented prepare statement
+        self.prepared_stmts.remove(&stmt_id);
     }
 
     async fn on_query<'a>(
@@ -148,13 +316,42 @@ impl<W: io::Write + Send + Sync> AsyncMysqlShim<W> for MysqlInstanceShim {
         // TODO(LFC): Find a better way:
         // `check` uses regex to filter out unsupported statements emitted by MySQL's federated
         // components, this is quick and dirty, there must be a better way to do it.
-        let output = if let Some(output) = crate::mysql::federated::check(query) {
-            Ok(output)
-        } else {
-            self.query_handler.do_query(query).await
-        };
+        let outputs =
+            if let Some(output) = crate::mysql::federated::check(query, self.query_ctx.clone()) {
+                vec![Ok(output)]
+            } else {
+                self.query_handler
+                    .do_query(query, self.query_ctx.clone())
+                    .await
+            };
 
         let mut writer = MysqlResultWriter::new(writer);
-        writer.write(output).await
+        writer.write(outputs).await
+    }
+
+    /// Switches the database of the session, either by `COM_INIT_DB` or the database given in
+    /// the handshake.
+    async fn on_init<'a>(&'a mut self, database: &'a str, w: InitWriter<'a, W>) -> Result<()> {
+        // The name is sent by client, quote it so it's always parsed as a single identifier.
+        // Our SQL parser doesn't support escaped backticks inside a quoted identifier, so no
+        // database could have a name containing backticks.
+        if database.contains('`') {
+            let msg = format!("Unknown database '{}'", database);
+            w.error(ErrorKind::ER_BAD_DB_ERROR, msg.as_bytes()).await?;
+            return Ok(());
+        }
+        let query = format!("USE `{}`", database);
+        match self
+            .query_handler
+            .do_statement_query(&query, self.query_ctx.clone())
+            .await
+        {
+            Ok(_) => w.ok().await?,
+            Err(e) => {
+                w.error(ErrorKind::ER_BAD_DB_ERROR, e.to_string().as_bytes())
+                    .await?
+            }
+        }
+        Ok(())
     }
 }
@@ -20,28 +20,43 @@ use async_trait::async_trait;
 use common_runtime::Runtime;
 use common_telemetry::logging::{error, info};
 use futures::StreamExt;
-use opensrv_mysql::AsyncMysqlIntermediary;
+use opensrv_mysql::{
+    plain_run_with_options, secure_run_with_options, AsyncMysqlIntermediary, IntermediaryOptions,
+};
+use snafu::ensure;
 use tokio;
+use tokio::io::BufWriter;
 use tokio::net::TcpStream;
 
-use crate::error::Result;
+use crate::auth::UserProviderRef;
+use crate::error::{self, Result};
 use crate::mysql::handler::MysqlInstanceShim;
 use crate::query_handler::SqlQueryHandlerRef;
 use crate::server::{AbortableStream, BaseTcpServer, Server};
+use crate::tls::ServerTls;
+
+// Default size of the buffer to write result sets to clients.
+const DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE: usize = 100 * 1024;
 
 pub struct MysqlServer {
     base_server: BaseTcpServer,
     query_handler: SqlQueryHandlerRef,
+    user_provider: Option<UserProviderRef>,
+    tls: ServerTls,
 }
 
 impl MysqlServer {
     pub fn create_server(
         query_handler: SqlQueryHandlerRef,
         io_runtime: Arc<Runtime>,
+        user_provider: Option<UserProviderRef>,
+        tls: ServerTls,
     ) -> Box<dyn Server> {
         Box::new(MysqlServer {
             base_server: BaseTcpServer::create_server("MySQL", io_runtime),
             query_handler,
+            user_provider,
+            tls,
         })
     }
 
@@ -51,14 +66,20 @@ impl MysqlServer {
         stream: AbortableStream,
     ) -> impl Future<Output = ()> {
         let query_handler = self.query_handler.clone();
+        let user_provider = self.user_provider.clone();
+        let tls = self.tls.clone();
         stream.for_each(move |tcp_stream| {
             let io_runtime = io_runtime.clone();
             let query_handler = query_handler.clone();
+            let user_provider = user_provider.clone();
+            let tls = tls.clone();
             async move {
                 match tcp_stream {
                     Err(error) => error!("Broken pipe: {}", error), // IoError doesn't impl ErrorExt.
                     Ok(io_stream) => {
-                        if let Err(error) = Self::handle(io_stream, io_runtime, query_handler) {
+                        if let Err(error) =
+                            Self::handle(io_stream, io_runtime, query_handler, user_provider, tls)
+                        {
                             error!(error; "Unexpected error when handling TcpStream");
                         };
                     }
@@ -71,13 +92,50 @@ impl MysqlServer {
         stream: TcpStream,
         io_runtime: Arc<Runtime>,
         query_handler: SqlQueryHandlerRef,
+        user_provider: Option<UserProviderRef>,
+        tls: ServerTls,
     ) -> Result<()> {
         info!("MySQL connection coming from: {}", stream.peer_addr()?);
-        let shim = MysqlInstanceShim::create(query_handler, stream.peer_addr()?.to_string());
+        let shim = MysqlInstanceShim::create(
+            query_handler,
+            stream.peer_addr()?.to_string(),
+            user_provider,
+        );
         // TODO(LFC): Relate "handler" with MySQL session; also deal with panics there.
-        let _handler = io_runtime.spawn(AsyncMysqlIntermediary::run_on(shim, stream));
+        let _handler = io_runtime.spawn(async move {
+            if let Err(e) = Self::do_handle(stream, shim, tls).await {
+                error!(e; "Failed to handle MySQL connection");
+            }
+        });
         Ok(())
     }
+
+    /// Serves the connection by TLS if the client asks to upgrade the connection in the handshake
+    /// (the SSLRequest packet), or in plaintext if TLS is not required.
+    async fn do_handle(
+        stream: TcpStream,
+        mut shim: MysqlInstanceShim,
+        tls: ServerTls,
+    ) -> Result<()> {
+        let (mut r, w) = stream.into_split();
+        let mut w = BufWriter::with_capacity(DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE, w);
+        let ops = IntermediaryOptions::default();
+
+        let (client_tls, init_params) =
+            AsyncMysqlIntermediary::init_before_ssl(&mut shim, &mut r, &mut w, &tls.config())
+                .await?;
+        ensure!(
+            client_tls || !tls.force_tls(),
+            error::TlsRequiredSnafu { server: "MySQL" }
+        );
+
+        match tls.config() {
+            Some(tls_config) if client_tls => {
+                secure_run_with_options(shim, w, ops, tls_config, init_params).await
+            }
+            _ => plain_run_with_options(shim, w, ops, init_params).await,
+        }
+    }
 }
 
 #[async_trait]
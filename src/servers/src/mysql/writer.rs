@@ -12,12 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io;
 use std::ops::Deref;
 
+use chrono::NaiveDateTime;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
-use common_time::datetime::DateTime;
 use common_time::timestamp::TimeUnit;
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::{ColumnSchema, SchemaRef};
@@ -25,82 +24,148 @@ use opensrv_mysql::{
     Column, ColumnFlags, ColumnType, ErrorKind, OkResponse, QueryResultWriter, RowWriter,
 };
 use snafu::prelude::*;
+use tokio::io::AsyncWrite;
 
 use crate::error::{self, Error, Result};
 
-struct QueryResult {
-    recordbatches: Vec<RecordBatch>,
-    schema: SchemaRef,
+enum QueryResult {
+    Records {
+        recordbatches: Vec<RecordBatch>,
+        schema: SchemaRef,
+    },
+    AffectedRows(usize),
 }
 
-pub struct MysqlResultWriter<'a, W: io::Write> {
+pub struct MysqlResultWriter<'a, W: AsyncWrite + Unpin> {
     // `QueryResultWriter` will be consumed when the write completed (see
     // QueryResultWriter::completed), thus we use an option to wrap it.
     inner: Option<QueryResultWriter<'a, W>>,
 }
 
-impl<'a, W: io::Write> MysqlResultWriter<'a, W> {
+impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
     pub fn new(inner: QueryResultWriter<'a, W>) -> MysqlResultWriter<'a, W> {
         MysqlResultWriter::<'a, W> { inner: Some(inner) }
     }
 
-    pub async fn write(&mut self, output: Result<Output>) -> Result<()> {
+    /// Writes the results of statements, as multiple result sets if there are more than one.
+    pub async fn write(&mut self, outputs: Vec<Result<Output>>) -> Result<()> {
         let writer = self.inner.take().context(error::InternalSnafu {
             err_msg: "inner MySQL writer is consumed",
         })?;
-        match output {
-            Ok(output) => match output {
-                Output::Stream(stream) => {
-                    let schema = stream.schema().clone();
-                    let recordbatches = util::collect(stream)
-                        .await
-                        .context(error::CollectRecordbatchSnafu)?;
-                    let query_result = QueryResult {
-                        recordbatches,
-                        schema,
-                    };
-                    Self::write_query_result(query_result, writer)?
-                }
-                Output::RecordBatches(recordbatches) => {
-                    let query_result = QueryResult {
-                        schema: recordbatches.schema(),
-                        recordbatches: recordbatches.take(),
-                    };
-                    Self::write_query_result(query_result, writer)?
-                }
-                Output::AffectedRows(rows) => Self::write_affected_rows(writer, rows)?,
-            },
-            Err(error) => Self::write_query_error(error, writer)?,
+
+        let mut results = Vec::with_capacity(outputs.len());
+        for output in outputs {
+            results.push(Self::collect_output(output).await);
         }
-        Ok(())
+        // The columns definition must outlive the writers of all the result sets.
+        let columns_defs = results
+            .iter()
+            .map(|result| match result {
+                Ok(QueryResult::Records { schema, .. }) => create_mysql_column_def(schema),
+                _ => Ok(vec![]),
+            })
+            .collect::<Vec<_>>();
+        Self::write_results(writer, results, &columns_defs).await
     }
 
-    fn write_affected_rows(writer: QueryResultWriter<W>, rows: usize) -> Result<()> {
-        writer.completed(OkResponse {
-            affected_rows: rows as u64,
-            ..Default::default()
-        })?;
-        Ok(())
+    async fn collect_output(output: Result<Output>) -> Result<QueryResult> {
+        match output? {
+            Output::Stream(stream) => {
+                let schema = stream.schema().clone();
+                let recordbatches = util::collect(stream)
+                    .await
+                    .context(error::CollectRecordbatchSnafu)?;
+                Ok(QueryResult::Records {
+                    recordbatches,
+                    schema,
+                })
+            }
+            Output::RecordBatches(recordbatches) => Ok(QueryResult::Records {
+                schema: recordbatches.schema(),
+                recordbatches: recordbatches.take(),
+            }),
+            Output::AffectedRows(rows) => Ok(QueryResult::AffectedRows(rows)),
+        }
     }
 
-    fn write_query_result(
-        query_result: QueryResult,
-        writer: QueryResultWriter<'a, W>,
+    async fn write_results<'b>(
+        mut writer: QueryResultWriter<'b, W>,
+        results: Vec<Result<QueryResult>>,
+        columns_defs: &'b [Result<Vec<Column>>],
     ) -> Result<()> {
-        match create_mysql_column_def(&query_result.schema) {
-            Ok(column_def) => {
-                let mut row_writer = writer.start(&column_def)?;
-                for recordbatch in &query_result.recordbatches {
-                    Self::write_recordbatch(&mut row_writer, recordbatch)?;
+        if results.is_empty() {
+            return Self::write_affected_rows(writer, 0).await;
+        }
+
+        let total = results.len();
+        // Rows affected by the statements before the current one.
+        let mut affected_rows = 0;
+        let mut results = results.into_iter().zip(columns_defs).enumerate().peekable();
+        while let Some((i, (result, columns_def))) = results.next() {
+            let more_results = i + 1 < total;
+            let next = match (result, columns_def) {
+                (Ok(QueryResult::Records { recordbatches, .. }), Ok(columns_def)) => {
+                    let mut row_writer = writer.start(columns_def).await?;
+                    for recordbatch in &recordbatches {
+                        Self::write_recordbatch(&mut row_writer, recordbatch).await?;
+                    }
+                    if more_results {
+                        Some(row_writer.finish_one().await?)
+                    } else {
+                        row_writer.finish().await?;
+                        None
+                    }
+                }
+                (Ok(QueryResult::Records { .. }), Err(error)) => {
+                    Self::write_query_error(error, writer).await?;
+                    None
+                }
+                (Ok(QueryResult::AffectedRows(rows)), _) if more_results => {
+                    // Only the OK packet written by `completed` reports affected rows, but it
+                    // consumes the writer. So the affected rows are summed up and reported by
+                    // the last statement, and consecutive statements share one OK packet.
+                    affected_rows += rows;
+                    let next_affects_rows = matches!(
+                        results.peek(),
+                        Some((_, (Ok(QueryResult::AffectedRows(_)), _)))
+                    );
+                    if next_affects_rows {
+                        Some(writer)
+                    } else {
+                        Some(writer.start(&[]).await?.finish_one().await?)
+                    }
                 }
-                row_writer.finish()?;
-                Ok(())
+                (Ok(QueryResult::AffectedRows(rows)), _) => {
+                    Self::write_affected_rows(writer, affected_rows + rows).await?;
+                    None
+                }
+                (Err(error), _) => {
+                    Self::write_query_error(&error, writer).await?;
+                    None
+                }
+            };
+            match next {
+                Some(next) => writer = next,
+                None => break,
             }
-            Err(error) => Self::write_query_error(error, writer),
         }
+        Ok(())
+    }
+
+    async fn write_affected_rows(writer: QueryResultWriter<'_, W>, rows: usize) -> Result<()> {
+        writer
+            .completed(OkResponse {
+                affected_rows: rows as u64,
+                ..Default::default()
+            })
+            .await?;
+        Ok(())
     }
 
-    fn write_recordbatch(row_writer: &mut RowWriter<W>, recordbatch: &RecordBatch) -> Result<()> {
+    async fn write_recordbatch(
+        row_writer: &mut RowWriter<'_, W>,
+        recordbatch: &RecordBatch,
+    ) -> Result<()> {
         for row in recordbatch.rows() {
             let row = row.context(error::CollectRecordbatchSnafu)?;
             for value in row.into_iter() {
@@ -121,8 +186,24 @@ impl<'a, W: io::Write> MysqlResultWriter<'a, W> {
                     Value::Binary(v) => row_writer.write_col(v.deref())?,
                     Value::Date(v) => row_writer.write_col(v.val())?,
                     Value::DateTime(v) => row_writer.write_col(v.val())?,
-                    Value::Timestamp(v) => row_writer
-                        .write_col(DateTime::new(v.convert_to(TimeUnit::Second)).to_string())?,
+                    // Written as `NaiveDateTime` so that it can be encoded in both text and
+                    // binary protocol.
+                    Value::Timestamp(v) => row_writer.write_col(NaiveDateTime::from_timestamp(
+                        v.convert_to(TimeUnit::Second),
+                        0,
+                    ))?,
+                    Value::Decimal128(v) => row_writer.write_col(v.to_string())?,
+                    Value::Json(v) => row_writer.write_col(v.as_utf8())?,
+                    Value::Interval(v) => row_writer.write_col(v.to_string())?,
+                    Value::Struct(_) | Value::Map(_) => {
+                        let datatype = value.data_type();
+                        let json = serde_json::Value::try_from(value)
+                            .map_err(|e| Error::Internal {
+                                err_msg: format!("cannot convert {:?} to json: {}", datatype, e),
+                            })?
+                            .to_string();
+                        row_writer.write_col(json)?
+                    }
                     Value::List(_) => {
                         return Err(Error::Internal {
                             err_msg: format!(
@@ -133,19 +214,21 @@ impl<'a, W: io::Write> MysqlResultWriter<'a, W> {
                     }
                 }
             }
-            row_writer.end_row()?;
+            row_writer.end_row().await?;
         }
         Ok(())
     }
 
-    fn write_query_error(error: Error, writer: QueryResultWriter<'a, W>) -> Result<()> {
-        writer.error(ErrorKind::ER_INTERNAL_ERROR, error.to_string().as_bytes())?;
+    async fn write_query_error(error: &Error, writer: QueryResultWriter<'_, W>) -> Result<()> {
+        writer
+            .error(ErrorKind::ER_INTERNAL_ERROR, error.to_string().as_bytes())
+            .await?;
         Ok(())
     }
 }
 
-fn create_mysql_column(column_schema: &ColumnSchema) -> Result<Column> {
-    let column_type = match column_schema.data_type {
+fn mysql_column_type(data_type: &ConcreteDataType) -> Result<ColumnType> {
+    match data_type {
         ConcreteDataType::Null(_) => Ok(ColumnType::MYSQL_TYPE_NULL),
         ConcreteDataType::Boolean(_) | ConcreteDataType::Int8(_) | ConcreteDataType::UInt8(_) => {
             Ok(ColumnType::MYSQL_TYPE_TINY)
@@ -157,29 +240,44 @@ fn create_mysql_column(column_schema: &ColumnSchema) -> Result<Column> {
         ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_) => {
             Ok(ColumnType::MYSQL_TYPE_LONGLONG)
         }
-        ConcreteDataType::Float32(_) | ConcreteDataType::Float64(_) => {
-            Ok(ColumnType::MYSQL_TYPE_FLOAT)
-        }
+        ConcreteDataType::Float32(_) => Ok(ColumnType::MYSQL_TYPE_FLOAT),
+        ConcreteDataType::Float64(_) => Ok(ColumnType::MYSQL_TYPE_DOUBLE),
         ConcreteDataType::Binary(_) | ConcreteDataType::String(_) => {
             Ok(ColumnType::MYSQL_TYPE_VARCHAR)
         }
         ConcreteDataType::Timestamp(_) => Ok(ColumnType::MYSQL_TYPE_DATETIME),
+        ConcreteDataType::Decimal128(_) => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
+        ConcreteDataType::Json(_) | ConcreteDataType::Struct(_) | ConcreteDataType::Map(_) => {
+            Ok(ColumnType::MYSQL_TYPE_JSON)
+        }
+        ConcreteDataType::Interval(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
         _ => error::InternalSnafu {
-            err_msg: format!(
-                "not implemented for column datatype {:?}",
-                column_schema.data_type
-            ),
+            err_msg: format!("not implemented for column datatype {:?}", data_type),
         }
         .fail(),
-    };
-    column_type.map(|column_type| Column {
+    }
+}
+
+// Unsigned columns must be flagged, or their values cannot be encoded in binary protocol.
+fn mysql_column_flags(data_type: &ConcreteDataType) -> ColumnFlags {
+    match data_type {
+        ConcreteDataType::UInt8(_)
+        | ConcreteDataType::UInt16(_)
+        | ConcreteDataType::UInt32(_)
+        | ConcreteDataType::UInt64(_) => ColumnFlags::UNSIGNED_FLAG,
+        _ => ColumnFlags::empty(),
+    }
+}
+
+fn create_mysql_column(column_schema: &ColumnSchema) -> Result<Column> {
+    mysql_column_type(&column_schema.data_type).map(|column_type| Column {
         column: column_schema.name.clone(),
         coltype: column_type,
+        colflags: mysql_column_flags(&column_schema.data_type),
 
-        // TODO(LFC): Currently "table" and "colflags" are not relevant in MySQL server
-        //   implementation, will revisit them again in the future.
+        // TODO(LFC): Currently "table" is not relevant in MySQL server implementation, will
+        //   revisit it again in the future.
         table: "".to_string(),
-        colflags: ColumnFlags::empty(),
     })
 }
 
@@ -191,3 +289,28 @@ pub fn create_mysql_column_def(schema: &SchemaRef) -> Result<Vec<Column>> {
         .map(create_mysql_column)
         .collect()
 }
+
+/// Creates MySQL columns definition of the parameters in a prepared statement, parameters of
+/// unknown types are defined as strings.
+pub fn create_mysql_param_def(param_types: &[Option<ConcreteDataType>]) -> Vec<Column> {
+    param_types
+        .iter()
+        .enumerate()
+        .map(|(i, data_type)| {
+            let (coltype, colflags) = data_type
+                .as_ref()
+                .and_then(|t| {
+                    mysql_column_type(t)
+                        .ok()
+                        .map(|coltype| (coltype, mysql_column_flags(t)))
+                })
+                .unwrap_or((ColumnType::MYSQL_TYPE_VAR_STRING, ColumnFlags::empty()));
+            Column {
+                table: "".to_string(),
+                column: format!("?{}", i),
+                coltype,
+                colflags,
+            }
+        })
+        .collect()
+}
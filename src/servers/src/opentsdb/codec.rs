@@ -18,7 +18,8 @@ use api::v1::codec::InsertBatch;
 use api::v1::column::SemanticType;
 use api::v1::{column, insert_expr, Column, ColumnDataType, InsertExpr};
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
-use common_grpc::writer::Precision;
+use common_grpc::writer::{LinesWriter, Precision};
+use snafu::ResultExt;
 use table::requests::InsertRequest;
 
 use crate::error::{self, Result};
@@ -217,6 +218,67 @@ impl DataPoint {
     }
 }
 
+/// Groups the items by metric, in the order the metrics first appear, so that the data points of
+/// one metric can be written to its table at once.
+pub fn group_by_metric<T>(items: Vec<T>, metric: impl Fn(&T) -> &str) -> Vec<Vec<T>> {
+    let mut groups: Vec<Vec<T>> = Vec::new();
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        let idx = match group_index.get(metric(&item)) {
+            Some(idx) => *idx,
+            None => {
+                let idx = groups.len();
+                let _ = group_index.insert(metric(&item).to_string(), idx);
+                groups.push(Vec::new());
+                idx
+            }
+        };
+        groups[idx].push(item);
+    }
+    groups
+}
+
+/// Converts the data points to insert exprs, one for each metric. Tags that are missing in some
+/// data points of a metric are inserted as nulls.
+pub fn data_points_to_grpc_inserts(data_points: &[DataPoint]) -> Result<Vec<InsertExpr>> {
+    group_by_metric(data_points.iter().collect(), |data_point| {
+        data_point.metric()
+    })
+    .into_iter()
+    .map(|group| {
+        let table_name = group[0].metric.clone();
+        let mut writer = LinesWriter::with_lines(group.len());
+        for data_point in group {
+            writer
+                .write_ts(
+                    OPENTSDB_TIMESTAMP_COLUMN_NAME,
+                    (data_point.ts_millis, Precision::MILLISECOND),
+                )
+                .context(error::OpentsdbLinesWriteSnafu)?;
+            writer
+                .write_f64(OPENTSDB_VALUE_COLUMN_NAME, data_point.value)
+                .context(error::OpentsdbLinesWriteSnafu)?;
+            for (tagk, tagv) in data_point.tags.iter() {
+                writer
+                    .write_tag(tagk, tagv)
+                    .context(error::OpentsdbLinesWriteSnafu)?;
+            }
+            writer.commit();
+        }
+
+        Ok(InsertExpr {
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name,
+            expr: Some(insert_expr::Expr::Values(insert_expr::Values {
+                values: vec![writer.finish().into()],
+            })),
+            options: HashMap::default(),
+            region_number: 0,
+        })
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -369,4 +431,72 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_data_points_to_grpc_inserts() {
+        let data_points = vec![
+            DataPoint::new(
+                "my_metric_1".to_string(),
+                1000,
+                1.0,
+                vec![("tagk1".to_string(), "tagv1".to_string())],
+            ),
+            DataPoint::new("my_metric_2".to_string(), 1000, 2.0, vec![]),
+            DataPoint::new(
+                "my_metric_1".to_string(),
+                2000,
+                3.0,
+                vec![("tagk2".to_string(), "tagv2".to_string())],
+            ),
+        ];
+
+        let inserts = data_points_to_grpc_inserts(&data_points).unwrap();
+        assert_eq!(inserts.len(), 2);
+        assert_eq!(inserts[0].table_name, "my_metric_1");
+        assert_eq!(inserts[1].table_name, "my_metric_2");
+
+        let insert_batch = match &inserts[0].expr {
+            Some(insert_expr::Expr::Values(insert_expr::Values { values })) => {
+                assert_eq!(values.len(), 1);
+                InsertBatch::try_from(values[0].as_slice()).unwrap()
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(insert_batch.row_count, 2);
+        let columns = insert_batch.columns;
+        assert_eq!(columns.len(), 4);
+
+        assert_eq!(columns[0].column_name, OPENTSDB_TIMESTAMP_COLUMN_NAME);
+        assert_eq!(
+            columns[0].values.as_ref().unwrap().ts_millis_values,
+            vec![1000, 2000]
+        );
+
+        assert_eq!(columns[1].column_name, OPENTSDB_VALUE_COLUMN_NAME);
+        assert_eq!(
+            columns[1].values.as_ref().unwrap().f64_values,
+            vec![1.0, 3.0]
+        );
+
+        // "tagk1" is null in the second row, and "tagk2" is null in the first row.
+        assert_eq!(columns[2].column_name, "tagk1");
+        assert_eq!(
+            columns[2].values.as_ref().unwrap().string_values,
+            vec!["tagv1"]
+        );
+        assert_eq!(columns[2].null_mask, vec![0b10]);
+
+        assert_eq!(columns[3].column_name, "tagk2");
+        assert_eq!(
+            columns[3].values.as_ref().unwrap().string_values,
+            vec!["tagv2"]
+        );
+        assert_eq!(columns[3].null_mask, vec![0b01]);
+    }
+
+    #[test]
+    fn test_group_by_metric() {
+        let groups = group_by_metric(vec!["b1", "a1", "b2", "c1", "a2"], |item| &item[..1]);
+        assert_eq!(groups, vec![vec!["b1", "b2"], vec!["a1", "a2"], vec!["c1"]]);
+    }
 }
@@ -69,6 +69,16 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Connection<S> {
         }
     }
 
+    /// Takes the complete lines that have already been received, without reading from the
+    /// underlying stream. Lines sent in a burst can then be handled as a batch.
+    pub fn read_buffered_lines(&mut self) -> Result<Vec<Line>> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.parse_line()? {
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+
     /// Tries to parse a line from the buffer.
     ///
     /// If the buffer contains enough data, the line is returned and the buffered data is removed.
@@ -145,6 +155,21 @@ mod tests {
             .contains("Connection reset by peer"));
     }
 
+    #[tokio::test]
+    async fn test_read_buffered_lines() {
+        let mock = Builder::new()
+            .read(b"line 1\r\nline 2\r\nline 3\r\nline ")
+            .build();
+        let mut conn = Connection::new(mock);
+        let line = conn.read_line().await.unwrap();
+        assert_eq!(line, Some("line 1".to_string()));
+
+        let lines = conn.read_buffered_lines().unwrap();
+        assert_eq!(lines, vec!["line 2".to_string(), "line 3".to_string()]);
+        assert!(conn.read_buffered_lines().unwrap().is_empty());
+        assert_eq!(&conn.buffer[..], b"line ");
+    }
+
     #[test]
     fn test_parse_line() {
         let mock = Builder::new().build();
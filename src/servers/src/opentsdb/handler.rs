@@ -14,10 +14,11 @@
 
 //! Modified from Tokio's mini-redis example.
 
+use session::context::{Channel, QueryContext, QueryContextRef};
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::error::Result;
-use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::codec::{group_by_metric, DataPoint};
 use crate::opentsdb::connection::Connection;
 use crate::query_handler::OpentsdbProtocolHandlerRef;
 use crate::shutdown::Shutdown;
@@ -43,6 +44,9 @@ pub(crate) struct Handler<S: AsyncWrite + AsyncRead + Unpin> {
     /// any in-flight work being processed for the peer is continued until it reaches a safe state,
     /// at which point the connection is terminated. (Graceful shutdown.)
     shutdown: Shutdown,
+
+    /// The session of this connection, the telnet protocol has no authentication.
+    query_ctx: QueryContextRef,
 }
 
 impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
@@ -51,10 +55,13 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
         connection: Connection<S>,
         shutdown: Shutdown,
     ) -> Self {
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_channel(Channel::Opentsdb);
         Self {
             query_handler,
             connection,
             shutdown,
+            query_ctx,
         }
     }
 
@@ -77,26 +84,45 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
                 None => return Ok(()),
             };
 
-            // Close connection upon receiving "quit" line. With actual OpenTSDB, telnet just won't
-            // quit, the connection to OpenTSDB server can be closed only via terminating telnet
-            // session manually, for example, close the terminal window. That is a little annoying,
-            // so I added "quit" command to the line protocol, to make telnet client able to quit
-            // gracefully.
-            if line.trim().eq_ignore_ascii_case("quit") {
-                return Ok(());
-            }
+            // Lines that have arrived along with this one are put in the same batch.
+            let mut lines = vec![line];
+            lines.extend(self.connection.read_buffered_lines()?);
+
+            let mut data_points = Vec::with_capacity(lines.len());
+            let mut quit = false;
+            for line in lines {
+                // Close connection upon receiving "quit" line. With actual OpenTSDB, telnet just
+                // won't quit, the connection to OpenTSDB server can be closed only via terminating
+                // telnet session manually, for example, close the terminal window. That is a
+                // little annoying, so I added "quit" command to the line protocol, to make telnet
+                // client able to quit gracefully.
+                if line.trim().eq_ignore_ascii_case("quit") {
+                    quit = true;
+                    break;
+                }
 
-            match DataPoint::try_create(&line) {
-                Ok(data_point) => {
-                    let result = self.query_handler.exec(&data_point).await;
-                    if let Err(e) = result {
+                match DataPoint::try_create(&line) {
+                    Ok(data_point) => data_points.push(data_point),
+                    Err(e) => {
                         self.connection.write_line(e.to_string()).await?;
                     }
                 }
-                Err(e) => {
+            }
+
+            // A failed metric writes one error line, the other metrics are not affected.
+            for data_points in group_by_metric(data_points, |data_point| data_point.metric()) {
+                let result = self
+                    .query_handler
+                    .exec(&data_points, self.query_ctx.clone())
+                    .await;
+                if let Err(e) = result {
                     self.connection.write_line(e.to_string()).await?;
                 }
             }
+
+            if quit {
+                return Ok(());
+            }
         }
         Ok(())
     }
@@ -116,20 +142,23 @@ mod tests {
     use crate::query_handler::OpentsdbProtocolHandler;
 
     struct DummyQueryHandler {
-        tx: mpsc::Sender<String>,
+        tx: mpsc::Sender<(String, usize)>,
     }
 
     #[async_trait]
     impl OpentsdbProtocolHandler for DummyQueryHandler {
-        async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-            let metric = data_point.metric();
+        async fn exec(&self, data_points: &[DataPoint], _query_ctx: QueryContextRef) -> Result<()> {
+            let metric = data_points[0].metric();
             if metric == "should_failed" {
                 return error::InternalSnafu {
                     err_msg: "expected",
                 }
                 .fail();
             }
-            self.tx.send(metric.to_string()).await.unwrap();
+            self.tx
+                .send((metric.to_string(), data_points.len()))
+                .await
+                .unwrap();
             Ok(())
         }
     }
@@ -149,13 +178,13 @@ mod tests {
             .write_line("put my_metric_1 1000 1.0 host=web01".to_string())
             .await
             .unwrap();
-        assert_eq!(rx.recv().await.unwrap(), "my_metric_1");
+        assert_eq!(rx.recv().await.unwrap(), ("my_metric_1".to_string(), 1));
 
         client
             .write_line("put my_metric_2 1000 1.0 host=web01".to_string())
             .await
             .unwrap();
-        assert_eq!(rx.recv().await.unwrap(), "my_metric_2");
+        assert_eq!(rx.recv().await.unwrap(), ("my_metric_2".to_string(), 1));
 
         client
             .write_line("put should_failed 1000 1.0 host=web01".to_string())
@@ -172,6 +201,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_run_batch() {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let query_handler = Arc::new(DummyQueryHandler { tx });
+        let (notify_shutdown, _) = broadcast::channel(1);
+        let addr = start_server(query_handler, notify_shutdown).await;
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut client = Connection::new(stream);
+
+        // Lines sent in one write are handled as one batch, grouped by metric.
+        let lines = [
+            "put my_metric_1 1000 1.0 host=web01",
+            "put my_metric_2 1000 1.0 host=web01",
+            "put should_failed 1000 1.0 host=web01",
+            "put my_metric_1 2000 2.0 host=web01",
+        ];
+        client.write_line(lines.join("\r\n")).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap(), ("my_metric_1".to_string(), 2));
+        assert_eq!(rx.recv().await.unwrap(), ("my_metric_2".to_string(), 1));
+        let resp = client.read_line().await.unwrap();
+        assert_eq!(resp, Some("Internal error: expected".to_string()));
+    }
+
     async fn start_server(
         query_handler: OpentsdbProtocolHandlerRef,
         notify_shutdown: broadcast::Sender<()>,
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use common_telemetry::warn;
 use futures::{Sink, SinkExt};
 use pgwire::api::auth::{ServerParameterProvider, StartupHandler};
 use pgwire::api::{auth, ClientInfo, PgWireConnectionState};
@@ -23,12 +24,60 @@ use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
 use pgwire::messages::response::ErrorResponse;
 use pgwire::messages::startup::Authentication;
 use pgwire::messages::{PgWireBackendMessage, PgWireFrontendMessage};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 
-struct PgPwdVerifier;
+use crate::auth::{Identity, Password, UserProviderRef};
+
+pub(crate) const METADATA_USER: &str = "user";
+// The salt of MD5 auth is saved in the connection's metadata, as the startup handler is shared by
+// all connections.
+const METADATA_MD5_SALT: &str = "greptime_md5_salt";
+
+/// The method to ask Postgres clients for passwords.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PgAuthMethod {
+    Cleartext,
+    Md5,
+}
+
+impl Default for PgAuthMethod {
+    fn default() -> Self {
+        PgAuthMethod::Md5
+    }
+}
+
+struct PgPwdVerifier {
+    user_provider: UserProviderRef,
+}
 
 impl PgPwdVerifier {
-    async fn verify_pwd(&self, _pwd: &str, _meta: HashMap<String, String>) -> PgWireResult<bool> {
-        Ok(true)
+    async fn verify_pwd(&self, pwd: &str, meta: &HashMap<String, String>) -> bool {
+        let username = match meta.get(METADATA_USER) {
+            Some(username) => username,
+            None => return false,
+        };
+        let id = Identity::UserId(username, None);
+
+        let result = if let Some(salt) = meta.get(METADATA_MD5_SALT) {
+            let salt = match hex::decode(salt) {
+                Ok(salt) => salt,
+                Err(_) => return false,
+            };
+            self.user_provider
+                .auth(id, Password::PgMD5(pwd.as_bytes(), &salt))
+                .await
+        } else {
+            self.user_provider.auth(id, Password::PlainText(pwd)).await
+        };
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Failed to auth Postgres user {}: {}", username, e);
+                false
+            }
+        }
     }
 }
 
@@ -60,21 +109,45 @@ impl ServerParameterProvider for GreptimeDBStartupParameters {
 }
 
 pub struct PgAuthStartupHandler {
-    verifier: PgPwdVerifier,
+    verifier: Option<PgPwdVerifier>,
     param_provider: GreptimeDBStartupParameters,
-    with_pwd: bool,
+    auth_method: PgAuthMethod,
+    force_tls: bool,
 }
 
 impl PgAuthStartupHandler {
-    pub fn new(with_pwd: bool) -> Self {
+    /// Passwords are checked only if `user_provider` is given. Clients not connected by TLS are
+    /// rejected if `force_tls` is true.
+    pub fn new(
+        user_provider: Option<UserProviderRef>,
+        auth_method: PgAuthMethod,
+        force_tls: bool,
+    ) -> Self {
         PgAuthStartupHandler {
-            verifier: PgPwdVerifier,
+            verifier: user_provider.map(|user_provider| PgPwdVerifier { user_provider }),
             param_provider: GreptimeDBStartupParameters::new(),
-            with_pwd,
+            auth_method,
+            force_tls,
         }
     }
 }
 
+async fn send_fatal_error<C>(client: &mut C, code: &str, message: &str) -> PgWireResult<()>
+where
+    C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send,
+    C::Error: Debug,
+    PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+{
+    let error_info = ErrorInfo::new("FATAL".to_owned(), code.to_owned(), message.to_owned());
+    let error = ErrorResponse::from(error_info);
+
+    client
+        .feed(PgWireBackendMessage::ErrorResponse(error))
+        .await?;
+    client.close().await?;
+    Ok(())
+}
+
 #[async_trait]
 impl StartupHandler for PgAuthStartupHandler {
     async fn on_startup<C>(
@@ -89,13 +162,25 @@ impl StartupHandler for PgAuthStartupHandler {
     {
         match message {
             PgWireFrontendMessage::Startup(ref startup) => {
+                if self.force_tls && !client.is_secure() {
+                    return send_fatal_error(client, "28000", "TLS is required").await;
+                }
                 auth::save_startup_parameters_to_metadata(client, startup);
-                if self.with_pwd {
+                if self.verifier.is_some() {
                     client.set_state(PgWireConnectionState::AuthenticationInProgress);
+                    let authentication = match self.auth_method {
+                        PgAuthMethod::Cleartext => Authentication::CleartextPassword,
+                        PgAuthMethod::Md5 => {
+                            let mut salt = vec![0u8; 4];
+                            rand::thread_rng().fill_bytes(&mut salt);
+                            let _ = client
+                                .metadata_mut()
+                                .insert(METADATA_MD5_SALT.to_string(), hex::encode(&salt));
+                            Authentication::MD5Password(salt)
+                        }
+                    };
                     client
-                        .send(PgWireBackendMessage::Authentication(
-                            Authentication::CleartextPassword,
-                        ))
+                        .send(PgWireBackendMessage::Authentication(authentication))
                         .await?;
                 } else {
                     auth::finish_authentication(client, &self.param_provider).await;
@@ -103,20 +188,14 @@ impl StartupHandler for PgAuthStartupHandler {
             }
             PgWireFrontendMessage::Password(ref pwd) => {
                 let meta = client.metadata().clone();
-                if let Ok(true) = self.verifier.verify_pwd(pwd.password(), meta).await {
+                let verified = match &self.verifier {
+                    Some(verifier) => verifier.verify_pwd(pwd.password(), &meta).await,
+                    None => true,
+                };
+                if verified {
                     auth::finish_authentication(client, &self.param_provider).await
                 } else {
-                    let error_info = ErrorInfo::new(
-                        "FATAL".to_owned(),
-                        "28P01".to_owned(),
-                        "Password authentication failed".to_owned(),
-                    );
-                    let error = ErrorResponse::from(error_info);
-
-                    client
-                        .feed(PgWireBackendMessage::ErrorResponse(error))
-                        .await?;
-                    client.close().await?;
+                    send_fatal_error(client, "28P01", "Password authentication failed").await?;
                 }
             }
             _ => {}
@@ -12,77 +12,219 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::{Arc, Mutex, Once};
 
 use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
 use common_query::Output;
 use common_recordbatch::error::Result as RecordBatchResult;
 use common_recordbatch::RecordBatch;
+use common_time::timestamp::TimeUnit;
+use common_time::Interval;
+use datatypes::decimal::Decimal128;
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::SchemaRef;
 use futures::{future, stream, Stream, StreamExt};
-use pgwire::api::portal::Portal;
+use pgwire::api::portal::{Format, Portal};
 use pgwire::api::query::{ExtendedQueryHandler, SimpleQueryHandler};
-use pgwire::api::results::{text_query_response, FieldInfo, Response, Tag, TextDataRowEncoder};
+use pgwire::api::results::{
+    binary_query_response, text_query_response, BinaryDataRowEncoder, FieldInfo, Response, Tag,
+    TextDataRowEncoder,
+};
 use pgwire::api::{ClientInfo, Type};
-use pgwire::error::{PgWireError, PgWireResult};
+use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
+use postgres_types::{accepts, to_sql_checked, IsNull, ToSql};
+use session::context::{Channel, QueryContext, QueryContextRef};
+use snafu::{ensure, ResultExt};
+use sql::ast::Value as SqlValue;
+use sql::prepared::PreparedStatement;
+use sql::statements::statement::Statement;
 
 use crate::error::{self, Error, Result};
+use crate::postgres::auth_handler::METADATA_USER;
 use crate::query_handler::SqlQueryHandlerRef;
 
+const METADATA_DATABASE: &str = "database";
+
+// The cache of prepared statements is simply cleared when it's full.
+const MAX_CACHED_STATEMENTS: usize = 256;
+
+// Days from Unix epoch (1970-01-01) to Postgres epoch (2000-01-01).
+const PG_EPOCH_DAYS: i64 = 10_957;
+
+/// Query handler of a single Postgres connection, which holds the session of the connection.
 pub struct PostgresServerHandler {
     query_handler: SqlQueryHandlerRef,
+    query_ctx: QueryContextRef,
+    init_session: Once,
+    // Prepared statements of this connection, keyed by the SQL text.
+    prepared_stmts: Mutex<HashMap<String, Arc<CachedStatement>>>,
+}
+
+struct CachedStatement {
+    stmt: PreparedStatement,
+    param_types: Vec<Option<ConcreteDataType>>,
 }
 
 impl PostgresServerHandler {
-    pub fn new(query_handler: SqlQueryHandlerRef) -> Self {
-        PostgresServerHandler { query_handler }
+    pub fn new(query_handler: SqlQueryHandlerRef, client_addr: Option<String>) -> Self {
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_client_addr(client_addr);
+        query_ctx.set_channel(Channel::Postgres);
+
+        PostgresServerHandler {
+            query_handler,
+            query_ctx,
+            init_session: Once::new(),
+            prepared_stmts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn prepare(&self, query: &str) -> Result<Arc<CachedStatement>> {
+        if let Some(stmt) = self.prepared_stmts.lock().unwrap().get(query) {
+            return Ok(stmt.clone());
+        }
+
+        let stmt = PreparedStatement::new(query).context(error::PrepareStatementSnafu { query })?;
+        let param_types = self
+            .query_handler
+            .param_types(&stmt, self.query_ctx.clone())?;
+        let stmt = Arc::new(CachedStatement { stmt, param_types });
+
+        let mut prepared_stmts = self.prepared_stmts.lock().unwrap();
+        if prepared_stmts.len() >= MAX_CACHED_STATEMENTS {
+            prepared_stmts.clear();
+        }
+        prepared_stmts.insert(query.to_string(), stmt.clone());
+        Ok(stmt)
+    }
+
+    /// Binds the parameters of the portal to the prepared statement, returns the statement to
+    /// execute along with its bound SQL text.
+    fn bind(&self, portal: &Portal) -> Result<(Statement, String)> {
+        let cached = self.prepare(portal.statement())?;
+        let parameters = portal.parameters();
+        ensure!(
+            parameters.len() == cached.param_types.len(),
+            error::InvalidQuerySnafu {
+                reason: format!(
+                    "expect {} parameters, actual: {}",
+                    cached.param_types.len(),
+                    parameters.len()
+                ),
+            }
+        );
+
+        let params = parameters
+            .iter()
+            .zip(cached.param_types.iter())
+            .enumerate()
+            .map(|(i, (param, data_type))| match param {
+                None => Ok(SqlValue::Null),
+                Some(bytes) if portal.parameter_format().is_binary(i) => {
+                    decode_binary_param(bytes, data_type.as_ref())
+                }
+                Some(bytes) => decode_text_param(bytes, data_type.as_ref()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let bound = cached.stmt.bind(&params).context(error::BindParamsSnafu {
+            query: portal.statement(),
+        })?;
+        let sql = cached
+            .stmt
+            .bind_sql(&params)
+            .context(error::BindParamsSnafu {
+                query: portal.statement(),
+            })?;
+        Ok((bound, sql))
+    }
+
+    /// Initializes the session with the startup parameters of the client, which are only
+    /// available after the connection is established.
+    fn init_session<C: ClientInfo>(&self, client: &C) {
+        self.init_session.call_once(|| {
+            let metadata = client.metadata();
+            if let Some(database) = metadata.get(METADATA_DATABASE) {
+                self.query_ctx.set_current_schema(database);
+            }
+            if let Some(user) = metadata.get(METADATA_USER) {
+                self.query_ctx.set_current_user(Some(user.clone()));
+            }
+        });
     }
 }
 
 #[async_trait]
 impl SimpleQueryHandler for PostgresServerHandler {
-    async fn do_query<C>(&self, _client: &C, query: &str) -> PgWireResult<Vec<Response>>
+    async fn do_query<C>(&self, client: &C, query: &str) -> PgWireResult<Vec<Response>>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        let output = self
+        self.init_session(client);
+
+        let outputs = self
             .query_handler
-            .do_query(query)
-            .await
-            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+            .do_query(query, self.query_ctx.clone())
+            .await;
 
-        match output {
-            Output::AffectedRows(rows) => Ok(vec![Response::Execution(Tag::new_for_execution(
-                "OK",
-                Some(rows),
-            ))]),
-            Output::Stream(record_stream) => {
-                let schema = record_stream.schema();
-                recordbatches_to_query_response(record_stream, schema)
-            }
-            Output::RecordBatches(recordbatches) => {
-                let schema = recordbatches.schema();
-                recordbatches_to_query_response(
-                    stream::iter(recordbatches.take().into_iter().map(Ok)),
-                    schema,
-                )
+        let mut responses = Vec::with_capacity(outputs.len());
+        for output in outputs {
+            // The execution stops at the first failed statement, so the error is the last one.
+            let response = output
+                .map_err(|e| PgWireError::ApiError(Box::new(e)))
+                .and_then(|output| output_to_query_response(output, false));
+            match response {
+                Ok(response) => responses.push(response),
+                Err(e) if responses.is_empty() => return Err(e),
+                Err(e) => {
+                    responses.push(Response::Error(Box::new(ErrorInfo::new(
+                        "ERROR".to_string(),
+                        "XX000".to_string(),
+                        e.to_string(),
+                    ))));
+                    break;
+                }
             }
         }
+        Ok(responses)
+    }
+}
+
+fn output_to_query_response(output: Output, binary: bool) -> PgWireResult<Response> {
+    match output {
+        Output::AffectedRows(rows) => Ok(Response::Execution(Tag::new_for_execution(
+            "OK",
+            Some(rows),
+        ))),
+        Output::Stream(record_stream) => {
+            let schema = record_stream.schema();
+            recordbatches_to_query_response(record_stream, schema, binary)
+        }
+        Output::RecordBatches(recordbatches) => {
+            let schema = recordbatches.schema();
+            recordbatches_to_query_response(
+                stream::iter(recordbatches.take().into_iter().map(Ok)),
+                schema,
+                binary,
+            )
+        }
     }
 }
 
 fn recordbatches_to_query_response<S>(
     recordbatches_stream: S,
     schema: SchemaRef,
-) -> PgWireResult<Vec<Response>>
+    binary: bool,
+) -> PgWireResult<Response>
 where
     S: Stream<Item = RecordBatchResult<RecordBatch>> + Send + Unpin + 'static,
 {
     let pg_schema = schema_to_pg(schema).map_err(|e| PgWireError::ApiError(Box::new(e)))?;
     let ncols = pg_schema.len();
 
-    let data_row_stream = recordbatches_stream
+    let row_stream = recordbatches_stream
         .map(|record_batch_result| match record_batch_result {
             Ok(rb) => stream::iter(
                 // collect rows from a single recordbatch into vector to avoid
@@ -95,8 +237,24 @@ where
             .boxed(),
             Err(e) => stream::once(future::err(PgWireError::ApiError(Box::new(e)))).boxed(),
         })
-        .flatten() // flatten into stream<result<row>>
-        .map(move |row| {
+        .flatten(); // flatten into stream<result<row>>
+
+    if binary {
+        let data_row_stream = row_stream.map(move |row| {
+            row.and_then(|row| {
+                let mut encoder = BinaryDataRowEncoder::new(ncols);
+                for value in row.into_iter() {
+                    encode_binary_value(&value, &mut encoder)?;
+                }
+                encoder.finish()
+            })
+        });
+        Ok(Response::Query(binary_query_response(
+            pg_schema,
+            data_row_stream,
+        )))
+    } else {
+        let data_row_stream = row_stream.map(move |row| {
             row.and_then(|row| {
                 let mut encoder = TextDataRowEncoder::new(ncols);
                 for value in row.into_iter() {
@@ -105,11 +263,11 @@ where
                 encoder.finish()
             })
         });
-
-    Ok(vec![Response::Query(text_query_response(
-        pg_schema,
-        data_row_stream,
-    ))])
+        Ok(Response::Query(text_query_response(
+            pg_schema,
+            data_row_stream,
+        )))
+    }
 }
 
 fn schema_to_pg(origin: SchemaRef) -> Result<Vec<FieldInfo>> {
@@ -146,6 +304,49 @@ fn encode_value(value: &Value, builder: &mut TextDataRowEncoder) -> PgWireResult
         Value::Date(v) => builder.append_field(Some(&v.to_string())),
         Value::DateTime(v) => builder.append_field(Some(&v.to_string())),
         Value::Timestamp(v) => builder.append_field(Some(&v.to_iso8601_string())),
+        Value::Decimal128(v) => builder.append_field(Some(&v.to_string())),
+        Value::Json(v) => builder.append_field(Some(&v.as_utf8())),
+        Value::Interval(v) => builder.append_field(Some(&v.to_string())),
+        Value::Struct(_) | Value::Map(_) => builder.append_field(Some(&to_json_string(value)?)),
+        Value::List(_) => Err(PgWireError::ApiError(Box::new(Error::Internal {
+            err_msg: format!(
+                "cannot write value {:?} in postgres protocol: unimplemented",
+                &value
+            ),
+        }))),
+    }
+}
+
+// Values are encoded as the types declared in `type_translate`, unsigned integers are
+// reinterpreted as the signed ones of the same width.
+fn encode_binary_value(value: &Value, builder: &mut BinaryDataRowEncoder) -> PgWireResult<()> {
+    match value {
+        Value::Null => builder.append_field(None::<&i8>),
+        Value::Boolean(v) => builder.append_field(Some(v)),
+        Value::UInt8(v) => builder.append_field(Some(&(*v as i8))),
+        Value::UInt16(v) => builder.append_field(Some(&(*v as i16))),
+        Value::UInt32(v) => builder.append_field(Some(&(*v as i32))),
+        Value::UInt64(v) => builder.append_field(Some(&(*v as i64))),
+        Value::Int8(v) => builder.append_field(Some(v)),
+        Value::Int16(v) => builder.append_field(Some(v)),
+        Value::Int32(v) => builder.append_field(Some(v)),
+        Value::Int64(v) => builder.append_field(Some(v)),
+        Value::Float32(v) => builder.append_field(Some(&v.0)),
+        Value::Float64(v) => builder.append_field(Some(&v.0)),
+        Value::String(v) => builder.append_field(Some(&v.as_utf8())),
+        Value::Binary(v) => builder.append_field(Some(&v.deref())),
+        // Postgres encodes DATE as days and TIMESTAMP as microseconds since 2000-01-01.
+        Value::Date(v) => builder.append_field(Some(&(v.val() - PG_EPOCH_DAYS as i32))),
+        Value::DateTime(v) => {
+            builder.append_field(Some(&((v.val() - PG_EPOCH_DAYS * 86_400) * 1_000_000)))
+        }
+        Value::Timestamp(v) => builder.append_field(Some(
+            &(v.convert_to(TimeUnit::Microsecond) - PG_EPOCH_DAYS * 86_400 * 1_000_000),
+        )),
+        Value::Decimal128(v) => builder.append_field(Some(&PgNumeric(*v))),
+        Value::Json(v) => builder.append_field(Some(&v.as_utf8())),
+        Value::Interval(v) => builder.append_field(Some(&PgInterval(*v))),
+        Value::Struct(_) | Value::Map(_) => builder.append_field(Some(&to_json_string(value)?)),
         Value::List(_) => Err(PgWireError::ApiError(Box::new(Error::Internal {
             err_msg: format!(
                 "cannot write value {:?} in postgres protocol: unimplemented",
@@ -155,6 +356,162 @@ fn encode_value(value: &Value, builder: &mut TextDataRowEncoder) -> PgWireResult
     }
 }
 
+/// Decimal in the binary format of postgres `numeric`.
+#[derive(Debug)]
+struct PgNumeric(Decimal128);
+
+impl ToSql for PgNumeric {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let (weight, digits) = numeric_digits(self.0.value(), self.0.scale());
+        out.put_i16(digits.len() as i16);
+        out.put_i16(weight);
+        out.put_u16(if self.0.value() < 0 { 0x4000 } else { 0 });
+        out.put_i16(self.0.scale() as i16);
+        for digit in digits {
+            out.put_i16(digit);
+        }
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+    to_sql_checked!();
+}
+
+/// Splits the absolute value of the decimal `value` with `scale` into base 10000 digits without
+/// leading or trailing zeros, returns the weight of the first digit and the digits, as postgres
+/// `numeric` stores them.
+fn numeric_digits(value: i128, scale: u8) -> (i16, Vec<i16>) {
+    let scale = scale as usize;
+    let mut decimal = value.unsigned_abs().to_string();
+    if decimal.len() <= scale {
+        decimal = format!("{}{}", "0".repeat(scale + 1 - decimal.len()), decimal);
+    }
+    let (integer, fraction) = decimal.split_at(decimal.len() - scale);
+    // Aligns both parts to groups of 4 decimal digits.
+    let integer = format!("{}{}", "0".repeat((4 - integer.len() % 4) % 4), integer);
+    let fraction = format!("{}{}", fraction, "0".repeat((4 - fraction.len() % 4) % 4));
+
+    let mut weight = (integer.len() / 4) as i16 - 1;
+    let mut digits = integer
+        .as_bytes()
+        .chunks(4)
+        .chain(fraction.as_bytes().chunks(4))
+        .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<i16>().unwrap())
+        .collect::<Vec<_>>();
+    let leading_zeros = digits.iter().take_while(|digit| **digit == 0).count();
+    if leading_zeros == digits.len() {
+        return (0, vec![]);
+    }
+    weight -= leading_zeros as i16;
+    digits.drain(..leading_zeros);
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    (weight, digits)
+}
+
+/// Interval in the binary format of postgres `interval`, which has a precision of microseconds.
+#[derive(Debug)]
+struct PgInterval(Interval);
+
+impl ToSql for PgInterval {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        out.put_i64(self.0.nanos() / 1_000);
+        out.put_i32(self.0.days());
+        out.put_i32(self.0.months());
+        Ok(IsNull::No)
+    }
+
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+
+// Struct and map values are sent as their JSON text, which is also the binary format of `json`.
+fn to_json_string(value: &Value) -> PgWireResult<String> {
+    serde_json::Value::try_from(value.clone())
+        .map(|json| json.to_string())
+        .map_err(|e| {
+            PgWireError::ApiError(Box::new(Error::Internal {
+                err_msg: format!("cannot convert {:?} to json: {}", value.data_type(), e),
+            }))
+        })
+}
+
+fn invalid_param(reason: String) -> Error {
+    error::InvalidQuerySnafu { reason }.build()
+}
+
+fn decode_text_param(bytes: &[u8], data_type: Option<&ConcreteDataType>) -> Result<SqlValue> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| invalid_param(format!("invalid UTF-8 parameter: {}", e)))?;
+    let value = match data_type {
+        Some(ConcreteDataType::Boolean(_)) => {
+            let v = match text.to_lowercase().as_str() {
+                "t" | "true" | "1" | "on" | "yes" => true,
+                "f" | "false" | "0" | "off" | "no" => false,
+                _ => {
+                    return Err(invalid_param(format!(
+                        "invalid boolean parameter: {}",
+                        text
+                    )))
+                }
+            };
+            SqlValue::Boolean(v)
+        }
+        Some(ConcreteDataType::String(_) | ConcreteDataType::Binary(_)) => {
+            SqlValue::SingleQuotedString(text.to_string())
+        }
+        // Numbers, timestamps in numeric form, or parameters of unknown types that look like
+        // numbers.
+        _ if text.parse::<f64>().is_ok() => SqlValue::Number(text.to_string(), false),
+        _ => SqlValue::SingleQuotedString(text.to_string()),
+    };
+    Ok(value)
+}
+
+fn decode_binary_param(bytes: &[u8], data_type: Option<&ConcreteDataType>) -> Result<SqlValue> {
+    macro_rules! decode_number {
+        ($ty: ty) => {{
+            let v = <$ty>::from_be_bytes(bytes.try_into().map_err(|_| {
+                invalid_param(format!(
+                    "invalid length of {} parameter: {}",
+                    stringify!($ty),
+                    bytes.len()
+                ))
+            })?);
+            SqlValue::Number(v.to_string(), false)
+        }};
+    }
+
+    let value = match data_type {
+        Some(ConcreteDataType::Boolean(_)) => SqlValue::Boolean(bytes.first() == Some(&1)),
+        Some(ConcreteDataType::Int8(_) | ConcreteDataType::UInt8(_)) => decode_number!(i8),
+        Some(ConcreteDataType::Int16(_) | ConcreteDataType::UInt16(_)) => decode_number!(i16),
+        Some(ConcreteDataType::Int32(_) | ConcreteDataType::UInt32(_)) => decode_number!(i32),
+        Some(ConcreteDataType::Int64(_) | ConcreteDataType::UInt64(_)) => decode_number!(i64),
+        Some(ConcreteDataType::Float32(_)) => decode_number!(f32),
+        Some(ConcreteDataType::Float64(_)) => decode_number!(f64),
+        Some(ConcreteDataType::String(_) | ConcreteDataType::Binary(_)) => {
+            SqlValue::SingleQuotedString(String::from_utf8_lossy(bytes).to_string())
+        }
+        other => {
+            return Err(invalid_param(format!(
+                "binary parameter of type {:?} is not supported",
+                other
+            )))
+        }
+    };
+    Ok(value)
+}
+
 fn type_translate(origin: &ConcreteDataType) -> Result<Type> {
     match origin {
         &ConcreteDataType::Null(_) => Ok(Type::UNKNOWN),
@@ -170,6 +527,11 @@ fn type_translate(origin: &ConcreteDataType) -> Result<Type> {
         &ConcreteDataType::Date(_) => Ok(Type::DATE),
         &ConcreteDataType::DateTime(_) => Ok(Type::TIMESTAMP),
         &ConcreteDataType::Timestamp(_) => Ok(Type::TIMESTAMP),
+        &ConcreteDataType::Decimal128(_) => Ok(Type::NUMERIC),
+        &ConcreteDataType::Interval(_) => Ok(Type::INTERVAL),
+        &ConcreteDataType::Json(_) | &ConcreteDataType::Struct(_) | &ConcreteDataType::Map(_) => {
+            Ok(Type::JSON)
+        }
         &ConcreteDataType::List(_) => error::InternalSnafu {
             err_msg: format!("not implemented for column datatype {:?}", origin),
         }
@@ -181,14 +543,26 @@ fn type_translate(origin: &ConcreteDataType) -> Result<Type> {
 impl ExtendedQueryHandler for PostgresServerHandler {
     async fn do_query<C>(
         &self,
-        _client: &mut C,
-        _portal: &Portal,
+        client: &mut C,
+        portal: &Portal,
         _max_rows: usize,
     ) -> PgWireResult<Response>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        unimplemented!()
+        self.init_session(client);
+
+        let (stmt, query) = self
+            .bind(portal)
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+        let output = self
+            .query_handler
+            .do_parsed_statement_query(stmt, &query, self.query_ctx.clone())
+            .await
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+        let binary = matches!(portal.result_column_format(), Format::UnifiedBinary);
+        output_to_query_response(output, binary)
     }
 }
 
@@ -329,4 +703,80 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_encode_binary_numeric_and_interval() {
+        assert_eq!((0, vec![]), numeric_digits(0, 2));
+        assert_eq!((1, vec![1, 2345]), numeric_digits(12345, 0));
+        assert_eq!((0, vec![123, 4500]), numeric_digits(12345, 2));
+        assert_eq!((-1, vec![1]), numeric_digits(1, 4));
+        assert_eq!((-2, vec![1000]), numeric_digits(-1, 5));
+        assert_eq!((2, vec![1]), numeric_digits(100_000_000, 0));
+
+        let mut out = BytesMut::new();
+        PgNumeric(Decimal128::new(-12345, 10, 2))
+            .to_sql(&Type::NUMERIC, &mut out)
+            .unwrap();
+        assert_eq!(
+            &[0, 2, 0, 0, 0x40, 0, 0, 2, 0, 123, 0x11, 0x94],
+            out.as_ref()
+        );
+
+        let mut out = BytesMut::new();
+        PgInterval(Interval::new(1, 2, 3_000))
+            .to_sql(&Type::INTERVAL, &mut out)
+            .unwrap();
+        assert_eq!(
+            &[0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 1],
+            out.as_ref()
+        );
+    }
+
+    #[test]
+    fn test_decode_params() {
+        let int32 = ConcreteDataType::int32_datatype();
+        let string = ConcreteDataType::string_datatype();
+        let boolean = ConcreteDataType::boolean_datatype();
+
+        assert_eq!(
+            SqlValue::Number("42".to_string(), false),
+            decode_text_param(b"42", Some(&int32)).unwrap()
+        );
+        assert_eq!(
+            SqlValue::SingleQuotedString("42".to_string()),
+            decode_text_param(b"42", Some(&string)).unwrap()
+        );
+        assert_eq!(
+            SqlValue::Boolean(true),
+            decode_text_param(b"t", Some(&boolean)).unwrap()
+        );
+        assert!(decode_text_param(b"maybe", Some(&boolean)).is_err());
+        assert_eq!(
+            SqlValue::Number("1.5".to_string(), false),
+            decode_text_param(b"1.5", None).unwrap()
+        );
+        assert_eq!(
+            SqlValue::SingleQuotedString("greptime".to_string()),
+            decode_text_param(b"greptime", None).unwrap()
+        );
+
+        assert_eq!(
+            SqlValue::Number("-42".to_string(), false),
+            decode_binary_param(&(-42i32).to_be_bytes(), Some(&int32)).unwrap()
+        );
+        assert_eq!(
+            SqlValue::Number("1.5".to_string(), false),
+            decode_binary_param(
+                &1.5f64.to_be_bytes(),
+                Some(&ConcreteDataType::float64_datatype())
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            SqlValue::SingleQuotedString("greptime".to_string()),
+            decode_binary_param(b"greptime", Some(&string)).unwrap()
+        );
+        assert!(decode_binary_param(&42i64.to_be_bytes(), Some(&int32)).is_err());
+        assert!(decode_binary_param(&42i64.to_be_bytes(), None).is_err());
+    }
 }
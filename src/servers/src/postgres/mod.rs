@@ -16,4 +16,5 @@ mod auth_handler;
 mod handler;
 mod server;
 
+pub use auth_handler::PgAuthMethod;
 pub use server::PostgresServer;
@@ -22,32 +22,44 @@ use common_telemetry::logging::error;
 use futures::StreamExt;
 use pgwire::tokio::process_socket;
 use tokio;
+use tokio_rustls::TlsAcceptor;
 
+use crate::auth::UserProviderRef;
 use crate::error::Result;
-use crate::postgres::auth_handler::PgAuthStartupHandler;
+use crate::postgres::auth_handler::{PgAuthMethod, PgAuthStartupHandler};
 use crate::postgres::handler::PostgresServerHandler;
 use crate::query_handler::SqlQueryHandlerRef;
 use crate::server::{AbortableStream, BaseTcpServer, Server};
+use crate::tls::ServerTls;
 
 pub struct PostgresServer {
     base_server: BaseTcpServer,
     auth_handler: Arc<PgAuthStartupHandler>,
-    query_handler: Arc<PostgresServerHandler>,
+    query_handler: SqlQueryHandlerRef,
+    tls_acceptor: Option<Arc<TlsAcceptor>>,
 }
 
 impl PostgresServer {
-    /// Creates a new Postgres server with provided query_handler and async runtime
+    /// Creates a new Postgres server with provided query_handler and async runtime.
+    /// Clients' passwords are checked by `user_provider` if it's given. Clients can upgrade
+    /// connections to TLS by SSLRequest if `tls` is enabled.
     pub fn new(
         query_handler: SqlQueryHandlerRef,
-        check_pwd: bool,
+        user_provider: Option<UserProviderRef>,
+        auth_method: PgAuthMethod,
+        tls: ServerTls,
         io_runtime: Arc<Runtime>,
     ) -> PostgresServer {
-        let postgres_handler = Arc::new(PostgresServerHandler::new(query_handler));
-        let startup_handler = Arc::new(PgAuthStartupHandler::new(check_pwd));
+        let startup_handler = Arc::new(PgAuthStartupHandler::new(
+            user_provider,
+            auth_method,
+            tls.force_tls(),
+        ));
         PostgresServer {
             base_server: BaseTcpServer::create_server("Postgres", io_runtime),
             auth_handler: startup_handler,
-            query_handler: postgres_handler,
+            query_handler,
+            tls_acceptor: tls.acceptor().map(Arc::new),
         }
     }
 
@@ -58,22 +70,30 @@ impl PostgresServer {
     ) -> impl Future<Output = ()> {
         let auth_handler = self.auth_handler.clone();
         let query_handler = self.query_handler.clone();
+        let tls_acceptor = self.tls_acceptor.clone();
 
         accepting_stream.for_each(move |tcp_stream| {
             let io_runtime = io_runtime.clone();
             let auth_handler = auth_handler.clone();
             let query_handler = query_handler.clone();
+            let tls_acceptor = tls_acceptor.clone();
 
             async move {
                 match tcp_stream {
                     Err(error) => error!("Broken pipe: {}", error), // IoError doesn't impl ErrorExt.
                     Ok(io_stream) => {
+                        // Each connection has its own handler to keep the session.
+                        let client_addr = io_stream.peer_addr().ok().map(|addr| addr.to_string());
+                        let postgres_handler = Arc::new(PostgresServerHandler::new(
+                            query_handler.clone(),
+                            client_addr,
+                        ));
                         io_runtime.spawn(process_socket(
                             io_stream,
-                            None,
+                            tls_acceptor,
                             auth_handler.clone(),
-                            query_handler.clone(),
-                            query_handler.clone(),
+                            postgres_handler.clone(),
+                            postgres_handler,
                         ));
                     }
                 };
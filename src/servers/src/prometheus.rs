@@ -814,6 +814,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let timeseries = select_result_to_timeseries("metric1", select_result).unwrap();
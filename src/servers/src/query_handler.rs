@@ -18,8 +18,15 @@ use api::prometheus::remote::{ReadRequest, WriteRequest};
 use api::v1::{AdminExpr, AdminResult, ObjectExpr, ObjectResult};
 use async_trait::async_trait;
 use common_query::Output;
+use common_recordbatch::{util, RecordBatches, SendableRecordBatchStream};
+use datatypes::prelude::ConcreteDataType;
+use session::context::QueryContextRef;
+use snafu::ResultExt;
+use sql::parser::ParserContext;
+use sql::prepared::PreparedStatement;
+use sql::statements::statement::Statement;
 
-use crate::error::Result;
+use crate::error::{self, Result};
 use crate::influxdb::InfluxdbRequest;
 use crate::opentsdb::codec::DataPoint;
 use crate::prometheus::Metrics;
@@ -44,37 +51,104 @@ pub type ScriptHandlerRef = Arc<dyn ScriptHandler + Send + Sync>;
 
 #[async_trait]
 pub trait SqlQueryHandler {
-    async fn do_query(&self, query: &str) -> Result<Output>;
+    /// Executes the SQL text of a single statement.
+    async fn do_statement_query(&self, query: &str, query_ctx: QueryContextRef) -> Result<Output>;
+
+    /// Executes a parsed statement, e.g. a prepared statement with its parameters bound.
+    /// `query` is the SQL text of the statement with the same parameters bound, which is
+    /// reported in errors, and executed where only SQL text is accepted.
+    async fn do_parsed_statement_query(
+        &self,
+        stmt: Statement,
+        query: &str,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output>;
+
+    /// Executes the statements in `query` one by one, returns the result of each executed
+    /// statement. The execution stops at the first failed statement, whose error is the last
+    /// result.
+    async fn do_query(&self, query: &str, query_ctx: QueryContextRef) -> Vec<Result<Output>> {
+        let statements = ParserContext::split_statements(query);
+        let total = statements.len();
+        let mut results = Vec::with_capacity(total);
+        for (i, statement) in statements.into_iter().enumerate() {
+            let is_last = i + 1 == total;
+            let result = match self.do_statement_query(statement, query_ctx.clone()).await {
+                // Collects the results before executing the next statement, which may
+                // change the data being read.
+                Ok(Output::Stream(stream)) if !is_last => collect_stream(stream).await,
+                Err(e) if total > 1 => Err(error::Error::ExecuteStatement {
+                    index: i + 1,
+                    total,
+                    source: Box::new(e),
+                }),
+                other => other,
+            };
+            let failed = result.is_err();
+            results.push(result);
+            if failed {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Infers the types of parameters in the prepared statement, `None` for the parameters
+    /// whose types are unknown.
+    fn param_types(
+        &self,
+        stmt: &PreparedStatement,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Vec<Option<ConcreteDataType>>> {
+        Ok(vec![None; stmt.param_num()])
+    }
+}
+
+async fn collect_stream(stream: SendableRecordBatchStream) -> Result<Output> {
+    let schema = stream.schema();
+    let batches = util::collect(stream)
+        .await
+        .context(error::CollectRecordbatchSnafu)?;
+    let recordbatches =
+        RecordBatches::try_new(schema, batches).context(error::CollectRecordbatchSnafu)?;
+    Ok(Output::RecordBatches(recordbatches))
 }
 
 #[async_trait]
 pub trait ScriptHandler {
-    async fn insert_script(&self, name: &str, script: &str) -> Result<()>;
-    async fn execute_script(&self, name: &str) -> Result<Output>;
+    /// Inserts the script `name` into the `schema`, replacing the existing one.
+    async fn insert_script(&self, schema: &str, name: &str, script: &str) -> Result<()>;
+    async fn execute_script(&self, schema: &str, name: &str) -> Result<Output>;
 }
 
 #[async_trait]
 pub trait GrpcQueryHandler {
-    async fn do_query(&self, query: ObjectExpr) -> Result<ObjectResult>;
+    async fn do_query(&self, query: ObjectExpr, query_ctx: QueryContextRef)
+        -> Result<ObjectResult>;
 }
 
 #[async_trait]
 pub trait GrpcAdminHandler {
-    async fn exec_admin_request(&self, expr: AdminExpr) -> Result<AdminResult>;
+    async fn exec_admin_request(
+        &self,
+        expr: AdminExpr,
+        query_ctx: QueryContextRef,
+    ) -> Result<AdminResult>;
 }
 
 #[async_trait]
 pub trait InfluxdbLineProtocolHandler {
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
-    async fn exec(&self, request: &InfluxdbRequest) -> Result<()>;
+    async fn exec(&self, request: &InfluxdbRequest, query_ctx: QueryContextRef) -> Result<()>;
 }
 
 #[async_trait]
 pub trait OpentsdbProtocolHandler {
+    /// Puts a batch of data points, the data points of each metric are inserted at once.
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
-    async fn exec(&self, data_point: &DataPoint) -> Result<()>;
+    async fn exec(&self, data_points: &[DataPoint], query_ctx: QueryContextRef) -> Result<()>;
 }
 
 pub struct PrometheusResponse {
@@ -86,9 +160,19 @@ pub struct PrometheusResponse {
 #[async_trait]
 pub trait PrometheusProtocolHandler {
     /// Handling prometheus remote write requests
-    async fn write(&self, database: &str, request: WriteRequest) -> Result<()>;
+    async fn write(
+        &self,
+        database: &str,
+        request: WriteRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<()>;
     /// Handling prometheus remote read requests
-    async fn read(&self, database: &str, request: ReadRequest) -> Result<PrometheusResponse>;
+    async fn read(
+        &self,
+        database: &str,
+        request: ReadRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<PrometheusResponse>;
     /// Handling push gateway requests
     async fn ingest_metrics(&self, metrics: Metrics) -> Result<()>;
 }
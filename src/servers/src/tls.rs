@@ -0,0 +1,240 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS of the MySQL, Postgres, HTTP and gRPC servers.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use common_grpc::tls::ServerTlsOption;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use rustls_pemfile::Item;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt};
+use tokio_rustls::TlsAcceptor;
+
+use crate::error::{self, Result};
+
+/// Whether clients connect to a server by TLS.
+///
+/// MySQL and Postgres clients negotiate TLS on connections (STARTTLS), so `prefer` serves both
+/// TLS and plaintext clients. HTTP and gRPC don't negotiate, `prefer` only accepts TLS on them,
+/// the same as `require`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsMode {
+    Disable,
+    Prefer,
+    Require,
+}
+
+impl Default for TlsMode {
+    fn default() -> Self {
+        TlsMode::Disable
+    }
+}
+
+/// TLS options of a server, loaded from PEM files.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsOption {
+    pub mode: TlsMode,
+    pub cert_path: String,
+    pub key_path: String,
+    /// Clients must present certificates signed by the CA (mutual TLS) if set.
+    pub ca_cert_path: Option<String>,
+}
+
+impl TlsOption {
+    pub fn setup(&self) -> Result<ServerTls> {
+        if self.mode == TlsMode::Disable {
+            return Ok(ServerTls::default());
+        }
+
+        let certs = load_certs(&self.cert_path)?;
+        ensure!(
+            !certs.is_empty(),
+            error::InvalidTlsConfigSnafu {
+                msg: format!("no certificate found in {}", self.cert_path),
+            }
+        );
+        let key = load_private_key(&self.key_path)?;
+
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match &self.ca_cert_path {
+            Some(ca_cert_path) => {
+                let mut roots = RootCertStore::empty();
+                let ca_certs = load_certs(ca_cert_path)?
+                    .into_iter()
+                    .map(|cert| cert.0)
+                    .collect::<Vec<_>>();
+                let (valid, _) = roots.add_parsable_certificates(&ca_certs);
+                ensure!(
+                    valid > 0,
+                    error::InvalidTlsConfigSnafu {
+                        msg: format!("no valid CA certificate found in {}", ca_cert_path),
+                    }
+                );
+                builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(certs, key)
+            .context(error::SetupTlsSnafu)?;
+
+        Ok(ServerTls {
+            config: Some(Arc::new(config)),
+            force_tls: self.mode == TlsMode::Require,
+        })
+    }
+
+    /// TLS options of gRPC servers, which only accept TLS if it's not disabled.
+    pub fn grpc_tls_option(&self) -> Option<ServerTlsOption> {
+        match self.mode {
+            TlsMode::Disable => None,
+            TlsMode::Prefer | TlsMode::Require => Some(ServerTlsOption {
+                cert_path: self.cert_path.clone(),
+                key_path: self.key_path.clone(),
+                ca_cert_path: self.ca_cert_path.clone(),
+            }),
+        }
+    }
+}
+
+/// TLS of a server set up by [TlsOption], disabled by default.
+#[derive(Clone, Default)]
+pub struct ServerTls {
+    config: Option<Arc<ServerConfig>>,
+    force_tls: bool,
+}
+
+impl ServerTls {
+    pub fn config(&self) -> Option<Arc<ServerConfig>> {
+        self.config.clone()
+    }
+
+    pub fn acceptor(&self) -> Option<TlsAcceptor> {
+        self.config.clone().map(TlsAcceptor::from)
+    }
+
+    /// Whether plaintext connections are rejected.
+    pub fn force_tls(&self) -> bool {
+        self.force_tls
+    }
+}
+
+fn open_pem(path: &str) -> Result<BufReader<File>> {
+    let file = File::open(path).context(error::ReadTlsFileSnafu { path })?;
+    Ok(BufReader::new(file))
+}
+
+fn load_certs(path: &str) -> Result<Vec<Certificate>> {
+    let certs =
+        rustls_pemfile::certs(&mut open_pem(path)?).context(error::ReadTlsFileSnafu { path })?;
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+fn load_private_key(path: &str) -> Result<PrivateKey> {
+    let items =
+        rustls_pemfile::read_all(&mut open_pem(path)?).context(error::ReadTlsFileSnafu { path })?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .context(error::InvalidTlsConfigSnafu {
+            msg: format!("no private key found in {}", path),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ssl_file(name: &str) -> String {
+        format!("{}/tests/ssl/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn test_tls_mode_serde() {
+        let option: TlsOption = serde_json::from_str(r#"{"mode": "require"}"#).unwrap();
+        assert_eq!(TlsMode::Require, option.mode);
+        assert!(option.ca_cert_path.is_none());
+
+        let option: TlsOption = serde_json::from_str("{}").unwrap();
+        assert_eq!(TlsOption::default(), option);
+    }
+
+    #[test]
+    fn test_setup_disabled() {
+        let tls = TlsOption::default().setup().unwrap();
+        assert!(tls.config().is_none());
+        assert!(tls.acceptor().is_none());
+        assert!(!tls.force_tls());
+        assert!(TlsOption::default().grpc_tls_option().is_none());
+    }
+
+    #[test]
+    fn test_setup() {
+        let mut option = TlsOption {
+            mode: TlsMode::Prefer,
+            cert_path: ssl_file("server.crt"),
+            key_path: ssl_file("server.key"),
+            ca_cert_path: None,
+        };
+        let tls = option.setup().unwrap();
+        assert!(tls.config().is_some());
+        assert!(!tls.force_tls());
+
+        option.mode = TlsMode::Require;
+        option.ca_cert_path = Some(ssl_file("ca.crt"));
+        let tls = option.setup().unwrap();
+        assert!(tls.acceptor().is_some());
+        assert!(tls.force_tls());
+
+        let grpc_option = option.grpc_tls_option().unwrap();
+        assert_eq!(Some(ssl_file("ca.crt")), grpc_option.ca_cert_path);
+        assert!(grpc_option.setup().is_ok());
+    }
+
+    #[test]
+    fn test_setup_invalid_files() {
+        let option = TlsOption {
+            mode: TlsMode::Require,
+            cert_path: ssl_file("not_exist.crt"),
+            key_path: ssl_file("server.key"),
+            ca_cert_path: None,
+        };
+        assert!(matches!(
+            option.setup(),
+            Err(error::Error::ReadTlsFile { .. })
+        ));
+
+        // Certificates are not private keys.
+        let option = TlsOption {
+            mode: TlsMode::Require,
+            cert_path: ssl_file("server.crt"),
+            key_path: ssl_file("server.crt"),
+            ca_cert_path: None,
+        };
+        assert!(matches!(
+            option.setup(),
+            Err(error::Error::InvalidTlsConfig { .. })
+        ));
+    }
+}
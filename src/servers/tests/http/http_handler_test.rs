@@ -16,9 +16,11 @@ use std::collections::HashMap;
 
 use axum::body::Body;
 use axum::extract::{Json, Query, RawBody, State};
+use axum::Extension;
 use common_telemetry::metric;
 use metrics::counter;
 use servers::http::{handler as http_handler, script as script_handler, ApiState, JsonOutput};
+use session::context::QueryContext;
 use table::test_util::MemTable;
 
 use crate::{create_testing_script_handler, create_testing_sql_query_handler};
@@ -31,6 +33,7 @@ async fn test_sql_not_provided() {
             sql_handler,
             script_handler: None,
         }),
+        Extension(QueryContext::arc()),
         Query(http_handler::SqlQuery::default()),
     )
     .await;
@@ -54,6 +57,7 @@ async fn test_sql_output_rows() {
             sql_handler,
             script_handler: None,
         }),
+        Extension(QueryContext::arc()),
         query,
     )
     .await;
@@ -67,6 +71,36 @@ async fn test_sql_output_rows() {
     }
 }
 
+#[tokio::test]
+async fn test_sql_multiple_statements() {
+    common_telemetry::init_default_ut_logging();
+
+    let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
+    let query = Query(http_handler::SqlQuery {
+        sql: Some("select * from numbers limit 1; select * from numbers limit 2;".to_string()),
+        db: None,
+    });
+
+    let Json(json) = http_handler::sql(
+        State(ApiState {
+            sql_handler,
+            script_handler: None,
+        }),
+        Extension(QueryContext::arc()),
+        query,
+    )
+    .await;
+    assert!(json.success(), "{:?}", json);
+    let output = json.output().unwrap();
+    assert_eq!(2, output.len());
+    for (i, output) in output.iter().enumerate() {
+        match output {
+            JsonOutput::Records(records) => assert_eq!(i + 1, records.num_rows()),
+            _ => unreachable!(),
+        }
+    }
+}
+
 #[tokio::test]
 async fn test_metrics() {
     metric::init_default_metrics_recorder();
@@ -121,17 +155,21 @@ def test(n):
 
 fn create_script_query() -> Query<script_handler::ScriptQuery> {
     Query(script_handler::ScriptQuery {
+        db: None,
         name: Some("test".to_string()),
     })
 }
 
 fn create_invalid_script_query() -> Query<script_handler::ScriptQuery> {
-    Query(script_handler::ScriptQuery { name: None })
+    Query(script_handler::ScriptQuery {
+        db: None,
+        name: None,
+    })
 }
 
 fn create_query() -> Query<http_handler::SqlQuery> {
     Query(http_handler::SqlQuery {
         sql: Some("select sum(uint32s) from numbers limit 20".to_string()),
-        database: None,
+        db: None,
     })
 }
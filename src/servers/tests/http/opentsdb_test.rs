@@ -22,6 +22,8 @@ use servers::error::{self, Result};
 use servers::http::HttpServer;
 use servers::opentsdb::codec::DataPoint;
 use servers::query_handler::{OpentsdbProtocolHandler, SqlQueryHandler};
+use session::context::QueryContextRef;
+use sql::statements::statement::Statement;
 use tokio::sync::mpsc;
 
 struct DummyInstance {
@@ -30,21 +32,39 @@ struct DummyInstance {
 
 #[async_trait]
 impl OpentsdbProtocolHandler for DummyInstance {
-    async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-        if data_point.metric() == "should_failed" {
+    async fn exec(&self, data_points: &[DataPoint], _query_ctx: QueryContextRef) -> Result<()> {
+        if data_points
+            .iter()
+            .any(|data_point| data_point.metric() == "should_failed")
+        {
             return error::InternalSnafu {
                 err_msg: "expected",
             }
             .fail();
         }
-        let _ = self.tx.send(data_point.metric().to_string()).await;
+        for data_point in data_points {
+            let _ = self.tx.send(data_point.metric().to_string()).await;
+        }
         Ok(())
     }
 }
 
 #[async_trait]
 impl SqlQueryHandler for DummyInstance {
-    async fn do_query(&self, _query: &str) -> Result<Output> {
+    async fn do_statement_query(
+        &self,
+        _query: &str,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        unimplemented!()
+    }
+
+    async fn do_parsed_statement_query(
+        &self,
+        _stmt: Statement,
+        _query: &str,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         unimplemented!()
     }
 }
@@ -85,6 +105,20 @@ async fn test_opentsdb_put() {
     assert_eq!(result.status(), 204);
     assert!(result.text().await.is_empty());
 
+    // data points are put in batches of the same metric
+    let result = client
+        .post("/v1/opentsdb/api/put")
+        .body(format!(
+            "[{},{},{}]",
+            create_data_point("m4"),
+            create_data_point("m5"),
+            create_data_point("m4")
+        ))
+        .send()
+        .await;
+    assert_eq!(result.status(), 204);
+    assert!(result.text().await.is_empty());
+
     // bad data point
     let result = client
         .post("/v1/opentsdb/api/put")
@@ -113,10 +147,7 @@ async fn test_opentsdb_put() {
     while let Ok(s) = rx.try_recv() {
         metrics.push(s);
     }
-    assert_eq!(
-        metrics,
-        vec!["m1".to_string(), "m2".to_string(), "m3".to_string()]
-    );
+    assert_eq!(metrics, vec!["m1", "m2", "m3", "m4", "m4", "m5"]);
 }
 
 #[tokio::test]
@@ -176,14 +207,25 @@ async fn test_opentsdb_debug_put() {
     assert_eq!(result.status(), 200);
     assert_eq!(result.text().await, "{\"success\":1,\"failed\":1,\"errors\":[{\"datapoint\":{\"metric\":\"should_failed\",\"timestamp\":1000,\"value\":1.0,\"tags\":{\"host\":\"web01\"}},\"error\":\"Internal error: expected\"}]}");
 
+    // all data points of the failed metric are counted
+    let result = client
+        .post("/v1/opentsdb/api/put?summary")
+        .body(format!(
+            "[{},{},{}]",
+            create_data_point("should_failed"),
+            create_data_point("m44"),
+            create_data_point("should_failed"),
+        ))
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(result.text().await, "{\"success\":1,\"failed\":2}");
+
     let mut metrics = vec![];
     while let Ok(s) = rx.try_recv() {
         metrics.push(s);
     }
-    assert_eq!(
-        metrics,
-        vec!["m11".to_string(), "m22".to_string(), "m33".to_string()]
-    );
+    assert_eq!(metrics, vec!["m11", "m22", "m33", "m44"]);
 }
 
 fn create_data_point(metric: &str) -> String {
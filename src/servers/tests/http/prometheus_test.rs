@@ -27,6 +27,8 @@ use servers::http::HttpServer;
 use servers::prometheus;
 use servers::prometheus::{snappy_compress, Metrics};
 use servers::query_handler::{PrometheusProtocolHandler, PrometheusResponse, SqlQueryHandler};
+use session::context::QueryContextRef;
+use sql::statements::statement::Statement;
 use tokio::sync::mpsc;
 
 struct DummyInstance {
@@ -35,7 +37,12 @@ struct DummyInstance {
 
 #[async_trait]
 impl PrometheusProtocolHandler for DummyInstance {
-    async fn write(&self, db: &str, request: WriteRequest) -> Result<()> {
+    async fn write(
+        &self,
+        db: &str,
+        request: WriteRequest,
+        _query_ctx: QueryContextRef,
+    ) -> Result<()> {
         let _ = self
             .tx
             .send((db.to_string(), request.encode_to_vec()))
@@ -43,7 +50,12 @@ impl PrometheusProtocolHandler for DummyInstance {
 
         Ok(())
     }
-    async fn read(&self, db: &str, request: ReadRequest) -> Result<PrometheusResponse> {
+    async fn read(
+        &self,
+        db: &str,
+        request: ReadRequest,
+        _query_ctx: QueryContextRef,
+    ) -> Result<PrometheusResponse> {
         let _ = self
             .tx
             .send((db.to_string(), request.encode_to_vec()))
@@ -69,7 +81,20 @@ impl PrometheusProtocolHandler for DummyInstance {
 
 #[async_trait]
 impl SqlQueryHandler for DummyInstance {
-    async fn do_query(&self, _query: &str) -> Result<Output> {
+    async fn do_statement_query(
+        &self,
+        _query: &str,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        unimplemented!()
+    }
+
+    async fn do_parsed_statement_query(
+        &self,
+        _stmt: Statement,
+        _query: &str,
+        _query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         unimplemented!()
     }
 }
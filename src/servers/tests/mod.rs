@@ -21,11 +21,15 @@ use catalog::{CatalogList, CatalogProvider, SchemaProvider};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
 use query::{QueryEngineFactory, QueryEngineRef};
+use servers::auth::{user_provider_from_option, UserProviderRef};
 use servers::error::Result;
 use servers::query_handler::{
     ScriptHandler, ScriptHandlerRef, SqlQueryHandler, SqlQueryHandlerRef,
 };
+use session::context::QueryContextRef;
+use sql::statements::statement::Statement;
 use table::test_util::MemTable;
+use tempdir::TempDir;
 
 mod http;
 mod mysql;
@@ -52,15 +56,28 @@ impl DummyInstance {
 
 #[async_trait]
 impl SqlQueryHandler for DummyInstance {
-    async fn do_query(&self, query: &str) -> Result<Output> {
-        let plan = self.query_engine.sql_to_plan(query).unwrap();
+    async fn do_statement_query(&self, query: &str, query_ctx: QueryContextRef) -> Result<Output> {
+        let plan = self.query_engine.sql_to_plan(query, query_ctx).unwrap();
+        Ok(self.query_engine.execute(&plan).await.unwrap())
+    }
+
+    async fn do_parsed_statement_query(
+        &self,
+        stmt: Statement,
+        _query: &str,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let plan = self
+            .query_engine
+            .statement_to_plan(stmt, query_ctx)
+            .unwrap();
         Ok(self.query_engine.execute(&plan).await.unwrap())
     }
 }
 
 #[async_trait]
 impl ScriptHandler for DummyInstance {
-    async fn insert_script(&self, name: &str, script: &str) -> Result<()> {
+    async fn insert_script(&self, schema: &str, name: &str, script: &str) -> Result<()> {
         let script = self
             .py_engine
             .compile(script, CompileContext::default())
@@ -69,13 +86,19 @@ impl ScriptHandler for DummyInstance {
         self.scripts
             .write()
             .unwrap()
-            .insert(name.to_string(), Arc::new(script));
+            .insert(format!("{}.{}", schema, name), Arc::new(script));
 
         Ok(())
     }
 
-    async fn execute_script(&self, name: &str) -> Result<Output> {
-        let py_script = self.scripts.read().unwrap().get(name).unwrap().clone();
+    async fn execute_script(&self, schema: &str, name: &str) -> Result<Output> {
+        let py_script = self
+            .scripts
+            .read()
+            .unwrap()
+            .get(&format!("{}.{}", schema, name))
+            .unwrap()
+            .clone();
 
         Ok(py_script.execute(EvalContext::default()).await.unwrap())
     }
@@ -108,3 +131,15 @@ fn create_testing_script_handler(table: MemTable) -> ScriptHandlerRef {
 fn create_testing_sql_query_handler(table: MemTable) -> SqlQueryHandlerRef {
     Arc::new(create_testing_instance(table)) as _
 }
+
+/// Creates a static user provider with user "test_user" and password "test_pwd".
+fn create_testing_user_provider() -> UserProviderRef {
+    let dir = TempDir::new("test_user_provider").unwrap();
+    let path = dir.path().join("users");
+    std::fs::write(&path, "test_user=test_pwd\n").unwrap();
+    user_provider_from_option(&format!(
+        "static_user_provider:file:{}",
+        path.to_str().unwrap()
+    ))
+    .unwrap()
+}
@@ -22,15 +22,25 @@ use datatypes::schema::Schema;
 use mysql_async::prelude::*;
 use rand::rngs::StdRng;
 use rand::Rng;
+use servers::auth::UserProviderRef;
 use servers::error::Result;
 use servers::mysql::server::MysqlServer;
 use servers::server::Server;
+use servers::tls::{ServerTls, TlsMode, TlsOption};
 use table::test_util::MemTable;
 
-use crate::create_testing_sql_query_handler;
 use crate::mysql::{all_datatype_testing_data, MysqlTextRow, TestingData};
+use crate::{create_testing_sql_query_handler, create_testing_user_provider};
 
 fn create_mysql_server(table: MemTable) -> Result<Box<dyn Server>> {
+    create_mysql_server_with_options(table, None, ServerTls::default())
+}
+
+fn create_mysql_server_with_options(
+    table: MemTable,
+    user_provider: Option<UserProviderRef>,
+    tls: ServerTls,
+) -> Result<Box<dyn Server>> {
     let query_handler = create_testing_sql_query_handler(table);
     let io_runtime = Arc::new(
         RuntimeBuilder::default()
@@ -39,7 +49,12 @@ fn create_mysql_server(table: MemTable) -> Result<Box<dyn Server>> {
             .build()
             .unwrap(),
     );
-    Ok(MysqlServer::create_server(query_handler, io_runtime))
+    Ok(MysqlServer::create_server(
+        query_handler,
+        io_runtime,
+        user_provider,
+        tls,
+    ))
 }
 
 #[tokio::test]
@@ -110,6 +125,106 @@ async fn test_shutdown_mysql_server() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_mysql_auth() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    let table = MemTable::default_numbers_table();
+    let mysql_server = create_mysql_server_with_options(
+        table,
+        Some(create_testing_user_provider()),
+        ServerTls::default(),
+    )?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_port = mysql_server.start(listening).await.unwrap().port();
+
+    let mut connection = create_connection_with_user(server_port, "test_user", Some("test_pwd"))
+        .await
+        .unwrap();
+    let result: u32 = connection
+        .query_first("SELECT uint32s FROM numbers LIMIT 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result, 0);
+
+    assert!(
+        create_connection_with_user(server_port, "test_user", Some("wrong_pwd"))
+            .await
+            .is_err()
+    );
+    assert!(create_connection_with_user(server_port, "test_user", None)
+        .await
+        .is_err());
+    assert!(
+        create_connection_with_user(server_port, "no_such_user", Some("test_pwd"))
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mysql_init_db_with_backtick() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    let table = MemTable::default_numbers_table();
+    let mysql_server = create_mysql_server(table)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_port = mysql_server.start(listening).await.unwrap().port();
+
+    let opts = mysql_async::OptsBuilder::default()
+        .ip_or_hostname("127.0.0.1")
+        .tcp_port(server_port)
+        .prefer_socket(false)
+        .wait_timeout(Some(1000))
+        .db_name(Some("public`; DROP TABLE numbers; --"));
+    assert!(mysql_async::Conn::new(opts).await.is_err());
+
+    let mut connection = create_connection(server_port, false).await.unwrap();
+    let result: u32 = connection
+        .query_first("SELECT uint32s FROM numbers LIMIT 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mysql_tls_mode() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    for (mode, plaintext_allowed) in [(TlsMode::Prefer, true), (TlsMode::Require, false)] {
+        let tls = TlsOption {
+            mode,
+            cert_path: format!("{}/tests/ssl/server.crt", env!("CARGO_MANIFEST_DIR")),
+            key_path: format!("{}/tests/ssl/server.key", env!("CARGO_MANIFEST_DIR")),
+            ca_cert_path: None,
+        }
+        .setup()?;
+        let table = MemTable::default_numbers_table();
+        let mysql_server = create_mysql_server_with_options(table, None, tls)?;
+        let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let server_port = mysql_server.start(listening).await.unwrap().port();
+
+        // The client doesn't send SSLRequest without SSL options.
+        let result = create_connection(server_port, false).await;
+        assert_eq!(plaintext_allowed, result.is_ok());
+        if let Ok(mut connection) = result {
+            let result: u32 = connection
+                .query_first("SELECT uint32s FROM numbers LIMIT 1")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(result, 0);
+        }
+
+        mysql_server.shutdown().await.unwrap();
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_query_all_datatypes() -> Result<()> {
     common_telemetry::init_default_ut_logging();
@@ -200,6 +315,70 @@ async fn test_query_concurrently() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_query_multiple_statements() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    let table = MemTable::default_numbers_table();
+
+    let mysql_server = create_mysql_server(table)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_addr = mysql_server.start(listening).await.unwrap();
+
+    let mut connection = create_connection(server_addr.port(), false).await.unwrap();
+    let mut result = connection
+        .query_iter(
+            "SELECT uint32s FROM numbers WHERE uint32s = 1; SELECT uint32s FROM numbers WHERE uint32s < 3 ORDER BY uint32s",
+        )
+        .await
+        .unwrap();
+    let first: Vec<u32> = result.collect().await.unwrap();
+    assert_eq!(vec![1], first);
+    let second: Vec<u32> = result.collect().await.unwrap();
+    assert_eq!(vec![0, 1, 2], second);
+    drop(result);
+
+    // the error of the failed statement is reported after the results before it
+    let mut result = connection
+        .query_iter("SELECT uint32s FROM numbers WHERE uint32s = 1; SELECT * FROM not_exist")
+        .await
+        .unwrap();
+    let first: Vec<u32> = result.collect().await.unwrap();
+    assert_eq!(vec![1], first);
+    let err = result.collect::<u32>().await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Failed to execute statement 2 of 2"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_statement() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    let table = MemTable::default_numbers_table();
+
+    let mysql_server = create_mysql_server(table)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_addr = mysql_server.start(listening).await.unwrap();
+
+    let mut connection = create_connection(server_addr.port(), false).await.unwrap();
+    let stmt = connection
+        .prep("SELECT uint32s FROM numbers WHERE uint32s > ? AND uint32s < ? ORDER BY uint32s")
+        .await
+        .unwrap();
+    assert_eq!(2, stmt.num_params());
+
+    let result: Vec<u32> = connection.exec(&stmt, (10, 13)).await.unwrap();
+    assert_eq!(vec![11, 12], result);
+    let result: Vec<u32> = connection.exec(&stmt, (97, 100)).await.unwrap();
+    assert_eq!(vec![98, 99], result);
+    assert!(connection.exec::<u32, _, _>(&stmt, (1,)).await.is_err());
+
+    connection.close(stmt).await.unwrap();
+    Ok(())
+}
+
 async fn create_connection(port: u16, with_pwd: bool) -> mysql_async::Result<mysql_async::Conn> {
     let mut opts = mysql_async::OptsBuilder::default()
         .ip_or_hostname("127.0.0.1")
@@ -213,3 +392,19 @@ async fn create_connection(port: u16, with_pwd: bool) -> mysql_async::Result<mys
 
     mysql_async::Conn::new(opts).await
 }
+
+async fn create_connection_with_user(
+    port: u16,
+    user: &str,
+    pwd: Option<&str>,
+) -> mysql_async::Result<mysql_async::Conn> {
+    let opts = mysql_async::OptsBuilder::default()
+        .ip_or_hostname("127.0.0.1")
+        .tcp_port(port)
+        .prefer_socket(false)
+        .wait_timeout(Some(1000))
+        .user(Some(user))
+        .pass(pwd);
+
+    mysql_async::Conn::new(opts).await
+}
@@ -27,6 +27,7 @@ use servers::opentsdb::connection::Connection;
 use servers::opentsdb::OpentsdbServer;
 use servers::query_handler::OpentsdbProtocolHandler;
 use servers::server::Server;
+use session::context::QueryContextRef;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Notify};
 
@@ -36,16 +37,18 @@ struct DummyOpentsdbInstance {
 
 #[async_trait]
 impl OpentsdbProtocolHandler for DummyOpentsdbInstance {
-    async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-        let metric = data_point.metric();
-        if metric == "should_failed" {
-            return server_error::InternalSnafu {
-                err_msg: "expected",
+    async fn exec(&self, data_points: &[DataPoint], _query_ctx: QueryContextRef) -> Result<()> {
+        for data_point in data_points {
+            let metric = data_point.metric();
+            if metric == "should_failed" {
+                return server_error::InternalSnafu {
+                    err_msg: "expected",
+                }
+                .fail();
             }
-            .fail();
+            let i = metric.parse::<i32>().unwrap();
+            let _ = self.tx.send(i * i).await;
         }
-        let i = metric.parse::<i32>().unwrap();
-        let _ = self.tx.send(i * i).await;
         Ok(())
     }
 }
@@ -20,14 +20,24 @@ use common_runtime::Builder as RuntimeBuilder;
 use rand::rngs::StdRng;
 use rand::Rng;
 use servers::error::Result;
-use servers::postgres::PostgresServer;
+use servers::postgres::{PgAuthMethod, PostgresServer};
 use servers::server::Server;
+use servers::tls::{ServerTls, TlsMode, TlsOption};
 use table::test_util::MemTable;
 use tokio_postgres::{Client, Error as PgError, NoTls, SimpleQueryMessage};
 
-use crate::create_testing_sql_query_handler;
+use crate::{create_testing_sql_query_handler, create_testing_user_provider};
 
 fn create_postgres_server(table: MemTable, check_pwd: bool) -> Result<Box<dyn Server>> {
+    create_postgres_server_with_options(table, check_pwd, PgAuthMethod::Md5, ServerTls::default())
+}
+
+fn create_postgres_server_with_options(
+    table: MemTable,
+    check_pwd: bool,
+    auth_method: PgAuthMethod,
+    tls: ServerTls,
+) -> Result<Box<dyn Server>> {
     let query_handler = create_testing_sql_query_handler(table);
     let io_runtime = Arc::new(
         RuntimeBuilder::default()
@@ -36,9 +46,16 @@ fn create_postgres_server(table: MemTable, check_pwd: bool) -> Result<Box<dyn Se
             .build()
             .unwrap(),
     );
+    let user_provider = if check_pwd {
+        Some(create_testing_user_provider())
+    } else {
+        None
+    };
     Ok(Box::new(PostgresServer::new(
         query_handler,
-        check_pwd,
+        user_provider,
+        auth_method,
+        tls,
         io_runtime,
     )))
 }
@@ -126,6 +143,73 @@ async fn test_shutdown_pg_server(with_pwd: bool) -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_pg_auth() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    for auth_method in [PgAuthMethod::Cleartext, PgAuthMethod::Md5] {
+        let table = MemTable::default_numbers_table();
+        let pg_server =
+            create_postgres_server_with_options(table, true, auth_method, ServerTls::default())?;
+        let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let server_port = pg_server.start(listening).await.unwrap().port();
+
+        let client = create_connection(server_port, true).await.unwrap();
+        let rows = client
+            .simple_query("SELECT uint32s FROM numbers LIMIT 1")
+            .await
+            .unwrap();
+        assert_eq!(unwrap_results(&rows)[0], "0");
+
+        let url = format!(
+            "host=127.0.0.1 port={} user=test_user password=wrong_pwd connect_timeout=2",
+            server_port
+        );
+        assert!(tokio_postgres::connect(&url, NoTls).await.is_err());
+        let url = format!(
+            "host=127.0.0.1 port={} user=no_such_user password=test_pwd connect_timeout=2",
+            server_port
+        );
+        assert!(tokio_postgres::connect(&url, NoTls).await.is_err());
+
+        pg_server.shutdown().await.unwrap();
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pg_tls_mode() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+
+    for (mode, plaintext_allowed) in [(TlsMode::Prefer, true), (TlsMode::Require, false)] {
+        let tls = TlsOption {
+            mode,
+            cert_path: format!("{}/tests/ssl/server.crt", env!("CARGO_MANIFEST_DIR")),
+            key_path: format!("{}/tests/ssl/server.key", env!("CARGO_MANIFEST_DIR")),
+            ca_cert_path: None,
+        }
+        .setup()?;
+        let table = MemTable::default_numbers_table();
+        let pg_server = create_postgres_server_with_options(table, false, PgAuthMethod::Md5, tls)?;
+        let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+        let server_port = pg_server.start(listening).await.unwrap().port();
+
+        // Clients without TLS never ask the server to upgrade the connection.
+        let result = create_connection(server_port, false).await;
+        assert_eq!(plaintext_allowed, result.is_ok());
+        if let Ok(client) = result {
+            let rows = client
+                .simple_query("SELECT uint32s FROM numbers LIMIT 1")
+                .await
+                .unwrap();
+            assert_eq!(unwrap_results(&rows)[0], "0");
+        }
+
+        pg_server.shutdown().await.unwrap();
+    }
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn test_query_pg_concurrently() -> Result<()> {
     common_telemetry::init_default_ut_logging();
@@ -0,0 +1,331 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use serde::{Deserialize, Serialize};
+
+pub type QueryContextRef = Arc<QueryContext>;
+
+/// Connection ids are `node_id << CONN_SEQ_BITS | seq`, where the node id tells which frontend
+/// of a cluster the connection belongs to.
+const CONN_SEQ_BITS: u32 = 20;
+const CONN_SEQ_MASK: u32 = (1 << CONN_SEQ_BITS) - 1;
+
+/// Number of distinct node ids of connections, node ids are taken modulo it.
+pub const MAX_CONN_NODES: u32 = 1 << (32 - CONN_SEQ_BITS);
+
+static CONN_NODE_ID: AtomicU32 = AtomicU32::new(0);
+static NEXT_CONN_SEQ: AtomicU32 = AtomicU32::new(1);
+
+/// Sets the node id of connections created afterwards.
+pub fn set_conn_node_id(node_id: u32) {
+    CONN_NODE_ID.store(
+        (node_id % MAX_CONN_NODES) << CONN_SEQ_BITS,
+        Ordering::Relaxed,
+    );
+}
+
+/// Returns the node id of the connection `conn_id`.
+pub fn conn_node_id(conn_id: u32) -> u32 {
+    conn_id >> CONN_SEQ_BITS
+}
+
+fn next_conn_id() -> u32 {
+    loop {
+        let seq = NEXT_CONN_SEQ.fetch_add(1, Ordering::Relaxed) & CONN_SEQ_MASK;
+        // MySQL clients take 0 as no connection id, it's skipped once the sequence wraps.
+        if seq != 0 {
+            return CONN_NODE_ID.load(Ordering::Relaxed) | seq;
+        }
+    }
+}
+
+/// Protocols clients connect with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Mysql,
+    Postgres,
+    Http,
+    Grpc,
+    Opentsdb,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Mysql => write!(f, "mysql"),
+            Channel::Postgres => write!(f, "postgres"),
+            Channel::Http => write!(f, "http"),
+            Channel::Grpc => write!(f, "grpc"),
+            Channel::Opentsdb => write!(f, "opentsdb"),
+        }
+    }
+}
+
+/// Resource limits of a user or a database, `None` means unlimited. They are enforced by the
+/// query handlers of frontend for each request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaLimits {
+    /// Max number of queries running at the same time.
+    pub max_concurrent_queries: Option<usize>,
+    /// Max number of rows a query can return, the query is cancelled once it returns more.
+    pub max_result_rows: Option<usize>,
+    /// Max duration of a query in milliseconds, including the time to fetch all of its results.
+    pub query_timeout_millis: Option<u64>,
+    /// Max number of rows ingested per second, writes over the rate are delayed.
+    pub max_ingest_rows_per_sec: Option<u64>,
+}
+
+impl QuotaLimits {
+    pub fn query_timeout(&self) -> Option<Duration> {
+        self.query_timeout_millis.map(Duration::from_millis)
+    }
+
+    /// Combines two limits by taking the stricter one of each.
+    pub fn intersect(&self, other: &QuotaLimits) -> QuotaLimits {
+        fn min<T: Ord + Copy>(a: Option<T>, b: Option<T>) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+
+        QuotaLimits {
+            max_concurrent_queries: min(self.max_concurrent_queries, other.max_concurrent_queries),
+            max_result_rows: min(self.max_result_rows, other.max_result_rows),
+            query_timeout_millis: min(self.query_timeout_millis, other.query_timeout_millis),
+            max_ingest_rows_per_sec: min(
+                self.max_ingest_rows_per_sec,
+                other.max_ingest_rows_per_sec,
+            ),
+        }
+    }
+}
+
+/// Context of queries, shared by all queries of the same session (connection). Statements like
+/// `USE db` or `SET time_zone = ...` change it, and the subsequent queries see the changes.
+pub struct QueryContext {
+    /// Unique id of the session, which is also the connection id of MySQL clients.
+    conn_id: u32,
+    client_addr: RwLock<Option<String>>,
+    channel: RwLock<Option<Channel>>,
+    current_catalog: RwLock<String>,
+    current_schema: RwLock<String>,
+    current_user: RwLock<Option<String>>,
+    /// Limits of the current user given by the user provider when the session is authenticated.
+    quota_limits: RwLock<QuotaLimits>,
+    time_zone: RwLock<Option<String>>,
+    /// Client variables set by `SET name = value`, names are in lowercase.
+    variables: RwLock<HashMap<String, String>>,
+}
+
+impl fmt::Debug for QueryContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryContext")
+            .field("conn_id", &self.conn_id)
+            .field("client_addr", &self.client_addr())
+            .field("channel", &self.channel())
+            .field("current_catalog", &self.current_catalog())
+            .field("current_schema", &self.current_schema())
+            .field("current_user", &self.current_user())
+            .field("time_zone", &self.time_zone())
+            .finish()
+    }
+}
+
+impl Default for QueryContext {
+    fn default() -> Self {
+        Self::with(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME)
+    }
+}
+
+impl QueryContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a context with default catalog and schema, for queries not bound to any session.
+    pub fn arc() -> QueryContextRef {
+        Arc::new(Self::new())
+    }
+
+    pub fn with(catalog: &str, schema: &str) -> Self {
+        Self {
+            conn_id: next_conn_id(),
+            client_addr: RwLock::new(None),
+            channel: RwLock::new(None),
+            current_catalog: RwLock::new(catalog.to_string()),
+            current_schema: RwLock::new(schema.to_string()),
+            current_user: RwLock::new(None),
+            quota_limits: RwLock::new(QuotaLimits::default()),
+            time_zone: RwLock::new(None),
+            variables: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn conn_id(&self) -> u32 {
+        self.conn_id
+    }
+
+    pub fn client_addr(&self) -> Option<String> {
+        self.client_addr.read().unwrap().clone()
+    }
+
+    pub fn set_client_addr(&self, client_addr: Option<String>) {
+        *self.client_addr.write().unwrap() = client_addr;
+    }
+
+    pub fn channel(&self) -> Option<Channel> {
+        *self.channel.read().unwrap()
+    }
+
+    pub fn set_channel(&self, channel: Channel) {
+        *self.channel.write().unwrap() = Some(channel);
+    }
+
+    pub fn current_catalog(&self) -> String {
+        self.current_catalog.read().unwrap().clone()
+    }
+
+    pub fn set_current_catalog(&self, catalog: &str) {
+        *self.current_catalog.write().unwrap() = catalog.to_string();
+    }
+
+    pub fn current_schema(&self) -> String {
+        self.current_schema.read().unwrap().clone()
+    }
+
+    pub fn set_current_schema(&self, schema: &str) {
+        *self.current_schema.write().unwrap() = schema.to_string();
+    }
+
+    pub fn current_user(&self) -> Option<String> {
+        self.current_user.read().unwrap().clone()
+    }
+
+    pub fn set_current_user(&self, user: Option<String>) {
+        *self.current_user.write().unwrap() = user;
+    }
+
+    pub fn quota_limits(&self) -> QuotaLimits {
+        self.quota_limits.read().unwrap().clone()
+    }
+
+    pub fn set_quota_limits(&self, quota_limits: QuotaLimits) {
+        *self.quota_limits.write().unwrap() = quota_limits;
+    }
+
+    pub fn time_zone(&self) -> Option<String> {
+        self.time_zone.read().unwrap().clone()
+    }
+
+    pub fn set_time_zone(&self, time_zone: Option<String>) {
+        *self.time_zone.write().unwrap() = time_zone;
+    }
+
+    pub fn variable(&self, name: &str) -> Option<String> {
+        self.variables
+            .read()
+            .unwrap()
+            .get(&name.to_lowercase())
+            .cloned()
+    }
+
+    pub fn set_variable(&self, name: &str, value: String) {
+        let _ = self
+            .variables
+            .write()
+            .unwrap()
+            .insert(name.to_lowercase(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_context() {
+        let ctx = QueryContext::arc();
+        assert_eq!(ctx.current_catalog(), DEFAULT_CATALOG_NAME);
+        assert_eq!(ctx.current_schema(), DEFAULT_SCHEMA_NAME);
+        assert_eq!(ctx.current_user(), None);
+        assert_eq!(ctx.time_zone(), None);
+
+        let shared = ctx.clone();
+        ctx.set_current_schema("my_db");
+        ctx.set_current_user(Some("greptime".to_string()));
+        ctx.set_time_zone(Some("+08:00".to_string()));
+        ctx.set_variable("AutoCommit", "1".to_string());
+        assert_eq!(shared.current_schema(), "my_db");
+        assert_eq!(shared.current_user(), Some("greptime".to_string()));
+        assert_eq!(shared.time_zone(), Some("+08:00".to_string()));
+        assert_eq!(shared.variable("autocommit"), Some("1".to_string()));
+        assert_eq!(shared.variable("AUTOCOMMIT"), Some("1".to_string()));
+        assert_eq!(shared.variable("not_set"), None);
+
+        let limits = QuotaLimits {
+            max_result_rows: Some(10),
+            ..Default::default()
+        };
+        ctx.set_quota_limits(limits.clone());
+        assert_eq!(shared.quota_limits(), limits);
+    }
+
+    #[test]
+    fn test_connection_info() {
+        let ctx = QueryContext::arc();
+        assert_ne!(ctx.conn_id(), QueryContext::arc().conn_id());
+        assert_eq!(3, conn_node_id((3 << CONN_SEQ_BITS) | 42));
+        assert_eq!(ctx.client_addr(), None);
+        assert_eq!(ctx.channel(), None);
+
+        ctx.set_client_addr(Some("127.0.0.1:4406".to_string()));
+        ctx.set_channel(Channel::Mysql);
+        assert_eq!(ctx.client_addr(), Some("127.0.0.1:4406".to_string()));
+        assert_eq!(ctx.channel(), Some(Channel::Mysql));
+        assert_eq!("mysql", Channel::Mysql.to_string());
+    }
+
+    #[test]
+    fn test_intersect_quota_limits() {
+        let a = QuotaLimits {
+            max_concurrent_queries: Some(4),
+            max_result_rows: None,
+            query_timeout_millis: Some(1000),
+            max_ingest_rows_per_sec: None,
+        };
+        let b = QuotaLimits {
+            max_concurrent_queries: Some(2),
+            max_result_rows: Some(100),
+            query_timeout_millis: Some(3000),
+            max_ingest_rows_per_sec: None,
+        };
+        let limits = a.intersect(&b);
+        assert_eq!(limits.max_concurrent_queries, Some(2));
+        assert_eq!(limits.max_result_rows, Some(100));
+        assert_eq!(limits.query_timeout_millis, Some(1000));
+        assert_eq!(limits.max_ingest_rows_per_sec, None);
+        assert_eq!(limits.query_timeout(), Some(Duration::from_secs(1)));
+
+        assert_eq!(QuotaLimits::default().intersect(&a), a);
+    }
+}
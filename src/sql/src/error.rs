@@ -116,6 +116,17 @@ pub enum Error {
         source: datatypes::error::Error,
     },
 
+    #[snafu(display(
+        "Mismatched number of parameters, expect: {}, actual: {}",
+        expect,
+        actual
+    ))]
+    ParamNumMismatch {
+        expect: usize,
+        actual: usize,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display(
         "Failed to convert data type to gRPC data type defined in proto, source: {}",
         source
@@ -141,9 +152,10 @@ impl ErrorExt for Error {
             | SqlTypeNotSupported { .. }
             | InvalidDefault { .. } => StatusCode::InvalidSyntax,
 
-            InvalidDatabaseName { .. } | ColumnTypeMismatch { .. } | InvalidTableName { .. } => {
-                StatusCode::InvalidArguments
-            }
+            InvalidDatabaseName { .. }
+            | ColumnTypeMismatch { .. }
+            | InvalidTableName { .. }
+            | ParamNumMismatch { .. } => StatusCode::InvalidArguments,
             UnsupportedAlterTableStatement { .. } => StatusCode::InvalidSyntax,
             SerializeColumnDefaultConstraint { source, .. } => source.status_code(),
             ConvertToGrpcDataType { source, .. } => source.status_code(),
@@ -19,4 +19,6 @@ pub mod dialect;
 pub mod error;
 pub mod parser;
 pub mod parsers;
+pub mod prepared;
+mod scanner;
 pub mod statements;
@@ -21,7 +21,10 @@ use sqlparser::tokenizer::{Token, Tokenizer};
 use crate::error::{
     self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result, SyntaxSnafu, TokenizerSnafu,
 };
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowKind, ShowTables};
+use crate::parsers::range_parser;
+use crate::statements::show::{
+    ShowCreateTable, ShowDatabases, ShowKind, ShowProcesslist, ShowTables,
+};
 use crate::statements::statement::Statement;
 
 /// GrepTime SQL parser context, a simple wrapper for Datafusion SQL parser.
@@ -37,6 +40,8 @@ impl<'a> ParserContext<'a> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
 
         let tokens: Vec<Token> = tokenizer.tokenize().context(TokenizerSnafu { sql })?;
+        let (tokens, mut range_selects) =
+            range_parser::extract_range_selects(sql, dialect, tokens)?;
 
         let mut parser_ctx = ParserContext {
             sql,
@@ -57,7 +62,19 @@ impl<'a> ParserContext<'a> {
                 return parser_ctx.unsupported(parser_ctx.peek_token_as_string());
             }
 
-            let statement = parser_ctx.parse_statement()?;
+            let mut statement = parser_ctx.parse_statement()?;
+            if let Some(range_select) = range_selects.pop_front().flatten() {
+                match &mut statement {
+                    Statement::Query(query) => query.range_select = Some(range_select),
+                    Statement::Explain(explain) => explain.query.range_select = Some(range_select),
+                    _ => {
+                        return error::InvalidSqlSnafu {
+                            msg: "RANGE and ALIGN are only allowed in SELECT",
+                        }
+                        .fail()
+                    }
+                }
+            }
             stmts.push(statement);
             expecting_statement_delimiter = true;
         }
@@ -65,6 +82,12 @@ impl<'a> ParserContext<'a> {
         Ok(stmts)
     }
 
+    /// Splits SQL text, which may contain multiple statements, into the text of each statement
+    /// so that they can be executed one by one.
+    pub fn split_statements(sql: &str) -> Vec<&str> {
+        crate::scanner::split_statements(sql)
+    }
+
     /// Parses parser context to a set of statements.
     pub fn parse_statement(&mut self) -> Result<Statement> {
         match self.parser.peek_token() {
@@ -91,6 +114,31 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::ALTER => self.parse_alter(),
 
+                    Keyword::SET => {
+                        self.parser.next_token();
+                        self.parse_set_variables()
+                    }
+
+                    _ if w.value.eq_ignore_ascii_case("USE") => {
+                        self.parser.next_token();
+                        self.parse_use()
+                    }
+
+                    _ if w.value.eq_ignore_ascii_case("GRANT") => {
+                        self.parser.next_token();
+                        self.parse_grant()
+                    }
+
+                    _ if w.value.eq_ignore_ascii_case("REVOKE") => {
+                        self.parser.next_token();
+                        self.parse_revoke()
+                    }
+
+                    _ if w.value.eq_ignore_ascii_case("KILL") => {
+                        self.parser.next_token();
+                        self.parse_kill()
+                    }
+
                     // todo(hl) support more statements.
                     _ => self.unsupported(self.peek_token_as_string()),
                 }
@@ -123,6 +171,14 @@ impl<'a> ParserContext<'a> {
             } else {
                 self.unsupported(self.peek_token_as_string())
             }
+        } else if self.consume_token("PROCESSLIST") {
+            Ok(Statement::ShowProcesslist(ShowProcesslist { full: false }))
+        } else if self.consume_token("FULL") {
+            if self.consume_token("PROCESSLIST") {
+                Ok(Statement::ShowProcesslist(ShowProcesslist { full: true }))
+            } else {
+                self.unsupported(self.peek_token_as_string())
+            }
         } else {
             self.unsupported(self.peek_token_as_string())
         }
@@ -217,10 +273,6 @@ impl<'a> ParserContext<'a> {
         Ok(Statement::ShowTables(ShowTables { kind, database }))
     }
 
-    fn parse_explain(&mut self) -> Result<Statement> {
-        todo!()
-    }
-
     // Report unexpected token
     pub(crate) fn expected<T>(&self, expected: &str, found: Token) -> Result<T> {
         Err(ParserError::ParserError(format!(
@@ -14,5 +14,12 @@
 
 mod alter_parser;
 pub(crate) mod create_parser;
+mod explain_parser;
 pub(crate) mod insert_parser;
+mod kill_parser;
+mod privilege_parser;
 pub(crate) mod query_parser;
+mod range_parser;
+mod set_var_parser;
+
+pub use range_parser::parse_duration_millis;
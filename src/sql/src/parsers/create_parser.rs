@@ -27,13 +27,17 @@ use crate::ast::{ColumnDef, Ident, TableConstraint, Value as SqlValue};
 use crate::error::{self, InvalidTimeIndexSnafu, Result, SyntaxSnafu};
 use crate::parser::ParserContext;
 use crate::statements::create::{
-    CreateDatabase, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateDatabase, CreateMaterializedView, CreateTable, PartitionEntry, PartitionMethod,
+    Partitions, TIME_INDEX,
 };
+use crate::statements::query::Query;
 use crate::statements::statement::Statement;
 use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
 
 const ENGINE: &str = "ENGINE";
 const MAXVALUE: &str = "MAXVALUE";
+const HASH: &str = "HASH";
+const PARTITIONS: &str = "PARTITIONS";
 
 static LESS: Lazy<Token> = Lazy::new(|| Token::make_keyword("LESS"));
 static THAN: Lazy<Token> = Lazy::new(|| Token::make_keyword("THAN"));
@@ -47,6 +51,14 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::DATABASE => self.parse_create_database(),
 
+                _ if w.value.eq_ignore_ascii_case("USER") => self.parse_create_user(),
+
+                _ if w.value.eq_ignore_ascii_case("ROLE") => self.parse_create_role(),
+
+                _ if w.value.eq_ignore_ascii_case("MATERIALIZED") => {
+                    self.parse_create_materialized_view()
+                }
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
@@ -70,6 +82,47 @@ impl<'a> ParserContext<'a> {
         }))
     }
 
+    fn parse_create_materialized_view(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        self.parser
+            .expect_keyword(Keyword::VIEW)
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "VIEW",
+                actual: self.peek_token_as_string(),
+            })?;
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+
+        let name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a view name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "AS",
+                actual: self.peek_token_as_string(),
+            })?;
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(Statement::CreateMaterializedView(CreateMaterializedView {
+            if_not_exists,
+            name,
+            query: Box::new(Query::try_from(query)?),
+        }))
+    }
+
     fn parse_create_table(&mut self) -> Result<Statement> {
         self.parser.next_token();
         let if_not_exists =
@@ -112,15 +165,28 @@ impl<'a> ParserContext<'a> {
 
     // "PARTITION BY ..." syntax:
     // https://dev.mysql.com/doc/refman/8.0/en/partitioning-columns-range.html
+    // https://dev.mysql.com/doc/refman/8.0/en/partitioning-hash.html
     fn parse_partitions(&mut self) -> Result<Option<Partitions>> {
         if !self.parser.parse_keyword(Keyword::PARTITION) {
             return Ok(None);
         }
         self.parser
-            .expect_keywords(&[Keyword::BY, Keyword::RANGE, Keyword::COLUMNS])
+            .expect_keyword(Keyword::BY)
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "BY",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        if self.consume_token(HASH) {
+            return self.parse_hash_partitions().map(Some);
+        }
+
+        self.parser
+            .expect_keywords(&[Keyword::RANGE, Keyword::COLUMNS])
             .context(error::UnexpectedSnafu {
                 sql: self.sql,
-                expected: "BY, RANGE, COLUMNS",
+                expected: "RANGE, COLUMNS",
                 actual: self.peek_token_as_string(),
             })?;
 
@@ -132,11 +198,40 @@ impl<'a> ParserContext<'a> {
         let entries = self.parse_comma_separated(Self::parse_partition_entry)?;
 
         Ok(Some(Partitions {
+            method: PartitionMethod::RangeColumns,
             column_list,
             entries,
         }))
     }
 
+    // "HASH(column_list) PARTITIONS num" after "PARTITION BY"
+    fn parse_hash_partitions(&mut self) -> Result<Partitions> {
+        let column_list = self
+            .parser
+            .parse_parenthesized_column_list(Mandatory)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        if !self.consume_token(PARTITIONS) {
+            return self.expected(PARTITIONS, self.parser.peek_token());
+        }
+        let partition_num = self
+            .parser
+            .parse_literal_uint()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let partition_num = u32::try_from(partition_num)
+            .ok()
+            .filter(|x| *x > 0)
+            .context(error::InvalidSqlSnafu {
+                msg: format!("Invalid number of hash partitions: {}", partition_num),
+            })?;
+
+        Ok(Partitions {
+            method: PartitionMethod::Hash { partition_num },
+            column_list,
+            entries: vec![],
+        })
+    }
+
     fn parse_partition_entry(&mut self) -> Result<PartitionEntry> {
         self.parser
             .expect_keyword(Keyword::PARTITION)
@@ -345,6 +440,16 @@ fn validate_create(create_table: &CreateTable) -> Result<()> {
 fn validate_partitions(columns: &[ColumnDef], partitions: &Partitions) -> Result<()> {
     let partition_columns = ensure_partition_columns_defined(columns, partitions)?;
 
+    if let PartitionMethod::Hash { .. } = partitions.method {
+        ensure!(
+            !partition_columns.is_empty(),
+            error::InvalidSqlSnafu {
+                msg: "Please provide at least one column for hash partitioning.",
+            }
+        );
+        return Ok(());
+    }
+
     ensure_partition_names_no_duplicate(partitions)?;
 
     ensure_value_list_len_matches_columns(partitions, &partition_columns)?;
@@ -450,7 +555,7 @@ fn ensure_value_list_len_matches_columns(
     Ok(())
 }
 
-/// Ensure that all columns used in "PARTITION BY RANGE COLUMNS" are defined in create table.
+/// Ensure that all columns used in "PARTITION BY" are defined in create table.
 fn ensure_partition_columns_defined<'a>(
     columns: &'a [ColumnDef],
     partitions: &'a Partitions,
@@ -519,6 +624,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_create_materialized_view() {
+        let sql = "CREATE MATERIALIZED VIEW IF NOT EXISTS cpu_1m AS \
+                   SELECT host, date_bin('1m', ts) AS ts, avg(cpu) FROM monitor \
+                   GROUP BY host, date_bin('1m', ts)";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateMaterializedView(c) => {
+                assert!(c.if_not_exists);
+                assert_eq!("cpu_1m", c.name.to_string());
+                assert_eq!(
+                    "SELECT host, date_bin('1m', ts) AS ts, avg(cpu) FROM monitor GROUP BY host, date_bin('1m', ts)",
+                    c.query.inner.to_string()
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "CREATE MATERIALIZED TABLE cpu_1m AS SELECT * FROM monitor";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unexpected token while parsing SQL statement"));
+    }
+
     #[test]
     fn test_validate_create() {
         let sql = r"
@@ -750,6 +882,69 @@ ENGINE=mito";
             .contains("sql parser error: Expected a concrete value, found: MAXVALU"));
     }
 
+    #[test]
+    fn test_parse_create_table_with_hash_partitions() {
+        let sql = r"
+CREATE TABLE monitor (
+  host_id    INT,
+  idc        STRING,
+  ts         TIMESTAMP,
+  cpu        DOUBLE DEFAULT 0,
+  TIME INDEX (ts),
+)
+PARTITION BY HASH (idc, host_id) PARTITIONS 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(result.len(), 1);
+        match &result[0] {
+            Statement::CreateTable(c) => {
+                let partitions = c.partitions.as_ref().unwrap();
+                assert_eq!(
+                    partitions.method,
+                    PartitionMethod::Hash { partition_num: 4 }
+                );
+                let column_list = partitions
+                    .column_list
+                    .iter()
+                    .map(|x| &x.value)
+                    .collect::<Vec<&String>>();
+                assert_eq!(column_list, vec!["idc", "host_id"]);
+                assert!(partitions.entries.is_empty());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("sql parser error: Expected PARTITIONS, found: 4"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) PARTITIONS 0
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid number of hash partitions: 0"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (x) PARTITIONS 2
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Partition column \"x\" not defined!"));
+    }
+
     fn assert_column_def(column: &ColumnDef, name: &str, data_type: &str) {
         assert_eq!(column.name.to_string(), name);
         assert_eq!(column.data_type.to_string(), data_type);
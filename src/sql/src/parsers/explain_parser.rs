@@ -0,0 +1,84 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::keywords::Keyword;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::explain::Explain;
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    /// Parses `EXPLAIN [ANALYZE] [VERBOSE] query`, the leading `EXPLAIN` has been consumed.
+    pub(crate) fn parse_explain(&mut self) -> Result<Statement> {
+        let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
+        let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+        match self.parse_statement()? {
+            Statement::Query(query) => Ok(Statement::Explain(Explain {
+                analyze,
+                verbose,
+                query,
+            })),
+            _ => error::InvalidSqlSnafu {
+                msg: "only queries can be explained",
+            }
+            .fail(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Result<Statement> {
+        ParserContext::create_with_dialect(sql, &GenericDialect {}).map(|mut stmts| {
+            assert_eq!(1, stmts.len());
+            stmts.remove(0)
+        })
+    }
+
+    #[test]
+    fn test_parse_explain() {
+        let cases = [
+            ("EXPLAIN SELECT * FROM t", false, false),
+            ("EXPLAIN ANALYZE SELECT * FROM t", true, false),
+            ("EXPLAIN VERBOSE SELECT * FROM t", false, true),
+            ("EXPLAIN ANALYZE VERBOSE SELECT * FROM t", true, true),
+        ];
+        for (sql, analyze, verbose) in cases {
+            match parse(sql).unwrap() {
+                Statement::Explain(explain) => {
+                    assert_eq!(analyze, explain.analyze, "{}", sql);
+                    assert_eq!(verbose, explain.verbose, "{}", sql);
+                    assert_eq!("SELECT * FROM t", explain.query.inner.to_string());
+                    assert!(explain.query.range_select.is_none());
+                }
+                stmt => unreachable!("{:?}", stmt),
+            }
+        }
+
+        match parse("EXPLAIN SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m'").unwrap() {
+            Statement::Explain(explain) => {
+                assert_eq!(60_000, explain.query.range_select.unwrap().align_millis)
+            }
+            stmt => unreachable!("{:?}", stmt),
+        }
+
+        let err = parse("EXPLAIN SHOW TABLES").unwrap_err();
+        assert!(err.to_string().contains("only queries can be explained"));
+    }
+}
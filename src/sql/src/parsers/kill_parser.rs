@@ -0,0 +1,80 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::tokenizer::Token;
+
+use crate::error::Result;
+use crate::parser::ParserContext;
+use crate::statements::kill::Kill;
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    /// Parses `KILL [CONNECTION | QUERY] id`, the leading `KILL` has been consumed. As in MySQL,
+    /// a bare `KILL id` kills the connection.
+    pub(crate) fn parse_kill(&mut self) -> Result<Statement> {
+        let connection = !self.consume_token("QUERY");
+        if connection {
+            let _ = self.consume_token("CONNECTION");
+        }
+        match self.parser.next_token() {
+            Token::Number(id, _) => match id.parse::<u32>() {
+                Ok(id) => Ok(Statement::Kill(Kill { id, connection })),
+                Err(_) => self.expected("a process id", Token::Number(id, false)),
+            },
+            unexpected => self.expected("a process id", unexpected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+    use crate::statements::show::ShowProcesslist;
+
+    fn parse(sql: &str) -> Result<Statement> {
+        ParserContext::create_with_dialect(sql, &GenericDialect {}).map(|mut stmts| {
+            assert_eq!(1, stmts.len());
+            stmts.remove(0)
+        })
+    }
+
+    #[test]
+    fn test_parse_kill() {
+        let kill = |id, connection| Statement::Kill(Kill { id, connection });
+        assert_eq!(kill(42, false), parse("KILL QUERY 42").unwrap());
+        assert_eq!(kill(7, false), parse("kill query 7;").unwrap());
+        assert_eq!(kill(42, true), parse("KILL 42").unwrap());
+        assert_eq!(kill(42, true), parse("KILL CONNECTION 42").unwrap());
+        assert_eq!(kill(3, true), parse("kill connection 3;").unwrap());
+        assert!(parse("KILL QUERY abc").is_err());
+        assert!(parse("KILL CONNECTION QUERY 1").is_err());
+        assert!(parse("KILL TABLE 1").is_err());
+        assert!(parse("KILL QUERY -1").is_err());
+    }
+
+    #[test]
+    fn test_parse_show_processlist() {
+        assert_eq!(
+            Statement::ShowProcesslist(ShowProcesslist { full: false }),
+            parse("SHOW PROCESSLIST").unwrap()
+        );
+        assert_eq!(
+            Statement::ShowProcesslist(ShowProcesslist { full: true }),
+            parse("show full processlist").unwrap()
+        );
+        assert!(parse("SHOW FULL TABLES").is_err());
+    }
+}
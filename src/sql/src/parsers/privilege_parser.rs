@@ -0,0 +1,332 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::Token;
+
+use crate::error::Result;
+use crate::parser::ParserContext;
+use crate::statements::privilege::{
+    CreateRole, CreateUser, Grant, GrantObject, GrantRole, Privilege, Revoke, RevokeRole,
+};
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    /// Parses `CREATE USER [IF NOT EXISTS] name`, the leading `CREATE` has been consumed.
+    pub(crate) fn parse_create_user(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_user_name()?;
+        Ok(Statement::CreateUser(CreateUser {
+            name,
+            if_not_exists,
+        }))
+    }
+
+    /// Parses `CREATE ROLE [IF NOT EXISTS] name`, the leading `CREATE` has been consumed.
+    pub(crate) fn parse_create_role(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_user_name()?;
+        Ok(Statement::CreateRole(CreateRole {
+            name,
+            if_not_exists,
+        }))
+    }
+
+    /// Parses `GRANT privileges ON object TO user` or `GRANT roles TO user`, the leading `GRANT`
+    /// has been consumed.
+    pub(crate) fn parse_grant(&mut self) -> Result<Statement> {
+        if !self.peek_privilege() {
+            let roles = self.parse_role_names()?;
+            if !self.parser.parse_keyword(Keyword::TO) {
+                return self.expected("TO", self.parser.peek_token());
+            }
+            let user = self.parse_user_name()?;
+            return Ok(Statement::GrantRole(GrantRole { roles, user }));
+        }
+
+        let (privileges, object) = self.parse_privileges_on_object()?;
+        if !self.parser.parse_keyword(Keyword::TO) {
+            return self.expected("TO", self.parser.peek_token());
+        }
+        let user = self.parse_user_name()?;
+        Ok(Statement::Grant(Grant {
+            privileges,
+            object,
+            user,
+        }))
+    }
+
+    /// Parses `REVOKE privileges ON object FROM user` or `REVOKE roles FROM user`, the leading
+    /// `REVOKE` has been consumed.
+    pub(crate) fn parse_revoke(&mut self) -> Result<Statement> {
+        if !self.peek_privilege() {
+            let roles = self.parse_role_names()?;
+            if !self.parser.parse_keyword(Keyword::FROM) {
+                return self.expected("FROM", self.parser.peek_token());
+            }
+            let user = self.parse_user_name()?;
+            return Ok(Statement::RevokeRole(RevokeRole { roles, user }));
+        }
+
+        let (privileges, object) = self.parse_privileges_on_object()?;
+        if !self.parser.parse_keyword(Keyword::FROM) {
+            return self.expected("FROM", self.parser.peek_token());
+        }
+        let user = self.parse_user_name()?;
+        Ok(Statement::Revoke(Revoke {
+            privileges,
+            object,
+            user,
+        }))
+    }
+
+    /// Whether the next token starts a list of privileges rather than a list of roles.
+    fn peek_privilege(&self) -> bool {
+        match self.parser.peek_token() {
+            Token::Word(w) => matches!(
+                w.keyword,
+                Keyword::SELECT | Keyword::INSERT | Keyword::ALTER | Keyword::ALL
+            ),
+            _ => false,
+        }
+    }
+
+    fn parse_role_names(&mut self) -> Result<Vec<String>> {
+        let mut roles = vec![self.parse_user_name()?];
+        while self.parser.consume_token(&Token::Comma) {
+            roles.push(self.parse_user_name()?);
+        }
+        Ok(roles)
+    }
+
+    fn parse_privileges_on_object(&mut self) -> Result<(Vec<Privilege>, GrantObject)> {
+        let mut privileges = Vec::new();
+        loop {
+            match self.parser.next_token() {
+                Token::Word(w) => match w.keyword {
+                    Keyword::SELECT => privileges.push(Privilege::Select),
+                    Keyword::INSERT => privileges.push(Privilege::Insert),
+                    Keyword::ALTER => privileges.push(Privilege::Alter),
+                    Keyword::ALL => {
+                        let _ = self.consume_token("PRIVILEGES");
+                        privileges.extend(Privilege::all());
+                    }
+                    _ => return self.expected("SELECT, INSERT, ALTER or ALL", Token::Word(w)),
+                },
+                unexpected => return self.expected("SELECT, INSERT, ALTER or ALL", unexpected),
+            }
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        let mut seen = Vec::with_capacity(privileges.len());
+        privileges.retain(|p| {
+            let duplicated = seen.contains(p);
+            seen.push(*p);
+            !duplicated
+        });
+
+        if !self.parser.parse_keyword(Keyword::ON) {
+            return self.expected("ON", self.parser.peek_token());
+        }
+        let object = self.parse_grant_object()?;
+        Ok((privileges, object))
+    }
+
+    /// Parses the object of `GRANT` and `REVOKE`, one of `*.*`, `*`, `db.*`, `db.table` and `table`.
+    fn parse_grant_object(&mut self) -> Result<GrantObject> {
+        match self.parser.next_token() {
+            Token::Mul => {
+                if !self.parser.consume_token(&Token::Period) {
+                    return Ok(GrantObject::Database(None));
+                }
+                match self.parser.next_token() {
+                    Token::Mul => Ok(GrantObject::AllDatabases),
+                    unexpected => self.expected("*", unexpected),
+                }
+            }
+            Token::Word(w) => {
+                let first = Ident {
+                    value: w.value,
+                    quote_style: w.quote_style,
+                };
+                if !self.parser.consume_token(&Token::Period) {
+                    return Ok(GrantObject::Table(ObjectName(vec![first])));
+                }
+                match self.parser.next_token() {
+                    Token::Mul => Ok(GrantObject::Database(Some(first.value))),
+                    Token::Word(w) => {
+                        let second = Ident {
+                            value: w.value,
+                            quote_style: w.quote_style,
+                        };
+                        Ok(GrantObject::Table(ObjectName(vec![first, second])))
+                    }
+                    unexpected => self.expected("a table name or *", unexpected),
+                }
+            }
+            unexpected => self.expected("a database or table name", unexpected),
+        }
+    }
+
+    fn parse_user_name(&mut self) -> Result<String> {
+        match self.parser.next_token() {
+            Token::Word(w) => Ok(w.value),
+            Token::SingleQuotedString(s) => Ok(s),
+            unexpected => self.expected("a user name", unexpected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        stmts.remove(0)
+    }
+
+    #[test]
+    fn test_parse_create_user() {
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "alice".to_string(),
+                if_not_exists: false,
+            }),
+            parse("CREATE USER alice")
+        );
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "bob".to_string(),
+                if_not_exists: true,
+            }),
+            parse("CREATE USER IF NOT EXISTS 'bob'")
+        );
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Select, Privilege::Insert],
+                object: GrantObject::Table(ObjectName(vec![Ident::new("db"), Ident::new("cpu")])),
+                user: "alice".to_string(),
+            }),
+            parse("GRANT SELECT, INSERT ON db.cpu TO alice")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: Privilege::all(),
+                object: GrantObject::AllDatabases,
+                user: "admin".to_string(),
+            }),
+            parse("GRANT ALL PRIVILEGES ON *.* TO admin")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Alter],
+                object: GrantObject::Database(Some("db".to_string())),
+                user: "alice".to_string(),
+            }),
+            parse("grant alter on db.* to alice")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Select],
+                object: GrantObject::Database(None),
+                user: "alice".to_string(),
+            }),
+            parse("GRANT SELECT ON * TO alice")
+        );
+
+        let result = ParserContext::create_with_dialect(
+            "GRANT DELETE ON db.cpu TO alice",
+            &GenericDialect {},
+        );
+        assert!(result.is_err());
+        let result =
+            ParserContext::create_with_dialect("GRANT SELECT ON db.cpu alice", &GenericDialect {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_revoke() {
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                privileges: vec![Privilege::Insert],
+                object: GrantObject::Table(ObjectName(vec![Ident::new("cpu")])),
+                user: "alice".to_string(),
+            }),
+            parse("REVOKE INSERT ON cpu FROM alice")
+        );
+    }
+
+    #[test]
+    fn test_parse_roles() {
+        assert_eq!(
+            Statement::CreateRole(CreateRole {
+                name: "reader".to_string(),
+                if_not_exists: false,
+            }),
+            parse("CREATE ROLE reader")
+        );
+        assert_eq!(
+            Statement::CreateRole(CreateRole {
+                name: "writer".to_string(),
+                if_not_exists: true,
+            }),
+            parse("create role if not exists 'writer'")
+        );
+        assert_eq!(
+            Statement::GrantRole(GrantRole {
+                roles: vec!["reader".to_string(), "writer".to_string()],
+                user: "alice".to_string(),
+            }),
+            parse("GRANT reader, 'writer' TO alice")
+        );
+        assert_eq!(
+            Statement::RevokeRole(RevokeRole {
+                roles: vec!["reader".to_string()],
+                user: "alice".to_string(),
+            }),
+            parse("REVOKE reader FROM alice")
+        );
+        // Privileges are granted to roles like to users.
+        assert_eq!(
+            Statement::Grant(Grant {
+                privileges: vec![Privilege::Select],
+                object: GrantObject::Database(Some("db".to_string())),
+                user: "reader".to_string(),
+            }),
+            parse("GRANT SELECT ON db.* TO reader")
+        );
+
+        let result = ParserContext::create_with_dialect("GRANT reader alice", &GenericDialect {});
+        assert!(result.is_err());
+        let result =
+            ParserContext::create_with_dialect("REVOKE reader TO alice", &GenericDialect {});
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,541 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Range queries extend `SELECT` with `RANGE` after aggregates and an `ALIGN` clause after `WHERE`:
+//!
+//! ```sql
+//! SELECT host, avg(cpu) RANGE '5m' [FILL ...] FROM t [WHERE ...]
+//! ALIGN '1m' [BY (host, ...)] [FILL NULL | PREV | LINEAR | <number>] [ORDER BY ...] [LIMIT ...]
+//! ```
+//!
+//! sqlparser does not know these clauses, so they are taken out of the tokens before parsing, and
+//! attached to the parsed query afterwards.
+
+use std::collections::VecDeque;
+
+use snafu::ResultExt;
+use sqlparser::dialect::Dialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result};
+use crate::statements::query::{Fill, RangeExpr, RangeSelect};
+
+/// Takes the range clauses out of the tokens. Returns the remaining tokens, and for each
+/// statement in order, its range clauses if it is a range query.
+pub(crate) fn extract_range_selects(
+    sql: &str,
+    dialect: &dyn Dialect,
+    tokens: Vec<Token>,
+) -> Result<(Vec<Token>, VecDeque<Option<RangeSelect>>)> {
+    let mut remaining = Vec::with_capacity(tokens.len());
+    let mut range_selects = VecDeque::new();
+
+    let mut statement = Vec::new();
+    let mut depth = 0;
+    for token in tokens.into_iter().chain(std::iter::once(Token::EOF)) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+        let end_of_statement =
+            matches!(token, Token::EOF) || (depth == 0 && token == Token::SemiColon);
+        if !end_of_statement {
+            statement.push(token);
+            continue;
+        }
+
+        // The parser skips empty statements.
+        if statement.iter().any(|token| !is_whitespace(token)) {
+            let (tokens, range_select) =
+                extract_range_select(sql, dialect, std::mem::take(&mut statement))?;
+            remaining.extend(tokens);
+            range_selects.push_back(range_select);
+        } else {
+            remaining.append(&mut statement);
+        }
+        if token != Token::EOF {
+            remaining.push(token);
+        }
+    }
+    Ok((remaining, range_selects))
+}
+
+fn extract_range_select(
+    sql: &str,
+    dialect: &dyn Dialect,
+    tokens: Vec<Token>,
+) -> Result<(Vec<Token>, Option<RangeSelect>)> {
+    let (select, from, align) = match find_align(&tokens) {
+        Some(clause) => clause,
+        None => return Ok((tokens, None)),
+    };
+    let mut removed = Vec::new();
+
+    // ALIGN '1m' [BY (...)] [FILL ...]
+    let mut cursor = TokenCursor::new(&tokens, align + 1);
+    let (align_str, align_millis) = cursor.parse_duration("ALIGN")?;
+    let by = if cursor.consume_word("BY") {
+        let by_tokens = cursor.parse_parenthesized("BY")?;
+        if by_tokens.iter().all(is_whitespace) {
+            Some(vec![])
+        } else {
+            Some(
+                Parser::new(by_tokens, dialect)
+                    .parse_comma_separated(Parser::parse_expr)
+                    .context(error::SyntaxSnafu { sql })?,
+            )
+        }
+    } else {
+        None
+    };
+    let fill = if cursor.consume_word("FILL") {
+        Some(cursor.parse_fill()?)
+    } else {
+        None
+    };
+    removed.push((align, cursor.pos));
+
+    // <aggregate> RANGE '5m' [FILL ...], in the projection.
+    let mut ranges = Vec::new();
+    let mut item = 0;
+    let mut item_start = select + 1;
+    while item_start < from {
+        let item_end = find_token(&tokens, item_start, from, &Token::Comma).unwrap_or(from);
+        if let Some(range) = find_word(&tokens, item_start, item_end, "RANGE") {
+            let mut cursor = TokenCursor::new(&tokens, range + 1);
+            let (range_str, range_millis) = cursor.parse_duration("RANGE")?;
+            let fill = if cursor.consume_word("FILL") {
+                Some(cursor.parse_fill()?)
+            } else {
+                None
+            };
+            removed.push((range, cursor.pos));
+            ranges.push(RangeExpr {
+                item,
+                range: range_str,
+                range_millis,
+                fill,
+            });
+        }
+        item += 1;
+        item_start = item_end + 1;
+    }
+    if ranges.is_empty() {
+        return error::InvalidSqlSnafu {
+            msg: "a range query needs at least one aggregate with RANGE",
+        }
+        .fail();
+    }
+
+    let tokens = tokens
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !removed.iter().any(|(start, end)| start <= i && i < end))
+        .map(|(_, token)| token)
+        .collect();
+    let range_select = RangeSelect {
+        align: align_str,
+        align_millis,
+        by,
+        fill,
+        ranges,
+    };
+    Ok((tokens, Some(range_select)))
+}
+
+/// Finds the ALIGN clause, which trails a SELECT after its FROM and WHERE clauses. Returns the
+/// positions of SELECT, FROM and ALIGN. ALIGN is taken as an identifier elsewhere, e.g. in
+/// `SELECT align FROM t` or `SELECT * FROM t align`, unless it's followed by a duration or the
+/// projection has ranges.
+fn find_align(tokens: &[Token]) -> Option<(usize, usize, usize)> {
+    let select = find_keyword(tokens, 0, tokens.len(), Keyword::SELECT)?;
+    let from = find_keyword(tokens, select + 1, tokens.len(), Keyword::FROM)?;
+    let mut has_ranges = false;
+    let mut start = select + 1;
+    while let Some(range) = find_word(tokens, start, from, "RANGE") {
+        if followed_by_string(tokens, range) {
+            has_ranges = true;
+            break;
+        }
+        start = range + 1;
+    }
+
+    let mut start = from + 1;
+    while let Some(align) = find_word(tokens, start, tokens.len(), "ALIGN") {
+        let aliased = tokens[..align]
+            .iter()
+            .rev()
+            .find(|token| !is_whitespace(token))
+            .map(|token| matches!(token, Token::Word(w) if w.keyword == Keyword::AS))
+            .unwrap_or(false);
+        if !aliased && (has_ranges || followed_by_string(tokens, align)) {
+            return Some((select, from, align));
+        }
+        start = align + 1;
+    }
+    None
+}
+
+/// Returns whether the token at `pos` is followed by a quoted string.
+fn followed_by_string(tokens: &[Token], pos: usize) -> bool {
+    matches!(
+        TokenCursor::new(tokens, pos + 1).peek(),
+        Some((_, Token::SingleQuotedString(_)))
+    )
+}
+
+fn is_whitespace(token: &Token) -> bool {
+    matches!(token, Token::Whitespace(_))
+}
+
+fn is_word(token: &Token, word: &str) -> bool {
+    matches!(token, Token::Word(w) if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word))
+}
+
+/// Finds the first token in `tokens[start..end]` that matches `predicate` and is not in parentheses.
+fn find_outside_parens(
+    tokens: &[Token],
+    start: usize,
+    end: usize,
+    predicate: impl Fn(&Token) -> bool,
+) -> Option<usize> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().take(end).skip(start) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            token if depth == 0 && predicate(token) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn find_word(tokens: &[Token], start: usize, end: usize, word: &str) -> Option<usize> {
+    find_outside_parens(tokens, start, end, |token| is_word(token, word))
+}
+
+fn find_keyword(tokens: &[Token], start: usize, end: usize, keyword: Keyword) -> Option<usize> {
+    find_outside_parens(
+        tokens,
+        start,
+        end,
+        |token| matches!(token, Token::Word(w) if w.quote_style.is_none() && w.keyword == keyword),
+    )
+}
+
+fn find_token(tokens: &[Token], start: usize, end: usize, expected: &Token) -> Option<usize> {
+    find_outside_parens(tokens, start, end, |token| token == expected)
+}
+
+/// Reads the tokens of a range clause, skipping whitespaces.
+struct TokenCursor<'a> {
+    tokens: &'a [Token],
+    /// Index of the next token to read.
+    pos: usize,
+}
+
+impl<'a> TokenCursor<'a> {
+    fn new(tokens: &'a [Token], pos: usize) -> Self {
+        Self { tokens, pos }
+    }
+
+    fn peek(&self) -> Option<(usize, &'a Token)> {
+        self.tokens
+            .iter()
+            .enumerate()
+            .skip(self.pos)
+            .find(|(_, token)| !is_whitespace(token))
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let (i, token) = self.peek()?;
+        self.pos = i + 1;
+        Some(token)
+    }
+
+    fn consume_word(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some((i, token)) if is_word(token, word) => {
+                self.pos = i + 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_duration(&mut self, clause: &str) -> Result<(String, i64)> {
+        match self.next() {
+            Some(Token::SingleQuotedString(s)) => match parse_duration_millis(s) {
+                Some(millis) => Ok((s.clone(), millis)),
+                None => error::InvalidSqlSnafu {
+                    msg: format!("invalid duration '{}' in {}", s, clause),
+                }
+                .fail(),
+            },
+            _ => error::InvalidSqlSnafu {
+                msg: format!("expect a quoted duration like '5m' after {}", clause),
+            }
+            .fail(),
+        }
+    }
+
+    /// Returns the tokens between a pair of parentheses.
+    fn parse_parenthesized(&mut self, clause: &str) -> Result<Vec<Token>> {
+        if !matches!(self.next(), Some(Token::LParen)) {
+            return error::InvalidSqlSnafu {
+                msg: format!("expect '(' after {}", clause),
+            }
+            .fail();
+        }
+        let start = self.pos;
+        let mut depth = 1;
+        while let Some(token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match token {
+                Token::LParen => depth += 1,
+                Token::RParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(self.tokens[start..self.pos - 1].to_vec());
+                    }
+                }
+                _ => {}
+            }
+        }
+        error::InvalidSqlSnafu {
+            msg: format!("expect ')' to close {}", clause),
+        }
+        .fail()
+    }
+
+    fn parse_fill(&mut self) -> Result<Fill> {
+        let fill = match self.next() {
+            Some(token) if is_word(token, "NULL") => Fill::Null,
+            Some(token) if is_word(token, "PREV") => Fill::Prev,
+            Some(token) if is_word(token, "LINEAR") => Fill::Linear,
+            Some(Token::Number(n, _)) => Fill::Const(n.clone()),
+            Some(Token::Minus) => match self.next() {
+                Some(Token::Number(n, _)) => Fill::Const(format!("-{}", n)),
+                _ => return invalid_fill(),
+            },
+            _ => return invalid_fill(),
+        };
+        if let Fill::Const(n) = &fill {
+            if n.parse::<f64>().is_err() {
+                return invalid_fill();
+            }
+        }
+        Ok(fill)
+    }
+}
+
+fn invalid_fill<T>() -> Result<T> {
+    error::InvalidSqlSnafu {
+        msg: "expect NULL, PREV, LINEAR or a number after FILL",
+    }
+    .fail()
+}
+
+/// Parses durations like `30s`, `5m` or `1h30m` into milliseconds, the units are `ms`, `s`, `m`,
+/// `h`, `d` and `w`.
+pub fn parse_duration_millis(s: &str) -> Option<i64> {
+    let mut millis: i64 = 0;
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value = rest[..digits].parse::<i64>().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            "d" => 24 * 60 * 60 * 1000,
+            "w" => 7 * 24 * 60 * 60 * 1000,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        millis = millis.checked_add(value.checked_mul(factor)?)?;
+    }
+    if millis > 0 {
+        Some(millis)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::ast::{Expr, Ident};
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+    use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
+
+    fn parse_range_select(sql: &str) -> (String, Option<RangeSelect>) {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match stmts.remove(0) {
+            Statement::Query(query) => (query.inner.to_string(), query.range_select),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_range_select() {
+        let (query, range_select) = parse_range_select(
+            "SELECT ts, host, avg(cpu) RANGE '5m', max(cpu) RANGE '1h30m' FILL 0 AS m FROM t \
+             WHERE host <> 'a' ALIGN '1m' BY (host) FILL PREV ORDER BY host LIMIT 10",
+        );
+        assert_eq!(
+            "SELECT ts, host, avg(cpu), max(cpu) AS m FROM t WHERE host <> 'a' ORDER BY host LIMIT 10",
+            query
+        );
+        assert_eq!(
+            Some(RangeSelect {
+                align: "1m".to_string(),
+                align_millis: 60_000,
+                by: Some(vec![Expr::Identifier(Ident::new("host"))]),
+                fill: Some(Fill::Prev),
+                ranges: vec![
+                    RangeExpr {
+                        item: 2,
+                        range: "5m".to_string(),
+                        range_millis: 300_000,
+                        fill: None,
+                    },
+                    RangeExpr {
+                        item: 3,
+                        range: "1h30m".to_string(),
+                        range_millis: 5_400_000,
+                        fill: Some(Fill::Const("0".to_string())),
+                    },
+                ],
+            }),
+            range_select
+        );
+
+        let (query, range_select) =
+            parse_range_select("select min(v) range '10s' fill -1.5 from t align '5s' by ()");
+        assert_eq!("SELECT min(v) FROM t", query);
+        let range_select = range_select.unwrap();
+        assert_eq!(Some(vec![]), range_select.by);
+        assert_eq!(None, range_select.fill);
+        assert_eq!(
+            Some(Fill::Const("-1.5".to_string())),
+            range_select.ranges[0].fill
+        );
+
+        // Not a range query.
+        let (query, range_select) = parse_range_select("SELECT avg(cpu) FROM t");
+        assert_eq!("SELECT avg(cpu) FROM t", query);
+        assert!(range_select.is_none());
+    }
+
+    #[test]
+    fn test_parse_align_identifier() {
+        for sql in [
+            "SELECT align FROM t",
+            "SELECT x AS align FROM t",
+            "SELECT * FROM t AS align WHERE align.x = 1",
+            "SELECT * FROM t WHERE align = 1",
+            "SELECT (SELECT align FROM t) FROM t",
+        ] {
+            let (query, range_select) = parse_range_select(sql);
+            assert_eq!(sql, query);
+            assert!(range_select.is_none(), "{}", sql);
+        }
+        let (query, range_select) = parse_range_select("SELECT * FROM t align");
+        assert_eq!("SELECT * FROM t AS align", query);
+        assert!(range_select.is_none());
+
+        let (query, range_select) = parse_range_select(
+            "SELECT align, avg(cpu) RANGE '5m' FROM t align WHERE align > 0 ALIGN '1m'",
+        );
+        assert_eq!(
+            "SELECT align, avg(cpu) FROM t AS align WHERE align > 0",
+            query
+        );
+        assert_eq!(60_000, range_select.unwrap().align_millis);
+    }
+
+    #[test]
+    fn test_parse_range_select_in_multiple_statements() {
+        let stmts = ParserContext::create_with_dialect(
+            "SELECT 1;; SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m'; SELECT 2",
+            &GenericDialect {},
+        )
+        .unwrap();
+        let range_selects = stmts
+            .into_iter()
+            .map(|stmt| match stmt {
+                Statement::Query(query) => query.range_select.is_some(),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![false, true, false], range_selects);
+    }
+
+    #[test]
+    fn test_parse_invalid_range_select() {
+        let parse = |sql| {
+            ParserContext::create_with_dialect(sql, &GenericDialect {})
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(parse("SELECT avg(cpu) FROM t ALIGN '1m'")
+            .contains("a range query needs at least one aggregate with RANGE"));
+        assert!(parse("SELECT avg(cpu) RANGE '5x' FROM t ALIGN '1m'")
+            .contains("invalid duration '5x' in RANGE"));
+        assert!(parse("SELECT avg(cpu) RANGE '5m' FROM t ALIGN 1")
+            .contains("expect a quoted duration like '5m' after ALIGN"));
+        assert!(
+            parse("SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' FILL NEXT")
+                .contains("expect NULL, PREV, LINEAR or a number after FILL")
+        );
+        assert!(
+            parse("SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' BY (host")
+                .contains("expect ')' to close BY")
+        );
+        assert!(
+            parse("INSERT INTO t SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m'")
+                .contains("RANGE and ALIGN are only allowed in SELECT")
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_millis() {
+        assert_eq!(Some(100), parse_duration_millis("100ms"));
+        assert_eq!(Some(30_000), parse_duration_millis("30s"));
+        assert_eq!(Some(90_000), parse_duration_millis("1m30s"));
+        assert_eq!(Some(86_400_000), parse_duration_millis("1d"));
+        assert_eq!(None, parse_duration_millis(""));
+        assert_eq!(None, parse_duration_millis("0s"));
+        assert_eq!(None, parse_duration_millis("5"));
+        assert_eq!(None, parse_duration_millis("m"));
+    }
+}
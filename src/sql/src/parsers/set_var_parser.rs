@@ -0,0 +1,134 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, ResultExt};
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, InvalidDatabaseNameSnafu, Result};
+use crate::parser::ParserContext;
+use crate::statements::set_variables::{SetVariables, TIME_ZONE_VARIABLE};
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    /// Parses `USE <database>` statement.
+    pub(crate) fn parse_use(&mut self) -> Result<Statement> {
+        let db_name = self
+            .parser
+            .parse_object_name()
+            .with_context(|_| error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a database name",
+                actual: self.peek_token_as_string(),
+            })?;
+        ensure!(
+            db_name.0.len() == 1,
+            InvalidDatabaseNameSnafu {
+                name: db_name.to_string(),
+            }
+        );
+        Ok(Statement::Use(db_name.0[0].value.clone()))
+    }
+
+    /// Parses `SET` statement, the leading `SET` keyword has been consumed.
+    pub(crate) fn parse_set_variables(&mut self) -> Result<Statement> {
+        let set_variables = self
+            .parse_set_variables_inner()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::SetVariables(set_variables))
+    }
+
+    fn parse_set_variables_inner(&mut self) -> std::result::Result<SetVariables, ParserError> {
+        let parser = &mut self.parser;
+        let _ = parser.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+
+        if parser.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            let value = parser.parse_expr()?;
+            return Ok(SetVariables {
+                variable: ObjectName(vec![Ident::new(TIME_ZONE_VARIABLE)]),
+                value,
+            });
+        }
+
+        let variable = parser.parse_object_name()?;
+        if !parser.consume_token(&Token::Eq) && !parser.parse_keyword(Keyword::TO) {
+            return parser.expected("= or TO", parser.peek_token());
+        }
+        let value = parser.parse_expr()?;
+        Ok(SetVariables { variable, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use sqlparser::ast::{Expr, Value};
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_use() {
+        let mut stmts =
+            ParserContext::create_with_dialect("USE my_db", &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_eq!(Statement::Use("my_db".to_string()), stmts.remove(0));
+
+        let result = ParserContext::create_with_dialect("USE a.b", &GenericDialect {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_set_variables() {
+        let sql = "SET time_zone = '+08:00'";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::SetVariables(SetVariables { variable, value }) => {
+                assert_eq!("time_zone", variable.to_string());
+                assert_eq!(
+                    Expr::Value(Value::SingleQuotedString("+08:00".to_string())),
+                    value
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "SET SESSION autocommit TO 1";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::SetVariables(set_variables) => {
+                assert_eq!("autocommit", set_variables.name());
+                assert_matches!(set_variables.value, Expr::Value(Value::Number(_, _)));
+                assert_eq!("1", set_variables.value());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "SET TIME ZONE 'UTC'";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::SetVariables(set_variables) => {
+                assert_eq!(TIME_ZONE_VARIABLE, set_variables.name());
+                assert_eq!("UTC", set_variables.value());
+            }
+            _ => unreachable!(),
+        }
+
+        let result = ParserContext::create_with_dialect("SET autocommit", &GenericDialect {});
+        assert!(result.is_err());
+    }
+}
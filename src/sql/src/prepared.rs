@@ -0,0 +1,613 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-side prepared statements with `?` (MySQL) or `$n` (PostgreSQL) placeholders.
+
+use std::ops::Range;
+
+use snafu::{ensure, OptionExt};
+use sqlparser::ast::{JoinConstraint, JoinOperator, Statement as SpStatement, Values};
+
+use crate::ast::{
+    Expr, FunctionArg, FunctionArgExpr, Ident, Query, SelectItem, SetExpr, TableFactor,
+    TableWithJoins, Value,
+};
+use crate::dialect::GenericDialect;
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::scanner::skip_quoted_or_comment;
+use crate::statements::query::Query as StatementQuery;
+use crate::statements::statement::Statement;
+
+const PLACEHOLDER_PREFIX: &str = "__greptime_param_";
+
+/// A parsed SQL statement that contains parameter placeholders.
+///
+/// The placeholders are replaced by identifiers named `__greptime_param_{index}` (see
+/// [placeholder_index]) before parsing, so the [Statement] can be inspected, e.g. to infer
+/// the types of parameters, without requiring the parser to support placeholders itself.
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    sql: String,
+    // Byte range of each placeholder in `sql`, along with the index of the parameter it refers to.
+    placeholders: Vec<(Range<usize>, usize)>,
+    param_num: usize,
+    statement: Statement,
+}
+
+impl PreparedStatement {
+    pub fn new(sql: &str) -> Result<Self> {
+        let placeholders = scan_placeholders(sql)?;
+        let param_num = placeholders
+            .iter()
+            .map(|(_, index)| index + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut normalized = String::with_capacity(sql.len());
+        let mut last = 0;
+        for (range, index) in &placeholders {
+            normalized.push_str(&sql[last..range.start]);
+            normalized.push_str(&format!("{}{}", PLACEHOLDER_PREFIX, index));
+            last = range.end;
+        }
+        normalized.push_str(&sql[last..]);
+
+        let mut statements = ParserContext::create_with_dialect(&normalized, &GenericDialect {})?;
+        ensure!(
+            statements.len() == 1,
+            error::InvalidSqlSnafu {
+                msg: format!(
+                    "only one statement can be prepared at a time, found: {}",
+                    statements.len()
+                ),
+            }
+        );
+
+        Ok(Self {
+            sql: sql.to_string(),
+            placeholders,
+            param_num,
+            statement: statements.remove(0),
+        })
+    }
+
+    /// The original SQL text.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Number of parameters required to execute this statement.
+    pub fn param_num(&self) -> usize {
+        self.param_num
+    }
+
+    /// The parsed statement, with placeholders represented as identifiers.
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    /// Substitutes the placeholders in the parsed statement with the values, returns the
+    /// statement to execute. The values are never re-parsed as SQL text.
+    pub fn bind(&self, params: &[Value]) -> Result<Statement> {
+        ensure!(
+            params.len() == self.param_num,
+            error::ParamNumMismatchSnafu {
+                expect: self.param_num,
+                actual: params.len(),
+            }
+        );
+
+        let mut binder = ParamBinder { params, bound: 0 };
+        let mut statement = self.statement.clone();
+        match &mut statement {
+            Statement::Query(query) => binder.bind_statement_query(query),
+            Statement::Explain(explain) => binder.bind_statement_query(&mut explain.query),
+            Statement::Insert(insert) => {
+                if let SpStatement::Insert { source, .. } = &mut insert.inner {
+                    binder.bind_query(source);
+                }
+            }
+            _ => {}
+        }
+        ensure!(
+            binder.bound == self.placeholders.len(),
+            error::InvalidSqlSnafu {
+                msg: "placeholders are only supported in expressions of queries and inserts",
+            }
+        );
+        Ok(statement)
+    }
+
+    /// Substitutes the placeholders in the original SQL text with the values, returns the SQL
+    /// text of the statement returned by [PreparedStatement::bind], for executing it where only
+    /// SQL text is accepted, e.g. on datanode in standalone mode. Strings are quoted and escaped,
+    /// so the values are still parsed as literals only.
+    pub fn bind_sql(&self, params: &[Value]) -> Result<String> {
+        ensure!(
+            params.len() == self.param_num,
+            error::ParamNumMismatchSnafu {
+                expect: self.param_num,
+                actual: params.len(),
+            }
+        );
+
+        let mut sql = String::with_capacity(self.sql.len());
+        let mut last = 0;
+        for (range, index) in &self.placeholders {
+            sql.push_str(&self.sql[last..range.start]);
+            // Avoids forming a comment with a negative number, like `a--1`.
+            if sql.ends_with('-') {
+                sql.push(' ');
+            }
+            sql.push_str(&literal(&params[*index]));
+            last = range.end;
+        }
+        sql.push_str(&self.sql[last..]);
+        Ok(sql)
+    }
+}
+
+/// Renders `value` as a SQL literal that can be put anywhere an expression is expected.
+fn literal(value: &Value) -> String {
+    match value {
+        // NaN and infinities are not numeric literals.
+        Value::Number(n, _) if n.parse::<f64>().map_or(false, |v| !v.is_finite()) => {
+            format!("CAST('{}' AS DOUBLE)", n)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Replaces placeholder identifiers in expressions with the parameter values.
+struct ParamBinder<'a> {
+    params: &'a [Value],
+    // Number of placeholders replaced.
+    bound: usize,
+}
+
+impl<'a> ParamBinder<'a> {
+    fn bind_statement_query(&mut self, query: &mut StatementQuery) {
+        self.bind_query(&mut query.inner);
+        if let Some(range_select) = &mut query.range_select {
+            range_select
+                .by
+                .iter_mut()
+                .flatten()
+                .for_each(|expr| self.bind_expr(expr));
+        }
+    }
+
+    fn bind_query(&mut self, query: &mut Query) {
+        if let Some(with) = &mut query.with {
+            for cte in &mut with.cte_tables {
+                self.bind_query(&mut cte.query);
+            }
+        }
+        self.bind_set_expr(&mut query.body);
+        for order_by in &mut query.order_by {
+            self.bind_expr(&mut order_by.expr);
+        }
+        if let Some(limit) = &mut query.limit {
+            self.bind_expr(limit);
+        }
+        if let Some(offset) = &mut query.offset {
+            self.bind_expr(&mut offset.value);
+        }
+    }
+
+    fn bind_set_expr(&mut self, set_expr: &mut SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                for item in &mut select.projection {
+                    match item {
+                        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                            self.bind_expr(expr)
+                        }
+                        _ => {}
+                    }
+                }
+                for table_with_joins in &mut select.from {
+                    self.bind_table_with_joins(table_with_joins);
+                }
+                if let Some(selection) = &mut select.selection {
+                    self.bind_expr(selection);
+                }
+                select
+                    .group_by
+                    .iter_mut()
+                    .for_each(|expr| self.bind_expr(expr));
+                if let Some(having) = &mut select.having {
+                    self.bind_expr(having);
+                }
+            }
+            SetExpr::Query(query) => self.bind_query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.bind_set_expr(left);
+                self.bind_set_expr(right);
+            }
+            SetExpr::Values(Values(rows)) => rows
+                .iter_mut()
+                .flatten()
+                .for_each(|expr| self.bind_expr(expr)),
+            _ => {}
+        }
+    }
+
+    fn bind_table_with_joins(&mut self, table_with_joins: &mut TableWithJoins) {
+        self.bind_table_factor(&mut table_with_joins.relation);
+        for join in &mut table_with_joins.joins {
+            self.bind_table_factor(&mut join.relation);
+            match &mut join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) => self.bind_expr(expr),
+                _ => {}
+            }
+        }
+    }
+
+    fn bind_table_factor(&mut self, table_factor: &mut TableFactor) {
+        match table_factor {
+            TableFactor::Derived { subquery, .. } => self.bind_query(subquery),
+            TableFactor::NestedJoin(table_with_joins) => {
+                self.bind_table_with_joins(table_with_joins)
+            }
+            _ => {}
+        }
+    }
+
+    fn bind_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Identifier(ident) => {
+                if let Some(index) = placeholder_index(ident) {
+                    *expr = Expr::Value(self.params[index].clone());
+                    self.bound += 1;
+                }
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                self.bind_expr(left);
+                self.bind_expr(right);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. } => self.bind_expr(expr),
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.bind_expr(expr);
+                self.bind_expr(low);
+                self.bind_expr(high);
+            }
+            Expr::InList { expr, list, .. } => {
+                self.bind_expr(expr);
+                list.iter_mut().for_each(|item| self.bind_expr(item));
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.bind_expr(expr);
+                self.bind_query(subquery);
+            }
+            Expr::Subquery(subquery) | Expr::Exists { subquery, .. } => self.bind_query(subquery),
+            Expr::Function(function) => {
+                for arg in &mut function.args {
+                    match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(expr),
+                            ..
+                        } => self.bind_expr(expr),
+                        _ => {}
+                    }
+                }
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                operand
+                    .iter_mut()
+                    .chain(else_result.iter_mut())
+                    .for_each(|expr| self.bind_expr(expr));
+                conditions
+                    .iter_mut()
+                    .chain(results.iter_mut())
+                    .for_each(|expr| self.bind_expr(expr));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the parameter index if the identifier is a placeholder.
+pub fn placeholder_index(ident: &Ident) -> Option<usize> {
+    if ident.quote_style.is_some() {
+        return None;
+    }
+    ident
+        .value
+        .strip_prefix(PLACEHOLDER_PREFIX)
+        .and_then(|index| index.parse().ok())
+}
+
+/// Returns the parameter index if the expression is a placeholder.
+pub fn expr_placeholder_index(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Identifier(ident) => placeholder_index(ident),
+        Expr::Nested(expr) => expr_placeholder_index(expr),
+        _ => None,
+    }
+}
+
+/// Finds the placeholders outside of quoted strings, quoted identifiers and comments.
+/// `?` placeholders are numbered sequentially, while `$n` refers to the n-th (1-based)
+/// parameter. Mixing both styles in one statement is not allowed.
+fn scan_placeholders(sql: &str) -> Result<Vec<(Range<usize>, usize)>> {
+    let bytes = sql.as_bytes();
+    let mut placeholders = Vec::new();
+    let mut question_marks = 0;
+    let mut dollars = 0;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_quoted_or_comment(bytes, i) {
+            i = end;
+            continue;
+        }
+        match bytes[i] {
+            b'?' => {
+                placeholders.push((i..i + 1, question_marks));
+                question_marks += 1;
+                i += 1;
+            }
+            b'$' if bytes.get(i + 1).map_or(false, u8::is_ascii_digit) => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let n: usize = sql[start + 1..i]
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .with_context(|| error::InvalidSqlSnafu {
+                        msg: format!("invalid placeholder: {}", &sql[start..i]),
+                    })?;
+                placeholders.push((start..i, n - 1));
+                dollars += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    ensure!(
+        question_marks == 0 || dollars == 0,
+        error::InvalidSqlSnafu {
+            msg: "cannot mix '?' and '$n' placeholders in one statement",
+        }
+    );
+    Ok(placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use sqlparser::ast::{BinaryOperator, SetExpr, Statement as SpStatement};
+
+    use super::*;
+    use crate::error::Error;
+
+    fn parse(sql: &str) -> Statement {
+        ParserContext::create_with_dialect(sql, &GenericDialect {})
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn test_question_mark_placeholders() {
+        let stmt =
+            PreparedStatement::new("SELECT * FROM t WHERE a = ? AND b = '?' AND c > ? LIMIT ?")
+                .unwrap();
+        assert_eq!(3, stmt.param_num());
+
+        let bound = stmt
+            .bind(&[
+                Value::SingleQuotedString("it's".to_string()),
+                Value::Number("1".to_string(), false),
+                Value::Number("10".to_string(), false),
+            ])
+            .unwrap();
+        assert_eq!(
+            parse("SELECT * FROM t WHERE a = 'it''s' AND b = '?' AND c > 1 LIMIT 10"),
+            bound
+        );
+    }
+
+    #[test]
+    fn test_bind_sql() {
+        let stmt = PreparedStatement::new(
+            "SELECT a-?, max(b) RANGE '5m' FROM t WHERE b = ? AND c > ? AND d <> '?' AND e = ? \
+             ALIGN '1m' BY (?)",
+        )
+        .unwrap();
+        let params = [
+            Value::Number("-1".to_string(), false),
+            Value::SingleQuotedString("x' OR '1' = '1".to_string()),
+            Value::Number("NaN".to_string(), false),
+            Value::Null,
+            Value::Number("2".to_string(), false),
+        ];
+        let sql = stmt.bind_sql(&params).unwrap();
+        assert_eq!(
+            "SELECT a- -1, max(b) RANGE '5m' FROM t WHERE b = 'x'' OR ''1'' = ''1' \
+             AND c > CAST('NaN' AS DOUBLE) AND d <> '?' AND e = NULL ALIGN '1m' BY (2)",
+            sql
+        );
+        // The range clauses are bound as well, and strings are parsed as literals only.
+        let params = [
+            Value::Number("1".to_string(), false),
+            params[1].clone(),
+            Value::Number("2.5".to_string(), false),
+            Value::Null,
+            Value::Number("2".to_string(), false),
+        ];
+        let sql = stmt.bind_sql(&params).unwrap();
+        assert_eq!(stmt.bind(&params).unwrap(), parse(&sql));
+
+        let err = stmt.bind_sql(&params[..1]).unwrap_err();
+        assert_matches!(
+            err,
+            Error::ParamNumMismatch {
+                expect: 5,
+                actual: 1,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn test_dollar_placeholders() {
+        let stmt = PreparedStatement::new(
+            "SELECT * FROM t /* $3 */ WHERE a = $2 -- $4\n AND b = $1 OR c = $2",
+        )
+        .unwrap();
+        assert_eq!(2, stmt.param_num());
+
+        let bound = stmt.bind(&[Value::Boolean(true), Value::Null]).unwrap();
+        assert_eq!(
+            parse("SELECT * FROM t WHERE a = NULL AND b = true OR c = NULL"),
+            bound
+        );
+
+        let err = stmt.bind(&[Value::Null]).unwrap_err();
+        assert_matches!(
+            err,
+            Error::ParamNumMismatch {
+                expect: 2,
+                actual: 1,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn test_bind_values_into_statement() {
+        let stmt = PreparedStatement::new(
+            "SELECT max(a) FROM t WHERE a IN (SELECT b FROM s WHERE c = ?) AND d = ?",
+        )
+        .unwrap();
+        // The values are not spliced into the SQL text, so they are never parsed as identifiers
+        // or as part of the statement.
+        let nan = Value::Number("NaN".to_string(), false);
+        let injection = Value::SingleQuotedString("x' OR '1' = '1".to_string());
+        let bound = stmt.bind(&[nan.clone(), injection.clone()]).unwrap();
+        match bound {
+            Statement::Query(query) => match &query.inner.body {
+                SetExpr::Select(select) => match &select.selection {
+                    Some(Expr::BinaryOp { left, right, .. }) => {
+                        match &**left {
+                            Expr::InSubquery { subquery, .. } => match &subquery.body {
+                                SetExpr::Select(select) => match &select.selection {
+                                    Some(Expr::BinaryOp { right, .. }) => {
+                                        assert_eq!(Expr::Value(nan), **right)
+                                    }
+                                    _ => unreachable!(),
+                                },
+                                _ => unreachable!(),
+                            },
+                            _ => unreachable!(),
+                        }
+                        match &**right {
+                            Expr::BinaryOp { right, .. } => {
+                                assert_eq!(Expr::Value(injection), **right)
+                            }
+                            _ => unreachable!(),
+                        }
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let stmt = PreparedStatement::new("INSERT INTO t(a, b) VALUES (?, ?)").unwrap();
+        let bound = stmt
+            .bind(&[Value::Number("inf".to_string(), false), Value::Null])
+            .unwrap();
+        match bound {
+            Statement::Insert(insert) => assert_eq!(
+                vec![vec![Value::Number("inf".to_string(), false), Value::Null]],
+                insert.values().unwrap()
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_placeholders_in_statement() {
+        let stmt = PreparedStatement::new("SELECT a FROM t WHERE a = ?").unwrap();
+        match stmt.statement() {
+            Statement::Query(query) => match &query.inner.body {
+                SetExpr::Select(select) => match &select.selection {
+                    Some(Expr::BinaryOp { left, op, right }) => {
+                        assert_eq!(BinaryOperator::Eq, *op);
+                        assert_eq!(None, expr_placeholder_index(left));
+                        assert_eq!(Some(0), expr_placeholder_index(right));
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let stmt = PreparedStatement::new("INSERT INTO t(a, b) VALUES ($2, $1)").unwrap();
+        assert_eq!(2, stmt.param_num());
+        match stmt.statement() {
+            Statement::Insert(insert) => match &insert.inner {
+                SpStatement::Insert { source, .. } => match &source.body {
+                    SetExpr::Values(values) => {
+                        let indexes = values.0[0]
+                            .iter()
+                            .map(expr_placeholder_index)
+                            .collect::<Vec<_>>();
+                        assert_eq!(vec![Some(1), Some(0)], indexes);
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_invalid_placeholders() {
+        assert!(PreparedStatement::new("SELECT * FROM t WHERE a = ? AND b = $1").is_err());
+        assert!(PreparedStatement::new("SELECT * FROM t WHERE a = $0").is_err());
+        assert!(PreparedStatement::new("SELECT 1; SELECT 2").is_err());
+
+        let stmt = PreparedStatement::new("SHOW TABLES LIKE ?").unwrap();
+        assert!(stmt
+            .bind(&[Value::SingleQuotedString("t".to_string())])
+            .is_err());
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A lightweight scanner over raw SQL text, for the jobs that must be done before parsing.
+
+/// If a quoted string, quoted identifier or comment starts at `i`, returns the position right
+/// after it, otherwise `None`. Unterminated ones extend to the end of `bytes`.
+pub(crate) fn skip_quoted_or_comment(bytes: &[u8], i: usize) -> Option<usize> {
+    let end = match bytes[i] {
+        quote @ (b'\'' | b'"' | b'`') => {
+            let mut i = i + 1;
+            while i < bytes.len() {
+                if bytes[i] == b'\\' && quote == b'\'' {
+                    i += 2;
+                    continue;
+                }
+                if bytes[i] == quote {
+                    // A doubled quote is an escaped quote.
+                    if bytes.get(i + 1) == Some(&quote) {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            i + 1
+        }
+        b'-' if bytes.get(i + 1) == Some(&b'-') => bytes[i..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(bytes.len(), |n| i + n + 1),
+        b'/' if bytes.get(i + 1) == Some(&b'*') => bytes[i + 2..]
+            .windows(2)
+            .position(|w| w == b"*/")
+            .map_or(bytes.len(), |n| i + 2 + n + 2),
+        _ => return None,
+    };
+    Some(end.min(bytes.len()))
+}
+
+/// Splits SQL text into statements by the `;` delimiters. Statements that contain only
+/// whitespaces and comments are omitted.
+pub(crate) fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut is_empty = true;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_quoted_or_comment(bytes, i) {
+            // Quoted strings or identifiers make the statement non-empty, comments don't.
+            is_empty &= matches!(bytes[i], b'-' | b'/');
+            i = end;
+            continue;
+        }
+        match bytes[i] {
+            b';' => {
+                if !is_empty {
+                    statements.push(sql[start..i].trim());
+                }
+                start = i + 1;
+                is_empty = true;
+            }
+            b if !b.is_ascii_whitespace() => is_empty = false,
+            _ => {}
+        }
+        i += 1;
+    }
+    if !is_empty {
+        statements.push(sql[start..].trim());
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            vec!["SELECT 1", "SELECT ';'", "INSERT INTO t VALUES ('a''b;')"],
+            split_statements("SELECT 1; SELECT ';' ;\nINSERT INTO t VALUES ('a''b;');")
+        );
+        assert_eq!(
+            vec!["SELECT 1 -- a; b\n, 2", "SELECT /* ; */ 3"],
+            split_statements("SELECT 1 -- a; b\n, 2; SELECT /* ; */ 3; -- the end")
+        );
+        assert!(split_statements(" ; ;\n").is_empty());
+        assert!(split_statements("").is_empty());
+    }
+
+    #[test]
+    fn test_skip_quoted_or_comment() {
+        let sql = b"'it''s' \"a\"\"b\" `c` -- d\n/* e */ f";
+        assert_eq!(Some(7), skip_quoted_or_comment(sql, 0));
+        assert_eq!(None, skip_quoted_or_comment(sql, 7));
+        assert_eq!(Some(14), skip_quoted_or_comment(sql, 8));
+        assert_eq!(Some(18), skip_quoted_or_comment(sql, 15));
+        assert_eq!(Some(24), skip_quoted_or_comment(sql, 19));
+        assert_eq!(Some(31), skip_quoted_or_comment(sql, 24));
+        // unterminated
+        assert_eq!(Some(2), skip_quoted_or_comment(b"'a", 0));
+    }
+}
@@ -14,20 +14,27 @@
 
 pub mod alter;
 pub mod create;
+pub mod explain;
 pub mod insert;
+pub mod kill;
+pub mod privilege;
 pub mod query;
+pub mod set_variables;
 pub mod show;
 pub mod statement;
 
 use std::str::FromStr;
 
 use api::helper::ColumnDataTypeWrapper;
-use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
-use common_time::Timestamp;
+use common_time::{Interval, Timestamp};
+use datatypes::decimal::{
+    Decimal128, DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE, DECIMAL128_MAX_PRECISION,
+};
 use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema};
-use datatypes::types::DateTimeType;
+use datatypes::types::{DateTimeType, JsonType};
 use datatypes::value::Value;
+use session::context::QueryContext;
 use snafu::{ensure, ResultExt};
 
 use crate::ast::{
@@ -40,16 +47,19 @@ use crate::error::{
 };
 
 /// Converts maybe fully-qualified table name (`<catalog>.<schema>.<table>` or `<table>` when
-/// catalog and schema are default) to tuple.
-pub fn table_idents_to_full_name(obj_name: &ObjectName) -> Result<(String, String, String)> {
+/// catalog and schema are taken from the query context) to tuple.
+pub fn table_idents_to_full_name(
+    obj_name: &ObjectName,
+    query_ctx: &QueryContext,
+) -> Result<(String, String, String)> {
     match &obj_name.0[..] {
         [table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
-            DEFAULT_SCHEMA_NAME.to_string(),
+            query_ctx.current_catalog(),
+            query_ctx.current_schema(),
             table.value.clone(),
         )),
         [schema, table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
+            query_ctx.current_catalog(),
             schema.value.clone(),
             table.value.clone(),
         )),
@@ -74,7 +84,13 @@ fn parse_string_to_value(
     data_type: &ConcreteDataType,
 ) -> Result<Value> {
     ensure!(
-        data_type.is_string(),
+        data_type.is_string()
+            || matches!(
+                data_type,
+                ConcreteDataType::Decimal128(_)
+                    | ConcreteDataType::Json(_)
+                    | ConcreteDataType::Interval(_)
+            ),
         ColumnTypeMismatchSnafu {
             column_name,
             expect: data_type.clone(),
@@ -117,6 +133,27 @@ fn parse_string_to_value(
                 .fail()
             }
         }
+        ConcreteDataType::Decimal128(_) => sql_number_to_value(data_type, &s),
+        ConcreteDataType::Json(_) => {
+            if serde_json::from_str::<serde_json::Value>(&s).is_ok() {
+                Ok(Value::Json(s.into()))
+            } else {
+                ParseSqlValueSnafu {
+                    msg: format!("Failed to parse {} to Json value", s),
+                }
+                .fail()
+            }
+        }
+        ConcreteDataType::Interval(_) => {
+            if let Ok(interval) = Interval::from_str(&s) {
+                Ok(Value::Interval(interval))
+            } else {
+                ParseSqlValueSnafu {
+                    msg: format!("Failed to parse {} to Interval value", s),
+                }
+                .fail()
+            }
+        }
         _ => {
             unreachable!()
         }
@@ -142,6 +179,17 @@ macro_rules! parse_number_to_value {
 
 /// Convert a sql value into datatype's value
 pub fn sql_number_to_value(data_type: &ConcreteDataType, n: &str) -> Result<Value> {
+    if let ConcreteDataType::Decimal128(t) = data_type {
+        return Decimal128::from_str_with(n, t.precision(), t.scale())
+            .map(Value::Decimal128)
+            .map_err(|e| {
+                ParseSqlValueSnafu {
+                    msg: format!("Fail to parse number {}, {}", n, e),
+                }
+                .build()
+            });
+    }
+
     parse_number_to_value!(
         data_type,
         n,
@@ -269,14 +317,15 @@ pub fn sql_column_def_to_grpc_column_def(col: ColumnDef) -> Result<api::v1::Colu
         .transpose()
         .context(SerializeColumnDefaultConstraintSnafu)?;
 
-    let data_type = ColumnDataTypeWrapper::try_from(data_type)
+    let (data_type, datatype_extension) = ColumnDataTypeWrapper::try_from(data_type)
         .context(ConvertToGrpcDataTypeSnafu)?
-        .datatype() as i32;
+        .into_parts();
     Ok(api::v1::ColumnDef {
         name,
-        datatype: data_type,
+        datatype: data_type as i32,
         is_nullable: nullable,
         default_constraint,
+        datatype_extension,
     })
 }
 
@@ -293,10 +342,30 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
         SqlDataType::Double => Ok(ConcreteDataType::float64_datatype()),
         SqlDataType::Boolean => Ok(ConcreteDataType::boolean_datatype()),
         SqlDataType::Date => Ok(ConcreteDataType::date_datatype()),
+        SqlDataType::Decimal(precision, scale) => {
+            let precision = precision.unwrap_or(DECIMAL128_DEFAULT_PRECISION as u64);
+            let scale = scale.unwrap_or(DECIMAL128_DEFAULT_SCALE as u64);
+            ensure!(
+                precision > 0 && precision <= DECIMAL128_MAX_PRECISION as u64 && scale <= precision,
+                error::InvalidSqlSnafu {
+                    msg: format!(
+                        "invalid decimal precision {} and scale {}",
+                        precision, scale
+                    ),
+                }
+            );
+            Ok(ConcreteDataType::decimal128_datatype(
+                precision as u8,
+                scale as u8,
+            ))
+        }
+        SqlDataType::Interval => Ok(ConcreteDataType::interval_datatype()),
         SqlDataType::Custom(obj_name) => match &obj_name.0[..] {
             [type_name] => {
                 if type_name.value.eq_ignore_ascii_case(DateTimeType::name()) {
                     Ok(ConcreteDataType::datetime_datatype())
+                } else if type_name.value.eq_ignore_ascii_case(JsonType::name()) {
+                    Ok(ConcreteDataType::json_datatype())
                 } else {
                     error::SqlTypeNotSupportedSnafu {
                         t: data_type.clone(),
@@ -365,6 +434,58 @@ mod tests {
             SqlDataType::Timestamp,
             ConcreteDataType::timestamp_millis_datatype(),
         );
+        check_type(
+            SqlDataType::Decimal(Some(10), Some(2)),
+            ConcreteDataType::decimal128_datatype(10, 2),
+        );
+        check_type(
+            SqlDataType::Decimal(None, None),
+            ConcreteDataType::decimal128_default_datatype(),
+        );
+        check_type(SqlDataType::Interval, ConcreteDataType::interval_datatype());
+        check_type(
+            SqlDataType::Custom(ObjectName(vec![Ident::new("JSON")])),
+            ConcreteDataType::json_datatype(),
+        );
+
+        assert!(
+            sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(Some(39), None)).is_err()
+        );
+        assert!(
+            sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(Some(5), Some(6))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_new_type_values() {
+        let decimal_type = ConcreteDataType::decimal128_datatype(10, 2);
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            sql_number_to_value(&decimal_type, "123.45").unwrap()
+        );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(100, 10, 2)),
+            sql_value_to_value(
+                "a",
+                &decimal_type,
+                &SqlValue::SingleQuotedString("1".to_string())
+            )
+            .unwrap()
+        );
+
+        let sql_val = SqlValue::SingleQuotedString("{\"a\": [1, 2]}".to_string());
+        assert_eq!(
+            Value::Json("{\"a\": [1, 2]}".into()),
+            sql_value_to_value("a", &ConcreteDataType::json_datatype(), &sql_val).unwrap()
+        );
+        let sql_val = SqlValue::SingleQuotedString("{\"a\"".to_string());
+        assert!(sql_value_to_value("a", &ConcreteDataType::json_datatype(), &sql_val).is_err());
+
+        let sql_val = SqlValue::SingleQuotedString("1 day 2 hours".to_string());
+        assert_eq!(
+            Value::Interval(Interval::new(0, 1, 2 * 3_600_000_000_000)),
+            sql_value_to_value("a", &ConcreteDataType::interval_datatype(), &sql_val).unwrap()
+        );
     }
 
     #[test]
@@ -13,9 +13,10 @@
 // limitations under the License.
 
 use api::v1::{alter_expr, AddColumn, AlterExpr};
+use session::context::QueryContext;
 use sqlparser::ast::{ColumnDef, ObjectName, TableConstraint};
 
-use crate::error::UnsupportedAlterTableStatementSnafu;
+use crate::error::{Result, UnsupportedAlterTableStatementSnafu};
 use crate::statements::{sql_column_def_to_grpc_column_def, table_idents_to_full_name};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -50,36 +51,33 @@ pub enum AlterTableOperation {
     // TODO(hl): support remove column
 }
 
-/// Convert `AlterTable` statement to `AlterExpr` for gRPC
-impl TryFrom<AlterTable> for AlterExpr {
-    type Error = crate::error::Error;
+/// Convert `AlterTable` statement to `AlterExpr` for gRPC, unqualified table name is resolved
+/// against the query context.
+pub fn alter_table_to_expr(value: AlterTable, query_ctx: &QueryContext) -> Result<AlterExpr> {
+    let (catalog, schema, table) = table_idents_to_full_name(&value.table_name, query_ctx)?;
 
-    fn try_from(value: AlterTable) -> Result<Self, Self::Error> {
-        let (catalog, schema, table) = table_idents_to_full_name(&value.table_name)?;
-
-        let kind = match value.alter_operation {
-            AlterTableOperation::AddConstraint(_) => {
-                return UnsupportedAlterTableStatementSnafu {
-                    msg: "ADD CONSTRAINT not supported yet.",
-                }
-                .fail();
-            }
-            AlterTableOperation::AddColumn { column_def } => {
-                alter_expr::Kind::AddColumns(api::v1::AddColumns {
-                    add_columns: vec![AddColumn {
-                        column_def: Some(sql_column_def_to_grpc_column_def(column_def)?),
-                        is_key: false,
-                    }],
-                })
+    let kind = match value.alter_operation {
+        AlterTableOperation::AddConstraint(_) => {
+            return UnsupportedAlterTableStatementSnafu {
+                msg: "ADD CONSTRAINT not supported yet.",
             }
-        };
-        let expr = AlterExpr {
-            catalog_name: Some(catalog),
-            schema_name: Some(schema),
-            table_name: table,
-            kind: Some(kind),
-        };
+            .fail();
+        }
+        AlterTableOperation::AddColumn { column_def } => {
+            alter_expr::Kind::AddColumns(api::v1::AddColumns {
+                add_columns: vec![AddColumn {
+                    column_def: Some(sql_column_def_to_grpc_column_def(column_def)?),
+                    is_key: false,
+                }],
+            })
+        }
+    };
+    let expr = AlterExpr {
+        catalog_name: Some(catalog),
+        schema_name: Some(schema),
+        table_name: table,
+        kind: Some(kind),
+    };
 
-        Ok(expr)
-    }
+    Ok(expr)
 }
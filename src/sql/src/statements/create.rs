@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::ast::{ColumnDef, Ident, ObjectName, SqlOption, TableConstraint, Value as SqlValue};
+use crate::statements::query::Query;
 
 /// Time index name, used in table constraints.
 pub const TIME_INDEX: &str = "__time_index";
@@ -34,10 +35,20 @@ pub struct CreateTable {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Partitions {
+    pub method: PartitionMethod,
     pub column_list: Vec<Ident>,
+    /// Partitions defined in `PARTITION BY RANGE COLUMNS`, empty for other methods.
     pub entries: Vec<PartitionEntry>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PartitionMethod {
+    /// `PARTITION BY RANGE COLUMNS(column_list) (partition_entry, ...)`
+    RangeColumns,
+    /// `PARTITION BY HASH(column_list) PARTITIONS partition_num`
+    Hash { partition_num: u32 },
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PartitionEntry {
     pub name: Ident,
@@ -48,3 +59,12 @@ pub struct PartitionEntry {
 pub struct CreateDatabase {
     pub name: ObjectName,
 }
+
+/// `CREATE MATERIALIZED VIEW [IF NOT EXISTS] name AS query`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateMaterializedView {
+    pub if_not_exists: bool,
+    /// View name, which is also the name of the table storing its rows
+    pub name: ObjectName,
+    pub query: Box<Query>,
+}
@@ -0,0 +1,25 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::statements::query::Query;
+
+/// SQL structure for `EXPLAIN [ANALYZE] [VERBOSE] query`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explain {
+    /// Executes the query and reports the metrics of each operator.
+    pub analyze: bool,
+    /// Also reports the plans after each optimizer rule.
+    pub verbose: bool,
+    pub query: Box<Query>,
+}
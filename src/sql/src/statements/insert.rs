@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use session::context::QueryContext;
 use sqlparser::ast::{SetExpr, Statement, UnaryOperator, Values};
 use sqlparser::parser::ParserError;
 
@@ -26,9 +27,11 @@ pub struct Insert {
 }
 
 impl Insert {
-    pub fn full_table_name(&self) -> Result<(String, String, String)> {
+    pub fn full_table_name(&self, query_ctx: &QueryContext) -> Result<(String, String, String)> {
         match &self.inner {
-            Statement::Insert { table_name, .. } => table_idents_to_full_name(table_name),
+            Statement::Insert { table_name, .. } => {
+                table_idents_to_full_name(table_name, query_ctx)
+            }
             _ => unreachable!(),
         }
     }
@@ -40,6 +43,17 @@ impl Insert {
         }
     }
 
+    /// Expressions in the `VALUES` clause, `None` if the source of insertion is not `VALUES`.
+    pub fn value_exprs(&self) -> Option<&Vec<Vec<Expr>>> {
+        match &self.inner {
+            Statement::Insert { source, .. } => match &source.body {
+                SetExpr::Values(Values(exprs)) => Some(exprs),
+                _ => None,
+            },
+            _ => unreachable!(),
+        }
+    }
+
     pub fn values(&self) -> Result<Vec<Vec<Value>>> {
         let values = match &self.inner {
             Statement::Insert { source, .. } => match &source.body {
@@ -0,0 +1,21 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// SQL structure for `KILL [CONNECTION | QUERY] id`. `KILL QUERY` cancels the running query of
+/// the process `id`, while `KILL [CONNECTION]` also rejects all later queries of the connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Kill {
+    pub id: u32,
+    pub connection: bool,
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use crate::ast::ObjectName;
+
+/// Privileges that can be granted to users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Alter,
+}
+
+impl Privilege {
+    /// All privileges, granted by `GRANT ALL [PRIVILEGES]`.
+    pub fn all() -> Vec<Privilege> {
+        vec![Privilege::Select, Privilege::Insert, Privilege::Alter]
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Privilege::Select => write!(f, "SELECT"),
+            Privilege::Insert => write!(f, "INSERT"),
+            Privilege::Alter => write!(f, "ALTER"),
+        }
+    }
+}
+
+/// The object privileges are granted on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantObject {
+    /// `*.*`, all databases in the current catalog.
+    AllDatabases,
+    /// `db.*`, all tables in the database, or `*` for the current database.
+    Database(Option<String>),
+    /// `[db.]table`.
+    Table(ObjectName),
+}
+
+/// SQL structure for `CREATE USER [IF NOT EXISTS] name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateUser {
+    pub name: String,
+    pub if_not_exists: bool,
+}
+
+/// SQL structure for `CREATE ROLE [IF NOT EXISTS] name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateRole {
+    pub name: String,
+    pub if_not_exists: bool,
+}
+
+/// SQL structure for `GRANT privileges ON object TO user`, the user may also be a role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    pub privileges: Vec<Privilege>,
+    pub object: GrantObject,
+    pub user: String,
+}
+
+/// SQL structure for `REVOKE privileges ON object FROM user`, the user may also be a role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revoke {
+    pub privileges: Vec<Privilege>,
+    pub object: GrantObject,
+    pub user: String,
+}
+
+/// SQL structure for `GRANT role [, role ...] TO user`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantRole {
+    pub roles: Vec<String>,
+    pub user: String,
+}
+
+/// SQL structure for `REVOKE role [, role ...] FROM user`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokeRole {
+    pub roles: Vec<String>,
+    pub user: String,
+}
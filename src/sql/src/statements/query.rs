@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sqlparser::ast::Query as SpQuery;
+use std::collections::HashSet;
+
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, JoinConstraint, JoinOperator, ObjectName, OrderByExpr,
+    Query as SpQuery, SelectItem, SetExpr, TableFactor, TableWithJoins, Values,
+};
 
 use crate::error::Error;
 
@@ -20,6 +25,239 @@ use crate::error::Error;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Query {
     pub inner: SpQuery,
+    /// The range clauses if this is a range query, they are removed from `inner`.
+    pub range_select: Option<RangeSelect>,
+}
+
+/// The clauses of a range query, for example
+/// `SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' BY (host) FILL PREV`, which computes the
+/// aggregates over the 5 minutes windows starting at every minute, for each host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSelect {
+    /// The interval of the window start times, as written in `ALIGN`.
+    pub align: String,
+    pub align_millis: i64,
+    /// Expressions that tell the series apart, the primary key of the table is used if `BY` is
+    /// omitted.
+    pub by: Option<Vec<Expr>>,
+    /// Fills the windows without data for the aggregates without their own `FILL`.
+    pub fill: Option<Fill>,
+    pub ranges: Vec<RangeExpr>,
+}
+
+/// An aggregate with a `RANGE` in the projection of a range query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeExpr {
+    /// Index of the aggregate in the projection.
+    pub item: usize,
+    /// The window length, as written in `RANGE`.
+    pub range: String,
+    pub range_millis: i64,
+    pub fill: Option<Fill>,
+}
+
+/// How a range query fills the windows without data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fill {
+    Null,
+    /// The value of the previous window.
+    Prev,
+    /// Interpolates linearly between the previous and the next window.
+    Linear,
+    /// A numeric constant, as written.
+    Const(String),
+}
+
+impl Query {
+    /// Names of the tables read by this query, including tables in subqueries. Names of common
+    /// table expressions are not included.
+    pub fn table_names(&self) -> Vec<ObjectName> {
+        let mut collector = TableNameCollector::default();
+        collector.visit_query(&self.inner);
+        collector.tables
+    }
+}
+
+#[derive(Default)]
+struct TableNameCollector {
+    tables: Vec<ObjectName>,
+    cte_names: HashSet<String>,
+}
+
+impl TableNameCollector {
+    fn visit_query(&mut self, query: &SpQuery) {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.visit_query(&cte.query);
+                let _ = self.cte_names.insert(cte.alias.name.value.clone());
+            }
+        }
+        self.visit_set_expr(&query.body);
+        self.visit_order_by(&query.order_by);
+        if let Some(limit) = &query.limit {
+            self.visit_expr(limit);
+        }
+        if let Some(offset) = &query.offset {
+            self.visit_expr(&offset.value);
+        }
+    }
+
+    fn visit_set_expr(&mut self, set_expr: &SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                for table_with_joins in &select.from {
+                    self.visit_table_with_joins(table_with_joins);
+                }
+                for item in &select.projection {
+                    match item {
+                        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                            self.visit_expr(expr)
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(selection) = &select.selection {
+                    self.visit_expr(selection);
+                }
+                self.visit_exprs(&select.group_by);
+                if let Some(having) = &select.having {
+                    self.visit_expr(having);
+                }
+            }
+            SetExpr::Query(query) => self.visit_query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.visit_set_expr(left);
+                self.visit_set_expr(right);
+            }
+            SetExpr::Values(Values(rows)) => rows.iter().for_each(|row| self.visit_exprs(row)),
+            _ => {}
+        }
+    }
+
+    fn visit_table_with_joins(&mut self, table_with_joins: &TableWithJoins) {
+        self.visit_table_factor(&table_with_joins.relation);
+        for join in &table_with_joins.joins {
+            self.visit_table_factor(&join.relation);
+            match &join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) => self.visit_expr(expr),
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &TableFactor) {
+        match table_factor {
+            TableFactor::Table { name, .. } => {
+                let is_cte = name.0.len() == 1 && self.cte_names.contains(&name.0[0].value);
+                if !is_cte {
+                    self.tables.push(name.clone());
+                }
+            }
+            TableFactor::Derived { subquery, .. } => self.visit_query(subquery),
+            TableFactor::TableFunction { expr, .. } => self.visit_expr(expr),
+            TableFactor::NestedJoin(table_with_joins) => {
+                self.visit_table_with_joins(table_with_joins)
+            }
+        }
+    }
+
+    fn visit_order_by(&mut self, order_by: &[OrderByExpr]) {
+        order_by
+            .iter()
+            .for_each(|order_by| self.visit_expr(&order_by.expr));
+    }
+
+    fn visit_exprs(&mut self, exprs: &[Expr]) {
+        exprs.iter().for_each(|expr| self.visit_expr(expr));
+    }
+
+    /// Visits every expression that may contain a subquery, the other expressions are leaves.
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::BinaryOp { left, right, .. }
+            | Expr::IsDistinctFrom(left, right)
+            | Expr::IsNotDistinctFrom(left, right) => {
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. }
+            | Expr::Extract { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::MapAccess { column: expr, .. } => self.visit_expr(expr),
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.visit_expr(expr);
+                self.visit_expr(low);
+                self.visit_expr(high);
+            }
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                self.visit_expr(expr);
+                substring_from
+                    .iter()
+                    .chain(substring_for.iter())
+                    .for_each(|expr| self.visit_expr(expr));
+            }
+            Expr::Trim { expr, trim_where } => {
+                self.visit_expr(expr);
+                if let Some((_, trim_what)) = trim_where {
+                    self.visit_expr(trim_what);
+                }
+            }
+            Expr::InList { expr, list, .. } => {
+                self.visit_expr(expr);
+                self.visit_exprs(list);
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.visit_expr(expr);
+                self.visit_query(subquery);
+            }
+            Expr::Subquery(subquery) | Expr::Exists { subquery, .. } => self.visit_query(subquery),
+            Expr::Function(function) => {
+                for arg in &function.args {
+                    match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(expr),
+                            ..
+                        } => self.visit_expr(expr),
+                        _ => {}
+                    }
+                }
+                if let Some(over) = &function.over {
+                    self.visit_exprs(&over.partition_by);
+                    self.visit_order_by(&over.order_by);
+                }
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                operand
+                    .iter()
+                    .chain(else_result.iter())
+                    .for_each(|expr| self.visit_expr(expr));
+                self.visit_exprs(conditions);
+                self.visit_exprs(results);
+            }
+            Expr::Tuple(exprs) => self.visit_exprs(exprs),
+            _ => {}
+        }
+    }
 }
 
 /// Automatically converts from sqlparser Query instance to SqlQuery.
@@ -27,7 +265,10 @@ impl TryFrom<SpQuery> for Query {
     type Error = Error;
 
     fn try_from(q: SpQuery) -> Result<Self, Self::Error> {
-        Ok(Query { inner: q })
+        Ok(Query {
+            inner: q,
+            range_select: None,
+        })
     }
 }
 
@@ -38,3 +279,73 @@ impl TryFrom<Query> for SpQuery {
         Ok(value.inner)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+    use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
+
+    fn table_names(sql: &str) -> Vec<String> {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::Query(query) => query
+                .table_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_table_names() {
+        assert_eq!(vec!["t1"], table_names("SELECT * FROM t1 WHERE a > 1"));
+        assert_eq!(
+            vec!["db.t1", "t2", "t3"],
+            table_names(
+                "SELECT * FROM db.t1 JOIN t2 ON t1.a = t2.a WHERE t1.b IN (SELECT b FROM t3)"
+            )
+        );
+        assert_eq!(
+            vec!["t1", "t2"],
+            table_names("SELECT (SELECT max(a) FROM t1) FROM (SELECT * FROM t2) AS s")
+        );
+        assert_eq!(
+            vec!["t1", "t2"],
+            table_names("SELECT a FROM t1 UNION SELECT a FROM t2")
+        );
+        assert_eq!(
+            vec!["t1"],
+            table_names("WITH cte AS (SELECT * FROM t1) SELECT * FROM cte")
+        );
+    }
+
+    #[test]
+    fn test_table_names_in_every_clause() {
+        let cases = [
+            "SELECT abs((SELECT max(v) FROM secret)) FROM t",
+            "SELECT CASE WHEN a > 0 THEN (SELECT max(v) FROM secret) ELSE 0 END FROM t",
+            "SELECT CASE (SELECT max(v) FROM secret) WHEN 1 THEN a END FROM t",
+            "SELECT * FROM t WHERE a BETWEEN 0 AND (SELECT max(v) FROM secret)",
+            "SELECT * FROM t WHERE a IN (1, (SELECT max(v) FROM secret))",
+            "SELECT CAST((SELECT max(v) FROM secret) AS DOUBLE) FROM t",
+            "SELECT * FROM t WHERE (SELECT max(v) FROM secret) IS NULL",
+            "SELECT * FROM t WHERE (SELECT max(v) FROM secret) IS NOT NULL",
+            "SELECT * FROM t ORDER BY (SELECT max(v) FROM secret)",
+            "SELECT a FROM t GROUP BY (SELECT max(v) FROM secret)",
+            "SELECT a FROM t GROUP BY a HAVING count(*) > (SELECT max(v) FROM secret)",
+            "SELECT * FROM t JOIN t2 ON t.a = t2.a AND t.b IN (SELECT v FROM secret)",
+            "SELECT * FROM t LEFT JOIN t2 ON t.a = (SELECT max(v) FROM secret)",
+            "SELECT * FROM t LIMIT (SELECT max(v) FROM secret)",
+            "SELECT * FROM t WHERE NOT EXISTS (SELECT v FROM secret)",
+            "SELECT sum(a) OVER (PARTITION BY (SELECT max(v) FROM secret)) FROM t",
+        ];
+        for sql in cases {
+            let tables = table_names(sql);
+            assert!(tables.contains(&"secret".to_string()), "{sql}: {tables:?}");
+        }
+    }
+}
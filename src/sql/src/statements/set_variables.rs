@@ -0,0 +1,43 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ast::{Expr, ObjectName, Value};
+
+/// Name of the variable set by `SET TIME ZONE`.
+pub const TIME_ZONE_VARIABLE: &str = "time_zone";
+
+/// SQL structure for `SET [SESSION | LOCAL] variable { = | TO } value` and
+/// `SET TIME ZONE value`, the latter is represented with variable `time_zone`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetVariables {
+    pub variable: ObjectName,
+    pub value: Expr,
+}
+
+impl SetVariables {
+    /// Name of the variable, in lowercase.
+    pub fn name(&self) -> String {
+        self.variable.to_string().to_lowercase()
+    }
+
+    /// Value of the variable, string literals and identifiers are unquoted.
+    pub fn value(&self) -> String {
+        match &self.value {
+            Expr::Value(Value::SingleQuotedString(s))
+            | Expr::Value(Value::DoubleQuotedString(s)) => s.clone(),
+            Expr::Identifier(ident) => ident.value.clone(),
+            other => other.to_string(),
+        }
+    }
+}
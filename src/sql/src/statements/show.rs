@@ -60,6 +60,12 @@ pub struct ShowCreateTable {
     pub table_name: String,
 }
 
+/// SQL structure for `SHOW [FULL] PROCESSLIST`, queries are truncated unless `FULL` is given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowProcesslist {
+    pub full: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -16,22 +16,30 @@ use sqlparser::ast::Statement as SpStatement;
 use sqlparser::parser::ParserError;
 
 use crate::statements::alter::AlterTable;
-use crate::statements::create::{CreateDatabase, CreateTable};
+use crate::statements::create::{CreateDatabase, CreateMaterializedView, CreateTable};
+use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
+use crate::statements::kill::Kill;
+use crate::statements::privilege::{CreateRole, CreateUser, Grant, GrantRole, Revoke, RevokeRole};
 use crate::statements::query::Query;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
+use crate::statements::set_variables::SetVariables;
+use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowProcesslist, ShowTables};
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Statement {
     // Query
     Query(Box<Query>),
+    // EXPLAIN
+    Explain(Explain),
     // Insert
     Insert(Box<Insert>),
     /// CREATE TABLE
     CreateTable(CreateTable),
     // CREATE DATABASE
     CreateDatabase(CreateDatabase),
+    /// CREATE MATERIALIZED VIEW
+    CreateMaterializedView(CreateMaterializedView),
     /// ALTER TABLE
     Alter(AlterTable),
     // Databases.
@@ -40,6 +48,26 @@ pub enum Statement {
     ShowTables(ShowTables),
     // SHOW CREATE TABLE
     ShowCreateTable(ShowCreateTable),
+    // USE
+    Use(String),
+    // SET variables
+    SetVariables(SetVariables),
+    // CREATE USER
+    CreateUser(CreateUser),
+    // GRANT
+    Grant(Grant),
+    // REVOKE
+    Revoke(Revoke),
+    // CREATE ROLE
+    CreateRole(CreateRole),
+    // GRANT roles
+    GrantRole(GrantRole),
+    // REVOKE roles
+    RevokeRole(RevokeRole),
+    // SHOW PROCESSLIST
+    ShowProcesslist(ShowProcesslist),
+    // KILL [CONNECTION | QUERY]
+    Kill(Kill),
 }
 
 /// Converts Statement to sqlparser statement
@@ -57,8 +85,34 @@ impl TryFrom<Statement> for SpStatement {
             Statement::ShowCreateTable(_) => Err(ParserError::ParserError(
                 "sqlparser does not support SHOW CREATE TABLE query.".to_string(),
             )),
+            Statement::Use(_) => Err(ParserError::ParserError(
+                "sqlparser does not support USE statement.".to_string(),
+            )),
+            Statement::SetVariables(_) => Err(ParserError::ParserError(
+                "sqlparser does not support SET statement.".to_string(),
+            )),
+            Statement::CreateUser(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)
+            | Statement::CreateRole(_)
+            | Statement::GrantRole(_)
+            | Statement::RevokeRole(_) => Err(ParserError::ParserError(
+                "sqlparser does not support privilege statements.".to_string(),
+            )),
+            Statement::ShowProcesslist(_) | Statement::Kill(_) => Err(ParserError::ParserError(
+                "sqlparser does not support process statements.".to_string(),
+            )),
             Statement::Query(s) => Ok(SpStatement::Query(Box::new(s.inner))),
+            Statement::Explain(e) => Ok(SpStatement::Explain {
+                describe_alias: false,
+                analyze: e.analyze,
+                verbose: e.verbose,
+                statement: Box::new(SpStatement::Query(Box::new(e.query.inner))),
+            }),
             Statement::Insert(i) => Ok(i.inner),
+            Statement::CreateMaterializedView(_) => Err(ParserError::ParserError(
+                "sqlparser does not support CREATE MATERIALIZED VIEW statement.".to_string(),
+            )),
             Statement::CreateDatabase(_) | Statement::CreateTable(_) | Statement::Alter(_) => {
                 unimplemented!()
             }
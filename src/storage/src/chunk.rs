@@ -17,12 +17,15 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use snafu::ResultExt;
-use store_api::storage::{Chunk, ChunkReader, SchemaRef, SequenceNumber};
+use store_api::storage::{Chunk, ChunkReader, ReadMetrics, SchemaRef, SequenceNumber};
 use table::predicate::Predicate;
 
 use crate::error::{self, Error, Result};
-use crate::memtable::{IterContext, MemtableRef};
-use crate::read::{BoxedBatchReader, DedupReader, MergeReaderBuilder};
+use crate::memtable::{BatchIterator, BoxedBatchIterator, IterContext, MemtableRef, RowOrdering};
+use crate::read::{
+    Batch, BoxedBatchReader, DedupReader, MergeReaderBuilder, ReadMetricsRecorder,
+    ReadMetricsRecorderRef,
+};
 use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchemaRef};
 use crate::sst::{AccessLayerRef, FileHandle, LevelMetas, ReadOptions, Visitor};
 
@@ -33,6 +36,7 @@ use crate::sst::{AccessLayerRef, FileHandle, LevelMetas, ReadOptions, Visitor};
 pub struct ChunkReaderImpl {
     schema: ProjectedSchemaRef,
     batch_reader: BoxedBatchReader,
+    metrics: ReadMetricsRecorderRef,
 }
 
 #[async_trait]
@@ -53,14 +57,51 @@ impl ChunkReader for ChunkReaderImpl {
 
         Ok(Some(chunk))
     }
+
+    fn metrics(&self) -> ReadMetrics {
+        self.metrics.metrics()
+    }
 }
 
 impl ChunkReaderImpl {
-    pub fn new(schema: ProjectedSchemaRef, batch_reader: BoxedBatchReader) -> ChunkReaderImpl {
+    pub fn new(
+        schema: ProjectedSchemaRef,
+        batch_reader: BoxedBatchReader,
+        metrics: ReadMetricsRecorderRef,
+    ) -> ChunkReaderImpl {
         ChunkReaderImpl {
             schema,
             batch_reader,
+            metrics,
+        }
+    }
+}
+
+/// Iterator of a memtable that records the rows it returns.
+struct MemtableRowsRecorder {
+    iter: BoxedBatchIterator,
+    metrics: ReadMetricsRecorderRef,
+}
+
+impl Iterator for MemtableRowsRecorder {
+    type Item = Result<Batch>;
+
+    fn next(&mut self) -> Option<Result<Batch>> {
+        let batch = self.iter.next();
+        if let Some(Ok(batch)) = &batch {
+            self.metrics.record_memtable_rows(batch.num_rows());
         }
+        batch
+    }
+}
+
+impl BatchIterator for MemtableRowsRecorder {
+    fn schema(&self) -> ProjectedSchemaRef {
+        self.iter.schema()
+    }
+
+    fn ordering(&self) -> RowOrdering {
+        self.iter.ordering()
     }
 }
 
@@ -135,16 +176,21 @@ impl ChunkReaderBuilder {
         let mut reader_builder = MergeReaderBuilder::with_capacity(schema.clone(), num_sources)
             .batch_size(self.iter_ctx.batch_size);
 
+        let metrics = Arc::new(ReadMetricsRecorder::default());
         self.iter_ctx.projected_schema = Some(schema.clone());
         for mem in self.memtables {
-            let iter = mem.iter(&self.iter_ctx)?;
-            reader_builder = reader_builder.push_batch_iter(iter);
+            let iter = MemtableRowsRecorder {
+                iter: mem.iter(&self.iter_ctx)?,
+                metrics: metrics.clone(),
+            };
+            reader_builder = reader_builder.push_batch_iter(Box::new(iter));
         }
 
         let read_opts = ReadOptions {
             batch_size: self.iter_ctx.batch_size,
             projected_schema: schema.clone(),
             predicate: Predicate::new(self.filters),
+            metrics: metrics.clone(),
         };
         for file in &self.files_to_read {
             let reader = self
@@ -158,7 +204,7 @@ impl ChunkReaderBuilder {
         let reader = reader_builder.build();
         let reader = DedupReader::new(schema.clone(), reader);
 
-        Ok(ChunkReaderImpl::new(schema, Box::new(reader)))
+        Ok(ChunkReaderImpl::new(schema, Box::new(reader), metrics))
     }
 }
 
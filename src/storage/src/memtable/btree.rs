@@ -20,16 +20,15 @@ use std::sync::{Arc, RwLock};
 
 use datatypes::prelude::*;
 use datatypes::value::Value;
-use datatypes::vectors::{
-    UInt64Vector, UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder, VectorBuilder,
-};
+use datatypes::vectors::{UInt64Vector, UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder};
+use snafu::ResultExt;
 use store_api::storage::{OpType, SequenceNumber};
 
-use crate::error::Result;
+use crate::error::{self, Result};
 use crate::memtable::{
     BatchIterator, BoxedBatchIterator, IterContext, KeyValues, Memtable, MemtableId, RowOrdering,
 };
-use crate::read::Batch;
+use crate::read::{self, Batch};
 use crate::schema::compat::ReadAdapter;
 use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchemaRef};
 
@@ -182,14 +181,16 @@ impl BTreeIterator {
 
         let key_columns = rows_to_vectors(
             key_data_types,
+            true,
             self.adapter.source_key_needed(),
             keys.as_slice(),
-        );
+        )?;
         let value_columns = rows_to_vectors(
             value_data_types,
+            false,
             self.adapter.source_value_needed(),
             values.as_slice(),
-        );
+        )?;
 
         let batch = self.adapter.batch_from_parts(
             key_columns,
@@ -428,20 +429,24 @@ impl<'a> RowsProvider for &'a [&RowValue] {
     }
 }
 
+/// Builds vectors from rows provided by `provider`.
+///
+/// String row key columns are built as dictionary vectors.
 fn rows_to_vectors<I: Iterator<Item = ConcreteDataType>, T: RowsProvider>(
     data_types: I,
+    is_row_key: bool,
     column_needed: &[bool],
     provider: T,
-) -> Vec<VectorRef> {
+) -> Result<Vec<VectorRef>> {
     if provider.is_empty() {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
     let column_num = provider.column_num();
     let row_num = provider.row_num();
     let mut builders = Vec::with_capacity(column_num);
     for data_type in data_types {
-        builders.push(VectorBuilder::with_capacity(data_type, row_num));
+        builders.push(read::new_column_builder(&data_type, is_row_key, row_num));
     }
 
     let mut vectors = Vec::with_capacity(column_num);
@@ -453,11 +458,13 @@ fn rows_to_vectors<I: Iterator<Item = ConcreteDataType>, T: RowsProvider>(
         for row_idx in 0..row_num {
             let row = provider.row_by_index(row_idx);
             let value = &row[col_idx];
-            builder.push(value);
+            builder
+                .push_value_ref(value.as_value_ref())
+                .context(error::PushBatchSnafu)?;
         }
 
-        vectors.push(builder.finish());
+        vectors.push(builder.to_vector());
     }
 
-    vectors
+    Ok(vectors)
 }
@@ -19,20 +19,32 @@ use std::sync::Arc;
 
 use common_base::BitVec;
 use common_error::prelude::*;
-use datatypes::data_type::ConcreteDataType;
+use common_time::{Date, DateTime, Interval};
+use datatypes::data_type::{ConcreteDataType, DataType as _};
+use datatypes::decimal::DECIMAL128_MAX_PRECISION;
 use datatypes::prelude::{ScalarVector, ScalarVectorBuilder};
 use datatypes::schema;
+use datatypes::types::{DecimalType, MapType, StructField, StructType};
+use datatypes::value::{MapValue, StructValue, Value};
 use datatypes::vectors::{
-    BinaryVector, BinaryVectorBuilder, BooleanVector, BooleanVectorBuilder, Float32Vector,
-    Float32VectorBuilder, Float64Vector, Float64VectorBuilder, Int16Vector, Int16VectorBuilder,
-    Int32Vector, Int32VectorBuilder, Int64Vector, Int64VectorBuilder, Int8Vector,
-    Int8VectorBuilder, StringVector, StringVectorBuilder, TimestampVector, TimestampVectorBuilder,
-    UInt16Vector, UInt16VectorBuilder, UInt32Vector, UInt32VectorBuilder, UInt64Vector,
-    UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder, Vector, VectorRef,
+    BinaryVector, BinaryVectorBuilder, BooleanVector, BooleanVectorBuilder, DateTimeVector,
+    DateTimeVectorBuilder, DateVector, DateVectorBuilder, DecimalVector, DictionaryVector,
+    Float32Vector, Float32VectorBuilder, Float64Vector, Float64VectorBuilder, Int16Vector,
+    Int16VectorBuilder, Int32Vector, Int32VectorBuilder, Int64Vector, Int64VectorBuilder,
+    Int8Vector, Int8VectorBuilder, IntervalVector, IntervalVectorBuilder, JsonVector,
+    JsonVectorBuilder, MapVector, StringVector, StringVectorBuilder, StructVector, TimestampVector,
+    TimestampVectorBuilder, UInt16Vector, UInt16VectorBuilder, UInt32Vector, UInt32VectorBuilder,
+    UInt64Vector, UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder, Vector, VectorRef,
 };
 use paste::paste;
 use snafu::OptionExt;
 
+use self::data_type_extension::TypeExt;
+
+/// Names of the entry fields of maps in [MapTypeExtension].
+const MAP_KEY_NAME: &str = "key";
+const MAP_VALUE_NAME: &str = "value";
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("Failed to convert datafusion type: {}", from))]
@@ -47,11 +59,40 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display(
+        "Invalid extension {:?} of data type: {}",
+        data_type_extension,
+        data_type
+    ))]
+    InvalidDataTypeExtension {
+        data_type: i32,
+        data_type_extension: Option<DataTypeExtension>,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unsupported data type: {:?}", data_type))]
+    UnsupportedDataType {
+        data_type: ConcreteDataType,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid column values, reason: {}", reason))]
+    InvalidColumnValues {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to convert schema, source: {}", source))]
     ConvertSchema {
         #[snafu(backtrace)]
         source: datatypes::error::Error,
     },
+
+    #[snafu(display("Failed to create vector, source: {}", source))]
+    CreateVector {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -62,20 +103,22 @@ impl TimestampIndex {
     }
 }
 
-impl From<&schema::SchemaRef> for Schema {
-    fn from(schema: &schema::SchemaRef) -> Self {
+impl TryFrom<&schema::SchemaRef> for Schema {
+    type Error = Error;
+
+    fn try_from(schema: &schema::SchemaRef) -> Result<Self> {
         let column_schemas = schema
             .column_schemas()
             .iter()
-            .map(|column_schema| column_schema.into())
-            .collect();
+            .map(ColumnSchema::try_from)
+            .collect::<Result<_>>()?;
 
-        Schema {
+        Ok(Schema {
             column_schemas,
             timestamp_index: schema
                 .timestamp_index()
                 .map(|index| TimestampIndex::new(index as u64)),
-        }
+        })
     }
 }
 
@@ -100,14 +143,13 @@ impl TryFrom<Schema> for schema::SchemaRef {
     }
 }
 
-impl From<&schema::ColumnSchema> for ColumnSchema {
-    fn from(cs: &schema::ColumnSchema) -> Self {
-        Self {
-            name: cs.name.clone(),
-            data_type: DataType::from(&cs.data_type).into(),
-            is_nullable: cs.is_nullable(),
-            is_time_index: cs.is_time_index(),
-        }
+impl TryFrom<&schema::ColumnSchema> for ColumnSchema {
+    type Error = Error;
+
+    fn try_from(cs: &schema::ColumnSchema) -> Result<Self> {
+        let mut column_schema = new_column_schema(&cs.name, &cs.data_type, cs.is_nullable())?;
+        column_schema.is_time_index = cs.is_time_index();
+        Ok(column_schema)
     }
 }
 
@@ -115,25 +157,59 @@ impl TryFrom<&ColumnSchema> for schema::ColumnSchema {
     type Error = Error;
 
     fn try_from(column_schema: &ColumnSchema) -> Result<Self> {
-        if let Some(data_type) = DataType::from_i32(column_schema.data_type) {
-            Ok(schema::ColumnSchema::new(
-                column_schema.name.clone(),
-                data_type.into(),
-                column_schema.is_nullable,
-            )
-            .with_time_index(column_schema.is_time_index))
-        } else {
-            InvalidDataTypeSnafu {
-                data_type: column_schema.data_type,
-            }
-            .fail()
-        }
+        Ok(schema::ColumnSchema::new(
+            column_schema.name.clone(),
+            ConcreteDataType::try_from(column_schema)?,
+            column_schema.is_nullable,
+        )
+        .with_time_index(column_schema.is_time_index))
     }
 }
 
-impl From<&ConcreteDataType> for DataType {
-    fn from(data_type: &ConcreteDataType) -> Self {
-        match data_type {
+fn new_column_schema(
+    name: &str,
+    data_type: &ConcreteDataType,
+    is_nullable: bool,
+) -> Result<ColumnSchema> {
+    let data_type_extension = match data_type {
+        ConcreteDataType::Decimal128(t) => Some(TypeExt::DecimalType(DecimalTypeExtension {
+            precision: t.precision() as u32,
+            scale: t.scale() as u32,
+        })),
+        ConcreteDataType::Struct(t) => Some(TypeExt::StructType(StructTypeExtension {
+            fields: t
+                .fields()
+                .iter()
+                .map(|field| {
+                    new_column_schema(field.name(), field.data_type(), field.is_nullable())
+                })
+                .collect::<Result<_>>()?,
+        })),
+        ConcreteDataType::Map(t) => Some(TypeExt::MapType(MapTypeExtension {
+            entry_fields: vec![
+                new_column_schema(MAP_KEY_NAME, t.key_type(), false)?,
+                new_column_schema(MAP_VALUE_NAME, t.value_type(), true)?,
+            ],
+        })),
+        _ => None,
+    };
+
+    Ok(ColumnSchema {
+        name: name.to_string(),
+        data_type: DataType::try_from(data_type)? as i32,
+        is_nullable,
+        is_time_index: false,
+        data_type_extension: data_type_extension.map(|type_ext| DataTypeExtension {
+            type_ext: Some(type_ext),
+        }),
+    })
+}
+
+impl TryFrom<&ConcreteDataType> for DataType {
+    type Error = Error;
+
+    fn try_from(data_type: &ConcreteDataType) -> Result<Self> {
+        let data_type = match data_type {
             ConcreteDataType::Boolean(_) => DataType::Boolean,
             ConcreteDataType::Int8(_) => DataType::Int8,
             ConcreteDataType::Int16(_) => DataType::Int16,
@@ -143,25 +219,51 @@ impl From<&ConcreteDataType> for DataType {
             ConcreteDataType::UInt16(_) => DataType::Uint16,
             ConcreteDataType::UInt32(_) => DataType::Uint32,
             ConcreteDataType::UInt64(_) => DataType::Uint64,
-            ConcreteDataType::Float32(_) => DataType::Float64,
+            ConcreteDataType::Float32(_) => DataType::Float32,
             ConcreteDataType::Float64(_) => DataType::Float64,
             ConcreteDataType::String(_) => DataType::String,
             ConcreteDataType::Null(_) => DataType::Null,
             ConcreteDataType::Binary(_) => DataType::Binary,
             ConcreteDataType::Timestamp(_) => DataType::Timestamp,
-            ConcreteDataType::Date(_)
-            | ConcreteDataType::DateTime(_)
-            | ConcreteDataType::List(_) => {
-                // TODO(jiachun): Maybe support some composite types in the future , such as list, struct, etc.
-                unimplemented!("data type {:?} is not supported", data_type)
+            ConcreteDataType::Date(_) => DataType::Date,
+            ConcreteDataType::DateTime(_) => DataType::Datetime,
+            ConcreteDataType::Decimal128(_) => DataType::Decimal128,
+            ConcreteDataType::Json(_) => DataType::Json,
+            ConcreteDataType::Interval(_) => DataType::Interval,
+            ConcreteDataType::Struct(_) => DataType::Struct,
+            ConcreteDataType::Map(_) => DataType::Map,
+            ConcreteDataType::List(_) => {
+                return UnsupportedDataTypeSnafu {
+                    data_type: data_type.clone(),
+                }
+                .fail()
             }
-        }
+        };
+        Ok(data_type)
     }
 }
 
-impl From<DataType> for ConcreteDataType {
-    fn from(data_type: DataType) -> Self {
-        match data_type {
+impl TryFrom<&ColumnSchema> for ConcreteDataType {
+    type Error = Error;
+
+    fn try_from(column_schema: &ColumnSchema) -> Result<Self> {
+        let data_type =
+            DataType::from_i32(column_schema.data_type).context(InvalidDataTypeSnafu {
+                data_type: column_schema.data_type,
+            })?;
+        let type_ext = column_schema
+            .data_type_extension
+            .as_ref()
+            .and_then(|ext| ext.type_ext.as_ref());
+        let invalid_extension = || {
+            InvalidDataTypeExtensionSnafu {
+                data_type: column_schema.data_type,
+                data_type_extension: column_schema.data_type_extension.clone(),
+            }
+            .fail()
+        };
+
+        let data_type = match data_type {
             DataType::Boolean => ConcreteDataType::boolean_datatype(),
             DataType::Int8 => ConcreteDataType::int8_datatype(),
             DataType::Int16 => ConcreteDataType::int16_datatype(),
@@ -177,10 +279,69 @@ impl From<DataType> for ConcreteDataType {
             DataType::Binary => ConcreteDataType::binary_datatype(),
             DataType::Null => ConcreteDataType::null_datatype(),
             DataType::Timestamp => ConcreteDataType::timestamp_millis_datatype(),
+            DataType::Date => ConcreteDataType::date_datatype(),
+            DataType::Datetime => ConcreteDataType::datetime_datatype(),
+            DataType::Json => ConcreteDataType::json_datatype(),
+            DataType::Interval => ConcreteDataType::interval_datatype(),
+            DataType::Decimal128 => match type_ext {
+                Some(TypeExt::DecimalType(DecimalTypeExtension { precision, scale }))
+                    if *precision > 0
+                        && *precision <= DECIMAL128_MAX_PRECISION as u32
+                        && scale <= precision =>
+                {
+                    ConcreteDataType::decimal128_datatype(*precision as u8, *scale as u8)
+                }
+                _ => return invalid_extension(),
+            },
+            DataType::Struct => match type_ext {
+                Some(TypeExt::StructType(StructTypeExtension { fields })) => {
+                    ConcreteDataType::struct_datatype(
+                        fields
+                            .iter()
+                            .map(|field| {
+                                Ok(StructField::new(
+                                    field.name.clone(),
+                                    ConcreteDataType::try_from(field)?,
+                                    field.is_nullable,
+                                ))
+                            })
+                            .collect::<Result<_>>()?,
+                    )
+                }
+                _ => return invalid_extension(),
+            },
+            DataType::Map => match type_ext {
+                Some(TypeExt::MapType(MapTypeExtension { entry_fields })) => {
+                    match &entry_fields[..] {
+                        [key, value] => ConcreteDataType::map_datatype(
+                            ConcreteDataType::try_from(key)?,
+                            ConcreteDataType::try_from(value)?,
+                        ),
+                        _ => return invalid_extension(),
+                    }
+                }
+                _ => return invalid_extension(),
+            },
+        };
+        Ok(data_type)
+    }
+}
+
+impl From<i128> for Decimal128 {
+    fn from(value: i128) -> Self {
+        Decimal128 {
+            hi: (value >> 64) as i64,
+            lo: value as i64,
         }
     }
 }
 
+impl From<&Decimal128> for i128 {
+    fn from(value: &Decimal128) -> Self {
+        ((value.hi as i128) << 64) | (value.lo as u64 as i128)
+    }
+}
+
 #[macro_export]
 macro_rules! gen_columns {
     ($key: tt, $vec_ty: ty, $vari: ident, $cast: expr) => {
@@ -240,6 +401,100 @@ gen_columns!(bool, BooleanVector, v, v);
 gen_columns!(binary, BinaryVector, v, v.to_vec());
 gen_columns!(string, StringVector, v, v.to_string());
 gen_columns!(timestamp, TimestampVector, v, v.value());
+gen_columns!(date, DateVector, v, v.val());
+gen_columns!(datetime, DateTimeVector, v, v.val());
+gen_columns!(decimal128, DecimalVector, v, v.value().into());
+gen_columns!(json, JsonVector, v, v.to_string());
+gen_columns!(
+    interval,
+    IntervalVector,
+    v,
+    IntervalMonthDayNano {
+        months: v.months(),
+        days: v.days(),
+        nanoseconds: v.nanos(),
+    }
+);
+
+/// Encodes each field of the struct `vector` as a nested column.
+pub fn gen_columns_struct(vector: &VectorRef) -> Result<Column> {
+    let struct_vector = vector
+        .as_any()
+        .downcast_ref::<StructVector>()
+        .with_context(|| ConversionSnafu {
+            from: format!("{:?}", vector.data_type()),
+        })?;
+    let values = Values {
+        struct_fields: struct_vector
+            .fields()
+            .iter()
+            .map(gen_columns)
+            .collect::<Result<_>>()?,
+        ..Default::default()
+    };
+
+    Ok(Column {
+        values: Some(values),
+        value_null_mask: gen_null_mask(vector),
+        num_rows: vector.len() as u64,
+    })
+}
+
+/// Encodes the entries of the map `vector` as a key column and a value column.
+pub fn gen_columns_map(vector: &VectorRef) -> Result<Column> {
+    let map_vector = vector
+        .as_any()
+        .downcast_ref::<MapVector>()
+        .with_context(|| ConversionSnafu {
+            from: format!("{:?}", vector.data_type()),
+        })?;
+    let map_type = match vector.data_type() {
+        ConcreteDataType::Map(t) => t,
+        data_type => return UnsupportedDataTypeSnafu { data_type }.fail(),
+    };
+
+    let mut map_lengths = Vec::with_capacity(vector.len());
+    let mut keys = map_type.key_type().create_mutable_vector(vector.len());
+    let mut values = map_type.value_type().create_mutable_vector(vector.len());
+    for idx in 0..vector.len() {
+        if vector.is_null(idx) {
+            map_lengths.push(0);
+            continue;
+        }
+        let range = map_vector.entries_range(idx);
+        map_lengths.push(range.len() as u32);
+        keys.extend_slice_of(&**map_vector.keys(), range.start, range.len())
+            .context(CreateVectorSnafu)?;
+        values
+            .extend_slice_of(&**map_vector.values(), range.start, range.len())
+            .context(CreateVectorSnafu)?;
+    }
+    let values = Values {
+        map_lengths,
+        map_entries: vec![
+            gen_columns(&keys.to_vector())?,
+            gen_columns(&values.to_vector())?,
+        ],
+        ..Default::default()
+    };
+
+    Ok(Column {
+        values: Some(values),
+        value_null_mask: gen_null_mask(vector),
+        num_rows: vector.len() as u64,
+    })
+}
+
+fn gen_null_mask(vector: &VectorRef) -> Vec<u8> {
+    if vector.null_count() == 0 {
+        return Vec::default();
+    }
+    let mut bits = BitVec::repeat(false, vector.len());
+    (0..vector.len())
+        .filter(|idx| vector.is_null(*idx))
+        .for_each(|idx| bits.set(idx, true));
+    bits.into_vec()
+}
 
 #[macro_export]
 macro_rules! gen_put_data {
@@ -288,6 +543,156 @@ gen_put_data!(bool, BooleanVectorBuilder, v, *v);
 gen_put_data!(binary, BinaryVectorBuilder, v, v.as_slice());
 gen_put_data!(string, StringVectorBuilder, v, v.as_str());
 gen_put_data!(timestamp, TimestampVectorBuilder, v, (*v).into());
+gen_put_data!(date, DateVectorBuilder, v, Date::new(*v));
+gen_put_data!(datetime, DateTimeVectorBuilder, v, DateTime::new(*v));
+gen_put_data!(json, JsonVectorBuilder, v, v.as_str());
+gen_put_data!(
+    interval,
+    IntervalVectorBuilder,
+    v,
+    Interval::new(v.months, v.days, v.nanoseconds)
+);
+
+/// Decodes the values of parameterized and nested types by pushing them to the mutable
+/// vector of `data_type`.
+fn gen_put_data_values(
+    data_type: &ConcreteDataType,
+    num_rows: usize,
+    null_mask: &BitVec,
+    mut values: impl Iterator<Item = Value>,
+) -> Result<VectorRef> {
+    let mut builder = data_type.create_mutable_vector(num_rows);
+    for idx in 0..num_rows {
+        let value = if is_null(null_mask, idx) {
+            Value::Null
+        } else {
+            values.next().unwrap_or(Value::Null)
+        };
+        builder
+            .push_value_ref(value.as_value_ref())
+            .context(CreateVectorSnafu)?;
+    }
+    Ok(builder.to_vector())
+}
+
+#[inline]
+fn is_null(null_mask: &BitVec, idx: usize) -> bool {
+    null_mask.get(idx).map(|bit| *bit).unwrap_or(false)
+}
+
+pub fn gen_put_data_decimal128(t: &DecimalType, column: Column) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let null_mask = BitVec::from_vec(column.value_null_mask);
+    let decimals = values.decimal128_values.iter().map(|v| {
+        Value::Decimal128(datatypes::decimal::Decimal128::new(
+            v.into(),
+            t.precision(),
+            t.scale(),
+        ))
+    });
+    gen_put_data_values(
+        &ConcreteDataType::Decimal128(t.clone()),
+        column.num_rows as usize,
+        &null_mask,
+        decimals,
+    )
+}
+
+pub fn gen_put_data_struct(t: &StructType, column: Column) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let num_rows = column.num_rows as usize;
+    ensure!(
+        values.struct_fields.len() == t.fields().len(),
+        InvalidColumnValuesSnafu {
+            reason: format!(
+                "expect {} struct fields, found {}",
+                t.fields().len(),
+                values.struct_fields.len()
+            ),
+        }
+    );
+    let fields = values
+        .struct_fields
+        .into_iter()
+        .zip(t.fields())
+        .map(|(field_column, field)| {
+            gen_nested_vector(field.data_type().clone(), field_column, num_rows)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let null_mask = BitVec::from_vec(column.value_null_mask);
+    let structs = (0..num_rows)
+        .filter(|idx| !is_null(&null_mask, *idx))
+        .map(|idx| {
+            let items = fields.iter().map(|field| field.get(idx)).collect();
+            Value::Struct(StructValue::new(items, t.clone()))
+        });
+    gen_put_data_values(
+        &ConcreteDataType::Struct(t.clone()),
+        num_rows,
+        &null_mask,
+        structs,
+    )
+}
+
+pub fn gen_put_data_map(t: &MapType, column: Column) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let num_rows = column.num_rows as usize;
+    ensure!(
+        values.map_lengths.len() == num_rows && values.map_entries.len() == 2,
+        InvalidColumnValuesSnafu {
+            reason: format!(
+                "expect {} map lengths and 2 entry columns, found {} and {}",
+                num_rows,
+                values.map_lengths.len(),
+                values.map_entries.len()
+            ),
+        }
+    );
+    let num_entries = values.map_lengths.iter().map(|len| *len as usize).sum();
+    let mut entries = values.map_entries.into_iter();
+    let keys = gen_nested_vector(t.key_type().clone(), entries.next().unwrap(), num_entries)?;
+    let map_values =
+        gen_nested_vector(t.value_type().clone(), entries.next().unwrap(), num_entries)?;
+
+    let null_mask = BitVec::from_vec(column.value_null_mask);
+    let mut maps = Vec::with_capacity(num_rows);
+    let mut offset = 0;
+    for (idx, len) in values.map_lengths.iter().enumerate() {
+        let len = *len as usize;
+        if !is_null(&null_mask, idx) {
+            let entries = (offset..offset + len)
+                .map(|i| (keys.get(i), map_values.get(i)))
+                .collect();
+            maps.push(Value::Map(MapValue::new(entries, t.clone())));
+        }
+        offset += len;
+    }
+    gen_put_data_values(
+        &ConcreteDataType::Map(t.clone()),
+        num_rows,
+        &null_mask,
+        maps.into_iter(),
+    )
+}
+
+/// Decodes the nested `column`, which must have `num_rows` rows.
+fn gen_nested_vector(
+    data_type: ConcreteDataType,
+    column: Column,
+    num_rows: usize,
+) -> Result<VectorRef> {
+    ensure!(
+        column.num_rows as usize == num_rows,
+        InvalidColumnValuesSnafu {
+            reason: format!(
+                "expect {} rows in nested column, found {}",
+                num_rows, column.num_rows
+            ),
+        }
+    );
+    gen_put_data_vector(data_type, column)
+}
 
 pub fn gen_columns(vector: &VectorRef) -> Result<Column> {
     let data_type = vector.data_type();
@@ -304,14 +709,20 @@ pub fn gen_columns(vector: &VectorRef) -> Result<Column> {
         ConcreteDataType::Float32(_) => gen_columns_f32(vector),
         ConcreteDataType::Float64(_) => gen_columns_f64(vector),
         ConcreteDataType::Binary(_) => gen_columns_binary(vector),
-        ConcreteDataType::String(_) => gen_columns_string(vector),
+        ConcreteDataType::String(_) => match vector.as_any().downcast_ref::<DictionaryVector>() {
+            Some(dict) => gen_columns_string(&(Arc::new(dict.decode()) as VectorRef)),
+            None => gen_columns_string(vector),
+        },
         ConcreteDataType::Timestamp(_) => gen_columns_timestamp(vector),
-        ConcreteDataType::Null(_)
-        | ConcreteDataType::Date(_)
-        | ConcreteDataType::DateTime(_)
-        | ConcreteDataType::List(_) => {
-            // TODO(jiachun): Maybe support some composite types in the future, such as list, struct, etc.
-            unimplemented!("data type {:?} is not supported", data_type)
+        ConcreteDataType::Date(_) => gen_columns_date(vector),
+        ConcreteDataType::DateTime(_) => gen_columns_datetime(vector),
+        ConcreteDataType::Decimal128(_) => gen_columns_decimal128(vector),
+        ConcreteDataType::Json(_) => gen_columns_json(vector),
+        ConcreteDataType::Interval(_) => gen_columns_interval(vector),
+        ConcreteDataType::Struct(_) => gen_columns_struct(vector),
+        ConcreteDataType::Map(_) => gen_columns_map(vector),
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) => {
+            UnsupportedDataTypeSnafu { data_type }.fail()
         }
     }
 }
@@ -332,12 +743,132 @@ pub fn gen_put_data_vector(data_type: ConcreteDataType, column: Column) -> Resul
         ConcreteDataType::Binary(_) => gen_put_data_binary(column),
         ConcreteDataType::String(_) => gen_put_data_string(column),
         ConcreteDataType::Timestamp(_) => gen_put_data_timestamp(column),
-        ConcreteDataType::Null(_)
-        | ConcreteDataType::Date(_)
-        | ConcreteDataType::DateTime(_)
-        | ConcreteDataType::List(_) => {
-            // TODO(jiachun): Maybe support some composite types in the future, such as list, struct, etc.
-            unimplemented!("data type {:?} is not supported", data_type)
+        ConcreteDataType::Date(_) => gen_put_data_date(column),
+        ConcreteDataType::DateTime(_) => gen_put_data_datetime(column),
+        ConcreteDataType::Decimal128(t) => gen_put_data_decimal128(&t, column),
+        ConcreteDataType::Json(_) => gen_put_data_json(column),
+        ConcreteDataType::Interval(_) => gen_put_data_interval(column),
+        ConcreteDataType::Struct(t) => gen_put_data_struct(&t, column),
+        ConcreteDataType::Map(t) => gen_put_data_map(&t, column),
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) => {
+            UnsupportedDataTypeSnafu { data_type }.fail()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested_types() -> (StructType, MapType) {
+        let struct_type = StructType::new(vec![
+            StructField::new("a", ConcreteDataType::int32_datatype(), true),
+            StructField::new("b", ConcreteDataType::decimal128_datatype(10, 2), true),
+        ]);
+        let map_type = MapType::new(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::Struct(struct_type.clone()),
+        );
+        (struct_type, map_type)
+    }
+
+    fn new_vector(data_type: &ConcreteDataType, values: &[Value]) -> VectorRef {
+        let mut builder = data_type.create_mutable_vector(values.len());
+        for value in values {
+            builder.push_value_ref(value.as_value_ref()).unwrap();
+        }
+        builder.to_vector()
+    }
+
+    #[test]
+    fn test_column_schema_roundtrip() {
+        let (struct_type, map_type) = nested_types();
+        let column_schemas = vec![
+            schema::ColumnSchema::new("f", ConcreteDataType::float32_datatype(), true),
+            schema::ColumnSchema::new("d", ConcreteDataType::decimal128_datatype(38, 10), true),
+            schema::ColumnSchema::new("s", ConcreteDataType::Struct(struct_type), true),
+            schema::ColumnSchema::new("m", ConcreteDataType::Map(map_type), false),
+            schema::ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+                .with_time_index(true),
+        ];
+        for column_schema in column_schemas {
+            let encoded = ColumnSchema::try_from(&column_schema).unwrap();
+            let decoded = schema::ColumnSchema::try_from(&encoded).unwrap();
+            assert_eq!(column_schema, decoded);
+        }
+
+        let list_type = ConcreteDataType::list_datatype(ConcreteDataType::int32_datatype());
+        let column_schema = schema::ColumnSchema::new("l", list_type, true);
+        assert!(ColumnSchema::try_from(&column_schema).is_err());
+
+        let encoded = ColumnSchema {
+            name: "d".to_string(),
+            data_type: DataType::Decimal128 as i32,
+            ..Default::default()
+        };
+        assert!(schema::ColumnSchema::try_from(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_column_roundtrip() {
+        let (struct_type, map_type) = nested_types();
+        let decimal = |v| Value::Decimal128(datatypes::decimal::Decimal128::new(v, 10, 2));
+        let new_struct = |a, b| Value::Struct(StructValue::new(vec![a, b], struct_type.clone()));
+        let new_map = |entries| Value::Map(MapValue::new(entries, map_type.clone()));
+
+        let vectors: Vec<VectorRef> = vec![
+            Arc::new(DateVector::from(vec![Some(1), None, Some(-1)])),
+            Arc::new(DateTimeVector::from(vec![Some(1), None, Some(i64::MAX)])),
+            Arc::new(DecimalVector::from_values(
+                10,
+                2,
+                vec![Some(-99_999_999), None, Some(1)],
+            )),
+            Arc::new(JsonVector::from(vec![Some(r#"{"a":1}"#), None, Some("[]")])),
+            Arc::new(IntervalVector::from(vec![
+                Some(Interval::new(1, 2, 3)),
+                None,
+                Some(Interval::new(-1, 0, i64::MIN)),
+            ])),
+            Arc::new(Float32Vector::from(vec![Some(1.5), None, Some(f32::MAX)])),
+            new_vector(
+                &ConcreteDataType::Struct(struct_type.clone()),
+                &[
+                    new_struct(Value::Int32(1), decimal(100)),
+                    Value::Null,
+                    new_struct(Value::Null, decimal(-1)),
+                ],
+            ),
+            new_vector(
+                &ConcreteDataType::Map(map_type.clone()),
+                &[
+                    new_map(vec![
+                        ("a".into(), new_struct(Value::Int32(1), Value::Null)),
+                        ("b".into(), Value::Null),
+                    ]),
+                    Value::Null,
+                    new_map(vec![]),
+                    new_map(vec![("c".into(), new_struct(Value::Null, decimal(5)))]),
+                ],
+            ),
+        ];
+
+        for vector in vectors {
+            let column = gen_columns(&vector).unwrap();
+            let decoded = gen_put_data_vector(vector.data_type(), column).unwrap();
+            assert_eq!(vector, decoded);
+        }
+    }
+
+    #[test]
+    fn test_invalid_nested_column() {
+        let (struct_type, map_type) = nested_types();
+        let column = Column {
+            values: Some(Values::default()),
+            value_null_mask: Vec::new(),
+            num_rows: 1,
+        };
+        assert!(gen_put_data_struct(&struct_type, column.clone()).is_err());
+        assert!(gen_put_data_map(&map_type, column).is_err());
+    }
+}
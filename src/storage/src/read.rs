@@ -18,15 +18,21 @@ mod dedup;
 mod merge;
 
 use std::cmp::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use datatypes::arrow::bitmap::MutableBitmap;
 use datatypes::data_type::DataType;
 use datatypes::prelude::ConcreteDataType;
-use datatypes::vectors::{BooleanVector, MutableVector, VectorRef};
+use datatypes::vectors::{
+    BooleanVector, DictionaryVector, DictionaryVectorBuilder, MutableVector, StringVector,
+    VectorRef,
+};
 pub use dedup::DedupReader;
 pub use merge::{MergeReader, MergeReaderBuilder};
 use snafu::{ensure, ResultExt};
+use store_api::storage::ReadMetrics;
 
 use crate::error::{self, Result};
 
@@ -137,6 +143,30 @@ pub trait BatchOp {
     fn filter(&self, batch: &Batch, filter: &BooleanVector) -> Result<Batch>;
 }
 
+/// Returns a builder for column of `data_type`.
+///
+/// String row key columns (tags) usually have low cardinality, so they are
+/// dictionary encoded.
+pub(crate) fn new_column_builder(
+    data_type: &ConcreteDataType,
+    is_row_key: bool,
+    capacity: usize,
+) -> Box<dyn MutableVector> {
+    if is_row_key && matches!(data_type, ConcreteDataType::String(_)) {
+        Box::new(DictionaryVectorBuilder::with_capacity(capacity))
+    } else {
+        data_type.create_mutable_vector(capacity)
+    }
+}
+
+/// Dictionary encodes `vector` if it is a [StringVector], otherwise returns it as is.
+pub(crate) fn encode_row_key_column(vector: VectorRef) -> VectorRef {
+    match vector.as_any().downcast_ref::<StringVector>() {
+        Some(strings) => Arc::new(DictionaryVector::from(strings)),
+        None => vector,
+    }
+}
+
 /// Reusable [Batch] builder.
 pub struct BatchBuilder {
     builders: Vec<Box<dyn MutableVector>>,
@@ -145,15 +175,19 @@ pub struct BatchBuilder {
 impl BatchBuilder {
     /// Create a new `BatchBuilder` from data types with given `capacity`.
     ///
+    /// The first `row_key_end` columns are row key columns, string row key columns
+    /// are built as dictionary vectors.
+    ///
     /// # Panics
     /// Panics if `types` is empty.
-    pub fn with_capacity<'a, I>(types: I, capacity: usize) -> BatchBuilder
+    pub fn with_capacity<'a, I>(types: I, row_key_end: usize, capacity: usize) -> BatchBuilder
     where
         I: IntoIterator<Item = &'a ConcreteDataType>,
     {
         let builders: Vec<_> = types
             .into_iter()
-            .map(|t| t.create_mutable_vector(capacity))
+            .enumerate()
+            .map(|(i, t)| new_column_builder(t, i < row_key_end, capacity))
             .collect();
         assert!(!builders.is_empty());
 
@@ -287,12 +321,68 @@ impl BatchReader for ConcatReader {
     }
 }
 
+/// Collects the [ReadMetrics] of the readers of a scan, which may run concurrently.
+#[derive(Debug, Default)]
+pub struct ReadMetricsRecorder {
+    ssts_read: AtomicUsize,
+    row_groups: AtomicUsize,
+    row_groups_pruned: AtomicUsize,
+    memtable_rows: AtomicUsize,
+}
+
+pub type ReadMetricsRecorderRef = Arc<ReadMetricsRecorder>;
+
+impl ReadMetricsRecorder {
+    /// Records an SST file read, in which `pruned` of the `row_groups` are skipped.
+    pub fn record_sst(&self, row_groups: usize, pruned: usize) {
+        self.ssts_read.fetch_add(1, AtomicOrdering::Relaxed);
+        self.row_groups
+            .fetch_add(row_groups, AtomicOrdering::Relaxed);
+        self.row_groups_pruned
+            .fetch_add(pruned, AtomicOrdering::Relaxed);
+    }
+
+    pub fn record_memtable_rows(&self, rows: usize) {
+        self.memtable_rows.fetch_add(rows, AtomicOrdering::Relaxed);
+    }
+
+    pub fn metrics(&self) -> ReadMetrics {
+        ReadMetrics {
+            ssts_read: self.ssts_read.load(AtomicOrdering::Relaxed),
+            row_groups: self.row_groups.load(AtomicOrdering::Relaxed),
+            row_groups_pruned: self.row_groups_pruned.load(AtomicOrdering::Relaxed),
+            memtable_rows: self.memtable_rows.load(AtomicOrdering::Relaxed),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::read::BatchReader;
     use crate::test_util::read_util;
 
+    #[test]
+    fn test_batch_builder_dictionary_key() {
+        let types = [
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::string_datatype(),
+        ];
+        let mut builder = BatchBuilder::with_capacity(&types, 1, 4);
+        let column: VectorRef = Arc::new(StringVector::from(vec!["b", "a", "b"]));
+        let batch = Batch::new(vec![column.clone(), column]);
+        builder.extend_slice_of(&batch, 0, 2).unwrap();
+        builder.push_row_of(&batch, 2).unwrap();
+
+        let output = builder.build().unwrap();
+        assert!(output.column(0).as_any().is::<DictionaryVector>());
+        assert!(output.column(1).as_any().is::<StringVector>());
+        assert_eq!(batch, output);
+
+        let encoded = encode_row_key_column(batch.column(0).clone());
+        assert!(encoded.as_any().is::<DictionaryVector>());
+    }
+
     #[tokio::test]
     async fn test_concat_reader_empty() {
         let mut reader = ConcatReader::new(Vec::new());
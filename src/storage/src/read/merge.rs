@@ -55,10 +55,13 @@
 //!  and the [google doc](https://docs.google.com/document/d/1uP0ubjM6ulnKVCRrXtwT_dqrTWjF9tlFSRk0JN2e_O0/edit#).
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use datatypes::arrow::array::ArrayRef;
+use datatypes::vectors::{DictionaryVector, VectorRef};
 use store_api::storage::consts;
 
 use crate::error::Result;
@@ -102,6 +105,41 @@ impl fmt::Debug for Source {
     }
 }
 
+/// Dictionaries shared by the dictionary encoded columns of batches in a merge.
+///
+/// Batches from different sources have their own dictionaries. Re-encoding them with the
+/// shared dictionaries allows [BatchOp::compare_row] to compare the keys of these columns
+/// instead of the strings.
+#[derive(Default)]
+struct SharedDictionaries {
+    /// Index of column -> dictionary shared by that column.
+    dictionaries: HashMap<usize, ArrayRef>,
+}
+
+impl SharedDictionaries {
+    /// Re-encodes the dictionary encoded columns of `batch` with the shared dictionaries.
+    fn share(&mut self, batch: Batch) -> Batch {
+        let mut columns = None;
+        for (idx, column) in batch.columns().iter().enumerate() {
+            let vector = match column.as_any().downcast_ref::<DictionaryVector>() {
+                Some(vector) => vector,
+                None => continue,
+            };
+            let shared = self
+                .dictionaries
+                .entry(idx)
+                .or_insert_with(|| Arc::new(vector.dictionary().clone()));
+            let vector: VectorRef = Arc::new(vector.share_dictionary(shared));
+            columns.get_or_insert_with(|| batch.columns().to_vec())[idx] = vector;
+        }
+
+        match columns {
+            Some(columns) => Batch::new(columns),
+            None => batch,
+        }
+    }
+}
+
 /// Reference to a row in [BatchCursor].
 #[derive(Debug)]
 struct RowCursor<'a> {
@@ -236,8 +274,15 @@ impl fmt::Debug for Node {
 }
 
 impl Node {
-    async fn new(schema: ProjectedSchemaRef, mut source: Source) -> Result<Node> {
-        let cursor = source.next_non_empty_batch().await?.map(BatchCursor::new);
+    async fn new(
+        schema: ProjectedSchemaRef,
+        mut source: Source,
+        dictionaries: &mut SharedDictionaries,
+    ) -> Result<Node> {
+        let cursor = source
+            .next_non_empty_batch()
+            .await?
+            .map(|batch| BatchCursor::new(dictionaries.share(batch)));
         Ok(Node {
             schema,
             source,
@@ -301,7 +346,10 @@ impl Node {
     /// is empty.
     ///
     /// Returns true if a new batch has been fetched.
-    async fn maybe_fetch_next_batch(&mut self) -> Result<bool> {
+    async fn maybe_fetch_next_batch(
+        &mut self,
+        dictionaries: &mut SharedDictionaries,
+    ) -> Result<bool> {
         let need_fetch = !self.is_eof() && self.cursor_ref().is_empty();
         if !need_fetch {
             // Still has remaining rows, no need to fetch.
@@ -311,7 +359,7 @@ impl Node {
         // This ensure the cursor is either non empty or None (EOF).
         match self.source.next_non_empty_batch().await? {
             Some(batch) => {
-                self.cursor = Some(BatchCursor::new(batch));
+                self.cursor = Some(BatchCursor::new(dictionaries.share(batch)));
                 Ok(true)
             }
             None => {
@@ -405,6 +453,8 @@ pub struct MergeReader {
     batch_size: usize,
     /// Buffered batch.
     batch_builder: BatchBuilder,
+    /// Dictionaries shared by batches from all nodes.
+    dictionaries: SharedDictionaries,
 }
 
 #[async_trait]
@@ -450,9 +500,11 @@ impl MergeReaderBuilder {
 
     pub fn build(self) -> MergeReader {
         let num_sources = self.sources.len();
-        let column_schemas = self.schema.schema_to_read().schema().column_schemas();
+        let schema_to_read = self.schema.schema_to_read();
+        let column_schemas = schema_to_read.schema().column_schemas();
         let batch_builder = BatchBuilder::with_capacity(
             column_schemas.iter().map(|c| &c.data_type),
+            schema_to_read.row_key_end(),
             self.batch_size,
         );
 
@@ -464,6 +516,7 @@ impl MergeReaderBuilder {
             cold: BinaryHeap::with_capacity(num_sources),
             batch_size: self.batch_size,
             batch_builder,
+            dictionaries: SharedDictionaries::default(),
         }
     }
 }
@@ -481,7 +534,7 @@ impl MergeReader {
         }
 
         for source in self.sources.drain(..) {
-            let node = Node::new(self.schema.clone(), source).await?;
+            let node = Node::new(self.schema.clone(), source, &mut self.dictionaries).await?;
 
             if !node.is_eof() {
                 self.cold.push(node);
@@ -570,7 +623,7 @@ impl MergeReader {
     /// Fetch next batch from this node and reset its cursor, then push the node back to a
     /// proper heap.
     async fn reheap(&mut self, mut node: Node) -> Result<()> {
-        let fetched_new_batch = node.maybe_fetch_next_batch().await?;
+        let fetched_new_batch = node.maybe_fetch_next_batch(&mut self.dictionaries).await?;
 
         if node.is_eof() {
             // The merge window would be updated, need to refill the hot heap.
@@ -605,9 +658,13 @@ impl MergeReader {
 #[cfg(test)]
 mod tests {
     use datatypes::prelude::ScalarVector;
-    use datatypes::vectors::{Int64Vector, TimestampVector};
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::vectors::{Int64Vector, TimestampVector, UInt64Vector, UInt8Vector};
+    use store_api::storage::OpType;
 
     use super::*;
+    use crate::metadata::RegionMetadata;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
     use crate::test_util::read_util;
 
     #[tokio::test]
@@ -625,14 +682,19 @@ mod tests {
     async fn test_node() {
         let schema = read_util::new_projected_schema();
         let left_source = read_util::build_boxed_iter(&[&[(1, None), (3, None), (5, None)]]);
-        let mut left = Node::new(schema.clone(), Source::Iter(left_source))
+        let mut dictionaries = SharedDictionaries::default();
+        let mut left = Node::new(schema.clone(), Source::Iter(left_source), &mut dictionaries)
             .await
             .unwrap();
 
         let right_source = read_util::build_boxed_reader(&[&[(2, None), (3, None), (6, None)]]);
-        let mut right = Node::new(schema.clone(), Source::Reader(right_source))
-            .await
-            .unwrap();
+        let mut right = Node::new(
+            schema.clone(),
+            Source::Reader(right_source),
+            &mut dictionaries,
+        )
+        .await
+        .unwrap();
 
         // We use reverse order for a node.
         assert!(left > right);
@@ -653,6 +715,63 @@ mod tests {
         assert!(output.contains("pos: 1"));
     }
 
+    fn new_tag_batch(tags: &[&str], timestamps: &[i64]) -> Batch {
+        let tags = Arc::new(DictionaryVector::from(
+            tags.iter().map(|tag| Some(*tag)).collect::<Vec<_>>(),
+        ));
+        let timestamps = Arc::new(TimestampVector::from_values(timestamps.iter().copied()));
+        let sequences = Arc::new(UInt64Vector::from_slice(&vec![0; timestamps.len()]));
+        let op_types = Arc::new(UInt8Vector::from_slice(&vec![
+            OpType::Put.as_u8();
+            timestamps.len()
+        ]));
+
+        Batch::new(vec![tags, timestamps, sequences, op_types])
+    }
+
+    fn dictionary_column(batch: &Batch) -> &DictionaryVector {
+        batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<DictionaryVector>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_shared_dictionaries() {
+        // Schema (k0, timestamp).
+        let desc = RegionDescBuilder::new("merge-dictionary")
+            .enable_version_column(false)
+            .push_key_column(("k0", LogicalTypeId::String, false))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+        let schema = ProjectedSchema::new(metadata.schema().clone(), None).unwrap();
+
+        let mut dictionaries = SharedDictionaries::default();
+        let left = dictionaries.share(new_tag_batch(&["a", "c"], &[1, 1]));
+        // The right batch brings a new value, so the shared dictionary grows.
+        let right = dictionaries.share(new_tag_batch(&["b", "c"], &[1, 1]));
+        assert!(!dictionary_column(&left).shares_dictionary(dictionary_column(&right)));
+
+        let left = dictionaries.share(new_tag_batch(&["a", "c"], &[1, 2]));
+        let third = dictionaries.share(new_tag_batch(&["a", "b"], &[1, 1]));
+        assert!(dictionary_column(&left).shares_dictionary(dictionary_column(&right)));
+        assert!(dictionary_column(&left).shares_dictionary(dictionary_column(&third)));
+
+        // Comparing keys of the shared dictionary gives the same order as comparing strings.
+        assert_eq!(Ordering::Less, schema.compare_row(&left, 0, &right, 0));
+        assert_eq!(Ordering::Greater, schema.compare_row(&left, 1, &right, 0));
+        assert_eq!(Ordering::Greater, schema.compare_row(&left, 1, &right, 1));
+        assert_eq!(Ordering::Less, schema.compare_row(&right, 1, &left, 1));
+        assert_eq!(Ordering::Equal, schema.compare_row(&left, 0, &third, 0));
+        assert_eq!(Ordering::Equal, schema.compare_row(&right, 0, &third, 1));
+        assert_eq!(Ordering::Less, schema.compare_row(&third, 1, &right, 1));
+
+        // Batches without dictionary columns are left untouched.
+        let batch = read_util::new_full_kv_batch(&[(1, 1, 0, OpType::Put)]);
+        assert_eq!(batch, dictionaries.share(batch.clone()));
+    }
+
     type Batches<'a> = &'a [&'a [(i64, Option<i64>)]];
 
     fn build_merge_reader(sources: &[Batches], num_iter: usize, batch_size: usize) -> MergeReader {
@@ -24,8 +24,8 @@ use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AlterRequest, OpenOptions, ReadContext, Region, RegionId, RegionMeta, SequenceNumber,
-    WriteContext, WriteResponse,
+    AlterRequest, DataVersion, OpenOptions, ReadContext, Region, RegionId, RegionMeta,
+    SequenceNumber, WriteContext, WriteResponse,
 };
 
 use crate::error::{self, Error, Result};
@@ -97,6 +97,14 @@ impl<S: LogStore> Region for RegionImpl<S> {
     async fn alter(&self, request: AlterRequest) -> Result<()> {
         self.inner.alter(request).await
     }
+
+    fn data_version(&self) -> DataVersion {
+        let version_control = self.inner.version_control();
+        DataVersion {
+            manifest_version: version_control.current_manifest_version(),
+            committed_sequence: version_control.committed_sequence(),
+        }
+    }
 }
 
 /// Storage related config for region.
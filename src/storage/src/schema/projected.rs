@@ -19,7 +19,7 @@ use std::sync::Arc;
 use common_error::prelude::*;
 use datatypes::arrow::bitmap::MutableBitmap;
 use datatypes::schema::{SchemaBuilder, SchemaRef};
-use datatypes::vectors::BooleanVector;
+use datatypes::vectors::{BooleanVector, DictionaryVector};
 use store_api::storage::{Chunk, ColumnId};
 
 use crate::error;
@@ -280,9 +280,18 @@ impl BatchOp for ProjectedSchema {
         let indices = self.schema_to_read.row_key_indices();
         for idx in indices {
             let (left_col, right_col) = (left.column(idx), right.column(idx));
-            // Comparision of vector is done by virtual method calls currently. Consider using
-            // enum dispatch if this becomes bottleneck.
-            let order = left_col.get_ref(i).cmp(&right_col.get_ref(j));
+            let order = match (
+                left_col.as_any().downcast_ref::<DictionaryVector>(),
+                right_col.as_any().downcast_ref::<DictionaryVector>(),
+            ) {
+                // Sorted dictionaries shared by both sides, so comparing keys is enough.
+                (Some(left_dict), Some(right_dict)) if left_dict.shares_dictionary(right_dict) => {
+                    left_dict.key(i).cmp(&right_dict.key(j))
+                }
+                // Comparision of vector is done by virtual method calls currently. Consider
+                // using enum dispatch if this becomes bottleneck.
+                _ => left_col.get_ref(i).cmp(&right_col.get_ref(j)),
+            };
             if order != Ordering::Equal {
                 return order;
             }
@@ -23,7 +23,7 @@ use table::predicate::Predicate;
 
 use crate::error::Result;
 use crate::memtable::BoxedBatchIterator;
-use crate::read::BoxedBatchReader;
+use crate::read::{BoxedBatchReader, ReadMetricsRecorderRef};
 use crate::schema::ProjectedSchemaRef;
 use crate::sst::parquet::{ParquetReader, ParquetWriter};
 
@@ -193,6 +193,8 @@ pub struct ReadOptions {
     pub projected_schema: ProjectedSchemaRef,
 
     pub predicate: Predicate,
+    /// Records the SST files and row groups read.
+    pub metrics: ReadMetricsRecorderRef,
 }
 
 /// SST access layer.
@@ -257,6 +259,7 @@ impl AccessLayer for FsAccessLayer {
             self.object_store.clone(),
             opts.projected_schema.clone(),
             opts.predicate.clone(),
+            opts.metrics.clone(),
         );
 
         let stream = reader.chunk_stream(opts.batch_size).await?;
@@ -41,7 +41,7 @@ use table::predicate::Predicate;
 
 use crate::error::{self, Result};
 use crate::memtable::BoxedBatchIterator;
-use crate::read::{Batch, BatchReader};
+use crate::read::{Batch, BatchReader, ReadMetricsRecorderRef};
 use crate::schema::compat::ReadAdapter;
 use crate::schema::{ProjectedSchemaRef, StoreSchema};
 use crate::sst;
@@ -190,6 +190,7 @@ pub struct ParquetReader<'a> {
     object_store: ObjectStore,
     projected_schema: ProjectedSchemaRef,
     predicate: Predicate,
+    metrics: ReadMetricsRecorderRef,
 }
 
 type ReaderFactoryFuture<'a, R> =
@@ -201,12 +202,14 @@ impl<'a> ParquetReader<'a> {
         object_store: ObjectStore,
         projected_schema: ProjectedSchemaRef,
         predicate: Predicate,
+        metrics: ReadMetricsRecorderRef,
     ) -> ParquetReader {
         ParquetReader {
             file_path,
             object_store,
             projected_schema,
             predicate,
+            metrics,
         }
     }
 
@@ -241,6 +244,10 @@ impl<'a> ParquetReader<'a> {
         let pruned_row_groups = self
             .predicate
             .prune_row_groups(store_schema.schema().clone(), &metadata.row_groups);
+        self.metrics.record_sst(
+            pruned_row_groups.len(),
+            pruned_row_groups.iter().filter(|valid| !**valid).count(),
+        );
 
         let projected_fields = adapter.fields_to_read();
         let chunk_stream = try_stream!({
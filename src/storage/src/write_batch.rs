@@ -31,7 +31,7 @@ use prost::{DecodeError, EncodeError};
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{consts, PutOperation, WriteRequest};
 
-use crate::proto;
+use crate::{proto, read};
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -373,7 +373,8 @@ impl PutOperation for PutData {
     type Error = Error;
 
     fn add_key_column(&mut self, name: &str, vector: VectorRef) -> Result<()> {
-        self.add_column_by_name(name, vector)
+        // Tags repeat a lot, so we keep string key columns dictionary encoded.
+        self.add_column_by_name(name, read::encode_row_key_column(vector))
     }
 
     fn add_version_column(&mut self, vector: VectorRef) -> Result<()> {
@@ -666,7 +667,7 @@ pub mod codec {
         type Error = WriteBatchError;
 
         fn encode(&self, item: &WriteBatch, dst: &mut Vec<u8>) -> Result<()> {
-            let schema = item.schema().into();
+            let schema = write_batch::Schema::try_from(item.schema()).context(ToProtobufSnafu)?;
 
             let mutations = item
                 .iter()
@@ -787,7 +788,8 @@ mod tests {
 
     use datatypes::type_id::LogicalTypeId;
     use datatypes::vectors::{
-        BooleanVector, ConstantVector, Int32Vector, Int64Vector, UInt64Vector,
+        BooleanVector, ConstantVector, DictionaryVector, Int32Vector, Int64Vector, StringVector,
+        UInt64Vector,
     };
 
     use super::*;
@@ -830,6 +832,24 @@ mod tests {
         assert!(put_data.is_empty());
     }
 
+    #[test]
+    fn test_put_data_string_key_encoded() {
+        let mut put_data = PutData::new();
+
+        let keys = Arc::new(StringVector::from(vec!["host1", "host2", "host1"]));
+        put_data.add_key_column("k1", keys.clone()).unwrap();
+        put_data.add_value_column("v1", keys).unwrap();
+
+        let k1 = put_data.column_by_name("k1").unwrap();
+        let dict = k1.as_any().downcast_ref::<DictionaryVector>().unwrap();
+        assert_eq!(2, dict.dictionary().len());
+        assert_eq!(Some("host1"), dict.get_data(2));
+        // Value columns are kept as is.
+        let v1 = put_data.column_by_name("v1").unwrap();
+        assert!(v1.as_any().is::<StringVector>());
+        assert_eq!(**k1, **v1);
+    }
+
     fn new_test_batch() -> WriteBatch {
         write_batch_util::new_write_batch(
             &[
@@ -30,11 +30,11 @@ pub use datatypes::schema::{
     ColumnDefaultConstraint, ColumnSchema, Schema, SchemaBuilder, SchemaRef,
 };
 
-pub use self::chunk::{Chunk, ChunkReader};
+pub use self::chunk::{Chunk, ChunkReader, ReadMetrics};
 pub use self::descriptors::*;
 pub use self::engine::{CreateOptions, EngineContext, OpenOptions, StorageEngine};
 pub use self::metadata::RegionMeta;
-pub use self::region::{Region, WriteContext};
+pub use self::region::{DataVersion, Region, WriteContext};
 pub use self::requests::{
     AddColumn, AlterOperation, AlterRequest, GetRequest, PutOperation, ScanRequest, WriteRequest,
 };
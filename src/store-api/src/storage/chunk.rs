@@ -30,6 +30,19 @@ impl Chunk {
     }
 }
 
+/// Metrics of reading the chunks of a region.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReadMetrics {
+    /// Number of SST files read.
+    pub ssts_read: usize,
+    /// Number of row groups in the SST files read.
+    pub row_groups: usize,
+    /// Number of row groups skipped by the filters.
+    pub row_groups_pruned: usize,
+    /// Number of rows read from the memtables.
+    pub memtable_rows: usize,
+}
+
 /// `ChunkReader` is similar to async iterator of [Chunk].
 #[async_trait]
 pub trait ChunkReader: Send {
@@ -40,4 +53,9 @@ pub trait ChunkReader: Send {
 
     /// Fetch next chunk from the reader.
     async fn next_chunk(&mut self) -> Result<Option<Chunk>, Self::Error>;
+
+    /// Returns the metrics of the chunks read so far.
+    fn metrics(&self) -> ReadMetrics {
+        ReadMetrics::default()
+    }
 }
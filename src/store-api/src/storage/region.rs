@@ -35,12 +35,13 @@
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 
+use crate::manifest::ManifestVersion;
 use crate::storage::engine::OpenOptions;
 use crate::storage::metadata::RegionMeta;
 use crate::storage::requests::{AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
 use crate::storage::snapshot::{ReadContext, Snapshot};
-use crate::storage::RegionId;
+use crate::storage::{RegionId, SequenceNumber};
 
 /// Chunks of rows in storage engine.
 #[async_trait]
@@ -72,6 +73,16 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
     fn write_request(&self) -> Self::WriteRequest;
 
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
+
+    /// Returns the version of the data in the region.
+    fn data_version(&self) -> DataVersion;
+}
+
+/// Version of the data in a region, it changes after every write, flush or alter of the region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DataVersion {
+    pub manifest_version: ManifestVersion,
+    pub committed_sequence: SequenceNumber,
 }
 
 /// Context for write operations.
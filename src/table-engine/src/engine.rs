@@ -335,6 +335,7 @@ impl<S: StorageEngine> MitoEngineInner<S> {
             .next_column_id(next_column_id)
             .primary_key_indices(request.primary_key_indices.clone())
             .region_numbers(vec![region_number])
+            .options(request.table_options)
             .build()
             .context(error::BuildTableMetaSnafu { table_name })?;
 
@@ -22,7 +22,7 @@ use std::sync::Arc;
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use common_query::logical_plan::Expr;
-use common_query::physical_plan::PhysicalPlanRef;
+use common_query::physical_plan::{ExecutionPlanMetricsSet, MetricBuilder, PhysicalPlanRef};
 use common_recordbatch::error::{Error as RecordBatchError, Result as RecordBatchResult};
 use common_recordbatch::{RecordBatch, RecordBatchStream};
 use common_telemetry::logging;
@@ -34,8 +34,8 @@ use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AddColumn, AlterOperation, AlterRequest, ChunkReader, PutOperation, ReadContext, Region,
-    RegionMeta, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
+    AddColumn, AlterOperation, AlterRequest, ChunkReader, DataVersion, PutOperation, ReadContext,
+    ReadMetrics, Region, RegionMeta, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error::{Error as TableError, MissingColumnSnafu, Result as TableResult};
 use table::metadata::{
@@ -187,15 +187,18 @@ impl<R: Region> Table for MitoTable<R> {
 
         let schema = reader.schema().clone();
         let stream_schema = schema.clone();
+        let metrics = ExecutionPlanMetricsSet::new();
+        let stream_metrics = metrics.clone();
 
         let stream = Box::pin(async_stream::try_stream! {
             while let Some(chunk) = reader.next_chunk().await.map_err(RecordBatchError::new)? {
                 yield RecordBatch::new(stream_schema.clone(), chunk.columns)?
             }
+            record_read_metrics(&stream_metrics, reader.metrics());
         });
 
         let stream = Box::pin(ChunkStream { schema, stream });
-        Ok(Arc::new(SimpleTableScan::new(stream)))
+        Ok(Arc::new(SimpleTableScan::new(stream).with_metrics(metrics)))
     }
 
     /// Alter table changes the schemas of the table.
@@ -260,6 +263,10 @@ impl<R: Region> Table for MitoTable<R> {
     fn supports_filter_pushdown(&self, _filter: &Expr) -> table::error::Result<FilterPushDownType> {
         Ok(FilterPushDownType::Inexact)
     }
+
+    async fn data_versions(&self) -> Option<Vec<DataVersion>> {
+        Some(vec![self.region.data_version()])
+    }
 }
 
 struct ChunkStream {
@@ -281,6 +288,19 @@ impl Stream for ChunkStream {
     }
 }
 
+/// Reports the metrics of reading the region in the metrics of the scan.
+fn record_read_metrics(metrics: &ExecutionPlanMetricsSet, read_metrics: ReadMetrics) {
+    let values = [
+        ("ssts_read", read_metrics.ssts_read),
+        ("row_groups", read_metrics.row_groups),
+        ("row_groups_pruned", read_metrics.row_groups_pruned),
+        ("memtable_rows", read_metrics.memtable_rows),
+    ];
+    for (name, value) in values {
+        MetricBuilder::new(metrics).global_counter(name).add(value);
+    }
+}
+
 #[inline]
 fn column_qualified_name(table_name: &str, region_name: &str, column_name: &str) -> String {
     format!("{}.{}.{}", table_name, region_name, column_name)
@@ -15,6 +15,7 @@
 //! A mock storage engine for table test purpose.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 use arc_swap::ArcSwap;
@@ -26,9 +27,9 @@ use datatypes::schema::{ColumnSchema, Schema};
 use storage::metadata::{RegionMetaImpl, RegionMetadata};
 use storage::write_batch::{Mutation, WriteBatch};
 use store_api::storage::{
-    AlterRequest, Chunk, ChunkReader, CreateOptions, EngineContext, GetRequest, GetResponse,
-    OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, RegionMeta, ScanRequest,
-    ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
+    AlterRequest, Chunk, ChunkReader, CreateOptions, DataVersion, EngineContext, GetRequest,
+    GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, RegionMeta,
+    ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -137,6 +138,8 @@ pub struct MockRegionInner {
     name: String,
     pub metadata: ArcSwap<RegionMetadata>,
     memtable: Arc<RwLock<MockMemtable>>,
+    /// Number of writes and alters, as the committed sequence of the data version.
+    sequence: AtomicU64,
 }
 
 /// A columnar memtable, maps column name to data of that column in each row.
@@ -163,6 +166,7 @@ impl Region for MockRegion {
 
     async fn write(&self, _ctx: &WriteContext, request: WriteBatch) -> Result<WriteResponse> {
         self.inner.write(request);
+        self.inner.sequence.fetch_add(1, Ordering::Relaxed);
         Ok(WriteResponse {})
     }
 
@@ -182,9 +186,17 @@ impl Region for MockRegion {
         // Mock engine just panic if failed to create a new metadata.
         let metadata = current.alter(&request).unwrap();
         self.inner.update_metadata(metadata);
+        self.inner.sequence.fetch_add(1, Ordering::Relaxed);
 
         Ok(())
     }
+
+    fn data_version(&self) -> DataVersion {
+        DataVersion {
+            manifest_version: 0,
+            committed_sequence: self.inner.sequence.load(Ordering::Relaxed),
+        }
+    }
 }
 
 impl MockRegionInner {
@@ -197,6 +209,7 @@ impl MockRegionInner {
             name: metadata.name().to_string(),
             metadata: ArcSwap::new(Arc::new(metadata)),
             memtable: Arc::new(RwLock::new(memtable)),
+            sequence: AtomicU64::new(0),
         }
     }
 
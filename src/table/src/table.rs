@@ -23,6 +23,7 @@ use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
 use datatypes::schema::SchemaRef;
+use store_api::storage::DataVersion;
 
 use crate::error::Result;
 use crate::metadata::{FilterPushDownType, TableId, TableInfoRef, TableType};
@@ -72,6 +73,13 @@ pub trait Table: Send + Sync {
     async fn alter(&self, _request: AlterTableRequest) -> Result<()> {
         unimplemented!()
     }
+
+    /// Returns the versions of the data in the regions of the table, which change after every
+    /// write, flush or alter. Returns `None` if the table doesn't track the versions of its data,
+    /// then results of queries over the table can not be cached.
+    async fn data_versions(&self) -> Option<Vec<DataVersion>> {
+        None
+    }
 }
 
 pub type TableRef = Arc<dyn Table>;
@@ -19,7 +19,9 @@ use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use common_query::error as query_error;
 use common_query::error::Result as QueryResult;
-use common_query::physical_plan::{Partitioning, PhysicalPlan, PhysicalPlanRef, RuntimeEnv};
+use common_query::physical_plan::{
+    ExecutionPlanMetricsSet, MetricsSet, Partitioning, PhysicalPlan, PhysicalPlanRef, RuntimeEnv,
+};
 use common_recordbatch::SendableRecordBatchStream;
 use datatypes::schema::SchemaRef;
 use snafu::OptionExt;
@@ -27,6 +29,7 @@ use snafu::OptionExt;
 pub struct SimpleTableScan {
     stream: Mutex<Option<SendableRecordBatchStream>>,
     schema: SchemaRef,
+    metrics: ExecutionPlanMetricsSet,
 }
 
 impl Debug for SimpleTableScan {
@@ -44,8 +47,15 @@ impl SimpleTableScan {
         Self {
             stream: Mutex::new(Some(stream)),
             schema,
+            metrics: ExecutionPlanMetricsSet::new(),
         }
     }
+
+    /// Reports the metrics recorded by the stream, like the metrics of the storage.
+    pub fn with_metrics(mut self, metrics: ExecutionPlanMetricsSet) -> Self {
+        self.metrics = metrics;
+        self
+    }
 }
 
 #[async_trait]
@@ -78,6 +88,10 @@ impl PhysicalPlan for SimpleTableScan {
         let mut stream = self.stream.lock().unwrap();
         Ok(stream.take().context(query_error::ExecuteRepeatedlySnafu)?)
     }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
 }
 
 #[cfg(test)]